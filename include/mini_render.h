@@ -27,6 +27,12 @@ typedef enum {
     MR_STYLE_FILL_AND_STROKE = 2
 } MRPaintStyle;
 
+// 填充规则
+typedef enum {
+    MR_FILL_NONZERO = 0,
+    MR_FILL_EVENODD = 1
+} MRFillRule;
+
 // ============ Canvas API ============
 
 // 创建画布
@@ -109,6 +115,9 @@ void mr_path_add_round_rect(Path* path, float x, float y, float w, float h, floa
 // 添加椭圆
 void mr_path_add_oval(Path* path, float cx, float cy, float rx, float ry);
 
+// 设置填充规则（MRFillRule）
+void mr_path_set_fill_rule(Path* path, uint8_t rule);
+
 #ifdef __cplusplus
 }
 #endif
@@ -158,6 +158,34 @@ Page({
     });
   },
 
+  // 触底加载更多商品（分页）
+  onReachBottom: function() {
+    var catId = this.data.currentCategory;
+    var allProducts = this.data.allProducts;
+    var list = allProducts[catId] || [];
+    if (list.length === 0) return;
+
+    var base = list[list.length - 1];
+    var more = [];
+    for (var i = 1; i <= 5; i++) {
+      more.push({
+        id: base.id + i,
+        name: this.data.currentCategoryName + ' ' + (list.length + i),
+        desc: base.desc,
+        price: base.price,
+        icon: base.icon,
+        color: base.color,
+        quantity: 0
+      });
+    }
+    allProducts[catId] = list.concat(more);
+    console.log('📦 加载更多商品，当前总数:', allProducts[catId].length);
+    this.setData({
+      allProducts: allProducts,
+      products: allProducts[catId]
+    });
+  },
+
   // 增加商品数量
   onPlus: function(e) {
     var id = e.currentTarget.dataset.id;
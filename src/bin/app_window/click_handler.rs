@@ -12,7 +12,7 @@ use super::{
     LOGICAL_WIDTH, LOGICAL_HEIGHT,
     NavigationRequest, TabBarConfig,
     handle_native_tabbar_click,
-    ui_overlay::ModalState,
+    ui_overlay::{ModalState, ACTION_SHEET_ITEM_HEIGHT, ACTION_SHEET_CANCEL_GAP},
 };
 use super::events::mouse;
 use super::interaction_handler::{handle_interaction_result, check_navigation, print_js_output};
@@ -89,6 +89,28 @@ pub fn detect_modal_button(x: f32, y: f32, layout: &ModalLayout, show_cancel: bo
     None
 }
 
+/// 检测 ActionSheet 点击（逻辑坐标）
+/// 返回选项下标，item_count 表示取消按钮；点在遮罩上返回 None
+pub fn detect_action_sheet_item(x: f32, y: f32, item_count: usize) -> Option<usize> {
+    if x < 0.0 || x > LOGICAL_WIDTH as f32 {
+        return None;
+    }
+    let sheet_height = ACTION_SHEET_ITEM_HEIGHT * (item_count + 1) as f32 + ACTION_SHEET_CANCEL_GAP;
+    let sheet_y = LOGICAL_HEIGHT as f32 - sheet_height;
+    if y < sheet_y {
+        return None;
+    }
+    let items_bottom = sheet_y + ACTION_SHEET_ITEM_HEIGHT * item_count as f32;
+    if y < items_bottom {
+        return Some(((y - sheet_y) / ACTION_SHEET_ITEM_HEIGHT) as usize);
+    }
+    if y >= items_bottom + ACTION_SHEET_CANCEL_GAP {
+        return Some(item_count);
+    }
+    // 间隔区域
+    None
+}
+
 /// 处理内容区域点击
 pub fn handle_content_click(
     x: f32, y: f32,
@@ -104,7 +126,7 @@ pub fn handle_content_click(
 ) -> Option<NavigationRequest> {
     let scroll_pos = scroll.get_position();
     
-    if let Some(result) = mouse::handle_content_click(
+    for result in mouse::handle_content_click(
         x, y, scroll_pos, has_tabbar,
         interaction,
         renderer,
@@ -128,6 +150,27 @@ pub fn handle_content_click(
     nav
 }
 
+/// 处理内容区域长按：可选中文本全选并复制到剪贴板，返回是否复制了文本
+pub fn handle_content_long_press(
+    x: f32, y: f32,
+    scroll: &ScrollController,
+    interaction: &mut InteractionManager,
+    renderer: Option<&WxmlRenderer>,
+    app: &mut MiniApp,
+    scale_factor: f64,
+    window: Option<&Arc<Window>>,
+    clipboard: &mut Option<arboard::Clipboard>,
+) -> bool {
+    let scroll_pos = scroll.get_position();
+    match mouse::handle_content_long_press(x, y, scroll_pos, interaction) {
+        Some(result) => {
+            handle_interaction_result(&result, window, renderer, app, clipboard, scroll_pos, scale_factor);
+            true
+        }
+        None => false,
+    }
+}
+
 /// 处理自定义 TabBar 点击
 pub fn handle_custom_tabbar_click(
     x: f32, y: f32,
@@ -139,7 +182,7 @@ pub fn handle_custom_tabbar_click(
             if let (Some(index_str), Some(path)) = (binding.data.get("index"), binding.data.get("path")) {
                 if let Ok(_index) = index_str.parse::<usize>() {
                     if path != current_path {
-                        println!("👆 TabBar -> {}", path);
+                        log::debug!("👆 TabBar -> {}", path);
                         return Some(NavigationRequest::SwitchTab { url: path.clone() });
                     }
                 }
@@ -24,11 +24,26 @@ pub struct WindowConfig {
     pub navigation_bar_text_style: String,
     #[serde(default = "default_bg")]
     pub background_color: String,
+    #[serde(default = "default_reach_bottom_distance")]
+    pub on_reach_bottom_distance: f32,
+    #[serde(default)]
+    pub enable_pull_down_refresh: bool,
+}
+
+/// 页面 json 配置，未设置的项沿用 app.json 的 window 配置
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PageConfig {
+    #[serde(default)]
+    pub on_reach_bottom_distance: Option<f32>,
+    #[serde(default)]
+    pub enable_pull_down_refresh: Option<bool>,
 }
 
 fn default_nav_title() -> String { "Mini App".to_string() }
 fn default_nav_bg() -> String { "#000000".to_string() }
 fn default_bg() -> String { "#FFFFFF".to_string() }
+fn default_reach_bottom_distance() -> f32 { 50.0 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -6,31 +6,33 @@ use winit::event::{ElementState, MouseScrollDelta};
 use winit::window::Window;
 
 use mini_render::runtime::UiEvent;
-use mini_render::ui::interaction::InteractionType;
+use mini_render::ui::interaction::{InteractionResult, InteractionType};
 
-use super::{NavigationRequest, ui_overlay::{ToastState, LoadingState, ModalState}};
+use super::{NavigationRequest, parse_color, ui_overlay::{ToastState, LoadingState, ModalState, ActionSheetState}};
 use super::events::{keyboard, ime};
 use super::interaction_handler::{handle_interaction_result, print_js_output};
 
-/// 处理 UI 事件（Toast/Loading/Modal）
+/// 处理 UI 事件（Toast/Loading/Modal/ActionSheet/下拉刷新）
 pub fn process_ui_events(
     app: &mut mini_render::runtime::MiniApp,
     toast: &mut Option<ToastState>,
     loading: &mut Option<LoadingState>,
     modal: &mut Option<ModalState>,
+    action_sheet: &mut Option<ActionSheetState>,
+    pull_refresh: &mut Option<Instant>,
 ) -> bool {
     let events = app.drain_ui_events();
     let mut needs_redraw = false;
     
     for event in events {
         match event {
-            UiEvent::ShowToast { title, icon, duration } => {
+            UiEvent::ShowToast { title, icon, .. } => {
+                // 由 JS 的 hideToast 定时器关闭，duration 不在这里计时
                 *toast = Some(ToastState {
                     title,
                     icon,
                     visible: true,
                     start_time: Instant::now(),
-                    duration_ms: duration,
                 });
                 needs_redraw = true;
             }
@@ -44,6 +46,7 @@ pub fn process_ui_events(
                 *loading = Some(LoadingState {
                     title,
                     visible: true,
+                    start_time: Instant::now(),
                 });
                 needs_redraw = true;
             }
@@ -71,23 +74,36 @@ pub fn process_ui_events(
                 }
                 needs_redraw = true;
             }
-        }
-    }
-    needs_redraw
-}
-
-/// 更新 Toast 超时
-pub fn update_toast_timeout(toast: &mut Option<ToastState>) -> bool {
-    if let Some(t) = toast {
-        if t.visible {
-            let elapsed = t.start_time.elapsed().as_millis() as u32;
-            if elapsed >= t.duration_ms {
-                *toast = None;
-                return true;
+            UiEvent::ShowActionSheet { item_list, item_color } => {
+                *action_sheet = Some(ActionSheetState {
+                    items: item_list,
+                    item_color: parse_color(&item_color).unwrap_or(mini_render::Color::BLACK),
+                    visible: true,
+                    pressed: None,
+                });
+                needs_redraw = true;
+            }
+            UiEvent::AuthRequest { scope } => {
+                // 模拟器中直接授权
+                log::debug!("🔐 Authorize: {} -> granted", scope);
+                app.resolve_auth(&scope, true).ok();
+            }
+            UiEvent::RequestPayment { id, order } => {
+                // 模拟器中直接支付成功
+                log::debug!("💰 RequestPayment #{}: {} -> ok", id, order);
+                app.complete_bridge_call(id, Ok(serde_json::json!({})));
+            }
+            UiEvent::StartPullDownRefresh => {
+                *pull_refresh = Some(Instant::now());
+                needs_redraw = true;
+            }
+            UiEvent::StopPullDownRefresh => {
+                *pull_refresh = None;
+                needs_redraw = true;
             }
         }
     }
-    false
+    needs_redraw
 }
 
 /// 处理滚动事件
@@ -99,15 +115,14 @@ pub fn handle_scroll_event(
     
     match event {
         ScrollEvent::ReachBottom => {
-            println!("📜 onReachBottom triggered");
-            let call_code = "if(__currentPage && __currentPage.onReachBottom) __currentPage.onReachBottom()";
-            app.eval(call_code).ok();
+            log::debug!("📜 onReachBottom triggered");
+            app.dispatch_page_event("onReachBottom", &serde_json::json!({}), &serde_json::json!({})).ok();
             print_js_output(app);
         }
-        ScrollEvent::ReachTop => {
-            println!("📜 onPullDownRefresh triggered");
-            let call_code = "if(__currentPage && __currentPage.onPullDownRefresh) __currentPage.onPullDownRefresh()";
-            app.eval(call_code).ok();
+        ScrollEvent::ReachTop => {}
+        ScrollEvent::PullDownRefresh => {
+            log::debug!("📜 onPullDownRefresh triggered");
+            app.dispatch_page_event("onPullDownRefresh", &serde_json::json!({}), &serde_json::json!({})).ok();
             print_js_output(app);
         }
     }
@@ -147,7 +162,7 @@ pub fn handle_keyboard_event(
         );
         
         if let Some(result) = result {
-            handle_interaction_result(
+            let replaced = handle_interaction_result(
                 &result,
                 window,
                 renderer,
@@ -156,6 +171,7 @@ pub fn handle_keyboard_event(
                 scroll.get_position(),
                 scale_factor,
             );
+            apply_replaced_value(&result, replaced, interaction);
         }
         
         // 处理文本输入
@@ -163,7 +179,7 @@ pub fn handle_keyboard_event(
             if let Some(ref text) = event.text {
                 let results = keyboard::handle_text_input(text, ctrl, interaction);
                 for result in results {
-                    handle_interaction_result(
+                    let replaced = handle_interaction_result(
                         &result,
                         window,
                         renderer,
@@ -172,6 +188,7 @@ pub fn handle_keyboard_event(
                         scroll.get_position(),
                         scale_factor,
                     );
+                    apply_replaced_value(&result, replaced, interaction);
                 }
             }
         }
@@ -183,11 +200,11 @@ pub fn handle_keyboard_event(
     }
     
     // 默认键盘处理
-    if let Some(action) = keyboard::handle_default_keyboard(event.physical_key, interaction) {
+    if let Some(action) = keyboard::handle_default_keyboard(event.physical_key, ctrl, interaction) {
         match action {
             keyboard::DefaultKeyAction::Exit => exit_requested = true,
             keyboard::DefaultKeyAction::NavigateBack => {
-                pending_nav = Some(NavigationRequest::NavigateBack);
+                pending_nav = Some(NavigationRequest::NavigateBack { delta: 1 });
             }
             keyboard::DefaultKeyAction::BlurInput => {
                 if let Some(result) = interaction.blur_input() {
@@ -203,6 +220,19 @@ pub fn handle_keyboard_event(
                 }
                 needs_redraw = true;
             }
+            keyboard::DefaultKeyAction::CopySelection => {
+                if let Some(result) = interaction.copy_text_selection() {
+                    handle_interaction_result(
+                        &result,
+                        window,
+                        renderer,
+                        app,
+                        clipboard,
+                        scroll.get_position(),
+                        scale_factor,
+                    );
+                }
+            }
             keyboard::DefaultKeyAction::ScrollUp => scroll.handle_scroll(8.0, false),
             keyboard::DefaultKeyAction::ScrollDown => scroll.handle_scroll(-8.0, false),
             keyboard::DefaultKeyAction::PageUp => scroll.handle_scroll(30.0, false),
@@ -224,11 +254,11 @@ pub fn handle_ime_event(
     scroll_pos: f32,
     scale_factor: f64,
 ) -> bool {
-    let results = ime::handle_ime_event(ime_event, interaction);
-    let has_results = !results.is_empty();
+    let (results, preedit_changed) = ime::handle_ime_event(ime_event, interaction);
+    let has_results = !results.is_empty() || preedit_changed;
     
     for result in results {
-        handle_interaction_result(
+        let replaced = handle_interaction_result(
             &result,
             window,
             renderer,
@@ -237,11 +267,25 @@ pub fn handle_ime_event(
             scroll_pos,
             scale_factor,
         );
+        apply_replaced_value(&result, replaced, interaction);
     }
     
     has_results
 }
 
+/// bindinput 返回了新值时写回输入框
+fn apply_replaced_value(
+    result: &InteractionResult,
+    replaced: Option<String>,
+    interaction: &mut mini_render::ui::interaction::InteractionManager,
+) {
+    if let (InteractionResult::InputChange { id, value }, Some(replaced)) = (result, replaced) {
+        if replaced != *value {
+            interaction.replace_input_value(id, replaced);
+        }
+    }
+}
+
 /// 处理鼠标移动
 pub fn handle_cursor_moved(
     x: f32, y: f32,
@@ -267,26 +311,17 @@ pub fn handle_cursor_moved(
             if let Some(tr) = text_renderer {
                 let sf = scale_factor as f32;
                 let font_size = 16.0 * sf;
-                let padding_left = 12.0 * sf;
                 let bounds = focused.bounds;
-                let text_offset = focused.text_offset;
                 let click_x = ((x - bounds.x) * sf).max(0.0);
-                
-                let mut char_widths = Vec::new();
-                for c in focused.value.chars() {
-                    let char_str = c.to_string();
-                    let width = tr.measure_text(&char_str, font_size);
-                    char_widths.push(width);
-                }
-                
-                let cursor_pos = mini_render::ui::interaction::calculate_cursor_position(
-                    &focused.value, &char_widths, click_x, padding_left, text_offset
-                );
+                let click_y = (y + scroll.get_position() - bounds.y) * sf;
+                let cursor_pos = focused.cursor_from_point(click_x, click_y, sf, |c| tr.measure_char(c, font_size));
                 
                 interaction.update_text_selection(cursor_pos);
                 needs_redraw = true;
             }
         }
+    } else if interaction.is_dragging_text() {
+        needs_redraw = interaction.update_text_drag(x, y, scroll.get_position());
     } else if interaction.is_dragging_slider() {
         if let Some(result) = interaction.handle_mouse_move(x, y + scroll.get_position()) {
             handle_interaction_result(
@@ -300,9 +335,23 @@ pub fn handle_cursor_moved(
             );
         }
         needs_redraw = true;
+    } else if interaction.dragging_movable.is_some() {
+        if let Some(result) = interaction.update_movable_drag(x, y, timestamp) {
+            handle_interaction_result(
+                &result,
+                window,
+                renderer,
+                app,
+                clipboard,
+                scroll.get_position(),
+                scale_factor,
+            );
+        }
+        needs_redraw = true;
     } else if let Some(id) = interaction.dragging_scroll_area.clone() {
         if let Some(controller) = interaction.get_scroll_controller_mut(&id) {
-            controller.update_drag(y, timestamp);
+            let pos = if controller.is_horizontal() { x } else { y };
+            controller.update_drag(pos, timestamp);
         }
     } else if scroll.is_dragging {
         scroll.update_drag(y, timestamp);
@@ -319,12 +368,16 @@ pub fn handle_mouse_wheel(
     scroll: &mut mini_render::ui::ScrollController,
     scale_factor: f64,
 ) -> bool {
-    let (delta_y, is_precise) = match delta {
-        MouseScrollDelta::LineDelta(_, y) => (-y * 20.0, false),
-        MouseScrollDelta::PixelDelta(pos) => (-pos.y as f32 / scale_factor as f32, true),
+    let (delta_x, delta_y, is_precise) = match delta {
+        MouseScrollDelta::LineDelta(x, y) => (-x * 20.0, -y * 20.0, false),
+        MouseScrollDelta::PixelDelta(pos) => (
+            -pos.x as f32 / scale_factor as f32,
+            -pos.y as f32 / scale_factor as f32,
+            true,
+        ),
     };
     
-    if delta_y.abs() < 0.1 {
+    if delta_x.abs() < 0.1 && delta_y.abs() < 0.1 {
         return false;
     }
     
@@ -357,13 +410,21 @@ pub fn handle_mouse_wheel(
     
     if let Some(id) = scroll_area_id {
         if let Some(controller) = interaction.get_scroll_controller_mut(&id) {
-            controller.handle_scroll(delta_y, is_precise);
-            handled_by_scrollview = true;
-            needs_redraw = true;
+            // 横向 scroll-view 优先使用横向增量，普通鼠标滚轮则回退到纵向增量
+            let delta = if controller.is_horizontal() {
+                if delta_x.abs() >= 0.1 { delta_x } else { delta_y }
+            } else {
+                delta_y
+            };
+            if delta.abs() >= 0.1 {
+                controller.handle_scroll(delta, is_precise);
+                handled_by_scrollview = true;
+                needs_redraw = true;
+            }
         }
     }
     
-    if !handled_by_scrollview {
+    if !handled_by_scrollview && delta_y.abs() >= 0.1 {
         scroll.handle_scroll(delta_y, is_precise);
     }
     
@@ -3,35 +3,35 @@
 use mini_render::ui::interaction::{InteractionManager, InteractionResult, KeyInput};
 use winit::event::Ime;
 
-/// 处理 IME 事件
+/// 处理 IME 事件，返回交互结果和组合文本是否变化
 pub fn handle_ime_event(
     ime: Ime,
     interaction: &mut InteractionManager,
-) -> Vec<InteractionResult> {
+) -> (Vec<InteractionResult>, bool) {
     let mut results = Vec::new();
+    let mut preedit_changed = false;
     
     match ime {
         Ime::Commit(text) => {
-            if interaction.has_focused_input() {
-                for c in text.chars() {
-                    if let Some(result) = interaction.handle_key_input(KeyInput::Char(c)) {
-                        results.push(result);
-                    }
+            // 整段提交，超出 maxlength 的部分和直接输入一样被截断
+            if interaction.has_focused_input() && !text.is_empty() {
+                if let Some(result) = interaction.handle_key_input(KeyInput::Commit(text)) {
+                    results.push(result);
                 }
             }
         }
-        Ime::Preedit(text, cursor) => {
-            if !text.is_empty() {
-                println!("📝 IME Preedit: {} {:?}", text, cursor);
-            }
+        Ime::Preedit(text, _cursor) => {
+            // 组合文本显示在光标处，提交前不修改输入框的值；空文本表示取消组合
+            preedit_changed = interaction.set_preedit(&text);
         }
         Ime::Enabled => {
-            println!("📝 IME Enabled");
+            log::debug!("📝 IME Enabled");
         }
         Ime::Disabled => {
-            println!("📝 IME Disabled");
+            log::debug!("📝 IME Disabled");
+            preedit_changed = interaction.set_preedit("");
         }
     }
     
-    results
+    (results, preedit_changed)
 }
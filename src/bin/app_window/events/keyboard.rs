@@ -25,6 +25,8 @@ pub fn handle_keyboard_input(
             KeyCode::ArrowRight if shift => Some(KeyInput::ShiftRight),
             KeyCode::ArrowLeft => Some(KeyInput::Left),
             KeyCode::ArrowRight => Some(KeyInput::Right),
+            KeyCode::ArrowUp => Some(KeyInput::Up),
+            KeyCode::ArrowDown => Some(KeyInput::Down),
             KeyCode::Home if shift => Some(KeyInput::ShiftHome),
             KeyCode::End if shift => Some(KeyInput::ShiftEnd),
             KeyCode::Home => Some(KeyInput::Home),
@@ -79,6 +81,7 @@ pub fn handle_text_input(
 /// 处理默认键盘事件（非输入框聚焦时）
 pub fn handle_default_keyboard(
     physical_key: PhysicalKey,
+    ctrl: bool,
     interaction: &mut InteractionManager,
 ) -> Option<DefaultKeyAction> {
     if let PhysicalKey::Code(code) = physical_key {
@@ -95,6 +98,9 @@ pub fn handle_default_keyboard(
                     return Some(DefaultKeyAction::NavigateBack);
                 }
             }
+            KeyCode::KeyC if ctrl && interaction.text_selection.is_some() => {
+                return Some(DefaultKeyAction::CopySelection);
+            }
             KeyCode::ArrowUp => return Some(DefaultKeyAction::ScrollUp),
             KeyCode::ArrowDown => return Some(DefaultKeyAction::ScrollDown),
             KeyCode::PageUp => return Some(DefaultKeyAction::PageUp),
@@ -110,6 +116,8 @@ pub enum DefaultKeyAction {
     Exit,
     NavigateBack,
     BlurInput,
+    /// 复制可选中文本的选中部分（Ctrl/Cmd+C）
+    CopySelection,
     ScrollUp,
     ScrollDown,
     PageUp,
@@ -66,7 +66,8 @@ pub fn handle_mouse_pressed(
             InteractionType::ScrollArea => {
                 if !element.is_fixed {
                     if let Some(controller) = interaction.get_scroll_controller_mut(&element.id) {
-                        controller.begin_drag(y, timestamp);
+                        let pos = if controller.is_horizontal() { x } else { y };
+                        controller.begin_drag(pos, timestamp);
                         interaction.dragging_scroll_area = Some(element.id.clone());
                         return true;
                     }
@@ -109,7 +110,20 @@ pub fn handle_mouse_released(
     scroll.end_drag()
 }
 
+/// 处理内容区域的长按：可选中文本全选并复制
+pub fn handle_content_long_press(
+    x: f32,
+    y: f32,
+    scroll_pos: f32,
+    interaction: &mut InteractionManager,
+) -> Option<InteractionResult> {
+    // 先按视口坐标匹配 fixed 元素，再按页面坐标匹配
+    interaction.handle_long_press(x, y)
+        .or_else(|| interaction.handle_long_press(x, y + scroll_pos))
+}
+
 /// 处理内容区域点击
+/// 返回按发生顺序排列的交互结果：点击落在聚焦输入框之外时，失焦结果排在最前
 pub fn handle_content_click(
     x: f32,
     y: f32,
@@ -120,7 +134,8 @@ pub fn handle_content_click(
     app: &mut MiniApp,
     scale_factor: f64,
     text_renderer: Option<&mini_render::text::TextRenderer>,
-) -> Option<InteractionResult> {
+) -> Vec<InteractionResult> {
+    let mut results = Vec::new();
     let actual_y = y + scroll_pos;
     let tabbar_y = if has_tabbar { (LOGICAL_HEIGHT - TABBAR_HEIGHT) as f32 } else { LOGICAL_HEIGHT as f32 };
     
@@ -141,6 +156,8 @@ pub fn handle_content_click(
     };
     
     if let Some((event_type, handler, data, _bounds)) = fixed_binding {
+        results.extend(interaction.blur_if_outside(x, y));
+        
         // 检查交互元素（使用视口坐标）
         if let Some(result) = interaction.handle_click(x, y) {
             let should_call_js = matches!(&result, 
@@ -149,22 +166,29 @@ pub fn handle_content_click(
                 InteractionResult::Select { .. }
             );
             
-            if should_call_js {
-                println!("👆 {} -> {}", event_type, handler);
-                let data_json = serde_json::to_string(&data).unwrap_or("{}".to_string());
-                let call_code = format!("__callPageMethod('{}', {})", handler, data_json);
-                app.eval(&call_code).ok();
+            // 组的 bindchange 由组的 change 事件带上选中值触发
+            let group_change = interaction.group_change(&result);
+            let open_type = interaction.open_type_event(&result);
+            if should_call_js && !(group_change.is_some() && event_type == "change") {
+                log::debug!("👆 {} -> {}", event_type, handler);
+                let data = serde_json::to_value(&data).unwrap_or_default();
+                app.dispatch_page_event(&handler, &data, &serde_json::json!({})).ok();
             }
             
-            return Some(result);
+            results.push(result);
+            results.extend(group_change);
+            results.extend(open_type);
+            return results;
         }
         
-        // 如果没有交互元素，直接调用事件处理
-        println!("👆 {} -> {}", event_type, handler);
-        let data_json = serde_json::to_string(&data).unwrap_or("{}".to_string());
-        let call_code = format!("__callPageMethod('{}', {})", handler, data_json);
-        app.eval(&call_code).ok();
-        return None;
+        // 如果没有交互元素，沿冒泡路径调用事件处理
+        let handled = renderer.map(|r| dispatch_bubbling(r, app, x, y, &event_type)).unwrap_or(false);
+        if !handled {
+            log::debug!("👆 {} -> {}", event_type, handler);
+            let data = serde_json::to_value(&data).unwrap_or_default();
+            app.dispatch_page_event(&handler, &data, &serde_json::json!({})).ok();
+        }
+        return results;
     }
     
     // 检查是否点击在 scroll-view 内部，如果是，需要调整坐标
@@ -180,52 +204,26 @@ pub fn handle_content_click(
         }
     }
     
+    // 点击落在聚焦的输入框之外（空白处、其他组件或另一个输入框）时先失焦
+    results.extend(interaction.blur_if_outside(x, adjusted_y));
+    
     // 使用交互管理器处理点击
     if let Some(result) = interaction.handle_click(x, adjusted_y) {
         // 处理输入框光标位置
-        if let InteractionResult::Focus { click_x, .. } = &result {
+        if let InteractionResult::Focus { click_x, click_y, .. } = &result {
             if let Some(focused) = &interaction.focused_input {
                 if let Some(tr) = text_renderer {
-                    // click_x 是逻辑坐标（相对于输入框左边缘）
+                    // click_x / click_y 是逻辑坐标（相对于输入框左上角）
                     // 需要转换为物理坐标来匹配 measure_text 的结果
                     let sf = scale_factor as f32;
                     let font_size = 16.0 * sf;
-                    let padding_left = 12.0 * sf;
-                    let click_x_physical = *click_x * sf;
-                    let text_offset = focused.text_offset;
-                    
-                    let mut char_widths = Vec::new();
-                    for c in focused.value.chars() {
-                        let char_str = c.to_string();
-                        let width = tr.measure_text(&char_str, font_size);
-                        char_widths.push(width);
-                    }
-                    
-                    use mini_render::ui::interaction::calculate_cursor_position;
-                    let cursor_pos = calculate_cursor_position(&focused.value, &char_widths, click_x_physical, padding_left, text_offset);
+                    let cursor_pos = focused.cursor_from_point(*click_x * sf, *click_y * sf, sf, |c| tr.measure_char(c, font_size));
                     
                     if let Some(input) = &mut interaction.focused_input {
                         input.cursor_pos = cursor_pos;
                     }
                 }
             }
-            
-            // 设置输入框的 maxlength 和 input_type
-            if let Some(renderer) = renderer {
-                if let Some(binding) = renderer.hit_test(x, adjusted_y) {
-                    if let Some(input) = &mut interaction.focused_input {
-                        // 从 binding.data 或渲染节点获取属性
-                        if let Some(maxlength_str) = binding.data.get("maxlength") {
-                            if let Ok(maxlength) = maxlength_str.parse::<i32>() {
-                                input.maxlength = maxlength;
-                            }
-                        }
-                        if let Some(input_type) = binding.data.get("type") {
-                            input.input_type = input_type.clone();
-                        }
-                    }
-                }
-            }
         }
         
         let should_call_js = matches!(&result,
@@ -235,34 +233,43 @@ pub fn handle_content_click(
             InteractionResult::Focus { .. }
         );
         
+        // 组的 bindchange 由组的 change 事件带上选中值触发
+        let group_change = interaction.group_change(&result);
+        let open_type = interaction.open_type_event(&result);
         if should_call_js {
             if let Some(renderer) = renderer {
-                if let Some(binding) = renderer.hit_test(x, adjusted_y) {
-                    println!("👆 {} -> {}", binding.event_type, binding.handler);
-                    let data_json = serde_json::to_string(&binding.data).unwrap_or("{}".to_string());
-                    let call_code = format!("__callPageMethod('{}', {})", binding.handler, data_json);
-                    app.eval(&call_code).ok();
+                if let Some(binding) = renderer.hit_test(x, adjusted_y).filter(|b| group_change.is_none() || b.event_type != "change") {
+                    log::debug!("👆 {} -> {}", binding.event_type, binding.handler);
+                    let data = serde_json::to_value(&binding.data).unwrap_or_default();
+                    app.dispatch_page_event(&binding.handler, &data, &serde_json::json!({})).ok();
                 }
             }
         }
         
-        return Some(result);
-    } else {
-        // 点击了非交互区域，让输入框失去焦点
-        if interaction.has_focused_input() {
-            return interaction.blur_input();
-        }
+        results.push(result);
+        results.extend(group_change);
+        results.extend(open_type);
+        return results;
     }
     
-    // 检查其他事件绑定
+    // 检查其他事件绑定（按冒泡路径依次触发）
     if let Some(renderer) = renderer {
-        if let Some(binding) = renderer.hit_test(x, adjusted_y) {
-            println!("👆 {} -> {}", binding.event_type, binding.handler);
-            let data_json = serde_json::to_string(&binding.data).unwrap_or("{}".to_string());
-            let call_code = format!("__callPageMethod('{}', {})", binding.handler, data_json);
-            app.eval(&call_code).ok();
+        if let Some(event_type) = renderer.hit_test(x, adjusted_y).map(|b| b.event_type.clone()) {
+            dispatch_bubbling(renderer, app, x, adjusted_y, &event_type);
         }
     }
     
-    None
+    results
+}
+
+/// 从命中的最内层节点向上冒泡调用事件处理函数，catch 绑定会阻止继续冒泡
+/// 返回是否有处理函数被调用
+fn dispatch_bubbling(renderer: &WxmlRenderer, app: &mut MiniApp, x: f32, y: f32, event_type: &str) -> bool {
+    let path = renderer.hit_test_path(x, y, event_type);
+    for binding in &path {
+        log::debug!("👆 {} -> {}", binding.event_type, binding.handler);
+        let data = serde_json::to_value(&binding.data).unwrap_or_default();
+        app.dispatch_page_event(&binding.handler, &data, &serde_json::json!({})).ok();
+    }
+    !path.is_empty()
 }
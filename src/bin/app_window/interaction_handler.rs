@@ -2,11 +2,14 @@
 
 use mini_render::ui::interaction::InteractionResult;
 use mini_render::renderer::WxmlRenderer;
+use mini_render::renderer::components::ButtonComponent;
 use mini_render::runtime::MiniApp;
 use winit::window::Window;
 use std::sync::Arc;
 
 /// 处理交互结果
+///
+/// 返回 bindinput 处理函数要求写回输入框的新值（处理函数返回了字符串）
 pub fn handle_interaction_result(
     result: &InteractionResult,
     window: Option<&Arc<Window>>,
@@ -15,22 +18,23 @@ pub fn handle_interaction_result(
     clipboard: &mut Option<arboard::Clipboard>,
     scroll_position: f32,
     scale_factor: f64,
-) {
+) -> Option<String> {
+    let mut replaced_value = None;
     match result {
         InteractionResult::Toggle { id, checked } => {
-            println!("🔘 Toggle {}: {}", id, checked);
+            log::debug!("🔘 Toggle {}: {}", id, checked);
         }
         InteractionResult::Select { id, value } => {
-            println!("🔘 Select {}: {}", id, value);
+            log::debug!("🔘 Select {}: {}", id, value);
         }
         InteractionResult::SliderChange { id, value } => {
-            println!("🎚️ Slider {}: {}", id, value);
+            log::debug!("🎚️ Slider {}: {}", id, value);
         }
         InteractionResult::SliderEnd { id } => {
-            println!("🎚️ Slider {} released", id);
+            log::debug!("🎚️ Slider {} released", id);
         }
-        InteractionResult::Focus { id, bounds, click_x: _, is_fixed } => {
-            println!("📝 Focus: {} at ({:.0}, {:.0}, {:.0}x{:.0}) fixed={}", id, bounds.x, bounds.y, bounds.width, bounds.height, is_fixed);
+        InteractionResult::Focus { id, bounds, click_x: _, click_y: _, is_fixed } => {
+            log::debug!("📝 Focus: {} at ({:.0}, {:.0}, {:.0}x{:.0}) fixed={}", id, bounds.x, bounds.y, bounds.width, bounds.height, is_fixed);
             if let Some(window) = window {
                 window.set_ime_allowed(true);
                 let sf = scale_factor;
@@ -51,7 +55,7 @@ pub fn handle_interaction_result(
                 // 光标 y 位置设置在输入框底部边缘
                 let ime_y = ((viewport_y + bounds.height) * sf as f32) as f64;
                 
-                println!("📝 IME cursor: ({:.0}, {:.0})", ime_x, ime_y);
+                log::trace!("📝 IME cursor: ({:.0}, {:.0})", ime_x, ime_y);
                 
                 // size 设置为光标大小（1x字体高度）
                 let cursor_height = (16.0 * sf) as f64; // 默认字体大小
@@ -62,22 +66,14 @@ pub fn handle_interaction_result(
             }
         }
         InteractionResult::InputChange { id, value } => {
-            println!("📝 Input {}: {}", id, value);
-            if let Some(renderer) = renderer {
-                for binding in renderer.get_event_bindings() {
-                    if binding.event_type == "input" {
-                        let mut event_data = binding.data.clone();
-                        event_data.insert("value".to_string(), value.clone());
-                        let data_json = serde_json::to_string(&event_data).unwrap_or("{}".to_string());
-                        let call_code = format!("__callPageMethod('{}', {})", binding.handler, data_json);
-                        app.eval(&call_code).ok();
-                        break;
-                    }
-                }
+            log::debug!("📝 Input {}: {}", id, value);
+            if let Some(binding) = renderer.and_then(|r| r.binding_for_element(id, "input")) {
+                let data = serde_json::to_value(&binding.data).unwrap_or_default();
+                replaced_value = app.on_input(&binding.handler, &data, value).ok().flatten();
             }
         }
         InteractionResult::InputBlur { id, value } => {
-            println!("📝 Blur {}: {}", id, value);
+            log::debug!("📝 Blur {}: {}", id, value);
             if let Some(window) = window {
                 window.set_ime_allowed(false);
             }
@@ -86,56 +82,90 @@ pub fn handle_interaction_result(
                     if binding.event_type == "blur" {
                         let mut event_data = binding.data.clone();
                         event_data.insert("value".to_string(), value.clone());
-                        let data_json = serde_json::to_string(&event_data).unwrap_or("{}".to_string());
-                        let call_code = format!("__callPageMethod('{}', {})", binding.handler, data_json);
-                        app.eval(&call_code).ok();
+                        let data = serde_json::to_value(&event_data).unwrap_or_default();
+                        app.dispatch_page_event(&binding.handler, &data, &serde_json::json!({})).ok();
                         break;
                     }
                 }
             }
         }
-        InteractionResult::InputConfirm { id, value } => {
-            println!("📝 Confirm {}: {}", id, value);
-            if let Some(window) = window {
-                window.set_ime_allowed(false);
-            }
-            if let Some(renderer) = renderer {
-                for binding in renderer.get_event_bindings() {
-                    if binding.event_type == "confirm" {
-                        let mut event_data = binding.data.clone();
-                        event_data.insert("value".to_string(), value.clone());
-                        let data_json = serde_json::to_string(&event_data).unwrap_or("{}".to_string());
-                        let call_code = format!("__callPageMethod('{}', {})", binding.handler, data_json);
-                        app.eval(&call_code).ok();
-                        break;
-                    }
+        InteractionResult::InputConfirm { id, value, hold } => {
+            log::debug!("📝 Confirm {}: {}", id, value);
+            if !hold {
+                if let Some(window) = window {
+                    window.set_ime_allowed(false);
                 }
             }
+            if let Some(binding) = renderer.and_then(|r| r.binding_for_element(id, "confirm")) {
+                let data = serde_json::to_value(&binding.data).unwrap_or_default();
+                let extra = serde_json::json!({ "type": "confirm", "detail": { "value": value } });
+                app.dispatch_page_event(&binding.handler, &data, &extra).ok();
+            }
         }
         InteractionResult::ButtonClick { id, bounds: _ } => {
-            println!("🔘 Button clicked: {}", id);
+            log::debug!("🔘 Button clicked: {}", id);
         }
         InteractionResult::CopyText { text } => {
-            println!("📋 Copy: {}", text);
+            log::debug!("📋 Copy: {}", text);
             if let Some(ref mut cb) = clipboard {
                 if let Err(e) = cb.set_text(text) {
-                    println!("❌ Clipboard copy failed: {}", e);
+                    log::error!("❌ Clipboard copy failed: {}", e);
                 } else {
-                    println!("✅ Copied to clipboard");
+                    log::debug!("✅ Copied to clipboard");
                 }
             }
         }
         InteractionResult::CutText { text, id, value } => {
-            println!("✂️ Cut from {}: {} (remaining: {})", id, text, value);
+            log::debug!("✂️ Cut from {}: {} (remaining: {})", id, text, value);
             if let Some(ref mut cb) = clipboard {
                 if let Err(e) = cb.set_text(text) {
-                    println!("❌ Clipboard cut failed: {}", e);
+                    log::error!("❌ Clipboard cut failed: {}", e);
                 } else {
-                    println!("✅ Cut to clipboard");
+                    log::debug!("✅ Cut to clipboard");
+                }
+            }
+        }
+        InteractionResult::MovableChange { id, x, y, source } => {
+            if let Some(binding) = renderer.and_then(|r| r.binding_for_element(id, "change")) {
+                let data = serde_json::to_value(&binding.data).unwrap_or_default();
+                let extra = serde_json::json!({ "type": "change", "detail": { "x": x, "y": y, "source": source } });
+                app.dispatch_page_event(&binding.handler, &data, &extra).ok();
+            }
+        }
+        InteractionResult::VideoTap { id, src } => {
+            log::debug!("🎬 Video {} toggled", id);
+            mini_render::renderer::components::toggle_video_play(src);
+        }
+        InteractionResult::CheckboxGroupChange { id, values } => {
+            log::debug!("☑️ Checkbox group {}: {:?}", id, values);
+            if let Some(binding) = renderer.and_then(|r| r.binding_for_element(id, "change")) {
+                let data = serde_json::to_value(&binding.data).unwrap_or_default();
+                let extra = serde_json::json!({ "type": "change", "detail": { "value": values } });
+                app.dispatch_page_event(&binding.handler, &data, &extra).ok();
+            }
+        }
+        InteractionResult::RadioGroupChange { id, value } => {
+            log::debug!("🔘 Radio group {}: {}", id, value);
+            if let Some(binding) = renderer.and_then(|r| r.binding_for_element(id, "change")) {
+                let data = serde_json::to_value(&binding.data).unwrap_or_default();
+                let extra = serde_json::json!({ "type": "change", "detail": { "value": value } });
+                app.dispatch_page_event(&binding.handler, &data, &extra).ok();
+            }
+        }
+        InteractionResult::OpenType { id, open_type } => {
+            log::debug!("🔓 open-type {} on {}", open_type, id);
+            if open_type == "share" {
+                app.dispatch_page_event("onShareAppMessage", &serde_json::json!({}), &serde_json::json!({ "from": "button" })).ok();
+            } else if let Some((event_type, detail)) = ButtonComponent::open_type_event(open_type) {
+                if let Some(binding) = renderer.and_then(|r| r.binding_for_element(id, event_type)) {
+                    let data = serde_json::to_value(&binding.data).unwrap_or_default();
+                    let extra = serde_json::json!({ "type": event_type, "detail": detail });
+                    app.dispatch_page_event(&binding.handler, &data, &extra).ok();
                 }
             }
         }
     }
+    replaced_value
 }
 
 /// 检查并获取导航请求
@@ -149,8 +179,13 @@ pub fn check_navigation(app: &mut MiniApp) -> Option<super::navigation::Navigati
                     let url = nav.get("url").and_then(|v| v.as_str()).unwrap_or("");
                     let result = match nav_type {
                         "navigateTo" => Some(NavigationRequest::NavigateTo { url: url.to_string() }),
-                        "navigateBack" => Some(NavigationRequest::NavigateBack),
+                        "redirectTo" => Some(NavigationRequest::RedirectTo { url: url.to_string() }),
+                        "navigateBack" => {
+                            let delta = nav.get("delta").and_then(|v| v.as_u64()).unwrap_or(1).max(1) as usize;
+                            Some(NavigationRequest::NavigateBack { delta })
+                        }
                         "switchTab" => Some(NavigationRequest::SwitchTab { url: url.to_string() }),
+                        "reLaunch" => Some(NavigationRequest::ReLaunch { url: url.to_string() }),
                         _ => None,
                     };
                     // 清除导航请求
@@ -163,12 +198,12 @@ pub fn check_navigation(app: &mut MiniApp) -> Option<super::navigation::Navigati
     None
 }
 
-/// 打印 JS 输出
+/// 输出 JS 的 console 内容（info 级别日志）
 pub fn print_js_output(app: &MiniApp) {
     if let Ok(output) = app.eval("__print_buffer.splice(0).join('\\n')") {
         if !output.is_empty() && output != "undefined" {
             for line in output.lines() {
-                println!("   {}", line);
+                log::info!("   {}", line);
             }
         }
     }
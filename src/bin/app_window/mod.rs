@@ -16,8 +16,8 @@ pub use navigation::*;
 pub use tabbar::*;
 pub use render::*;
 pub use interaction_handler::*;
-pub use ui_overlay::{ToastState, LoadingState, ModalState, render_ui_overlay};
-pub use page_loader::{CustomTabBar, load_all_pages, load_custom_tabbar};
+pub use ui_overlay::{ToastState, LoadingState, ModalState, ActionSheetState, render_ui_overlay, render_pull_refresh_indicator};
+pub use page_loader::{APP_DIR, CustomTabBar, load_all_pages, load_custom_tabbar};
 pub use click_handler::*;
 pub use event_handler::*;
 
@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 use mini_render::parser::wxml::WxmlNode;
 use mini_render::parser::wxss::StyleSheet;
+use super::config::PageConfig;
 
 /// 页面信息
 pub struct PageInfo {
@@ -10,12 +11,12 @@ pub struct PageInfo {
     pub wxml: String,
     pub wxss: String,
     pub js: String,
+    pub config: PageConfig,
 }
 
 /// 页面栈中的页面实例
 pub struct PageInstance {
     pub path: String,
-    pub query: HashMap<String, String>,
     pub wxml_nodes: Vec<WxmlNode>,
     pub stylesheet: StyleSheet,
 }
@@ -24,8 +25,44 @@ pub struct PageInstance {
 #[derive(Clone)]
 pub enum NavigationRequest {
     NavigateTo { url: String },
-    NavigateBack,
+    /// 替换栈顶页面
+    RedirectTo { url: String },
+    /// 返回 delta 层，至少保留一个页面
+    NavigateBack { delta: usize },
     SwitchTab { url: String },
+    /// 清空页面栈后打开
+    ReLaunch { url: String },
+}
+
+impl NavigationRequest {
+    /// 对应的 wx 导航接口名和 url，用于录制宿主发起的导航
+    pub fn kind_and_url(&self) -> (&'static str, &str) {
+        match self {
+            NavigationRequest::NavigateTo { url } => ("navigateTo", url),
+            NavigationRequest::RedirectTo { url } => ("redirectTo", url),
+            NavigationRequest::NavigateBack { .. } => ("navigateBack", ""),
+            NavigationRequest::SwitchTab { url } => ("switchTab", url),
+            NavigationRequest::ReLaunch { url } => ("reLaunch", url),
+        }
+    }
+}
+
+/// 把页面中以 `./` 或 `../` 开头的路径（如 `../detail/detail?id=1`）解析为相对于当前页面的路径，
+/// 其他路径（`/pages/x/x`、tabBar 配置中的 `pages/x/x`）视为根路径
+pub fn resolve_url(current_path: &str, url: &str) -> String {
+    if !url.starts_with('.') {
+        return url.trim_start_matches('/').to_string();
+    }
+    let mut parts: Vec<&str> = current_path.split('/').collect();
+    parts.pop();
+    for segment in url.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => { parts.pop(); }
+            s => parts.push(s),
+        }
+    }
+    parts.join("/")
 }
 
 /// 解析 URL，返回路径和查询参数
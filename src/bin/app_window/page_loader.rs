@@ -1,10 +1,15 @@
 //! 页面加载模块
 
 use std::collections::HashMap;
+use std::path::Path;
 use mini_render::parser::{WxmlParser, WxssParser};
 use mini_render::parser::wxml::WxmlNode;
 use mini_render::parser::wxss::StyleSheet;
 use super::navigation::PageInfo;
+use super::config::PageConfig;
+
+/// 内置示例小程序目录，页面 import / include 的文件运行时从这里读取
+pub const APP_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/sample-app");
 
 /// 自定义 TabBar 数据
 pub struct CustomTabBar {
@@ -13,6 +18,14 @@ pub struct CustomTabBar {
     pub js_code: String,
 }
 
+/// 解析页面 json 配置，解析失败时使用默认配置
+fn parse_page_config(json: &str) -> PageConfig {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        log::warn!("⚠️ Page config parse error: {}", e);
+        PageConfig::default()
+    })
+}
+
 /// 加载所有页面
 pub fn load_all_pages() -> HashMap<String, PageInfo> {
     let mut pages = HashMap::new();
@@ -22,6 +35,7 @@ pub fn load_all_pages() -> HashMap<String, PageInfo> {
         wxml: include_str!("../../../sample-app/pages/index/index.wxml").to_string(),
         wxss: include_str!("../../../sample-app/pages/index/index.wxss").to_string(),
         js: include_str!("../../../sample-app/pages/index/index.js").to_string(),
+        config: parse_page_config(include_str!("../../../sample-app/pages/index/index.json")),
     });
     
     pages.insert("pages/category/category".to_string(), PageInfo {
@@ -29,6 +43,7 @@ pub fn load_all_pages() -> HashMap<String, PageInfo> {
         wxml: include_str!("../../../sample-app/pages/category/category.wxml").to_string(),
         wxss: include_str!("../../../sample-app/pages/category/category.wxss").to_string(),
         js: include_str!("../../../sample-app/pages/category/category.js").to_string(),
+        config: parse_page_config(include_str!("../../../sample-app/pages/category/category.json")),
     });
     
     pages.insert("pages/cart/cart".to_string(), PageInfo {
@@ -36,6 +51,7 @@ pub fn load_all_pages() -> HashMap<String, PageInfo> {
         wxml: include_str!("../../../sample-app/pages/cart/cart.wxml").to_string(),
         wxss: include_str!("../../../sample-app/pages/cart/cart.wxss").to_string(),
         js: include_str!("../../../sample-app/pages/cart/cart.js").to_string(),
+        config: PageConfig::default(),
     });
     
     pages.insert("pages/profile/profile".to_string(), PageInfo {
@@ -43,6 +59,7 @@ pub fn load_all_pages() -> HashMap<String, PageInfo> {
         wxml: include_str!("../../../sample-app/pages/profile/profile.wxml").to_string(),
         wxss: include_str!("../../../sample-app/pages/profile/profile.wxss").to_string(),
         js: include_str!("../../../sample-app/pages/profile/profile.js").to_string(),
+        config: PageConfig::default(),
     });
     
     pages.insert("pages/detail/detail".to_string(), PageInfo {
@@ -50,6 +67,7 @@ pub fn load_all_pages() -> HashMap<String, PageInfo> {
         wxml: include_str!("../../../sample-app/pages/detail/detail.wxml").to_string(),
         wxss: include_str!("../../../sample-app/pages/detail/detail.wxss").to_string(),
         js: include_str!("../../../sample-app/pages/detail/detail.js").to_string(),
+        config: PageConfig::default(),
     });
     
     pages.insert("pages/canvas/canvas".to_string(), PageInfo {
@@ -57,6 +75,7 @@ pub fn load_all_pages() -> HashMap<String, PageInfo> {
         wxml: include_str!("../../../sample-app/pages/canvas/canvas.wxml").to_string(),
         wxss: include_str!("../../../sample-app/pages/canvas/canvas.wxss").to_string(),
         js: include_str!("../../../sample-app/pages/canvas/canvas.js").to_string(),
+        config: parse_page_config(include_str!("../../../sample-app/pages/canvas/canvas.json")),
     });
     
     pages.insert("pages/components/components".to_string(), PageInfo {
@@ -64,6 +83,7 @@ pub fn load_all_pages() -> HashMap<String, PageInfo> {
         wxml: include_str!("../../../sample-app/pages/components/components.wxml").to_string(),
         wxss: include_str!("../../../sample-app/pages/components/components.wxss").to_string(),
         js: include_str!("../../../sample-app/pages/components/components.js").to_string(),
+        config: parse_page_config(include_str!("../../../sample-app/pages/components/components.json")),
     });
     
     pages
@@ -75,7 +95,7 @@ pub fn load_custom_tabbar() -> Result<Option<CustomTabBar>, String> {
     let wxss = include_str!("../../../sample-app/custom-tab-bar/index.wxss");
     let js = include_str!("../../../sample-app/custom-tab-bar/index.js");
     
-    let mut wxml_parser = WxmlParser::new(wxml);
+    let mut wxml_parser = WxmlParser::with_app_dir(wxml, Path::new(APP_DIR), "custom-tab-bar/index");
     let wxml_nodes = wxml_parser.parse().map_err(|e| format!("Custom TabBar WXML error: {}", e))?;
     
     let mut wxss_parser = WxssParser::new(wxss);
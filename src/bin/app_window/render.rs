@@ -3,6 +3,10 @@
 use mini_render::Canvas;
 
 /// 将内容渲染到窗口缓冲区
+///
+/// dirty_rows 为需要更新的缓冲区行区间 [start, end)，None 表示整帧复制（包括 TabBar）。
+/// 只更新部分行时缓冲区中其余行必须保留着上一帧的内容。
+#[allow(clippy::too_many_arguments)]
 pub fn present_to_buffer(
     buffer: &mut [u32],
     buffer_width: u32,
@@ -13,12 +17,16 @@ pub fn present_to_buffer(
     scroll_offset: i32,
     has_tabbar: bool,
     tabbar_physical_height: u32,
+    dirty_rows: Option<&[(u32, u32)]>,
 ) {
     let pixels = canvas.pixels();
     let canvas_width = canvas.width();
     let canvas_height = canvas.height();
     
     let content_area_height = buffer_height - if has_tabbar { tabbar_physical_height } else { 0 };
+    let full = [(0, content_area_height)];
+    let rows = || dirty_rows.unwrap_or(&full).iter()
+        .flat_map(move |&(start, end)| start.min(content_area_height)..end.min(content_area_height));
     
     // 背景色 (0xF5F5F5)
     let bg_color: u32 = 0xF5F5F5;
@@ -26,7 +34,7 @@ pub fn present_to_buffer(
     let copy_width = buffer_width.min(canvas_width) as usize;
     
     // 渲染主内容
-    for dst_y in 0..content_area_height {
+    for dst_y in rows() {
         let src_y = dst_y as i32 + scroll_offset;
         let dst_row_start = (dst_y * buffer_width) as usize;
         
@@ -57,7 +65,7 @@ pub fn present_to_buffer(
         let draw_h = content_area_height.min(fixed_height);
         let draw_w = (buffer_width as usize).min(fixed_width);
         
-        for y in 0..draw_h {
+        for y in rows().filter(|&y| y < draw_h) {
             let src_row = (y as usize) * fixed_width;
             let dst_row = (y * buffer_width) as usize;
             
@@ -81,8 +89,8 @@ pub fn present_to_buffer(
         }
     }
     
-    // 渲染 TabBar
-    if has_tabbar {
+    // 渲染 TabBar（TabBar 只在整帧更新时变化）
+    if has_tabbar && dirty_rows.is_none() {
         if let Some(tabbar_canvas) = tabbar_canvas {
             let tabbar_pixels = tabbar_canvas.pixels();
             let tabbar_width = tabbar_canvas.width() as usize;
@@ -104,3 +112,23 @@ pub fn present_to_buffer(
         }
     }
 }
+
+/// 把页面逻辑坐标中的脏区域换算为缓冲区中的行区间（已合并重叠部分）
+pub fn dirty_rows_for(rects: &[mini_render::Rect], scale_factor: f32, scroll_offset: i32, content_area_height: u32) -> Vec<(u32, u32)> {
+    let mut rows: Vec<(u32, u32)> = rects.iter().filter_map(|r| {
+        // 多留一个像素，覆盖抗锯齿边缘
+        let top = (r.y * scale_factor).floor() as i32 - scroll_offset - 1;
+        let bottom = ((r.y + r.height) * scale_factor).ceil() as i32 - scroll_offset + 1;
+        let (top, bottom) = (top.max(0) as u32, bottom.clamp(0, content_area_height as i32) as u32);
+        (top < bottom).then_some((top, bottom))
+    }).collect();
+    rows.sort_unstable();
+    let mut merged: Vec<(u32, u32)> = Vec::with_capacity(rows.len());
+    for (start, end) in rows {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
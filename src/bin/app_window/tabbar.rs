@@ -96,7 +96,7 @@ pub fn handle_native_tabbar_click(
     if clicked_index < item_count {
         let target_path = &tab_bar.list[clicked_index].page_path;
         if target_path != current_path {
-            println!("👆 TabBar -> {} ({})", tab_bar.list[clicked_index].text, target_path);
+            log::debug!("👆 TabBar -> {} ({})", tab_bar.list[clicked_index].text, target_path);
             return Some(target_path.clone());
         }
     }
@@ -1,19 +1,20 @@
 //! UI 覆盖层模块 - Toast/Loading/Modal 的状态和渲染
 
 use mini_render::{Canvas, Color, Paint};
+use mini_render::renderer::components::IconComponent;
 use mini_render::text::TextRenderer;
 use std::sync::Arc;
 use std::time::Instant;
 use winit::window::Window;
 
-/// Toast 状态
+/// Toast 状态（由 JS 的 hideToast 定时器关闭）
 #[derive(Clone)]
 pub struct ToastState {
     pub title: String,
     pub icon: String,
     pub visible: bool,
+    /// 显示时间，用于 loading 图标的旋转
     pub start_time: Instant,
-    pub duration_ms: u32,
 }
 
 /// Loading 状态
@@ -21,6 +22,8 @@ pub struct ToastState {
 pub struct LoadingState {
     pub title: String,
     pub visible: bool,
+    /// 显示时间，用于旋转动画
+    pub start_time: Instant,
 }
 
 /// Modal 状态
@@ -35,17 +38,33 @@ pub struct ModalState {
     pub pressed_button: Option<String>, // "cancel" or "confirm"
 }
 
-/// 渲染 UI 覆盖层（Toast/Loading/Modal）
+/// ActionSheet 每个选项（以及取消按钮）的高度（逻辑像素）
+pub const ACTION_SHEET_ITEM_HEIGHT: f32 = 56.0;
+/// ActionSheet 选项与取消按钮之间的间隔（逻辑像素）
+pub const ACTION_SHEET_CANCEL_GAP: f32 = 8.0;
+
+/// ActionSheet 状态
+#[derive(Clone)]
+pub struct ActionSheetState {
+    pub items: Vec<String>,
+    pub item_color: Color,
+    pub visible: bool,
+    /// 按下的行：选项下标，items.len() 为取消按钮
+    pub pressed: Option<usize>,
+}
+
+/// 渲染 UI 覆盖层（Toast/Loading/Modal/ActionSheet）
 pub fn render_ui_overlay(
     buffer: &mut softbuffer::Buffer<Arc<Window>, Arc<Window>>,
-    width: u32, height: u32, sf: f32, last_frame: Instant,
+    width: u32, height: u32, sf: f32,
     toast: &Option<ToastState>, loading: &Option<LoadingState>, modal: &Option<ModalState>,
+    action_sheet: &Option<ActionSheetState>,
     text_renderer: Option<&TextRenderer>
 ) {
     // 渲染 Loading（优先级最高）
     if let Some(loading) = loading {
         if loading.visible {
-            render_loading_to_buffer(buffer, width, height, &loading.title, sf, last_frame, text_renderer);
+            render_loading_to_buffer(buffer, width, height, &loading.title, sf, loading.start_time, text_renderer);
             return;
         }
     }
@@ -58,10 +77,17 @@ pub fn render_ui_overlay(
         }
     }
     
+    // 渲染 ActionSheet（toast 仍可显示在其上方）
+    if let Some(sheet) = action_sheet {
+        if sheet.visible {
+            render_action_sheet_to_buffer(buffer, width, height, sheet, sf, text_renderer);
+        }
+    }
+    
     // 渲染 Toast
     if let Some(toast) = toast {
         if toast.visible {
-            render_toast_to_buffer(buffer, width, height, &toast.title, &toast.icon, sf, text_renderer);
+            render_toast_to_buffer(buffer, width, height, toast, sf, text_renderer);
         }
     }
 }
@@ -70,9 +96,10 @@ pub fn render_ui_overlay(
 /// 渲染 Toast 到 buffer
 fn render_toast_to_buffer(
     buffer: &mut softbuffer::Buffer<Arc<Window>, Arc<Window>>,
-    width: u32, height: u32, title: &str, icon: &str, sf: f32,
+    width: u32, height: u32, toast: &ToastState, sf: f32,
     text_renderer: Option<&TextRenderer>
 ) {
+    let (title, icon) = (toast.title.as_str(), toast.icon.as_str());
     let toast_padding = (20.0 * sf) as i32;
     let toast_min_width = (140.0 * sf) as i32;
     let toast_height = if icon == "none" { (50.0 * sf) as i32 } else { (120.0 * sf) as i32 };
@@ -97,12 +124,12 @@ fn render_toast_to_buffer(
     if icon != "none" {
         let icon_x = toast_x + (toast_width - icon_size) / 2;
         let icon_y = toast_y + toast_padding;
-        let icon_color = if icon == "success" { 0xFF09BB07u32 } else { 0xFFFFFFFFu32 };
         
-        if icon == "success" {
-            draw_checkmark(buffer, width, height, icon_x, icon_y, icon_size, icon_color, sf);
-        } else {
-            draw_circle_outline(buffer, width, height, icon_x + icon_size/2, icon_y + icon_size/2, icon_size/2 - 4, icon_color, (3.0 * sf) as i32);
+        // success / error 复用 icon 组件的图标
+        match icon {
+            "error" => draw_icon_direct(buffer, width, height, "cancel", icon_x, icon_y, icon_size, Color::WHITE, Color::from_hex(0x4C4C4C)),
+            "loading" => draw_spinner(buffer, width, height, icon_x + icon_size / 2, icon_y + icon_size / 2, sf, toast.start_time),
+            _ => draw_icon_direct(buffer, width, height, "success", icon_x, icon_y, icon_size, Color::from_hex(0x09BB07), Color::WHITE),
         }
     }
     
@@ -121,7 +148,7 @@ fn render_toast_to_buffer(
 /// 渲染 Loading 到 buffer
 fn render_loading_to_buffer(
     buffer: &mut softbuffer::Buffer<Arc<Window>, Arc<Window>>,
-    width: u32, height: u32, title: &str, sf: f32, last_frame: Instant,
+    width: u32, height: u32, title: &str, sf: f32, start_time: Instant,
     text_renderer: Option<&TextRenderer>
 ) {
     let loading_size = (120.0 * sf) as i32;
@@ -132,23 +159,7 @@ fn render_loading_to_buffer(
     
     draw_rounded_rect(buffer, width, height, loading_x, loading_y, loading_size, loading_size, radius, bg_color);
     
-    let center_x = loading_x + loading_size / 2;
-    let center_y = loading_y + (45.0 * sf) as i32;
-    let spinner_radius = (22.0 * sf) as i32;
-    let time = last_frame.elapsed().as_secs_f32();
-    let angle = time * 5.0;
-    let dot_radius = (4.0 * sf) as i32;
-    
-    for i in 0..12 {
-        let seg_angle = angle + (i as f32 * std::f32::consts::PI / 6.0);
-        let alpha = ((12 - i) as f32 / 12.0 * 255.0) as u8;
-        let color = 0xFF000000 | ((alpha as u32) << 16) | ((alpha as u32) << 8) | (alpha as u32);
-        
-        let dot_x = center_x + (spinner_radius as f32 * seg_angle.cos()) as i32;
-        let dot_y = center_y + (spinner_radius as f32 * seg_angle.sin()) as i32;
-        
-        draw_filled_circle(buffer, width, height, dot_x, dot_y, dot_radius, color);
-    }
+    draw_spinner(buffer, width, height, loading_x + loading_size / 2, loading_y + (45.0 * sf) as i32, sf, start_time);
     
     if let Some(tr) = text_renderer {
         let font_size = 14.0 * sf;
@@ -161,6 +172,33 @@ fn render_loading_to_buffer(
 }
 
 
+/// 渲染下拉刷新指示器（顶部三个圆点，刷新中时依次闪烁）
+pub fn render_pull_refresh_indicator(
+    buffer: &mut softbuffer::Buffer<Arc<Window>, Arc<Window>>,
+    width: u32, height: u32, sf: f32, pull_distance: f32, refresh_start: Option<Instant>
+) {
+    if pull_distance <= 0.0 && refresh_start.is_none() { return; }
+    
+    // 下拉过程中圆点跟随下拉区域居中，刷新中固定在顶部
+    let area = if refresh_start.is_some() { pull_distance.max(40.0) } else { pull_distance };
+    let center_y = (area / 2.0 * sf) as i32;
+    let center_x = width as i32 / 2;
+    let spacing = (14.0 * sf) as i32;
+    let dot_radius = (3.0 * sf) as i32;
+    
+    for i in 0..3 {
+        let gray = match refresh_start {
+            // 当前高亮的圆点颜色最深
+            Some(start) => if i == (start.elapsed().as_secs_f32() * 3.0) as i32 % 3 { 0x99u32 } else { 0xCCu32 },
+            None => 0xBBu32,
+        };
+        let color = 0xFF000000 | (gray << 16) | (gray << 8) | gray;
+        let dot_x = center_x + (i - 1) * spacing;
+        draw_filled_circle(buffer, width, height, dot_x, center_y, dot_radius, color);
+    }
+}
+
+
 /// 渲染 Modal 到 buffer
 fn render_modal_to_buffer(
     buffer: &mut softbuffer::Buffer<Arc<Window>, Arc<Window>>,
@@ -270,6 +308,44 @@ fn render_modal_to_buffer(
     }
 }
 
+/// 渲染 ActionSheet 到 buffer：遮罩 + 底部选项列表 + 取消按钮
+fn render_action_sheet_to_buffer(
+    buffer: &mut [u32],
+    width: u32, height: u32, sheet: &ActionSheetState, sf: f32,
+    text_renderer: Option<&TextRenderer>
+) {
+    // 遮罩 rgba(0, 0, 0, 0.6)
+    for px in buffer.iter_mut() {
+        let (r, g, b) = ((*px >> 16) & 0xFF, (*px >> 8) & 0xFF, *px & 0xFF);
+        *px = 0xFF000000 | ((r * 2 / 5) << 16) | ((g * 2 / 5) << 8) | (b * 2 / 5);
+    }
+    
+    let item_height = (ACTION_SHEET_ITEM_HEIGHT * sf) as i32;
+    let gap = (ACTION_SHEET_CANCEL_GAP * sf) as i32;
+    let count = sheet.items.len() as i32;
+    let sheet_y = height as i32 - item_height * (count + 1) - gap;
+    let font_size = 17.0 * sf;
+    
+    draw_rounded_rect(buffer, width, height, 0, sheet_y, width as i32, height as i32 - sheet_y, 0, 0xFFF7F7F7);
+    
+    let rows = sheet.items.iter().map(|item| (item.as_str(), sheet.item_color)).chain(std::iter::once(("取消", Color::BLACK)));
+    for (i, (text, color)) in rows.enumerate() {
+        let is_cancel = i as i32 == count;
+        // 选项之间 1px 分隔线，取消按钮上方是间隔
+        let row_y = sheet_y + i as i32 * item_height + if is_cancel { gap } else { 0 };
+        let separator = if i > 0 && !is_cancel { 1 } else { 0 };
+        let bg = if sheet.pressed == Some(i) { 0xFFECECEC } else { 0xFFFFFFFF };
+        draw_rounded_rect(buffer, width, height, 0, row_y + separator, width as i32, item_height - separator, 0, bg);
+        
+        if let Some(tr) = text_renderer {
+            let text_w = tr.measure_text(text, font_size) as i32;
+            let text_x = (width as i32 - text_w) / 2;
+            let text_y = row_y + (item_height - font_size as i32) / 2;
+            draw_text_direct(buffer, width, height, tr, text, text_x, text_y, font_size, color);
+        }
+    }
+}
+
 /// 文字换行
 fn wrap_text(tr: &TextRenderer, text: &str, font_size: f32, max_width: f32) -> (Vec<String>, i32) {
     let mut lines = Vec::new();
@@ -360,69 +436,22 @@ fn draw_filled_circle(buffer: &mut [u32], width: u32, height: u32, cx: i32, cy:
     }
 }
 
-/// 绘制圆环
-fn draw_circle_outline(buffer: &mut [u32], width: u32, height: u32, cx: i32, cy: i32, radius: i32, color: u32, thickness: i32) {
-    let outer = radius as f32;
-    let inner = (radius - thickness) as f32;
-    for dy in -(radius + 1)..=(radius + 1) {
-        for dx in -(radius + 1)..=(radius + 1) {
-            let dist = ((dx * dx + dy * dy) as f32).sqrt();
-            if dist <= outer && dist >= inner {
-                let px = cx + dx;
-                let py = cy + dy;
-                if px >= 0 && px < width as i32 && py >= 0 && py < height as i32 {
-                    let idx = (py as u32 * width + px as u32) as usize;
-                    if idx < buffer.len() { buffer[idx] = color; }
-                }
-            }
-        }
-    }
-}
-
-/// 绘制勾号图标
-fn draw_checkmark(buffer: &mut [u32], width: u32, height: u32, x: i32, y: i32, size: i32, color: u32, sf: f32) {
-    let cx = x + size / 2;
-    let cy = y + size / 2;
-    let r = size / 2 - (4.0 * sf) as i32;
-    let stroke = (4.0 * sf) as i32;
-    
-    draw_circle_outline(buffer, width, height, cx, cy, r + stroke/2, color, stroke);
-    
-    let p1_x = cx - r * 2 / 5;
-    let p1_y = cy;
-    let p2_x = cx - r / 10;
-    let p2_y = cy + r / 3;
-    let p3_x = cx + r * 2 / 5;
-    let p3_y = cy - r / 3;
-    
-    draw_thick_line(buffer, width, height, p1_x, p1_y, p2_x, p2_y, stroke, color);
-    draw_thick_line(buffer, width, height, p2_x, p2_y, p3_x, p3_y, stroke, color);
-}
-
-/// 绘制粗线
-fn draw_thick_line(buffer: &mut [u32], width: u32, height: u32, x1: i32, y1: i32, x2: i32, y2: i32, thickness: i32, color: u32) {
-    let dx = (x2 - x1) as f32;
-    let dy = (y2 - y1) as f32;
-    let len = (dx * dx + dy * dy).sqrt();
-    let steps = (len * 2.0) as i32;
+/// 绘制旋转的加载图标：12 个渐隐圆点，按显示时长转动
+fn draw_spinner(buffer: &mut [u32], width: u32, height: u32, cx: i32, cy: i32, sf: f32, start_time: Instant) {
+    let spinner_radius = (22.0 * sf) as i32;
+    let dot_radius = (4.0 * sf) as i32;
+    // 每 80ms 前进一格，与微信的 loading 图标节奏接近
+    let step = (start_time.elapsed().as_millis() / 80) as usize % 12;
     
-    for i in 0..=steps {
-        let t = i as f32 / steps as f32;
-        let px = (x1 as f32 + dx * t) as i32;
-        let py = (y1 as f32 + dy * t) as i32;
+    for i in 0..12 {
+        let seg_angle = (step + 12 - i) as f32 * std::f32::consts::PI / 6.0;
+        let alpha = ((12 - i) as f32 / 12.0 * 255.0) as u8;
+        let color = 0xFF000000 | ((alpha as u32) << 16) | ((alpha as u32) << 8) | (alpha as u32);
         
-        for ddy in -thickness..=thickness {
-            for ddx in -thickness..=thickness {
-                if ddx * ddx + ddy * ddy <= thickness * thickness {
-                    let fx = px + ddx;
-                    let fy = py + ddy;
-                    if fx >= 0 && fx < width as i32 && fy >= 0 && fy < height as i32 {
-                        let idx = (fy as u32 * width + fx as u32) as usize;
-                        if idx < buffer.len() { buffer[idx] = color; }
-                    }
-                }
-            }
-        }
+        let dot_x = cx + (spinner_radius as f32 * seg_angle.cos()) as i32;
+        let dot_y = cy + (spinner_radius as f32 * seg_angle.sin()) as i32;
+        
+        draw_filled_circle(buffer, width, height, dot_x, dot_y, dot_radius, color);
     }
 }
 
@@ -448,10 +477,24 @@ fn draw_text_direct(
     let baseline_y = font_size * 0.85;
     tr.draw_text(&mut temp_canvas, text, 0.0, baseline_y, font_size, &paint);
     
+    blit_canvas(buffer, buf_width, buf_height, &temp_canvas, x, y);
+}
+
+/// 用 icon 组件的矢量图标绘制到 buffer，size 为物理像素边长
+fn draw_icon_direct(buffer: &mut [u32], buf_width: u32, buf_height: u32, icon_type: &str, x: i32, y: i32, size: i32, color: Color, mark: Color) {
+    if size <= 0 { return; }
+    let mut temp_canvas = Canvas::new(size as u32, size as u32);
+    temp_canvas.clear(Color::TRANSPARENT);
+    IconComponent::draw_icon(&mut temp_canvas, icon_type, 0.0, 0.0, size as f32, color, mark);
+    blit_canvas(buffer, buf_width, buf_height, &temp_canvas, x, y);
+}
+
+/// 把临时画布按 alpha 混合到 buffer 的 (x, y) 处
+fn blit_canvas(buffer: &mut [u32], buf_width: u32, buf_height: u32, temp_canvas: &Canvas, x: i32, y: i32) {
     let temp_pixels = temp_canvas.pixels();
-    for py in 0..text_height as i32 {
-        for px in 0..text_width as i32 {
-            let src_idx = (py as u32 * text_width + px as u32) as usize;
+    for py in 0..temp_canvas.height() as i32 {
+        for px in 0..temp_canvas.width() as i32 {
+            let src_idx = (py as u32 * temp_canvas.width() + px as u32) as usize;
             let dst_x = x + px;
             let dst_y = y + py;
             if dst_x >= 0 && dst_x < buf_width as i32 && dst_y >= 0 && dst_y < buf_height as i32 {
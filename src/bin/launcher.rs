@@ -6,6 +6,7 @@
 //! 3. 点击后加载并运行选中的小程序
 
 use std::collections::HashMap;
+use std::io::Write;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::num::NonZeroU32;
@@ -226,7 +227,7 @@ impl LauncherApp {
                 let btn_x = WINDOW_WIDTH as f32 - 32.0 - 16.0 - btn_width;
                 
                 if lx >= btn_x && lx < btn_x + btn_width {
-                    println!("🚀 启动小程序: {}", app.name);
+                    log::info!("🚀 启动小程序: {}", app.name);
                     self.launch_mini_app(app.path.clone());
                     return true;
                 }
@@ -238,21 +239,21 @@ impl LauncherApp {
     
     /// 启动小程序
     fn launch_mini_app(&mut self, app_path: PathBuf) {
-        println!("📂 加载小程序: {:?}", app_path);
+        log::info!("📂 加载小程序: {:?}", app_path);
         
         match load_mini_app(&app_path, self.scale_factor) {
             Ok(running_app) => {
                 self.state = LauncherState::Running(running_app);
             }
             Err(e) => {
-                eprintln!("❌ 加载小程序失败: {}", e);
+                log::error!("❌ 加载小程序失败: {}", e);
             }
         }
     }
     
     /// 返回列表
     fn back_to_list(&mut self) {
-        println!("🔙 返回小程序列表");
+        log::info!("🔙 返回小程序列表");
         self.state = LauncherState::List;
         // 重新扫描目录
         self.mini_apps = scan_sample_directory();
@@ -399,18 +400,21 @@ impl ApplicationHandler for LauncherApp {
                     let canvas_width = self.canvas.width() as usize;
                     let canvas_height = self.canvas.height() as usize;
                     
+                    let copy_w = size.width.min(canvas_width as u32) as usize;
                     for y in 0..size.height.min(canvas_height as u32) {
-                        for x in 0..size.width.min(canvas_width as u32) {
-                            let src_y = match &self.state {
-                                LauncherState::List => y as usize,
-                                LauncherState::Running(_) => (y as f32 + scroll_offset).min(canvas_height as f32 - 1.0).max(0.0) as usize,
-                            };
-                            let src_idx = src_y * canvas_width + x as usize;
-                            let dst_idx = y as usize * size.width as usize + x as usize;
-                            if src_idx < pixels.len() && dst_idx < buffer.len() {
-                                let color = &pixels[src_idx];
-                                buffer[dst_idx] = ((color.r as u32) << 16) | ((color.g as u32) << 8) | (color.b as u32);
-                            }
+                        let src_y = match &self.state {
+                            LauncherState::List => y as usize,
+                            LauncherState::Running(_) => (y as f32 + scroll_offset).min(canvas_height as f32 - 1.0).max(0.0) as usize,
+                        };
+                        // 按行整体复制，每行只做一次边界检查
+                        let src_start = src_y * canvas_width;
+                        let dst_start = y as usize * size.width as usize;
+                        if src_start + copy_w > pixels.len() || dst_start + copy_w > buffer.len() {
+                            continue;
+                        }
+                        let src_row = &pixels[src_start..src_start + copy_w];
+                        for (dst, color) in buffer[dst_start..dst_start + copy_w].iter_mut().zip(src_row) {
+                            *dst = ((color.r as u32) << 16) | ((color.g as u32) << 8) | (color.b as u32);
                         }
                     }
                     
@@ -517,7 +521,7 @@ impl ApplicationHandler for LauncherApp {
                             if let Some((logical_x, logical_y)) = click_info {
                                 // 检查事件绑定
                                 if let Some(binding) = app.renderer.hit_test(logical_x, logical_y) {
-                                    println!("👆 点击事件: {} -> {}", binding.event_type, binding.handler);
+                                    log::debug!("👆 点击事件: {} -> {}", binding.event_type, binding.handler);
                                 }
                             }
                         }
@@ -577,7 +581,7 @@ fn scan_sample_directory() -> Vec<MiniAppInfo> {
     
     for sample_dir in possible_paths {
         if sample_dir.exists() && sample_dir.is_dir() {
-            println!("📁 扫描目录: {:?}", sample_dir);
+            log::info!("📁 扫描目录: {:?}", sample_dir);
             
             if let Ok(entries) = fs::read_dir(&sample_dir) {
                 for entry in entries.flatten() {
@@ -586,7 +590,7 @@ fn scan_sample_directory() -> Vec<MiniAppInfo> {
                         let app_json = path.join("app.json");
                         if app_json.exists() {
                             if let Some(info) = parse_app_info(&path) {
-                                println!("  ✅ 发现小程序: {}", info.name);
+                                log::info!("  ✅ 发现小程序: {}", info.name);
                                 apps.push(info);
                             }
                         }
@@ -601,7 +605,7 @@ fn scan_sample_directory() -> Vec<MiniAppInfo> {
     }
     
     if apps.is_empty() {
-        println!("⚠️ 未找到任何小程序，请确保 sample 目录存在");
+        log::warn!("⚠️ 未找到任何小程序，请确保 sample 目录存在");
     }
     
     apps
@@ -677,8 +681,8 @@ fn load_mini_app(app_path: &Path, scale_factor: f32) -> Result<RunningApp, Strin
     let page_info = page_map.get(&first_page)
         .ok_or("首页不存在")?;
     
-    // 解析 WXML
-    let mut wxml_parser = WxmlParser::new(&page_info.wxml);
+    // 解析 WXML（import / include 相对页面目录，/ 开头相对小程序根目录）
+    let mut wxml_parser = WxmlParser::with_app_dir(&page_info.wxml, app_path, &first_page);
     let wxml_nodes = wxml_parser.parse()
         .map_err(|e| format!("解析 WXML 失败: {}", e))?;
     
@@ -688,12 +692,15 @@ fn load_mini_app(app_path: &Path, scale_factor: f32) -> Result<RunningApp, Strin
         .map_err(|e| format!("解析 WXSS 失败: {}", e))?;
     
     // 创建渲染器
-    let renderer = mini_render::renderer::WxmlRenderer::new_with_scale(
+    let mut renderer = mini_render::renderer::WxmlRenderer::new_with_scale(
         stylesheet.clone(),
         WINDOW_WIDTH as f32,
         WINDOW_HEIGHT as f32,
         scale_factor,
     );
+    if let Some(page_dir) = app_path.join(&first_page).parent() {
+        renderer.set_source_dir(page_dir);
+    }
     
     // 创建 MiniApp
     let mut mini_app = MiniApp::new(WINDOW_WIDTH, WINDOW_HEIGHT)
@@ -748,7 +755,7 @@ fn load_custom_tabbar_from_path(app_path: &Path) -> Option<CustomTabBar> {
     let wxml = fs::read_to_string(&wxml_path).ok()?;
     let wxss = fs::read_to_string(&wxss_path).ok()?;
     
-    let mut wxml_parser = WxmlParser::new(&wxml);
+    let mut wxml_parser = WxmlParser::with_app_dir(&wxml, app_path, "custom-tab-bar/index");
     let wxml_nodes = wxml_parser.parse().ok()?;
     
     let mut wxss_parser = WxssParser::new(&wxss);
@@ -761,9 +768,11 @@ fn load_custom_tabbar_from_path(app_path: &Path) -> Option<CustomTabBar> {
 }
 
 fn main() {
-    println!("🚀 Mini Program Launcher");
-    println!("========================");
-    println!("扫描 sample 目录下的小程序...\n");
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .format(|buf, record| writeln!(buf, "{}", record.args()))
+        .init();
+    log::info!("🚀 Mini Program Launcher");
+    log::info!("扫描 sample 目录下的小程序...");
     
     let event_loop = EventLoop::new().unwrap();
     event_loop.set_control_flow(ControlFlow::Poll);
@@ -80,10 +80,7 @@ fn main() -> Result<(), String> {
             println!("\n[Click {}] {} -> {}", i + 1, binding.event_type, binding.handler);
             
             // 调用 JS 事件处理函数
-            let _ = app.eval(&format!(
-                "__callPageMethod('{}', {{}})",
-                binding.handler
-            ));
+            let _ = app.dispatch_page_event(&binding.handler, &json!({}), &json!({}));
         }
     }
     
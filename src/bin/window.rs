@@ -3,11 +3,12 @@
 mod app_window;
 
 use app_window::*;
-use app_window::ui_overlay::{ToastState, LoadingState, ModalState, render_ui_overlay};
+use app_window::ui_overlay::{ToastState, LoadingState, ModalState, ActionSheetState, render_ui_overlay, render_pull_refresh_indicator};
 use app_window::event_handler as evt;
 use app_window::click_handler as click;
 
-use mini_render::runtime::MiniApp;
+use mini_render::runtime::{LaunchOptions, MiniApp};
+use mini_render::js::SystemInfo;
 use mini_render::parser::{WxmlParser, WxssParser};
 use mini_render::renderer::WxmlRenderer;
 use mini_render::ui::interaction::InteractionManager;
@@ -18,11 +19,14 @@ use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::Instant;
 use std::collections::HashMap;
+use std::path::Path;
+use std::io::Write;
 use winit::application::ApplicationHandler;
 use winit::event::{ElementState, MouseButton, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::window::{Window, WindowAttributes, WindowId};
 use mini_render::ui::ScrollController;
+use mini_render::ui::scroll_controller::ScrollEvent;
 
 struct MiniAppWindow {
     window: Option<Arc<Window>>,
@@ -45,13 +49,26 @@ struct MiniAppWindow {
     last_frame: Instant,
     click_start_pos: (f32, f32),
     click_start_time: Instant,
+    /// 触摸起点（页面坐标），touchmove/touchend 派发给 touchstart 命中的元素
+    touch_start: Option<(f32, f32)>,
     pending_navigation: Option<NavigationRequest>,
+    /// 上次通知 onPageScroll 的页面滚动位置
+    last_page_scroll: f32,
     interaction: InteractionManager,
     modifiers: winit::keyboard::ModifiersState,
     clipboard: Option<arboard::Clipboard>,
     toast: Option<ToastState>,
     loading: Option<LoadingState>,
     modal: Option<ModalState>,
+    action_sheet: Option<ActionSheetState>,
+    /// 下拉刷新开始时间，None 表示未在刷新
+    pull_refresh: Option<Instant>,
+    /// 栈顶页面尚未完成首次渲染，渲染后触发 onReady
+    page_ready_pending: bool,
+    /// 上次 present 之后是否重新渲染过
+    frame_rendered: bool,
+    /// 上次 present 时的 (滚动偏移, 窗口尺寸, 是否有 TabBar)，变化时整帧复制
+    last_present: Option<(i32, (u32, u32), bool)>,
 }
 
 impl MiniAppWindow {
@@ -59,8 +76,15 @@ impl MiniAppWindow {
         let mut app = MiniApp::new(LOGICAL_WIDTH, LOGICAL_HEIGHT)?;
         app.init()?;
         
+        // MINI_RECORD=<file> 时录制本次会话的输入事件，退出时写入文件
+        if std::env::var("MINI_RECORD").is_ok() {
+            app.start_recording();
+            log::info!("⏺️ Recording events");
+        }
+        
+        app.set_launch_options(LaunchOptions { path: "pages/index/index".into(), ..Default::default() })?;
         app.load_script(include_str!("../../sample-app/app.js"))?;
-        println!("📱 App.js loaded");
+        log::info!("📱 App.js loaded");
         
         let app_config: AppConfig = serde_json::from_str(include_str!("../../sample-app/app.json"))
             .map_err(|e| format!("Failed to parse app.json: {}", e))?;
@@ -81,11 +105,12 @@ impl MiniAppWindow {
             page_stack: Vec::new(), pages, app_config, custom_tabbar,
             mouse_pos: (0.0, 0.0), needs_redraw: true, scale_factor: 1.0,
             scroll: ScrollController::new(CONTENT_HEIGHT as f32, (LOGICAL_HEIGHT - if has_tabbar { TABBAR_HEIGHT } else { 0 }) as f32),
-            last_frame: now, click_start_pos: (0.0, 0.0), click_start_time: now,
-            pending_navigation: None, interaction: InteractionManager::new(),
+            last_frame: now, click_start_pos: (0.0, 0.0), click_start_time: now, touch_start: None,
+            pending_navigation: None, last_page_scroll: 0.0, interaction: InteractionManager::new(),
             modifiers: winit::keyboard::ModifiersState::empty(),
             clipboard: arboard::Clipboard::new().ok(),
-            toast: None, loading: None, modal: None,
+            toast: None, loading: None, modal: None, action_sheet: None, pull_refresh: None, page_ready_pending: false,
+            frame_rendered: false, last_present: None,
         };
         
         window.navigate_to("pages/index/index", HashMap::new())?;
@@ -104,54 +129,85 @@ impl MiniAppWindow {
         self.app_config.tab_bar.as_ref().map(|tb| tb.custom).unwrap_or(false) && self.custom_tabbar.is_some()
     }
 
+    /// 创建页面滚动控制器，触底距离和下拉刷新开关取页面配置，其次 app.json window 配置
+    fn create_page_scroll(&self, path: &str) -> ScrollController {
+        let has_tabbar = self.is_tabbar_page(path);
+        let mut scroll = ScrollController::new(CONTENT_HEIGHT as f32, (LOGICAL_HEIGHT - if has_tabbar { TABBAR_HEIGHT } else { 0 }) as f32);
+        let config = self.pages.get(path).map(|p| &p.config);
+        let distance = config.and_then(|c| c.on_reach_bottom_distance)
+            .unwrap_or(self.app_config.window.on_reach_bottom_distance);
+        scroll.set_reach_bottom_distance(distance);
+        let pull_down_refresh = config.and_then(|c| c.enable_pull_down_refresh)
+            .unwrap_or(self.app_config.window.enable_pull_down_refresh);
+        scroll.set_pull_down_refresh(pull_down_refresh);
+        scroll
+    }
+
     fn navigate_to(&mut self, path: &str, query: HashMap<String, String>) -> Result<(), String> {
         let path = path.trim_start_matches('/');
         let page_info = self.pages.get(path).ok_or_else(|| format!("Page not found: {}", path))?;
         
-        let mut wxml_parser = WxmlParser::new(&page_info.wxml);
+        let mut wxml_parser = WxmlParser::with_app_dir(&page_info.wxml, Path::new(APP_DIR), path);
         let wxml_nodes = remove_manual_tabbar(&wxml_parser.parse().map_err(|e| format!("WXML error: {}", e))?);
         
         let mut wxss_parser = WxssParser::new(&page_info.wxss);
         let stylesheet = wxss_parser.parse().map_err(|e| format!("WXSS error: {}", e))?;
         
-        self.app.load_script(&page_info.js)?;
-        let query_json = serde_json::to_string(&query).unwrap_or("{}".to_string());
-        self.app.eval(&format!("if(__currentPage && __currentPage.onLoad) __currentPage.onLoad({})", query_json)).ok();
-        self.app.eval("if(__currentPage && __currentPage.onShow) __currentPage.onShow()").ok();
+        let js = page_info.js.clone();
+        self.app.push_page(path, &js, &json!(query))?;
         print_js_output(&self.app);
         
-        self.page_stack.push(PageInstance { path: path.to_string(), query, wxml_nodes, stylesheet });
+        self.page_stack.push(PageInstance { path: path.to_string(), wxml_nodes, stylesheet });
         
-        let has_tabbar = self.is_tabbar_page(path);
-        self.scroll = ScrollController::new(CONTENT_HEIGHT as f32, (LOGICAL_HEIGHT - if has_tabbar { TABBAR_HEIGHT } else { 0 }) as f32);
+        self.scroll = self.create_page_scroll(path);
+        self.last_page_scroll = 0.0;
+        self.page_ready_pending = true;
         self.needs_redraw = true;
-        println!("✅ Page loaded: {}", path);
+        log::info!("✅ Page loaded: {}", path);
         Ok(())
     }
     
-    fn navigate_back(&mut self) -> Result<(), String> {
+    fn navigate_back(&mut self, delta: usize) -> Result<(), String> {
         if self.page_stack.len() <= 1 { return Ok(()); }
-        self.page_stack.pop();
+        let keep = self.page_stack.len().saturating_sub(delta).max(1);
+        self.page_stack.truncate(keep);
+        // 回到的页面保留原有实例和数据，只触发 onShow
+        self.app.pop_pages(delta)?;
+        print_js_output(&self.app);
         
         if let Some(page) = self.page_stack.last() {
-            let (path, query) = (page.path.clone(), page.query.clone());
-            if let Some(page_info) = self.pages.get(&path) {
-                self.app.load_script(&page_info.js)?;
-                self.app.eval(&format!("if(__currentPage && __currentPage.onLoad) __currentPage.onLoad({})", 
-                    serde_json::to_string(&query).unwrap_or("{}".to_string()))).ok();
-                print_js_output(&self.app);
-            }
-            let has_tabbar = self.is_tabbar_page(&path);
-            self.scroll = ScrollController::new(CONTENT_HEIGHT as f32, (LOGICAL_HEIGHT - if has_tabbar { TABBAR_HEIGHT } else { 0 }) as f32);
+            self.scroll = self.create_page_scroll(&page.path.clone());
+            self.last_page_scroll = 0.0;
         }
+        self.interaction.clear_page_state();
         self.needs_redraw = true;
         Ok(())
     }
     
     fn switch_tab(&mut self, path: &str) -> Result<(), String> {
-        self.page_stack.clear();
+        self.relaunch(path, HashMap::new())
+    }
+    
+    /// 关闭栈顶的 count 个页面后打开新页面，目标页面不存在时保留原页面
+    fn replace_pages(&mut self, count: usize, path: &str, query: HashMap<String, String>) -> Result<(), String> {
+        if !self.pages.contains_key(path.trim_start_matches('/')) {
+            return Err(format!("Page not found: {}", path));
+        }
+        self.app.unload_pages(count)?;
+        let keep = self.page_stack.len().saturating_sub(count);
+        self.page_stack.truncate(keep);
         self.interaction.clear_page_state();
-        self.navigate_to(path.trim_start_matches('/'), HashMap::new())
+        self.navigate_to(path, query)
+    }
+    
+    /// 关闭当前页面并打开新页面
+    fn redirect_to(&mut self, path: &str, query: HashMap<String, String>) -> Result<(), String> {
+        self.replace_pages(1, path, query)
+    }
+    
+    /// 关闭所有页面后打开新页面
+    fn relaunch(&mut self, path: &str, query: HashMap<String, String>) -> Result<(), String> {
+        self.replace_pages(self.page_stack.len(), path, query)
     }
     
     fn setup_canvas(&mut self, scale_factor: f64) {
@@ -162,13 +218,20 @@ impl MiniAppWindow {
         self.fixed_canvas = Some(Canvas::new(pw, (LOGICAL_HEIGHT as f64 * scale_factor) as u32));
         self.text_renderer = TextRenderer::load_system_font()
             .or_else(|_| TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf"))).ok();
+        let info = SystemInfo { pixel_ratio: scale_factor as f32, ..self.app.system_info().clone() };
+        if let Err(e) = self.app.set_system_info(info) { log::warn!("⚠️ Failed to update system info: {}", e); }
     }
     
     fn update_renderers(&mut self) {
         if let Some(page) = self.page_stack.last() {
-            self.renderer = Some(WxmlRenderer::new_with_scale(page.stylesheet.clone(), LOGICAL_WIDTH as f32, LOGICAL_HEIGHT as f32, self.scale_factor as f32));
+            let insets = self.app.system_info().safe_area_insets;
+            let mut renderer = WxmlRenderer::new_with_scale(page.stylesheet.clone(), LOGICAL_WIDTH as f32, LOGICAL_HEIGHT as f32, self.scale_factor as f32);
+            renderer.set_safe_area_insets(insets);
+            self.renderer = Some(renderer);
             if let Some(ref ct) = self.custom_tabbar {
-                self.tabbar_renderer = Some(WxmlRenderer::new_with_scale(ct.stylesheet.clone(), LOGICAL_WIDTH as f32, TABBAR_HEIGHT as f32, self.scale_factor as f32));
+                let mut tabbar_renderer = WxmlRenderer::new_with_scale(ct.stylesheet.clone(), LOGICAL_WIDTH as f32, TABBAR_HEIGHT as f32, self.scale_factor as f32);
+                tabbar_renderer.set_safe_area_insets(insets);
+                self.tabbar_renderer = Some(tabbar_renderer);
             }
         }
     }
@@ -180,6 +243,7 @@ impl MiniAppWindow {
         let viewport_height = (LOGICAL_HEIGHT - if has_tabbar { TABBAR_HEIGHT } else { 0 }) as f32;
         let scroll_offset = self.scroll.get_position();
         
+        self.frame_rendered = true;
         let mut content_height = 0.0f32;
         if let Some(canvas) = &mut self.canvas {
             canvas.clear(Color::from_hex(0xF5F5F5));
@@ -202,6 +266,24 @@ impl MiniAppWindow {
             }
         }
         
+        // scroll-into-view / scroll-top 属性变化：滚动对应的 scroll-view
+        if let Some(renderer) = &mut self.renderer {
+            if renderer.apply_scroll_requests(&mut self.interaction) {
+                self.needs_redraw = true;
+                if let Some(w) = &self.window { w.request_redraw(); }
+            }
+        }
+        
+        // focus 属性变化：聚焦 / 失焦对应的输入框，开启或关闭输入法
+        let focus_results = self.renderer.as_mut().map(|r| r.apply_focus_requests(&mut self.interaction)).unwrap_or_default();
+        for r in &focus_results {
+            handle_interaction_result(r, self.window.as_ref(), self.renderer.as_ref(), &mut self.app, &mut self.clipboard, self.scroll.get_position(), self.scale_factor);
+        }
+        if !focus_results.is_empty() {
+            self.needs_redraw = true;
+            if let Some(w) = &self.window { w.request_redraw(); }
+        }
+        
         if let Some(page) = self.page_stack.last() {
             if let (Some(fc), Some(r)) = (&mut self.fixed_canvas, &mut self.renderer) {
                 fc.clear(Color::new(0, 0, 0, 0));
@@ -238,18 +320,51 @@ impl MiniAppWindow {
         let page = match self.page_stack.last() { Some(p) => p, None => return };
         let has_tabbar = self.is_tabbar_page(&page.path);
         let (toast_state, loading_state, modal_state) = (self.toast.clone(), self.loading.clone(), self.modal.clone());
+        let action_sheet_state = self.action_sheet.clone();
+        
+        let sf = self.scale_factor as f32;
+        let scroll_px = (self.scroll.get_position() * sf) as i32;
+        let tabbar_h = if has_tabbar { (TABBAR_HEIGHT as f64 * self.scale_factor) as u32 } else { 0 };
+        // toast / loading / modal / action sheet 和下拉刷新指示器每帧叠加绘制在缓冲区上，显示时整帧更新
+        let overlays = toast_state.as_ref().map(|t| t.visible).unwrap_or(false) || loading_state.as_ref().map(|l| l.visible).unwrap_or(false)
+            || modal_state.as_ref().map(|m| m.visible).unwrap_or(false) || action_sheet_state.as_ref().map(|a| a.visible).unwrap_or(false)
+            || self.pull_refresh.is_some() || self.scroll.pull_distance() != 0.0;
+        let frame_rendered = std::mem::take(&mut self.frame_rendered);
         
         if let (Some(window), Some(surface)) = (&self.window, &mut self.surface) {
             let size = window.inner_size();
+            let frame_key = (scroll_px, (size.width, size.height), has_tabbar);
+            let same_frame = self.last_present == Some(frame_key) && !overlays;
+            // 没有重新渲染也没有滚动：画面不变，不需要 present
+            if same_frame && !frame_rendered { return; }
+            self.last_present = Some(frame_key);
+            
             if let (Some(w), Some(h)) = (NonZeroU32::new(size.width), NonZeroU32::new(size.height)) {
                 surface.resize(w, h).ok();
                 if let Ok(mut buffer) = surface.buffer_mut() {
+                    // 缓冲区保留着上一帧（age == 1）且没有滚动时，只复制渲染器报告的变化区域所在的行
+                    let dirty_rows = if same_frame && buffer.age() == 1 {
+                        self.renderer.as_ref().and_then(|r| r.dirty_rects())
+                            .map(|rects| dirty_rows_for(rects, sf, scroll_px, size.height - tabbar_h))
+                    } else {
+                        None
+                    };
                     present_to_buffer(&mut buffer, size.width, size.height, canvas, self.fixed_canvas.as_ref(), self.tabbar_canvas.as_ref(),
-                        (self.scroll.get_position() * self.scale_factor as f32) as i32, has_tabbar,
-                        if has_tabbar { (TABBAR_HEIGHT as f64 * self.scale_factor) as u32 } else { 0 });
-                    render_ui_overlay(&mut buffer, size.width, size.height, self.scale_factor as f32, self.last_frame,
-                        &toast_state, &loading_state, &modal_state, self.text_renderer.as_ref());
-                    buffer.present().ok();
+                        scroll_px, has_tabbar, tabbar_h, dirty_rows.as_deref());
+                    render_pull_refresh_indicator(&mut buffer, size.width, size.height, sf,
+                        self.scroll.pull_distance(), self.pull_refresh);
+                    render_ui_overlay(&mut buffer, size.width, size.height, sf,
+                        &toast_state, &loading_state, &modal_state, &action_sheet_state, self.text_renderer.as_ref());
+                    match dirty_rows {
+                        Some(rows) => {
+                            let damage: Vec<softbuffer::Rect> = rows.iter().filter_map(|&(start, end)| Some(softbuffer::Rect {
+                                x: 0, y: start, width: w, height: NonZeroU32::new(end - start)?,
+                            })).collect();
+                            if damage.is_empty() { return; }
+                            buffer.present_with_damage(&damage).ok();
+                        }
+                        None => { buffer.present().ok(); }
+                    }
                 }
             }
         }
@@ -257,6 +372,11 @@ impl MiniAppWindow {
     
     fn handle_click(&mut self, x: f32, y: f32) {
         if self.modal.as_ref().map(|m| m.visible).unwrap_or(false) { self.handle_modal_click(x, y); return; }
+        if self.action_sheet.as_ref().map(|a| a.visible).unwrap_or(false) {
+            self.handle_action_sheet_press(x, y);
+            self.handle_action_sheet_release(x, y);
+            return;
+        }
         if self.loading.as_ref().map(|l| l.visible).unwrap_or(false) { return; }
         
         let page = match self.page_stack.last() { Some(p) => p, None => return };
@@ -269,7 +389,7 @@ impl MiniAppWindow {
             } else {
                 self.app_config.tab_bar.as_ref().and_then(|tb| click::handle_native_tabbar_click_wrapper(x, tb, &page.path))
             };
-            if let Some(n) = nav { self.pending_navigation = Some(n); if let Some(w) = &self.window { w.request_redraw(); } }
+            if let Some(n) = nav { self.request_host_navigation(n); if let Some(w) = &self.window { w.request_redraw(); } }
         } else {
             if let Some(nav) = click::handle_content_click(x, y, &self.scroll, has_tabbar, &mut self.interaction,
                 self.renderer.as_ref(), &mut self.app, self.scale_factor, self.text_renderer.as_ref(), self.window.as_ref(), &mut self.clipboard) {
@@ -279,6 +399,33 @@ impl MiniAppWindow {
         }
     }
     
+    /// 长按可选中文本：全选并复制到剪贴板，提示“内容已复制”
+    fn handle_long_press(&mut self, x: f32, y: f32) {
+        if click::handle_content_long_press(x, y, &self.scroll, &mut self.interaction, self.renderer.as_ref(),
+            &mut self.app, self.scale_factor, self.window.as_ref(), &mut self.clipboard) {
+            self.app.eval("wx.showToast({ title: '内容已复制', icon: 'none' })").ok();
+            self.needs_redraw = true;
+        }
+    }
+    
+    /// 派发触摸事件，payload 中的 touches 同时携带视口坐标 (clientX/Y) 和页面坐标 (pageX/Y)
+    fn dispatch_touch_event(&mut self, event_type: &str, x: f32, y: f32) {
+        let (start_x, start_y) = match self.touch_start { Some(p) => p, None => return };
+        let renderer = match &self.renderer { Some(r) => r, None => return };
+        
+        let touch = json!({ "identifier": 0, "clientX": x, "clientY": y, "pageX": x, "pageY": y + self.scroll.get_position() });
+        let touches = if event_type == "touchend" { json!([]) } else { json!([touch.clone()]) };
+        let extra = json!({ "type": event_type, "touches": touches, "changedTouches": [touch] });
+        
+        let calls: Vec<(String, serde_json::Value)> = renderer.hit_test_path(start_x, start_y, event_type).iter()
+            .map(|b| (b.handler.clone(), serde_json::to_value(&b.data).unwrap_or_default()))
+            .collect();
+        
+        if calls.is_empty() { return; }
+        for (handler, data) in &calls { self.app.dispatch_page_event(handler, data, &extra).ok(); }
+        self.needs_redraw = true;
+    }
+    
     fn handle_modal_press(&mut self, x: f32, y: f32) -> bool {
         let modal = match &self.modal { Some(m) if m.visible => m, _ => return false };
         let layout = click::calculate_modal_layout(modal, self.scale_factor as f32, self.text_renderer.as_ref());
@@ -298,10 +445,11 @@ impl MiniAppWindow {
         let layout = click::calculate_modal_layout(modal, self.scale_factor as f32, self.text_renderer.as_ref());
         if let Some(btn) = click::detect_modal_button(x, y, &layout, modal.show_cancel) {
             if pressed.as_deref() == Some(&btn) {
-                let code = if btn == "cancel" { "if(__modalCallback) __modalCallback({ confirm: false, cancel: true })" }
-                           else { "if(__modalCallback) __modalCallback({ confirm: true, cancel: false })" };
-                self.app.eval(code).ok();
                 self.modal = None;
+                if let Err(e) = self.app.resolve_modal(btn == "confirm") { log::error!("❌ Modal callback failed: {}", e); }
+                // 回调中弹出的 toast / modal 立即显示
+                evt::process_ui_events(&mut self.app, &mut self.toast, &mut self.loading, &mut self.modal, &mut self.action_sheet, &mut self.pull_refresh);
+                print_js_output(&self.app);
             }
         }
         self.needs_redraw = true;
@@ -310,13 +458,55 @@ impl MiniAppWindow {
     
     fn handle_modal_click(&mut self, x: f32, y: f32) { self.handle_modal_release(x, y); }
     
+    fn handle_action_sheet_press(&mut self, x: f32, y: f32) {
+        let sheet = match &mut self.action_sheet { Some(a) if a.visible => a, _ => return };
+        sheet.pressed = click::detect_action_sheet_item(x, y, sheet.items.len());
+        self.needs_redraw = true;
+        if let Some(w) = &self.window { w.request_redraw(); }
+    }
+    
+    fn handle_action_sheet_release(&mut self, x: f32, y: f32) {
+        let sheet = match &mut self.action_sheet { Some(a) if a.visible => a, _ => return };
+        let pressed = sheet.pressed.take();
+        let count = sheet.items.len();
+        let hit = click::detect_action_sheet_item(x, y, count);
+        // 点击遮罩等同于取消；按下与抬起不在同一行时不响应
+        let result = match (pressed, hit) {
+            (None, None) => Some(None),
+            (Some(p), Some(h)) if p == h => Some(if h < count { Some(h) } else { None }),
+            _ => None,
+        };
+        if let Some(tap_index) = result {
+            self.action_sheet = None;
+            if let Err(e) = self.app.resolve_action_sheet(tap_index) { log::error!("❌ ActionSheet callback failed: {}", e); }
+            evt::process_ui_events(&mut self.app, &mut self.toast, &mut self.loading, &mut self.modal, &mut self.action_sheet, &mut self.pull_refresh);
+            print_js_output(&self.app);
+        }
+        self.needs_redraw = true;
+        if let Some(w) = &self.window { w.request_redraw(); }
+    }
+    
+    /// 宿主发起的导航（tabBar 点击、返回键）需要录制，页面脚本发起的导航回放触发它的事件即可
+    fn request_host_navigation(&mut self, nav: NavigationRequest) {
+        let (nav_type, url) = nav.kind_and_url();
+        self.app.record_navigation(nav_type, url);
+        self.pending_navigation = Some(nav);
+    }
+    
     fn process_navigation(&mut self) {
         if let Some(nav) = self.pending_navigation.take() {
-            match nav {
-                NavigationRequest::NavigateTo { url } => { let (p, q) = parse_url(&url); self.navigate_to(&p, q).ok(); }
-                NavigationRequest::NavigateBack => { self.navigate_back().ok(); }
-                NavigationRequest::SwitchTab { url } => { let (p, _) = parse_url(&url); self.switch_tab(&p).ok(); }
-            }
+            self.pull_refresh = None;
+            // 页面脚本中的相对路径相对于当前页面解析
+            let current = self.page_stack.last().map(|p| p.path.clone()).unwrap_or_default();
+            let target = |url: &str| parse_url(&resolve_url(&current, url));
+            let result = match nav {
+                NavigationRequest::NavigateTo { url } => { let (p, q) = target(&url); self.navigate_to(&p, q) }
+                NavigationRequest::RedirectTo { url } => { let (p, q) = target(&url); self.redirect_to(&p, q) }
+                NavigationRequest::NavigateBack { delta } => self.navigate_back(delta),
+                NavigationRequest::SwitchTab { url } => { let (p, _) = target(&url); self.switch_tab(&p) }
+                NavigationRequest::ReLaunch { url } => { let (p, q) = target(&url); self.relaunch(&p, q) }
+            };
+            if let Err(e) = result { log::warn!("⚠️ Navigation failed: {}", e); }
             self.update_renderers();
         }
     }
@@ -327,10 +517,33 @@ impl MiniAppWindow {
         self.last_frame = now;
         
         let (animating, event) = self.scroll.update_with_events(dt);
-        if let Some(e) = event { evt::handle_scroll_event(e, &mut self.app); self.needs_redraw = true; }
+        // 页面滚动位置变化（拖动、滚轮、惯性）时调用 onPageScroll
+        let scroll_top = self.scroll.get_position();
+        if scroll_top != self.last_page_scroll {
+            self.last_page_scroll = scroll_top;
+            if let Err(e) = self.app.on_page_scroll(scroll_top) { log::warn!("⚠️ onPageScroll failed: {}", e); }
+        }
+        if let Some(e) = event {
+            if e == ScrollEvent::PullDownRefresh { self.pull_refresh = Some(Instant::now()); }
+            evt::handle_scroll_event(e, &mut self.app);
+            self.needs_redraw = true;
+        }
         
         let mut changed = animating;
         for c in self.interaction.scroll_controllers.values_mut() { if c.update(dt) { changed = true; } }
+        
+        // movable-view 惯性/回弹，位置变化时触发 bindchange
+        let (movable_animating, movable_changes) = self.interaction.update_movables(dt);
+        for r in &movable_changes {
+            handle_interaction_result(r, self.window.as_ref(), self.renderer.as_ref(), &mut self.app, &mut self.clipboard, self.scroll.get_position(), self.scale_factor);
+        }
+        if movable_animating || !movable_changes.is_empty() { self.needs_redraw = true; changed = true; }
+        
+        // 推进 animation 属性绑定的动画，播放期间（含最后一帧）持续重绘
+        if let Some(r) = &mut self.renderer {
+            let was_running = r.has_running_animations();
+            if r.advance_animations(dt * 1000.0) || was_running { self.needs_redraw = true; changed = true; }
+        }
         if changed { if let Some(w) = &self.window { w.request_redraw(); } }
     }
 }
@@ -349,19 +562,39 @@ impl ApplicationHandler for MiniAppWindow {
         self.window = Some(window);
         self.render();
         self.present();
-        println!("\n🎮 Ready!\n");
+        log::info!("🎮 Ready!");
     }
     
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         match event {
-            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::CloseRequested => {
+                if let (Ok(path), Some(log)) = (std::env::var("MINI_RECORD"), self.app.stop_recording()) {
+                    match log.to_json().and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string())) {
+                        Ok(()) => log::info!("⏺️ Saved {} events to {}", log.len(), path),
+                        Err(e) => log::error!("❌ Failed to save event log: {}", e),
+                    }
+                }
+                event_loop.exit();
+            }
             WindowEvent::ModifiersChanged(m) => self.modifiers = m.state(),
             
+            // 窗口获得/失去焦点、被遮挡视为切到前台/后台
+            WindowEvent::Focused(focused) => {
+                let r = if focused { self.app.on_app_show() } else { self.app.on_app_hide() };
+                if let Err(e) = r { log::error!("❌ App lifecycle error: {}", e); }
+                print_js_output(&self.app);
+            }
+            WindowEvent::Occluded(occluded) => {
+                let r = if occluded { self.app.on_app_hide() } else { self.app.on_app_show() };
+                if let Err(e) = r { log::error!("❌ App lifecycle error: {}", e); }
+                print_js_output(&self.app);
+            }
+            
             WindowEvent::KeyboardInput { event, .. } => {
                 let (nr, pn, ex) = evt::handle_keyboard_event(event, self.modifiers, &mut self.interaction, &mut self.clipboard,
                     self.window.as_ref(), self.renderer.as_ref(), &mut self.app, &mut self.scroll, self.scale_factor);
                 if ex { event_loop.exit(); }
-                if let Some(n) = pn { self.pending_navigation = Some(n); }
+                if let Some(n) = pn { self.request_host_navigation(n); }
                 if nr { self.needs_redraw = true; if let Some(w) = &self.window { w.request_redraw(); } }
             }
             
@@ -383,6 +616,15 @@ impl ApplicationHandler for MiniAppWindow {
             WindowEvent::CursorMoved { position, .. } => {
                 let (x, y) = (position.x as f32 / self.scale_factor as f32, position.y as f32 / self.scale_factor as f32);
                 self.mouse_pos = (x, y);
+                self.interaction.pointer_pos = Some((x, y));
+                if self.renderer.as_ref().map(|r| r.has_pseudo_class_rules()).unwrap_or(false) { self.needs_redraw = true; }
+                if self.touch_start.is_some() { self.dispatch_touch_event("touchmove", x, y); }
+                // 按下后滑动超出点击范围时取消 hover-class
+                let (dx, dy) = ((x - self.click_start_pos.0).abs(), (y - self.click_start_pos.1).abs());
+                if self.interaction.pointer_down && (dx >= 10.0 || dy >= 10.0) && self.interaction.hover_press().is_some() {
+                    self.interaction.cancel_hover();
+                    self.needs_redraw = true;
+                }
                 if evt::handle_cursor_moved(x, y, &mut self.interaction, &mut self.scroll, self.text_renderer.as_ref(),
                     self.window.as_ref(), self.renderer.as_ref(), &mut self.app, &mut self.clipboard, self.scale_factor) {
                     self.needs_redraw = true;
@@ -400,12 +642,15 @@ impl ApplicationHandler for MiniAppWindow {
             WindowEvent::MouseInput { state, button: MouseButton::Left, .. } => {
                 let ts = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis() as u64;
                 let (x, y) = self.mouse_pos;
+                self.interaction.pointer_down = state == ElementState::Pressed;
+                if self.renderer.as_ref().map(|r| r.has_pseudo_class_rules()).unwrap_or(false) { self.needs_redraw = true; }
                 
                 if state == ElementState::Pressed {
                     self.click_start_pos = self.mouse_pos;
                     self.click_start_time = Instant::now();
                     
                     if self.modal.as_ref().map(|m| m.visible).unwrap_or(false) { self.handle_modal_press(x, y); return; }
+                    if self.action_sheet.as_ref().map(|a| a.visible).unwrap_or(false) { self.handle_action_sheet_press(x, y); return; }
                     if self.loading.as_ref().map(|l| l.visible).unwrap_or(false) { return; }
                     
                     let has_tabbar = self.page_stack.last().map(|p| self.is_tabbar_page(&p.path)).unwrap_or(false);
@@ -414,6 +659,11 @@ impl ApplicationHandler for MiniAppWindow {
                     
                     let actual_y = y + self.scroll.get_position();
                     
+                    self.touch_start = Some((x, actual_y));
+                    self.dispatch_touch_event("touchstart", x, y);
+                    self.interaction.press_hover(x, y);
+                    self.needs_redraw = true;
+                    
                     // 输入框内点击
                     if let Some(focused) = &self.interaction.focused_input {
                         let b = focused.bounds;
@@ -421,8 +671,8 @@ impl ApplicationHandler for MiniAppWindow {
                            (x >= b.x && x <= b.x + b.width && actual_y >= b.y && actual_y <= b.y + b.height) {
                             if let Some(tr) = &self.text_renderer {
                                 let sf = self.scale_factor as f32;
-                                let cw: Vec<f32> = focused.value.chars().map(|c| tr.measure_text(&c.to_string(), 16.0 * sf)).collect();
-                                let cp = mini_render::ui::interaction::calculate_cursor_position(&focused.value, &cw, (x - b.x) * sf, 12.0 * sf, focused.text_offset);
+                                let cy = if actual_y >= b.y && actual_y <= b.y + b.height { actual_y } else { y };
+                                let cp = focused.cursor_from_point((x - b.x) * sf, (cy - b.y) * sf, sf, |c| tr.measure_char(c, 16.0 * sf));
                                 self.interaction.prepare_text_selection(cp);
                                 self.needs_redraw = true;
                                 if let Some(w) = &self.window { w.request_redraw(); }
@@ -431,6 +681,9 @@ impl ApplicationHandler for MiniAppWindow {
                         }
                     }
                     
+                    // 可选中文本上按下：拖动选择文本而不是滚动页面
+                    if self.interaction.begin_text_drag(x, y, self.scroll.get_position()) { return; }
+                    
                     // 交互元素
                     if let Some(el) = self.interaction.hit_test(x, y).or_else(|| self.interaction.hit_test(x, actual_y)).cloned() {
                         use mini_render::ui::interaction::InteractionType;
@@ -449,9 +702,12 @@ impl ApplicationHandler for MiniAppWindow {
                                 self.needs_redraw = true;
                                 if let Some(w) = &self.window { w.request_redraw(); }
                             }
+                            InteractionType::Movable if !el.disabled => {
+                                if self.interaction.begin_movable_drag(&el.id, x, y, ts) { return; }
+                            }
                             InteractionType::ScrollArea => {
                                 if let Some(c) = self.interaction.get_scroll_controller_mut(&el.id) {
-                                    c.begin_drag(y, ts);
+                                    c.begin_drag(if c.is_horizontal() { x } else { y }, ts);
                                     self.interaction.dragging_scroll_area = Some(el.id.clone());
                                     return;
                                 }
@@ -463,15 +719,29 @@ impl ApplicationHandler for MiniAppWindow {
                     if !self.interaction.is_dragging_slider() { self.scroll.begin_drag(y, ts); }
                 } else {
                     // Released
+                    self.dispatch_touch_event("touchend", x, y);
+                    self.touch_start = None;
+                    
                     if self.modal.as_ref().map(|m| m.visible && m.pressed_button.is_some()).unwrap_or(false) {
                         self.handle_modal_release(x, y);
                         return;
                     }
+                    if self.action_sheet.as_ref().map(|a| a.visible).unwrap_or(false) {
+                        self.handle_action_sheet_release(x, y);
+                        return;
+                    }
                     
                     self.interaction.clear_button_pressed();
+                    self.interaction.release_hover();
                     let was_sel = self.interaction.is_dragging_selection();
                     self.interaction.end_text_selection();
                     if was_sel { self.needs_redraw = true; if let Some(w) = &self.window { w.request_redraw(); } return; }
+                    if self.interaction.end_text_drag() { self.needs_redraw = true; if let Some(w) = &self.window { w.request_redraw(); } return; }
+                    
+                    if self.interaction.end_movable_drag() {
+                        self.needs_redraw = true;
+                        if let Some(w) = &self.window { w.request_redraw(); }
+                    }
                     
                     if let Some(id) = self.interaction.dragging_scroll_area.take() {
                         if let Some(c) = self.interaction.get_scroll_controller_mut(&id) { c.end_drag(); }
@@ -485,7 +755,9 @@ impl ApplicationHandler for MiniAppWindow {
                     
                     let anim = self.scroll.end_drag();
                     let (dx, dy) = ((x - self.click_start_pos.0).abs(), (y - self.click_start_pos.1).abs());
-                    if dx < 10.0 && dy < 10.0 && self.click_start_time.elapsed().as_millis() < 300 { self.handle_click(x, y); }
+                    let held_ms = self.click_start_time.elapsed().as_millis();
+                    if dx < 10.0 && dy < 10.0 && held_ms < 300 { self.handle_click(x, y); }
+                    else if dx < 10.0 && dy < 10.0 && held_ms >= 500 { self.handle_long_press(x, y); }
                     
                     self.needs_redraw = true;
                     if let Some(w) = &self.window { w.request_redraw(); }
@@ -495,25 +767,49 @@ impl ApplicationHandler for MiniAppWindow {
             
             WindowEvent::RedrawRequested => {
                 self.app.update().ok();
+                // 帧回调通常会 setData 或绘制 canvas，执行后需要重绘
+                if self.app.has_animation_frames() {
+                    self.app.flush_animation_frames().ok();
+                    self.needs_redraw = true;
+                }
                 print_js_output(&self.app);
                 
-                if evt::process_ui_events(&mut self.app, &mut self.toast, &mut self.loading, &mut self.modal) { self.needs_redraw = true; }
-                if evt::update_toast_timeout(&mut self.toast) { self.needs_redraw = true; }
+                if evt::process_ui_events(&mut self.app, &mut self.toast, &mut self.loading, &mut self.modal, &mut self.action_sheet, &mut self.pull_refresh) { self.needs_redraw = true; }
                 
                 self.update_scroll();
+                // 定时器、网络回调等非点击路径中调用的 wx.navigateTo 等
+                if self.pending_navigation.is_none() {
+                    self.pending_navigation = check_navigation(&mut self.app);
+                }
                 self.process_navigation();
                 
+                // 网络图片下载完成后重绘
+                if mini_render::renderer::components::take_images_loaded() {
+                    if let Some(r) = &mut self.renderer { r.mark_content_dirty(); }
+                    self.needs_redraw = true;
+                }
+                
                 let scrolling = self.scroll.is_animating() || self.scroll.is_dragging;
                 let sv_scroll = self.interaction.scroll_controllers.values().any(|c| c.is_animating() || c.is_dragging);
-                if self.needs_redraw || mini_render::renderer::components::has_playing_video() || sv_scroll || self.interaction.has_focused_input() || scrolling {
+                if self.needs_redraw || mini_render::renderer::components::has_playing_video() ||
+                   mini_render::renderer::components::has_playing_animation() || sv_scroll || self.interaction.has_focused_input() || scrolling {
                     self.render();
                     self.needs_redraw = false;
+                    if self.page_ready_pending {
+                        self.page_ready_pending = false;
+                        self.app.page_ready().ok();
+                        print_js_output(&self.app);
+                    }
                 }
                 self.present();
                 
                 if scrolling || sv_scroll || self.interaction.has_focused_input() || self.app.has_active_timers() ||
+                   self.app.has_animation_frames() || self.app.has_pending_requests() ||
                    self.toast.as_ref().map(|t| t.visible).unwrap_or(false) || self.loading.as_ref().map(|l| l.visible).unwrap_or(false) ||
-                   self.modal.as_ref().map(|m| m.visible).unwrap_or(false) || mini_render::renderer::components::has_playing_video() {
+                   self.modal.as_ref().map(|m| m.visible).unwrap_or(false) || self.pull_refresh.is_some() ||
+                   mini_render::renderer::components::has_playing_video() ||
+                   mini_render::renderer::components::has_playing_animation() ||
+                   mini_render::renderer::components::has_loading_images() {
                     if let Some(w) = &self.window { w.request_redraw(); }
                 }
             }
@@ -523,7 +819,11 @@ impl ApplicationHandler for MiniAppWindow {
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Mini App Engine\n");
+    // 默认输出 info 级别，可用 RUST_LOG 调整（如 RUST_LOG=mini_render=debug）
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .format(|buf, record| writeln!(buf, "{}", record.args()))
+        .init();
+    log::info!("🚀 Mini App Engine");
     let event_loop = EventLoop::new()?;
     event_loop.set_control_flow(ControlFlow::Poll);
     event_loop.run_app(&mut MiniAppWindow::new()?)?;
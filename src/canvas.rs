@@ -1,12 +1,31 @@
 //! Canvas 画布模块 - 核心渲染接口
 
-use crate::{Color, Paint, PaintStyle, Path, Point, Rect};
+use crate::stroke::{dash_polyline, stroke_outline, StrokeStyle};
+use crate::{Color, FillRule, Matrix, Paint, PaintStyle, Path, Point, Rect, RingSegment, StrokeJoin};
+
+/// 裁剪形状（设备坐标）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipShape {
+    Rect(Rect),
+    /// 圆角矩形，圆角半径依次为左上、右上、右下、左下
+    RoundRect(Rect, [f32; 4]),
+}
+
+impl ClipShape {
+    fn bounds(&self) -> Rect {
+        match self {
+            ClipShape::Rect(r) | ClipShape::RoundRect(r, _) => *r,
+        }
+    }
+}
 
 /// 画布状态
 #[derive(Clone)]
 struct CanvasState {
     clip_rect: Option<Rect>,
+    clip_round_rects: Vec<(Rect, [f32; 4])>,
     translation: (f32, f32),
+    matrix: Matrix,
 }
 
 /// 画布 - 主要渲染接口
@@ -14,8 +33,15 @@ pub struct Canvas {
     width: u32,
     height: u32,
     pixels: Vec<Color>,
+    /// 像素缓冲左上角的设备坐标：new_layer_in 创建的局部图层只覆盖画布的一块区域，其余画布为 (0, 0)
+    origin: (i32, i32),
     clip_rect: Option<Rect>,
+    /// 圆角裁剪（与 clip_rect 同时生效），每行的可见区间再按圆角收窄
+    clip_round_rects: Vec<(Rect, [f32; 4])>,
     translation: (f32, f32),
+    /// 变换矩阵，作用于平移之前的坐标：设备坐标 = translation + matrix * p
+    /// 只影响矢量图元（矩形、圆、线、路径）；文本和图片按像素绘制，需要变换时先画到图层再用 draw_layer 合成
+    matrix: Matrix,
     state_stack: Vec<CanvasState>,
 }
 
@@ -25,17 +51,56 @@ impl Canvas {
             width,
             height,
             pixels: vec![Color::TRANSPARENT; (width * height) as usize],
+            origin: (0, 0),
             clip_rect: None,
+            clip_round_rects: Vec::new(),
             translation: (0.0, 0.0),
+            matrix: Matrix::IDENTITY,
             state_stack: Vec::new(),
         }
     }
 
+    /// 创建与当前画布尺寸、裁剪区域和平移一致的透明图层
+    pub fn new_layer(&self) -> Canvas {
+        let mut layer = Canvas::new(self.width, self.height);
+        layer.origin = self.origin;
+        layer.clip_rect = self.clip_rect;
+        layer.clip_round_rects = self.clip_round_rects.clone();
+        layer.translation = self.translation;
+        layer
+    }
+
+    /// 同 new_layer，但像素缓冲只覆盖 rect（用户坐标，只计平移）与画布、裁剪区域的交集，
+    /// 图层内仍按画布的设备坐标绘制，超出范围的部分被丢弃，合成时也只处理这块区域
+    pub fn new_layer_in(&self, rect: &Rect) -> Canvas {
+        let (left, top, right, bottom) = self.device_bounds();
+        let (tx, ty) = self.translation;
+        let mut x0 = ((rect.x + tx).floor() as i32).max(left);
+        let mut y0 = ((rect.y + ty).floor() as i32).max(top);
+        let mut x1 = ((rect.right() + tx).ceil() as i32).min(right);
+        let mut y1 = ((rect.bottom() + ty).ceil() as i32).min(bottom);
+        if let Some(clip) = &self.clip_rect {
+            x0 = x0.max(clip.x.floor() as i32);
+            y0 = y0.max(clip.y.floor() as i32);
+            x1 = x1.min(clip.right().ceil() as i32);
+            y1 = y1.min(clip.bottom().ceil() as i32);
+        }
+        // 交集为空时保留 1 像素，图层内的绘制照常进行（落在范围外的像素被丢弃）
+        let mut layer = Canvas::new((x1 - x0).max(1) as u32, (y1 - y0).max(1) as u32);
+        layer.origin = (x0, y0);
+        layer.clip_rect = self.clip_rect;
+        layer.clip_round_rects = self.clip_round_rects.clone();
+        layer.translation = self.translation;
+        layer
+    }
+
     /// 保存当前状态（裁剪区域和变换）
     pub fn save(&mut self) {
         self.state_stack.push(CanvasState {
             clip_rect: self.clip_rect,
+            clip_round_rects: self.clip_round_rects.clone(),
             translation: self.translation,
+            matrix: self.matrix,
         });
     }
 
@@ -43,7 +108,9 @@ impl Canvas {
     pub fn restore(&mut self) {
         if let Some(state) = self.state_stack.pop() {
             self.clip_rect = state.clip_rect;
+            self.clip_round_rects = state.clip_round_rects;
             self.translation = state.translation;
+            self.matrix = state.matrix;
         }
     }
 
@@ -53,10 +120,58 @@ impl Canvas {
         self.translation.1 += dy;
     }
 
+    /// 在当前变换之前追加变换（新变换先作用于图元坐标）
+    pub fn concat(&mut self, m: &Matrix) {
+        self.matrix = self.matrix.concat(m);
+    }
+
+    /// 当前变换矩阵
+    pub fn matrix(&self) -> Matrix {
+        self.matrix
+    }
+
+    /// 当前平移（作用在变换矩阵之后）
+    pub fn translation(&self) -> (f32, f32) {
+        self.translation
+    }
+
+    pub fn reset_matrix(&mut self) {
+        self.matrix = Matrix::IDENTITY;
+    }
+
+    /// 用户坐标 -> 设备坐标
+    #[inline]
+    fn map(&self, x: f32, y: f32) -> (f32, f32) {
+        let p = self.matrix.map_point(x, y);
+        (p.x + self.translation.0, p.y + self.translation.1)
+    }
+
+    /// 用户坐标下的矩形映射到设备坐标后的包围盒
+    pub fn map_rect(&self, rect: &Rect) -> Rect {
+        let r = self.matrix.map_rect(rect);
+        Rect::new(r.x + self.translation.0, r.y + self.translation.1, r.width, r.height)
+    }
+
     pub fn width(&self) -> u32 { self.width }
     pub fn height(&self) -> u32 { self.height }
+
+    /// 像素缓冲左上角的设备坐标（pixels() 中第一个像素的位置）
+    pub fn origin(&self) -> (i32, i32) { self.origin }
+
+    /// 像素缓冲覆盖的设备坐标范围 (left, top, right, bottom)，右、下边界不含
+    #[inline]
+    fn device_bounds(&self) -> (i32, i32, i32, i32) {
+        let (x, y) = self.origin;
+        (x, y, x + self.width as i32, y + self.height as i32)
+    }
+
+    /// 设备坐标在像素缓冲中的下标，调用方保证坐标在 device_bounds 内
+    #[inline]
+    fn index(&self, x: i32, y: i32) -> usize {
+        ((y - self.origin.1) as u32 * self.width + (x - self.origin.0) as u32) as usize
+    }
     
-    /// 获取像素数据引用
+    /// 获取像素数据引用（逐行存放，第一个像素对应图层左上角）
     pub fn pixels(&self) -> &[Color] {
         &self.pixels
     }
@@ -100,13 +215,32 @@ impl Canvas {
     /// 重置裁剪区域
     pub fn reset_clip(&mut self) {
         self.clip_rect = None;
+        self.clip_round_rects.clear();
+    }
+
+    /// 保存状态并叠加裁剪区域，与 pop_clip 成对使用
+    pub fn push_clip(&mut self, shape: ClipShape) {
+        self.save();
+        self.clip_rect(shape.bounds());
+        if let ClipShape::RoundRect(rect, radii) = shape {
+            if radii.iter().any(|r| *r > 0.0) {
+                self.clip_round_rects.push((rect, radii));
+            }
+        }
+    }
+
+    /// 恢复 push_clip 之前的裁剪区域
+    pub fn pop_clip(&mut self) {
+        self.restore();
     }
 
     /// 获取像素
     #[inline]
     pub fn get_pixel(&self, x: u32, y: u32) -> Color {
-        if x < self.width && y < self.height {
-            self.pixels[(y * self.width + x) as usize]
+        let (left, top, right, bottom) = self.device_bounds();
+        let (x, y) = (x as i32, y as i32);
+        if x >= left && y >= top && x < right && y < bottom {
+            self.pixels[self.index(x, y)]
         } else {
             Color::TRANSPARENT
         }
@@ -115,7 +249,8 @@ impl Canvas {
     /// 设置像素（带 alpha 混合）
     #[inline]
     pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) {
-        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+        let (left, top, right, bottom) = self.device_bounds();
+        if x < left || y < top || x >= right || y >= bottom {
             return;
         }
 
@@ -126,8 +261,11 @@ impl Canvas {
                 return;
             }
         }
+        if !self.clip_round_rects.is_empty() && self.row_span(y, x, x + 1).is_none() {
+            return;
+        }
 
-        let idx = (y as u32 * self.width + x as u32) as usize;
+        let idx = self.index(x, y);
         if color.a == 255 {
             self.pixels[idx] = color;
         } else if color.a > 0 {
@@ -135,6 +273,60 @@ impl Canvas {
         }
     }
 
+    /// 计算第 y 行在画布和裁剪区域内的可见区间 [x0, x1)，不可见时返回 None
+    #[inline]
+    fn row_span(&self, y: i32, x0: i32, x1: i32) -> Option<(i32, i32)> {
+        let (left, top, right, bottom) = self.device_bounds();
+        if y < top || y >= bottom {
+            return None;
+        }
+        let (mut x0, mut x1) = (x0.max(left), x1.min(right));
+        if let Some(clip) = &self.clip_rect {
+            if y < clip.y as i32 || y >= clip.bottom() as i32 {
+                return None;
+            }
+            x0 = x0.max(clip.x as i32);
+            x1 = x1.min(clip.right() as i32);
+        }
+        for (rect, radii) in &self.clip_round_rects {
+            let (left, right) = round_rect_row_span(rect, radii, y as f32 + 0.5)?;
+            // 像素中心落在区间内才可见
+            x0 = x0.max((left - 0.5).ceil() as i32);
+            x1 = x1.min((right - 0.5).ceil() as i32);
+        }
+        if x0 < x1 { Some((x0, x1)) } else { None }
+    }
+
+    /// 用纯色填充一行中的 [x0, x1) 区间（带 alpha 混合），等价于 blend_span
+    pub fn fill_row(&mut self, y: i32, x0: i32, x1: i32, color: Color) {
+        self.blend_span(y, x0, x1, color);
+    }
+
+    /// 将纯色混合到第 y 行的 [x0, x1) 区间
+    /// 每行只做一次边界和裁剪检查，混合系数按整行预先计算，结果与逐个 set_pixel 完全一致
+    pub fn blend_span(&mut self, y: i32, x0: i32, x1: i32, color: Color) {
+        if color.a == 0 { return; }
+        let Some((x0, x1)) = self.row_span(y, x0, x1) else { return };
+        let start = self.index(x0, y);
+        blend_span_pixels(&mut self.pixels[start..start + (x1 - x0) as usize], color);
+    }
+
+    /// 将一行源像素从 (x, y) 开始混合到画布上，colors[i] 对应 x + i
+    /// 超出画布或裁剪区域的部分被丢弃，完全透明的像素被跳过
+    pub fn blend_row(&mut self, y: i32, x: i32, colors: &[Color]) {
+        let Some((x0, x1)) = self.row_span(y, x, x.saturating_add(colors.len() as i32)) else { return };
+        let start = self.index(x0, y);
+        let row = &mut self.pixels[start..start + (x1 - x0) as usize];
+        let src = &colors[(x0 - x) as usize..(x1 - x) as usize];
+        for (dst, color) in row.iter_mut().zip(src) {
+            if color.a == 255 {
+                *dst = *color;
+            } else if color.a > 0 {
+                *dst = color.blend(dst);
+            }
+        }
+    }
+
     /// 设置像素（带抗锯齿 coverage）
     fn set_pixel_aa(&mut self, x: i32, y: i32, color: Color, coverage: f32) {
         if coverage <= 0.0 { return; }
@@ -155,23 +347,35 @@ impl Canvas {
     }
 
     fn fill_rect(&mut self, rect: &Rect, color: &Color) {
+        if !self.matrix.is_identity() {
+            let mut path = Path::new();
+            path.add_rect(rect.x, rect.y, rect.width, rect.height);
+            self.draw_path(&path, &Paint::new().with_color(*color));
+            return;
+        }
         let tx = self.translation.0;
         let ty = self.translation.1;
+        let (left, top, right, bottom) = self.device_bounds();
         
-        let x0 = (rect.x + tx).max(0.0) as i32;
-        let y0 = (rect.y + ty).max(0.0) as i32;
-        let x1 = (rect.right() + tx).min(self.width as f32) as i32;
-        let y1 = (rect.bottom() + ty).min(self.height as f32) as i32;
+        let x0 = (rect.x + tx).max(left as f32) as i32;
+        let y0 = (rect.y + ty).max(top as f32) as i32;
+        let x1 = (rect.right() + tx).min(right as f32) as i32;
+        let y1 = (rect.bottom() + ty).min(bottom as f32) as i32;
 
         for y in y0..y1 {
-            for x in x0..x1 {
-                self.set_pixel(x, y, *color);
-            }
+            self.blend_span(y, x0, x1, *color);
         }
     }
 
     fn stroke_rect(&mut self, rect: &Rect, paint: &Paint) {
         let w = paint.stroke_width;
+        // 虚线和圆角、斜角连接交给描边器：沿内缩半个线宽的矩形描边，覆盖范围与四条边的画法一致
+        if !paint.dash_pattern.is_empty() || paint.stroke_join != StrokeJoin::Miter {
+            let mut path = Path::new();
+            path.add_rect(rect.x + w / 2.0, rect.y + w / 2.0, rect.width - w, rect.height - w);
+            self.draw_path(&path, &Paint { style: PaintStyle::Stroke, ..paint.clone() });
+            return;
+        }
         // 上边
         self.fill_rect(&Rect::new(rect.x, rect.y, rect.width, w), &paint.color);
         // 下边
@@ -184,6 +388,13 @@ impl Canvas {
 
     /// 绘制圆形
     pub fn draw_circle(&mut self, cx: f32, cy: f32, radius: f32, paint: &Paint) {
+        let dashed = paint.style != PaintStyle::Fill && !paint.dash_pattern.is_empty();
+        if !self.matrix.is_identity() || dashed {
+            let mut path = Path::new();
+            path.add_circle(cx, cy, radius);
+            self.draw_path(&path, paint);
+            return;
+        }
         match paint.style {
             PaintStyle::Fill => self.fill_circle(cx, cy, radius, paint),
             PaintStyle::Stroke => self.stroke_circle(cx, cy, radius, paint),
@@ -199,10 +410,11 @@ impl Canvas {
         let cy = cy + self.translation.1;
 
         let r2 = radius * radius;
-        let x0 = (cx - radius - 1.0).max(0.0) as i32;
-        let y0 = (cy - radius - 1.0).max(0.0) as i32;
-        let x1 = (cx + radius + 1.0).min(self.width as f32) as i32;
-        let y1 = (cy + radius + 1.0).min(self.height as f32) as i32;
+        let (left, top, right, bottom) = self.device_bounds();
+        let x0 = (cx - radius - 1.0).max(left as f32) as i32;
+        let y0 = (cy - radius - 1.0).max(top as f32) as i32;
+        let x1 = (cx + radius + 1.0).min(right as f32) as i32;
+        let y1 = (cy + radius + 1.0).min(bottom as f32) as i32;
 
         for y in y0..y1 {
             for x in x0..x1 {
@@ -230,10 +442,11 @@ impl Canvas {
         let inner = radius - paint.stroke_width / 2.0;
         let outer = radius + paint.stroke_width / 2.0;
 
-        let x0 = (cx - outer - 1.0).max(0.0) as i32;
-        let y0 = (cy - outer - 1.0).max(0.0) as i32;
-        let x1 = (cx + outer + 1.0).min(self.width as f32) as i32;
-        let y1 = (cy + outer + 1.0).min(self.height as f32) as i32;
+        let (left, top, right, bottom) = self.device_bounds();
+        let x0 = (cx - outer - 1.0).max(left as f32) as i32;
+        let y0 = (cy - outer - 1.0).max(top as f32) as i32;
+        let x1 = (cx + outer + 1.0).min(right as f32) as i32;
+        let y1 = (cy + outer + 1.0).min(bottom as f32) as i32;
 
         for y in y0..y1 {
             for x in x0..x1 {
@@ -256,13 +469,37 @@ impl Canvas {
         }
     }
 
+    /// 绘制圆弧（只描边），角度为弧度，从 x 轴正方向顺时针
+    pub fn draw_arc(&mut self, cx: f32, cy: f32, radius: f32, start: f32, end: f32, paint: &Paint) {
+        let mut path = Path::new();
+        path.arc(cx, cy, radius, start, end, false);
+        let paint = Paint { style: PaintStyle::Stroke, ..paint.clone() };
+        self.draw_path(&path, &paint);
+    }
+
+    /// 绘制扇形（饼图切片）
+    pub fn draw_pie(&mut self, cx: f32, cy: f32, radius: f32, start: f32, end: f32, paint: &Paint) {
+        let mut path = Path::new();
+        path.add_pie(cx, cy, radius, start, end);
+        self.draw_path(&path, paint);
+    }
+
+    /// 绘制环形扇区（环形图的一段）
+    pub fn draw_ring_segment(&mut self, ring: &RingSegment, paint: &Paint) {
+        let mut path = Path::new();
+        path.add_ring_segment(ring.cx, ring.cy, ring.inner_radius, ring.outer_radius, ring.start, ring.end);
+        self.draw_path(&path, paint);
+    }
+
     /// 绘制线段
     pub fn draw_line(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, paint: &Paint) {
-        let x0 = x0 + self.translation.0;
-        let y0 = y0 + self.translation.1;
-        let x1 = x1 + self.translation.0;
-        let y1 = y1 + self.translation.1;
+        let (x0, y0) = self.map(x0, y0);
+        let (x1, y1) = self.map(x1, y1);
+        self.stroke_polyline(&[Point::new(x0, y0), Point::new(x1, y1)], false, paint);
+    }
 
+    /// 绘制设备坐标下的线段
+    fn draw_line_device(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, paint: &Paint) {
         if paint.anti_alias {
             self.draw_line_aa(x0, y0, x1, y1, paint);
         } else {
@@ -336,175 +573,346 @@ impl Canvas {
 
     /// 绘制路径
     pub fn draw_path(&mut self, path: &Path, paint: &Paint) {
-        let mut contours = path.flatten(1.0);
+        let mut contours = path.flatten(path.tolerance());
 
-        // Apply translation
-        let tx = self.translation.0;
-        let ty = self.translation.1;
-        if tx != 0.0 || ty != 0.0 {
+        // 变换到设备坐标
+        if !self.matrix.is_identity() || self.translation != (0.0, 0.0) {
             for contour in &mut contours {
                 for p in contour {
-                    p.x += tx;
-                    p.y += ty;
+                    let (x, y) = self.map(p.x, p.y);
+                    p.x = x;
+                    p.y = y;
                 }
             }
         }
 
+        let rule = path.fill_rule();
         match paint.style {
-            PaintStyle::Fill => self.fill_path(&contours, paint),
+            PaintStyle::Fill => self.fill_path(&contours, paint, rule),
             PaintStyle::Stroke => self.stroke_path(&contours, paint),
             PaintStyle::FillAndStroke => {
-                self.fill_path(&contours, paint);
+                self.fill_path(&contours, paint, rule);
                 self.stroke_path(&contours, paint);
             }
         }
     }
 
     /// 填充路径（扫描线算法，支持抗锯齿）
-    fn fill_path(&mut self, contours: &[Vec<Point>], paint: &Paint) {
-        if contours.is_empty() { return; }
-
-        // 找边界
-        let mut min_y = f32::MAX;
-        let mut max_y = f32::MIN;
-        for contour in contours {
-            for p in contour {
-                min_y = min_y.min(p.y);
-                max_y = max_y.max(p.y);
+    ///
+    /// 抗锯齿时每行取 FILL_SUBSAMPLES 条子扫描线，每条子扫描线上的区间按精确的水平覆盖面积
+    /// 累加到像素上；完全覆盖的连续像素用 blend_span 一次混合。
+    /// 不抗锯齿时按像素中心采样。
+    fn fill_path(&mut self, contours: &[Vec<Point>], paint: &Paint, rule: FillRule) {
+        let edges = path_edges(contours);
+        if edges.is_empty() { return; }
+
+        let (mut min_x, mut max_x) = (f32::MAX, f32::MIN);
+        let (mut min_y, mut max_y) = (f32::MAX, f32::MIN);
+        for e in &edges {
+            min_x = min_x.min(e.x0.min(e.x1));
+            max_x = max_x.max(e.x0.max(e.x1));
+            min_y = min_y.min(e.y0);
+            max_y = max_y.max(e.y1);
+        }
+        let (left, top, right, bottom) = self.device_bounds();
+        if max_x <= left as f32 || min_x >= right as f32 { return; }
+
+        let y0 = (min_y.floor() as i32).max(top);
+        let y1 = (max_y.ceil() as i32).min(bottom);
+        let mut crossings = Vec::new();
+        let mut spans = Vec::new();
+
+        if !paint.anti_alias {
+            for y in y0..y1 {
+                scanline_spans(&edges, y as f32 + 0.5, rule, &mut crossings, &mut spans);
+                for &(l, r) in &spans {
+                    // 像素中心落在区间内才填充
+                    let x0 = (l - 0.5).ceil() as i32;
+                    let x1 = (r - 0.5).ceil() as i32;
+                    self.blend_span(y, x0, x1, paint.color);
+                }
             }
+            return;
         }
 
-        let y0 = (min_y - 1.0).floor() as i32;
-        let y1 = (max_y + 1.0).ceil() as i32;
+        // 覆盖率缓冲只覆盖路径在画布内的列
+        let bx0 = (min_x.floor() as i32).max(left);
+        let bx1 = (max_x.ceil() as i32).min(right);
+        let cols = (bx1 - bx0) as usize;
+        let mut area = vec![0.0f32; cols + 1];
+        let mut delta = vec![0.0f32; cols + 1];
+        let weight = 1.0 / FILL_SUBSAMPLES as f32;
 
-        if paint.anti_alias {
-            // 抗锯齿填充 - 使用边缘覆盖率计算
-            for y in y0..=y1 {
-                // 收集多个子扫描线的交点
-                let sub_samples = 4;
-                let mut all_intersections: Vec<Vec<f32>> = Vec::new();
-                
-                for sub in 0..sub_samples {
-                    let scan_y = y as f32 + (sub as f32 + 0.5) / sub_samples as f32;
-                    let mut intersections = Vec::new();
-                    
-                    for contour in contours {
-                        for i in 0..contour.len() {
-                            let p0 = &contour[i];
-                            let p1 = &contour[(i + 1) % contour.len()];
-
-                            if (p0.y <= scan_y && p1.y > scan_y) || (p1.y <= scan_y && p0.y > scan_y) {
-                                let t = (scan_y - p0.y) / (p1.y - p0.y);
-                                let x = p0.x + t * (p1.x - p0.x);
-                                intersections.push(x);
-                            }
-                        }
+        for y in y0..y1 {
+            area.fill(0.0);
+            delta.fill(0.0);
+            for sub in 0..FILL_SUBSAMPLES {
+                let scan_y = y as f32 + (sub as f32 + 0.5) * weight;
+                scanline_spans(&edges, scan_y, rule, &mut crossings, &mut spans);
+                for &(l, r) in &spans {
+                    let l = (l - bx0 as f32).clamp(0.0, cols as f32);
+                    let r = (r - bx0 as f32).clamp(0.0, cols as f32);
+                    if r <= l { continue; }
+                    let (il, ir) = (l as usize, r as usize);
+                    if il == ir {
+                        area[il] += (r - l) * weight;
+                    } else {
+                        // 两端的部分像素记入 area，中间的整像素用差分数组记录
+                        area[il] += (il as f32 + 1.0 - l) * weight;
+                        delta[il + 1] += weight;
+                        delta[ir] -= weight;
+                        area[ir] += (r - ir as f32) * weight;
                     }
-                    
-                    intersections.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                    all_intersections.push(intersections);
                 }
-                
-                // 找出所有交点的 x 范围
-                let mut x_min = f32::MAX;
-                let mut x_max = f32::MIN;
-                for intersections in &all_intersections {
-                    for &x in intersections {
-                        x_min = x_min.min(x);
-                        x_max = x_max.max(x);
-                    }
+            }
+
+            let mut full = 0.0;
+            let mut run_start: Option<i32> = None;
+            for i in 0..cols {
+                full += delta[i];
+                let coverage = (area[i] + full).min(1.0);
+                let x = bx0 + i as i32;
+                if coverage >= 0.999 {
+                    run_start.get_or_insert(x);
+                    continue;
                 }
-                
-                if x_min > x_max { continue; }
-                
-                let x0 = (x_min - 1.0).floor() as i32;
-                let x1 = (x_max + 1.0).ceil() as i32;
-                
-                for x in x0..=x1 {
-                    let px = x as f32;
-                    let mut coverage = 0.0;
-                    
-                    // 计算每个子扫描线的覆盖
-                    for intersections in &all_intersections {
-                        let mut sub_coverage = 0.0;
-                        
-                        for pair in intersections.chunks(2) {
-                            if pair.len() == 2 {
-                                let left = pair[0];
-                                let right = pair[1];
-                                
-                                // 计算这个像素在这个区间的覆盖
-                                let pixel_left = px;
-                                let pixel_right = px + 1.0;
-                                
-                                if pixel_right <= left || pixel_left >= right {
-                                    // 完全在区间外
-                                    continue;
-                                } else if pixel_left >= left && pixel_right <= right {
-                                    // 完全在区间内
-                                    sub_coverage += 1.0;
-                                } else {
-                                    // 部分覆盖
-                                    let overlap_left = pixel_left.max(left);
-                                    let overlap_right = pixel_right.min(right);
-                                    sub_coverage += overlap_right - overlap_left;
-                                }
-                            }
-                        }
-                        
-                        coverage += sub_coverage;
-                    }
-                    
-                    coverage /= sub_samples as f32;
-                    
-                    if coverage > 0.0 {
-                        self.set_pixel_aa(x, y, paint.color, coverage.min(1.0));
-                    }
+                if let Some(start) = run_start.take() {
+                    self.blend_span(y, start, x, paint.color);
                 }
-            }
-        } else {
-            // 非抗锯齿填充
-            for y in y0..=y1 {
-                let mut intersections = Vec::new();
-                let scan_y = y as f32 + 0.5;
-
-                for contour in contours {
-                    for i in 0..contour.len() {
-                        let p0 = &contour[i];
-                        let p1 = &contour[(i + 1) % contour.len()];
-
-                        if (p0.y <= scan_y && p1.y > scan_y) || (p1.y <= scan_y && p0.y > scan_y) {
-                            let t = (scan_y - p0.y) / (p1.y - p0.y);
-                            let x = p0.x + t * (p1.x - p0.x);
-                            intersections.push(x);
-                        }
-                    }
+                if coverage > 0.0 {
+                    self.set_pixel_aa(x, y, paint.color, coverage);
                 }
+            }
+            if let Some(start) = run_start {
+                self.blend_span(y, start, bx1, paint.color);
+            }
+        }
+    }
 
-                intersections.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// 描边路径
+    fn stroke_path(&mut self, contours: &[Vec<Point>], paint: &Paint) {
+        for contour in contours {
+            // close() 会补上起点，首尾重合的轮廓按闭合处理，拐角用连接样式而不是线帽
+            let closed = contour.len() > 2 && contour[0] == contour[contour.len() - 1];
+            self.stroke_polyline(contour, closed, paint);
+        }
+    }
 
-                for pair in intersections.chunks(2) {
-                    if pair.len() == 2 {
-                        let x0 = pair[0].floor() as i32;
-                        let x1 = pair[1].ceil() as i32;
-                        for x in x0..=x1 {
-                            self.set_pixel(x, y, paint.color);
-                        }
+    /// 描边设备坐标下的折线
+    /// 先按虚线模式切分；线宽不超过 1 像素时直接画细线，否则展开成描边轮廓按非零规则填充
+    fn stroke_polyline(&mut self, points: &[Point], closed: bool, paint: &Paint) {
+        let scale = self.stroke_scale();
+        let pattern: Vec<f32> = paint.dash_pattern.iter().map(|d| d * scale).collect();
+        let (pieces, closed) = match dash_polyline(points, closed, &pattern, paint.dash_offset * scale) {
+            Some(dashes) => (dashes, false),
+            None => (vec![points.to_vec()], closed),
+        };
+
+        let width = paint.stroke_width * scale;
+        if width <= 1.0 {
+            for piece in &pieces {
+                for seg in piece.windows(2) {
+                    if seg[0] != seg[1] {
+                        self.draw_line_device(seg[0].x, seg[0].y, seg[1].x, seg[1].y, paint);
                     }
                 }
             }
+            return;
+        }
+
+        let style = StrokeStyle {
+            width,
+            cap: paint.stroke_cap,
+            join: paint.stroke_join,
+            miter_limit: paint.stroke_miter,
+        };
+        let polygons: Vec<Vec<Point>> = pieces.iter().flat_map(|piece| stroke_outline(piece, closed, &style)).collect();
+        self.fill_path(&polygons, paint, FillRule::NonZero);
+    }
+
+    /// 当前变换对线宽的缩放（面积缩放的平方根）
+    fn stroke_scale(&self) -> f32 {
+        let m = &self.matrix;
+        (m.a * m.d - m.b * m.c).abs().sqrt()
+    }
+
+    /// 把图层按变换矩阵合成到画布上（双线性采样）
+    ///
+    /// 图层由 new_layer / new_layer_in 创建，与画布共用坐标系；m 作用于平移之前的坐标，
+    /// 与当前画布的变换矩阵叠加。
+    pub fn draw_layer(&mut self, layer: &Canvas, m: &Matrix) {
+        let Some((lx0, ly0, lx1, ly1)) = layer.content_bounds() else { return };
+        let (lx0, lx1) = (lx0 as i32 + layer.origin.0, lx1 as i32 + layer.origin.0);
+        let (ly0, ly1) = (ly0 as i32 + layer.origin.1, ly1 as i32 + layer.origin.1);
+        let (tx, ty) = self.translation;
+        let full = self.matrix.concat(m);
+        let Some(inv) = full.invert() else { return };
+
+        // 图层内容的包围盒映射到目标画布
+        let corners = [(lx0, ly0), (lx1, ly0), (lx0, ly1), (lx1, ly1)];
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for (cx, cy) in corners {
+            let p = full.map_point(cx as f32 - tx, cy as f32 - ty);
+            min_x = min_x.min(p.x + tx);
+            min_y = min_y.min(p.y + ty);
+            max_x = max_x.max(p.x + tx);
+            max_y = max_y.max(p.y + ty);
+        }
+        let (left, top, right, bottom) = self.device_bounds();
+        let x0 = (min_x.floor() as i32 - 1).max(left);
+        let x1 = (max_x.ceil() as i32 + 1).min(right);
+        let y0 = (min_y.floor() as i32 - 1).max(top);
+        let y1 = (max_y.ceil() as i32 + 1).min(bottom);
+        if x0 >= x1 || y0 >= y1 { return; }
+
+        let mut row = vec![Color::TRANSPARENT; (x1 - x0) as usize];
+        for y in y0..y1 {
+            if self.row_span(y, x0, x1).is_none() {
+                continue;
+            }
+            for x in x0..x1 {
+                // 目标像素中心逆变换回图层坐标
+                let p = inv.map_point(x as f32 + 0.5 - tx, y as f32 + 0.5 - ty);
+                row[(x - x0) as usize] = layer.sample_bilinear(p.x + tx - 0.5, p.y + ty - 0.5);
+            }
+            self.blend_row(y, x0, &row);
         }
     }
 
-    /// 描边路径
-    fn stroke_path(&mut self, contours: &[Vec<Point>], paint: &Paint) {
-        for contour in contours {
-            for i in 0..contour.len().saturating_sub(1) {
-                self.draw_line(
-                    contour[i].x, contour[i].y,
-                    contour[i + 1].x, contour[i + 1].y,
-                    paint
-                );
+    /// 把同坐标系的图层按整体不透明度合成到画布上（用于 opacity 作用于整棵子树）
+    ///
+    /// 图层由 new_layer / new_layer_in 创建，逐像素对应，不做重采样。
+    pub fn draw_layer_with_opacity(&mut self, layer: &Canvas, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if opacity <= 0.0 { return; }
+        let Some((x0, y0, x1, y1)) = layer.content_bounds() else { return };
+        let mut row = vec![Color::TRANSPARENT; (x1 - x0) as usize];
+        for y in y0..y1 {
+            let start = (y * layer.width) as usize;
+            for (dst, src) in row.iter_mut().zip(&layer.pixels[start + x0 as usize..start + x1 as usize]) {
+                *dst = Color::new(src.r, src.g, src.b, (src.a as f32 * opacity).round() as u8);
+            }
+            self.blend_row(y as i32 + layer.origin.1, x0 as i32 + layer.origin.0, &row);
+        }
+    }
+
+    /// 非透明像素在像素缓冲中的包围盒 [x0, x1) x [y0, y1)，全透明时返回 None
+    fn content_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        let (mut x0, mut y0, mut x1, mut y1) = (u32::MAX, u32::MAX, 0, 0);
+        for y in 0..self.height {
+            let row = &self.pixels[(y * self.width) as usize..((y + 1) * self.width) as usize];
+            let Some(first) = row.iter().position(|c| c.a > 0) else { continue };
+            let last = row.iter().rposition(|c| c.a > 0).unwrap_or(first);
+            x0 = x0.min(first as u32);
+            x1 = x1.max(last as u32 + 1);
+            y0 = y0.min(y);
+            y1 = y + 1;
+        }
+        if x0 < x1 { Some((x0, y0, x1, y1)) } else { None }
+    }
+
+    /// 以像素中心为采样点的双线性插值（设备坐标，预乘 alpha 后插值，避免透明边缘发黑）
+    fn sample_bilinear(&self, x: f32, y: f32) -> Color {
+        let (fx0, fy0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - fx0, y - fy0);
+        let (ix, iy) = (fx0 as i64 - self.origin.0 as i64, fy0 as i64 - self.origin.1 as i64);
+        let pixel = |px: i64, py: i64| -> Color {
+            if px < 0 || py < 0 || px >= self.width as i64 || py >= self.height as i64 {
+                Color::TRANSPARENT
+            } else {
+                self.pixels[(py as u32 * self.width + px as u32) as usize]
+            }
+        };
+
+        let mut acc = [0.0f32; 4];
+        for (px, py, weight) in [
+            (ix, iy, (1.0 - fx) * (1.0 - fy)),
+            (ix + 1, iy, fx * (1.0 - fy)),
+            (ix, iy + 1, (1.0 - fx) * fy),
+            (ix + 1, iy + 1, fx * fy),
+        ] {
+            let c = pixel(px, py);
+            if c.a == 0 || weight <= 0.0 { continue; }
+            let a = c.a as f32 * weight;
+            acc[0] += c.r as f32 * a;
+            acc[1] += c.g as f32 * a;
+            acc[2] += c.b as f32 * a;
+            acc[3] += a;
+        }
+        if acc[3] <= 0.0 {
+            return Color::TRANSPARENT;
+        }
+        Color::new(
+            (acc[0] / acc[3]).round() as u8,
+            (acc[1] / acc[3]).round() as u8,
+            (acc[2] / acc[3]).round() as u8,
+            acc[3].round().min(255.0) as u8,
+        )
+    }
+
+    /// 把另一块画布按原尺寸混合到 (x, y)（只应用平移，与 draw_image 一致）
+    pub fn draw_bitmap(&mut self, bitmap: &Canvas, x: f32, y: f32) {
+        let x = (x + self.translation.0).round() as i32;
+        let y = (y + self.translation.1).round() as i32;
+        let w = bitmap.width as usize;
+        for row in 0..bitmap.height as usize {
+            self.blend_row(y + row as i32, x, &bitmap.pixels[row * w..(row + 1) * w]);
+        }
+    }
+
+    /// 对区域内的像素做高斯模糊，radius 为 CSS 模糊半径（标准差取 radius / 2）
+    ///
+    /// 用三次可分离的盒式模糊近似高斯模糊，耗时与半径无关。
+    /// 在预乘 alpha 下计算，区域外视为透明。
+    pub fn blur_region(&mut self, rect: &Rect, radius: f32) {
+        let sigma = radius / 2.0;
+        if sigma < 0.5 { return; }
+        // 换算到像素缓冲内的坐标
+        let (tx, ty) = (self.translation.0 - self.origin.0 as f32, self.translation.1 - self.origin.1 as f32);
+        let x0 = ((rect.x + tx).floor() as i32).clamp(0, self.width as i32) as usize;
+        let y0 = ((rect.y + ty).floor() as i32).clamp(0, self.height as i32) as usize;
+        let x1 = ((rect.right() + tx).ceil() as i32).clamp(0, self.width as i32) as usize;
+        let y1 = ((rect.bottom() + ty).ceil() as i32).clamp(0, self.height as i32) as usize;
+        if x0 >= x1 || y0 >= y1 { return; }
+        let (w, h) = (x1 - x0, y1 - y0);
+
+        let mut buf: Vec<[f32; 4]> = Vec::with_capacity(w * h);
+        for y in y0..y1 {
+            for c in &self.pixels[y * self.width as usize + x0..y * self.width as usize + x1] {
+                let a = c.a as f32 / 255.0;
+                buf.push([c.r as f32 * a, c.g as f32 * a, c.b as f32 * a, c.a as f32]);
+            }
+        }
+
+        let mut line = Vec::with_capacity(w.max(h));
+        for r in gauss_box_radii(sigma) {
+            for y in 0..h {
+                line.clear();
+                line.extend_from_slice(&buf[y * w..(y + 1) * w]);
+                box_blur_line(&line, r, |i, v| buf[y * w + i] = v);
+            }
+            for x in 0..w {
+                line.clear();
+                line.extend((0..h).map(|y| buf[y * w + x]));
+                box_blur_line(&line, r, |i, v| buf[i * w + x] = v);
+            }
+        }
+
+        for y in 0..h {
+            let start = (y0 + y) * self.width as usize + x0;
+            for (dst, v) in self.pixels[start..start + w].iter_mut().zip(&buf[y * w..(y + 1) * w]) {
+                let a = v[3].round().clamp(0.0, 255.0);
+                *dst = if a < 1.0 {
+                    Color::TRANSPARENT
+                } else {
+                    let k = 255.0 / v[3];
+                    Color::new(
+                        (v[0] * k).round().clamp(0.0, 255.0) as u8,
+                        (v[1] * k).round().clamp(0.0, 255.0) as u8,
+                        (v[2] * k).round().clamp(0.0, 255.0) as u8,
+                        a as u8,
+                    )
+                };
             }
         }
     }
@@ -521,6 +929,37 @@ impl Canvas {
         data
     }
 
+    /// 导出为 RGB565（16 位屏幕），dither 为 true 时使用有序抖动减少渐变色带
+    pub fn to_rgb565(&self, dither: bool) -> Vec<u16> {
+        let mut data = Vec::with_capacity(self.pixels.len());
+        for (i, pixel) in self.pixels.iter().enumerate() {
+            let t = if dither { bayer_threshold(i as u32 % self.width, i as u32 / self.width) } else { 0.0 };
+            let r = quantize_level(pixel.r, 5, t) as u16;
+            let g = quantize_level(pixel.g, 6, t) as u16;
+            let b = quantize_level(pixel.b, 5, t) as u16;
+            data.push((r << 11) | (g << 5) | b);
+        }
+        data
+    }
+
+    /// 把每个颜色通道量化为 bits_per_channel 位（1-8），结果映射回 8 位颜色，alpha 不变
+    ///
+    /// 用于墨水屏等低位深输出，dither 为 true 时使用 4x4 Bayer 有序抖动，结果是确定的。
+    pub fn quantize(&self, bits_per_channel: u32, dither: bool) -> Canvas {
+        let bits = bits_per_channel.clamp(1, 8);
+        let max = ((1u32 << bits) - 1) as f32;
+        let expand = |level: u8| (level as f32 * 255.0 / max).round() as u8;
+        let mut out = Canvas::new(self.width, self.height);
+        out.copy_from(self);
+        for (i, pixel) in out.pixels.iter_mut().enumerate() {
+            let t = if dither { bayer_threshold(i as u32 % self.width, i as u32 / self.width) } else { 0.0 };
+            pixel.r = expand(quantize_level(pixel.r, bits, t));
+            pixel.g = expand(quantize_level(pixel.g, bits, t));
+            pixel.b = expand(quantize_level(pixel.b, bits, t));
+        }
+        out
+    }
+
     /// 保存为 PNG
     pub fn save_png(&self, path: &str) -> Result<(), String> {
         use image::{ImageBuffer, Rgba};
@@ -536,8 +975,9 @@ impl Canvas {
 
     /// 直接设置像素（供文本渲染使用）
     pub fn set_pixel_direct(&mut self, x: i32, y: i32, color: Color) {
-        if x >= 0 && y >= 0 && x < self.width as i32 && y < self.height as i32 {
-            let idx = (y as u32 * self.width + x as u32) as usize;
+        let (left, top, right, bottom) = self.device_bounds();
+        if x >= left && y >= top && x < right && y < bottom {
+            let idx = self.index(x, y);
             self.pixels[idx] = color;
         }
     }
@@ -546,7 +986,9 @@ impl Canvas {
     /// img_data: RGBA 像素数据
     /// img_w, img_h: 图片原始尺寸
     /// x, y, w, h: 目标绘制区域
-    /// mode: 缩放模式 (aspectFit, aspectFill, scaleToFill)
+    /// mode: image 组件的 mode（scaleToFill、aspectFit、aspectFill、widthFix、heightFix、
+    ///       top、bottom、center、left、right、top left 等），见 image_mode_layout
+    /// pixel_ratio: 不缩放的定位模式下，一个图片像素对应的画布像素数
     pub fn draw_image(
         &mut self,
         img_data: &[u8],
@@ -558,6 +1000,7 @@ impl Canvas {
         h: f32,
         mode: &str,
         radius: f32,
+        pixel_ratio: f32,
     ) {
         if img_data.len() < (img_w * img_h * 4) as usize {
             return;
@@ -568,30 +1011,7 @@ impl Canvas {
         let y = y + self.translation.1;
 
         // 计算缩放和偏移
-        let (scale_x, scale_y, offset_x, offset_y) = match mode {
-            "aspectFit" => {
-                // 保持比例，完整显示，可能有留白
-                let scale = (w / img_w as f32).min(h / img_h as f32);
-                let scaled_w = img_w as f32 * scale;
-                let scaled_h = img_h as f32 * scale;
-                let ox = (w - scaled_w) / 2.0;
-                let oy = (h - scaled_h) / 2.0;
-                (scale, scale, ox, oy)
-            }
-            "aspectFill" => {
-                // 保持比例，填满区域，可能裁剪
-                let scale = (w / img_w as f32).max(h / img_h as f32);
-                let scaled_w = img_w as f32 * scale;
-                let scaled_h = img_h as f32 * scale;
-                let ox = (w - scaled_w) / 2.0;
-                let oy = (h - scaled_h) / 2.0;
-                (scale, scale, ox, oy)
-            }
-            _ => {
-                // scaleToFill: 拉伸填满
-                (w / img_w as f32, h / img_h as f32, 0.0, 0.0)
-            }
-        };
+        let (scale_x, scale_y, offset_x, offset_y) = image_mode_layout(mode, img_w, img_h, w, h, pixel_ratio);
 
         let dest_x0 = x as i32;
         let dest_y0 = y as i32;
@@ -600,10 +1020,14 @@ impl Canvas {
 
         // 圆角裁剪预计算
         let has_radius = radius > 0.0;
-        let _cx = x + w / 2.0;
-        let _cy = y + h / 2.0;
 
+        // 逐行采样到行缓冲，再一次性混合到画布
+        let mut row = vec![Color::TRANSPARENT; (dest_x1 - dest_x0).max(0) as usize];
         for dest_y in dest_y0..dest_y1 {
+            if self.row_span(dest_y, dest_x0, dest_x1).is_none() {
+                continue;
+            }
+            row.fill(Color::TRANSPARENT);
             for dest_x in dest_x0..dest_x1 {
                 // 检查圆角裁剪
                 if has_radius {
@@ -674,8 +1098,245 @@ impl Canvas {
                 let b = lerp(lerp(c00.2, c10.2, fx), lerp(c01.2, c11.2, fx), fy) as u8;
                 let a = lerp(lerp(c00.3, c10.3, fx), lerp(c01.3, c11.3, fx), fy) as u8;
 
-                self.set_pixel(dest_x, dest_y, Color::new(r, g, b, a));
+                row[(dest_x - dest_x0) as usize] = Color::new(r, g, b, a);
+            }
+            self.blend_row(dest_y, dest_x0, &row);
+        }
+    }
+}
+
+/// 4x4 Bayer 矩阵，阈值范围 [-0.5, 0.5)
+const BAYER_4X4: [[u8; 4]; 4] = [
+    [0, 8, 2, 10],
+    [12, 4, 14, 6],
+    [3, 11, 1, 9],
+    [15, 7, 13, 5],
+];
+
+fn bayer_threshold(x: u32, y: u32) -> f32 {
+    (BAYER_4X4[(y % 4) as usize][(x % 4) as usize] as f32 + 0.5) / 16.0 - 0.5
+}
+
+/// 把 8 位通道值量化为 bits 位的级别，threshold 为抖动偏移（不抖动时为 0，即四舍五入）
+fn quantize_level(value: u8, bits: u32, threshold: f32) -> u8 {
+    let max = ((1u32 << bits) - 1) as f32;
+    (value as f32 / 255.0 * max + 0.5 + threshold).floor().clamp(0.0, max) as u8
+}
+
+/// 按 image 组件的 mode 计算图片在 w x h 区域内的缩放和偏移，返回 (scale_x, scale_y, offset_x, offset_y)
+///
+/// - scaleToFill：拉伸填满
+/// - aspectFit：保持比例完整显示，居中留白
+/// - aspectFill / widthFix / heightFix：保持比例填满，居中裁剪（widthFix/heightFix 的区域已按比例调整）
+/// - top、bottom、center、left、right 及四个角：不缩放（按 pixel_ratio），按方位对齐，超出部分裁剪
+pub fn image_mode_layout(mode: &str, img_w: u32, img_h: u32, w: f32, h: f32, pixel_ratio: f32) -> (f32, f32, f32, f32) {
+    let (iw, ih) = (img_w.max(1) as f32, img_h.max(1) as f32);
+    let aspect = |scale: f32| (scale, scale, (w - iw * scale) / 2.0, (h - ih * scale) / 2.0);
+    match mode {
+        "aspectFit" => aspect((w / iw).min(h / ih)),
+        "aspectFill" | "widthFix" | "heightFix" => aspect((w / iw).max(h / ih)),
+        "top" | "bottom" | "center" | "left" | "right"
+        | "top left" | "top right" | "bottom left" | "bottom right" => {
+            let (sw, sh) = (iw * pixel_ratio, ih * pixel_ratio);
+            let ox = if mode.ends_with("left") {
+                0.0
+            } else if mode.ends_with("right") {
+                w - sw
+            } else {
+                (w - sw) / 2.0
+            };
+            let oy = if mode.starts_with("top") {
+                0.0
+            } else if mode.starts_with("bottom") {
+                h - sh
+            } else {
+                (h - sh) / 2.0
+            };
+            (pixel_ratio, pixel_ratio, ox, oy)
+        }
+        _ => (w / iw, h / ih, 0.0, 0.0),
+    }
+}
+
+/// 三次盒式模糊近似标准差为 sigma 的高斯模糊时，每次使用的盒半径
+fn gauss_box_radii(sigma: f32) -> [usize; 3] {
+    let n = 3.0;
+    let ideal = (12.0 * sigma * sigma / n + 1.0).sqrt();
+    let mut wl = ideal.floor() as i32;
+    if wl % 2 == 0 { wl -= 1; }
+    let wu = wl + 2;
+    let wl_f = wl as f32;
+    let m = ((12.0 * sigma * sigma - n * wl_f * wl_f - 4.0 * n * wl_f - 3.0 * n) / (-4.0 * wl_f - 4.0)).round() as i32;
+    let mut radii = [0; 3];
+    for (i, r) in radii.iter_mut().enumerate() {
+        let size = if (i as i32) < m { wl } else { wu };
+        *r = ((size - 1) / 2).max(0) as usize;
+    }
+    radii
+}
+
+/// 一维盒式模糊，窗口为 [i - r, i + r]，越界部分按透明计算
+fn box_blur_line(src: &[[f32; 4]], r: usize, mut write: impl FnMut(usize, [f32; 4])) {
+    let n = src.len();
+    let inv = 1.0 / (2 * r + 1) as f32;
+    let mut sum = [0.0f32; 4];
+    for v in src.iter().take(r.min(n)) {
+        for k in 0..4 { sum[k] += v[k]; }
+    }
+    for i in 0..n {
+        if i + r < n {
+            for k in 0..4 { sum[k] += src[i + r][k]; }
+        }
+        if i > r {
+            for k in 0..4 { sum[k] -= src[i - r - 1][k]; }
+        }
+        write(i, [sum[0] * inv, sum[1] * inv, sum[2] * inv, sum[3] * inv]);
+    }
+}
+
+/// 抗锯齿填充时每个像素行的子扫描线数
+const FILL_SUBSAMPLES: usize = 16;
+
+/// 路径的一条边，端点按 y 从小到大排列，winding 为原始方向（向下为 1，向上为 -1）
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    winding: i32,
+}
+
+/// 把闭合轮廓拆成非水平的边
+fn path_edges(contours: &[Vec<Point>]) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for contour in contours {
+        for i in 0..contour.len() {
+            let p0 = contour[i];
+            let p1 = contour[(i + 1) % contour.len()];
+            if p0.y == p1.y { continue; }
+            let (a, b, winding) = if p0.y < p1.y { (p0, p1, 1) } else { (p1, p0, -1) };
+            edges.push(Edge { x0: a.x, y0: a.y, x1: b.x, y1: b.y, winding });
+        }
+    }
+    edges
+}
+
+/// 按填充规则计算扫描线 scan_y 上被填充的区间，写入 spans
+fn scanline_spans(edges: &[Edge], scan_y: f32, rule: FillRule, crossings: &mut Vec<(f32, i32)>, spans: &mut Vec<(f32, f32)>) {
+    crossings.clear();
+    spans.clear();
+    for e in edges {
+        if scan_y >= e.y0 && scan_y < e.y1 {
+            let t = (scan_y - e.y0) / (e.y1 - e.y0);
+            crossings.push((e.x0 + t * (e.x1 - e.x0), e.winding));
+        }
+    }
+    crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let is_inside = |winding: i32| match rule {
+        FillRule::NonZero => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    };
+    let mut winding = 0;
+    let mut start = 0.0;
+    for &(x, w) in crossings.iter() {
+        let was_inside = is_inside(winding);
+        winding += w;
+        let inside = is_inside(winding);
+        if !was_inside && inside {
+            start = x;
+        } else if was_inside && !inside && x > start {
+            spans.push((start, x));
+        }
+    }
+}
+
+/// 把半透明纯色混合到一段像素上
+/// 目标不透明时走预乘后的快速路径，除以 255 用 (v + 1 + (v >> 8)) >> 8 代替，
+/// 对 v <= 255 * 255 与整数除法结果相同；目标半透明时回退到 Color::blend
+fn blend_span_pixels(row: &mut [Color], color: Color) {
+    if color.a == 255 {
+        row.fill(color);
+        return;
+    }
+    let alpha = color.a as u32;
+    let inv = 255 - alpha;
+    let (sr, sg, sb) = (color.r as u32 * alpha, color.g as u32 * alpha, color.b as u32 * alpha);
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    let row = simd::blend_opaque_chunks(row, [sr as u16, sg as u16, sb as u16], inv as u16);
+
+    for dst in row {
+        if dst.a == 255 {
+            dst.r = div255(sr + dst.r as u32 * inv);
+            dst.g = div255(sg + dst.g as u32 * inv);
+            dst.b = div255(sb + dst.b as u32 * inv);
+        } else {
+            *dst = color.blend(dst);
+        }
+    }
+}
+
+#[inline(always)]
+fn div255(v: u32) -> u8 {
+    ((v + 1 + (v >> 8)) >> 8) as u8
+}
+
+/// SSE2 版本的 span 混合，每次处理 4 个像素
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd {
+    use crate::Color;
+    use std::arch::x86_64::*;
+
+    /// 混合开头连续的、目标全部不透明的 4 像素块，返回剩余未处理的部分
+    pub(super) fn blend_opaque_chunks(row: &mut [Color], src: [u16; 3], inv: u16) -> &mut [Color] {
+        let chunks = row.len() / 4;
+        let mut done = 0;
+        // SAFETY: x86_64 必定支持 SSE2；Color 为 repr(C) 的 4 个 u8，4 个像素正好 16 字节，读写使用非对齐指令
+        unsafe {
+            let premul = _mm_setr_epi16(src[0] as i16, src[1] as i16, src[2] as i16, 0, src[0] as i16, src[1] as i16, src[2] as i16, 0);
+            let inv = _mm_set1_epi16(inv as i16);
+            let one = _mm_set1_epi16(1);
+            let zero = _mm_setzero_si128();
+            let alpha_mask = _mm_set1_epi32(0xFF00_0000u32 as i32);
+            while done < chunks {
+                let ptr = row.as_mut_ptr().add(done * 4) as *mut __m128i;
+                let dst = _mm_loadu_si128(ptr);
+                // 4 个像素的 alpha 都是 255 才走这里
+                if _mm_movemask_epi8(_mm_cmpeq_epi32(_mm_and_si128(dst, alpha_mask), alpha_mask)) != 0xFFFF {
+                    break;
+                }
+                let blend = |half: __m128i| {
+                    let v = _mm_add_epi16(_mm_mullo_epi16(half, inv), premul);
+                    _mm_srli_epi16(_mm_add_epi16(_mm_add_epi16(v, one), _mm_srli_epi16(v, 8)), 8)
+                };
+                let lo = blend(_mm_unpacklo_epi8(dst, zero));
+                let hi = blend(_mm_unpackhi_epi8(dst, zero));
+                _mm_storeu_si128(ptr, _mm_or_si128(_mm_packus_epi16(lo, hi), alpha_mask));
+                done += 1;
             }
         }
+        &mut row[done * 4..]
+    }
+}
+
+/// 圆角矩形在高度 py 处的水平区间 [left, right)，py 在矩形外时返回 None
+fn round_rect_row_span(rect: &Rect, radii: &[f32; 4], py: f32) -> Option<(f32, f32)> {
+    if py < rect.y || py >= rect.bottom() {
+        return None;
     }
+    let [tl, tr, br, bl] = radii.map(|r| r.min(rect.width / 2.0).min(rect.height / 2.0).max(0.0));
+    // 半径为 r 的圆角在距离圆心 dy 处向内收缩的距离
+    let inset = |r: f32, dy: f32| if r > 0.0 && dy > 0.0 { r - (r * r - dy * dy).max(0.0).sqrt() } else { 0.0 };
+    let left = if py < rect.y + tl {
+        inset(tl, rect.y + tl - py)
+    } else if py > rect.bottom() - bl {
+        inset(bl, py - (rect.bottom() - bl))
+    } else { 0.0 };
+    let right = if py < rect.y + tr {
+        inset(tr, rect.y + tr - py)
+    } else if py > rect.bottom() - br {
+        inset(br, py - (rect.bottom() - br))
+    } else { 0.0 };
+    Some((rect.x + left, rect.right() - right))
 }
@@ -1,6 +1,6 @@
 //! FFI 接口 - C/C++ 绑定
 
-use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect};
+use crate::{Canvas, Color, FillRule, Paint, PaintStyle, Path, Rect};
 use std::ffi::CStr;
 use std::os::raw::c_char;
 
@@ -160,6 +160,18 @@ pub extern "C" fn mr_path_add_oval(path: *mut Path, cx: f32, cy: f32, rx: f32, r
     }
 }
 
+/// 设置路径填充规则：0 为非零环绕，1 为奇偶
+///
+/// # Safety
+///
+/// path 必须为空指针或 mr_path_new 返回且尚未释放的路径，调用期间不能被其他线程访问。
+#[no_mangle]
+pub unsafe extern "C" fn mr_path_set_fill_rule(path: *mut Path, rule: u8) {
+    if let Some(path) = path.as_mut() {
+        path.set_fill_rule(if rule == 1 { FillRule::EvenOdd } else { FillRule::NonZero });
+    }
+}
+
 /// 绘制路径
 #[no_mangle]
 pub extern "C" fn mr_canvas_draw_path(
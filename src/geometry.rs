@@ -79,6 +79,13 @@ impl Rect {
         self.y < other.bottom() && self.bottom() > other.y
     }
 
+    /// 同时包含两个矩形的最小矩形
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect::new(x, y, self.right().max(other.right()) - x, self.bottom().max(other.bottom()) - y)
+    }
+
     pub fn inset(&self, dx: f32, dy: f32) -> Rect {
         Rect {
             x: self.x + dx,
@@ -88,3 +95,113 @@ impl Rect {
         }
     }
 }
+
+/// 环形扇区（环形图的一段），角度为弧度，从 x 轴正方向顺时针
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RingSegment {
+    pub cx: f32,
+    pub cy: f32,
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+/// 2D 仿射变换矩阵
+///
+/// x' = a * x + c * y + e
+/// y' = b * x + d * y + f
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+    pub const IDENTITY: Matrix = Matrix { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    pub const fn translate(tx: f32, ty: f32) -> Self {
+        Self { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: tx, f: ty }
+    }
+
+    pub const fn scale(sx: f32, sy: f32) -> Self {
+        Self { a: sx, b: 0.0, c: 0.0, d: sy, e: 0.0, f: 0.0 }
+    }
+
+    /// 旋转（角度，顺时针，y 轴向下）
+    pub fn rotate(deg: f32) -> Self {
+        let (sin, cos) = deg.to_radians().sin_cos();
+        Self { a: cos, b: sin, c: -sin, d: cos, e: 0.0, f: 0.0 }
+    }
+
+    /// 斜切（角度）
+    pub fn skew(x_deg: f32, y_deg: f32) -> Self {
+        Self { a: 1.0, b: y_deg.to_radians().tan(), c: x_deg.to_radians().tan(), d: 1.0, e: 0.0, f: 0.0 }
+    }
+
+    /// 矩阵相乘：返回 self * other，即先应用 other 再应用 self
+    pub fn concat(&self, other: &Matrix) -> Matrix {
+        Matrix {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            e: self.a * other.e + self.c * other.f + self.e,
+            f: self.b * other.e + self.d * other.f + self.f,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    pub fn map_point(&self, x: f32, y: f32) -> Point {
+        Point::new(self.a * x + self.c * y + self.e, self.b * x + self.d * y + self.f)
+    }
+
+    /// 矩形四个角变换后的包围盒
+    pub fn map_rect(&self, rect: &Rect) -> Rect {
+        let corners = [
+            self.map_point(rect.x, rect.y),
+            self.map_point(rect.right(), rect.y),
+            self.map_point(rect.x, rect.bottom()),
+            self.map_point(rect.right(), rect.bottom()),
+        ];
+        let (mut x0, mut y0, mut x1, mut y1) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for p in corners {
+            x0 = x0.min(p.x);
+            y0 = y0.min(p.y);
+            x1 = x1.max(p.x);
+            y1 = y1.max(p.y);
+        }
+        Rect::new(x0, y0, x1 - x0, y1 - y0)
+    }
+
+    /// 逆矩阵，不可逆（缩放为 0 等）时返回 None
+    pub fn invert(&self) -> Option<Matrix> {
+        let det = self.a * self.d - self.b * self.c;
+        if det.abs() < 1e-8 {
+            return None;
+        }
+        let inv = 1.0 / det;
+        Some(Matrix {
+            a: self.d * inv,
+            b: -self.b * inv,
+            c: -self.c * inv,
+            d: self.a * inv,
+            e: (self.c * self.f - self.d * self.e) * inv,
+            f: (self.b * self.e - self.a * self.f) * inv,
+        })
+    }
+}
@@ -4,6 +4,24 @@ use super::JsRuntime;
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 
+/// 设备信息（逻辑像素），wx.getSystemInfoSync 返回的数据来源
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemInfo {
+    pub window_width: u32,
+    pub window_height: u32,
+    /// 设备像素比
+    pub pixel_ratio: f32,
+    pub status_bar_height: f32,
+    /// 安全区域内边距：上、右、下、左
+    pub safe_area_insets: [f32; 4],
+}
+
+impl SystemInfo {
+    pub fn new(window_width: u32, window_height: u32) -> Self {
+        Self { window_width, window_height, pixel_ratio: 1.0, status_bar_height: 0.0, safe_area_insets: [0.0; 4] }
+    }
+}
+
 /// 小程序 API
 pub struct MiniAppApi {
     runtime: Arc<Mutex<JsRuntime>>,
@@ -18,21 +36,42 @@ impl MiniAppApi {
         }
     }
     
+    /// 更新 wx.getSystemInfoSync 返回的设备信息
+    pub fn set_system_info(&self, info: &SystemInfo) -> Result<(), String> {
+        let [top, right, bottom, left] = info.safe_area_insets;
+        let (w, h) = (info.window_width as f32, info.window_height as f32);
+        let rt = self.runtime.lock().unwrap();
+        rt.eval(&format!(
+            "__setSystemInfo({}, {}, {}, {}, {{ left: {}, right: {}, top: {}, bottom: {}, width: {}, height: {} }})",
+            info.window_width, info.window_height, info.pixel_ratio, info.status_bar_height,
+            left, w - right, top, h - bottom, (w - left - right).max(0.0), (h - top - bottom).max(0.0),
+        ))?;
+        Ok(())
+    }
+    
     /// 初始化所有 API
     pub fn init(&self) -> Result<(), String> {
-        println!("    init_console...");
+        log::trace!("    init_console...");
         self.init_console().map_err(|e| format!("console: {}", e))?;
-        println!("    init_wx_object...");
+        log::trace!("    init_wx_object...");
         self.init_wx_object().map_err(|e| format!("wx: {}", e))?;
-        println!("    init_timer...");
+        log::trace!("    init_timer...");
         self.init_timer_api().map_err(|e| format!("timer: {}", e))?;
-        println!("    init_storage...");
+        log::trace!("    init_storage...");
         self.init_storage_api().map_err(|e| format!("storage: {}", e))?;
-        println!("    init_ui...");
+        log::trace!("    init_auth...");
+        self.init_auth_api().map_err(|e| format!("auth: {}", e))?;
+        log::trace!("    init_bridge_call...");
+        self.init_bridge_call_api().map_err(|e| format!("bridge call: {}", e))?;
+        log::trace!("    init_network...");
+        self.init_network().map_err(|e| format!("network: {}", e))?;
+        log::trace!("    init_ui...");
         self.init_ui_api().map_err(|e| format!("ui: {}", e))?;
-        println!("    init_canvas...");
+        log::trace!("    init_canvas...");
         self.init_canvas_api().map_err(|e| format!("canvas: {}", e))?;
-        println!("    init_app...");
+        log::trace!("    init_animation...");
+        self.init_animation_api().map_err(|e| format!("animation: {}", e))?;
+        log::trace!("    init_app...");
         self.init_app().map_err(|e| format!("app: {}", e))?;
         Ok(())
     }
@@ -117,6 +156,32 @@ impl MiniAppApi {
                     if (timer.type === 'timeout') { delete __timers[id]; }
                 }
             }
+            
+            // 帧回调：由宿主每帧调用 __flushAnimationFrames，回调中再次注册的在下一帧执行
+            var __frameCallbacks = [];
+            var __frame_id = 0;
+            
+            function requestAnimationFrame(callback) {
+                var id = ++__frame_id;
+                __frameCallbacks.push({ id: id, callback: callback });
+                return id;
+            }
+            
+            function cancelAnimationFrame(id) {
+                __frameCallbacks = __frameCallbacks.filter(function(f) { return f.id !== id; });
+            }
+            
+            function __flushAnimationFrames(timestamp) {
+                var callbacks = __frameCallbacks;
+                __frameCallbacks = [];
+                for (var i = 0; i < callbacks.length; i++) {
+                    try { callbacks[i].callback(timestamp); } catch (e) { console.error('Animation frame error:', e); }
+                }
+                return __frameCallbacks.length > 0;
+            }
+            
+            wx.requestAnimationFrame = requestAnimationFrame;
+            wx.cancelAnimationFrame = cancelAnimationFrame;
         "#)?;
         Ok(())
     }
@@ -190,6 +255,140 @@ impl MiniAppApi {
         Ok(())
     }
     
+    fn init_auth_api(&self) -> Result<(), String> {
+        let rt = self.runtime.lock().unwrap();
+        rt.eval(r#"
+            // 等待宿主应答的授权回调 scope -> [options]
+            var __authCallbacks = {};
+            
+            wx.getSetting = function(options) {
+                options = options || {};
+                var authSetting = {};
+                if (typeof __native_get_setting === 'function') {
+                    try { authSetting = JSON.parse(__native_get_setting()); } catch(e) {}
+                }
+                var res = { authSetting: authSetting, errMsg: 'getSetting:ok' };
+                options.success && options.success(res);
+                options.complete && options.complete(res);
+            };
+            
+            wx.authorize = function(options) {
+                options = options || {};
+                var scope = options.scope;
+                if (!scope) {
+                    __finishAuthorize(options, false, 'authorize:fail invalid scope');
+                    return;
+                }
+                // 同一 scope 已在等待宿主应答，只追加回调
+                if (__authCallbacks[scope]) {
+                    __authCallbacks[scope].push(options);
+                    return;
+                }
+                var state = typeof __native_authorize === 'function' ? __native_authorize(scope) : 'denied';
+                if (state === 'pending') {
+                    __authCallbacks[scope] = [options];
+                    return;
+                }
+                __finishAuthorize(options, state === 'granted');
+            };
+            
+            function __finishAuthorize(options, granted, errMsg) {
+                var res = { errMsg: errMsg || (granted ? 'authorize:ok' : 'authorize:fail auth deny') };
+                if (granted) { options.success && options.success(res); }
+                else { options.fail && options.fail(res); }
+                options.complete && options.complete(res);
+            }
+            
+            // 宿主应答授权请求（由 MiniApp::resolve_auth 调用）
+            function __resolveAuth(scope, granted) {
+                var list = __authCallbacks[scope] || [];
+                delete __authCallbacks[scope];
+                for (var i = 0; i < list.length; i++) {
+                    __finishAuthorize(list[i], granted);
+                }
+            }
+        "#)?;
+        Ok(())
+    }
+    
+    fn init_bridge_call_api(&self) -> Result<(), String> {
+        let rt = self.runtime.lock().unwrap();
+        rt.eval(r#"
+            // 等待宿主应答的异步调用 id -> { name, options }
+            var __bridgeCalls = {};
+            var __nextBridgeCallId = 1;
+            
+            function __startBridgeCall(name, options) {
+                var id = __nextBridgeCallId++;
+                __bridgeCalls[id] = { name: name, options: options || {} };
+                return id;
+            }
+            
+            // 宿主应答异步调用（由 MiniApp::update 在 complete_bridge_call 之后调用）
+            function __completeBridgeCall(id, ok, res) {
+                var call = __bridgeCalls[id];
+                if (!call) return;
+                delete __bridgeCalls[id];
+                res = res || {};
+                if (!res.errMsg) res.errMsg = call.name + (ok ? ':ok' : ':fail');
+                if (ok) { call.options.success && call.options.success(res); }
+                else { call.options.fail && call.options.fail(res); }
+                call.options.complete && call.options.complete(res);
+            }
+            
+            wx.requestPayment = function(options) {
+                options = options || {};
+                var id = __startBridgeCall('requestPayment', options);
+                var order = {
+                    timeStamp: options.timeStamp,
+                    nonceStr: options.nonceStr,
+                    package: options.package,
+                    signType: options.signType || 'MD5',
+                    paySign: options.paySign
+                };
+                if (typeof __native_request_payment === 'function') {
+                    __native_request_payment(String(id), JSON.stringify(order));
+                } else {
+                    __completeBridgeCall(id, false, { errMsg: 'requestPayment:fail not supported' });
+                }
+            };
+        "#)?;
+        Ok(())
+    }
+    
+    /// wx.request：请求在宿主后台线程执行，完成后在下一次 update 中回调
+    fn init_network(&self) -> Result<(), String> {
+        let rt = self.runtime.lock().unwrap();
+        rt.eval(r#"
+            wx.request = function(options) {
+                options = options || {};
+                var id = __startBridgeCall('request', options);
+                var task = {
+                    abort: function() { __completeBridgeCall(id, false, { errMsg: 'request:fail abort' }); }
+                };
+                if (!options.url) {
+                    __completeBridgeCall(id, false, { errMsg: 'request:fail invalid url' });
+                    return task;
+                }
+                var req = {
+                    url: String(options.url),
+                    method: String(options.method || 'GET').toUpperCase(),
+                    header: options.header || {},
+                    timeout: options.timeout || 60000,
+                    dataType: options.dataType || 'json'
+                };
+                if (options.data !== undefined && options.data !== null) { req.data = options.data; }
+                if (typeof __native_request === 'function') {
+                    __native_request(String(id), JSON.stringify(req));
+                } else {
+                    __completeBridgeCall(id, false, { errMsg: 'request:fail not supported' });
+                }
+                return task;
+            };
+        "#)?;
+        Ok(())
+    }
+    
     fn init_ui_api(&self) -> Result<(), String> {
         let rt = self.runtime.lock().unwrap();
         rt.eval(r#"
@@ -201,6 +400,9 @@ impl MiniAppApi {
             var __modalVisible = false;
             var __modalConfig = null;
             var __modalCallback = null;
+            var __actionSheetVisible = false;
+            var __actionSheetConfig = null;
+            var __actionSheetCallback = null;
             
             wx.showToast = function(options) {
                 options = options || {};
@@ -286,22 +488,52 @@ impl MiniAppApi {
             
             wx.showActionSheet = function(options) {
                 options = options || {};
-                __native_print('[ActionSheet] ' + (options.itemList || []).join(', '));
-                options.success && options.success({ tapIndex: 0 });
-                options.complete && options.complete();
+                var itemList = (options.itemList || []).map(String);
+                if (itemList.length === 0 || itemList.length > 6) {
+                    var err = { errMsg: 'showActionSheet:fail parameter error: itemList should have 1 to 6 items' };
+                    options.fail && options.fail(err);
+                    options.complete && options.complete(err);
+                    return;
+                }
+                __actionSheetVisible = true;
+                __actionSheetConfig = { itemList: itemList, itemColor: options.itemColor || '#000000' };
+                __actionSheetCallback = options;
+                
+                if (typeof __native_show_action_sheet === 'function') {
+                    __native_show_action_sheet(JSON.stringify(itemList), __actionSheetConfig.itemColor);
+                }
+                __native_print('[ActionSheet] ' + itemList.join(', '));
             };
             
+            // tapIndex 为 -1 表示取消（点击取消按钮或遮罩）
+            function __handleActionSheetResult(tapIndex) {
+                var callback = __actionSheetCallback;
+                __actionSheetVisible = false;
+                __actionSheetConfig = null;
+                __actionSheetCallback = null;
+                if (!callback) return;
+                if (tapIndex >= 0) {
+                    var res = { errMsg: 'showActionSheet:ok', tapIndex: tapIndex };
+                    callback.success && callback.success(res);
+                    callback.complete && callback.complete(res);
+                } else {
+                    var err = { errMsg: 'showActionSheet:fail cancel' };
+                    callback.fail && callback.fail(err);
+                    callback.complete && callback.complete(err);
+                }
+            }
+            
             // 下拉刷新相关
             wx.startPullDownRefresh = function(options) {
                 options = options || {};
-                __native_print('[PullDownRefresh] start');
+                if (typeof __native_start_pull_down_refresh === 'function') { __native_start_pull_down_refresh(); }
                 options.success && options.success();
                 options.complete && options.complete();
             };
             
             wx.stopPullDownRefresh = function(options) {
                 options = options || {};
-                __native_print('[PullDownRefresh] stop');
+                if (typeof __native_stop_pull_down_refresh === 'function') { __native_stop_pull_down_refresh(); }
                 options.success && options.success();
                 options.complete && options.complete();
             };
@@ -310,7 +542,8 @@ impl MiniAppApi {
                 return JSON.stringify({
                     toast: __toastVisible ? __toastConfig : null,
                     loading: __loadingVisible ? __loadingConfig : null,
-                    modal: __modalVisible ? __modalConfig : null
+                    modal: __modalVisible ? __modalConfig : null,
+                    actionSheet: __actionSheetVisible ? __actionSheetConfig : null
                 });
             }
         "#)?;
@@ -321,17 +554,63 @@ impl MiniAppApi {
         let rt = self.runtime.lock().unwrap();
         rt.eval(r#"
             var __canvasContexts = {};
+
+            // 导出在 draw() 之后执行，与绘制命令按调用顺序处理
+            wx.canvasToTempFilePath = function(options, component) {
+                options = options || {};
+                var id = __startBridgeCall('canvasToTempFilePath', options);
+                if (!options.canvasId) {
+                    __completeBridgeCall(id, false, { errMsg: 'canvasToTempFilePath:fail canvasId is required' });
+                    return;
+                }
+                var req = { canvasId: String(options.canvasId) };
+                ['x', 'y', 'width', 'height', 'destWidth', 'destHeight'].forEach(function(key) {
+                    if (typeof options[key] === 'number') { req[key] = options[key]; }
+                });
+                if (typeof __native_canvas_to_temp_file === 'function') {
+                    __native_canvas_to_temp_file(String(id), JSON.stringify(req));
+                } else {
+                    __completeBridgeCall(id, false, { errMsg: 'canvasToTempFilePath:fail not supported' });
+                }
+            };
+
+            // 渐变对象：颜色停止点记录在 stops 中，随 setFillStyle 命令序列化给原生层
+            function __createCanvasGradient(desc) {
+                desc.stops = [];
+                desc.addColorStop = function(offset, color) { this.stops.push({ offset: offset, color: color }); };
+                return desc;
+            }
             
             wx.createCanvasContext = function(canvasId, component) {
                 var ctx = {
                     _canvasId: canvasId,
                     _commands: [],
-                    setFillStyle: function(color) { this._commands.push({ type: 'setFillStyle', color: color }); return this; },
-                    setStrokeStyle: function(color) { this._commands.push({ type: 'setStrokeStyle', color: color }); return this; },
+                    _fontSize: 10,
+                    setFillStyle: function(style) {
+                        if (style && typeof style === 'object') { this._commands.push({ type: 'setFillStyle', gradient: style }); }
+                        else { this._commands.push({ type: 'setFillStyle', color: style }); }
+                        return this;
+                    },
+                    setStrokeStyle: function(style) {
+                        if (style && typeof style === 'object') { this._commands.push({ type: 'setStrokeStyle', gradient: style }); }
+                        else { this._commands.push({ type: 'setStrokeStyle', color: style }); }
+                        return this;
+                    },
+                    createLinearGradient: function(x0, y0, x1, y1) {
+                        return __createCanvasGradient({ kind: 'linear', x0: x0, y0: y0, x1: x1, y1: y1 });
+                    },
+                    createCircularGradient: function(x, y, r) {
+                        return __createCanvasGradient({ kind: 'radial', x0: x, y0: y, r0: 0, x1: x, y1: y, r1: r });
+                    },
+                    createRadialGradient: function(x0, y0, r0, x1, y1, r1) {
+                        return __createCanvasGradient({ kind: 'radial', x0: x0, y0: y0, r0: r0, x1: x1, y1: y1, r1: r1 });
+                    },
                     setLineWidth: function(width) { this._commands.push({ type: 'setLineWidth', width: width }); return this; },
                     setLineCap: function(cap) { this._commands.push({ type: 'setLineCap', cap: cap }); return this; },
                     setLineJoin: function(join) { this._commands.push({ type: 'setLineJoin', join: join }); return this; },
-                    setFontSize: function(size) { this._commands.push({ type: 'setFontSize', size: size }); return this; },
+                    setMiterLimit: function(limit) { this._commands.push({ type: 'setMiterLimit', limit: limit }); return this; },
+                    setLineDash: function(pattern, offset) { this._commands.push({ type: 'setLineDash', pattern: pattern || [], offset: offset || 0 }); return this; },
+                    setFontSize: function(size) { this._fontSize = size; this._commands.push({ type: 'setFontSize', size: size }); return this; },
                     setTextAlign: function(align) { this._commands.push({ type: 'setTextAlign', align: align }); return this; },
                     setTextBaseline: function(baseline) { this._commands.push({ type: 'setTextBaseline', baseline: baseline }); return this; },
                     setGlobalAlpha: function(alpha) { this._commands.push({ type: 'setGlobalAlpha', alpha: alpha }); return this; },
@@ -345,10 +624,15 @@ impl MiniAppApi {
                     arc: function(x, y, r, s, e, cc) { this._commands.push({ type: 'arc', x: x, y: y, r: r, sAngle: s, eAngle: e, counterclockwise: cc || false }); return this; },
                     quadraticCurveTo: function(cpx, cpy, x, y) { this._commands.push({ type: 'quadraticCurveTo', cpx: cpx, cpy: cpy, x: x, y: y }); return this; },
                     bezierCurveTo: function(cp1x, cp1y, cp2x, cp2y, x, y) { this._commands.push({ type: 'bezierCurveTo', cp1x: cp1x, cp1y: cp1y, cp2x: cp2x, cp2y: cp2y, x: x, y: y }); return this; },
-                    fill: function() { this._commands.push({ type: 'fill' }); return this; },
+                    fill: function(rule) { this._commands.push({ type: 'fill', rule: rule || 'nonzero' }); return this; },
                     stroke: function() { this._commands.push({ type: 'stroke' }); return this; },
                     fillText: function(text, x, y, maxWidth) { this._commands.push({ type: 'fillText', text: text, x: x, y: y, maxWidth: maxWidth }); return this; },
                     strokeText: function(text, x, y, maxWidth) { this._commands.push({ type: 'strokeText', text: text, x: x, y: y, maxWidth: maxWidth }); return this; },
+                    measureText: function(text) {
+                        var width = typeof __native_canvas_measure_text === 'function'
+                            ? parseFloat(__native_canvas_measure_text(String(text), String(this._fontSize))) : 0;
+                        return { width: width || 0 };
+                    },
                     drawImage: function(src, sx, sy, sw, sh, dx, dy, dw, dh) {
                         if (arguments.length === 3) { this._commands.push({ type: 'drawImage', src: src, dx: sx, dy: sy }); }
                         else if (arguments.length === 5) { this._commands.push({ type: 'drawImage', src: src, dx: sx, dy: sy, dWidth: sw, dHeight: sh }); }
@@ -375,6 +659,64 @@ impl MiniAppApi {
         Ok(())
     }
     
+    /// 初始化动画 API（wx.createAnimation），export() 的结果通过 animation 属性交给渲染器播放
+    fn init_animation_api(&self) -> Result<(), String> {
+        let rt = self.runtime.lock().unwrap();
+        rt.eval(r#"
+            wx.createAnimation = function(options) {
+                options = options || {};
+                var defaults = {
+                    duration: options.duration !== undefined ? options.duration : 400,
+                    timingFunction: options.timingFunction || 'linear',
+                    delay: options.delay || 0,
+                    transformOrigin: options.transformOrigin || '50% 50% 0'
+                };
+                var anim = {
+                    _actions: [],
+                    _current: [],
+                    _push: function(type, args) { this._current.push({ type: type, args: args }); return this; },
+                    translate: function(x, y) { return this._push('translate', [x || 0, y || 0]); },
+                    translateX: function(x) { return this._push('translateX', [x || 0]); },
+                    translateY: function(y) { return this._push('translateY', [y || 0]); },
+                    scale: function(sx, sy) { return this._push('scale', [sx, sy === undefined ? sx : sy]); },
+                    scaleX: function(s) { return this._push('scaleX', [s]); },
+                    scaleY: function(s) { return this._push('scaleY', [s]); },
+                    rotate: function(deg) { return this._push('rotate', [deg || 0]); },
+                    skew: function(x, y) { return this._push('skew', [x || 0, y || 0]); },
+                    skewX: function(x) { return this._push('skewX', [x || 0]); },
+                    skewY: function(y) { return this._push('skewY', [y || 0]); },
+                    opacity: function(v) { return this._push('opacity', [v]); },
+                    step: function(opts) {
+                        opts = opts || {};
+                        this._actions.push({
+                            animates: this._current,
+                            option: {
+                                transition: {
+                                    duration: opts.duration !== undefined ? opts.duration : defaults.duration,
+                                    timingFunction: opts.timingFunction || defaults.timingFunction,
+                                    delay: opts.delay !== undefined ? opts.delay : defaults.delay
+                                },
+                                transformOrigin: opts.transformOrigin || defaults.transformOrigin
+                            }
+                        });
+                        this._current = [];
+                        return this;
+                    },
+                    export: function() {
+                        if (this._current.length > 0) { this.step(); }
+                        var actions = this._actions;
+                        this._actions = [];
+                        // 同一组步骤再次 export 时也视为新动画
+                        return { actions: actions, id: ++__animationSeq };
+                    }
+                };
+                return anim;
+            };
+            var __animationSeq = 0;
+        "#)?;
+        Ok(())
+    }
+    
     /// 初始化 App API
     fn init_app(&self) -> Result<(), String> {
         let rt = self.runtime.lock().unwrap();
@@ -384,19 +726,96 @@ impl MiniAppApi {
             var __pages = {};
             var __currentPage = null;
             var __pendingNavigation = null;
+            // 最近一次事件处理函数的返回值（bindinput 返回字符串时替换输入框的值）
+            var __lastEventResult;
+            
+            // 启动参数，宿主通过 MiniApp::set_launch_options 设置
+            var __launchOptions = { path: '', query: {}, scene: 1001, referrerInfo: {} };
+            var __enterOptions = __launchOptions;
+            var __appShowListeners = [];
+            var __appHideListeners = [];
             
             function App(config) {
                 __app = config;
                 if (config.onLaunch) {
-                    config.onLaunch();
+                    config.onLaunch(__launchOptions);
                 }
                 return __app;
             }
             
+            // 切到前台（供 native 调用）：先回调 App.onShow，再通知 wx.onAppShow 监听器
+            function __appShow(options) {
+                __enterOptions = options || __launchOptions;
+                if (__app && typeof __app.onShow === 'function') {
+                    try { __app.onShow(__enterOptions); } catch (e) { __native_print('[Error] App.onShow: ' + e.message); }
+                }
+                __appShowListeners.slice().forEach(function(cb) {
+                    try { cb(__enterOptions); } catch (e) { __native_print('[Error] onAppShow: ' + e.message); }
+                });
+                __showPage(__pageStack[__pageStack.length - 1]);
+            }
+            
+            // 切到后台（供 native 调用）：栈顶页面先 onHide
+            function __appHide() {
+                __hidePage(__pageStack[__pageStack.length - 1]);
+                if (__app && typeof __app.onHide === 'function') {
+                    try { __app.onHide(); } catch (e) { __native_print('[Error] App.onHide: ' + e.message); }
+                }
+                __appHideListeners.slice().forEach(function(cb) {
+                    try { cb(); } catch (e) { __native_print('[Error] onAppHide: ' + e.message); }
+                });
+            }
+            
+            wx.onAppShow = function(callback) {
+                if (typeof callback === 'function') __appShowListeners.push(callback);
+            };
+            wx.offAppShow = function(callback) {
+                __appShowListeners = callback ? __appShowListeners.filter(function(cb) { return cb !== callback; }) : [];
+            };
+            wx.onAppHide = function(callback) {
+                if (typeof callback === 'function') __appHideListeners.push(callback);
+            };
+            wx.offAppHide = function(callback) {
+                __appHideListeners = callback ? __appHideListeners.filter(function(cb) { return cb !== callback; }) : [];
+            };
+            wx.getLaunchOptionsSync = function() { return __launchOptions; };
+            wx.getEnterOptionsSync = function() { return __enterOptions; };
+            
             function getApp() {
                 return __app;
             }
             
+            // 解析数据路径：'a.b[0].c' → ['a', 'b', 0, 'c']
+            function __parseDataPath(path) {
+                var segments = [];
+                var re = /([^.\[\]]+)|\[(\d+)\]/g;
+                var m;
+                while ((m = re.exec(path)) !== null) {
+                    segments.push(m[2] !== undefined ? parseInt(m[2], 10) : m[1]);
+                }
+                return segments;
+            }
+            
+            // 按路径写入数据，中间缺失（或不是对象）的节点按下一段创建为数组或对象
+            function __setDataPath(data, path, value) {
+                var segments = __parseDataPath(path);
+                if (segments.length <= 1) {
+                    data[path] = value;
+                    return;
+                }
+                var target = data;
+                for (var i = 0; i < segments.length - 1; i++) {
+                    var seg = segments[i];
+                    var next = target[seg];
+                    if (next === null || typeof next !== 'object') {
+                        next = typeof segments[i + 1] === 'number' ? [] : {};
+                        target[seg] = next;
+                    }
+                    target = next;
+                }
+                target[segments[segments.length - 1]] = value;
+            }
+            
             function Page(config) {
                 // 创建页面实例
                 var page = {
@@ -404,10 +823,10 @@ impl MiniAppApi {
                     
                     // setData 方法 - 更新数据并触发重新渲染
                     setData: function(newData, callback) {
-                        // 合并数据
+                        // 合并数据，key 可以是 'list[0].done' 形式的路径
                         for (var key in newData) {
                             if (newData.hasOwnProperty(key)) {
-                                this.data[key] = newData[key];
+                                __setDataPath(this.data, key, newData[key]);
                             }
                         }
                         // 通知 native 层数据更新
@@ -438,17 +857,71 @@ impl MiniAppApi {
                 return page;
             }
             
+            // 页面栈：宿主的页面跳转通过 __pushPage / __popPages / __unloadPages 驱动生命周期
+            var __pageStack = [];
+            
             function getCurrentPages() {
+                if (__pageStack.length > 0) { return __pageStack.slice(); }
                 return __currentPage ? [__currentPage] : [];
             }
             
+            function __callLifecycle(page, name, arg) {
+                if (page && typeof page[name] === 'function') {
+                    try { page[name](arg); } catch (e) { __native_print('[Error] ' + name + ': ' + e.message); }
+                }
+            }
+            
+            function __showPage(page) {
+                if (page && !page.__shown) { page.__shown = true; __callLifecycle(page, 'onShow'); }
+            }
+            
+            function __hidePage(page) {
+                if (page && page.__shown) { page.__shown = false; __callLifecycle(page, 'onHide'); }
+            }
+            
+            // 新页面入栈：原栈顶 onHide，新页面 onLoad → onShow
+            function __pushPage(route, query) {
+                var page = __currentPage;
+                if (!page || __pageStack.indexOf(page) >= 0) { page = Page({}); }
+                __hidePage(__pageStack[__pageStack.length - 1]);
+                page.route = route;
+                page.options = query || {};
+                __pageStack.push(page);
+                __currentPage = page;
+                __callLifecycle(page, 'onLoad', page.options);
+                __showPage(page);
+            }
+            
+            // 关闭栈顶的 count 个页面（只触发 onUnload，不触发 onHide），不显示下面的页面，供 redirectTo / reLaunch 使用
+            function __unloadPages(count) {
+                for (var i = 0; i < count && __pageStack.length > 0; i++) {
+                    var page = __pageStack.pop();
+                    page.__shown = false;
+                    __callLifecycle(page, 'onUnload');
+                }
+                __currentPage = __pageStack[__pageStack.length - 1] || null;
+            }
+            
+            // 返回 delta 层，至少保留一个页面，回到的页面 onShow
+            function __popPages(delta) {
+                __unloadPages(Math.min(delta, __pageStack.length - 1));
+                __showPage(__currentPage);
+            }
+            
+            // 栈顶页面首次渲染完成
+            function __pageReady() {
+                var page = __pageStack[__pageStack.length - 1];
+                if (page && !page.__ready) { page.__ready = true; __callLifecycle(page, 'onReady'); }
+            }
+            
             // 获取当前页面实例（供 native 调用）
             function __getPageInstance() {
                 return __currentPage;
             }
             
             // 调用页面方法（供 native 调用事件处理）
-            function __callPageMethod(methodName, eventData) {
+            // extra: 额外的事件字段（如触摸事件的 type/touches/changedTouches）
+            function __callPageMethod(methodName, eventData, extra) {
                 if (__currentPage && typeof __currentPage[methodName] === 'function') {
                     // 确保 eventData 是对象
                     if (typeof eventData === 'string') {
@@ -497,8 +970,14 @@ impl MiniAppApi {
                         },
                         detail: dataset
                     };
+                    if (extra) {
+                        for (var k in extra) {
+                            if (extra.hasOwnProperty(k)) event[k] = extra[k];
+                        }
+                    }
+                    __lastEventResult = undefined;
                     try {
-                        __currentPage[methodName](event);
+                        __lastEventResult = __currentPage[methodName](event);
                     } catch (e) {
                         __native_print('[Error] ' + methodName + ': ' + e.message);
                     }
@@ -507,6 +986,15 @@ impl MiniAppApi {
                 return false;
             }
             
+            // 取出 bindinput 的返回值：字符串或数字，或带 value 字段的对象；没有时返回空字符串
+            function __takeInputResult() {
+                var r = __lastEventResult;
+                __lastEventResult = undefined;
+                if (r && typeof r === 'object') r = r.value;
+                if (typeof r === 'number') r = String(r);
+                return typeof r === 'string' ? JSON.stringify(r) : '';
+            }
+            
             // 获取页面数据（供 native 调用）
             function __getPageData() {
                 if (__currentPage) {
@@ -515,70 +1003,66 @@ impl MiniAppApi {
                 return '{}';
             }
             
-            // 导航 API
-            wx.navigateTo = function(options) {
-                __pendingNavigation = {
-                    type: 'navigateTo',
-                    url: options.url
-                };
-                __native_print('[Navigate] navigateTo: ' + options.url);
-                options.success && options.success();
-            };
-            
-            wx.navigateBack = function(options) {
+            // 导航 API：请求写入 __pendingNavigation，由宿主在下一帧取出执行
+            function __requestNavigation(type, options, needUrl) {
                 options = options || {};
-                __pendingNavigation = {
-                    type: 'navigateBack',
-                    delta: options.delta || 1
-                };
-                __native_print('[Navigate] navigateBack');
-                options.success && options.success();
-            };
+                var res;
+                if (needUrl && !options.url) {
+                    res = { errMsg: type + ':fail url is required' };
+                    options.fail && options.fail(res);
+                } else {
+                    __pendingNavigation = { type: type, url: options.url || '' };
+                    if (type === 'navigateBack') { __pendingNavigation.delta = Math.max(1, options.delta || 1); }
+                    __native_print('[Navigate] ' + type + (options.url ? ': ' + options.url : ''));
+                    res = { errMsg: type + ':ok' };
+                    options.success && options.success(res);
+                }
+                options.complete && options.complete(res);
+            }
             
-            wx.switchTab = function(options) {
-                __pendingNavigation = {
-                    type: 'switchTab',
-                    url: options.url
-                };
-                __native_print('[Navigate] switchTab: ' + options.url);
-                options.success && options.success();
-            };
+            wx.navigateTo = function(options) { __requestNavigation('navigateTo', options, true); };
+            wx.redirectTo = function(options) { __requestNavigation('redirectTo', options, true); };
+            wx.navigateBack = function(options) { __requestNavigation('navigateBack', options, false); };
+            wx.switchTab = function(options) { __requestNavigation('switchTab', options, true); };
+            wx.reLaunch = function(options) { __requestNavigation('reLaunch', options, true); };
             
-            wx.redirectTo = function(options) {
-                __pendingNavigation = {
-                    type: 'navigateTo',
-                    url: options.url
-                };
-                __native_print('[Navigate] redirectTo: ' + options.url);
-                options.success && options.success();
+            // 系统信息 API：设备参数由宿主通过 __setSystemInfo 设置
+            var __systemInfo = {
+                brand: 'devtools',
+                model: 'mini-app',
+                platform: 'mini-app',
+                system: 'mini-app',
+                version: '1.0.0',
+                SDKVersion: '1.0.0',
+                language: 'zh_CN',
+                screenWidth: 375,
+                screenHeight: 667,
+                windowWidth: 375,
+                windowHeight: 667,
+                pixelRatio: 1,
+                statusBarHeight: 0,
+                safeArea: { left: 0, right: 375, top: 0, bottom: 667, width: 375, height: 667 }
             };
             
-            wx.reLaunch = function(options) {
-                __pendingNavigation = {
-                    type: 'switchTab',
-                    url: options.url
-                };
-                __native_print('[Navigate] reLaunch: ' + options.url);
-                options.success && options.success();
-            };
+            function __setSystemInfo(width, height, pixelRatio, statusBarHeight, safeArea) {
+                __systemInfo.screenWidth = __systemInfo.windowWidth = width;
+                __systemInfo.screenHeight = __systemInfo.windowHeight = height;
+                __systemInfo.pixelRatio = pixelRatio;
+                __systemInfo.statusBarHeight = statusBarHeight;
+                __systemInfo.safeArea = safeArea;
+            }
             
-            // 系统信息 API
+            // 返回副本，页面修改返回值不影响后续调用
             wx.getSystemInfoSync = function() {
-                return {
-                    platform: 'mini-app',
-                    version: '1.0.0',
-                    SDKVersion: '1.0.0',
-                    windowWidth: 375,
-                    windowHeight: 667,
-                    pixelRatio: 2
-                };
+                return JSON.parse(JSON.stringify(__systemInfo));
             };
             
             wx.getSystemInfo = function(options) {
                 options = options || {};
                 var info = wx.getSystemInfoSync();
+                info.errMsg = 'getSystemInfo:ok';
                 options.success && options.success(info);
-                options.complete && options.complete();
+                options.complete && options.complete(info);
             };
         "#)?;
         
@@ -12,6 +12,8 @@ pub struct JsBridge {
     component_tree: Arc<Mutex<ComponentTree>>,
     storage: Arc<Mutex<HashMap<String, String>>>,
     event_queue: Arc<Mutex<Vec<BridgeEvent>>>,
+    /// 授权状态 scope -> 是否授权（未记录的 scope 视为未授权）
+    auth_settings: Arc<Mutex<HashMap<String, bool>>>,
 }
 
 /// 桥接事件
@@ -25,6 +27,8 @@ pub enum BridgeEvent {
     ShowLoading { title: String, mask: bool },
     HideLoading,
     ShowModal { title: String, content: String, show_cancel: bool, cancel_text: String, confirm_text: String },
+    /// 显示操作菜单，由宿主通过 MiniApp::resolve_action_sheet 应答
+    ShowActionSheet { item_list: Vec<String>, item_color: String },
     NavigateTo(String),
     NavigateBack(u32),
     SetTimer { id: u32, delay: u32, repeat: bool },
@@ -34,6 +38,16 @@ pub enum BridgeEvent {
     StorageGet { key: String },
     StorageRemove { key: String },
     StorageClear,
+    /// 请求用户授权，由宿主通过 MiniApp::resolve_auth 应答
+    AuthRequest { scope: String },
+    StartPullDownRefresh,
+    StopPullDownRefresh,
+    /// 发起支付，由宿主通过 MiniApp::complete_bridge_call(id, ..) 应答
+    RequestPayment { id: u32, order: serde_json::Value },
+    /// wx.request 网络请求，由 MiniApp 在后台线程执行后应答
+    Request { id: u32, request: serde_json::Value },
+    /// 把 canvas 导出为临时 PNG 文件，由 MiniApp 在绘制命令之后应答
+    CanvasToTempFile { id: u32, options: serde_json::Value },
 }
 
 impl JsBridge {
@@ -43,19 +57,24 @@ impl JsBridge {
             component_tree: Arc::new(Mutex::new(ComponentTree::new())),
             storage: Arc::new(Mutex::new(HashMap::new())),
             event_queue: Arc::new(Mutex::new(Vec::new())),
+            auth_settings: Arc::new(Mutex::new(HashMap::new())),
         }
     }
     
     /// 初始化 native 函数
     pub fn init(&self) -> Result<(), String> {
-        println!("    register_print_function...");
+        log::trace!("    register_print_function...");
         self.register_print_function().map_err(|e| format!("print: {}", e))?;
-        println!("    register_timer_functions...");
+        log::trace!("    register_timer_functions...");
         self.register_timer_functions().map_err(|e| format!("timer: {}", e))?;
-        println!("    register_storage_functions...");
+        log::trace!("    register_storage_functions...");
         self.register_storage_functions().map_err(|e| format!("storage: {}", e))?;
-        println!("    register_ui_functions...");
+        log::trace!("    register_ui_functions...");
         self.register_ui_functions().map_err(|e| format!("ui: {}", e))?;
+        log::trace!("    register_auth_functions...");
+        self.register_auth_functions().map_err(|e| format!("auth: {}", e))?;
+        log::trace!("    register_async_functions...");
+        self.register_async_functions().map_err(|e| format!("async: {}", e))?;
         Ok(())
     }
     
@@ -174,6 +193,19 @@ impl JsBridge {
             "undefined".to_string()
         })?;
         
+        // start/stopPullDownRefresh
+        let q = queue.clone();
+        rt.register_function("__native_start_pull_down_refresh", move |_args| {
+            q.lock().unwrap().push(BridgeEvent::StartPullDownRefresh);
+            "undefined".to_string()
+        })?;
+        
+        let q = queue.clone();
+        rt.register_function("__native_stop_pull_down_refresh", move |_args| {
+            q.lock().unwrap().push(BridgeEvent::StopPullDownRefresh);
+            "undefined".to_string()
+        })?;
+        
         // showModal
         let q = queue.clone();
         rt.register_function("__native_show_modal", move |args| {
@@ -186,6 +218,17 @@ impl JsBridge {
             "undefined".to_string()
         })?;
         
+        // showActionSheet
+        let q = queue.clone();
+        rt.register_function("__native_show_action_sheet", move |args| {
+            let item_list = args.first()
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or_default();
+            let item_color = args.get(1).cloned().unwrap_or_else(|| "#000000".to_string());
+            q.lock().unwrap().push(BridgeEvent::ShowActionSheet { item_list, item_color });
+            "undefined".to_string()
+        })?;
+        
         // Canvas 绘制
         let q = queue.clone();
         rt.register_function("__native_canvas_draw", move |args| {
@@ -194,10 +237,93 @@ impl JsBridge {
             q.lock().unwrap().push(BridgeEvent::CanvasDraw { canvas_id, commands });
             "undefined".to_string()
         })?;
+
+        // Canvas 导出为临时文件
+        let q = queue.clone();
+        rt.register_function("__native_canvas_to_temp_file", move |args| {
+            let id: u32 = args.first().and_then(|s| s.parse().ok()).unwrap_or(0);
+            let options = args.get(1)
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or(serde_json::Value::Null);
+            q.lock().unwrap().push(BridgeEvent::CanvasToTempFile { id, options });
+            "undefined".to_string()
+        })?;
+
+        // Canvas 文本测量（同步返回宽度）
+        rt.register_function("__native_canvas_measure_text", move |args| {
+            let text = args.first().cloned().unwrap_or_default();
+            let size = args.get(1).and_then(|s| s.parse::<f32>().ok()).unwrap_or(10.0);
+            let width = crate::TextRenderer::shared().map_or(0.0, |tr| tr.measure_text(&text, size));
+            width.to_string()
+        })?;
         
         Ok(())
     }
     
+    fn register_auth_functions(&self) -> Result<(), String> {
+        let rt = self.runtime.lock().unwrap();
+        
+        // getSetting: 返回 authSetting JSON
+        let a = self.auth_settings.clone();
+        rt.register_function("__native_get_setting", move |_args| {
+            serde_json::to_string(&*a.lock().unwrap()).unwrap_or_else(|_| "{}".to_string())
+        })?;
+        
+        // authorize: 已有记录直接返回结果，否则向宿主发起授权请求
+        let a = self.auth_settings.clone();
+        let q = self.event_queue.clone();
+        rt.register_function("__native_authorize", move |args| {
+            let scope = args.first().cloned().unwrap_or_default();
+            match a.lock().unwrap().get(&scope) {
+                Some(true) => "granted".to_string(),
+                Some(false) => "denied".to_string(),
+                None => {
+                    q.lock().unwrap().push(BridgeEvent::AuthRequest { scope });
+                    "pending".to_string()
+                }
+            }
+        })?;
+        
+        Ok(())
+    }
+    
+    /// 由宿主应答的异步 API，调用 ID 由 JS 侧分配
+    fn register_async_functions(&self) -> Result<(), String> {
+        let rt = self.runtime.lock().unwrap();
+        
+        let q = self.event_queue.clone();
+        rt.register_function("__native_request_payment", move |args| {
+            let id: u32 = args.first().and_then(|s| s.parse().ok()).unwrap_or(0);
+            let order = args.get(1)
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or(serde_json::Value::Null);
+            q.lock().unwrap().push(BridgeEvent::RequestPayment { id, order });
+            "undefined".to_string()
+        })?;
+        
+        let q = self.event_queue.clone();
+        rt.register_function("__native_request", move |args| {
+            let id: u32 = args.first().and_then(|s| s.parse().ok()).unwrap_or(0);
+            let request = args.get(1)
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or(serde_json::Value::Null);
+            q.lock().unwrap().push(BridgeEvent::Request { id, request });
+            "undefined".to_string()
+        })?;
+        
+        Ok(())
+    }
+    
+    /// 记录授权结果
+    pub fn set_auth(&self, scope: &str, granted: bool) {
+        self.auth_settings.lock().unwrap().insert(scope.to_string(), granted);
+    }
+    
+    /// 获取授权状态
+    pub fn auth_settings(&self) -> Arc<Mutex<HashMap<String, bool>>> {
+        self.auth_settings.clone()
+    }
+    
     /// 获取并清空事件队列
     pub fn drain_events(&self) -> Vec<BridgeEvent> {
         let mut queue = self.event_queue.lock().unwrap();
@@ -2,8 +2,10 @@
 
 mod runtime;
 mod api;
+mod wxs;
 pub mod bridge;
 
 pub use runtime::JsRuntime;
-pub use api::MiniAppApi;
+pub use api::{MiniAppApi, SystemInfo};
+pub use wxs::WxsRuntime;
 pub use bridge::{JsBridge, BridgeEvent};
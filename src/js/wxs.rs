@@ -0,0 +1,87 @@
+//! WXS 模块运行时
+//!
+//! `<wxs module="m">` 和 `.wxs` 文件在独立的 QuickJS 上下文中执行，与页面逻辑隔离。
+//! 上下文中只保留 WXS 允许的全局对象（Math、JSON、Number、String、Array、
+//! parseInt 等），Date / RegExp 只能通过 getDate() / getRegExp() 创建。
+//! 模板表达式中的 `{{m.format(price)}}` 通过 `call` 调用模块导出的函数，
+//! 参数和返回值以 JSON 传递。
+
+use super::JsRuntime;
+use serde_json::Value as JsonValue;
+use std::collections::HashSet;
+
+/// 初始化沙箱：保存模块注册和调用入口，移除 WXS 中不可用的全局对象
+const SANDBOX_PRELUDE: &str = r#"
+(function () {
+    var NativeDate = Date;
+    var NativeRegExp = RegExp;
+    var modules = {};
+    var g = globalThis;
+    g.getDate = function (...args) { return new NativeDate(...args); };
+    g.getRegExp = function (pattern, flags) { return new NativeRegExp(pattern, flags); };
+    Object.defineProperty(g, '__wxs_define', {
+        value: function (name, factory) {
+            var module = { exports: {} };
+            factory(module, module.exports);
+            modules[name] = module.exports;
+        }
+    });
+    Object.defineProperty(g, '__wxs_call', {
+        value: function (name, func, args) {
+            var result = modules[name][func].apply(null, args);
+            return result === undefined ? 'null' : JSON.stringify(result);
+        }
+    });
+    // 函数实例的 constructor 同样能从字符串创建函数，一并切断
+    [function () {}, function* () {}, async function () {}, async function* () {}].forEach(function (f) {
+        Object.defineProperty(Object.getPrototypeOf(f), 'constructor', { value: undefined });
+    });
+    ['Date', 'RegExp', 'Function', 'eval', 'Promise', 'Proxy', 'Reflect', 'Symbol',
+     'Map', 'Set', 'WeakMap', 'WeakSet', 'WeakRef', 'FinalizationRegistry',
+     'ArrayBuffer', 'SharedArrayBuffer', 'Atomics', 'DataView'].forEach(function (name) {
+        delete g[name];
+    });
+})();
+"#;
+
+/// WXS 模块运行时
+pub struct WxsRuntime {
+    runtime: JsRuntime,
+    modules: HashSet<String>,
+}
+
+impl WxsRuntime {
+    pub fn new() -> Result<Self, String> {
+        let runtime = JsRuntime::new()?;
+        runtime.eval(SANDBOX_PRELUDE)?;
+        Ok(Self { runtime, modules: HashSet::new() })
+    }
+
+    /// 执行模块代码，module.exports 注册为 name
+    pub fn load_module(&mut self, name: &str, code: &str) -> Result<(), String> {
+        let name_json = serde_json::to_string(name).map_err(|e| e.to_string())?;
+        let wrapped = format!("__wxs_define({}, function (module, exports) {{\n{}\n}});", name_json, code);
+        self.runtime.eval(&wrapped).map_err(|e| format!("wxs module '{}': {}", name, e))?;
+        self.modules.insert(name.to_string());
+        Ok(())
+    }
+
+    pub fn has_module(&self, name: &str) -> bool {
+        self.modules.contains(name)
+    }
+
+    /// 调用模块导出的函数
+    pub fn call(&self, module: &str, func: &str, args: &[JsonValue]) -> Result<JsonValue, String> {
+        if !self.has_module(module) {
+            return Err(format!("wxs module '{}' not found", module));
+        }
+        let code = format!(
+            "__wxs_call({}, {}, {})",
+            serde_json::to_string(module).map_err(|e| e.to_string())?,
+            serde_json::to_string(func).map_err(|e| e.to_string())?,
+            JsonValue::Array(args.to_vec()),
+        );
+        let result = self.runtime.eval(&code).map_err(|e| format!("wxs {}.{}: {}", module, func, e))?;
+        serde_json::from_str(&result).map_err(|e| e.to_string())
+    }
+}
@@ -1,5 +1,6 @@
 //! Yoga 布局引擎 (使用 Taffy - Rust 实现的 Flexbox 布局)
 
+use crate::parser::wxss::rpx_to_px;
 use taffy::prelude::*;
 use std::collections::HashMap;
 
@@ -474,7 +475,7 @@ fn parse_dimension(value: &str, screen_width: f32) -> Option<f32> {
     
     if value.ends_with("rpx") {
         let num: f32 = value.trim_end_matches("rpx").parse().ok()?;
-        Some(num * screen_width / 750.0)
+        Some(rpx_to_px(num, screen_width))
     } else if value.ends_with("px") {
         value.trim_end_matches("px").parse().ok()
     } else if value.ends_with('%') {
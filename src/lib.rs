@@ -6,13 +6,14 @@ mod color;
 mod geometry;
 mod paint;
 mod path;
+mod stroke;
 pub mod text;
 
-pub use canvas::Canvas;
+pub use canvas::{Canvas, ClipShape};
 pub use color::Color;
-pub use geometry::{Point, Rect, Size};
-pub use paint::{Paint, PaintStyle};
-pub use path::Path;
+pub use geometry::{Matrix, Point, Rect, RingSegment, Size};
+pub use paint::{Paint, PaintStyle, StrokeCap, StrokeJoin};
+pub use path::{FillRule, Path};
 pub use text::TextRenderer;
 
 // UI 组件系统
@@ -43,6 +43,12 @@ pub struct Paint {
     pub stroke_width: f32,
     pub stroke_cap: StrokeCap,
     pub stroke_join: StrokeJoin,
+    /// 斜接长度与线宽之比的上限，超过时按斜切处理
+    pub stroke_miter: f32,
+    /// 虚线模式，交替的实线段和间隔长度，为空时画实线
+    pub dash_pattern: Vec<f32>,
+    /// 虚线起始偏移
+    pub dash_offset: f32,
     pub anti_alias: bool,
 }
 
@@ -54,6 +60,9 @@ impl Default for Paint {
             stroke_width: 1.0,
             stroke_cap: StrokeCap::Butt,
             stroke_join: StrokeJoin::Miter,
+            stroke_miter: 10.0,
+            dash_pattern: Vec::new(),
+            dash_offset: 0.0,
             anti_alias: true,
         }
     }
@@ -79,6 +88,27 @@ impl Paint {
         self
     }
 
+    pub fn with_stroke_cap(mut self, cap: StrokeCap) -> Self {
+        self.stroke_cap = cap;
+        self
+    }
+
+    pub fn with_stroke_join(mut self, join: StrokeJoin) -> Self {
+        self.stroke_join = join;
+        self
+    }
+
+    pub fn with_stroke_miter(mut self, limit: f32) -> Self {
+        self.stroke_miter = limit;
+        self
+    }
+
+    pub fn with_dash(mut self, pattern: &[f32], offset: f32) -> Self {
+        self.dash_pattern = pattern.to_vec();
+        self.dash_offset = offset;
+        self
+    }
+
     pub fn with_anti_alias(mut self, aa: bool) -> Self {
         self.anti_alias = aa;
         self
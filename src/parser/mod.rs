@@ -5,5 +5,5 @@ pub mod wxss;
 pub mod template;
 
 pub use wxml::{WxmlParser, WxmlNode, WxmlNodeType};
-pub use wxss::{WxssParser, StyleSheet, StyleRule, StyleValue};
+pub use wxss::{WxssParser, StyleSheet, StyleRule, StyleValue, Keyframe, FontFace, CalcExpr};
 pub use template::TemplateEngine;
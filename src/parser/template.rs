@@ -1,7 +1,27 @@
 //! 模板引擎 - 处理数据绑定和条件渲染
 
 use super::wxml::WxmlNode;
+use crate::js::WxsRuntime;
 use serde_json::Value as JsonValue;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+thread_local! {
+    /// 当前渲染使用的 WXS 模块，表达式中的 `m.fn(args)` 调用它们
+    static WXS_RUNTIME: RefCell<Option<Rc<WxsRuntime>>> = const { RefCell::new(None) };
+}
+
+/// 一次渲染中可用的命名模板和自定义组件
+struct Scope<'a> {
+    templates: HashMap<String, WxmlNode>,
+    components: &'a HashMap<String, Vec<WxmlNode>>,
+    /// 自定义组件嵌套深度，防止组件递归引用自身
+    depth: u32,
+}
+
+/// 自定义组件最大嵌套深度
+const MAX_COMPONENT_DEPTH: u32 = 32;
 
 /// 模板引擎
 pub struct TemplateEngine;
@@ -15,10 +35,59 @@ impl TemplateEngine {
     /// 渲染模板，支持虚拟列表
     /// viewport: (scroll_offset, viewport_height) 用于虚拟列表优化
     pub fn render_with_virtual_list(nodes: &[WxmlNode], data: &JsonValue, viewport: Option<(f32, f32)>) -> Vec<WxmlNode> {
+        Self::render_with_components(nodes, data, viewport, &HashMap::new())
+    }
+    
+    /// 渲染模板并展开自定义组件：components 为组件标签 -> 组件的 WXML 节点
+    ///
+    /// 组件节点以宿主属性（kebab-case 转为 camelCase）作为数据渲染，宿主的子节点
+    /// 按 `slot="name"` 投影到组件中同名的 `<slot name>`，其余投影到默认 `<slot>`。
+    pub fn render_with_components(nodes: &[WxmlNode], data: &JsonValue, viewport: Option<(f32, f32)>, components: &HashMap<String, Vec<WxmlNode>>) -> Vec<WxmlNode> {
+        let scope = Scope { templates: Self::collect_templates(nodes), components, depth: 0 };
+        Self::render_nodes(nodes, data, viewport, &scope)
+    }
+    
+    /// 渲染模板，表达式可以调用 WXS 模块导出的函数
+    pub fn render_with_wxs(nodes: &[WxmlNode], data: &JsonValue, viewport: Option<(f32, f32)>, wxs: &Rc<WxsRuntime>) -> Vec<WxmlNode> {
+        Self::with_wxs(wxs, || Self::render_with_virtual_list(nodes, data, viewport))
+    }
+    
+    /// 在 f 执行期间让表达式可以调用 wxs 中的模块
+    pub fn with_wxs<R>(wxs: &Rc<WxsRuntime>, f: impl FnOnce() -> R) -> R {
+        let previous = WXS_RUNTIME.with(|w| w.replace(Some(wxs.clone())));
+        let result = f();
+        WXS_RUNTIME.with(|w| *w.borrow_mut() = previous);
+        result
+    }
+    
+    /// 使用额外的命名模板渲染（例如 WxmlParser::templates），节点树中的同名定义优先
+    pub fn render_with_templates(nodes: &[WxmlNode], data: &JsonValue, templates: &HashMap<String, WxmlNode>) -> Vec<WxmlNode> {
+        let mut all = templates.clone();
+        all.extend(Self::collect_templates(nodes));
+        let scope = Scope { templates: all, components: &HashMap::new(), depth: 0 };
+        Self::render_nodes(nodes, data, None, &scope)
+    }
+    
+    /// 收集节点树中的 <template name="..."> 定义
+    pub fn collect_templates(nodes: &[WxmlNode]) -> HashMap<String, WxmlNode> {
+        let mut templates = HashMap::new();
+        for node in nodes {
+            if node.tag_name == "template" {
+                if let Some(name) = node.get_attr("name") {
+                    templates.insert(name.to_string(), node.clone());
+                    continue;
+                }
+            }
+            templates.extend(Self::collect_templates(&node.children));
+        }
+        templates
+    }
+    
+    fn render_nodes(nodes: &[WxmlNode], data: &JsonValue, viewport: Option<(f32, f32)>, scope: &Scope) -> Vec<WxmlNode> {
         let mut result = Vec::new();
         
         for node in nodes {
-            if let Some(rendered) = Self::render_node_with_viewport(node, data, viewport) {
+            if let Some(rendered) = Self::render_node_with_viewport(node, data, viewport, scope) {
                 result.extend(rendered);
             }
         }
@@ -26,7 +95,7 @@ impl TemplateEngine {
         result
     }
     
-    fn render_node_with_viewport(node: &WxmlNode, data: &JsonValue, viewport: Option<(f32, f32)>) -> Option<Vec<WxmlNode>> {
+    fn render_node_with_viewport(node: &WxmlNode, data: &JsonValue, viewport: Option<(f32, f32)>, scope: &Scope) -> Option<Vec<WxmlNode>> {
         match node.node_type {
             super::wxml::WxmlNodeType::Text => {
                 let text = Self::interpolate(&node.text_content, data);
@@ -43,7 +112,21 @@ impl TemplateEngine {
                 
                 // 处理 wx:for - 使用虚拟列表优化
                 if let Some(for_expr) = node.attributes.get("wx:for") {
-                    return Some(Self::render_for_loop_virtual(node, for_expr, data, viewport));
+                    return Some(Self::render_for_loop_virtual(node, for_expr, data, viewport, scope));
+                }
+                
+                // wxs 模块由渲染器加载，import 只提供模板定义，都不输出节点
+                if node.tag_name == "wxs" || node.tag_name == "import" {
+                    return None;
+                }
+                
+                // <template name> 定义不输出，<template is> 展开为模板内容
+                if node.tag_name == "template" {
+                    return Some(Self::render_template(node, data, viewport, scope));
+                }
+                
+                if scope.components.contains_key(&node.tag_name) {
+                    return Some(Self::render_component(node, data, viewport, scope));
                 }
                 
                 // 普通元素
@@ -59,7 +142,7 @@ impl TemplateEngine {
                 }
                 
                 // 处理子节点
-                new_node.children = Self::render_with_virtual_list(&node.children, data, viewport);
+                new_node.children = Self::render_nodes(&node.children, data, viewport, scope);
                 
                 Some(vec![new_node])
             }
@@ -67,9 +150,228 @@ impl TemplateEngine {
         }
     }
     
+    /// 展开 <template is="name" data="{{...}}">，模板内容只能访问 data 提供的数据
+    fn render_template(node: &WxmlNode, data: &JsonValue, viewport: Option<(f32, f32)>, scope: &Scope) -> Vec<WxmlNode> {
+        if node.get_attr("name").is_some() {
+            return Vec::new();
+        }
+        let name = match node.get_attr("is") {
+            Some(is) => Self::interpolate(is, data),
+            None => return Vec::new(),
+        };
+        let template = match scope.templates.get(name.trim()) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        let template_data = node.get_attr("data")
+            .map(|d| Self::evaluate_data_object(&Self::extract_expression(d), data))
+            .unwrap_or_else(|| JsonValue::Object(Default::default()));
+        
+        Self::render_nodes(&template.children, &template_data, viewport, scope)
+    }
+    
+    /// 实例化自定义组件：宿主节点保留标签和属性，子节点为投影了 slot 内容的组件节点
+    fn render_component(node: &WxmlNode, data: &JsonValue, viewport: Option<(f32, f32)>, scope: &Scope) -> Vec<WxmlNode> {
+        let mut host = WxmlNode::new_element(&node.tag_name);
+        let mut properties = serde_json::Map::new();
+        for (key, value) in &node.attributes {
+            if key.starts_with("wx:") {
+                continue;
+            }
+            // 单个 {{expr}} 保留数据类型，便于组件中访问对象和数组
+            let trimmed = value.trim();
+            let is_single_binding = trimmed.starts_with("{{") && trimmed.ends_with("}}") && trimmed[2..].find("{{").is_none();
+            let property = if is_single_binding {
+                Self::evaluate_json(&Self::extract_expression(trimmed), data)
+            } else {
+                JsonValue::String(Self::interpolate(value, data))
+            };
+            properties.insert(Self::camel_case(key), property);
+            host.attributes.insert(key.clone(), Self::interpolate(value, data));
+        }
+        
+        if scope.depth >= MAX_COMPONENT_DEPTH {
+            return vec![host];
+        }
+        
+        // 宿主的子节点在页面数据下渲染，按 slot 属性分组
+        let mut named: HashMap<String, Vec<WxmlNode>> = HashMap::new();
+        let mut default = Vec::new();
+        for child in Self::render_nodes(&node.children, data, viewport, scope) {
+            match child.get_attr("slot") {
+                Some(name) if !name.is_empty() => named.entry(name.to_string()).or_default().push(child),
+                _ => default.push(child),
+            }
+        }
+        
+        let component_nodes = &scope.components[&node.tag_name];
+        let component_scope = Scope { templates: Self::collect_templates(component_nodes), components: scope.components, depth: scope.depth + 1 };
+        let rendered = Self::render_nodes(component_nodes, &JsonValue::Object(properties), viewport, &component_scope);
+        host.children = Self::project_slots(rendered, &mut named, &mut default);
+        vec![host]
+    }
+    
+    /// 把 <slot> 替换为投影的节点，没有投影内容时使用 slot 自身的子节点
+    fn project_slots(nodes: Vec<WxmlNode>, named: &mut HashMap<String, Vec<WxmlNode>>, default: &mut Vec<WxmlNode>) -> Vec<WxmlNode> {
+        let mut result = Vec::new();
+        for mut node in nodes {
+            if node.tag_name != "slot" {
+                node.children = Self::project_slots(std::mem::take(&mut node.children), named, default);
+                result.push(node);
+                continue;
+            }
+            let projected = match node.get_attr("name") {
+                Some(name) if !name.is_empty() => named.remove(name).unwrap_or_default(),
+                _ => std::mem::take(default),
+            };
+            if projected.is_empty() {
+                result.extend(node.children);
+            } else {
+                result.extend(projected);
+            }
+        }
+        result
+    }
+    
+    /// item-title -> itemTitle
+    fn camel_case(name: &str) -> String {
+        let mut result = String::new();
+        let mut upper = false;
+        for c in name.chars() {
+            if c == '-' {
+                upper = true;
+            } else if upper {
+                result.extend(c.to_uppercase());
+                upper = false;
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
+    
+    /// 计算 template 的 data 对象：`...obj` 展开、`key: expr` 和简写 `key`
+    fn evaluate_data_object(expr: &str, data: &JsonValue) -> JsonValue {
+        let mut obj = serde_json::Map::new();
+        
+        for entry in Self::split_top_level(expr, ',') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            if let Some(spread) = entry.strip_prefix("...") {
+                if let JsonValue::Object(fields) = Self::evaluate_json(spread, data) {
+                    obj.extend(fields);
+                }
+            } else if let Some((key, value)) = entry.split_once(':') {
+                let key = key.trim().trim_matches(|c| c == '\'' || c == '"');
+                obj.insert(key.to_string(), Self::evaluate_json(value, data));
+            } else {
+                obj.insert(entry.to_string(), Self::evaluate_json(entry, data));
+            }
+        }
+        
+        JsonValue::Object(obj)
+    }
+    
+    /// 调用 WXS 模块函数 `module.func(args)`，不是模块调用时返回 None
+    fn call_wxs(expr: &str, data: &JsonValue) -> Option<JsonValue> {
+        let inner = expr.strip_suffix(')')?;
+        let (callee, args) = inner.split_once('(')?;
+        let (module, func) = callee.trim().split_once('.')?;
+        let is_ident = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+        if !is_ident(module) || !is_ident(func) {
+            return None;
+        }
+        // 括号必须是整个调用的参数列表，排除 `a.f(x) + b.g(y)` 之类
+        let mut depth = 0;
+        for c in args.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => return None,
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+        let wxs = WXS_RUNTIME.with(|w| w.borrow().clone())?;
+        if !wxs.has_module(module) {
+            return None;
+        }
+        let args: Vec<JsonValue> = Self::split_top_level(args, ',')
+            .into_iter()
+            .filter(|a| !a.trim().is_empty())
+            .map(|a| Self::evaluate_json(a, data))
+            .collect();
+        match wxs.call(module, func, &args) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                log::warn!("⚠️ {}", e);
+                Some(JsonValue::Null)
+            }
+        }
+    }
+    
+    /// 按不在引号和括号内的分隔符拆分
+    fn split_top_level(expr: &str, sep: char) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut start = 0;
+        for (i, c) in expr.char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None => match c {
+                    '\'' | '"' => quote = Some(c),
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' | '}' => depth -= 1,
+                    _ if c == sep && depth == 0 => {
+                        parts.push(&expr[start..i]);
+                        start = i + c.len_utf8();
+                    }
+                    _ => {}
+                },
+            }
+        }
+        parts.push(&expr[start..]);
+        parts
+    }
+    
+    /// 计算表达式，保留 JSON 类型（对象、数组、数字、布尔）
+    fn evaluate_json(expr: &str, data: &JsonValue) -> JsonValue {
+        let expr = expr.trim();
+        match expr {
+            "true" => return JsonValue::Bool(true),
+            "false" => return JsonValue::Bool(false),
+            "null" => return JsonValue::Null,
+            _ => {}
+        }
+        if let Ok(n) = expr.parse::<i64>() {
+            return JsonValue::from(n);
+        }
+        if let Ok(n) = expr.parse::<f64>() {
+            return JsonValue::from(n);
+        }
+        if let Some(value) = Self::call_wxs(expr, data) {
+            return value;
+        }
+        let is_literal = (expr.starts_with('\'') && expr.ends_with('\'')) || (expr.starts_with('"') && expr.ends_with('"'));
+        // 比较和取反得到布尔值
+        let is_condition = expr.starts_with('!') || ["==", "!=", ">", "<"].iter().any(|op| expr.contains(op));
+        if !is_literal && !expr.contains('?') && is_condition {
+            return JsonValue::Bool(Self::evaluate_condition(expr, data));
+        }
+        if !is_literal {
+            if let Some(value) = Self::get_value(expr, data) {
+                return value.clone();
+            }
+        }
+        JsonValue::String(Self::evaluate_expression(expr, data))
+    }
+    
     /// 虚拟列表渲染 - 只渲染可见区域的元素
     /// 注意：为了保持布局正确，我们仍然渲染所有元素，视口裁剪在绘制阶段处理
-    fn render_for_loop_virtual(node: &WxmlNode, for_expr: &str, data: &JsonValue, _viewport: Option<(f32, f32)>) -> Vec<WxmlNode> {
+    fn render_for_loop_virtual(node: &WxmlNode, for_expr: &str, data: &JsonValue, _viewport: Option<(f32, f32)>, scope: &Scope) -> Vec<WxmlNode> {
         let array_name = Self::extract_expression(for_expr);
         let item_name = node.attributes.get("wx:for-item")
             .map(|s| s.as_str())
@@ -89,11 +391,11 @@ impl TemplateEngine {
         };
         
         // 直接使用完整渲染，视口裁剪在绘制阶段处理
-        Self::render_for_loop_full(node, arr, item_name, index_name, data)
+        Self::render_for_loop_full(node, arr, item_name, index_name, data, scope)
     }
     
     /// 完整渲染 for 循环（不使用虚拟列表）
-    fn render_for_loop_full(node: &WxmlNode, arr: &[JsonValue], item_name: &str, index_name: &str, data: &JsonValue) -> Vec<WxmlNode> {
+    fn render_for_loop_full(node: &WxmlNode, arr: &[JsonValue], item_name: &str, index_name: &str, data: &JsonValue, scope: &Scope) -> Vec<WxmlNode> {
         let mut result = Vec::new();
         
         for (index, item) in arr.iter().enumerate() {
@@ -104,19 +406,12 @@ impl TemplateEngine {
                 obj.insert(index_name.to_string(), JsonValue::Number(index.into()));
             }
             
-            // 渲染节点（不包含 wx:for 属性）
-            let mut new_node = WxmlNode::new_element(&node.tag_name);
-            
-            for (key, value) in &node.attributes {
-                if key.starts_with("wx:") {
-                    continue;
-                }
-                let new_value = Self::interpolate(value, &loop_data);
-                new_node.attributes.insert(key.clone(), new_value);
+            // 去掉 wx: 属性后按普通节点渲染（模板、自定义组件同样适用）
+            let mut item_node = node.clone();
+            item_node.attributes.retain(|key, _| !key.starts_with("wx:"));
+            if let Some(rendered) = Self::render_node_with_viewport(&item_node, &loop_data, None, scope) {
+                result.extend(rendered);
             }
-            
-            new_node.children = Self::render(&node.children, &loop_data);
-            result.push(new_node);
         }
         
         result
@@ -196,6 +491,11 @@ impl TemplateEngine {
             return "0".to_string();
         }
         
+        // WXS 模块函数调用
+        if let Some(value) = Self::call_wxs(expr, data) {
+            return Self::json_to_string(&value);
+        }
+        
         // 变量访问
         if let Some(value) = Self::get_value(expr, data) {
             return Self::json_to_string(value);
@@ -241,6 +541,9 @@ impl TemplateEngine {
         if let Some(value) = Self::get_value(expr, data) {
             return Self::is_truthy(value);
         }
+        if let Some(value) = Self::call_wxs(expr, data) {
+            return Self::is_truthy(&value);
+        }
         
         !expr.is_empty() && expr != "false" && expr != "0"
     }
@@ -296,3 +599,69 @@ impl TemplateEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::wxml::WxmlParser;
+    use serde_json::json;
+    
+    #[test]
+    fn test_template_is_with_data_spread() {
+        let wxml = r#"
+            <template name="item">
+                <view class="item {{active ? 'on' : ''}}"><text>{{title}} #{{index}}</text></view>
+            </template>
+            <template is="item" wx:for="{{list}}" data="{{...item, index, active: index === 0}}"/>
+            <template is="{{missing}}" data="{{title: 'x'}}"/>
+        "#;
+        let nodes = WxmlParser::new(wxml).parse().unwrap();
+        let data = json!({ "list": [{ "title": "A" }, { "title": "B" }] });
+        let rendered = TemplateEngine::render(&nodes, &data);
+        
+        assert_eq!(rendered.len(), 2);
+        assert_eq!(rendered[0].get_attr("class"), Some("item on"));
+        assert_eq!(rendered[1].get_attr("class"), Some("item "));
+        assert_eq!(rendered[1].children[0].children[0].text_content.trim(), "B #1");
+    }
+    
+    #[test]
+    fn test_wxs_call_in_expression() {
+        let mut wxs = WxsRuntime::new().unwrap();
+        wxs.load_module("m", "module.exports.price = function (c, unit) { return unit + (c / 100).toFixed(2); };").unwrap();
+        let nodes = WxmlParser::new(r#"<text>{{m.price(item.cents, '$')}}</text>"#).parse().unwrap();
+        let rendered = TemplateEngine::render_with_wxs(&nodes, &json!({ "item": { "cents": 1999 } }), None, &Rc::new(wxs));
+        
+        assert_eq!(rendered[0].children[0].text_content, "$19.99");
+    }
+    
+    #[test]
+    fn test_component_slots() {
+        let card = WxmlParser::new(r#"
+            <view class="card">
+                <view class="header"><slot name="header"/></view>
+                <text>{{cardTitle}}</text>
+                <slot/>
+                <view class="footer"><slot name="footer"><text>default footer</text></slot></view>
+            </view>
+        "#).parse().unwrap();
+        let components: HashMap<String, Vec<WxmlNode>> = [("card".to_string(), card)].into_iter().collect();
+        let page = WxmlParser::new(r#"
+            <card wx:for="{{list}}" card-title="{{item.title}}">
+                <text slot="header">H{{index}}</text>
+                <view class="body">{{item.body}}</view>
+            </card>
+        "#).parse().unwrap();
+        let data = json!({ "list": [{ "title": "A", "body": "a" }, { "title": "B", "body": "b" }] });
+        let rendered = TemplateEngine::render_with_components(&page, &data, None, &components);
+        
+        assert_eq!(rendered.len(), 2);
+        let card = &rendered[1].children[0];
+        assert_eq!(rendered[1].tag_name, "card");
+        assert_eq!(card.children[0].children[0].children[0].text_content, "H1");
+        assert_eq!(card.children[1].children[0].text_content, "B");
+        assert_eq!(card.children[2].get_attr("class"), Some("body"));
+        assert_eq!(card.children[2].children[0].text_content, "b");
+        assert_eq!(card.children[3].children[0].children[0].text_content, "default footer");
+    }
+}
@@ -1,6 +1,11 @@
 //! WXML 解析器
 
 use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+
+/// 按路径读取被 import / include 的文件内容
+pub type SourceResolver = Rc<dyn Fn(&str) -> Option<String>>;
 
 /// WXML 节点类型
 #[derive(Debug, Clone, PartialEq)]
@@ -11,7 +16,7 @@ pub enum WxmlNodeType {
 }
 
 /// WXML 节点
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WxmlNode {
     pub node_type: WxmlNodeType,
     pub tag_name: String,
@@ -58,6 +63,14 @@ impl WxmlNode {
 pub struct WxmlParser {
     input: Vec<char>,
     pos: usize,
+    /// 解析过程中遇到的 <template name="..."> 定义
+    templates: HashMap<String, WxmlNode>,
+    /// 解析 <import src> / <include src> 的文件读取函数，没有时按普通元素保留
+    resolver: Option<SourceResolver>,
+    /// 当前文件路径（相对路径的基准），入口文件为空
+    path: String,
+    /// 正在解析的 include / import 链，用于检测循环引用
+    stack: Vec<String>,
 }
 
 impl WxmlParser {
@@ -65,9 +78,45 @@ impl WxmlParser {
         Self {
             input: input.chars().collect(),
             pos: 0,
+            templates: HashMap::new(),
+            resolver: None,
+            path: String::new(),
+            stack: Vec::new(),
         }
     }
     
+    /// 支持 import / include 的解析器，resolver 把相对入口文件的路径映射为文件内容
+    ///
+    /// `<import src>` 只引入目标文件中定义的模板（不传递目标文件自己 import 的模板），
+    /// `<include src>` 把目标文件中除 template / wxs 以外的内容原地展开。
+    pub fn with_resolver<F>(input: &str, resolver: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + 'static,
+    {
+        Self { resolver: Some(Rc::new(resolver)), ..Self::new(input) }
+    }
+    
+    /// 从小程序目录读取 import / include 的文件：相对路径相对页面所在目录，/ 开头的相对小程序根目录。
+    /// page_path 为页面路径（如 `pages/index/index`）
+    pub fn with_app_dir(input: &str, app_root: &Path, page_path: &str) -> Self {
+        let root = app_root.to_path_buf();
+        let page_dir = root.join(page_path.trim_start_matches('/')).parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| root.clone());
+        Self::with_resolver(input, move |src| {
+            let path = match src.strip_prefix('/') {
+                Some(rel) => root.join(rel),
+                None => page_dir.join(src),
+            };
+            std::fs::read_to_string(path).ok()
+        })
+    }
+    
+    /// 已解析的命名模板（定义节点仍保留在节点树中，渲染时不输出）
+    pub fn templates(&self) -> &HashMap<String, WxmlNode> {
+        &self.templates
+    }
+    
     pub fn parse(&mut self) -> Result<Vec<WxmlNode>, String> {
         let mut nodes = Vec::new();
         
@@ -84,7 +133,7 @@ impl WxmlParser {
                     break; // 结束标签，返回上层
                 }
                 if let Some(node) = self.parse_element()? {
-                    nodes.push(node);
+                    nodes.extend(self.expand_directive(node)?);
                 }
             } else {
                 if let Some(text) = self.parse_text() {
@@ -123,13 +172,21 @@ impl WxmlParser {
         if self.starts_with("/>") {
             self.advance();
             self.advance();
+            self.record_template(&node);
             return Ok(Some(node));
         }
         
         self.expect('>')?;
         
-        // 解析子节点
-        node.children = self.parse()?;
+        // 解析子节点，wxs 的内容是脚本，按原文保留
+        if tag_name == "wxs" {
+            let code = self.parse_raw_until("</wxs");
+            if !code.trim().is_empty() {
+                node.children = vec![WxmlNode::new_text(&code)];
+            }
+        } else {
+            node.children = self.parse()?;
+        }
         
         // 解析结束标签
         self.skip_whitespace();
@@ -144,9 +201,55 @@ impl WxmlParser {
             self.expect('>')?;
         }
         
+        self.record_template(&node);
         Ok(Some(node))
     }
     
+    /// 展开 import / include，其他节点原样返回
+    fn expand_directive(&mut self, node: WxmlNode) -> Result<Vec<WxmlNode>, String> {
+        let is_directive = node.tag_name == "import" || node.tag_name == "include";
+        let (Some(resolver), Some(src), true) = (self.resolver.clone(), node.get_attr("src"), is_directive) else {
+            return Ok(vec![node]);
+        };
+        
+        let mut path = join_src_path(&self.path, src);
+        if !path.ends_with(".wxml") {
+            path.push_str(".wxml");
+        }
+        if self.stack.contains(&path) || path == self.path {
+            return Err(format!("Circular {}: {}", node.tag_name, path));
+        }
+        let content = resolver(&path).ok_or_else(|| format!("Cannot resolve {} src: {}", node.tag_name, path))?;
+        
+        let mut child = WxmlParser::new(&content);
+        child.resolver = Some(resolver);
+        child.stack = self.stack.clone();
+        child.stack.push(self.path.clone());
+        child.path = path.clone();
+        let nodes = child.parse().map_err(|e| format!("{}: {}", path, e))?;
+        
+        let is_template = |n: &WxmlNode| n.tag_name == "template" && n.get_attr("name").is_some();
+        if node.tag_name == "include" {
+            return Ok(nodes.into_iter().filter(|n| !is_template(n) && n.tag_name != "wxs").collect());
+        }
+        
+        // import 节点保留在树中，子节点是目标文件定义的模板，渲染时不输出
+        let mut import = node;
+        import.children = nodes.into_iter().filter(|n| is_template(n)).collect();
+        for template in &import.children {
+            self.record_template(template);
+        }
+        Ok(vec![import])
+    }
+    
+    fn record_template(&mut self, node: &WxmlNode) {
+        if node.tag_name == "template" {
+            if let Some(name) = node.get_attr("name") {
+                self.templates.insert(name.to_string(), node.clone());
+            }
+        }
+    }
+    
     fn parse_tag_name(&mut self) -> String {
         let mut name = String::new();
         while self.pos < self.input.len() {
@@ -237,6 +340,15 @@ impl WxmlParser {
         }
     }
     
+    fn parse_raw_until(&mut self, end: &str) -> String {
+        let mut text = String::new();
+        while self.pos < self.input.len() && !self.starts_with(end) {
+            text.push(self.current_char());
+            self.advance();
+        }
+        text
+    }
+    
     fn parse_comment(&mut self) {
         // Skip <!--
         for _ in 0..4 {
@@ -293,6 +405,27 @@ impl WxmlParser {
     }
 }
 
+/// 按当前文件解析 src：`/` 开头相对根目录，否则相对当前文件所在目录
+fn join_src_path(current: &str, src: &str) -> String {
+    let joined = if src.starts_with('/') {
+        src.to_string()
+    } else {
+        let dir = current.rfind('/').map(|i| &current[..=i]).unwrap_or("");
+        format!("{}{}", dir, src)
+    };
+    
+    let mut parts: Vec<&str> = Vec::new();
+    for part in joined.split('/') {
+        match part {
+            "" | "." => {}
+            ".." if parts.last().map(|p| *p != "..").unwrap_or(false) => { parts.pop(); }
+            _ => parts.push(part),
+        }
+    }
+    let path = parts.join("/");
+    if joined.starts_with('/') { format!("/{}", path) } else { path }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -307,4 +440,62 @@ mod tests {
         assert_eq!(nodes[0].tag_name, "view");
         assert_eq!(nodes[0].get_attr("class"), Some("container"));
     }
+    
+    #[test]
+    fn test_collect_named_templates() {
+        let wxml = r#"<template name="item"><text>{{title}}</text></template><template is="item" data="{{...a}}"/>"#;
+        let mut parser = WxmlParser::new(wxml);
+        let nodes = parser.parse().unwrap();
+        
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(parser.templates().len(), 1);
+        assert_eq!(parser.templates()["item"].children[0].tag_name, "text");
+    }
+    
+    #[test]
+    fn test_import_and_include_with_resolver() {
+        let files: HashMap<&str, &str> = [
+            ("common/item.wxml", r#"<import src="./base.wxml"/><template name="item"><text>{{title}}</text></template><view class="ignored"/>"#),
+            ("common/base.wxml", r#"<template name="base"><view/></template>"#),
+            ("common/header.wxml", r#"<template name="hidden"><view/></template><view class="header"/><include src="footer"/>"#),
+            ("common/footer.wxml", r#"<view class="footer"/>"#),
+            ("common/loop.wxml", r#"<include src="/common/loop.wxml"/>"#),
+        ].into_iter().collect();
+        let files = std::rc::Rc::new(files.into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>());
+        let resolver = move |path: &str| files.get(path.trim_start_matches('/')).cloned();
+        
+        let wxml = r#"<import src="common/item.wxml"/><include src="./common/header.wxml"/><template is="item"/>"#;
+        let mut parser = WxmlParser::with_resolver(wxml, resolver.clone());
+        let nodes = parser.parse().unwrap();
+        
+        // import 只带来目标文件自己定义的模板
+        assert_eq!(nodes[0].tag_name, "import");
+        assert_eq!(nodes[0].children.len(), 1);
+        assert!(parser.templates().contains_key("item"));
+        assert!(!parser.templates().contains_key("base"));
+        // include 展开除 template 以外的内容，嵌套 include 相对被引入的文件解析
+        assert_eq!(nodes[1].get_attr("class"), Some("header"));
+        assert_eq!(nodes[2].get_attr("class"), Some("footer"));
+        assert_eq!(nodes[3].tag_name, "template");
+        
+        let err = WxmlParser::with_resolver(r#"<include src="common/loop.wxml"/>"#, resolver).parse().unwrap_err();
+        assert!(err.contains("Circular"), "{}", err);
+    }
+    
+    #[test]
+    fn test_include_from_app_dir() {
+        let root = std::env::temp_dir().join(format!("mini_render_app_dir_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("pages/index")).unwrap();
+        std::fs::create_dir_all(root.join("common")).unwrap();
+        std::fs::write(root.join("pages/index/header.wxml"), r#"<view class="header"/>"#).unwrap();
+        std::fs::write(root.join("common/footer.wxml"), r#"<view class="footer"/>"#).unwrap();
+        
+        let wxml = r#"<include src="header.wxml"/><include src="/common/footer.wxml"/>"#;
+        let nodes = WxmlParser::with_app_dir(wxml, &root, "pages/index/index").parse().unwrap();
+        std::fs::remove_dir_all(&root).ok();
+        
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].get_attr("class"), Some("header"));
+        assert_eq!(nodes[1].get_attr("class"), Some("footer"));
+    }
 }
@@ -1,6 +1,6 @@
 //! WXSS 解析器 - 完整支持微信小程序样式
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use crate::Color;
 
 /// 样式值
@@ -10,10 +10,219 @@ pub enum StyleValue {
     Color(Color),
     String(String),
     Number(f32),
+    /// calc() 表达式，使用时按屏幕尺寸求值
+    Calc(CalcExpr),
     Auto,
     None,
 }
 
+/// calc() 表达式树
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcExpr {
+    Length(f32, LengthUnit),
+    Number(f32),
+    Add(Box<CalcExpr>, Box<CalcExpr>),
+    Sub(Box<CalcExpr>, Box<CalcExpr>),
+    Mul(Box<CalcExpr>, Box<CalcExpr>),
+    Div(Box<CalcExpr>, Box<CalcExpr>),
+}
+
+impl CalcExpr {
+    /// 求值，length 把带单位的长度换算成 px（纯数字原样参与运算）
+    pub fn evaluate(&self, length: &dyn Fn(f32, LengthUnit) -> f32) -> f32 {
+        match self {
+            CalcExpr::Length(n, unit) => length(*n, *unit),
+            CalcExpr::Number(n) => *n,
+            CalcExpr::Add(a, b) => a.evaluate(length) + b.evaluate(length),
+            CalcExpr::Sub(a, b) => a.evaluate(length) - b.evaluate(length),
+            CalcExpr::Mul(a, b) => a.evaluate(length) * b.evaluate(length),
+            CalcExpr::Div(a, b) => {
+                let d = b.evaluate(length);
+                if d == 0.0 { 0.0 } else { a.evaluate(length) / d }
+            }
+        }
+    }
+}
+
+/// 解析 calc(...)，支持 + - * /、嵌套括号（包括嵌套的 calc）和混合单位
+pub fn parse_calc(value: &str) -> Option<CalcExpr> {
+    let inner = value.trim().strip_prefix("calc(")?.strip_suffix(')')?;
+    let tokens = tokenize_calc(inner)?;
+    let mut pos = 0;
+    let expr = parse_calc_sum(&tokens, &mut pos)?;
+    if pos == tokens.len() { Some(expr) } else { None }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CalcToken {
+    Value(CalcExpr),
+    Op(char),
+    Open,
+    Close,
+}
+
+fn tokenize_calc(s: &str) -> Option<Vec<CalcToken>> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let after_operand = matches!(tokens.last(), Some(CalcToken::Value(_)) | Some(CalcToken::Close));
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(CalcToken::Open);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(CalcToken::Close);
+            i += 1;
+        } else if s[char_offset(&chars, i)..].starts_with("calc(") {
+            tokens.push(CalcToken::Open);
+            i += 5;
+        } else if matches!(c, '*' | '/') || (matches!(c, '+' | '-') && after_operand) {
+            tokens.push(CalcToken::Op(c));
+            i += 1;
+        } else {
+            // 数字（可带符号）加单位
+            let start = i;
+            if matches!(c, '+' | '-') { i += 1; }
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '%') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = match text.parse::<f32>() {
+                Ok(n) => CalcExpr::Number(n),
+                Err(_) => {
+                    let (n, unit) = WxssParser::parse_length(&text)?;
+                    CalcExpr::Length(n, unit)
+                }
+            };
+            tokens.push(CalcToken::Value(value));
+        }
+    }
+    Some(tokens)
+}
+
+/// 字符下标对应的字节偏移
+pub(crate) fn char_offset(chars: &[char], i: usize) -> usize {
+    chars[..i].iter().map(|c| c.len_utf8()).sum()
+}
+
+fn parse_calc_sum(tokens: &[CalcToken], pos: &mut usize) -> Option<CalcExpr> {
+    let mut left = parse_calc_product(tokens, pos)?;
+    while let Some(CalcToken::Op(op @ ('+' | '-'))) = tokens.get(*pos) {
+        *pos += 1;
+        let right = parse_calc_product(tokens, pos)?;
+        left = if *op == '+' {
+            CalcExpr::Add(Box::new(left), Box::new(right))
+        } else {
+            CalcExpr::Sub(Box::new(left), Box::new(right))
+        };
+    }
+    Some(left)
+}
+
+fn parse_calc_product(tokens: &[CalcToken], pos: &mut usize) -> Option<CalcExpr> {
+    let mut left = parse_calc_operand(tokens, pos)?;
+    while let Some(CalcToken::Op(op @ ('*' | '/'))) = tokens.get(*pos) {
+        *pos += 1;
+        let right = parse_calc_operand(tokens, pos)?;
+        left = if *op == '*' {
+            CalcExpr::Mul(Box::new(left), Box::new(right))
+        } else {
+            CalcExpr::Div(Box::new(left), Box::new(right))
+        };
+    }
+    Some(left)
+}
+
+fn parse_calc_operand(tokens: &[CalcToken], pos: &mut usize) -> Option<CalcExpr> {
+    match tokens.get(*pos)? {
+        CalcToken::Value(v) => {
+            *pos += 1;
+            Some(v.clone())
+        }
+        CalcToken::Open => {
+            *pos += 1;
+            let expr = parse_calc_sum(tokens, pos)?;
+            if tokens.get(*pos) != Some(&CalcToken::Close) { return None; }
+            *pos += 1;
+            Some(expr)
+        }
+        _ => None,
+    }
+}
+
+/// 去掉值末尾的 !important（允许 ! 与 important 之间有空格），没有时返回 None
+pub fn strip_important(value: &str) -> Option<&str> {
+    let v = value.trim_end();
+    let lower = v.to_ascii_lowercase();
+    if !lower.ends_with("important") { return None; }
+    let rest = v[..v.len() - "important".len()].trim_end();
+    rest.strip_suffix('!').map(|r| r.trim_end())
+}
+
+/// 替换值中的 var(--name, fallback)，变量未定义且没有 fallback 时返回 None
+pub fn resolve_var_refs(value: &str, vars: &HashMap<String, String>) -> Option<String> {
+    resolve_var_refs_depth(value, vars, 0)
+}
+
+fn resolve_var_refs_depth(value: &str, vars: &HashMap<String, String>, depth: u32) -> Option<String> {
+    // 变量之间循环引用时放弃
+    if depth > 16 { return None; }
+    if !value.contains("var(") { return Some(value.to_string()); }
+    let (start, name, fallback, end) = find_function_ref(value, "var(")?;
+    let replacement = match vars.get(name) {
+        Some(v) => resolve_var_refs_depth(v, vars, depth + 1)?,
+        None => resolve_var_refs_depth(fallback?, vars, depth + 1)?,
+    };
+    let rest = resolve_var_refs_depth(&value[end + 1..], vars, depth)?;
+    Some(format!("{}{}{}", &value[..start], replacement, rest))
+}
+
+/// 替换值中的 env(safe-area-inset-top|right|bottom|left, fallback)，insets 为上、右、下、左（px）。
+/// 未知的环境变量使用 fallback，没有 fallback 时返回 None
+pub fn resolve_env_refs(value: &str, insets: [f32; 4]) -> Option<String> {
+    if !value.contains("env(") { return Some(value.to_string()); }
+    let (start, name, fallback, end) = find_function_ref(value, "env(")?;
+    let inset = match name {
+        "safe-area-inset-top" => Some(insets[0]),
+        "safe-area-inset-right" => Some(insets[1]),
+        "safe-area-inset-bottom" => Some(insets[2]),
+        "safe-area-inset-left" => Some(insets[3]),
+        _ => None,
+    };
+    let replacement = match inset {
+        Some(v) => format!("{}px", v),
+        None => resolve_env_refs(fallback?, insets)?,
+    };
+    let rest = resolve_env_refs(&value[end + 1..], insets)?;
+    Some(format!("{}{}{}", &value[..start], replacement, rest))
+}
+
+/// 找到值中第一个 prefix（如 "var("、"env("）引用，返回 (起始位置, 名称, fallback, 右括号位置)。
+/// 名称和 fallback 以第一个顶层逗号分隔并去掉首尾空白；没有找到或括号不匹配时返回 None
+fn find_function_ref<'a>(value: &'a str, prefix: &str) -> Option<(usize, &'a str, Option<&'a str>, usize)> {
+    let start = value.find(prefix)?;
+    let body_start = start + prefix.len();
+    let mut depth_paren = 0;
+    let mut comma = None;
+    let mut end = None;
+    for (i, c) in value[body_start..].char_indices() {
+        match c {
+            '(' => depth_paren += 1,
+            ')' if depth_paren == 0 => { end = Some(body_start + i); break; }
+            ')' => depth_paren -= 1,
+            ',' if depth_paren == 0 && comma.is_none() => comma = Some(body_start + i),
+            _ => {}
+        }
+    }
+    let end = end?;
+    let name = value[body_start..comma.unwrap_or(end)].trim();
+    let fallback = comma.map(|c| value[c + 1..end].trim());
+    Some((start, name, fallback, end))
+}
+
 /// 长度单位
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LengthUnit {
@@ -31,62 +240,232 @@ pub enum LengthUnit {
 pub struct StyleRule {
     pub selector: String,
     pub properties: HashMap<String, StyleValue>,
+    /// 带 !important 的属性名
+    pub important: HashSet<String>,
+}
+
+/// @keyframes 中的一帧
+#[derive(Debug, Clone)]
+pub struct Keyframe {
+    /// 帧位置（0.0 - 1.0），from = 0，to = 1
+    pub offset: f32,
+    pub properties: HashMap<String, StyleValue>,
+}
+
+/// @font-face 声明的字体
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontFace {
+    /// font-family 名称（去掉引号）
+    pub family: String,
+    /// src 中第一个 url(...) 的地址
+    pub src: String,
 }
 
 /// 样式表
 #[derive(Debug, Clone, Default)]
 pub struct StyleSheet {
     pub rules: Vec<StyleRule>,
+    /// @keyframes 名称 -> 按 offset 排序的关键帧
+    pub keyframes: HashMap<String, Vec<Keyframe>>,
+    /// @font-face 声明，按出现顺序
+    pub font_faces: Vec<FontFace>,
+}
+
+/// 元素的交互状态，用于匹配 :active / :hover / :disabled 伪类
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PseudoState {
+    pub active: bool,
+    pub hover: bool,
+    pub disabled: bool,
+    /// 按下后应用元素的 hover-class（不参与伪类匹配）
+    pub hover_class: bool,
+}
+
+impl PseudoState {
+    fn has(&self, pseudo: &str) -> bool {
+        match pseudo {
+            "active" => self.active,
+            "hover" => self.hover,
+            "disabled" => self.disabled,
+            _ => false,
+        }
+    }
+
+    fn set(&mut self, pseudo: &str) {
+        match pseudo {
+            "active" => self.active = true,
+            "hover" => self.hover = true,
+            "disabled" => self.disabled = true,
+            _ => {}
+        }
+    }
+}
+
+/// 拆出选择器末尾的状态伪类，例如 `.btn:active` -> (".btn", ["active"])
+/// 包含其他伪类或伪元素时返回 None，按普通选择器处理
+fn split_pseudo_classes(selector: &str) -> Option<(&str, Vec<&str>)> {
+    if selector.contains("::") { return None; }
+    let colon = selector.find(':')?;
+    let pseudos: Vec<&str> = selector[colon + 1..].split(':').collect();
+    if pseudos.iter().all(|p| matches!(*p, "active" | "hover" | "disabled")) {
+        Some((&selector[..colon], pseudos))
+    } else {
+        None
+    }
+}
+
+/// 拆出选择器末尾的伪元素，例如 `.item::after` -> (".item", "after")，兼容单冒号写法
+fn split_pseudo_element(selector: &str) -> Option<(&str, &str)> {
+    ["before", "after"].into_iter().find_map(|element| {
+        let base = selector.strip_suffix(element)?;
+        let base = base.strip_suffix("::").or_else(|| base.strip_suffix(':'))?;
+        Some((base, element))
+    })
+}
+
+/// 去掉伪元素部分，`.btn:active::after` 的交互状态作用于 `.btn:active`
+fn strip_pseudo_element(selector: &str) -> &str {
+    split_pseudo_element(selector).map(|(base, _)| base).unwrap_or(selector)
 }
 
 impl StyleSheet {
     pub fn new() -> Self {
-        Self { rules: Vec::new() }
+        Self { rules: Vec::new(), keyframes: HashMap::new(), font_faces: Vec::new() }
+    }
+    
+    /// page / :root 上声明的 CSS 变量（--name），作为整棵元素树的初始变量
+    pub fn root_variables(&self) -> HashMap<String, String> {
+        let mut vars = HashMap::new();
+        for tag in ["page", ":root"] {
+            for (name, value) in self.get_styles(&[], tag) {
+                if let (true, StyleValue::String(v)) = (name.starts_with("--"), value) {
+                    vars.insert(name, v);
+                }
+            }
+        }
+        vars
     }
     
     /// 获取元素的样式（支持多选择器匹配和优先级）
     pub fn get_styles(&self, class_names: &[&str], tag_name: &str) -> HashMap<String, StyleValue> {
-        let mut styles = HashMap::new();
-        
+        self.get_styles_with_importance(class_names, tag_name).0
+    }
+    
+    /// 获取元素的样式，同时返回最终值来自 !important 声明的属性名（内联普通声明不能覆盖它们）
+    pub fn get_styles_with_importance(&self, class_names: &[&str], tag_name: &str) -> (HashMap<String, StyleValue>, HashSet<String>) {
+        self.get_styles_with_state(class_names, tag_name, PseudoState::default())
+    }
+    
+    /// 按元素的交互状态获取样式，匹配的伪类规则按类选择器计算特异性
+    pub fn get_styles_with_state(&self, class_names: &[&str], tag_name: &str, state: PseudoState) -> (HashMap<String, StyleValue>, HashSet<String>) {
         // 按选择器特异性排序应用
         let mut matched_rules: Vec<(u32, &StyleRule)> = Vec::new();
         
         for rule in &self.rules {
-            if let Some(specificity) = self.selector_matches(&rule.selector, class_names, tag_name) {
+            if let Some(specificity) = self.selector_matches(&rule.selector, class_names, tag_name, state) {
+                matched_rules.push((specificity, rule));
+            }
+        }
+        
+        Self::cascade(matched_rules)
+    }
+    
+    /// 元素 ::before / ::after 伪元素的样式，没有匹配的规则时返回 None
+    pub fn get_pseudo_element_styles(&self, class_names: &[&str], tag_name: &str, state: PseudoState, element: &str) -> Option<(HashMap<String, StyleValue>, HashSet<String>)> {
+        let mut matched_rules: Vec<(u32, &StyleRule)> = Vec::new();
+        
+        for rule in &self.rules {
+            let specificity = rule.selector.split(',').find_map(|part| {
+                let (base, el) = split_pseudo_element(part.trim())?;
+                if el != element { return None; }
+                let base_specificity = if base.is_empty() { 0 } else { self.single_selector_matches(base, class_names, tag_name, state)? };
+                Some(base_specificity + 1)
+            });
+            if let Some(specificity) = specificity {
                 matched_rules.push((specificity, rule));
             }
         }
         
+        if matched_rules.is_empty() { return None; }
+        Some(Self::cascade(matched_rules))
+    }
+    
+    /// 按特异性层叠匹配到的规则，返回样式和来自 !important 声明的属性名
+    fn cascade(mut matched_rules: Vec<(u32, &StyleRule)>) -> (HashMap<String, StyleValue>, HashSet<String>) {
+        let mut styles = HashMap::new();
+        
         // 按特异性排序（低到高）
         matched_rules.sort_by_key(|(s, _)| *s);
         
-        for (_, rule) in matched_rules {
+        for (_, rule) in &matched_rules {
             for (key, value) in &rule.properties {
-                styles.insert(key.clone(), value.clone());
+                if !rule.important.contains(key) {
+                    styles.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        
+        // !important 声明优先于所有普通声明，相互之间仍按特异性和顺序
+        let mut important = HashSet::new();
+        for (_, rule) in &matched_rules {
+            for key in &rule.important {
+                if let Some(value) = rule.properties.get(key) {
+                    styles.insert(key.clone(), value.clone());
+                    important.insert(key.clone());
+                }
             }
         }
         
-        styles
+        (styles, important)
+    }
+    
+    /// 是否有 :active / :hover / :disabled 规则
+    pub fn has_pseudo_class_rules(&self) -> bool {
+        self.rules.iter().any(|r| r.selector.split(',').any(|part| split_pseudo_classes(strip_pseudo_element(part.trim())).is_some()))
+    }
+    
+    /// 元素在哪些交互状态下有对应的伪类规则（渲染器据此决定是否需要跟踪元素状态）
+    pub fn pseudo_class_rules_for(&self, class_names: &[&str], tag_name: &str) -> PseudoState {
+        let mut kinds = PseudoState::default();
+        for rule in &self.rules {
+            for part in rule.selector.split(',') {
+                let Some((base, pseudos)) = split_pseudo_classes(strip_pseudo_element(part.trim())) else { continue };
+                if base.is_empty() || self.single_selector_matches(base, class_names, tag_name, PseudoState::default()).is_some() {
+                    pseudos.iter().for_each(|p| kinds.set(p));
+                }
+            }
+        }
+        kinds
     }
     
     /// 返回 Some(specificity) 如果匹配，None 如果不匹配
-    fn selector_matches(&self, selector: &str, class_names: &[&str], tag_name: &str) -> Option<u32> {
+    fn selector_matches(&self, selector: &str, class_names: &[&str], tag_name: &str, state: PseudoState) -> Option<u32> {
         let selector = selector.trim();
         
         // 处理多选择器（逗号分隔）
         if selector.contains(',') {
             for part in selector.split(',') {
-                if let Some(s) = self.single_selector_matches(part.trim(), class_names, tag_name) {
+                if let Some(s) = self.single_selector_matches(part.trim(), class_names, tag_name, state) {
                     return Some(s);
                 }
             }
             return None;
         }
         
-        self.single_selector_matches(selector, class_names, tag_name)
+        self.single_selector_matches(selector, class_names, tag_name, state)
     }
     
-    fn single_selector_matches(&self, selector: &str, class_names: &[&str], tag_name: &str) -> Option<u32> {
+    fn single_selector_matches(&self, selector: &str, class_names: &[&str], tag_name: &str, state: PseudoState) -> Option<u32> {
+        // 伪元素规则只作用于 ::before / ::after，不匹配元素本身
+        if split_pseudo_element(selector).is_some() { return None; }
+        
+        // 伪类 :active / :hover / :disabled，元素处于对应状态时才匹配
+        if let Some((base, pseudos)) = split_pseudo_classes(selector) {
+            if !pseudos.iter().all(|p| state.has(p)) { return None; }
+            let base_specificity = if base.is_empty() { 0 } else { self.single_selector_matches(base, class_names, tag_name, state)? };
+            return Some(base_specificity + 10 * pseudos.len() as u32);
+        }
+        
         // 类选择器 .class
         if selector.starts_with('.') && !selector[1..].contains('.') {
             let class = &selector[1..];
@@ -169,6 +548,21 @@ impl WxssParser {
                 break;
             }
             
+            if self.starts_with("@keyframes") || self.starts_with("@-webkit-keyframes") {
+                let (name, frames) = self.parse_keyframes()?;
+                if !name.is_empty() {
+                    stylesheet.keyframes.insert(name, frames);
+                }
+                continue;
+            }
+            
+            if self.starts_with("@font-face") {
+                if let Some(face) = self.parse_font_face()? {
+                    stylesheet.font_faces.push(face);
+                }
+                continue;
+            }
+            
             // 跳过 @import 等 at-rules
             if self.current_char() == '@' {
                 self.skip_at_rule();
@@ -183,6 +577,85 @@ impl WxssParser {
         Ok(stylesheet)
     }
     
+    /// 解析 `@keyframes name { from {...} 50% {...} to {...} }`
+    fn parse_keyframes(&mut self) -> Result<(String, Vec<Keyframe>), String> {
+        // 跳过 @keyframes 关键字
+        while self.pos < self.input.len() && !self.current_char().is_whitespace() && self.current_char() != '{' {
+            self.advance();
+        }
+        let name = self.parse_selector();
+        if self.current_char() != '{' {
+            return Err(format!("Expected '{{' after @keyframes {}", name));
+        }
+        self.advance();
+        
+        let mut frames = Vec::new();
+        loop {
+            self.skip_whitespace_and_comments();
+            if self.pos >= self.input.len() {
+                break;
+            }
+            if self.current_char() == '}' {
+                self.advance();
+                break;
+            }
+            let selector = self.parse_selector();
+            if self.current_char() != '{' {
+                return Err(format!("Expected '{{' after keyframe selector '{}'", selector));
+            }
+            self.advance();
+            let (properties, _) = self.parse_properties()?;
+            self.skip_whitespace_and_comments();
+            if self.current_char() == '}' {
+                self.advance();
+            }
+            // `0%, 100% { ... }` 同时定义多帧
+            for part in selector.split(',') {
+                let offset = match part.trim() {
+                    "from" => Some(0.0),
+                    "to" => Some(1.0),
+                    p => p.strip_suffix('%').and_then(|v| v.trim().parse::<f32>().ok()).map(|v| v / 100.0),
+                };
+                if let Some(offset) = offset.filter(|o| (0.0..=1.0).contains(o)) {
+                    frames.push(Keyframe { offset, properties: properties.clone() });
+                }
+            }
+        }
+        frames.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        Ok((name, frames))
+    }
+    
+    /// 解析 `@font-face { font-family: "Name"; src: url(...) format(...); }`，缺少名称或地址时返回 None
+    fn parse_font_face(&mut self) -> Result<Option<FontFace>, String> {
+        while self.pos < self.input.len() && self.current_char() != '{' {
+            self.advance();
+        }
+        if self.current_char() != '{' {
+            return Err("Expected '{' after @font-face".to_string());
+        }
+        self.advance();
+        let (properties, _) = self.parse_properties()?;
+        self.skip_whitespace_and_comments();
+        if self.current_char() == '}' {
+            self.advance();
+        }
+        
+        let text = |name: &str| match properties.get(name) {
+            Some(StyleValue::String(s)) => Some(s.clone()),
+            _ => None,
+        };
+        let family = text("font-family").map(|f| f.trim().trim_matches(|c| c == '"' || c == '\'').to_string());
+        let src = text("src").and_then(|src| {
+            let start = src.find("url(")? + 4;
+            let end = start + src[start..].find(')')?;
+            Some(src[start..end].trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        });
+        Ok(match (family, src) {
+            (Some(family), Some(src)) if !family.is_empty() && !src.is_empty() => Some(FontFace { family, src }),
+            _ => None,
+        })
+    }
+    
     fn skip_at_rule(&mut self) {
         while self.pos < self.input.len() && self.current_char() != ';' && self.current_char() != '{' {
             self.advance();
@@ -215,14 +688,14 @@ impl WxssParser {
         }
         self.advance();
         
-        let properties = self.parse_properties()?;
+        let (properties, important) = self.parse_properties()?;
         
         self.skip_whitespace_and_comments();
         if self.current_char() == '}' {
             self.advance();
         }
         
-        Ok(Some(StyleRule { selector, properties }))
+        Ok(Some(StyleRule { selector, properties, important }))
     }
     
     fn parse_selector(&mut self) -> String {
@@ -240,8 +713,10 @@ impl WxssParser {
         selector.trim().to_string()
     }
     
-    fn parse_properties(&mut self) -> Result<HashMap<String, StyleValue>, String> {
+    /// 解析声明块，返回属性和其中带 !important 的属性名
+    fn parse_properties(&mut self) -> Result<(HashMap<String, StyleValue>, HashSet<String>), String> {
         let mut properties = HashMap::new();
+        let mut important = HashSet::new();
         
         loop {
             self.skip_whitespace_and_comments();
@@ -270,11 +745,24 @@ impl WxssParser {
                 self.advance();
             }
             
-            let parsed_value = Self::parse_value(&name, &value);
+            let value = match strip_important(&value) {
+                Some(v) => {
+                    important.insert(name.clone());
+                    v.to_string()
+                }
+                None => value,
+            };
+            
+            // 自定义属性和引用了 var() 的值保留原文，构建样式时再替换
+            let parsed_value = if name.starts_with("--") || value.contains("var(") {
+                StyleValue::String(value)
+            } else {
+                Self::parse_value(&name, &value)
+            };
             properties.insert(name, parsed_value);
         }
         
-        Ok(properties)
+        Ok((properties, important))
     }
     
     fn parse_property_name(&mut self) -> String {
@@ -296,14 +784,22 @@ impl WxssParser {
     fn parse_property_value(&mut self) -> String {
         let mut value = String::new();
         let mut paren_depth = 0;
+        let mut quote: Option<char> = None;
         
         while self.pos < self.input.len() {
             let c = self.current_char();
             
-            if c == '(' { paren_depth += 1; }
-            else if c == ')' { paren_depth -= 1; }
+            // 引号内的 ; } 属于字符串（content: ';'）
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '\'' || c == '"' => quote = Some(c),
+                None if c == '(' => paren_depth += 1,
+                None if c == ')' => paren_depth -= 1,
+                None => {}
+            }
             
-            if paren_depth == 0 && (c == ';' || c == '}') {
+            if quote.is_none() && paren_depth == 0 && (c == ';' || c == '}') {
                 break;
             }
             
@@ -314,9 +810,15 @@ impl WxssParser {
         value.trim().to_string()
     }
     
-    fn parse_value(_name: &str, value: &str) -> StyleValue {
+    pub fn parse_value(name: &str, value: &str) -> StyleValue {
         let value = value.trim();
         
+        if value.starts_with("calc(") {
+            if let Some(expr) = parse_calc(value) {
+                return StyleValue::Calc(expr);
+            }
+        }
+        
         // 颜色值
         if value.starts_with('#') {
             if let Some(color) = Self::parse_color(value) {
@@ -330,13 +832,23 @@ impl WxssParser {
             }
         }
         
-        // 渐变值 - 提取第一个颜色作为 fallback
+        // 渐变值 - background 保留完整定义（渐变文字等使用），其他属性提取第一个颜色作为 fallback
         if value.starts_with("linear-gradient") || value.starts_with("radial-gradient") {
+            if matches!(name, "background" | "background-image") {
+                return StyleValue::String(value.to_string());
+            }
             if let Some(color) = Self::parse_gradient_fallback(value) {
                 return StyleValue::Color(color);
             }
         }
         
+        // 无单位行高是字号的倍数，不能当作 px
+        if name == "line-height" {
+            if let Ok(num) = value.parse::<f32>() {
+                return StyleValue::Number(num);
+            }
+        }
+        
         // 命名颜色
         if let Some(color) = Self::parse_named_color(value) {
             return StyleValue::Color(color);
@@ -448,7 +960,7 @@ impl WxssParser {
     
     /// 解析渐变值，提取第一个颜色作为 fallback
     /// 支持格式：linear-gradient(135deg, #ff6b35 0%, #ff8f5a 100%)
-    fn parse_gradient_fallback(value: &str) -> Option<Color> {
+    pub(crate) fn parse_gradient_fallback(value: &str) -> Option<Color> {
         // 找到括号内的内容
         let start = value.find('(')?;
         let end = value.rfind(')')?;
@@ -488,7 +1000,7 @@ impl WxssParser {
         None
     }
     
-    fn parse_length(value: &str) -> Option<(f32, LengthUnit)> {
+    pub(crate) fn parse_length(value: &str) -> Option<(f32, LengthUnit)> {
         let value = value.trim();
         
         let units = [
@@ -564,7 +1076,10 @@ impl WxssParser {
     }
 }
 
-/// rpx 转 px (基于 750 设计稿)
+/// rpx 的设计稿宽度：屏幕宽度固定等于 750rpx
+pub const RPX_DESIGN_WIDTH: f32 = 750.0;
+
+/// rpx 转 px (基于 750 设计稿)，所有 rpx 换算都应经过这里
 pub fn rpx_to_px(rpx: f32, screen_width: f32) -> f32 {
-    rpx * screen_width / 750.0
+    rpx * screen_width / RPX_DESIGN_WIDTH
 }
@@ -12,11 +12,54 @@ pub enum PathCommand {
     Close,
 }
 
+/// 默认展平容差（曲线与折线的最大偏差，单位像素）
+pub const DEFAULT_TOLERANCE: f32 = 0.2;
+
+/// 单条曲线最多展平的线段数
+const MAX_SEGMENTS: usize = 256;
+
+/// 填充规则，与 canvas 2D 的 fill(rule) 一致
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// 非零环绕：同向的子路径叠加，反向的子路径挖空
+    #[default]
+    NonZero,
+    /// 奇偶：被奇数条轮廓包围的区域才填充
+    EvenOdd,
+}
+
+impl FillRule {
+    /// 解析 "nonzero" / "evenodd"，其他值返回 None
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "nonzero" => Some(FillRule::NonZero),
+            "evenodd" => Some(FillRule::EvenOdd),
+            _ => None,
+        }
+    }
+}
+
 /// 路径
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Path {
     commands: Vec<PathCommand>,
     current: Point,
+    /// 曲线展平容差，越小越平滑、线段越多
+    tolerance: f32,
+    /// 填充规则
+    fill_rule: FillRule,
+}
+
+impl Default for Path {
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            current: Point::default(),
+            tolerance: DEFAULT_TOLERANCE,
+            fill_rule: FillRule::NonZero,
+        }
+    }
 }
 
 impl Path {
@@ -24,6 +67,26 @@ impl Path {
         Self::default()
     }
 
+    /// 设置曲线展平容差（光栅化时使用）
+    pub fn set_tolerance(&mut self, tolerance: f32) -> &mut Self {
+        self.tolerance = tolerance.max(0.01);
+        self
+    }
+
+    pub fn tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    /// 设置填充规则
+    pub fn set_fill_rule(&mut self, rule: FillRule) -> &mut Self {
+        self.fill_rule = rule;
+        self
+    }
+
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
     pub fn move_to(&mut self, x: f32, y: f32) -> &mut Self {
         let p = Point::new(x, y);
         self.commands.push(PathCommand::MoveTo(p));
@@ -177,10 +240,16 @@ impl Path {
             return self.add_circle(cx, cy, radius);
         }
         
+        self.arc_sweep(cx, cy, radius, start, angle_diff)
+    }
+    
+    /// 从 start 角度开始扫过 sweep 弧度（负值为逆时针）的圆弧
+    /// 路径为空时以 move_to 开始，否则先连线到圆弧起点
+    fn arc_sweep(&mut self, cx: f32, cy: f32, radius: f32, start: f32, sweep: f32) -> &mut Self {
         // 使用贝塞尔曲线近似圆弧
         // 每 90 度一段
-        let segments = ((angle_diff / (std::f32::consts::PI / 2.0)).ceil() as usize).max(1);
-        let segment_angle = angle_diff / segments as f32;
+        let segments = ((sweep.abs() / (std::f32::consts::PI / 2.0)).ceil() as usize).max(1);
+        let segment_angle = sweep / segments as f32;
         
         // 起点
         let start_x = cx + radius * start.cos();
@@ -222,6 +291,47 @@ impl Path {
         self
     }
     
+    /// 添加扇形（饼图切片）：圆心 -> 圆弧 -> 圆心，闭合
+    /// 角度为弧度，从 x 轴正方向顺时针，end < start 时按顺时针补到 end + 2π
+    pub fn add_pie(&mut self, cx: f32, cy: f32, radius: f32, start: f32, end: f32) -> &mut Self {
+        let sweep = Self::clockwise_sweep(start, end);
+        if sweep >= std::f32::consts::TAU - 0.001 {
+            return self.add_circle(cx, cy, radius);
+        }
+        self.move_to(cx, cy);
+        self.arc_sweep(cx, cy, radius, start, sweep);
+        self.close()
+    }
+    
+    /// 添加环形扇区（环形图的一段）：外圆弧顺时针，内圆弧逆时针返回，闭合
+    pub fn add_ring_segment(&mut self, cx: f32, cy: f32, inner_radius: f32, outer_radius: f32, start: f32, end: f32) -> &mut Self {
+        let sweep = Self::clockwise_sweep(start, end);
+        if sweep >= std::f32::consts::TAU - 0.001 {
+            // 完整圆环：外圆顺时针、内圆逆时针，两种填充规则下内圆部分都为空
+            self.add_circle(cx, cy, outer_radius);
+            if inner_radius > 0.0 {
+                self.move_to(cx + inner_radius, cy);
+                self.arc_sweep(cx, cy, inner_radius, 0.0, -std::f32::consts::TAU);
+                self.close();
+            }
+            return self;
+        }
+        self.move_to(cx + outer_radius * start.cos(), cy + outer_radius * start.sin());
+        self.arc_sweep(cx, cy, outer_radius, start, sweep);
+        self.arc_sweep(cx, cy, inner_radius, start + sweep, -sweep);
+        self.close()
+    }
+    
+    /// start 到 end 的顺时针扫过角度（0 ..= 2π）
+    fn clockwise_sweep(start: f32, end: f32) -> f32 {
+        let diff = end - start;
+        if diff >= std::f32::consts::TAU {
+            std::f32::consts::TAU
+        } else {
+            diff.rem_euclid(std::f32::consts::TAU)
+        }
+    }
+    
     /// 添加圆弧（通过两点和半径）
     pub fn arc_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, radius: f32) -> &mut Self {
         // 简化实现：使用二次贝塞尔曲线近似
@@ -278,10 +388,23 @@ impl Path {
     }
 }
 
+/// 二阶差分的长度 |a - 2b + c|
+fn second_diff(a: &Point, b: &Point, c: &Point) -> f32 {
+    let dx = a.x - 2.0 * b.x + c.x;
+    let dy = a.y - 2.0 * b.y + c.y;
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// 按 Wang 公式计算线段数：n 次曲线需要 sqrt(n(n-1)/8 * M / tolerance) 段，
+/// M 为控制点二阶差分的最大长度。曲线越大、越弯，段数越多
+fn segment_count(degree_factor: f32, max_diff: f32, tolerance: f32) -> usize {
+    let n = (degree_factor * max_diff / tolerance).sqrt().ceil();
+    (n as usize).clamp(1, MAX_SEGMENTS)
+}
+
 /// 二次贝塞尔曲线展平
 fn flatten_quad(p0: &Point, p1: &Point, p2: &Point, tolerance: f32, out: &mut Vec<Point>) {
-    let steps = ((p0.distance(p1) + p1.distance(p2)) / tolerance).ceil() as usize;
-    let steps = steps.max(2).min(100);
+    let steps = segment_count(0.25, second_diff(p0, p1, p2), tolerance);
 
     for i in 1..=steps {
         let t = i as f32 / steps as f32;
@@ -298,8 +421,8 @@ fn flatten_quad(p0: &Point, p1: &Point, p2: &Point, tolerance: f32, out: &mut Ve
 
 /// 三次贝塞尔曲线展平
 fn flatten_cubic(p0: &Point, p1: &Point, p2: &Point, p3: &Point, tolerance: f32, out: &mut Vec<Point>) {
-    let steps = ((p0.distance(p1) + p1.distance(p2) + p2.distance(p3)) / tolerance).ceil() as usize;
-    let steps = steps.max(2).min(100);
+    let max_diff = second_diff(p0, p1, p2).max(second_diff(p1, p2, p3));
+    let steps = segment_count(0.75, max_diff, tolerance);
 
     for i in 1..=steps {
         let t = i as f32 / steps as f32;
@@ -315,3 +438,79 @@ fn flatten_cubic(p0: &Point, p1: &Point, p2: &Point, p3: &Point, tolerance: f32,
         ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment_total(path: &Path, tolerance: f32) -> usize {
+        path.flatten(tolerance).iter().map(|c| c.len().saturating_sub(1)).sum()
+    }
+
+    #[test]
+    fn test_large_arc_has_more_segments() {
+        let mut small = Path::new();
+        small.arc(0.0, 0.0, 5.0, 0.0, std::f32::consts::PI, false);
+        let mut large = Path::new();
+        large.arc(0.0, 0.0, 500.0, 0.0, std::f32::consts::PI, false);
+
+        assert!(segment_total(&large, DEFAULT_TOLERANCE) > segment_total(&small, DEFAULT_TOLERANCE));
+    }
+
+    #[test]
+    fn test_higher_tolerance_reduces_segments() {
+        let mut path = Path::new();
+        path.add_round_rect(0.0, 0.0, 600.0, 400.0, 120.0);
+
+        let fine = segment_total(&path, 0.1);
+        let coarse = segment_total(&path, 2.0);
+        assert!(coarse < fine);
+    }
+
+    #[test]
+    fn test_quarter_pie_fills_one_quadrant() {
+        use crate::{Canvas, Color, Paint};
+
+        let mut canvas = Canvas::new(200, 200);
+        let paint = Paint::new().with_color(Color::rgb(255, 0, 0));
+        canvas.draw_pie(100.0, 100.0, 80.0, 0.0, std::f32::consts::FRAC_PI_2, &paint);
+
+        let painted = |x0: u32, x1: u32, y0: u32, y1: u32| {
+            let mut n = 0;
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    if canvas.get_pixel(x, y).a > 0 { n += 1; }
+                }
+            }
+            n as f32
+        };
+        // 0 到 90 度为右下象限（y 轴向下）
+        let quarter = std::f32::consts::PI * 80.0 * 80.0 / 4.0;
+        let filled = painted(100, 200, 100, 200);
+        assert!((filled - quarter).abs() / quarter < 0.05, "filled = {}, expected ≈ {}", filled, quarter);
+        assert_eq!(painted(0, 200, 0, 99), 0.0);
+        assert_eq!(painted(0, 99, 0, 200), 0.0);
+    }
+
+    #[test]
+    fn test_ring_segment_leaves_center_empty() {
+        use crate::{Canvas, Color, Paint, RingSegment};
+
+        let mut canvas = Canvas::new(200, 200);
+        let paint = Paint::new().with_color(Color::rgb(0, 0, 255));
+        let ring = RingSegment { cx: 100.0, cy: 100.0, inner_radius: 40.0, outer_radius: 80.0, start: 0.0, end: std::f32::consts::PI };
+        canvas.draw_ring_segment(&ring, &paint);
+
+        assert!(canvas.get_pixel(100, 160).a > 0);
+        assert_eq!(canvas.get_pixel(100, 120).a, 0);
+        assert_eq!(canvas.get_pixel(100, 40).a, 0);
+    }
+
+    #[test]
+    fn test_set_tolerance() {
+        let mut path = Path::new();
+        assert_eq!(path.tolerance(), DEFAULT_TOLERANCE);
+        path.set_tolerance(1.5);
+        assert_eq!(path.tolerance(), 1.5);
+    }
+}
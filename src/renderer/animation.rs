@@ -0,0 +1,720 @@
+//! 动画模块 - 解析 wx.createAnimation().export() 导出的动画数据
+//!
+//! 导出格式：
+//! { actions: [ { animates: [{ type, args }], option: { transition: { duration, timingFunction, delay }, transformOrigin } } ] }
+//!
+//! 每个 action 对应一个 step()，按顺序执行；每一步从上一步的结束状态开始插值。
+//!
+//! 另外实现 CSS `transition`：样式中的 transform/opacity 变化时，从当前显示值过渡到新值；
+//! 以及 CSS `animation` + `@keyframes`：按关键帧插值 transform/opacity/background-color。
+
+use crate::Color;
+use serde_json::Value as JsonValue;
+
+/// 动画在某一时刻的属性值
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimatedProps {
+    pub translate_x: f32,
+    pub translate_y: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    /// 旋转角度（度）
+    pub rotate: f32,
+    pub skew_x: f32,
+    pub skew_y: f32,
+    pub opacity: f32,
+    /// 变换原点（相对元素尺寸的比例）
+    pub origin_x: f32,
+    pub origin_y: f32,
+}
+
+impl Default for AnimatedProps {
+    fn default() -> Self {
+        Self {
+            translate_x: 0.0,
+            translate_y: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            rotate: 0.0,
+            skew_x: 0.0,
+            skew_y: 0.0,
+            opacity: 1.0,
+            origin_x: 0.5,
+            origin_y: 0.5,
+        }
+    }
+}
+
+impl AnimatedProps {
+    fn lerp(&self, to: &AnimatedProps, t: f32) -> AnimatedProps {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        AnimatedProps {
+            translate_x: mix(self.translate_x, to.translate_x),
+            translate_y: mix(self.translate_y, to.translate_y),
+            scale_x: mix(self.scale_x, to.scale_x),
+            scale_y: mix(self.scale_y, to.scale_y),
+            rotate: mix(self.rotate, to.rotate),
+            skew_x: mix(self.skew_x, to.skew_x),
+            skew_y: mix(self.skew_y, to.skew_y),
+            opacity: mix(self.opacity, to.opacity),
+            // 原点不参与插值，直接使用当前步骤的值
+            origin_x: to.origin_x,
+            origin_y: to.origin_y,
+        }
+    }
+}
+
+/// 缓动函数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimingFunction {
+    Linear,
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    StepStart,
+    StepEnd,
+}
+
+impl TimingFunction {
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "ease" => TimingFunction::Ease,
+            "ease-in" => TimingFunction::EaseIn,
+            "ease-out" => TimingFunction::EaseOut,
+            "ease-in-out" => TimingFunction::EaseInOut,
+            "step-start" => TimingFunction::StepStart,
+            "step-end" => TimingFunction::StepEnd,
+            _ => TimingFunction::Linear,
+        }
+    }
+
+    /// 将线性进度 t (0..1) 映射为缓动后的进度
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            TimingFunction::Linear => t,
+            TimingFunction::Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, t),
+            TimingFunction::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, t),
+            TimingFunction::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, t),
+            TimingFunction::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+            TimingFunction::StepStart => if t > 0.0 { 1.0 } else { 0.0 },
+            TimingFunction::StepEnd => if t >= 1.0 { 1.0 } else { 0.0 },
+        }
+    }
+}
+
+/// CSS cubic-bezier 求值：先二分求出 x 对应的参数，再计算 y
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let bezier = |a: f32, b: f32, s: f32| {
+        let ms = 1.0 - s;
+        3.0 * ms * ms * s * a + 3.0 * ms * s * s * b + s * s * s
+    };
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    for _ in 0..24 {
+        let mid = (lo + hi) / 2.0;
+        if bezier(x1, x2, mid) < x { lo = mid; } else { hi = mid; }
+    }
+    bezier(y1, y2, (lo + hi) / 2.0)
+}
+
+/// 动画步骤
+#[derive(Debug, Clone)]
+pub struct AnimationStep {
+    pub duration: f32,
+    pub delay: f32,
+    pub timing: TimingFunction,
+    /// 本步骤结束时的属性
+    pub target: AnimatedProps,
+}
+
+/// 动画时间线（一次 export() 的全部步骤）
+#[derive(Debug, Clone)]
+pub struct AnimationTimeline {
+    pub steps: Vec<AnimationStep>,
+}
+
+impl AnimationTimeline {
+    /// 从 export() 的 JSON 解析
+    pub fn from_json(value: &JsonValue) -> Option<Self> {
+        let actions = value.get("actions")?.as_array()?;
+        let mut current = AnimatedProps::default();
+        let mut steps = Vec::new();
+
+        for action in actions {
+            let option = action.get("option");
+            let transition = option.and_then(|o| o.get("transition"));
+            let num = |key: &str, default: f32| {
+                transition.and_then(|t| t.get(key)).and_then(|v| v.as_f64()).map(|v| v as f32).unwrap_or(default)
+            };
+            let timing = transition.and_then(|t| t.get("timingFunction")).and_then(|v| v.as_str())
+                .map(TimingFunction::parse).unwrap_or(TimingFunction::Linear);
+
+            let mut target = current;
+            if let Some(origin) = option.and_then(|o| o.get("transformOrigin")).and_then(|v| v.as_str()) {
+                let (ox, oy) = parse_transform_origin(origin);
+                target.origin_x = ox;
+                target.origin_y = oy;
+            }
+            if let Some(animates) = action.get("animates").and_then(|a| a.as_array()) {
+                for animate in animates {
+                    let kind = animate.get("type").and_then(|t| t.as_str()).unwrap_or("");
+                    let args: Vec<f32> = animate.get("args").and_then(|a| a.as_array())
+                        .map(|a| a.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+                        .unwrap_or_default();
+                    apply_animate(&mut target, kind, &args);
+                }
+            }
+
+            steps.push(AnimationStep { duration: num("duration", 400.0), delay: num("delay", 0.0), timing, target });
+            current = target;
+        }
+
+        if steps.is_empty() { None } else { Some(Self { steps }) }
+    }
+
+    /// 解析模板渲染后的属性字符串（对象会被序列化为单引号 JSON）
+    pub fn from_attr(attr: &str) -> Option<Self> {
+        let json: JsonValue = serde_json::from_str(attr)
+            .or_else(|_| serde_json::from_str(&attr.replace('\'', "\"")))
+            .ok()?;
+        Self::from_json(&json)
+    }
+
+    /// 总时长（毫秒）
+    pub fn total_duration(&self) -> f32 {
+        self.steps.iter().map(|s| s.delay + s.duration).sum()
+    }
+
+    /// 计算经过 elapsed 毫秒时的属性值，结束后保持最后一步的状态
+    pub fn sample(&self, elapsed: f32) -> AnimatedProps {
+        let mut from = AnimatedProps::default();
+        let mut remaining = elapsed.max(0.0);
+
+        for step in &self.steps {
+            let span = step.delay + step.duration;
+            if remaining < span {
+                let local = remaining - step.delay;
+                if local <= 0.0 { return from; }
+                let t = if step.duration > 0.0 { local / step.duration } else { 1.0 };
+                return from.lerp(&step.target, step.timing.apply(t));
+            }
+            remaining -= span;
+            from = step.target;
+        }
+        from
+    }
+
+    pub fn is_finished(&self, elapsed: f32) -> bool {
+        elapsed >= self.total_duration()
+    }
+}
+
+/// 可过渡的属性
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionProperty {
+    All,
+    Opacity,
+    Transform,
+}
+
+/// `transition` 中的一项，如 `opacity 0.3s ease`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionSpec {
+    pub property: TransitionProperty,
+    /// 时长（毫秒）
+    pub duration: f32,
+    /// 延迟（毫秒）
+    pub delay: f32,
+    pub timing: TimingFunction,
+}
+
+impl TransitionSpec {
+    /// 解析 `transition` 属性值，如 `opacity 0.3s ease, transform 0.2s`
+    /// 不支持过渡的属性（如 background-color）会被忽略
+    pub fn parse_list(s: &str) -> Vec<TransitionSpec> {
+        s.split(',').filter_map(Self::parse).collect()
+    }
+
+    fn parse(s: &str) -> Option<TransitionSpec> {
+        let mut spec = TransitionSpec {
+            property: TransitionProperty::All,
+            duration: 0.0,
+            delay: 0.0,
+            timing: TimingFunction::Ease,
+        };
+        let mut times = 0;
+        for token in s.split_whitespace() {
+            if let Some(ms) = parse_time(token) {
+                if times == 0 { spec.duration = ms; } else { spec.delay = ms; }
+                times += 1;
+                continue;
+            }
+            match token {
+                "all" => spec.property = TransitionProperty::All,
+                "opacity" => spec.property = TransitionProperty::Opacity,
+                "transform" => spec.property = TransitionProperty::Transform,
+                "linear" | "ease" | "ease-in" | "ease-out" | "ease-in-out" | "step-start" | "step-end" => {
+                    spec.timing = TimingFunction::parse(token);
+                }
+                // none 或不支持过渡的属性
+                _ => return None,
+            }
+        }
+        if spec.duration > 0.0 { Some(spec) } else { None }
+    }
+
+    fn matches(&self, property: TransitionProperty) -> bool {
+        self.property == TransitionProperty::All || self.property == property
+    }
+}
+
+/// 解析时间值：`0.3s`、`200ms`，返回毫秒
+fn parse_time(s: &str) -> Option<f32> {
+    if let Some(ms) = s.strip_suffix("ms") {
+        return ms.parse().ok();
+    }
+    s.strip_suffix('s').and_then(|v| v.parse::<f32>().ok()).map(|v| v * 1000.0)
+}
+
+/// 单个属性的过渡
+#[derive(Debug, Clone, Copy)]
+struct Tween {
+    from: AnimatedProps,
+    to: AnimatedProps,
+    start: f32,
+    duration: f32,
+    delay: f32,
+    timing: TimingFunction,
+}
+
+impl Tween {
+    /// 直接停在目标值
+    fn settled(to: AnimatedProps) -> Self {
+        Self { from: to, to, start: 0.0, duration: 0.0, delay: 0.0, timing: TimingFunction::Linear }
+    }
+
+    fn sample(&self, clock: f32) -> AnimatedProps {
+        let local = clock - self.start - self.delay;
+        if local <= 0.0 {
+            return self.from;
+        }
+        if self.duration <= 0.0 || local >= self.duration {
+            return self.to;
+        }
+        self.from.lerp(&self.to, self.timing.apply(local / self.duration))
+    }
+
+    fn is_finished(&self, clock: f32) -> bool {
+        clock - self.start >= self.delay + self.duration
+    }
+}
+
+/// 单个节点的 transition 状态：transform 和 opacity 分别过渡
+#[derive(Debug, Clone)]
+pub struct NodeTransition {
+    transform: Tween,
+    opacity: Tween,
+    /// 当前目标值
+    target: AnimatedProps,
+}
+
+impl NodeTransition {
+    /// 首次出现的节点直接显示目标值，不产生过渡
+    pub fn new(target: AnimatedProps) -> Self {
+        Self { transform: Tween::settled(target), opacity: Tween::settled(target), target }
+    }
+
+    /// 样式变化时，从当前显示值开始向新目标过渡
+    pub fn retarget(&mut self, target: AnimatedProps, specs: &[TransitionSpec], clock: f32) {
+        let current = self.sample(clock);
+        let start = |property: TransitionProperty| {
+            match specs.iter().rev().find(|s| s.matches(property)) {
+                Some(spec) => Tween {
+                    from: current,
+                    to: target,
+                    start: clock,
+                    duration: spec.duration,
+                    delay: spec.delay,
+                    timing: spec.timing,
+                },
+                None => Tween::settled(target),
+            }
+        };
+
+        let transform_changed = {
+            let mut a = self.target;
+            a.opacity = target.opacity;
+            a != target
+        };
+        if transform_changed {
+            self.transform = start(TransitionProperty::Transform);
+        }
+        if (self.target.opacity - target.opacity).abs() > f32::EPSILON {
+            self.opacity = start(TransitionProperty::Opacity);
+        }
+        self.target = target;
+    }
+
+    /// 某一时刻的显示值
+    pub fn sample(&self, clock: f32) -> AnimatedProps {
+        let mut props = self.transform.sample(clock);
+        props.opacity = self.opacity.sample(clock).opacity;
+        props
+    }
+
+    pub fn is_finished(&self, clock: f32) -> bool {
+        self.transform.is_finished(clock) && self.opacity.is_finished(clock)
+    }
+}
+
+/// progress 组件 active 动画：进度按每增加 1% 所需的毫秒数匀速变化
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressTween {
+    from: f32,
+    to: f32,
+    start: f32,
+    ms_per_percent: f32,
+}
+
+impl ProgressTween {
+    /// 首次出现的进度条从 0 开始
+    pub fn new(to: f32, ms_per_percent: f32, clock: f32) -> Self {
+        Self { from: 0.0, to, start: clock, ms_per_percent }
+    }
+
+    /// percent 变化时重新开始：active-mode 为 forwards 时从当前值继续，backwards 时从 0 开始
+    pub fn retarget(&mut self, to: f32, forwards: bool, clock: f32) {
+        if (to - self.to).abs() <= f32::EPSILON {
+            return;
+        }
+        self.from = if forwards { self.sample(clock) } else { 0.0 };
+        self.to = to;
+        self.start = clock;
+    }
+
+    pub fn sample(&self, clock: f32) -> f32 {
+        let duration = (self.to - self.from).abs() * self.ms_per_percent;
+        let elapsed = clock - self.start;
+        if duration <= 0.0 || elapsed >= duration {
+            return self.to;
+        }
+        self.from + (self.to - self.from) * (elapsed / duration).max(0.0)
+    }
+
+    pub fn is_finished(&self, clock: f32) -> bool {
+        clock - self.start >= (self.to - self.from).abs() * self.ms_per_percent
+    }
+}
+
+/// animation-direction
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AnimationDirection {
+    #[default]
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+/// `animation` 简写，如 `spin 1s linear infinite`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationSpec {
+    /// @keyframes 名称
+    pub name: String,
+    /// 单次时长（毫秒）
+    pub duration: f32,
+    /// 延迟（毫秒）
+    pub delay: f32,
+    pub timing: TimingFunction,
+    /// 播放次数，infinite 为 f32::INFINITY
+    pub iteration_count: f32,
+    pub direction: AnimationDirection,
+    /// animation-fill-mode 为 forwards / both 时结束后保持最后一帧
+    pub fill_forwards: bool,
+}
+
+impl AnimationSpec {
+    /// 解析 `animation` 简写，多个动画时只取第一个
+    pub fn parse(s: &str) -> Option<AnimationSpec> {
+        let first = s.split(',').next()?;
+        let mut spec = AnimationSpec {
+            name: String::new(),
+            duration: 0.0,
+            delay: 0.0,
+            timing: TimingFunction::Ease,
+            iteration_count: 1.0,
+            direction: AnimationDirection::Normal,
+            fill_forwards: false,
+        };
+        let mut times = 0;
+        for token in first.split_whitespace() {
+            if let Some(ms) = parse_time(token) {
+                if times == 0 { spec.duration = ms; } else { spec.delay = ms; }
+                times += 1;
+                continue;
+            }
+            match token {
+                "linear" | "ease" | "ease-in" | "ease-out" | "ease-in-out" | "step-start" | "step-end" => {
+                    spec.timing = TimingFunction::parse(token);
+                }
+                "infinite" => spec.iteration_count = f32::INFINITY,
+                "normal" => spec.direction = AnimationDirection::Normal,
+                "reverse" => spec.direction = AnimationDirection::Reverse,
+                "alternate" => spec.direction = AnimationDirection::Alternate,
+                "alternate-reverse" => spec.direction = AnimationDirection::AlternateReverse,
+                "forwards" | "both" => spec.fill_forwards = true,
+                "backwards" | "running" | "paused" => {}
+                "none" => return None,
+                _ => match token.parse::<f32>() {
+                    Ok(n) => spec.iteration_count = n.max(0.0),
+                    Err(_) => spec.name = token.to_string(),
+                },
+            }
+        }
+        if spec.name.is_empty() || spec.duration <= 0.0 { None } else { Some(spec) }
+    }
+}
+
+/// 关键帧中可动画的属性，未声明的属性为 None
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct KeyframeProps {
+    /// 只使用其中的变换字段，opacity 单独记录
+    pub transform: Option<AnimatedProps>,
+    pub opacity: Option<f32>,
+    pub background_color: Option<Color>,
+}
+
+/// 一个节点上运行的 CSS 动画：animation 声明 + 解析后的关键帧
+#[derive(Debug, Clone)]
+pub struct KeyframeAnimation {
+    pub spec: AnimationSpec,
+    /// (offset, 属性)，按 offset 排序
+    pub frames: Vec<(f32, KeyframeProps)>,
+}
+
+impl KeyframeAnimation {
+    pub fn new(spec: AnimationSpec, mut frames: Vec<(f32, KeyframeProps)>) -> Self {
+        frames.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { spec, frames }
+    }
+
+    /// 经过 elapsed 毫秒时在关键帧上的位置（0..1，已考虑播放方向），
+    /// 延迟期间或结束后（未设置 forwards）返回 None
+    pub fn progress(&self, elapsed: f32) -> Option<f32> {
+        let spec = &self.spec;
+        let local = elapsed - spec.delay;
+        if local < 0.0 || spec.iteration_count <= 0.0 {
+            return None;
+        }
+        let iterations = local / spec.duration;
+        let (index, frac) = if iterations >= spec.iteration_count {
+            if !spec.fill_forwards {
+                return None;
+            }
+            // 停在最后一次播放的结束位置
+            let end = spec.iteration_count;
+            let index = (end.ceil() - 1.0).max(0.0);
+            (index, end - index)
+        } else {
+            (iterations.floor(), iterations.fract())
+        };
+        let odd = index as u64 % 2 == 1;
+        let reversed = match spec.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => odd,
+            AnimationDirection::AlternateReverse => !odd,
+        };
+        Some(if reversed { 1.0 - frac } else { frac })
+    }
+
+    /// 计算某一时刻的属性值，base 为元素自身的样式值（用于缺少 0%/100% 帧的属性和动画未生效时）
+    pub fn sample(&self, elapsed: f32, base: &KeyframeProps) -> KeyframeProps {
+        let Some(p) = self.progress(elapsed) else { return *base };
+        let timing = self.spec.timing;
+        KeyframeProps {
+            transform: self.track(p, timing, base.transform, |f| f.transform)
+                .map(|(a, b, t)| a.lerp(&b, t)),
+            opacity: self.track(p, timing, base.opacity, |f| f.opacity)
+                .map(|(a, b, t)| a + (b - a) * t),
+            background_color: self.track(p, timing, base.background_color, |f| f.background_color)
+                .map(|(a, b, t)| lerp_color(a, b, t)),
+        }
+    }
+
+    /// 在声明了某属性的帧之间定位 p，返回 (起始值, 结束值, 缓动后的进度)
+    /// 缓动函数作用于每一段关键帧之间
+    fn track<T: Copy>(
+        &self,
+        p: f32,
+        timing: TimingFunction,
+        base: Option<T>,
+        get: impl Fn(&KeyframeProps) -> Option<T>,
+    ) -> Option<(T, T, f32)> {
+        let mut stops: Vec<(f32, T)> = self.frames.iter()
+            .filter_map(|(offset, props)| get(props).map(|v| (*offset, v)))
+            .collect();
+        if stops.is_empty() {
+            return base.map(|b| (b, b, 0.0));
+        }
+        // 缺少 0% / 100% 帧时使用元素自身的值
+        if let Some(b) = base {
+            if stops[0].0 > 0.0 { stops.insert(0, (0.0, b)); }
+            if stops[stops.len() - 1].0 < 1.0 { stops.push((1.0, b)); }
+        }
+        let first = stops[0];
+        if p <= first.0 {
+            return Some((first.1, first.1, 0.0));
+        }
+        for pair in stops.windows(2) {
+            let ((o0, v0), (o1, v1)) = (pair[0], pair[1]);
+            if p <= o1 {
+                let t = if o1 > o0 { (p - o0) / (o1 - o0) } else { 1.0 };
+                return Some((v0, v1, timing.apply(t)));
+            }
+        }
+        let last = stops[stops.len() - 1];
+        Some((last.1, last.1, 0.0))
+    }
+
+    pub fn is_finished(&self, elapsed: f32) -> bool {
+        elapsed >= self.spec.delay + self.spec.duration * self.spec.iteration_count
+    }
+}
+
+fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    let mix = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round().clamp(0.0, 255.0) as u8;
+    Color::new(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b), mix(a.a, b.a))
+}
+
+/// 应用单个动画操作到目标状态
+fn apply_animate(target: &mut AnimatedProps, kind: &str, args: &[f32]) {
+    let arg = |i: usize| args.get(i).copied();
+    match kind {
+        "translate" => {
+            target.translate_x = arg(0).unwrap_or(0.0);
+            target.translate_y = arg(1).unwrap_or(0.0);
+        }
+        "translateX" => target.translate_x = arg(0).unwrap_or(0.0),
+        "translateY" => target.translate_y = arg(0).unwrap_or(0.0),
+        "scale" => {
+            let sx = arg(0).unwrap_or(1.0);
+            target.scale_x = sx;
+            target.scale_y = arg(1).unwrap_or(sx);
+        }
+        "scaleX" => target.scale_x = arg(0).unwrap_or(1.0),
+        "scaleY" => target.scale_y = arg(0).unwrap_or(1.0),
+        "rotate" | "rotateZ" => target.rotate = arg(0).unwrap_or(0.0),
+        "skew" => {
+            target.skew_x = arg(0).unwrap_or(0.0);
+            target.skew_y = arg(1).unwrap_or(0.0);
+        }
+        "skewX" => target.skew_x = arg(0).unwrap_or(0.0),
+        "skewY" => target.skew_y = arg(0).unwrap_or(0.0),
+        "opacity" => target.opacity = arg(0).unwrap_or(1.0).clamp(0.0, 1.0),
+        _ => {}
+    }
+}
+
+/// 解析 transformOrigin，如 "50% 50% 0"、"left top"
+fn parse_transform_origin(s: &str) -> (f32, f32) {
+    let parse = |part: &str| -> Option<f32> {
+        match part {
+            "left" | "top" => Some(0.0),
+            "center" => Some(0.5),
+            "right" | "bottom" => Some(1.0),
+            p if p.ends_with('%') => p.trim_end_matches('%').parse::<f32>().ok().map(|v| v / 100.0),
+            _ => None,
+        }
+    };
+    let mut parts = s.split_whitespace();
+    let x = parts.next().and_then(parse).unwrap_or(0.5);
+    let y = parts.next().and_then(parse).unwrap_or(0.5);
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transition_list() {
+        let specs = TransitionSpec::parse_list("opacity 0.3s ease-in, transform 200ms linear 50ms, color 1s");
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0].property, TransitionProperty::Opacity);
+        assert_eq!(specs[0].duration, 300.0);
+        assert_eq!(specs[0].timing, TimingFunction::EaseIn);
+        assert_eq!(specs[1].property, TransitionProperty::Transform);
+        assert_eq!((specs[1].duration, specs[1].delay), (200.0, 50.0));
+        assert!(TransitionSpec::parse_list("none").is_empty());
+    }
+
+    #[test]
+    fn test_transition_interpolates_from_current_value() {
+        let specs = TransitionSpec::parse_list("opacity 100ms linear, transform 200ms linear");
+        let mut transition = NodeTransition::new(AnimatedProps::default());
+
+        let target = AnimatedProps { opacity: 0.0, translate_x: 100.0, ..Default::default() };
+        transition.retarget(target, &specs, 0.0);
+        let mid = transition.sample(50.0);
+        assert!((mid.opacity - 0.5).abs() < 1e-4);
+        assert!((mid.translate_x - 25.0).abs() < 1e-4);
+        assert!(!transition.is_finished(150.0));
+
+        // 过渡中途改回原值，从当前位置反向过渡
+        transition.retarget(AnimatedProps::default(), &specs, 100.0);
+        assert!((transition.sample(100.0).translate_x - 50.0).abs() < 1e-4);
+        assert!((transition.sample(200.0).translate_x - 25.0).abs() < 1e-4);
+        assert!(transition.is_finished(300.0));
+        assert_eq!(transition.sample(300.0), AnimatedProps::default());
+    }
+
+    #[test]
+    fn test_parse_animation_shorthand() {
+        let spec = AnimationSpec::parse("pulse 2s ease-in-out 0.5s infinite alternate").unwrap();
+        assert_eq!(spec.name, "pulse");
+        assert_eq!((spec.duration, spec.delay), (2000.0, 500.0));
+        assert_eq!(spec.timing, TimingFunction::EaseInOut);
+        assert!(spec.iteration_count.is_infinite());
+        assert_eq!(spec.direction, AnimationDirection::Alternate);
+        assert_eq!(AnimationSpec::parse("spin 300ms 3").unwrap().iteration_count, 3.0);
+        assert!(AnimationSpec::parse("none").is_none());
+    }
+
+    #[test]
+    fn test_keyframe_sample_at_half() {
+        let spec = AnimationSpec::parse("spin 1s linear infinite").unwrap();
+        let frame = |rotate: f32, translate_x: f32| KeyframeProps {
+            transform: Some(AnimatedProps { rotate, translate_x, ..Default::default() }),
+            ..Default::default()
+        };
+        let animation = KeyframeAnimation::new(spec, vec![(0.0, frame(0.0, 0.0)), (1.0, frame(360.0, 40.0))]);
+        let base = KeyframeProps::default();
+
+        let mid = animation.sample(500.0, &base).transform.unwrap();
+        assert!((mid.rotate - 180.0).abs() < 1e-3);
+        assert!((mid.translate_x - 20.0).abs() < 1e-3);
+        // 无限循环：第二轮的同一位置
+        let again = animation.sample(1500.0, &base).transform.unwrap();
+        assert!((again.rotate - 180.0).abs() < 1e-3);
+        assert!(!animation.is_finished(10_000.0));
+    }
+
+    #[test]
+    fn test_keyframe_missing_end_uses_base_value() {
+        let spec = AnimationSpec::parse("fade 1s linear alternate 2").unwrap();
+        let frames = vec![(0.0, KeyframeProps { opacity: Some(0.0), ..Default::default() })];
+        let animation = KeyframeAnimation::new(spec, frames);
+        let base = KeyframeProps { opacity: Some(1.0), background_color: Some(Color::WHITE), ..Default::default() };
+
+        let first = animation.sample(250.0, &base);
+        assert!((first.opacity.unwrap() - 0.25).abs() < 1e-4);
+        assert_eq!(first.background_color, Some(Color::WHITE));
+        // 第二轮反向播放
+        assert!((animation.sample(1250.0, &base).opacity.unwrap() - 0.75).abs() < 1e-4);
+        // 结束后恢复元素自身的值
+        assert!(animation.is_finished(2000.0));
+        assert_eq!(animation.sample(2000.0, &base), base);
+    }
+}
@@ -1,9 +1,12 @@
 //! 组件基础定义
 
 use crate::parser::wxml::WxmlNode;
-use crate::parser::wxss::{StyleSheet, StyleValue, LengthUnit, rpx_to_px};
-use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect as GeoRect};
-use std::collections::HashMap;
+use crate::parser::wxss::{PseudoState, StyleSheet, StyleValue, LengthUnit, WxssParser, parse_calc, resolve_env_refs, resolve_var_refs, rpx_to_px, strip_important};
+use crate::renderer::animation::{AnimatedProps, AnimationSpec, KeyframeAnimation, KeyframeProps, TransitionSpec};
+use crate::text::FontSpec;
+use crate::{Canvas, ClipShape, Color, FillRule, Matrix, Paint, PaintStyle, Path, Rect as GeoRect};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use taffy::prelude::*;
 
 /// 渲染节点
@@ -23,6 +26,12 @@ pub struct RenderNode {
 #[derive(Clone, Default)]
 pub struct NodeStyle {
     pub background_color: Option<Color>,
+    /// background-image: url(...) 及其尺寸、位置、重复方式
+    pub background_image: Option<BackgroundImage>,
+    /// background / background-image 中的 linear-gradient / radial-gradient
+    pub background_gradient: Option<CssGradient>,
+    /// background-clip: text，背景只透过文字字形显示（渐变文字）
+    pub background_clip_text: bool,
     pub text_color: Option<Color>,
     pub border_color: Option<Color>,
     pub border_width: f32,
@@ -31,8 +40,16 @@ pub struct NodeStyle {
     pub border_radius_tr: Option<f32>,
     pub border_radius_br: Option<f32>,
     pub border_radius_bl: Option<f32>,
+    /// 单边边框：上、右、下、左（border-top 等），按实线绘制，不受圆角影响
+    pub border_sides: [Option<BorderSide>; 4],
+    /// border-collapse: collapse，相邻子元素的边框合并为一条分隔线，并去掉末尾的分隔线
+    pub border_collapse: bool,
     pub font_size: f32,
     pub font_weight: FontWeight,
+    /// font-family 声明值（按优先级），None 表示继承父元素
+    pub font_family: Option<Arc<[String]>>,
+    /// 继承解析后的 font-family
+    pub used_font_family: Option<Arc<[String]>>,
     pub opacity: f32,
     pub text_align: TextAlign,
     pub text_decoration: TextDecoration,
@@ -52,14 +69,68 @@ pub struct NodeStyle {
     pub is_fixed: bool,
     /// fixed 定位的 bottom 值
     pub fixed_bottom: Option<f32>,
-    /// fixed 定位的 top 值
+    /// fixed 定位的 top 值，sticky 定位时为吸顶距离
     pub fixed_top: Option<f32>,
     /// fixed 定位的 left 值
     pub fixed_left: Option<f32>,
     /// fixed 定位的 right 值
     pub fixed_right: Option<f32>,
+    /// 是否是 sticky 定位（滚动到 top 位置后吸顶，直到父元素底部离开）
+    pub is_sticky: bool,
+    /// visibility 声明值：Some(false)=hidden，Some(true)=visible，None=继承父元素
+    pub visible: Option<bool>,
+    /// 继承解析后的不可见状态（保留布局占位，但跳过绘制和事件）
+    pub hidden: bool,
     /// 是否是 block 显示（占满整行）
     pub is_block: bool,
+    /// 显式声明了 display: flex（子元素按 flex 项目排列，不再按行内流排列）
+    pub is_flex: bool,
+    /// display: inline / inline-block（宽度贴合内容，与相邻行内元素排在同一行）
+    pub inline_level: Option<InlineLevel>,
+    /// width / min-width / max-width 的内容尺寸关键字（布局前由内容尺寸解析）
+    pub width_keyword: Option<SizeKeyword>,
+    pub min_width_keyword: Option<SizeKeyword>,
+    pub max_width_keyword: Option<SizeKeyword>,
+    /// transition 声明（transform / opacity 变化时过渡）
+    pub transitions: Vec<TransitionSpec>,
+    /// animation 声明及对应的 @keyframes
+    pub animation: Option<KeyframeAnimation>,
+    /// 元素上可用的 CSS 变量（继承自父元素并合并自身声明），传给子元素
+    pub css_vars: Arc<HashMap<String, String>>,
+}
+
+/// 内容尺寸关键字：min-content / max-content / fit-content
+///
+/// Taffy 0.4 的 Dimension 不支持这些关键字，先按 auto 处理，
+/// 子树构建完成后再由 `resolve_intrinsic_width` 换算成固定长度。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SizeKeyword {
+    MinContent,
+    MaxContent,
+    FitContent,
+}
+
+impl SizeKeyword {
+    pub fn parse(v: &StyleValue) -> Option<Self> {
+        match v {
+            StyleValue::String(s) => match s.trim() {
+                "min-content" => Some(SizeKeyword::MinContent),
+                "max-content" => Some(SizeKeyword::MaxContent),
+                "fit-content" => Some(SizeKeyword::FitContent),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// 行内级别的 display 取值
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InlineLevel {
+    /// display: inline，忽略 width / height，尺寸完全由内容决定
+    Inline,
+    /// display: inline-block，保留盒子尺寸，未设置宽度时贴合内容
+    InlineBlock,
 }
 
 #[derive(Clone, Copy, Default, PartialEq)]
@@ -78,6 +149,23 @@ pub enum FontWeight {
     W900,
 }
 
+impl FontWeight {
+    /// 数值字重（normal = 400，bold = 700）
+    pub fn value(self) -> u16 {
+        match self {
+            FontWeight::W100 => 100,
+            FontWeight::W200 => 200,
+            FontWeight::W300 => 300,
+            FontWeight::Normal | FontWeight::W400 => 400,
+            FontWeight::W500 => 500,
+            FontWeight::W600 => 600,
+            FontWeight::Bold | FontWeight::W700 => 700,
+            FontWeight::W800 => 800,
+            FontWeight::W900 => 900,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Default, PartialEq)]
 pub enum TextAlign {
     #[default]
@@ -140,6 +228,20 @@ pub enum WordBreak {
     BreakWord,
 }
 
+/// 单边边框
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderSide {
+    /// 宽度（物理像素）
+    pub width: f32,
+    pub color: Color,
+}
+
+/// border_sides 的下标
+pub const BORDER_TOP: usize = 0;
+pub const BORDER_RIGHT: usize = 1;
+pub const BORDER_BOTTOM: usize = 2;
+pub const BORDER_LEFT: usize = 3;
+
 /// 盒子阴影
 #[derive(Clone, Copy, Default)]
 pub struct BoxShadow {
@@ -151,8 +253,221 @@ pub struct BoxShadow {
     pub inset: bool,
 }
 
+/// 背景图片的长度值（物理像素或相对背景区域的百分比）
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BackgroundLength {
+    #[default]
+    Auto,
+    Px(f32),
+    Percent(f32),
+}
+
+impl BackgroundLength {
+    /// 按背景区域尺寸换算为物理像素，auto 返回 None
+    fn resolve(&self, container: f32) -> Option<f32> {
+        match self {
+            BackgroundLength::Auto => None,
+            BackgroundLength::Px(v) => Some(*v),
+            BackgroundLength::Percent(p) => Some(container * p / 100.0),
+        }
+    }
+}
+
+/// background-size
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BackgroundSize {
+    /// 图片原始尺寸
+    #[default]
+    Auto,
+    Cover,
+    Contain,
+    /// 宽高，一边为 auto 时按图片比例计算
+    Size(BackgroundLength, BackgroundLength),
+}
+
+/// 背景图片
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundImage {
+    /// url(...) 中的地址，为空时只记录了尺寸 / 位置等属性
+    pub src: String,
+    pub size: BackgroundSize,
+    /// background-position，百分比表示图片与背景区域的对齐点
+    pub position: (BackgroundLength, BackgroundLength),
+    /// background-repeat 在 x / y 方向是否平铺，默认 no-repeat
+    pub repeat: (bool, bool),
+    /// 图片像素与物理像素的比例（auto 尺寸时使用）
+    pub pixel_ratio: f32,
+}
+
+impl BackgroundImage {
+    fn new(pixel_ratio: f32) -> Self {
+        Self {
+            src: String::new(),
+            size: BackgroundSize::Auto,
+            position: (BackgroundLength::Percent(0.0), BackgroundLength::Percent(0.0)),
+            repeat: (false, false),
+            pixel_ratio,
+        }
+    }
+}
+
+impl NodeStyle {
+    /// 绘制和测量文本使用的字体
+    pub fn font(&self) -> FontSpec<'_> {
+        FontSpec { families: self.used_font_family.as_deref().unwrap_or(&[]), weight: self.font_weight.value() }
+    }
+    
+    /// 行高（物理像素），未设置时为字号的 1.5 倍
+    pub fn used_line_height(&self, sf: f32) -> f32 {
+        self.line_height.unwrap_or(self.font_size * 1.5) * sf
+    }
+}
+
+/// CSS 渐变，linear-gradient 按角度铺满盒子，radial-gradient 以盒子中心为圆心、到最远角为半径
+#[derive(Clone, Debug, PartialEq)]
+pub struct CssGradient {
+    /// linear-gradient 的角度（0deg 指向上方，顺时针），radial-gradient 为 None
+    pub angle: Option<f32>,
+    /// 颜色停止点，位置为渐变线上 0~1 的比例
+    pub stops: Vec<(f32, Color)>,
+}
+
+impl CssGradient {
+    /// 解析 `linear-gradient(135deg, #ff6b35 0%, #ff8f5a 100%)` / `radial-gradient(circle, red, blue)`。
+    /// 只支持百分比位置，其他单位的位置按均匀分布处理
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let radial = s.starts_with("radial-gradient(");
+        if !radial && !s.starts_with("linear-gradient(") {
+            return None;
+        }
+        let inner = &s[s.find('(')? + 1..s.rfind(')')?];
+        let mut angle = if radial { None } else { Some(180.0) };
+        let mut stops: Vec<(Option<f32>, Color)> = Vec::new();
+        for (i, part) in split_top_level_commas(inner).into_iter().enumerate() {
+            let part = part.trim();
+            if i == 0 && !radial {
+                if let Some(deg) = part.strip_suffix("deg").and_then(|d| d.trim().parse::<f32>().ok()) {
+                    angle = Some(deg);
+                    continue;
+                }
+                if let Some(side) = part.strip_prefix("to ") {
+                    angle = Some(gradient_side_angle(side)?);
+                    continue;
+                }
+            }
+            let (color, position) = match part.rsplit_once(' ') {
+                Some((c, p)) if !p.ends_with(')') => match WxssParser::parse_length(p) {
+                    Some((n, LengthUnit::Percent)) => (c, Some(n / 100.0)),
+                    Some(_) => (c, None),
+                    None => (part, None),
+                },
+                _ => (part, None),
+            };
+            // radial-gradient 的形状 / 圆心参数（circle、ellipse at center 等）不是颜色，跳过
+            if let StyleValue::Color(c) = WxssParser::parse_value("color", color.trim()) {
+                stops.push((position, c));
+            }
+        }
+        if stops.is_empty() {
+            return None;
+        }
+        
+        // 未指定位置的停止点：首尾分别为 0 和 1，中间的在相邻已知位置之间均匀分布
+        let last = stops.len() - 1;
+        if stops[0].0.is_none() { stops[0].0 = Some(0.0); }
+        if stops[last].0.is_none() { stops[last].0 = Some(if last == 0 { 0.0 } else { 1.0 }); }
+        let known: Vec<Option<f32>> = stops.iter().map(|s| s.0).collect();
+        let mut resolved: Vec<(f32, Color)> = Vec::with_capacity(stops.len());
+        for (k, &(position, color)) in stops.iter().enumerate() {
+            let t = position.unwrap_or_else(|| {
+                let a = (0..k).rev().find(|&a| known[a].is_some()).unwrap_or(0);
+                let b = (k + 1..=last).find(|&b| known[b].is_some()).unwrap_or(last);
+                let (ta, tb) = (known[a].unwrap_or(0.0), known[b].unwrap_or(1.0));
+                ta + (tb - ta) * (k - a) as f32 / (b - a) as f32
+            });
+            resolved.push((t, color));
+        }
+        // 位置不能倒退
+        for k in 1..resolved.len() {
+            resolved[k].0 = resolved[k].0.max(resolved[k - 1].0);
+        }
+        Some(Self { angle, stops: resolved })
+    }
+    
+    /// 渐变铺满盒子 (x, y, w, h) 时点 (px, py) 的颜色
+    pub fn sample(&self, px: f32, py: f32, x: f32, y: f32, w: f32, h: f32) -> Color {
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        let t = match self.angle {
+            Some(deg) => {
+                let (sin, cos) = deg.to_radians().sin_cos();
+                // 渐变线经过中心，长度使两端的垂线恰好经过盒子的角
+                let len = w * sin.abs() + h * cos.abs();
+                if len <= 0.0 { 0.0 } else { ((px - cx) * sin - (py - cy) * cos) / len + 0.5 }
+            }
+            None => {
+                let radius = (w * w + h * h).sqrt() / 2.0;
+                if radius <= 0.0 { 0.0 } else { ((px - cx).powi(2) + (py - cy).powi(2)).sqrt() / radius }
+            }
+        };
+        self.color_at(t.clamp(0.0, 1.0))
+    }
+    
+    fn color_at(&self, t: f32) -> Color {
+        let first = self.stops[0];
+        if t <= first.0 {
+            return first.1;
+        }
+        for pair in self.stops.windows(2) {
+            let ((t0, c0), (t1, c1)) = (pair[0], pair[1]);
+            if t <= t1 {
+                let ratio = if t1 > t0 { (t - t0) / (t1 - t0) } else { 1.0 };
+                let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+                return Color::new(mix(c0.r, c1.r), mix(c0.g, c1.g), mix(c0.b, c1.b), mix(c0.a, c1.a));
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+/// `to right` / `to top left` 等方向对应的角度
+fn gradient_side_angle(side: &str) -> Option<f32> {
+    let mut words: Vec<&str> = side.split_whitespace().collect();
+    words.sort_unstable();
+    Some(match words.as_slice() {
+        ["top"] => 0.0,
+        ["right", "top"] => 45.0,
+        ["right"] => 90.0,
+        ["bottom", "right"] => 135.0,
+        ["bottom"] => 180.0,
+        ["bottom", "left"] => 225.0,
+        ["left"] => 270.0,
+        ["left", "top"] => 315.0,
+        _ => return None,
+    })
+}
+
+/// 按不在括号内的逗号分割（rgb(...) 中的逗号不拆开）
+fn split_top_level_commas(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
 /// 变换
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy)]
 pub struct Transform {
     pub translate_x: f32,
     pub translate_y: f32,
@@ -161,14 +476,86 @@ pub struct Transform {
     pub rotate: f32, // 角度
     pub skew_x: f32,
     pub skew_y: f32,
+    /// 变换原点（相对元素尺寸的比例，默认中心）
+    pub origin_x: f32,
+    pub origin_y: f32,
 }
 
-impl Transform {
-    pub fn new() -> Self {
+/// 默认为恒等变换，原点在中心
+impl Default for Transform {
+    fn default() -> Self {
         Self {
+            translate_x: 0.0,
+            translate_y: 0.0,
             scale_x: 1.0,
             scale_y: 1.0,
-            ..Default::default()
+            rotate: 0.0,
+            skew_x: 0.0,
+            skew_y: 0.0,
+            origin_x: 0.5,
+            origin_y: 0.5,
+        }
+    }
+}
+
+impl Transform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// 对元素盒子应用平移和缩放（绕变换原点），返回变换后的 (x, y, w, h)
+    /// 平移量为逻辑像素，sf 为缩放因子
+    pub fn apply_to_box(&self, x: f32, y: f32, w: f32, h: f32, sf: f32) -> (f32, f32, f32, f32) {
+        let origin_x = x + w * self.origin_x;
+        let origin_y = y + h * self.origin_y;
+        let nx = origin_x + (x - origin_x) * self.scale_x + self.translate_x * sf;
+        let ny = origin_y + (y - origin_y) * self.scale_y + self.translate_y * sf;
+        (nx, ny, w * self.scale_x, h * self.scale_y)
+    }
+    
+    /// 旋转、斜切无法按盒子直接绘制；有子节点的缩放需要子树一起缩放。
+    /// 这两种情况先绘制到图层再按矩阵合成
+    pub fn needs_layer(&self, has_children: bool) -> bool {
+        self.rotate != 0.0 || self.skew_x != 0.0 || self.skew_y != 0.0
+            || (has_children && (self.scale_x != 1.0 || self.scale_y != 1.0))
+    }
+    
+    /// 绕 (px, py) 的缩放、斜切、旋转矩阵（不含平移，平移在布局位置上处理）
+    pub fn matrix_about(&self, px: f32, py: f32) -> Matrix {
+        Matrix::translate(px, py)
+            .concat(&Matrix::rotate(self.rotate))
+            .concat(&Matrix::skew(self.skew_x, self.skew_y))
+            .concat(&Matrix::scale(self.scale_x, self.scale_y))
+            .concat(&Matrix::translate(-px, -py))
+    }
+    
+    /// 转换为动画插值用的属性
+    pub fn to_animated_props(&self, opacity: f32) -> AnimatedProps {
+        AnimatedProps {
+            translate_x: self.translate_x,
+            translate_y: self.translate_y,
+            scale_x: self.scale_x,
+            scale_y: self.scale_y,
+            rotate: self.rotate,
+            skew_x: self.skew_x,
+            skew_y: self.skew_y,
+            opacity,
+            origin_x: self.origin_x,
+            origin_y: self.origin_y,
+        }
+    }
+    
+    pub fn from_animated_props(props: &AnimatedProps) -> Self {
+        Self {
+            translate_x: props.translate_x,
+            translate_y: props.translate_y,
+            scale_x: props.scale_x,
+            scale_y: props.scale_y,
+            rotate: props.rotate,
+            skew_x: props.skew_x,
+            skew_y: props.skew_y,
+            origin_x: props.origin_x,
+            origin_y: props.origin_y,
         }
     }
 }
@@ -180,6 +567,12 @@ pub struct ComponentContext<'a> {
     pub screen_height: f32,
     pub stylesheet: &'a StyleSheet,
     pub taffy: &'a mut TaffyTree,
+    /// 从父元素继承的 CSS 变量（--name -> 值）
+    pub css_vars: Arc<HashMap<String, String>>,
+    /// 正在构建的元素的交互状态（:active / :hover），由渲染器根据 InteractionManager 提供
+    pub pseudo_state: PseudoState,
+    /// 安全区域内边距（上、右、下、左，逻辑像素），用于 env(safe-area-inset-*)
+    pub safe_area_insets: [f32; 4],
 }
 
 /// 组件 trait
@@ -222,7 +615,11 @@ pub fn parse_color_str(s: &str) -> Option<Color> {
 /// 返回 (event_type, handler, data, is_catch)
 pub fn extract_events(node: &WxmlNode) -> Vec<(String, String, HashMap<String, String>, bool)> {
     let mut events = vec![];
-    for attr in ["bindtap", "catchtap", "bindchange", "bindinput", "bindblur", "bindfocus", "bindconfirm", "bindlinechange"] {
+    for attr in [
+        "bindtap", "catchtap", "bindchange", "bindinput", "bindblur", "bindfocus", "bindconfirm", "bindlinechange",
+        "bindtouchstart", "catchtouchstart", "bindtouchmove", "catchtouchmove", "bindtouchend", "catchtouchend",
+        "bindgetuserinfo", "bindgetphonenumber", "bindchooseavatar", "bindopensetting", "bindcontact",
+    ] {
         if let Some(h) = node.get_attr(attr) {
             let mut d = HashMap::new();
             // 添加 data-* 属性
@@ -282,6 +679,9 @@ pub fn to_px(v: &StyleValue, screen_width: f32, screen_height: f32) -> Option<f3
             LengthUnit::Vh => *n / 100.0 * screen_height,
         }),
         StyleValue::Number(n) => Some(*n),
+        StyleValue::Calc(expr) => Some(expr.evaluate(&|n, unit| {
+            to_px(&StyleValue::Length(n, unit), screen_width, screen_height).unwrap_or(0.0)
+        })),
         _ => None,
     }
 }
@@ -290,20 +690,111 @@ pub fn to_px(v: &StyleValue, screen_width: f32, screen_height: f32) -> Option<f3
 pub fn to_dimension(v: &StyleValue, screen_width: f32, screen_height: f32, sf: f32) -> Option<Dimension> {
     match v {
         StyleValue::Auto => Some(Dimension::Auto),
+        // 内容尺寸关键字先按 auto 布局，由 resolve_intrinsic_width 换算
+        _ if SizeKeyword::parse(v).is_some() => Some(Dimension::Auto),
         StyleValue::Length(n, LengthUnit::Percent) => Some(percent(*n / 100.0)),
         _ => to_px(v, screen_width, screen_height).map(|px| length(px * sf)),
     }
 }
 
+/// 将 width / min-width / max-width 上的内容尺寸关键字换算为固定长度
+///
+/// 子树构建完成后调用：分别在 min-content / max-content 可用空间下测量节点，
+/// fit-content 取 max-content 宽度，并用 max-width: 100% 限制不超过父容器。
+/// 设置了固定宽度后，节点在列方向容器中也不会被 stretch 拉伸。
+pub fn resolve_intrinsic_width(taffy: &mut TaffyTree, node: NodeId, ns: &NodeStyle) {
+    if ns.width_keyword.is_none() && ns.min_width_keyword.is_none() && ns.max_width_keyword.is_none() {
+        return;
+    }
+    let Ok(mut style) = taffy.style(node).cloned() else { return };
+
+    let mut measure = |space: AvailableSpace| -> f32 {
+        taffy.compute_layout(node, Size { width: space, height: AvailableSpace::MaxContent }).ok();
+        taffy.layout(node).map(|l| l.size.width).unwrap_or(0.0)
+    };
+    let min_content = measure(AvailableSpace::MinContent);
+    let max_content = measure(AvailableSpace::MaxContent);
+    let resolve = |kw: SizeKeyword| match kw {
+        SizeKeyword::MinContent => min_content,
+        SizeKeyword::MaxContent | SizeKeyword::FitContent => max_content,
+    };
+
+    if let Some(kw) = ns.width_keyword {
+        style.size.width = length(resolve(kw));
+        if kw == SizeKeyword::FitContent {
+            if matches!(style.max_size.width, Dimension::Auto) {
+                style.max_size.width = percent(1.0);
+            }
+            if matches!(style.min_size.width, Dimension::Auto) {
+                style.min_size.width = length(min_content);
+            }
+        }
+    }
+    if let Some(kw) = ns.min_width_keyword {
+        style.min_size.width = length(resolve(kw));
+    }
+    if let Some(kw) = ns.max_width_keyword {
+        style.max_size.width = length(resolve(kw));
+    }
+    taffy.set_style(node, style).ok();
+}
+
 /// 构建基础 Taffy 样式
 pub fn build_base_style(
     node: &WxmlNode,
     ctx: &mut ComponentContext,
 ) -> (Style, NodeStyle) {
-    let classes = get_classes(node);
-    let css = ctx.stylesheet.get_styles(&classes, &node.tag_name);
+    let mut classes = get_classes(node);
+    let state = element_state(node, ctx);
+    if state.hover_class {
+        classes.extend(node.get_attr("hover-class").unwrap_or("").split_whitespace());
+    }
+    let (css, important) = ctx.stylesheet.get_styles_with_state(&classes, &node.tag_name, state);
+    build_style_from_rules(&css, &important, node.get_attr("style").unwrap_or(""), ctx)
+}
+
+/// 元素当前的交互状态：指针状态来自渲染器，disabled 来自属性
+pub fn element_state(node: &WxmlNode, ctx: &ComponentContext) -> PseudoState {
+    PseudoState {
+        disabled: node.get_attr("disabled").map(|v| v == "true" || v == "{{true}}").unwrap_or(false),
+        ..ctx.pseudo_state
+    }
+}
+
+/// 由样式表匹配结果和内联样式构建布局样式与绘制样式
+pub fn build_style_from_rules(
+    css: &HashMap<String, StyleValue>,
+    important: &HashSet<String>,
+    inline_style: &str,
+    ctx: &mut ComponentContext,
+) -> (Style, NodeStyle) {
+    // 内联样式：普通声明不覆盖样式表中的 !important，内联 !important 优先级最高
+    let mut inline: Vec<(String, StyleValue)> = vec![];
+    let mut inline_important: Vec<(String, StyleValue)> = vec![];
+    for part in inline_style.split(';') {
+        let Some((name, value)) = part.split_once(':') else { continue };
+        let name = name.trim();
+        if name.is_empty() { continue; }
+        let (value, is_important) = match strip_important(value) {
+            Some(v) => (v, true),
+            None => (value, false),
+        };
+        // 自定义属性保留原文
+        let value = if name.starts_with("--") {
+            StyleValue::String(value.trim().to_string())
+        } else {
+            parse_inline_value(value)
+        };
+        if is_important {
+            inline_important.push((name.to_string(), value));
+        } else if !important.contains(name) {
+            inline.push((name.to_string(), value));
+        }
+    }
+    inline.extend(inline_important);
     
-    let mut ns = NodeStyle { font_size: 14.0, opacity: 1.0, ..Default::default() };
+    let css_vars = collect_css_vars(&ctx.css_vars, css, &inline);
+    let mut ns = NodeStyle { font_size: 14.0, opacity: 1.0, css_vars: css_vars.clone(), ..Default::default() };
     
     // 默认样式：flex 布局，列方向
     let mut ts = Style { 
@@ -313,31 +804,95 @@ pub fn build_base_style(
     };
 
     // 应用类样式
-    for (name, value) in &css {
-        apply_style_property(name, value, &mut ts, &mut ns, ctx);
+    let insets = ctx.safe_area_insets;
+    for (name, value) in css {
+        if let Some(value) = resolve_style_vars(name, value, &css_vars, insets, false) {
+            apply_style_property(name, &value, &mut ts, &mut ns, ctx);
+        }
     }
 
     // 应用内联样式
-    if let Some(style_str) = node.get_attr("style") {
-        for part in style_str.split(';') {
-            let part = part.trim();
-            if part.is_empty() { continue; }
-            
-            if let Some(colon_pos) = part.find(':') {
-                let name = part[..colon_pos].trim();
-                let value_str = part[colon_pos + 1..].trim();
-                let value = parse_inline_value(value_str);
-                apply_style_property(name, &value, &mut ts, &mut ns, ctx);
-            }
+    for (name, value) in &inline {
+        if let Some(value) = resolve_style_vars(name, value, &css_vars, insets, true) {
+            apply_style_property(name, &value, &mut ts, &mut ns, ctx);
         }
     }
     
+    // 无单位、百分比和 em 行高按最终的字号计算，与 font-size 的声明顺序无关
+    let line_height = inline.iter().rev().find(|(name, _)| name == "line-height").map(|(_, v)| v)
+        .or_else(|| css.get("line-height"));
+    if let Some(k) = line_height.and_then(relative_line_height) {
+        ns.line_height = Some(ns.font_size * k);
+    }
+    
+    // display: inline 的盒子尺寸由内容决定
+    if ns.inline_level == Some(InlineLevel::Inline) {
+        ts.size = Size::auto();
+    }
+    // sticky 的 top 等只在吸顶时生效，不参与相对定位偏移
+    if ns.is_sticky {
+        let auto = LengthPercentageAuto::Auto;
+        ts.inset = Rect { top: auto, right: auto, bottom: auto, left: auto };
+    }
+    
     (ts, ns)
 }
 
+/// 相对字号的行高倍数（line-height: 1.5 / 150% / 1.5em）
+fn relative_line_height(value: &StyleValue) -> Option<f32> {
+    match value {
+        StyleValue::Number(n) | StyleValue::Length(n, LengthUnit::Em) => Some(*n),
+        StyleValue::Length(n, LengthUnit::Percent) => Some(*n / 100.0),
+        _ => None,
+    }
+}
+
+/// 合并继承的 CSS 变量和元素自身声明的 --name，没有新声明时共享父元素的变量表
+fn collect_css_vars(
+    inherited: &Arc<HashMap<String, String>>,
+    css: &HashMap<String, StyleValue>,
+    inline: &[(String, StyleValue)],
+) -> Arc<HashMap<String, String>> {
+    let declared: Vec<(&String, &String)> = css.iter()
+        .chain(inline.iter().map(|(n, v)| (n, v)))
+        .filter_map(|(name, value)| match value {
+            StyleValue::String(v) if name.starts_with("--") => Some((name, v)),
+            _ => None,
+        })
+        .collect();
+    if declared.is_empty() {
+        return inherited.clone();
+    }
+    
+    let mut vars = (**inherited).clone();
+    for (name, value) in declared {
+        // 变量值中的 var() 按继承的变量解析
+        match resolve_var_refs(value, inherited) {
+            Some(v) => { vars.insert(name.clone(), v); }
+            None => { vars.remove(name); }
+        }
+    }
+    Arc::new(vars)
+}
+
+/// 替换样式值中的 var() 和 env()，无法解析（变量未定义且没有 fallback）时丢弃该属性
+fn resolve_style_vars(name: &str, value: &StyleValue, vars: &HashMap<String, String>, insets: [f32; 4], inline: bool) -> Option<StyleValue> {
+    match value {
+        _ if name.starts_with("--") => None,
+        StyleValue::String(s) if s.contains("var(") || s.contains("env(") => {
+            let resolved = resolve_env_refs(&resolve_var_refs(s, vars)?, insets)?;
+            Some(if inline { parse_inline_value(&resolved) } else { WxssParser::parse_value(name, &resolved) })
+        }
+        _ => Some(value.clone()),
+    }
+}
+
 /// 解析内联样式值
 fn parse_inline_value(value: &str) -> StyleValue {
     let value = value.trim();
+    if let Some(expr) = parse_calc(value) {
+        return StyleValue::Calc(expr);
+    }
     if value.ends_with("rpx") {
         if let Ok(n) = value.trim_end_matches("rpx").parse() { return StyleValue::Length(n, LengthUnit::Rpx); }
     }
@@ -367,11 +922,20 @@ fn apply_style_property(
 ) {
     let sf = ctx.scale_factor;
     match name {
-            "width" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) { ts.size.width = v; }
+            "width" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) {
+                ts.size.width = v;
+                ns.width_keyword = SizeKeyword::parse(value);
+            }
             "height" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) { ts.size.height = v; }
-            "min-width" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) { ts.min_size.width = v; }
+            "min-width" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) {
+                ts.min_size.width = v;
+                ns.min_width_keyword = SizeKeyword::parse(value);
+            }
             "min-height" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) { ts.min_size.height = v; }
-            "max-width" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) { ts.max_size.width = v; }
+            "max-width" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) {
+                ts.max_size.width = v;
+                ns.max_width_keyword = SizeKeyword::parse(value);
+            }
             "max-height" => if let Some(v) = to_dimension(value, ctx.screen_width, ctx.screen_height, sf) { ts.max_size.height = v; }
             "padding" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) {
                 let sv = v * sf;
@@ -389,6 +953,13 @@ fn apply_style_property(
             "margin-right" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ts.margin.right = length(v * sf); }
             "margin-bottom" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ts.margin.bottom = length(v * sf); }
             "margin-left" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ts.margin.left = length(v * sf); }
+            "visibility" => if let StyleValue::String(s) = value {
+                match s.as_str() {
+                    "hidden" | "collapse" => ns.visible = Some(false),
+                    "visible" => ns.visible = Some(true),
+                    _ => {}
+                }
+            }
             "display" => if let StyleValue::String(s) = value {
                 match s.as_str() {
                     "none" => ts.display = Display::None,
@@ -396,7 +967,18 @@ fn apply_style_property(
                         ts.display = Display::Flex;
                         ns.is_block = true;
                     }
-                    "flex" => ts.display = Display::Flex,
+                    "flex" => {
+                        ts.display = Display::Flex;
+                        ns.is_flex = true;
+                    }
+                    "inline-block" => {
+                        ts.display = Display::Flex;
+                        ns.inline_level = Some(InlineLevel::InlineBlock);
+                    }
+                    "inline" => {
+                        ts.display = Display::Flex;
+                        ns.inline_level = Some(InlineLevel::Inline);
+                    }
                     "grid" => ts.display = Display::Grid,
                     _ => ts.display = Display::Flex,
                 };
@@ -476,7 +1058,39 @@ fn apply_style_property(
             }
             "row-gap" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ts.gap.height = length(v * sf); }
             "column-gap" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ts.gap.width = length(v * sf); }
-            "background-color" | "background" => if let StyleValue::Color(c) = value { ns.background_color = Some(*c); }
+            "background-color" => if let StyleValue::Color(c) = value { ns.background_color = Some(*c); }
+            "background" => match value {
+                StyleValue::Color(c) => ns.background_color = Some(*c),
+                StyleValue::String(s) => apply_background_shorthand(s, ns, ctx),
+                _ => {}
+            }
+            "background-image" => if let StyleValue::String(s) = value {
+                if let Some(gradient) = CssGradient::parse(s) {
+                    ns.background_gradient = Some(gradient);
+                } else if let Some(src) = parse_css_url(s) {
+                    ns.background_image.get_or_insert_with(|| BackgroundImage::new(sf)).src = src;
+                }
+            }
+            "background-clip" | "-webkit-background-clip" => if let StyleValue::String(s) = value {
+                ns.background_clip_text = s == "text";
+            }
+            "background-size" => {
+                let text = style_value_text(value);
+                if let Some(size) = parse_background_size(&text, ctx) {
+                    ns.background_image.get_or_insert_with(|| BackgroundImage::new(sf)).size = size;
+                }
+            }
+            "background-position" => {
+                let text = style_value_text(value);
+                if let Some(position) = parse_background_position(&text, ctx) {
+                    ns.background_image.get_or_insert_with(|| BackgroundImage::new(sf)).position = position;
+                }
+            }
+            "background-repeat" => if let StyleValue::String(s) = value {
+                if let Some(repeat) = parse_background_repeat(s) {
+                    ns.background_image.get_or_insert_with(|| BackgroundImage::new(sf)).repeat = repeat;
+                }
+            }
             "color" => if let StyleValue::Color(c) = value { ns.text_color = Some(*c); }
             "border-color" => if let StyleValue::Color(c) = value { ns.border_color = Some(*c); }
             "border-width" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ns.border_width = v * sf; }
@@ -485,6 +1099,21 @@ fn apply_style_property(
             "border-top-right-radius" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ns.border_radius_tr = Some(v * sf); }
             "border-bottom-right-radius" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ns.border_radius_br = Some(v * sf); }
             "border-bottom-left-radius" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ns.border_radius_bl = Some(v * sf); }
+            "border-top" | "border-right" | "border-bottom" | "border-left" => {
+                let side = match name {
+                    "border-top" => BORDER_TOP,
+                    "border-right" => BORDER_RIGHT,
+                    "border-bottom" => BORDER_BOTTOM,
+                    _ => BORDER_LEFT,
+                };
+                ns.border_sides[side] = match value {
+                    StyleValue::String(s) => parse_border_side(s, ctx.screen_width, sf),
+                    _ => None,
+                };
+            }
+            "border-collapse" => if let StyleValue::String(s) = value {
+                ns.border_collapse = s == "collapse";
+            }
             "border" => {
                 // border: 1px solid #000
                 if let StyleValue::String(s) = value {
@@ -492,7 +1121,21 @@ fn apply_style_property(
                 }
             }
             "font-size" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ns.font_size = v; }
-            "font-weight" => if let StyleValue::String(s) = value {
+            "font-family" => if let StyleValue::String(s) = value {
+                let families: Vec<String> = s.split(',')
+                    .map(|f| f.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+                    .filter(|f| !f.is_empty())
+                    .collect();
+                ns.font_family = (!families.is_empty()).then(|| families.into());
+            }
+            "font-weight" => if let Some(s) = match value {
+                StyleValue::String(s) => Some(s.clone()),
+                // 数字字重（如 700）解析为无单位长度，按最接近的整百取值
+                StyleValue::Length(n, LengthUnit::Px) | StyleValue::Number(n) => {
+                    Some(((n / 100.0).round().clamp(1.0, 9.0) as u16 * 100).to_string())
+                }
+                _ => None,
+            } {
                 ns.font_weight = match s.as_str() {
                     "100" => FontWeight::W100,
                     "200" => FontWeight::W200,
@@ -523,10 +1166,10 @@ fn apply_style_property(
                 };
             }
             "line-height" => {
-                if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { 
+                if let Some(k) = relative_line_height(value) {
+                    ns.line_height = Some(ns.font_size * k);
+                } else if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { 
                     ns.line_height = Some(v); 
-                } else if let StyleValue::Number(n) = value {
-                    ns.line_height = Some(ns.font_size * n);
                 }
             }
             "letter-spacing" => if let Some(v) = to_px(value, ctx.screen_width, ctx.screen_height) { ns.letter_spacing = v; }
@@ -575,13 +1218,22 @@ fn apply_style_property(
                     _ => WordBreak::Normal,
                 };
             }
-            "z-index" => if let StyleValue::Number(n) = value { ns.z_index = *n as i32; }
-            "opacity" => if let StyleValue::Number(n) = value { ns.opacity = *n; }
+            // WXSS 解析器把无单位数字当作 px 长度
+            "z-index" => if let StyleValue::Number(n) | StyleValue::Length(n, LengthUnit::Px) = value { ns.z_index = *n as i32; }
+            "opacity" => if let StyleValue::Number(n) | StyleValue::Length(n, LengthUnit::Px) = value {
+                ns.opacity = n.clamp(0.0, 1.0);
+            }
             "box-shadow" => if let StyleValue::String(s) = value {
                 if let Some(shadow) = parse_box_shadow(s, ctx.screen_width) {
                     ns.box_shadow = Some(shadow);
                 }
             }
+            "transition" => if let StyleValue::String(s) = value {
+                ns.transitions = TransitionSpec::parse_list(s);
+            }
+            "animation" => if let StyleValue::String(s) = value {
+                ns.animation = AnimationSpec::parse(s).and_then(|spec| resolve_keyframes(spec, ctx));
+            }
             "transform" => if let StyleValue::String(s) = value {
                 if let Some(transform) = parse_transform(s) {
                     ns.transform = Some(transform);
@@ -595,6 +1247,11 @@ fn apply_style_property(
                         ts.position = Position::Absolute;
                         ns.is_fixed = true;
                     }
+                    "sticky" => {
+                        // sticky 在布局中按普通流处理，吸顶偏移在绘制时根据滚动位置计算
+                        ts.position = Position::Relative;
+                        ns.is_sticky = true;
+                    }
                     _ => ts.position = Position::Relative,
                 };
             }
@@ -626,6 +1283,151 @@ fn apply_style_property(
     }
 }
 
+/// 查找 animation 引用的 @keyframes，把各帧的样式解析成可插值的属性
+fn resolve_keyframes(spec: AnimationSpec, ctx: &mut ComponentContext) -> Option<KeyframeAnimation> {
+    let stylesheet = ctx.stylesheet;
+    let keyframes = stylesheet.keyframes.get(&spec.name)?;
+    let frames = keyframes.iter().map(|kf| {
+        let mut ts = Style::default();
+        let mut ns = NodeStyle { opacity: 1.0, ..Default::default() };
+        for (name, value) in &kf.properties {
+            apply_style_property(name, value, &mut ts, &mut ns, ctx);
+        }
+        let has = |name: &str| kf.properties.contains_key(name);
+        let props = KeyframeProps {
+            transform: ns.transform.filter(|_| has("transform")).map(|t| t.to_animated_props(1.0)),
+            opacity: Some(ns.opacity).filter(|_| has("opacity")),
+            background_color: ns.background_color.filter(|_| has("background-color") || has("background")),
+        };
+        (kf.offset, props)
+    }).collect();
+    Some(KeyframeAnimation::new(spec, frames))
+}
+
+/// 把样式值还原成文本（background-size 等多值属性可能被解析成单个长度）
+fn style_value_text(value: &StyleValue) -> String {
+    match value {
+        StyleValue::String(s) => s.clone(),
+        StyleValue::Length(n, unit) => {
+            let suffix = match unit {
+                LengthUnit::Px => "px",
+                LengthUnit::Rpx => "rpx",
+                LengthUnit::Percent => "%",
+                LengthUnit::Em => "em",
+                LengthUnit::Rem => "rem",
+                LengthUnit::Vw => "vw",
+                LengthUnit::Vh => "vh",
+            };
+            format!("{}{}", n, suffix)
+        }
+        StyleValue::Number(n) => n.to_string(),
+        StyleValue::Auto => "auto".into(),
+        _ => String::new(),
+    }
+}
+
+/// 解析 url(...)，去掉引号
+fn parse_css_url(s: &str) -> Option<String> {
+    let start = s.find("url(")? + 4;
+    let end = start + s[start..].find(')')?;
+    let url = s[start..end].trim().trim_matches(|c| c == '"' || c == '\'');
+    if url.is_empty() { None } else { Some(url.to_string()) }
+}
+
+fn parse_background_length(token: &str, ctx: &ComponentContext) -> Option<BackgroundLength> {
+    match parse_inline_value(token) {
+        StyleValue::Auto => Some(BackgroundLength::Auto),
+        StyleValue::Length(n, LengthUnit::Percent) => Some(BackgroundLength::Percent(n)),
+        v => to_px(&v, ctx.screen_width, ctx.screen_height).map(|px| BackgroundLength::Px(px * ctx.scale_factor)),
+    }
+}
+
+/// 解析 background-size：cover / contain / 一个或两个长度
+fn parse_background_size(s: &str, ctx: &ComponentContext) -> Option<BackgroundSize> {
+    let tokens: Vec<&str> = s.split_whitespace().collect();
+    match tokens.as_slice() {
+        ["cover"] => Some(BackgroundSize::Cover),
+        ["contain"] => Some(BackgroundSize::Contain),
+        ["auto"] | ["auto", "auto"] => Some(BackgroundSize::Auto),
+        [w] => Some(BackgroundSize::Size(parse_background_length(w, ctx)?, BackgroundLength::Auto)),
+        [w, h] => Some(BackgroundSize::Size(parse_background_length(w, ctx)?, parse_background_length(h, ctx)?)),
+        _ => None,
+    }
+}
+
+/// 解析 background-position：方位关键字、百分比或长度，只给一个值时另一方向居中
+fn parse_background_position(s: &str, ctx: &ComponentContext) -> Option<(BackgroundLength, BackgroundLength)> {
+    let center = BackgroundLength::Percent(50.0);
+    let mut x = None;
+    let mut y = None;
+    let mut lengths = vec![];
+    for token in s.split_whitespace() {
+        match token {
+            "left" => x = Some(BackgroundLength::Percent(0.0)),
+            "right" => x = Some(BackgroundLength::Percent(100.0)),
+            "top" => y = Some(BackgroundLength::Percent(0.0)),
+            "bottom" => y = Some(BackgroundLength::Percent(100.0)),
+            "center" => lengths.push(center),
+            _ => lengths.push(parse_background_length(token, ctx)?),
+        }
+    }
+    // 长度值依次填入还没有被关键字确定的方向
+    for v in lengths {
+        if x.is_none() { x = Some(v); } else if y.is_none() { y = Some(v); }
+    }
+    if x.is_none() && y.is_none() { return None; }
+    Some((x.unwrap_or(center), y.unwrap_or(center)))
+}
+
+fn parse_background_repeat(s: &str) -> Option<(bool, bool)> {
+    match s.trim() {
+        "repeat" => Some((true, true)),
+        "no-repeat" => Some((false, false)),
+        "repeat-x" => Some((true, false)),
+        "repeat-y" => Some((false, true)),
+        _ => None,
+    }
+}
+
+/// 解析 background 简写：颜色、url(...)、重复方式、位置 [/ 尺寸]
+fn apply_background_shorthand(s: &str, ns: &mut NodeStyle, ctx: &ComponentContext) {
+    // 渐变：记录完整定义，普通背景用第一个颜色作为 fallback
+    if let Some(gradient) = CssGradient::parse(s) {
+        ns.background_color = Some(gradient.stops[0].1);
+        ns.background_gradient = Some(gradient);
+        return;
+    }
+    let mut rest = s.to_string();
+    if let Some(src) = parse_css_url(s) {
+        let start = s.find("url(").unwrap_or(0);
+        let end = start + s[start..].find(')').map(|i| i + 1).unwrap_or(s.len() - start);
+        rest = format!("{} {}", &s[..start], &s[end..]);
+        ns.background_image.get_or_insert_with(|| BackgroundImage::new(ctx.scale_factor)).src = src;
+    }
+    
+    let (position_part, size_part) = match rest.split_once('/') {
+        Some((p, sz)) => (p.to_string(), Some(sz.trim().to_string())),
+        None => (rest.clone(), None),
+    };
+    let mut position_tokens = vec![];
+    for token in position_part.split_whitespace() {
+        if let Some(repeat) = parse_background_repeat(token) {
+            ns.background_image.get_or_insert_with(|| BackgroundImage::new(ctx.scale_factor)).repeat = repeat;
+        } else if let StyleValue::Color(c) = parse_inline_value(token) {
+            ns.background_color = Some(c);
+        } else {
+            position_tokens.push(token);
+        }
+    }
+    let Some(bg) = ns.background_image.as_mut() else { return };
+    if let Some(position) = parse_background_position(&position_tokens.join(" "), ctx) {
+        bg.position = position;
+    }
+    if let Some(size) = size_part.and_then(|sz| parse_background_size(&sz, ctx)) {
+        bg.size = size;
+    }
+}
+
 /// 解析 box-shadow
 fn parse_box_shadow(s: &str, screen_width: f32) -> Option<BoxShadow> {
     let s = s.trim();
@@ -646,7 +1448,7 @@ fn parse_box_shadow(s: &str, screen_width: f32) -> Option<BoxShadow> {
             }
         } else if let Some((num, unit)) = parse_length_simple(part) {
             let px = match unit {
-                "rpx" => num * screen_width / 750.0,
+                "rpx" => rpx_to_px(num, screen_width),
                 _ => num,
             };
             match num_idx {
@@ -733,12 +1535,120 @@ fn parse_border_shorthand(s: &str, ns: &mut NodeStyle, screen_width: f32, sf: f3
         if part.starts_with('#') || part.starts_with("rgb") {
             if let Some(color) = parse_color_str(part) { ns.border_color = Some(color); }
         } else if let Some((num, unit)) = parse_length_simple(part) {
-            let px = match unit { "rpx" => num * screen_width / 750.0, _ => num };
+            let px = match unit { "rpx" => rpx_to_px(num, screen_width), _ => num };
             ns.border_width = px * sf;
         }
     }
 }
 
+/// 解析单边边框简写，如 `1rpx solid #eee`；宽度为 0 或样式为 none 时返回 None
+fn parse_border_side(s: &str, screen_width: f32, sf: f32) -> Option<BorderSide> {
+    let mut side = BorderSide { width: sf, color: Color::BLACK };
+    for part in s.split_whitespace() {
+        if part == "none" || part == "hidden" {
+            return None;
+        } else if let Some(color) = parse_color_str(part) {
+            side.color = color;
+        } else if let Some((num, unit)) = parse_length_simple(part) {
+            let px = match unit { "rpx" => rpx_to_px(num, screen_width), _ => num };
+            side.width = px * sf;
+        }
+    }
+    if side.width > 0.0 { Some(side) } else { None }
+}
+
+/// 把块级流容器中连续的行内元素（display: inline / inline-block）放进匿名行盒
+///
+/// 默认的 view 是列方向 flex 容器，行内元素会被拉伸成整行。这里把相邻的行内
+/// 元素收进一个横向换行的匿名 view，宽度贴合内容并排成一行，块级兄弟元素仍然
+/// 各占一行。显式声明 display: flex 的容器中行内元素按普通 flex 项目处理。
+pub fn wrap_inline_runs(
+    taffy: &mut TaffyTree,
+    ts: &Style,
+    ns: &NodeStyle,
+    children: Vec<RenderNode>,
+) -> Vec<RenderNode> {
+    let block_flow = ts.display == Display::Flex
+        && ts.flex_direction == FlexDirection::Column
+        && !ns.is_flex;
+    let is_inline = |c: &RenderNode| c.style.inline_level.is_some() && !c.style.is_fixed;
+    if !block_flow || !children.iter().any(is_inline) {
+        return children;
+    }
+    
+    let justify = match ns.text_align {
+        TextAlign::Center => Some(JustifyContent::Center),
+        TextAlign::Right => Some(JustifyContent::FlexEnd),
+        TextAlign::Left | TextAlign::Justify => None,
+    };
+    
+    let mut result = Vec::with_capacity(children.len());
+    let mut run: Vec<RenderNode> = vec![];
+    let flush = |taffy: &mut TaffyTree, run: &mut Vec<RenderNode>, result: &mut Vec<RenderNode>| {
+        if run.is_empty() { return; }
+        let ids: Vec<NodeId> = run.iter().map(|c| c.taffy_node).collect();
+        let line = taffy.new_with_children(Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            align_items: Some(AlignItems::FlexEnd),
+            justify_content: justify,
+            ..Default::default()
+        }, &ids).unwrap();
+        result.push(RenderNode {
+            tag: "view".into(),
+            text: String::new(),
+            attrs: HashMap::new(),
+            taffy_node: line,
+            style: NodeStyle { font_size: ns.font_size, opacity: 1.0, ..Default::default() },
+            children: std::mem::take(run),
+            events: vec![],
+        });
+    };
+    
+    for child in children {
+        if is_inline(&child) {
+            run.push(child);
+        } else {
+            flush(taffy, &mut run, &mut result);
+            result.push(child);
+        }
+    }
+    flush(taffy, &mut run, &mut result);
+    result
+}
+
+/// 合并容器内相邻子元素的边框（border-collapse: collapse）
+///
+/// 完整的 border 先拆成四条单边边框；前一项有下边框时去掉后一项的上边框，
+/// 只有下边框的最后一项（列表分隔线）去掉下边框。
+pub fn collapse_child_borders(children: &mut [RenderNode]) {
+    let items: Vec<usize> = (0..children.len()).filter(|&i| !children[i].style.is_fixed).collect();
+    for &i in &items {
+        let style = &mut children[i].style;
+        if style.border_width > 0.0 {
+            if let Some(color) = style.border_color {
+                let side = BorderSide { width: style.border_width, color };
+                for s in style.border_sides.iter_mut() {
+                    s.get_or_insert(side);
+                }
+                style.border_width = 0.0;
+            }
+        }
+    }
+    for pair in items.windows(2) {
+        if children[pair[0]].style.border_sides[BORDER_BOTTOM].is_some() {
+            children[pair[1]].style.border_sides[BORDER_TOP] = None;
+        }
+    }
+    if let Some(&last) = items.last() {
+        let sides = &mut children[last].style.border_sides;
+        if sides[BORDER_TOP].is_none() {
+            sides[BORDER_BOTTOM] = None;
+        }
+    }
+}
+
 /// 简单长度解析
 fn parse_length_simple(s: &str) -> Option<(f32, &str)> {
     let s = s.trim();
@@ -752,10 +1662,10 @@ fn parse_length_simple(s: &str) -> Option<(f32, &str)> {
     s.parse::<f32>().ok().map(|n| (n, "px"))
 }
 
-/// 绘制盒子阴影
+/// 绘制盒子阴影（内阴影由 draw_inset_box_shadow 在背景之后绘制）
 pub fn draw_box_shadow(canvas: &mut Canvas, shadow: &BoxShadow, x: f32, y: f32, w: f32, h: f32, border_radius: f32) {
     if shadow.inset {
-        return; // 暂不支持内阴影
+        return;
     }
     
     let shadow_x = x + shadow.offset_x;
@@ -764,31 +1674,126 @@ pub fn draw_box_shadow(canvas: &mut Canvas, shadow: &BoxShadow, x: f32, y: f32,
     let shadow_h = h + shadow.spread * 2.0;
     let adjusted_x = shadow_x - shadow.spread;
     let adjusted_y = shadow_y - shadow.spread;
+    if shadow_w <= 0.0 || shadow_h <= 0.0 {
+        return;
+    }
     
-    // 简化的阴影绘制：使用多层半透明矩形模拟模糊
-    let blur_steps = (shadow.blur / 2.0).max(1.0) as i32;
-    let base_alpha = shadow.color.a as f32 / blur_steps as f32;
-    
-    for i in 0..blur_steps {
-        let expand = i as f32 * 2.0;
-        let alpha = (base_alpha * (1.0 - i as f32 / blur_steps as f32)) as u8;
-        if alpha == 0 { continue; }
-        
-        let color = Color::new(shadow.color.r, shadow.color.g, shadow.color.b, alpha);
-        let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill);
-        
-        let sx = adjusted_x - expand;
-        let sy = adjusted_y - expand;
-        let sw = shadow_w + expand * 2.0;
-        let sh = shadow_h + expand * 2.0;
-        
+    let paint = Paint::new().with_color(shadow.color).with_style(PaintStyle::Fill);
+    let fill_shape = |canvas: &mut Canvas, sx: f32, sy: f32| {
         if border_radius > 0.0 {
             let mut path = Path::new();
-            path.add_round_rect(sx, sy, sw, sh, border_radius + expand);
+            path.add_round_rect(sx, sy, shadow_w, shadow_h, border_radius + shadow.spread.max(0.0));
             canvas.draw_path(&path, &paint);
         } else {
-            canvas.draw_rect(&GeoRect::new(sx, sy, sw, sh), &paint);
+            canvas.draw_rect(&GeoRect::new(sx, sy, shadow_w, shadow_h), &paint);
         }
+    };
+    
+    if shadow.blur < 1.0 {
+        fill_shape(canvas, adjusted_x, adjusted_y);
+        return;
+    }
+    
+    // 在离屏遮罩上绘制阴影形状，模糊后再合成，模糊会向外扩散约 blur 的距离
+    let pad = shadow.blur.ceil() + 1.0;
+    let mask_x = (adjusted_x - pad).floor();
+    let mask_y = (adjusted_y - pad).floor();
+    let mask_w = (shadow_w + pad * 2.0).ceil() as u32 + 1;
+    let mask_h = (shadow_h + pad * 2.0).ceil() as u32 + 1;
+    let mut mask = Canvas::new(mask_w, mask_h);
+    fill_shape(&mut mask, adjusted_x - mask_x, adjusted_y - mask_y);
+    mask.blur_region(&GeoRect::new(0.0, 0.0, mask_w as f32, mask_h as f32), shadow.blur);
+    canvas.draw_bitmap(&mask, mask_x, mask_y);
+}
+
+/// 绘制内阴影：元素外侧整片着色，挖掉按 offset 平移、按 spread 收缩的内框，
+/// 模糊后裁剪到元素的圆角矩形内，形成从边缘向中心变淡的阴影
+pub fn draw_inset_box_shadow(canvas: &mut Canvas, shadow: &BoxShadow, x: f32, y: f32, w: f32, h: f32, radii: [f32; 4]) {
+    if !shadow.inset || w <= 0.0 || h <= 0.0 {
+        return;
+    }
+    
+    let pad = shadow.blur.ceil() + 1.0;
+    let mask_x = (x - pad).floor();
+    let mask_y = (y - pad).floor();
+    let mask_w = (w + pad * 2.0).ceil() as u32 + 1;
+    let mask_h = (h + pad * 2.0).ceil() as u32 + 1;
+    
+    // 外框和内框两条轮廓，按奇偶规则填充出环形
+    let mut path = Path::new();
+    path.set_fill_rule(FillRule::EvenOdd);
+    path.add_rect(0.0, 0.0, mask_w as f32, mask_h as f32);
+    let hole_w = w - shadow.spread * 2.0;
+    let hole_h = h - shadow.spread * 2.0;
+    if hole_w > 0.0 && hole_h > 0.0 {
+        let hole_x = x + shadow.offset_x + shadow.spread - mask_x;
+        let hole_y = y + shadow.offset_y + shadow.spread - mask_y;
+        add_round_rect_with_radii(&mut path, hole_x, hole_y, hole_w, hole_h, radii.map(|r| (r - shadow.spread).max(0.0)));
+    }
+    
+    let mut mask = Canvas::new(mask_w, mask_h);
+    mask.draw_path(&path, &Paint::new().with_color(shadow.color).with_style(PaintStyle::Fill));
+    mask.blur_region(&GeoRect::new(0.0, 0.0, mask_w as f32, mask_h as f32), shadow.blur);
+    
+    let mut clipped = Canvas::new(mask_w, mask_h);
+    clipped.push_clip(ClipShape::RoundRect(GeoRect::new(x - mask_x, y - mask_y, w, h), radii));
+    clipped.draw_bitmap(&mask, 0.0, 0.0);
+    canvas.draw_bitmap(&clipped, mask_x, mask_y);
+}
+
+/// 绘制 background-image，裁剪到元素的圆角矩形内，网络图片下载完成前不绘制
+pub fn draw_background_image(canvas: &mut Canvas, bg: &BackgroundImage, x: f32, y: f32, w: f32, h: f32, radii: [f32; 4]) {
+    if bg.src.is_empty() || w <= 0.0 || h <= 0.0 { return; }
+    let Some(img) = super::image::load_image(&bg.src) else { return };
+    if img.width == 0 || img.height == 0 { return; }
+    
+    let (iw, ih) = (img.width as f32 * bg.pixel_ratio, img.height as f32 * bg.pixel_ratio);
+    let (tw, th) = background_tile_size(&bg.size, iw, ih, w, h);
+    if tw < 0.5 || th < 0.5 { return; }
+    
+    // 百分比位置：图片上的对齐点与背景区域上的对齐点重合
+    let offset = |pos: &BackgroundLength, container: f32, tile: f32| match pos {
+        BackgroundLength::Percent(p) => (container - tile) * p / 100.0,
+        other => other.resolve(container).unwrap_or(0.0),
+    };
+    let ox = x + offset(&bg.position.0, w, tw);
+    let oy = y + offset(&bg.position.1, h, th);
+    
+    // 平铺方向从对齐的图块向两侧铺满背景区域
+    let tiles = |origin: f32, start: f32, len: f32, tile: f32, repeat: bool| -> Vec<f32> {
+        if !repeat { return vec![origin]; }
+        let first = origin - ((origin - start) / tile).ceil() * tile;
+        (0..((start + len - first) / tile).ceil() as usize).map(|i| first + i as f32 * tile).collect()
+    };
+    
+    let frame = img.current_frame();
+    canvas.push_clip(ClipShape::RoundRect(GeoRect::new(x, y, w, h), radii));
+    for ty in tiles(oy, y, h, th, bg.repeat.1) {
+        for tx in tiles(ox, x, w, tw, bg.repeat.0) {
+            canvas.draw_image(frame, img.width, img.height, tx, ty, tw, th, "scaleToFill", 0.0, bg.pixel_ratio);
+        }
+    }
+    canvas.pop_clip();
+}
+
+/// 按 background-size 计算单个图块的尺寸，(iw, ih) 为图片在画布上的原始尺寸
+pub fn background_tile_size(size: &BackgroundSize, iw: f32, ih: f32, w: f32, h: f32) -> (f32, f32) {
+    match size {
+        BackgroundSize::Auto => (iw, ih),
+        BackgroundSize::Cover => {
+            let s = (w / iw).max(h / ih);
+            (iw * s, ih * s)
+        }
+        BackgroundSize::Contain => {
+            let s = (w / iw).min(h / ih);
+            (iw * s, ih * s)
+        }
+        BackgroundSize::Size(bw, bh) => match (bw.resolve(w), bh.resolve(h)) {
+            (Some(tw), Some(th)) => (tw, th),
+            (Some(tw), None) => (tw, tw * ih / iw),
+            (None, Some(th)) => (th * iw / ih, th),
+            (None, None) => (iw, ih),
+        },
     }
 }
 
@@ -803,6 +1808,31 @@ pub fn get_border_radii(style: &NodeStyle) -> [f32; 4] {
 }
 
 /// 绘制背景和边框
+/// 用渐变填充 draw 画出的形状：先在透明图层上用白色画出覆盖率遮罩，再按覆盖率逐像素混合渐变色。
+/// (x, y, w, h) 是渐变铺满的盒子
+pub fn fill_with_gradient(
+    canvas: &mut Canvas,
+    gradient: &CssGradient,
+    (x, y, w, h): (f32, f32, f32, f32),
+    draw: impl FnOnce(&mut Canvas, &Paint),
+) {
+    // 渐变只铺满盒子，遮罩只需覆盖盒子范围
+    let mut mask = canvas.new_layer_in(&GeoRect::new(x, y, w, h));
+    draw(&mut mask, &Paint::new().with_color(Color::WHITE).with_style(PaintStyle::Fill));
+    let (tx, ty) = canvas.translation();
+    let (ox, oy) = mask.origin();
+    let width = mask.width() as i32;
+    for (i, coverage) in mask.pixels().iter().enumerate() {
+        if coverage.a == 0 {
+            continue;
+        }
+        let (px, py) = (ox + i as i32 % width, oy + i as i32 / width);
+        let c = gradient.sample(px as f32 + 0.5 - tx, py as f32 + 0.5 - ty, x, y, w, h);
+        let a = c.a as f32 * coverage.a as f32 / 255.0;
+        canvas.set_pixel(px, py, Color::new(c.r, c.g, c.b, a.round() as u8));
+    }
+}
+
 pub fn draw_background(canvas: &mut Canvas, style: &NodeStyle, x: f32, y: f32, w: f32, h: f32) {
     // 绘制阴影（在背景之前）
     if let Some(shadow) = &style.box_shadow {
@@ -812,8 +1842,8 @@ pub fn draw_background(canvas: &mut Canvas, style: &NodeStyle, x: f32, y: f32, w
     let radii = get_border_radii(style);
     let has_different_radii = radii[0] != radii[1] || radii[1] != radii[2] || radii[2] != radii[3];
     
-    // 绘制背景
-    if let Some(bg) = style.background_color {
+    // 绘制背景（background-clip: text 时背景只透过文字显示，由文本组件绘制）
+    if let Some(bg) = style.background_color.filter(|_| !style.background_clip_text) {
         let mut paint = Paint::new().with_color(bg).with_style(PaintStyle::Fill);
         if style.opacity < 1.0 { 
             paint.color.a = (paint.color.a as f32 * style.opacity) as u8; 
@@ -832,6 +1862,16 @@ pub fn draw_background(canvas: &mut Canvas, style: &NodeStyle, x: f32, y: f32, w
         }
     }
     
+    // 背景图片画在背景色之上
+    if let Some(bg) = style.background_image.as_ref().filter(|_| !style.background_clip_text) {
+        draw_background_image(canvas, bg, x, y, w, h, radii);
+    }
+    
+    // 内阴影画在背景之上、边框之下
+    if let Some(shadow) = &style.box_shadow {
+        draw_inset_box_shadow(canvas, shadow, x, y, w, h, radii);
+    }
+    
     // 绘制边框
     if style.border_width > 0.0 {
         if let Some(bc) = style.border_color {
@@ -849,6 +1889,23 @@ pub fn draw_background(canvas: &mut Canvas, style: &NodeStyle, x: f32, y: f32, w
             }
         }
     }
+    
+    // 绘制单边边框，细线至少占一个物理像素
+    for (i, side) in style.border_sides.iter().enumerate() {
+        let Some(side) = side else { continue };
+        let bw = side.width.max(1.0);
+        let rect = match i {
+            BORDER_TOP => GeoRect::new(x, y, w, bw),
+            BORDER_RIGHT => GeoRect::new(x + w - bw, y, bw, h),
+            BORDER_BOTTOM => GeoRect::new(x, y + h - bw, w, bw),
+            _ => GeoRect::new(x, y, bw, h),
+        };
+        let mut paint = Paint::new().with_color(side.color).with_style(PaintStyle::Fill);
+        if style.opacity < 1.0 {
+            paint.color.a = (paint.color.a as f32 * style.opacity) as u8;
+        }
+        canvas.draw_rect(&rect, &paint);
+    }
 }
 
 /// 添加带有不同圆角的圆角矩形路径
@@ -4,12 +4,15 @@
 //! - type: default(灰色) / primary(绿色) / warn(红色)
 //! - size: default / mini
 //! - plain: 镂空按钮
-//! - disabled: 禁用状态
+//! - disabled: 禁用状态（按类型淡化背景、文字和边框）
+//! - loading: 在文字左侧显示加载图标
+//! - open-type: 开放能力，点击后触发 bindgetuserinfo 等事件（见 open_type_event）
 
 use super::base::*;
 use crate::parser::wxml::WxmlNode;
 use crate::text::TextRenderer;
-use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect as GeoRect};
+use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect as GeoRect, StrokeCap};
+use serde_json::{json, Value as JsonValue};
 use taffy::prelude::*;
 
 pub struct ButtonComponent;
@@ -73,10 +76,21 @@ impl ButtonComponent {
             }
         }
         
-        // 禁用状态覆盖颜色
+        // 禁用状态：微信默认样式按类型取禁用配色，自定义颜色向白色淡化
         if disabled {
-            ns.background_color = Some(Color::from_hex(0xF7F7F7));
-            ns.text_color = Some(Color::from_hex(0xB2B2B2));
+            let (bg, fg, border) = match (btn_type, plain) {
+                (_, true) => (Color::WHITE, Color::new(0, 0, 0, 51), Some(Color::new(0, 0, 0, 51))),
+                ("primary", false) => (Color::from_hex(0x9ED99D), Color::new(255, 255, 255, 153), None),
+                ("warn", false) => (Color::from_hex(0xEC8B89), Color::new(255, 255, 255, 153), None),
+                _ => (Color::from_hex(0xF7F7F7), Color::new(0, 0, 0, 77), Some(Color::from_hex(0xD9D9D9))),
+            };
+            ns.background_color = if has_custom_bg { ns.background_color.map(Self::fade_color) } else { Some(bg) };
+            ns.text_color = if has_custom_color { ns.text_color.map(Self::fade_color) } else { Some(fg) };
+            if has_custom_border {
+                ns.border_color = ns.border_color.map(Self::fade_color);
+            } else if ns.border_color.is_some() {
+                ns.border_color = border;
+            }
         }
         
         // 只有在 CSS 没有定义尺寸时才使用默认尺寸
@@ -103,12 +117,12 @@ impl ButtonComponent {
             
             if !has_custom_size {
                 // 估算文本宽度
-                let char_width = ns.font_size * 0.6 * sf;
+                let char_width = (ns.font_size * 0.6 + ns.letter_spacing) * sf;
                 let tw = text.chars().count() as f32 * char_width;
                 
                 ts.size = Size { 
                     width: if btn_size == "mini" { length(tw + padding_h * 2.0 * sf) } else { percent(1.0) },
-                    height: length((ns.font_size + padding_v * 2.0) * sf) 
+                    height: length((ns.line_height.unwrap_or(ns.font_size) + padding_v * 2.0) * sf) 
                 };
             }
             
@@ -269,27 +283,79 @@ impl ButtonComponent {
             }
         }
         
-        // 绘制文本
+        // 绘制文本（loading 时加载图标在文字左侧，与文字一起对齐）
+        let loading = node.attrs.get("loading")
+            .map(|s| s == "true" || s == "{{true}}")
+            .unwrap_or(false);
+        let color = style.text_color.unwrap_or(Color::WHITE);
+        let size = style.font_size * sf;
+        let letter_spacing = style.letter_spacing * sf;
+        let tw = text_renderer
+            .map(|tr| tr.measure_text_with_font(&node.text, size, letter_spacing, style.font()))
+            .unwrap_or(0.0);
+        let icon_w = if loading { size + if tw > 0.0 { 5.0 * sf } else { 0.0 } } else { 0.0 };
+        let content_w = icon_w + tw;
+        
+        // 根据 text-align 计算 x 位置
+        let cx = match style.text_align {
+            TextAlign::Center => x + (w - content_w) / 2.0,
+            TextAlign::Right => x + w - content_w - 8.0 * sf, // 右边留点 padding
+            TextAlign::Left | TextAlign::Justify => x + 8.0 * sf, // 左边留点 padding
+        };
+        
+        if loading {
+            Self::draw_loading(canvas, cx + size / 2.0, y + h / 2.0, size / 2.0, color);
+        }
+        
         if let Some(tr) = text_renderer {
-            let color = style.text_color.unwrap_or(Color::WHITE);
-            let size = style.font_size * sf;
-            let tw = tr.measure_text(&node.text, size);
-            
-            // 根据 text-align 计算 x 位置
-            let tx = match style.text_align {
-                TextAlign::Center => x + (w - tw) / 2.0,
-                TextAlign::Right => x + w - tw - 8.0 * sf, // 右边留点 padding
-                TextAlign::Left | TextAlign::Justify => x + 8.0 * sf, // 左边留点 padding
-            };
-            
-            // 垂直居中
-            let ty = y + (h - size) / 2.0 + size;
+            // 文字在整个按钮高度内垂直居中（行高撑高的按钮同样居中）
+            let ty = y + tr.baseline_offset(size, h);
             
             let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill);
-            tr.draw_text(canvas, &node.text, tx, ty, size, &paint);
+            tr.draw_text_with_font(canvas, &node.text, cx + icon_w, ty, size, letter_spacing, style.font(), &paint);
+        }
+    }
+    
+    /// 加载图标：12 条辐条，透明度依次递减
+    fn draw_loading(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color) {
+        for i in 0..12 {
+            let angle = i as f32 * std::f32::consts::PI / 6.0 - std::f32::consts::FRAC_PI_2;
+            let alpha = (color.a as f32 * (12 - i) as f32 / 12.0) as u8;
+            let paint = Paint::new()
+                .with_color(Color::new(color.r, color.g, color.b, alpha))
+                .with_style(PaintStyle::Stroke)
+                .with_stroke_width((r * 0.2).max(1.0))
+                .with_stroke_cap(StrokeCap::Round)
+                .with_anti_alias(true);
+            let (cos, sin) = (angle.cos(), angle.sin());
+            let mut spoke = Path::new();
+            spoke.move_to(cx + cos * r * 0.5, cy + sin * r * 0.5);
+            spoke.line_to(cx + cos * r * 0.9, cy + sin * r * 0.9);
+            canvas.draw_path(&spoke, &paint);
         }
     }
     
+    /// open-type 对应的事件名和 event.detail，share 由宿主调用 onShareAppMessage，不在此列
+    pub fn open_type_event(open_type: &str) -> Option<(&'static str, JsonValue)> {
+        Some(match open_type {
+            "getUserInfo" => ("getuserinfo", json!({
+                "errMsg": "getUserInfo:ok",
+                "userInfo": { "nickName": "微信用户", "avatarUrl": "", "gender": 0, "language": "zh_CN" }
+            })),
+            "getPhoneNumber" => ("getphonenumber", json!({ "errMsg": "getPhoneNumber:fail user deny" })),
+            "chooseAvatar" => ("chooseavatar", json!({ "avatarUrl": "" })),
+            "openSetting" => ("opensetting", json!({ "errMsg": "openSetting:ok", "authSetting": {} })),
+            "contact" => ("contact", json!({ "errMsg": "enterContact:ok", "path": "", "query": {} })),
+            _ => return None,
+        })
+    }
+    
+    /// 禁用时向白色淡化颜色
+    fn fade_color(color: Color) -> Color {
+        let mix = |c: u8| (c as f32 + (255.0 - c as f32) * 0.55) as u8;
+        Color::new(mix(color.r), mix(color.g), mix(color.b), color.a)
+    }
+    
     /// 使颜色变暗
     fn darken_color(color: Color, amount: f32) -> Color {
         let factor = 1.0 - amount;
@@ -1,8 +1,9 @@
 //! Canvas 组件 - 微信小程序 Canvas 2D API 实现
 
 use super::base::*;
+use super::image::load_image;
 use crate::parser::wxml::WxmlNode;
-use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect as GeoRect};
+use crate::{Canvas, Color, FillRule, Matrix, Paint, PaintStyle, Path, Rect as GeoRect, StrokeCap, StrokeJoin, TextRenderer};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
@@ -24,8 +25,22 @@ pub struct Canvas2DContext {
     fill_style: Color,
     /// 当前描边颜色
     stroke_style: Color,
+    /// 填充渐变，设置后代替填充颜色
+    fill_gradient: Option<CanvasGradient>,
+    /// 描边渐变，设置后代替描边颜色
+    stroke_gradient: Option<CanvasGradient>,
     /// 线宽
     line_width: f32,
+    /// 线帽
+    line_cap: StrokeCap,
+    /// 线连接
+    line_join: StrokeJoin,
+    /// 斜接长度上限
+    miter_limit: f32,
+    /// 虚线模式
+    line_dash: Vec<f32>,
+    /// 虚线偏移
+    line_dash_offset: f32,
     /// 字体大小
     font_size: f32,
     /// 文本对齐
@@ -70,7 +85,14 @@ enum PathCommand {
 struct ContextState {
     fill_style: Color,
     stroke_style: Color,
+    fill_gradient: Option<CanvasGradient>,
+    stroke_gradient: Option<CanvasGradient>,
     line_width: f32,
+    line_cap: StrokeCap,
+    line_join: StrokeJoin,
+    miter_limit: f32,
+    line_dash: Vec<f32>,
+    line_dash_offset: f32,
     font_size: f32,
     text_align: TextAlign,
     text_baseline: TextBaseline,
@@ -87,7 +109,14 @@ impl Canvas2DContext {
             canvas: Arc::new(Mutex::new(canvas)),
             fill_style: Color::BLACK,
             stroke_style: Color::BLACK,
+            fill_gradient: None,
+            stroke_gradient: None,
             line_width: 1.0,
+            line_cap: StrokeCap::Butt,
+            line_join: StrokeJoin::Miter,
+            miter_limit: 10.0,
+            line_dash: Vec::new(),
+            line_dash_offset: 0.0,
             font_size: 10.0,
             text_align: TextAlign::Left,
             text_baseline: TextBaseline::default(),
@@ -104,7 +133,14 @@ impl Canvas2DContext {
         self.state_stack.push(ContextState {
             fill_style: self.fill_style,
             stroke_style: self.stroke_style,
+            fill_gradient: self.fill_gradient.clone(),
+            stroke_gradient: self.stroke_gradient.clone(),
             line_width: self.line_width,
+            line_cap: self.line_cap,
+            line_join: self.line_join,
+            miter_limit: self.miter_limit,
+            line_dash: self.line_dash.clone(),
+            line_dash_offset: self.line_dash_offset,
             font_size: self.font_size,
             text_align: self.text_align,
             text_baseline: self.text_baseline,
@@ -120,7 +156,14 @@ impl Canvas2DContext {
         if let Some(state) = self.state_stack.pop() {
             self.fill_style = state.fill_style;
             self.stroke_style = state.stroke_style;
+            self.fill_gradient = state.fill_gradient;
+            self.stroke_gradient = state.stroke_gradient;
             self.line_width = state.line_width;
+            self.line_cap = state.line_cap;
+            self.line_join = state.line_join;
+            self.miter_limit = state.miter_limit;
+            self.line_dash = state.line_dash;
+            self.line_dash_offset = state.line_dash_offset;
             self.font_size = state.font_size;
             self.text_align = state.text_align;
             self.text_baseline = state.text_baseline;
@@ -137,21 +180,64 @@ impl Canvas2DContext {
     pub fn set_fill_style(&mut self, color: &str) {
         if let Some(c) = parse_color_str(color) {
             self.fill_style = c;
+            self.fill_gradient = None;
         }
     }
+
+    /// 设置填充渐变
+    pub fn set_fill_gradient(&mut self, gradient: CanvasGradient) {
+        self.fill_gradient = Some(gradient);
+    }
     
     /// 设置描边颜色
     pub fn set_stroke_style(&mut self, color: &str) {
         if let Some(c) = parse_color_str(color) {
             self.stroke_style = c;
+            self.stroke_gradient = None;
         }
     }
+
+    /// 设置描边渐变
+    pub fn set_stroke_gradient(&mut self, gradient: CanvasGradient) {
+        self.stroke_gradient = Some(gradient);
+    }
     
     /// 设置线宽
     pub fn set_line_width(&mut self, width: f32) {
         self.line_width = width;
     }
 
+    /// 设置线帽：butt / round / square
+    pub fn set_line_cap(&mut self, cap: &str) {
+        self.line_cap = match cap {
+            "round" => StrokeCap::Round,
+            "square" => StrokeCap::Square,
+            _ => StrokeCap::Butt,
+        };
+    }
+
+    /// 设置线连接：miter / round / bevel
+    pub fn set_line_join(&mut self, join: &str) {
+        self.line_join = match join {
+            "round" => StrokeJoin::Round,
+            "bevel" => StrokeJoin::Bevel,
+            _ => StrokeJoin::Miter,
+        };
+    }
+
+    /// 设置斜接长度上限
+    pub fn set_miter_limit(&mut self, limit: f32) {
+        if limit > 0.0 {
+            self.miter_limit = limit;
+        }
+    }
+
+    /// 设置虚线模式，pattern 为空时恢复实线
+    pub fn set_line_dash(&mut self, pattern: &[f32], offset: f32) {
+        self.line_dash = pattern.to_vec();
+        self.line_dash_offset = offset;
+    }
+
     /// 设置全局透明度
     pub fn set_global_alpha(&mut self, alpha: f32) {
         self.global_alpha = alpha.clamp(0.0, 1.0);
@@ -169,6 +255,13 @@ impl Canvas2DContext {
         }
     }
     
+    /// 设置字号
+    pub fn set_font_size(&mut self, size: f32) {
+        if size > 0.0 {
+            self.font_size = size;
+        }
+    }
+
     /// 设置文本对齐
     pub fn set_text_align(&mut self, align: &str) {
         self.text_align = match align {
@@ -196,10 +289,30 @@ impl Canvas2DContext {
     /// 清除矩形区域（设置为透明）
     pub fn clear_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
         if let Ok(mut canvas) = self.canvas.lock() {
-            let x0 = x.max(0.0) as i32;
-            let y0 = y.max(0.0) as i32;
-            let x1 = (x + width).min(canvas.width() as f32) as i32;
-            let y1 = (y + height).min(canvas.height() as f32) as i32;
+            let (tx, ty) = canvas.translation();
+            if !canvas.matrix().is_identity() {
+                // 旋转或缩放后的矩形：先画出覆盖率，再按覆盖率擦除
+                let mut mask = canvas.new_layer();
+                mask.concat(&canvas.matrix());
+                mask.draw_rect(&GeoRect::new(x, y, width, height), &Paint::new().with_color(Color::WHITE));
+                let w = mask.width() as i32;
+                let (ox, oy) = mask.origin();
+                for (i, coverage) in mask.pixels().iter().enumerate() {
+                    if coverage.a == 0 {
+                        continue;
+                    }
+                    let (px, py) = (ox + i as i32 % w, oy + i as i32 / w);
+                    let c = canvas.get_pixel(px as u32, py as u32);
+                    let keep = 255 - coverage.a as u32;
+                    let scale = |v: u8| (v as u32 * keep / 255) as u8;
+                    canvas.set_pixel_direct(px, py, Color::new(c.r, c.g, c.b, scale(c.a)));
+                }
+                return;
+            }
+            let x0 = (x + tx).max(0.0) as i32;
+            let y0 = (y + ty).max(0.0) as i32;
+            let x1 = (x + tx + width).min(canvas.width() as f32) as i32;
+            let y1 = (y + ty + height).min(canvas.height() as f32) as i32;
             
             // 直接设置像素为透明
             for py in y0..y1 {
@@ -212,25 +325,17 @@ impl Canvas2DContext {
     
     /// 填充矩形
     pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
-        if let Ok(mut canvas) = self.canvas.lock() {
-            let rect = GeoRect::new(x, y, width, height);
-            let paint = Paint::new()
-                .with_color(self.apply_alpha(self.fill_style))
-                .with_style(PaintStyle::Fill);
-            canvas.draw_rect(&rect, &paint);
-        }
+        let rect = GeoRect::new(x, y, width, height);
+        let paint = Paint::new()
+            .with_color(self.apply_alpha(self.fill_style))
+            .with_style(PaintStyle::Fill);
+        self.paint_with(&self.fill_gradient, paint, |canvas, paint| canvas.draw_rect(&rect, paint));
     }
 
     /// 描边矩形
     pub fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
-        if let Ok(mut canvas) = self.canvas.lock() {
-            let rect = GeoRect::new(x, y, width, height);
-            let paint = Paint::new()
-                .with_color(self.apply_alpha(self.stroke_style))
-                .with_style(PaintStyle::Stroke)
-                .with_stroke_width(self.line_width);
-            canvas.draw_rect(&rect, &paint);
-        }
+        let rect = GeoRect::new(x, y, width, height);
+        self.paint_with(&self.stroke_gradient, self.stroke_paint(), |canvas, paint| canvas.draw_rect(&rect, paint));
     }
 
     // ========== 路径绑制 ==========
@@ -275,29 +380,21 @@ impl Canvas2DContext {
         self.current_path.push(PathCommand::Rect(x, y, width, height));
     }
 
-    /// 填充当前路径
-    pub fn fill(&mut self) {
-        let path = self.build_path();
-        if let Ok(mut canvas) = self.canvas.lock() {
-            let paint = Paint::new()
-                .with_color(self.apply_alpha(self.fill_style))
-                .with_style(PaintStyle::Fill)
-                .with_anti_alias(true);
-            canvas.draw_path(&path, &paint);
-        }
+    /// 按填充规则填充当前路径
+    pub fn fill(&mut self, rule: FillRule) {
+        let mut path = self.build_path();
+        path.set_fill_rule(rule);
+        let paint = Paint::new()
+            .with_color(self.apply_alpha(self.fill_style))
+            .with_style(PaintStyle::Fill)
+            .with_anti_alias(true);
+        self.paint_with(&self.fill_gradient, paint, |canvas, paint| canvas.draw_path(&path, paint));
     }
     
     /// 描边当前路径
     pub fn stroke(&mut self) {
         let path = self.build_path();
-        if let Ok(mut canvas) = self.canvas.lock() {
-            let paint = Paint::new()
-                .with_color(self.apply_alpha(self.stroke_style))
-                .with_style(PaintStyle::Stroke)
-                .with_stroke_width(self.line_width)
-                .with_anti_alias(true);
-            canvas.draw_path(&path, &paint);
-        }
+        self.paint_with(&self.stroke_gradient, self.stroke_paint(), |canvas, paint| canvas.draw_path(&path, paint));
     }
     
     /// 构建 Path 对象
@@ -333,46 +430,133 @@ impl Canvas2DContext {
     
     /// 绘制填充圆
     pub fn fill_circle(&mut self, x: f32, y: f32, radius: f32) {
-        if let Ok(mut canvas) = self.canvas.lock() {
-            let paint = Paint::new()
-                .with_color(self.apply_alpha(self.fill_style))
-                .with_style(PaintStyle::Fill)
-                .with_anti_alias(true);
-            canvas.draw_circle(x, y, radius, &paint);
-        }
+        let paint = Paint::new()
+            .with_color(self.apply_alpha(self.fill_style))
+            .with_style(PaintStyle::Fill)
+            .with_anti_alias(true);
+        self.paint_with(&self.fill_gradient, paint, |canvas, paint| canvas.draw_circle(x, y, radius, paint));
     }
 
     /// 绘制描边圆
     pub fn stroke_circle(&mut self, x: f32, y: f32, radius: f32) {
-        if let Ok(mut canvas) = self.canvas.lock() {
-            let paint = Paint::new()
-                .with_color(self.apply_alpha(self.stroke_style))
-                .with_style(PaintStyle::Stroke)
-                .with_stroke_width(self.line_width)
-                .with_anti_alias(true);
-            canvas.draw_circle(x, y, radius, &paint);
+        self.paint_with(&self.stroke_gradient, self.stroke_paint(), |canvas, paint| canvas.draw_circle(x, y, radius, paint));
+    }
+
+    // ========== 文本绘制 ==========
+
+    /// 填充文本，文本宽度超过 max_width 时缩小字号
+    pub fn fill_text(&mut self, text: &str, x: f32, y: f32, max_width: Option<f32>) {
+        let Some(tr) = TextRenderer::shared() else { return };
+        let (size, x, baseline) = self.layout_text(&tr, text, x, y, max_width);
+        let paint = Paint::new().with_color(self.apply_alpha(self.fill_style));
+        self.paint_with(&self.fill_gradient, paint, |canvas, paint| {
+            draw_in_device_space(canvas, |target, tx, ty| {
+                tr.draw_text(target, text, x + tx, baseline + ty, size, paint);
+            });
+        });
+    }
+
+    /// 描边文本（沿字形轮廓描出 line_width 宽的边）
+    pub fn stroke_text(&mut self, text: &str, x: f32, y: f32, max_width: Option<f32>) {
+        let Some(tr) = TextRenderer::shared() else { return };
+        let (size, x, baseline) = self.layout_text(&tr, text, x, y, max_width);
+        let paint = Paint::new().with_color(self.apply_alpha(self.stroke_style));
+        let radius = self.line_width / 2.0;
+        self.paint_with(&self.stroke_gradient, paint, |canvas, paint| {
+            draw_in_device_space(canvas, |target, tx, ty| {
+                stroke_glyphs(target, &tr, text, x + tx, baseline + ty, size, radius, paint.color);
+            });
+        });
+    }
+
+    /// 按当前字号测量文本宽度
+    pub fn measure_text(&self, text: &str) -> f32 {
+        TextRenderer::shared().map_or(0.0, |tr| tr.measure_text(text, self.font_size))
+    }
+
+    /// 计算实际字号、绘制起点 x 和基线 y
+    fn layout_text(&self, tr: &TextRenderer, text: &str, x: f32, y: f32, max_width: Option<f32>) -> (f32, f32, f32) {
+        let mut size = self.font_size;
+        let mut width = tr.measure_text(text, size);
+        if let Some(max) = max_width.filter(|m| *m > 0.0) {
+            if width > max {
+                size *= max / width;
+                width = max;
+            }
         }
+        let x = match self.text_align {
+            TextAlign::Center => x - width / 2.0,
+            TextAlign::Right => x - width,
+            _ => x,
+        };
+        let (ascent, descent) = tr.vertical_metrics(size);
+        let baseline = match self.text_baseline {
+            TextBaseline::Top | TextBaseline::Hanging => y + ascent,
+            TextBaseline::Middle => y + (ascent - descent) / 2.0,
+            TextBaseline::Alphabetic => y,
+            TextBaseline::Ideographic | TextBaseline::Bottom => y - descent,
+        };
+        (size, x, baseline)
     }
 
     // ========== 线条绘制 ==========
     
     /// 绘制线条（从当前点到指定点）
     pub fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
-        if let Ok(mut canvas) = self.canvas.lock() {
-            let paint = Paint::new()
-                .with_color(self.apply_alpha(self.stroke_style))
-                .with_stroke_width(self.line_width)
-                .with_anti_alias(true);
-            canvas.draw_line(x1, y1, x2, y2, &paint);
+        self.paint_with(&self.stroke_gradient, self.stroke_paint(), |canvas, paint| canvas.draw_line(x1, y1, x2, y2, paint));
+    }
+
+    // ========== 图片绘制 ==========
+
+    /// 绘制图片：source 为图片上的源矩形（默认整张图片），
+    /// 目标矩形左上角为 (dx, dy)，size 默认与源矩形同大
+    ///
+    /// 网络图片在下载完成前跳过绘制。
+    pub fn draw_image(&mut self, src: &str, source: Option<GeoRect>, dx: f32, dy: f32, size: Option<(f32, f32)>) {
+        let Some(img) = load_image(src) else {
+            log::debug!("⚠️ [Canvas] drawImage: image not ready: {}", src);
+            return;
+        };
+        let frame = img.current_frame();
+        let source = source.unwrap_or_else(|| GeoRect::new(0.0, 0.0, img.width as f32, img.height as f32));
+        let (dw, dh) = size.unwrap_or((source.width, source.height));
+        if source.width <= 0.0 || source.height <= 0.0 || dw == 0.0 || dh == 0.0 {
+            return;
         }
+
+        let dest = GeoRect::new(dx, dy, dw, dh);
+        let (sx, sy) = (source.width / dw, source.height / dh);
+        self.paint_shaded(
+            Paint::new().with_style(PaintStyle::Fill),
+            |canvas, paint| canvas.draw_rect(&dest, paint),
+            |x, y| sample_image(frame, img.width, img.height, &source, source.x + (x - dx) * sx, source.y + (y - dy) * sy),
+        );
     }
 
     // ========== 变换 ==========
     
-    /// 平移
+    /// 平移（在当前变换的坐标系中）
+    ///
+    /// 内部画布的变换矩阵只保存线性部分，平移经过矩阵映射后累加到画布的平移上，
+    /// 这样只有平移时仍走画布的快速路径。
     pub fn translate(&mut self, x: f32, y: f32) {
         if let Ok(mut canvas) = self.canvas.lock() {
-            canvas.translate(x, y);
+            let m = canvas.matrix();
+            canvas.translate(m.a * x + m.c * y, m.b * x + m.d * y);
+        }
+    }
+
+    /// 旋转（弧度，顺时针）
+    pub fn rotate(&mut self, angle: f32) {
+        if let Ok(mut canvas) = self.canvas.lock() {
+            canvas.concat(&Matrix::rotate(angle.to_degrees()));
+        }
+    }
+
+    /// 缩放
+    pub fn scale(&mut self, sx: f32, sy: f32) {
+        if let Ok(mut canvas) = self.canvas.lock() {
+            canvas.concat(&Matrix::scale(sx, sy));
         }
     }
 
@@ -389,6 +573,55 @@ impl Canvas2DContext {
     }
 
     // ========== 辅助方法 ==========
+
+    /// 按填充或描边样式绘制：纯色直接画到画布上，渐变逐像素按用户坐标取色
+    fn paint_with(&self, gradient: &Option<CanvasGradient>, paint: Paint, draw: impl FnOnce(&mut Canvas, &Paint)) {
+        match gradient {
+            Some(gradient) => self.paint_shaded(paint, draw, |x, y| gradient.get_color_at(x, y)),
+            None => {
+                if let Ok(mut canvas) = self.canvas.lock() {
+                    draw(&mut canvas, &paint);
+                }
+            }
+        }
+    }
+
+    /// 先用白色画出覆盖率遮罩，再对每个覆盖到的像素按用户坐标调用 shader 取色混合
+    fn paint_shaded(&self, paint: Paint, draw: impl FnOnce(&mut Canvas, &Paint), shader: impl Fn(f32, f32) -> Color) {
+        let Ok(mut canvas) = self.canvas.lock() else { return };
+        let matrix = canvas.matrix();
+        let Some(inv) = matrix.invert() else { return };
+        let (tx, ty) = canvas.translation();
+        let mut mask = canvas.new_layer();
+        mask.concat(&matrix);
+        draw(&mut mask, &paint.with_color(Color::WHITE));
+
+        let width = mask.width() as i32;
+        let (ox, oy) = mask.origin();
+        for (i, coverage) in mask.pixels().iter().enumerate() {
+            if coverage.a == 0 {
+                continue;
+            }
+            let (x, y) = (ox + i as i32 % width, oy + i as i32 / width);
+            let p = inv.map_point(x as f32 + 0.5 - tx, y as f32 + 0.5 - ty);
+            let c = shader(p.x, p.y);
+            let a = c.a as f32 * self.global_alpha * coverage.a as f32 / 255.0;
+            canvas.set_pixel(x, y, Color::new(c.r, c.g, c.b, a.round() as u8));
+        }
+    }
+
+    /// 当前描边样式对应的画笔
+    fn stroke_paint(&self) -> Paint {
+        Paint::new()
+            .with_color(self.apply_alpha(self.stroke_style))
+            .with_style(PaintStyle::Stroke)
+            .with_stroke_width(self.line_width)
+            .with_stroke_cap(self.line_cap)
+            .with_stroke_join(self.line_join)
+            .with_stroke_miter(self.miter_limit)
+            .with_dash(&self.line_dash, self.line_dash_offset)
+            .with_anti_alias(true)
+    }
     
     /// 应用全局透明度
     fn apply_alpha(&self, color: Color) -> Color {
@@ -408,6 +641,29 @@ impl Canvas2DContext {
         }
     }
     
+    /// 把 rect 区域（默认整个画布）导出为 dest_size 大小（默认与区域同大）的 PNG
+    pub fn export_png(&self, path: &std::path::Path, rect: Option<GeoRect>, dest_size: Option<(u32, u32)>) -> Result<(), String> {
+        let canvas = self.canvas.lock().map_err(|e| e.to_string())?;
+        let (cw, ch) = (canvas.width(), canvas.height());
+        let rect = rect.unwrap_or_else(|| GeoRect::new(0.0, 0.0, cw as f32, ch as f32));
+        let x0 = (rect.x.max(0.0) as u32).min(cw);
+        let y0 = (rect.y.max(0.0) as u32).min(ch);
+        let x1 = (rect.right().max(0.0).ceil() as u32).min(cw);
+        let y1 = (rect.bottom().max(0.0).ceil() as u32).min(ch);
+        if x1 <= x0 || y1 <= y0 {
+            return Err("empty region".into());
+        }
+
+        let rgba = canvas.to_rgba();
+        drop(canvas);
+        let full = image::RgbaImage::from_raw(cw, ch, rgba).ok_or("invalid canvas buffer")?;
+        let mut region = image::imageops::crop_imm(&full, x0, y0, x1 - x0, y1 - y0).to_image();
+        if let Some((w, h)) = dest_size.filter(|&(w, h)| w > 0 && h > 0 && (w, h) != region.dimensions()) {
+            region = image::imageops::resize(&region, w, h, image::imageops::FilterType::Triangle);
+        }
+        region.save_with_format(path, image::ImageFormat::Png).map_err(|e| e.to_string())
+    }
+
     /// 获取内部 Canvas 引用（用于渲染）
     pub fn get_canvas(&self) -> Arc<Mutex<Canvas>> {
         self.canvas.clone()
@@ -415,6 +671,140 @@ impl Canvas2DContext {
 }
 
 
+/// 双线性采样图片（按预乘 alpha 插值），采样范围限制在源矩形内
+fn sample_image(data: &[u8], width: u32, height: u32, source: &GeoRect, u: f32, v: f32) -> Color {
+    let x0 = source.x.max(0.0);
+    let y0 = source.y.max(0.0);
+    let x1 = source.right().min(width as f32) - 1.0;
+    let y1 = source.bottom().min(height as f32) - 1.0;
+    if x1 < x0 || y1 < y0 {
+        return Color::TRANSPARENT;
+    }
+    let u = (u - 0.5).clamp(x0, x1);
+    let v = (v - 0.5).clamp(y0, y1);
+    let (ix, iy) = (u.floor() as u32, v.floor() as u32);
+    let (fx, fy) = (u - ix as f32, v - iy as f32);
+    let ix1 = (ix + 1).min(x1 as u32);
+    let iy1 = (iy + 1).min(y1 as u32);
+
+    let mut acc = [0.0f32; 4];
+    for (px, py, w) in [(ix, iy, (1.0 - fx) * (1.0 - fy)), (ix1, iy, fx * (1.0 - fy)), (ix, iy1, (1.0 - fx) * fy), (ix1, iy1, fx * fy)] {
+        let i = ((py * width + px) * 4) as usize;
+        let Some(p) = data.get(i..i + 4) else { continue };
+        let a = p[3] as f32 * w;
+        acc[0] += p[0] as f32 * a;
+        acc[1] += p[1] as f32 * a;
+        acc[2] += p[2] as f32 * a;
+        acc[3] += a;
+    }
+    if acc[3] <= 0.0 {
+        return Color::TRANSPARENT;
+    }
+    Color::new(
+        (acc[0] / acc[3]).round() as u8,
+        (acc[1] / acc[3]).round() as u8,
+        (acc[2] / acc[3]).round() as u8,
+        acc[3].round() as u8,
+    )
+}
+
+/// 文本按像素直接绘制，不经过变换矩阵：平移直接加到坐标上，
+/// 有旋转或缩放时先画到图层上再按当前变换合成
+fn draw_in_device_space(canvas: &mut Canvas, draw: impl FnOnce(&mut Canvas, f32, f32)) {
+    let (tx, ty) = canvas.translation();
+    if canvas.matrix().is_identity() {
+        draw(canvas, tx, ty);
+    } else {
+        let mut layer = canvas.new_layer();
+        draw(&mut layer, tx, ty);
+        canvas.draw_layer(&layer, &Matrix::IDENTITY);
+    }
+}
+
+/// 描边文字：字形覆盖率按半个线宽膨胀减去腐蚀，得到沿轮廓的边
+#[allow(clippy::too_many_arguments)]
+fn stroke_glyphs(target: &mut Canvas, tr: &TextRenderer, text: &str, x: f32, baseline: f32, size: f32, radius: f32, color: Color) {
+    let radius = radius.max(1.0);
+    let reach = radius.ceil() as i32;
+    let (ascent, descent) = tr.vertical_metrics(size);
+    let pad = reach + 2;
+    let ox = x.floor() as i32 - pad;
+    let oy = (baseline - ascent).floor() as i32 - pad;
+    let w = (tr.measure_text(text, size).ceil() as i32 + pad * 2).max(1) as u32;
+    let h = ((ascent + descent).ceil() as i32 + pad * 2).max(1) as u32;
+
+    let mut mask = Canvas::new(w, h);
+    tr.draw_text(&mut mask, text, x - ox as f32, baseline - oy as f32, size, &Paint::new().with_color(Color::BLACK));
+    let coverage: Vec<u8> = mask.pixels().iter().map(|c| c.a).collect();
+    let at = |px: i32, py: i32| {
+        if px < 0 || py < 0 || px >= w as i32 || py >= h as i32 { 0 } else { coverage[(py * w as i32 + px) as usize] }
+    };
+
+    let offsets: Vec<(i32, i32)> = (-reach..=reach)
+        .flat_map(|dy| (-reach..=reach).map(move |dx| (dx, dy)))
+        .filter(|(dx, dy)| (dx * dx + dy * dy) as f32 <= radius * radius)
+        .collect();
+    for py in 0..h as i32 {
+        for px in 0..w as i32 {
+            let (mut outer, mut inner) = (0u8, 255u8);
+            for (dx, dy) in &offsets {
+                let c = at(px + dx, py + dy);
+                outer = outer.max(c);
+                inner = inner.min(c);
+            }
+            let alpha = outer.saturating_sub(inner);
+            if alpha > 0 {
+                let a = (color.a as u32 * alpha as u32 / 255) as u8;
+                target.set_pixel(ox + px, oy + py, Color::new(color.r, color.g, color.b, a));
+            }
+        }
+    }
+}
+
+/// 填充 / 描边用的渐变
+#[derive(Clone)]
+pub enum CanvasGradient {
+    Linear(LinearGradient),
+    Radial(RadialGradient),
+}
+
+impl CanvasGradient {
+    /// 从 JS 传来的渐变描述解析：
+    /// `{kind: 'linear', x0, y0, x1, y1, stops}` 或 `{kind: 'radial', x0, y0, r0, x1, y1, r1, stops}`，
+    /// stops 为 `[{offset, color}]`
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let num = |key: &str| value.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+        let mut gradient = match value.get("kind")?.as_str()? {
+            "linear" => CanvasGradient::Linear(LinearGradient::new(num("x0"), num("y0"), num("x1"), num("y1"))),
+            "radial" => CanvasGradient::Radial(RadialGradient::new(num("x0"), num("y0"), num("r0"), num("x1"), num("y1"), num("r1"))),
+            _ => return None,
+        };
+        for stop in value.get("stops").and_then(|v| v.as_array()).into_iter().flatten() {
+            let offset = stop.get("offset").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+            if let Some(color) = stop.get("color").and_then(|v| v.as_str()) {
+                gradient.add_color_stop(offset, color);
+            }
+        }
+        Some(gradient)
+    }
+
+    /// 添加颜色停止点
+    pub fn add_color_stop(&mut self, offset: f32, color: &str) {
+        match self {
+            CanvasGradient::Linear(g) => g.add_color_stop(offset, color),
+            CanvasGradient::Radial(g) => g.add_color_stop(offset, color),
+        }
+    }
+
+    /// 获取指定位置的颜色
+    pub fn get_color_at(&self, x: f32, y: f32) -> Color {
+        match self {
+            CanvasGradient::Linear(g) => g.get_color_at(x, y),
+            CanvasGradient::Radial(g) => g.get_color_at(x, y),
+        }
+    }
+}
+
 /// 线性渐变
 #[derive(Clone)]
 pub struct LinearGradient {
@@ -610,11 +1000,15 @@ impl CanvasContextManager {
                 "setFillStyle" => {
                     if let Some(color) = cmd.get("color").and_then(|v| v.as_str()) {
                         ctx.set_fill_style(color);
+                    } else if let Some(gradient) = cmd.get("gradient").and_then(CanvasGradient::from_json) {
+                        ctx.set_fill_gradient(gradient);
                     }
                 }
                 "setStrokeStyle" => {
                     if let Some(color) = cmd.get("color").and_then(|v| v.as_str()) {
                         ctx.set_stroke_style(color);
+                    } else if let Some(gradient) = cmd.get("gradient").and_then(CanvasGradient::from_json) {
+                        ctx.set_stroke_gradient(gradient);
                     }
                 }
                 "setLineWidth" => {
@@ -622,6 +1016,28 @@ impl CanvasContextManager {
                         ctx.set_line_width(width as f32);
                     }
                 }
+                "setLineCap" => {
+                    if let Some(cap) = cmd.get("cap").and_then(|v| v.as_str()) {
+                        ctx.set_line_cap(cap);
+                    }
+                }
+                "setLineJoin" => {
+                    if let Some(join) = cmd.get("join").and_then(|v| v.as_str()) {
+                        ctx.set_line_join(join);
+                    }
+                }
+                "setMiterLimit" => {
+                    if let Some(limit) = cmd.get("limit").and_then(|v| v.as_f64()) {
+                        ctx.set_miter_limit(limit as f32);
+                    }
+                }
+                "setLineDash" => {
+                    let pattern: Vec<f32> = cmd.get("pattern").and_then(|v| v.as_array())
+                        .map(|a| a.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+                        .unwrap_or_default();
+                    let offset = cmd.get("offset").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+                    ctx.set_line_dash(&pattern, offset);
+                }
                 "fillRect" => {
                     let x = cmd.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
                     let y = cmd.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
@@ -664,7 +1080,44 @@ impl CanvasContextManager {
                     let cc = cmd.get("counterclockwise").and_then(|v| v.as_bool()).unwrap_or(false);
                     ctx.arc(x, y, r, s, e, cc);
                 }
-                "fill" => ctx.fill(),
+                "setGlobalAlpha" => {
+                    if let Some(alpha) = cmd.get("alpha").and_then(|v| v.as_f64()) {
+                        ctx.set_global_alpha(alpha as f32);
+                    }
+                }
+                "setFontSize" => {
+                    if let Some(size) = cmd.get("size").and_then(|v| v.as_f64()) {
+                        ctx.set_font_size(size as f32);
+                    }
+                }
+                "setTextAlign" => {
+                    if let Some(align) = cmd.get("align").and_then(|v| v.as_str()) {
+                        ctx.set_text_align(align);
+                    }
+                }
+                "setTextBaseline" => {
+                    if let Some(baseline) = cmd.get("baseline").and_then(|v| v.as_str()) {
+                        ctx.set_text_baseline(baseline);
+                    }
+                }
+                "fillText" | "strokeText" => {
+                    let text = cmd.get("text").map(|v| match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    }).unwrap_or_default();
+                    let x = cmd.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+                    let y = cmd.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+                    let max_width = cmd.get("maxWidth").and_then(|v| v.as_f64()).map(|v| v as f32);
+                    if cmd_type == "fillText" {
+                        ctx.fill_text(&text, x, y, max_width);
+                    } else {
+                        ctx.stroke_text(&text, x, y, max_width);
+                    }
+                }
+                "fill" => {
+                    let rule = cmd.get("rule").and_then(|v| v.as_str()).and_then(FillRule::parse).unwrap_or_default();
+                    ctx.fill(rule);
+                }
                 "stroke" => ctx.stroke(),
                 "save" => ctx.save(),
                 "restore" => ctx.restore(),
@@ -673,6 +1126,25 @@ impl CanvasContextManager {
                     let y = cmd.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
                     ctx.translate(x, y);
                 }
+                "drawImage" => {
+                    let num = |key: &str| cmd.get(key).and_then(|v| v.as_f64()).map(|v| v as f32);
+                    let src = cmd.get("src").and_then(|v| v.as_str()).unwrap_or_default();
+                    let source = match (num("sx"), num("sy"), num("sWidth"), num("sHeight")) {
+                        (Some(x), Some(y), Some(w), Some(h)) => Some(GeoRect::new(x, y, w, h)),
+                        _ => None,
+                    };
+                    let size = num("dWidth").zip(num("dHeight"));
+                    ctx.draw_image(src, source, num("dx").unwrap_or(0.0), num("dy").unwrap_or(0.0), size);
+                }
+                "rotate" => {
+                    let angle = cmd.get("angle").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+                    ctx.rotate(angle);
+                }
+                "scale" => {
+                    let sx = cmd.get("scaleX").and_then(|v| v.as_f64()).unwrap_or(1.0) as f32;
+                    let sy = cmd.get("scaleY").and_then(|v| v.as_f64()).map_or(sx, |v| v as f32);
+                    ctx.scale(sx, sy);
+                }
                 _ => {}
             }
         }
@@ -696,13 +1168,48 @@ pub static CANVAS_MANAGER: Lazy<Mutex<CanvasContextManager>> = Lazy::new(|| {
 
 /// 执行 Canvas 绘制命令（供外部调用）
 pub fn execute_canvas_draw(canvas_id: &str, commands_json: &str) {
-    println!("[Canvas] execute_canvas_draw: {} commands for '{}'", 
+    log::trace!("[Canvas] execute_canvas_draw: {} commands for '{}'", 
         commands_json.len(), canvas_id);
     if let Ok(mut manager) = CANVAS_MANAGER.lock() {
         manager.execute_commands(canvas_id, commands_json);
     }
 }
 
+/// wx.canvasToTempFilePath：按 {canvasId, x, y, width, height, destWidth, destHeight}
+/// 导出 canvas 到临时目录下的 PNG，返回文件路径
+pub fn canvas_to_temp_file(options: &serde_json::Value) -> Result<String, String> {
+    static NEXT_FILE_ID: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(1);
+
+    let canvas_id = options.get("canvasId").and_then(|v| v.as_str()).unwrap_or_default();
+    let num = |key: &str| options.get(key).and_then(|v| v.as_f64()).map(|v| v as f32);
+    let manager = CANVAS_MANAGER.lock().map_err(|e| e.to_string())?;
+    let ctx = manager.get_existing_context(canvas_id)
+        .ok_or_else(|| format!("canvas '{}' not found", canvas_id))?;
+
+    let (x, y) = (num("x").unwrap_or(0.0), num("y").unwrap_or(0.0));
+    let rect = GeoRect::new(
+        x,
+        y,
+        num("width").unwrap_or(ctx.width as f32 - x),
+        num("height").unwrap_or(ctx.height as f32 - y),
+    );
+    let dest_size = match (num("destWidth"), num("destHeight")) {
+        (None, None) => None,
+        (w, h) => Some((
+            w.unwrap_or(rect.width).round() as u32,
+            h.unwrap_or(rect.height).round() as u32,
+        )),
+    };
+
+    let dir = std::env::temp_dir().join("mini-render");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let id = NEXT_FILE_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let path = dir.join(format!("canvas_{}_{}.png", std::process::id(), id));
+    ctx.export_png(&path, Some(rect), dest_size)?;
+    log::debug!("[Canvas] canvasToTempFilePath '{}' -> {}", canvas_id, path.display());
+    Ok(path.to_string_lossy().into_owned())
+}
+
 impl CanvasComponent {
     pub fn build(node: &WxmlNode, ctx: &mut ComponentContext) -> Option<RenderNode> {
         let (ts, mut ns) = build_base_style(node, ctx);
@@ -752,14 +1259,9 @@ impl CanvasComponent {
                         let copy_h = (h as usize).min(src_h);
                         
                         for sy in 0..copy_h {
-                            for sx in 0..copy_w {
-                                let src_idx = sy * src_w + sx;
-                                if src_idx < src_pixels.len() {
-                                    let color = src_pixels[src_idx];
-                                    if color.a > 0 {
-                                        canvas.set_pixel(dst_x + sx as i32, dst_y + sy as i32, color);
-                                    }
-                                }
+                            let row_start = sy * src_w;
+                            if row_start + copy_w <= src_pixels.len() {
+                                canvas.blend_row(dst_y + sy as i32, dst_x, &src_pixels[row_start..row_start + copy_w]);
                             }
                         }
                     }
@@ -0,0 +1,45 @@
+//! cover-view / cover-image 组件 - 覆盖在原生组件（video 等）之上的视图
+//!
+//! 渲染器不会在遍历时直接绘制 cover 组件，而是记录下来，在所有内容绘制完成后
+//! 作为顶层统一绘制，因此总是位于视频画面之上，不受 z-index 影响。
+//! 交互区域同样在最后注册，点击命中 cover 组件时不会触发视频的播放/暂停。
+
+use super::base::*;
+use super::image::ImageComponent;
+use crate::parser::wxml::WxmlNode;
+
+pub struct CoverViewComponent;
+
+impl CoverViewComponent {
+    pub fn build(node: &WxmlNode, ctx: &mut ComponentContext) -> Option<RenderNode> {
+        let (ts, ns) = build_base_style(node, ctx);
+        let tn = ctx.taffy.new_leaf(ts).unwrap();
+
+        Some(RenderNode {
+            tag: "cover-view".into(),
+            text: String::new(),
+            attrs: node.attributes.clone(),
+            taffy_node: tn,
+            style: ns,
+            children: vec![],
+            events: extract_events(node),
+        })
+    }
+}
+
+pub struct CoverImageComponent;
+
+impl CoverImageComponent {
+    pub fn build(node: &WxmlNode, ctx: &mut ComponentContext) -> Option<RenderNode> {
+        let mut rn = ImageComponent::build(node, ctx)?;
+        rn.tag = "cover-image".into();
+        // cover-image 没有占位背景，未加载时透出下面的视频画面
+        rn.style.background_color = build_base_style(node, ctx).1.background_color;
+        Some(rn)
+    }
+}
+
+/// 是否是需要绘制到顶层的 cover 组件
+pub fn is_cover_component(tag: &str) -> bool {
+    matches!(tag, "cover-view" | "cover-image")
+}
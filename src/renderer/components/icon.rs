@@ -10,7 +10,9 @@
 //! - cancel: 取消（红色X）
 //! - download: 下载
 //! - search: 搜索
-//! - clear: 清除
+//! - clear: 清除（灰色X）
+//! 
+//! size 为图标边长，支持 px / rpx，默认 23px
 //! 
 //! CSS 支持：
 //! - width/height: 自定义尺寸（覆盖 size 属性）
@@ -20,7 +22,8 @@
 
 use super::base::*;
 use crate::parser::wxml::WxmlNode;
-use crate::{Canvas, Color, Paint, PaintStyle, Path};
+use crate::parser::wxss::rpx_to_px;
+use crate::{Canvas, Color, Paint, PaintStyle, Path, StrokeCap, StrokeJoin};
 use taffy::prelude::*;
 
 pub struct IconComponent;
@@ -33,7 +36,14 @@ impl IconComponent {
         let sf = ctx.scale_factor;
         
         let icon_type = node.get_attr("type").unwrap_or("success");
-        let icon_size = node.get_attr("size").and_then(|s| s.parse::<f32>().ok()).unwrap_or(23.0);
+        // size 支持 px 和 rpx，不带单位按 px
+        let icon_size = node.get_attr("size").and_then(|s| {
+            let s = s.trim();
+            match s.strip_suffix("rpx") {
+                Some(n) => n.trim().parse::<f32>().ok().map(|n| rpx_to_px(n, ctx.screen_width)),
+                None => s.trim_end_matches("px").trim().parse::<f32>().ok(),
+            }
+        }).unwrap_or(23.0);
         let icon_color = node.get_attr("color").and_then(|c| parse_color_str(c));
         
         // 检查 CSS 是否定义了尺寸和颜色
@@ -54,7 +64,8 @@ impl IconComponent {
                 "info" | "info_circle" => Color::from_hex(0x10AEFF),
                 "warn" => Color::from_hex(0xF76260),
                 "waiting" | "waiting_circle" => Color::from_hex(0x10AEFF),
-                "cancel" | "clear" => Color::from_hex(0xF43530),
+                "cancel" => Color::from_hex(0xF43530),
+                "clear" => Color::from_hex(0xB2B2B2),
                 "download" => Color::from_hex(0x09BB07),
                 "search" => Color::from_hex(0xB2B2B2),
                 _ => Color::from_hex(0x09BB07),
@@ -97,259 +108,118 @@ impl IconComponent {
         };
         
         let color = apply_opacity(style.text_color.unwrap_or(Color::from_hex(0x09BB07)));
-        let icon_type = node.text.as_str();
-        let cx = x + w / 2.0;
-        let cy = y + h / 2.0;
-        let r = w.min(h) / 2.0;
-        // 线条粗细根据图标大小调整
-        let stroke_width = (r * 0.12).max(2.0);
+        let mark = apply_opacity(Color::WHITE);
+        let size = w.min(h);
+        Self::draw_icon(canvas, node.text.as_str(), x + (w - size) / 2.0, y + (h - size) / 2.0, size, color, mark);
+    }
+    
+    /// 在 (x, y) 处绘制 size 见方的图标（物理像素），color 为图标主色，mark 为圆形底上的符号颜色
+    /// 
+    /// 图标框对齐到整像素，线宽随尺寸缩放，任意缩放比例下边缘都清晰。Toast 等覆盖层也用它绘制图标。
+    pub fn draw_icon(canvas: &mut Canvas, icon_type: &str, x: f32, y: f32, size: f32, color: Color, mark: Color) {
+        let size = size.round();
+        if size <= 0.0 {
+            return;
+        }
+        let (cx, cy) = (x.round() + size / 2.0, y.round() + size / 2.0);
+        let r = size / 2.0;
+        let fill = |color: Color| Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
         
         match icon_type {
-            "success" => Self::draw_success(canvas, cx, cy, r, color, true, stroke_width),
-            "success_no_circle" => Self::draw_success(canvas, cx, cy, r, color, false, stroke_width),
-            "info" | "info_circle" => Self::draw_info(canvas, cx, cy, r, color, stroke_width),
-            "warn" => Self::draw_warn(canvas, cx, cy, r, color, stroke_width),
-            "waiting" | "waiting_circle" => Self::draw_waiting(canvas, cx, cy, r, color, stroke_width),
-            "cancel" | "clear" => Self::draw_cancel(canvas, cx, cy, r, color, stroke_width),
-            "download" => Self::draw_download(canvas, cx, cy, r, color, stroke_width),
-            "search" => Self::draw_search(canvas, cx, cy, r, color, stroke_width),
-            _ => Self::draw_success(canvas, cx, cy, r, color, true, stroke_width),
+            "success_no_circle" => Self::stroke_check(canvas, cx, cy, r * 1.25, color, size * 0.1),
+            "info" | "info_circle" => {
+                canvas.draw_circle(cx, cy, r, &fill(color));
+                Self::draw_bar_and_dot(canvas, cx, cy, r, mark, true);
+            }
+            "warn" => {
+                canvas.draw_circle(cx, cy, r, &fill(color));
+                Self::draw_bar_and_dot(canvas, cx, cy, r, mark, false);
+            }
+            "waiting" | "waiting_circle" => {
+                canvas.draw_circle(cx, cy, r, &fill(color));
+                // 时钟指针：12 点和 3 点方向
+                let mut hands = Path::new();
+                hands.move_to(cx, cy - r * 0.5);
+                hands.line_to(cx, cy);
+                hands.line_to(cx + r * 0.38, cy);
+                canvas.draw_path(&hands, &Self::stroke(mark, size * 0.08));
+            }
+            "cancel" | "clear" => {
+                canvas.draw_circle(cx, cy, r, &fill(color));
+                let arm = r * 0.32;
+                let mut cross = Path::new();
+                cross.move_to(cx - arm, cy - arm);
+                cross.line_to(cx + arm, cy + arm);
+                cross.move_to(cx + arm, cy - arm);
+                cross.line_to(cx - arm, cy + arm);
+                canvas.draw_path(&cross, &Self::stroke(mark, size * 0.08));
+            }
+            "download" => {
+                canvas.draw_circle(cx, cy, r, &fill(color));
+                // 向下的箭头和底部横线
+                let mut arrow = Path::new();
+                arrow.move_to(cx, cy - r * 0.5);
+                arrow.line_to(cx, cy + r * 0.2);
+                arrow.move_to(cx - r * 0.28, cy - r * 0.05);
+                arrow.line_to(cx, cy + r * 0.22);
+                arrow.line_to(cx + r * 0.28, cy - r * 0.05);
+                arrow.move_to(cx - r * 0.38, cy + r * 0.48);
+                arrow.line_to(cx + r * 0.38, cy + r * 0.48);
+                canvas.draw_path(&arrow, &Self::stroke(mark, size * 0.08));
+            }
+            "search" => {
+                // 放大镜：镜片为圆环（中间透出背景），手柄 45° 向右下
+                let line = size * 0.09;
+                let lens_r = r * 0.55;
+                let (lens_cx, lens_cy) = (cx - r * 0.15, cy - r * 0.15);
+                let mut lens = Path::new();
+                lens.add_circle(lens_cx, lens_cy, lens_r);
+                canvas.draw_path(&lens, &Self::stroke(color, line));
+                
+                let d = std::f32::consts::FRAC_1_SQRT_2;
+                let mut handle = Path::new();
+                handle.move_to(lens_cx + lens_r * d, lens_cy + lens_r * d);
+                handle.line_to(cx + r * 0.75, cy + r * 0.75);
+                canvas.draw_path(&handle, &Self::stroke(color, line * 1.2));
+            }
+            // success 以及未知类型
+            _ => {
+                canvas.draw_circle(cx, cy, r, &fill(color));
+                Self::stroke_check(canvas, cx, cy, r, mark, size * 0.08);
+            }
         }
     }
     
-    fn draw_success(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, with_circle: bool, stroke_width: f32) {
-        if with_circle {
-            // 绘制圆形背景 - 使用抗锯齿
-            let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-            canvas.draw_circle(cx, cy, r, &paint);
-            
-            // 绘制白色对勾
-            Self::draw_thick_check(canvas, cx, cy, r, Color::WHITE, stroke_width * 1.2);
-        } else {
-            // 只绘制对勾
-            Self::draw_thick_check(canvas, cx, cy, r * 0.9, color, stroke_width * 1.5);
-        }
+    /// 圆头圆角的描边画笔，线宽至少 1 像素
+    fn stroke(color: Color, width: f32) -> Paint {
+        Paint::new()
+            .with_color(color)
+            .with_style(PaintStyle::Stroke)
+            .with_stroke_width(width.max(1.0))
+            .with_stroke_cap(StrokeCap::Round)
+            .with_stroke_join(StrokeJoin::Round)
+            .with_anti_alias(true)
     }
     
-    /// 绘制粗对勾（使用填充多边形）
-    fn draw_thick_check(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, thickness: f32) {
-        let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 对勾的三个关键点
-        let p1 = (cx - r * 0.35, cy);           // 左端点
-        let p2 = (cx - r * 0.05, cy + r * 0.3); // 拐点
-        let p3 = (cx + r * 0.35, cy - r * 0.25); // 右端点
-        
-        let half = thickness / 2.0;
-        
-        // 绘制第一段（左到中）
-        let angle1 = ((p2.1 - p1.1) / (p2.0 - p1.0)).atan();
-        let dx1 = half * angle1.sin();
-        let dy1 = half * angle1.cos();
-        
-        let mut seg1 = Path::new();
-        seg1.move_to(p1.0 - dx1, p1.1 + dy1);
-        seg1.line_to(p1.0 + dx1, p1.1 - dy1);
-        seg1.line_to(p2.0 + dx1, p2.1 - dy1);
-        seg1.line_to(p2.0 - dx1, p2.1 + dy1);
-        seg1.close();
-        canvas.draw_path(&seg1, &paint);
-        
-        // 绘制第二段（中到右）
-        let angle2 = ((p3.1 - p2.1) / (p3.0 - p2.0)).atan();
-        let dx2 = half * angle2.sin();
-        let dy2 = half * angle2.cos();
-        
-        let mut seg2 = Path::new();
-        seg2.move_to(p2.0 - dx2, p2.1 + dy2);
-        seg2.line_to(p2.0 + dx2, p2.1 - dy2);
-        seg2.line_to(p3.0 + dx2, p3.1 - dy2);
-        seg2.line_to(p3.0 - dx2, p3.1 + dy2);
-        seg2.close();
-        canvas.draw_path(&seg2, &paint);
-        
-        // 绘制拐点圆形，使连接更平滑
-        canvas.draw_circle(p2.0, p2.1, half, &paint);
-    }
-    
-    fn draw_info(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, _stroke_width: f32) {
-        let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 绘制圆形背景
-        canvas.draw_circle(cx, cy, r, &paint);
-        
-        // 绘制白色 "i"
-        let white = Paint::new().with_color(Color::WHITE).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 点
-        canvas.draw_circle(cx, cy - r * 0.35, r * 0.14, &white);
-        
-        // 竖线 - 圆角矩形
-        let bar_w = r * 0.18;
-        let bar_h = r * 0.55;
-        let bar_x = cx - bar_w / 2.0;
-        let bar_y = cy - r * 0.05;
-        
-        let mut bar = Path::new();
-        bar.add_round_rect(bar_x, bar_y, bar_w, bar_h, bar_w / 2.0);
-        canvas.draw_path(&bar, &white);
+    /// 对勾，r 为参照半径
+    fn stroke_check(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, width: f32) {
+        let mut check = Path::new();
+        check.move_to(cx - r * 0.4, cy + r * 0.02);
+        check.line_to(cx - r * 0.1, cy + r * 0.32);
+        check.line_to(cx + r * 0.42, cy - r * 0.28);
+        canvas.draw_path(&check, &Self::stroke(color, width));
     }
     
-    fn draw_warn(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, _stroke_width: f32) {
+    /// info 的 "i"（点在上）和 warn 的 "!"（点在下）
+    fn draw_bar_and_dot(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, dot_on_top: bool) {
         let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 绘制圆形背景
-        canvas.draw_circle(cx, cy, r, &paint);
-        
-        // 绘制白色 "!"
-        let white = Paint::new().with_color(Color::WHITE).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 竖线 - 圆角矩形
         let bar_w = r * 0.18;
         let bar_h = r * 0.55;
-        let bar_x = cx - bar_w / 2.0;
-        let bar_y = cy - r * 0.5;
+        let (bar_y, dot_y) = if dot_on_top { (cy - r * 0.05, cy - r * 0.35) } else { (cy - r * 0.5, cy + r * 0.38) };
         
         let mut bar = Path::new();
-        bar.add_round_rect(bar_x, bar_y, bar_w, bar_h, bar_w / 2.0);
-        canvas.draw_path(&bar, &white);
-        
-        // 点
-        canvas.draw_circle(cx, cy + r * 0.38, r * 0.14, &white);
-    }
-    
-    fn draw_waiting(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, stroke_width: f32) {
-        let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 绘制圆形背景
-        canvas.draw_circle(cx, cy, r, &paint);
-        
-        // 绘制白色时钟指针
-        let white = Paint::new().with_color(Color::WHITE).with_style(PaintStyle::Fill).with_anti_alias(true);
-        let line_w = stroke_width * 1.2;
-        
-        // 垂直指针（12点方向）
-        let mut v_line = Path::new();
-        v_line.add_round_rect(cx - line_w / 2.0, cy - r * 0.45, line_w, r * 0.45, line_w / 2.0);
-        canvas.draw_path(&v_line, &white);
-        
-        // 水平指针（3点方向）
-        let mut h_line = Path::new();
-        h_line.add_round_rect(cx, cy - line_w / 2.0, r * 0.32, line_w, line_w / 2.0);
-        canvas.draw_path(&h_line, &white);
-        
-        // 中心圆点
-        canvas.draw_circle(cx, cy, line_w * 0.8, &white);
-    }
-    
-    fn draw_cancel(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, stroke_width: f32) {
-        let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 绘制圆形背景
-        canvas.draw_circle(cx, cy, r, &paint);
-        
-        // 绘制白色 X
-        Self::draw_thick_x(canvas, cx, cy, r * 0.35, Color::WHITE, stroke_width * 1.3);
-    }
-    
-    /// 绘制粗 X
-    fn draw_thick_x(canvas: &mut Canvas, cx: f32, cy: f32, offset: f32, color: Color, thickness: f32) {
-        let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-        let half = thickness / 2.0;
-        
-        // 左上到右下
-        let mut line1 = Path::new();
-        line1.move_to(cx - offset - half, cy - offset);
-        line1.line_to(cx - offset, cy - offset - half);
-        line1.line_to(cx + offset + half, cy + offset);
-        line1.line_to(cx + offset, cy + offset + half);
-        line1.close();
-        canvas.draw_path(&line1, &paint);
-        
-        // 右上到左下
-        let mut line2 = Path::new();
-        line2.move_to(cx + offset, cy - offset - half);
-        line2.line_to(cx + offset + half, cy - offset);
-        line2.line_to(cx - offset, cy + offset + half);
-        line2.line_to(cx - offset - half, cy + offset);
-        line2.close();
-        canvas.draw_path(&line2, &paint);
-    }
-    
-    fn draw_download(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, stroke_width: f32) {
-        let stroke_paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 外圆
-        canvas.draw_circle(cx, cy, r, &stroke_paint);
-        
-        // 内圆（挖空）
-        let inner_r = r - stroke_width * 1.2;
-        let bg_paint = Paint::new().with_color(Color::WHITE).with_style(PaintStyle::Fill).with_anti_alias(true);
-        canvas.draw_circle(cx, cy, inner_r, &bg_paint);
-        
-        // 绘制下载箭头
-        let arrow_w = stroke_width * 1.2;
-        
-        // 垂直线
-        let mut v_line = Path::new();
-        v_line.add_round_rect(cx - arrow_w / 2.0, cy - r * 0.4, arrow_w, r * 0.5, arrow_w / 2.0);
-        canvas.draw_path(&v_line, &stroke_paint);
-        
-        // 箭头三角形
-        let mut arrow = Path::new();
-        arrow.move_to(cx, cy + r * 0.35);
-        arrow.line_to(cx - r * 0.28, cy);
-        arrow.line_to(cx + r * 0.28, cy);
-        arrow.close();
-        canvas.draw_path(&arrow, &stroke_paint);
-        
-        // 底部横线
-        let mut h_line = Path::new();
-        h_line.add_round_rect(cx - r * 0.35, cy + r * 0.45, r * 0.7, arrow_w, arrow_w / 2.0);
-        canvas.draw_path(&h_line, &stroke_paint);
-    }
-    
-    fn draw_search(canvas: &mut Canvas, cx: f32, cy: f32, r: f32, color: Color, stroke_width: f32) {
-        let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill).with_anti_alias(true);
-        
-        // 放大镜参数
-        let lens_r = r * 0.45;
-        let lens_cx = cx - r * 0.12;
-        let lens_cy = cy - r * 0.12;
-        let ring_width = stroke_width * 1.5;
-        
-        // 绘制放大镜圆环（外圆 - 内圆）
-        canvas.draw_circle(lens_cx, lens_cy, lens_r, &paint);
-        
-        let bg_paint = Paint::new().with_color(Color::WHITE).with_style(PaintStyle::Fill).with_anti_alias(true);
-        canvas.draw_circle(lens_cx, lens_cy, lens_r - ring_width, &bg_paint);
-        
-        // 绘制手柄
-        let handle_len = r * 0.45;
-        let handle_w = ring_width;
-        let angle = std::f32::consts::PI / 4.0; // 45度
-        
-        let start_x = lens_cx + lens_r * 0.7;
-        let start_y = lens_cy + lens_r * 0.7;
-        
-        let cos_a = angle.cos();
-        let sin_a = angle.sin();
-        let half_w = handle_w / 2.0;
-        
-        let mut handle = Path::new();
-        handle.move_to(start_x - half_w * cos_a, start_y + half_w * sin_a);
-        handle.line_to(start_x + half_w * cos_a, start_y - half_w * sin_a);
-        handle.line_to(start_x + handle_len * sin_a + half_w * cos_a, start_y + handle_len * cos_a - half_w * sin_a);
-        handle.line_to(start_x + handle_len * sin_a - half_w * cos_a, start_y + handle_len * cos_a + half_w * sin_a);
-        handle.close();
-        canvas.draw_path(&handle, &paint);
-        
-        // 手柄末端圆角
-        canvas.draw_circle(
-            start_x + handle_len * sin_a, 
-            start_y + handle_len * cos_a, 
-            half_w,
-            &paint
-        );
+        bar.add_round_rect(cx - bar_w / 2.0, bar_y, bar_w, bar_h, bar_w / 2.0);
+        canvas.draw_path(&bar, &paint);
+        canvas.draw_circle(cx, dot_y, r * 0.12, &paint);
     }
 }
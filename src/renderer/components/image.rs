@@ -2,13 +2,17 @@
 //! 
 //! 支持完整的 CSS 样式，同时保留微信默认样式作为 fallback
 //! 属性：
-//! - src: 图片资源地址（支持网络URL和本地路径）
+//! - src: 图片资源地址（支持网络URL和本地路径，网络图片在后台下载，完成前显示占位符）
+//!   解码结果放在全局 LRU 缓存中（按解析后的路径 / URL），超出容量时淘汰最久未使用的图片
+//!   支持 GIF 和 WebP（有损 / 无损 / 动画），动画图片按帧时长循环播放
 //! - mode: 图片裁剪、缩放模式
 //!   - scaleToFill: 缩放模式，不保持纵横比缩放图片
 //!   - aspectFit: 缩放模式，保持纵横比缩放图片，完整显示
 //!   - aspectFill: 缩放模式，保持纵横比缩放图片，只保证短边完全显示
 //!   - widthFix: 缩放模式，宽度不变，高度自动变化
 //!   - heightFix: 缩放模式，高度不变，宽度自动变化
+//!   - top / bottom / center / left / right / top left / top right / bottom left / bottom right:
+//!     裁剪模式，不缩放图片，只显示对应方位的区域
 //! - lazy-load: 懒加载
 //! - show-menu-by-longpress: 长按显示菜单
 //! 
@@ -28,59 +32,295 @@ use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect as GeoRect};
 use taffy::prelude::*;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use std::io::Read;
 
-/// 图片缓存数据
-struct ImageData {
-    data: Vec<u8>,  // RGBA 数据
-    width: u32,
-    height: u32,
+/// 解码后的图片数据，缓存淘汰后仍被引用的帧（正在绘制）不会被释放
+pub(crate) struct ImageData {
+    pub(crate) data: Vec<u8>,  // RGBA 数据（动画图片为第一帧）
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    /// 动画图片（GIF / 动画 WebP）第一帧之后的帧，每帧都是完整画布大小的 RGBA
+    pub(crate) frames: Vec<Vec<u8>>,
+    /// 每帧的显示时长（毫秒），静态图片为空
+    pub(crate) delays_ms: Vec<u32>,
+    /// 解码完成的时间，动画按经过的时间选帧
+    pub(crate) started: Instant,
 }
 
-/// 全局图片缓存
-static IMAGE_CACHE: OnceLock<Arc<Mutex<HashMap<String, Option<ImageData>>>>> = OnceLock::new();
+impl ImageData {
+    pub(crate) fn new(data: Vec<u8>, width: u32, height: u32) -> Self {
+        Self { data, width, height, frames: vec![], delays_ms: vec![], started: Instant::now() }
+    }
+
+    pub(crate) fn is_animated(&self) -> bool {
+        !self.frames.is_empty()
+    }
+
+    fn byte_size(&self) -> usize {
+        self.data.len() + self.frames.iter().map(|f| f.len()).sum::<usize>()
+    }
+
+    /// 动画开始后 elapsed_ms 毫秒时显示的帧（循环播放）
+    pub(crate) fn frame_at(&self, elapsed_ms: u64) -> &[u8] {
+        let total: u64 = self.delays_ms.iter().map(|&d| d as u64).sum();
+        if !self.is_animated() || total == 0 {
+            return &self.data;
+        }
+        let mut t = elapsed_ms % total;
+        for (i, &delay) in self.delays_ms.iter().enumerate() {
+            if t < delay as u64 {
+                return if i == 0 { &self.data } else { &self.frames[i - 1] };
+            }
+            t -= delay as u64;
+        }
+        &self.data
+    }
 
-fn get_image_cache() -> &'static Arc<Mutex<HashMap<String, Option<ImageData>>>> {
-    IMAGE_CACHE.get_or_init(|| Arc::new(Mutex::new(HashMap::new())))
+    /// 当前应显示的帧，动画图片会记录绘制时间让窗口继续刷新
+    pub(crate) fn current_frame(&self) -> &[u8] {
+        if self.is_animated() {
+            if let Ok(mut drawn_at) = ANIMATION_DRAWN_AT.lock() {
+                *drawn_at = Some(Instant::now());
+            }
+        }
+        self.frame_at(self.started.elapsed().as_millis() as u64)
+    }
 }
 
-/// 加载图片（支持网络URL和本地文件）
-fn load_image(src: &str) -> Option<ImageData> {
-    // 检查缓存
-    {
-        let cache = get_image_cache();
-        let cache_guard = cache.lock().ok()?;
-        if let Some(cached) = cache_guard.get(src) {
-            return cached.as_ref().map(|d| ImageData {
-                data: d.data.clone(),
-                width: d.width,
-                height: d.height,
-            });
+/// 缓存项：网络图片在后台线程下载期间为 Loading
+#[derive(Clone)]
+pub(crate) enum ImageEntry {
+    Loading,
+    Ready(Arc<ImageData>),
+    Failed,
+}
+
+/// 默认的图片缓存容量（解码后的 RGBA 字节数）
+const DEFAULT_CACHE_BUDGET: usize = 64 * 1024 * 1024;
+
+/// 图片缓存统计
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ImageCacheStats {
+    /// 已解码的图片数量
+    pub entries: usize,
+    /// 已解码图片占用的字节数
+    pub bytes: usize,
+    /// 容量上限（字节）
+    pub budget: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// 因超出容量被淘汰的图片数量
+    pub evictions: u64,
+}
+
+struct CacheSlot {
+    entry: ImageEntry,
+    last_used: u64,
+}
+
+/// 按解析后的路径 / URL 缓存解码结果，超出容量时淘汰最久未使用的图片
+pub(crate) struct ImageCache {
+    slots: HashMap<String, CacheSlot>,
+    /// src 属性 -> 解析后的缓存键（本地路径会尝试多个目录）
+    aliases: HashMap<String, String>,
+    budget: usize,
+    bytes: usize,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl ImageCache {
+    pub(crate) fn new(budget: usize) -> Self {
+        Self {
+            slots: HashMap::new(),
+            aliases: HashMap::new(),
+            budget,
+            bytes: 0,
+            clock: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
         }
     }
 
-    let result = if src.starts_with("http://") || src.starts_with("https://") {
-        load_image_from_url(src)
-    } else {
-        load_image_from_file(src)
-    };
+    /// 查找缓存项并标记为最近使用
+    pub(crate) fn get(&mut self, key: &str) -> Option<ImageEntry> {
+        self.clock += 1;
+        match self.slots.get_mut(key) {
+            Some(slot) => {
+                slot.last_used = self.clock;
+                if matches!(slot.entry, ImageEntry::Ready(_)) {
+                    self.hits += 1;
+                }
+                Some(slot.entry.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn insert(&mut self, key: String, entry: ImageEntry) {
+        self.clock += 1;
+        let size = Self::entry_bytes(&entry);
+        let old = self.slots.insert(key.clone(), CacheSlot { entry, last_used: self.clock });
+        if let Some(old) = old {
+            self.bytes -= Self::entry_bytes(&old.entry);
+        }
+        self.bytes += size;
+        self.evict(&key);
+    }
+
+    pub(crate) fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict("");
+    }
+
+    pub(crate) fn is_loading(&self) -> bool {
+        self.slots.values().any(|s| matches!(s.entry, ImageEntry::Loading))
+    }
+
+    pub(crate) fn stats(&self) -> ImageCacheStats {
+        ImageCacheStats {
+            entries: self.slots.values().filter(|s| matches!(s.entry, ImageEntry::Ready(_))).count(),
+            bytes: self.bytes,
+            budget: self.budget,
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+        }
+    }
+
+    fn entry_bytes(entry: &ImageEntry) -> usize {
+        match entry {
+            ImageEntry::Ready(img) => img.byte_size(),
+            _ => 0,
+        }
+    }
 
-    // 存入缓存
-    {
-        let cache = get_image_cache();
-        if let Ok(mut cache_guard) = cache.lock() {
-            cache_guard.insert(src.to_string(), result.as_ref().map(|d| ImageData {
-                data: d.data.clone(),
-                width: d.width,
-                height: d.height,
-            }));
+    /// 超出容量时按最近使用时间淘汰，keep 为刚插入的图片（单张超出容量时仍然保留）
+    fn evict(&mut self, keep: &str) {
+        while self.bytes > self.budget {
+            let victim = self.slots.iter()
+                .filter(|(k, s)| k.as_str() != keep && matches!(s.entry, ImageEntry::Ready(_)))
+                .min_by_key(|(_, s)| s.last_used)
+                .map(|(k, _)| k.clone());
+            let Some(victim) = victim else { break };
+            if let Some(slot) = self.slots.remove(&victim) {
+                self.bytes -= Self::entry_bytes(&slot.entry);
+            }
+            self.aliases.retain(|_, k| *k != victim);
+            self.evictions += 1;
         }
     }
+}
+
+/// 全局图片缓存，所有 image 组件共享
+static IMAGE_CACHE: OnceLock<Mutex<ImageCache>> = OnceLock::new();
+
+/// 上次检查之后是否有网络图片下载完成
+static IMAGES_LOADED: AtomicBool = AtomicBool::new(false);
+
+/// 最近一次绘制动画图片的时间
+static ANIMATION_DRAWN_AT: Mutex<Option<Instant>> = Mutex::new(None);
+
+fn get_image_cache() -> &'static Mutex<ImageCache> {
+    IMAGE_CACHE.get_or_init(|| Mutex::new(ImageCache::new(DEFAULT_CACHE_BUDGET)))
+}
+
+/// 全局图片缓存的统计信息
+pub fn cache_stats() -> ImageCacheStats {
+    get_image_cache().lock().map(|cache| cache.stats()).unwrap_or_default()
+}
+
+/// 设置全局图片缓存的容量（字节），超出部分立即淘汰
+pub fn set_cache_budget(bytes: usize) {
+    if let Ok(mut cache) = get_image_cache().lock() {
+        cache.set_budget(bytes);
+    }
+}
 
+/// 加载图片：首次使用时才解码，本地文件同步加载，网络 URL 交给后台线程下载，下载完成前返回 None
+pub(crate) fn load_image(src: &str) -> Option<Arc<ImageData>> {
+    let is_remote = src.starts_with("http://") || src.starts_with("https://");
+    let key = {
+        let mut cache = get_image_cache().lock().ok()?;
+        let key = match cache.aliases.get(src) {
+            Some(key) => key.clone(),
+            None => {
+                let key = if is_remote {
+                    src.to_string()
+                } else {
+                    resolve_local_path(src).unwrap_or_else(|| src.to_string())
+                };
+                cache.aliases.insert(src.to_string(), key.clone());
+                key
+            }
+        };
+        match cache.get(&key) {
+            Some(ImageEntry::Ready(img)) => return Some(img),
+            Some(_) => return None,
+            None if is_remote => cache.insert(key.clone(), ImageEntry::Loading),
+            None => {}
+        }
+        key
+    };
+
+    if is_remote {
+        std::thread::spawn(move || {
+            let entry = match load_image_from_url(&key) {
+                Some(img) => ImageEntry::Ready(Arc::new(img)),
+                None => {
+                    log::warn!("⚠️ Failed to load image: {}", key);
+                    ImageEntry::Failed
+                }
+            };
+            if let Ok(mut cache) = get_image_cache().lock() {
+                cache.insert(key, entry);
+            }
+            IMAGES_LOADED.store(true, Ordering::SeqCst);
+        });
+        return None;
+    }
+
+    let result = std::fs::read(&key).ok().and_then(|bytes| decode_image_bytes(&bytes)).map(Arc::new);
+    if let Ok(mut cache) = get_image_cache().lock() {
+        cache.insert(key, match &result {
+            Some(img) => ImageEntry::Ready(img.clone()),
+            None => ImageEntry::Failed,
+        });
+    }
     result
 }
 
-/// 从网络URL加载图片
+/// 获取图片的 RGBA 数据和尺寸，网络图片下载完成前返回 None（与 get_video_frame 对应）
+pub fn get_image_rgba(src: &str) -> Option<(Vec<u8>, u32, u32)> {
+    load_image(src).map(|img| (img.current_frame().to_vec(), img.width, img.height))
+}
+
+/// 是否有动画图片正在播放（最近绘制过），窗口需要持续刷新（与 has_playing_video 对应）
+pub fn has_playing_animation() -> bool {
+    ANIMATION_DRAWN_AT.lock()
+        .map(|t| t.map(|t| t.elapsed() < Duration::from_millis(500)).unwrap_or(false))
+        .unwrap_or(false)
+}
+
+/// 是否有网络图片正在下载
+pub fn has_loading_images() -> bool {
+    get_image_cache().lock().map(|cache| cache.is_loading()).unwrap_or(false)
+}
+
+/// 上次调用之后是否有网络图片下载完成（需要重绘）
+pub fn take_images_loaded() -> bool {
+    IMAGES_LOADED.swap(false, Ordering::SeqCst)
+}
+
+/// 从网络URL加载图片（阻塞，在后台线程中调用）
 fn load_image_from_url(url: &str) -> Option<ImageData> {
     // 使用 ureq 下载图片
     let response = ureq::get(url)
@@ -94,40 +334,90 @@ fn load_image_from_url(url: &str) -> Option<ImageData> {
     decode_image_bytes(&bytes)
 }
 
-/// 从本地文件加载图片
-fn load_image_from_file(path: &str) -> Option<ImageData> {
-    // 尝试多个可能的路径
-    let paths_to_try = vec![
+/// 解析本地图片路径（依次尝试多个目录），返回第一个存在的文件
+pub(crate) fn resolve_local_path(path: &str) -> Option<String> {
+    let paths_to_try = [
         path.to_string(),
         format!("sample-app{}", path),
         format!("sample-app/{}", path.trim_start_matches('/')),
         format!("assets{}", path),
         format!("assets/{}", path.trim_start_matches('/')),
     ];
+    paths_to_try.into_iter().find(|p| std::path::Path::new(p).is_file())
+}
 
-    for p in paths_to_try {
-        if let Ok(bytes) = std::fs::read(&p) {
-            if let Some(img) = decode_image_bytes(&bytes) {
+/// 解码图片字节数据，GIF 和动画 WebP 解码出所有帧
+pub(crate) fn decode_image_bytes(bytes: &[u8]) -> Option<ImageData> {
+    use image::GenericImageView;
+    
+    match image::guess_format(bytes) {
+        Ok(image::ImageFormat::Gif) => {
+            if let Some(img) = decode_gif_frames(bytes) {
+                return Some(img);
+            }
+        }
+        Ok(image::ImageFormat::WebP) => {
+            if let Some(img) = decode_webp_frames(bytes) {
                 return Some(img);
             }
         }
+        _ => {}
     }
-    None
-}
-
-/// 解码图片字节数据
-fn decode_image_bytes(bytes: &[u8]) -> Option<ImageData> {
-    use image::GenericImageView;
     
+    // 静态图片（包括 VP8 / VP8L 无损 WebP）
     let img = image::load_from_memory(bytes).ok()?;
     let rgba = img.to_rgba8();
     let (width, height) = img.dimensions();
     
-    Some(ImageData {
-        data: rgba.into_raw(),
-        width,
-        height,
-    })
+    Some(ImageData::new(rgba.into_raw(), width, height))
+}
+
+fn decode_gif_frames(bytes: &[u8]) -> Option<ImageData> {
+    use image::AnimationDecoder;
+    
+    let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(bytes)).ok()?;
+    frames_to_image(decoder.into_frames().collect_frames().ok()?)
+}
+
+fn decode_webp_frames(bytes: &[u8]) -> Option<ImageData> {
+    use image::AnimationDecoder;
+    
+    let decoder = image::codecs::webp::WebPDecoder::new(std::io::Cursor::new(bytes)).ok()?;
+    if !decoder.has_animation() {
+        return None;
+    }
+    frames_to_image(decoder.into_frames().collect_frames().ok()?)
+}
+
+/// 把解码出的帧合成 ImageData，只有一帧时按静态图片处理
+fn frames_to_image(frames: Vec<image::Frame>) -> Option<ImageData> {
+    let mut frames = frames.into_iter();
+    let first = frames.next()?;
+    let mut delays_ms = vec![frame_delay_ms(&first)];
+    let first = first.into_buffer();
+    let (width, height) = first.dimensions();
+    let mut img = ImageData::new(first.into_raw(), width, height);
+    
+    for frame in frames {
+        let delay = frame_delay_ms(&frame);
+        let buffer = frame.into_buffer();
+        if buffer.dimensions() != (width, height) {
+            continue;
+        }
+        delays_ms.push(delay);
+        img.frames.push(buffer.into_raw());
+    }
+    if img.is_animated() {
+        img.delays_ms = delays_ms;
+    }
+    Some(img)
+}
+
+/// 帧时长，和浏览器一样把过短的延迟（常见为 0）当作 100ms
+fn frame_delay_ms(frame: &image::Frame) -> u32 {
+    let (numer, denom) = frame.delay().numer_denom_ms();
+    let ms = numer.checked_div(denom).unwrap_or(0);
+    if ms < 20 { 100 } else { ms }
 }
 
 pub struct ImageComponent;
@@ -163,6 +453,21 @@ impl ImageComponent {
             ts.size.height = length(default_height * sf);
         }
         
+        // widthFix / heightFix：按图片宽高比由宽度推导高度（或反之），
+        // 网络图片下载完成前使用默认尺寸，下载完成后重新布局
+        if matches!(mode, "widthFix" | "heightFix") && !src.is_empty() {
+            if let Some(img) = load_image(src) {
+                if img.width > 0 && img.height > 0 {
+                    ts.aspect_ratio = Some(img.width as f32 / img.height as f32);
+                    if mode == "widthFix" {
+                        ts.size.height = Dimension::Auto;
+                    } else {
+                        ts.size.width = Dimension::Auto;
+                    }
+                }
+            }
+        }
+        
         // 只在 CSS 没有定义时使用默认占位符背景
         if !has_custom_bg {
             ns.background_color = Some(Color::from_hex(0xF5F5F5));
@@ -189,6 +494,12 @@ impl ImageComponent {
         })
     }
     
+    /// 节点的图片是否是已加载的动画图片（GIF / 动画 WebP），绘制时会随时间换帧
+    pub fn is_playing_animation(node: &RenderNode) -> bool {
+        let src = node.text.split('|').next().unwrap_or("");
+        !src.is_empty() && load_image(src).is_some_and(|img| img.is_animated())
+    }
+    
     pub fn draw(
         node: &RenderNode, 
         canvas: &mut Canvas, 
@@ -197,7 +508,7 @@ impl ImageComponent {
         y: f32, 
         w: f32, 
         h: f32, 
-        _sf: f32
+        sf: f32
     ) {
         let style = &node.style;
         
@@ -253,12 +564,13 @@ impl ImageComponent {
             if let Some(img_data) = load_image(src) {
                 // 绘制图片（透明度通过背景色已经处理）
                 canvas.draw_image(
-                    &img_data.data,
+                    img_data.current_frame(),
                     img_data.width,
                     img_data.height,
                     x, y, w, h,
                     mode,
                     radius,
+                    sf,
                 );
                 
                 // 绘制边框
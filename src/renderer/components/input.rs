@@ -7,7 +7,7 @@
 //! | 属性 | 类型 | 默认值 | 说明 |
 //! |------|------|--------|------|
 //! | value | string | | 输入框的初始内容 |
-//! | type | string | text | 输入类型：text/number/idcard/digit/safe-password/nickname |
+//! | type | string | text | 输入类型：text/number/idcard/digit/password/safe-password/nickname |
 //! | password | boolean | false | 是否是密码类型 |
 //! | placeholder | string | | 输入框为空时占位符 |
 //! | placeholder-style | string | | 占位符的样式（仅支持 color） |
@@ -15,13 +15,17 @@
 //! | disabled | boolean | false | 是否禁用 |
 //! | maxlength | number | 140 | 最大输入长度，-1 为不限制 |
 //! | cursor-spacing | number | 0 | 光标与键盘的距离（px） |
-//! | focus | boolean | false | 获取焦点 |
-//! | confirm-type | string | done | 键盘右下角按钮文字：send/search/next/go/done |
+//! | focus | boolean | false | 获取焦点，变为 false 时失焦 |
+//! | confirm-type | string | done | 键盘右下角按钮文字：send/search/next/go/done，textarea 默认 return |
 //! | confirm-hold | boolean | false | 点击键盘确认按钮时是否保持键盘不收起 |
 //! | cursor | number | | 指定 focus 时的光标位置 |
 //! | selection-start | number | -1 | 光标起始位置，需与 selection-end 搭配使用 |
 //! | selection-end | number | -1 | 光标结束位置，需与 selection-start 搭配使用 |
 //! | adjust-position | boolean | true | 键盘弹起时，是否自动上推页面 |
+//! | auto-height | boolean | false | textarea 是否随内容自动增高，设置后 style.height 不生效 |
+//! 
+//! textarea 按宽度自动换行，回车插入换行符，上下键在视觉行之间移动光标。
+//! 单行输入框（以及 confirm-type 不为 return 的 textarea）回车触发 bindconfirm，未设置 confirm-hold 时随后失焦。
 //! 
 //! ## 事件
 //! 
@@ -46,6 +50,7 @@
 use super::base::*;
 use crate::parser::wxml::WxmlNode;
 use crate::text::TextRenderer;
+use crate::ui::interaction::TextLine;
 use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect as GeoRect};
 use taffy::prelude::*;
 use std::time::Instant;
@@ -65,7 +70,7 @@ fn get_start_time() -> &'static Instant {
 }
 
 /// 判断光标是否应该显示（闪烁效果）
-fn should_show_cursor() -> bool {
+pub fn should_show_cursor() -> bool {
     let elapsed = get_start_time().elapsed().as_millis() as u64;
     // 每个周期的前半段显示光标
     (elapsed / CURSOR_BLINK_INTERVAL_MS) % 2 == 0
@@ -88,7 +93,7 @@ impl InputType {
             "number" => Self::Number,
             "idcard" => Self::IdCard,
             "digit" => Self::Digit,
-            "safe-password" => Self::SafePassword,
+            "safe-password" | "password" => Self::SafePassword,
             "nickname" => Self::Nickname,
             _ => Self::Text,
         }
@@ -292,11 +297,22 @@ impl InputComponent {
             ns.font_size = 16.0;
         }
         
+        // auto-height 先按屏幕宽度估算行数，布局后由渲染器按实际宽度和内容调整
+        if is_textarea && is_auto_height(&node.attributes) {
+            let width = match ts.size.width {
+                Dimension::Length(w) => w,
+                _ => ctx.screen_width * sf,
+            };
+            ts.size.height = length(Self::auto_height(value, &ns, width, sf));
+            ts.min_size.height = Dimension::Auto;
+            ts.max_size.height = Dimension::Auto;
+        }
+        
         // 显示文本
         let display_text = if value.is_empty() {
             placeholder.to_string()
         } else if password {
-            "•".repeat(value.chars().count())
+            mask_password(value)
         } else {
             value.to_string()
         };
@@ -355,7 +371,7 @@ impl InputComponent {
         focused: bool,
         cursor_pos: usize,
     ) {
-        Self::draw_with_selection(node, canvas, text_renderer, x, y, w, h, sf, focused, cursor_pos, None);
+        Self::draw_with_selection(node, canvas, text_renderer, x, y, w, h, sf, focused, cursor_pos, None, "");
     }
     
     pub fn draw_with_selection(
@@ -370,67 +386,27 @@ impl InputComponent {
         focused: bool,
         cursor_pos: usize,
         selection: Option<(usize, usize)>,
+        preedit: &str,
     ) {
-        let style = &node.style;
-        
-        // 获取圆角值（支持四角独立设置）
-        let radius_tl = style.border_radius_tl.unwrap_or(style.border_radius);
-        let radius_tr = style.border_radius_tr.unwrap_or(style.border_radius);
-        let radius_br = style.border_radius_br.unwrap_or(style.border_radius);
-        let radius_bl = style.border_radius_bl.unwrap_or(style.border_radius);
-        let has_radius = radius_tl > 0.0 || radius_tr > 0.0 || radius_br > 0.0 || radius_bl > 0.0;
-        let uniform_radius = radius_tl == radius_tr && radius_tr == radius_br && radius_br == radius_bl;
-        
-        // 绘制盒子阴影
-        if let Some(shadow) = &style.box_shadow {
-            draw_box_shadow(canvas, shadow, x, y, w, h, style.border_radius);
-        }
-        
-        // 绘制背景
-        if let Some(bg) = style.background_color {
-            // 应用透明度
-            let bg = if style.opacity < 1.0 {
-                Color::new(bg.r, bg.g, bg.b, (bg.a as f32 * style.opacity) as u8)
-            } else {
-                bg
-            };
-            
-            let paint = Paint::new().with_color(bg).with_style(PaintStyle::Fill);
-            if has_radius {
-                let mut path = Path::new();
-                if uniform_radius {
-                    path.add_round_rect(x, y, w, h, radius_tl);
-                } else {
-                    path.add_round_rect_varying(x, y, w, h, radius_tl, radius_tr, radius_br, radius_bl);
-                }
-                canvas.draw_path(&path, &paint);
-            } else {
-                canvas.draw_rect(&GeoRect::new(x, y, w, h), &paint);
-            }
-        }
-        
-        // 绘制边框 - 聚焦时高亮（除非有自定义边框颜色）
-        let border_color = if focused {
-            Color::from_hex(0x07C160) // 微信绿色
-        } else {
-            style.border_color.unwrap_or(Color::from_hex(0xD9D9D9))
+        // 输入法组合文本插入到光标处显示，光标移到组合文本之后，组合期间不显示选中
+        let composed = compose_preedit(node, cursor_pos, preedit);
+        let (node, cursor_pos, selection, preedit_range) = match &composed {
+            Some((composed, range)) => (composed, range.1, None, Some(*range)),
+            None => (node, cursor_pos, selection, None),
         };
         
-        if style.border_width > 0.0 {
-            let paint = Paint::new().with_color(border_color).with_style(PaintStyle::Stroke);
-            if has_radius {
-                let mut path = Path::new();
-                if uniform_radius {
-                    path.add_round_rect(x, y, w, h, radius_tl);
-                } else {
-                    path.add_round_rect_varying(x, y, w, h, radius_tl, radius_tr, radius_br, radius_bl);
-                }
-                canvas.draw_path(&path, &paint);
-            } else {
-                canvas.draw_rect(&GeoRect::new(x, y, w, h), &paint);
+        if node.tag == "textarea" {
+            if let Some(tr) = text_renderer {
+                let layout = Self::layout_textarea(node, tr, w, h, sf, cursor_pos, 0.0);
+                let state = TextareaState { focused, cursor_pos, selection, preedit: preedit_range };
+                Self::draw_textarea(node, canvas, tr, (x, y, w, h), sf, &state, &layout);
+                return;
             }
         }
         
+        Self::draw_frame(node, canvas, x, y, w, h, focused);
+        let style = &node.style;
+        
         // 计算文本位置
         let font_size = style.font_size * sf;
         let padding_left = 12.0 * sf; // 默认左边距
@@ -506,6 +482,14 @@ impl InputComponent {
                 };
                 
                 tr.draw_text(canvas, &node.text, final_x, text_y, font_size, &paint);
+                
+                // 组合文本下方画下划线
+                if let Some((start, end)) = preedit_range {
+                    let prefix: String = node.text.chars().take(start).collect();
+                    let composing: String = node.text.chars().skip(start).take(end - start).collect();
+                    let x0 = final_x + tr.measure_text(&prefix, font_size);
+                    draw_preedit_underline(canvas, x0, x0 + tr.measure_text(&composing, font_size), text_y + 2.0 * sf, sf, color);
+                }
             }
             
             // 绘制光标（只在没有选中或选中范围为空时显示，带闪烁效果）
@@ -530,6 +514,255 @@ impl InputComponent {
             canvas.restore();
         }
     }
+    
+    /// 绘制阴影、背景和边框（聚焦时边框高亮）
+    fn draw_frame(node: &RenderNode, canvas: &mut Canvas, x: f32, y: f32, w: f32, h: f32, focused: bool) {
+        let style = &node.style;
+        
+        // 获取圆角值（支持四角独立设置）
+        let radius_tl = style.border_radius_tl.unwrap_or(style.border_radius);
+        let radius_tr = style.border_radius_tr.unwrap_or(style.border_radius);
+        let radius_br = style.border_radius_br.unwrap_or(style.border_radius);
+        let radius_bl = style.border_radius_bl.unwrap_or(style.border_radius);
+        let has_radius = radius_tl > 0.0 || radius_tr > 0.0 || radius_br > 0.0 || radius_bl > 0.0;
+        let uniform_radius = radius_tl == radius_tr && radius_tr == radius_br && radius_br == radius_bl;
+        
+        // 绘制盒子阴影
+        if let Some(shadow) = &style.box_shadow {
+            draw_box_shadow(canvas, shadow, x, y, w, h, style.border_radius);
+        }
+        
+        // 绘制背景
+        if let Some(bg) = style.background_color {
+            // 应用透明度
+            let bg = if style.opacity < 1.0 {
+                Color::new(bg.r, bg.g, bg.b, (bg.a as f32 * style.opacity) as u8)
+            } else {
+                bg
+            };
+            
+            let paint = Paint::new().with_color(bg).with_style(PaintStyle::Fill);
+            if has_radius {
+                let mut path = Path::new();
+                if uniform_radius {
+                    path.add_round_rect(x, y, w, h, radius_tl);
+                } else {
+                    path.add_round_rect_varying(x, y, w, h, radius_tl, radius_tr, radius_br, radius_bl);
+                }
+                canvas.draw_path(&path, &paint);
+            } else {
+                canvas.draw_rect(&GeoRect::new(x, y, w, h), &paint);
+            }
+        }
+        
+        if let Some(shadow) = &style.box_shadow {
+            draw_inset_box_shadow(canvas, shadow, x, y, w, h, [radius_tl, radius_tr, radius_br, radius_bl]);
+        }
+        
+        // 绘制边框 - 聚焦时高亮（除非有自定义边框颜色）
+        let border_color = if focused {
+            Color::from_hex(0x07C160) // 微信绿色
+        } else {
+            style.border_color.unwrap_or(Color::from_hex(0xD9D9D9))
+        };
+        
+        if style.border_width > 0.0 {
+            let paint = Paint::new().with_color(border_color).with_style(PaintStyle::Stroke);
+            if has_radius {
+                let mut path = Path::new();
+                if uniform_radius {
+                    path.add_round_rect(x, y, w, h, radius_tl);
+                } else {
+                    path.add_round_rect_varying(x, y, w, h, radius_tl, radius_tr, radius_br, radius_bl);
+                }
+                canvas.draw_path(&path, &paint);
+            } else {
+                canvas.draw_rect(&GeoRect::new(x, y, w, h), &paint);
+            }
+        }
+        
+    }
+    
+    /// textarea 的行高（物理像素）
+    fn textarea_line_height(style: &NodeStyle, sf: f32) -> f32 {
+        style.line_height.unwrap_or(style.font_size * 1.5) * sf
+    }
+    
+    /// auto-height 的 textarea 在 width（物理像素）下容纳 text 需要的高度，至少一行
+    pub fn auto_height(text: &str, style: &NodeStyle, width: f32, sf: f32) -> f32 {
+        let line_height = Self::textarea_line_height(style, sf);
+        let lines = TextRenderer::shared()
+            .map(|tr| wrap_lines(&tr, text, style.font_size * sf, width - 24.0 * sf).len())
+            .unwrap_or_else(|| text.split('\n').count());
+        lines.max(1) as f32 * line_height + 16.0 * sf
+    }
+    
+    /// textarea 排版：按宽度换行，并调整纵向滚动使光标所在行可见
+    pub fn layout_textarea(
+        node: &RenderNode,
+        tr: &TextRenderer,
+        w: f32,
+        h: f32,
+        sf: f32,
+        cursor_pos: usize,
+        scroll_top: f32,
+    ) -> TextareaLayout {
+        let line_height = Self::textarea_line_height(&node.style, sf);
+        let lines = wrap_lines(tr, &node.text, node.style.font_size * sf, w - 24.0 * sf);
+        let visible = (h - 16.0 * sf).max(line_height);
+        let content = lines.len() as f32 * line_height;
+        let row = lines.iter().rposition(|l| l.start <= cursor_pos).unwrap_or(0) as f32;
+        let mut scroll_top = scroll_top.clamp(0.0, (content - visible).max(0.0));
+        if row * line_height < scroll_top {
+            scroll_top = row * line_height;
+        } else if (row + 1.0) * line_height > scroll_top + visible {
+            scroll_top = (row + 1.0) * line_height - visible;
+        }
+        TextareaLayout { lines, line_height, scroll_top }
+    }
+    
+    /// 绘制多行输入框：逐行绘制文本，选中背景按行绘制，光标显示在所在行
+    pub fn draw_textarea(
+        node: &RenderNode,
+        canvas: &mut Canvas,
+        tr: &TextRenderer,
+        (x, y, w, h): (f32, f32, f32, f32),
+        sf: f32,
+        state: &TextareaState,
+        layout: &TextareaLayout,
+    ) {
+        let TextareaState { focused, cursor_pos, selection, preedit } = *state;
+        Self::draw_frame(node, canvas, x, y, w, h, focused);
+        
+        let style = &node.style;
+        let font_size = style.font_size * sf;
+        let line_height = layout.line_height;
+        let text_x = x + 12.0 * sf;
+        let text_top = y + 8.0 * sf - layout.scroll_top;
+        let available_width = w - 24.0 * sf;
+        
+        canvas.save();
+        canvas.clip_rect(GeoRect::new(text_x, y + 8.0 * sf, available_width, h - 16.0 * sf));
+        
+        let chars: Vec<char> = node.text.chars().collect();
+        let color = style.text_color.unwrap_or(Color::BLACK);
+        let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill);
+        let sel_paint = Paint::new()
+            .with_color(Color::new(7, 193, 96, 80)) // 半透明绿色
+            .with_style(PaintStyle::Fill);
+        let (ascent, descent) = tr.vertical_metrics(font_size);
+        
+        for (row, line) in layout.lines.iter().enumerate() {
+            let line_y = text_top + row as f32 * line_height;
+            if line_y + line_height < y || line_y > y + h {
+                continue;
+            }
+            
+            // 根据 text-align 调整行的起点
+            let line_width = line.x_of(line.end());
+            let line_x = match style.text_align {
+                TextAlign::Center => text_x + (available_width - line_width).max(0.0) / 2.0,
+                TextAlign::Right => text_x + (available_width - line_width).max(0.0),
+                _ => text_x,
+            };
+            
+            if let Some((sel_start, sel_end)) = selection.filter(|_| focused) {
+                let (s, e) = (sel_start.max(line.start), sel_end.min(line.end()));
+                // 跨行选中时行尾的换行符也显示为选中
+                let newline = sel_end > line.end() && line.end() < chars.len() && chars[line.end()] == '\n';
+                if s < e || (newline && s <= e) {
+                    let x0 = line.x_of(s);
+                    let x1 = line.x_of(e) + if newline { font_size * 0.3 } else { 0.0 };
+                    canvas.draw_rect(&GeoRect::new(line_x + x0, line_y, x1 - x0, line_height), &sel_paint);
+                }
+            }
+            
+            let text: String = chars[line.start..line.end()].iter().collect();
+            let baseline = line_y + (line_height + ascent - descent) / 2.0;
+            if !text.is_empty() {
+                tr.draw_text(canvas, &text, line_x, baseline, font_size, &paint);
+            }
+            
+            // 组合文本跨行时每行分别画下划线
+            if let Some((start, end)) = preedit {
+                let (s, e) = (start.max(line.start), end.min(line.end()));
+                if s < e {
+                    draw_preedit_underline(canvas, line_x + line.x_of(s), line_x + line.x_of(e), baseline + 2.0 * sf, sf, color);
+                }
+            }
+        }
+        
+        // 绘制光标（没有选中时显示，带闪烁效果）
+        if focused && selection.map(|(s, e)| s == e).unwrap_or(true) && should_show_cursor() {
+            let row = layout.lines.iter().rposition(|l| l.start <= cursor_pos).unwrap_or(0);
+            if let Some(line) = layout.lines.get(row) {
+                let line_width = line.x_of(line.end());
+                let line_x = match style.text_align {
+                    TextAlign::Center => text_x + (available_width - line_width).max(0.0) / 2.0,
+                    TextAlign::Right => text_x + (available_width - line_width).max(0.0),
+                    _ => text_x,
+                };
+                let cursor_x = line_x + line.x_of(cursor_pos);
+                let cursor_y1 = text_top + row as f32 * line_height + (line_height - font_size) / 2.0;
+                
+                let cursor_paint = Paint::new()
+                    .with_color(Color::from_hex(0x07C160))
+                    .with_style(PaintStyle::Stroke);
+                let mut cursor_path = Path::new();
+                cursor_path.move_to(cursor_x, cursor_y1);
+                cursor_path.line_to(cursor_x, cursor_y1 + font_size);
+                canvas.draw_path(&cursor_path, &cursor_paint);
+            }
+        }
+        
+        canvas.restore();
+    }
+}
+
+/// textarea 的排版结果（物理像素）
+pub struct TextareaLayout {
+    pub lines: Vec<TextLine>,
+    pub line_height: f32,
+    pub scroll_top: f32,
+}
+
+/// textarea 的编辑状态：聚焦、光标、选中范围和输入法组合文本范围（字符索引）
+#[derive(Clone, Copy)]
+pub struct TextareaState {
+    pub focused: bool,
+    pub cursor_pos: usize,
+    pub selection: Option<(usize, usize)>,
+    pub preedit: Option<(usize, usize)>,
+}
+
+/// 把输入法组合文本插入到光标处，返回插入后的节点和组合文本的字符范围；没有组合文本时返回 None
+pub fn compose_preedit(node: &RenderNode, cursor_pos: usize, preedit: &str) -> Option<(RenderNode, (usize, usize))> {
+    if preedit.is_empty() {
+        return None;
+    }
+    let mut chars: Vec<char> = node.text.chars().collect();
+    let start = cursor_pos.min(chars.len());
+    let count = preedit.chars().count();
+    chars.splice(start..start, preedit.chars());
+    let mut composed = node.clone();
+    composed.text = chars.into_iter().collect();
+    Some((composed, (start, start + count)))
+}
+
+/// 组合文本的下划线
+fn draw_preedit_underline(canvas: &mut Canvas, x0: f32, x1: f32, y: f32, sf: f32, color: Color) {
+    let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill);
+    canvas.draw_rect(&GeoRect::new(x0, y, x1 - x0, sf.max(1.0)), &paint);
+}
+
+/// textarea 按内容宽度换行
+pub fn wrap_lines(tr: &TextRenderer, text: &str, font_size: f32, max_width: f32) -> Vec<TextLine> {
+    TextLine::wrap(text, max_width, |ch| tr.measure_char(ch, font_size))
+}
+
+/// textarea 是否设置了 auto-height
+pub fn is_auto_height(attrs: &std::collections::HashMap<String, String>) -> bool {
+    attrs.get("auto-height").map(|s| s.is_empty() || s == "true" || s == "{{true}}").unwrap_or(false)
 }
 
 /// 获取输入框的 maxlength 属性
@@ -552,6 +785,19 @@ pub fn is_password(attrs: &std::collections::HashMap<String, String>) -> bool {
         || get_input_type(attrs) == InputType::SafePassword
 }
 
+/// 输入框显示的文本：密码框用圆点遮盖，字符数不变以保证光标位置一致
+pub fn display_value(attrs: &std::collections::HashMap<String, String>, value: &str) -> String {
+    if is_password(attrs) {
+        mask_password(value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn mask_password(value: &str) -> String {
+    "•".repeat(value.chars().count())
+}
+
 /// 检查输入框是否禁用
 pub fn is_disabled(attrs: &std::collections::HashMap<String, String>) -> bool {
     attrs.get("disabled").map(|s| s == "true" || s == "{{true}}").unwrap_or(false)
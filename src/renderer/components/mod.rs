@@ -19,10 +19,13 @@ mod swiper;
 mod rich_text;
 mod picker;
 mod checkbox_group;
+mod movable;
+mod cover_view;
+mod pseudo_element;
 
 pub use base::*;
 pub use view::ViewComponent;
-pub use text::TextComponent;
+pub use text::{SelectionGeometry, TextComponent};
 pub use button::ButtonComponent;
 pub use icon::IconComponent;
 pub use progress::ProgressComponent;
@@ -30,15 +33,22 @@ pub use switch::SwitchComponent;
 pub use checkbox::CheckboxComponent;
 pub use radio::RadioComponent;
 pub use slider::SliderComponent;
-pub use input::InputComponent;
-pub use image::ImageComponent;
+pub use input::{InputComponent, TextareaLayout, TextareaState, compose_preedit, display_value, get_maxlength, is_auto_height, is_password, should_show_cursor, wrap_lines};
+pub use image::{ImageComponent, ImageCacheStats, get_image_rgba, has_loading_images, has_playing_animation, take_images_loaded};
+pub use image::{cache_stats as image_cache_stats, set_cache_budget as set_image_cache_budget};
+#[cfg(test)]
+pub(crate) use image::{ImageCache, ImageData, ImageEntry, decode_image_bytes};
+pub(crate) use image::resolve_local_path;
 pub use video::VideoComponent;
-pub use video::has_playing_video;
-pub use canvas::{CanvasComponent, Canvas2DContext, CanvasContextManager, LinearGradient, RadialGradient, execute_canvas_draw};
+pub use video::{has_playing_video, toggle_video_play};
+pub use canvas::{CanvasComponent, Canvas2DContext, CanvasContextManager, CanvasGradient, LinearGradient, RadialGradient, canvas_to_temp_file, execute_canvas_draw};
 pub use swiper::{SwiperComponent, SwiperItemComponent, SWIPER_MANAGER};
 pub use rich_text::RichTextComponent;
 pub use picker::{PickerComponent, PickerViewComponent, PickerViewColumnComponent, PickerMode, PICKER_MANAGER};
 pub use checkbox_group::{CheckboxGroupComponent, RadioGroupComponent};
+pub use movable::{MovableAreaComponent, MovableViewComponent};
+pub use cover_view::{CoverViewComponent, CoverImageComponent, is_cover_component};
+pub use pseudo_element::PseudoElementComponent;
 
 use crate::parser::wxml::WxmlNode;
 use crate::parser::wxss::StyleSheet;
@@ -70,6 +80,9 @@ impl ComponentRegistry {
             screen_height: self.screen_height,
             stylesheet,
             taffy,
+            css_vars: std::sync::Arc::new(stylesheet.root_variables()),
+            pseudo_state: Default::default(),
+            safe_area_insets: Default::default(),
         };
         
         match tag {
@@ -94,6 +107,10 @@ impl ComponentRegistry {
             "picker" => PickerComponent::build(node, &mut ctx),
             "picker-view" => PickerViewComponent::build(node, &mut ctx),
             "picker-view-column" => PickerViewColumnComponent::build(node, &mut ctx),
+            "movable-area" => MovableAreaComponent::build(node, &mut ctx),
+            "movable-view" => MovableViewComponent::build(node, &mut ctx),
+            "cover-view" => CoverViewComponent::build(node, &mut ctx),
+            "cover-image" => CoverImageComponent::build(node, &mut ctx),
             // 默认作为 view 处理
             _ => ViewComponent::build(node, &mut ctx),
         }
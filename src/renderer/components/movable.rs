@@ -0,0 +1,91 @@
+//! movable-area / movable-view 组件
+//!
+//! movable-view 绝对定位在 movable-area 的左上角，拖动产生的平移量由
+//! InteractionManager 维护，渲染时叠加到布局位置上。
+//!
+//! 支持的属性：
+//!
+//! | 属性 | 说明 | 默认值 |
+//! |------|------|--------|
+//! | direction | all / vertical / horizontal / none | none |
+//! | x, y | 平移量（px），变化时移动到新位置 | 0 |
+//! | inertia | 松手后是否有惯性 | false |
+//! | out-of-bounds | 是否可以拖出 movable-area 范围 | false |
+//! | animation | x/y 变化时是否使用动画 | true |
+//! | bindchange | 拖动时触发 | event.detail = {x, y, source} |
+
+use super::base::*;
+use crate::parser::wxml::WxmlNode;
+use crate::ui::movable::{MovableConfig, MovableDirection};
+use taffy::prelude::*;
+
+pub struct MovableAreaComponent;
+
+impl MovableAreaComponent {
+    pub fn build(node: &WxmlNode, ctx: &mut ComponentContext) -> Option<RenderNode> {
+        let (ts, ns) = build_base_style(node, ctx);
+        let tn = ctx.taffy.new_leaf(ts).unwrap();
+
+        Some(RenderNode {
+            tag: "movable-area".into(),
+            text: String::new(),
+            attrs: node.attributes.clone(),
+            taffy_node: tn,
+            style: ns,
+            children: vec![],
+            events: extract_events(node),
+        })
+    }
+}
+
+pub struct MovableViewComponent;
+
+impl MovableViewComponent {
+    pub fn build(node: &WxmlNode, ctx: &mut ComponentContext) -> Option<RenderNode> {
+        let (mut ts, ns) = build_base_style(node, ctx);
+        Self::apply_layout(&mut ts);
+        let tn = ctx.taffy.new_leaf(ts).unwrap();
+
+        Some(RenderNode {
+            tag: "movable-view".into(),
+            text: String::new(),
+            attrs: node.attributes.clone(),
+            taffy_node: tn,
+            style: ns,
+            children: vec![],
+            events: extract_events(node),
+        })
+    }
+
+    /// movable-view 脱离文档流，默认位于 movable-area 左上角
+    pub fn apply_layout(ts: &mut Style) {
+        ts.position = Position::Absolute;
+        if ts.inset.left == LengthPercentageAuto::Auto {
+            ts.inset.left = LengthPercentageAuto::Length(0.0);
+        }
+        if ts.inset.top == LengthPercentageAuto::Auto {
+            ts.inset.top = LengthPercentageAuto::Length(0.0);
+        }
+    }
+
+    /// 从属性解析拖动配置，range 为可移动范围（逻辑像素）
+    pub fn config(node: &RenderNode, range_x: f32, range_y: f32) -> MovableConfig {
+        let flag = |name: &str, default: bool| {
+            node.attrs.get(name).map(|s| s == "true" || s == "{{true}}").unwrap_or(default)
+        };
+        let num = |name: &str| {
+            node.attrs.get(name).and_then(|s| s.trim().trim_end_matches("px").parse::<f32>().ok()).unwrap_or(0.0)
+        };
+
+        MovableConfig {
+            direction: node.attrs.get("direction").map(|s| MovableDirection::parse(s)).unwrap_or_default(),
+            range_x: range_x.max(0.0),
+            range_y: range_y.max(0.0),
+            x: num("x"),
+            y: num("y"),
+            inertia: flag("inertia", false),
+            out_of_bounds: flag("out-of-bounds", false),
+            animation: flag("animation", true),
+        }
+    }
+}
@@ -6,8 +6,12 @@
 //! - stroke-width: 进度条宽度，默认 6px
 //! - activeColor: 已选择的进度条颜色
 //! - backgroundColor: 未选择的进度条颜色
+//! - border-radius: 圆角大小，默认为进度条高度的一半（圆头）
 //! - active: 进度条从左往右的动画
-//! - show-info: 在进度条右侧显示百分比
+//! - active-mode: backwards 动画从头播放，forwards 从上次结束点接着播
+//! - duration: 进度增加 1% 所需毫秒数，默认 30
+//! - show-info: 在进度条右侧显示百分比（环形进度条显示在圆心）
+//! - type: circle 时绘制环形进度条，从 12 点方向顺时针扫过 percent * 360°，默认 80px 见方
 //! 
 //! CSS 支持：
 //! - width/height: 自定义尺寸
@@ -20,7 +24,7 @@
 use super::base::*;
 use crate::parser::wxml::WxmlNode;
 use crate::text::TextRenderer;
-use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect as GeoRect};
+use crate::{Canvas, Color, Paint, PaintStyle, Path, Rect as GeoRect, StrokeCap};
 use taffy::prelude::*;
 
 pub struct ProgressComponent;
@@ -33,8 +37,10 @@ impl ProgressComponent {
         let sf = ctx.scale_factor;
         
         let pct = node.get_attr("percent").and_then(|s| s.parse::<f32>().ok()).unwrap_or(0.0);
-        let stroke_width = node.get_attr("stroke-width").and_then(|s| s.parse::<f32>().ok()).unwrap_or(6.0);
+        let px_attr = |name: &str| node.get_attr(name).and_then(|s| s.trim().trim_end_matches("px").parse::<f32>().ok());
+        let stroke_width = px_attr("stroke-width").unwrap_or(6.0);
         let show_info = node.get_attr("show-info").map(|s| s == "true" || s == "{{true}}").unwrap_or(false);
+        let circle = node.get_attr("type") == Some("circle");
         
         // 检查 CSS 是否定义了颜色
         let has_custom_bg = ns.background_color.is_some();
@@ -55,8 +61,18 @@ impl ProgressComponent {
             ns.text_color = Some(active_color);
         }
         
+        // 环形进度条缺省的宽高都是 80px
+        if circle {
+            if !has_custom_height {
+                ts.size.height = length(80.0 * sf);
+            }
+            if matches!(ts.size.width, Dimension::Auto) {
+                ts.size.width = length(80.0 * sf);
+            }
+        }
+        
         // 进度条高度 - 使用固定的容器高度，内部绘制时使用 stroke_width
-        if !has_custom_height {
+        if !has_custom_height && !circle {
             let container_height = (stroke_width + 4.0) * sf;
             ts.size.height = length(container_height);
         }
@@ -80,7 +96,7 @@ impl ProgressComponent {
         ns.border_width = stroke_width; // 存储实际的进度条高度
         
         if !has_custom_radius {
-            ns.border_radius = stroke_width * sf / 2.0;
+            ns.border_radius = px_attr("border-radius").unwrap_or(stroke_width / 2.0) * sf;
         }
         
         let tn = ctx.taffy.new_leaf(ts).unwrap();
@@ -113,6 +129,18 @@ impl ProgressComponent {
         let percent = style.custom_data / 100.0;
         let stroke_width = style.border_width * sf; // 实际进度条高度
         
+        if node.attrs.get("type").is_some_and(|t| t == "circle") {
+            let ring = CircleStyle {
+                percent,
+                stroke_width,
+                track: style.background_color,
+                active: style.text_color,
+                opacity: style.opacity,
+            };
+            Self::draw_circle(canvas, text_renderer, &node.text, (x, y, w, h), sf, &ring);
+            return;
+        }
+        
         // 绘制盒子阴影
         if let Some(shadow) = &style.box_shadow {
             draw_box_shadow(canvas, shadow, x, y, w, h, style.border_radius);
@@ -193,4 +221,61 @@ impl ProgressComponent {
             }
         }
     }
+    
+    /// 环形进度条：轨道为整圆，进度为圆头圆弧，百分比文字居中
+    fn draw_circle(
+        canvas: &mut Canvas,
+        text_renderer: Option<&TextRenderer>,
+        label: &str,
+        (x, y, w, h): (f32, f32, f32, f32),
+        sf: f32,
+        ring: &CircleStyle,
+    ) {
+        let CircleStyle { percent, stroke_width, track, active, opacity } = *ring;
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        let radius = (w.min(h) - stroke_width) / 2.0;
+        if radius <= 0.0 {
+            return;
+        }
+        let with_opacity = |color: Color| Color::new(color.r, color.g, color.b, (color.a as f32 * opacity) as u8);
+        let stroke = |color: Color| Paint::new()
+            .with_color(with_opacity(color))
+            .with_style(PaintStyle::Stroke)
+            .with_stroke_width(stroke_width)
+            .with_stroke_cap(StrokeCap::Round)
+            .with_anti_alias(true);
+        
+        if let Some(bg) = track {
+            let mut path = Path::new();
+            path.add_circle(cx, cy, radius);
+            canvas.draw_path(&path, &stroke(bg));
+        }
+        
+        if percent > 0.0 {
+            if let Some(active) = active {
+                let start = -std::f32::consts::FRAC_PI_2;
+                let mut path = Path::new();
+                path.arc(cx, cy, radius, start, start + percent.min(1.0) * std::f32::consts::TAU, false);
+                canvas.draw_path(&path, &stroke(active));
+            }
+        }
+        
+        if let (Some(tr), false) = (text_renderer, label.is_empty()) {
+            let font_size = (radius * 0.4).max(12.0 * sf);
+            let tw = tr.measure_text(label, font_size);
+            let baseline = cy - font_size / 2.0 + tr.baseline_offset(font_size, font_size);
+            let paint = Paint::new().with_color(with_opacity(Color::from_hex(0x333333))).with_style(PaintStyle::Fill);
+            tr.draw_text(canvas, label, cx - tw / 2.0, baseline, font_size, &paint);
+        }
+    }
+}
+
+/// 环形进度条的样式：进度比例、圆环线宽（物理像素）、轨道色、进度色和透明度
+#[derive(Clone, Copy)]
+struct CircleStyle {
+    percent: f32,
+    stroke_width: f32,
+    track: Option<Color>,
+    active: Option<Color>,
+    opacity: f32,
 }
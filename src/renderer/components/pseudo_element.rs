@@ -0,0 +1,107 @@
+//! ::before / ::after 伪元素
+//!
+//! 宿主元素匹配到带 content 的伪元素规则时，生成一个额外的子节点插在最前或最后。
+//! content 为空字符串时是一个只有样式的空盒子（分隔线、角标），否则按文本绘制。
+//! 伪元素默认 display: inline，绝对定位时相对宿主元素定位。
+//!
+//! 支持的 content 写法：`''`、`'文本'`、`'\2022'`、`attr(data-x)` 以及它们的拼接。
+
+use super::base::*;
+use super::text::TextComponent;
+use crate::parser::wxml::WxmlNode;
+use crate::parser::wxss::{char_offset, resolve_var_refs, StyleValue};
+use std::collections::HashMap;
+use taffy::prelude::*;
+
+pub struct PseudoElementComponent;
+
+impl PseudoElementComponent {
+    /// 构建宿主元素的 ::before 或 ::after（element 为 "before" / "after"）
+    pub fn build(node: &WxmlNode, element: &str, ctx: &mut ComponentContext) -> Option<RenderNode> {
+        let classes = get_classes(node);
+        let state = element_state(node, ctx);
+        let (css, important) = ctx.stylesheet.get_pseudo_element_styles(&classes, &node.tag_name, state, element)?;
+
+        let content = match css.get("content")? {
+            StyleValue::String(s) => s.clone(),
+            _ => return None,
+        };
+
+        let (mut ts, mut ns) = build_style_from_rules(&css, &important, "", ctx);
+        let content = if content.contains("var(") { resolve_var_refs(&content, &ns.css_vars)? } else { content };
+        let text = parse_content(&content, node)?;
+
+        // 伪元素默认是行内元素
+        if !css.contains_key("display") && ts.position != Position::Absolute {
+            ns.inline_level = Some(InlineLevel::Inline);
+            ts.size = Size::auto();
+        }
+
+        let tag = if text.is_empty() {
+            "view"
+        } else {
+            TextComponent::apply_text_size(&mut ts, &ns, &text, ctx.scale_factor);
+            "text"
+        };
+        let tn = ctx.taffy.new_leaf(ts).unwrap();
+
+        Some(RenderNode {
+            tag: tag.into(),
+            text,
+            attrs: HashMap::new(),
+            taffy_node: tn,
+            style: ns,
+            children: vec![],
+            events: vec![],
+        })
+    }
+}
+
+/// 解析 content 的值，none / normal 表示不生成伪元素
+pub fn parse_content(value: &str, node: &WxmlNode) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || value == "none" || value == "normal" {
+        return None;
+    }
+
+    let chars: Vec<char> = value.chars().collect();
+    let mut result = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\'' || c == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                    // \2022 形式的十六进制转义，后面的一个空格属于转义
+                    let hex: String = chars[i..].iter().take(6).take_while(|c| c.is_ascii_hexdigit()).collect();
+                    if hex.is_empty() {
+                        result.push(chars[i]);
+                        i += 1;
+                    } else {
+                        i += hex.len();
+                        if let Some(ch) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                            result.push(ch);
+                        }
+                        if i < chars.len() && chars[i] == ' ' { i += 1; }
+                    }
+                } else {
+                    result.push(chars[i]);
+                    i += 1;
+                }
+            }
+            i += 1;
+        } else if value[char_offset(&chars, i)..].starts_with("attr(") {
+            let start = i + 5;
+            let end = (start..chars.len()).find(|&j| chars[j] == ')').unwrap_or(chars.len());
+            let name: String = chars[start..end].iter().collect();
+            result.push_str(node.get_attr(name.trim()).unwrap_or(""));
+            i = end + 1;
+        } else {
+            // counter() 等不支持的写法跳过
+            i += 1;
+        }
+    }
+    Some(result)
+}
@@ -8,9 +8,9 @@
 //! - value: 当前值
 //! - activeColor: 已选择的颜色
 //! - backgroundColor: 背景条颜色
-//! - block-size: 滑块大小，默认 28
+//! - block-size: 滑块大小，默认 28，取值范围 12 - 28
 //! - block-color: 滑块颜色
-//! - show-value: 是否显示当前值
+//! - show-value: 是否显示当前值（显示在轨道右侧）
 //! 
 //! CSS 支持：
 //! - width/height: 自定义尺寸
@@ -23,11 +23,20 @@
 use super::base::*;
 use crate::parser::wxml::WxmlNode;
 use crate::text::TextRenderer;
+use crate::ui::interaction::format_slider_value;
 use crate::{Canvas, Color, Paint, PaintStyle, Path};
 use taffy::prelude::*;
+use std::collections::HashMap;
+
+/// show-value 时轨道右侧为数值预留的宽度（逻辑像素）
+const VALUE_WIDTH: f32 = 50.0;
 
 pub struct SliderComponent;
 
+fn attr_f32(attrs: &HashMap<String, String>, key: &str, default: f32) -> f32 {
+    attrs.get(key).and_then(|s| s.parse::<f32>().ok()).unwrap_or(default)
+}
+
 impl SliderComponent {
     pub fn build(node: &WxmlNode, ctx: &mut ComponentContext) -> Option<RenderNode> {
         let (mut ts, mut ns) = build_base_style(node, ctx);
@@ -38,7 +47,7 @@ impl SliderComponent {
         let value = node.get_attr("value").and_then(|s| s.parse::<f32>().ok()).unwrap_or(0.0);
         let min = node.get_attr("min").and_then(|s| s.parse::<f32>().ok()).unwrap_or(0.0);
         let max = node.get_attr("max").and_then(|s| s.parse::<f32>().ok()).unwrap_or(100.0);
-        let block_size = node.get_attr("block-size").and_then(|s| s.parse::<f32>().ok()).unwrap_or(28.0);
+        let block_size = node.get_attr("block-size").and_then(|s| s.parse::<f32>().ok()).unwrap_or(28.0).clamp(12.0, 28.0);
         let show_value = node.get_attr("show-value").map(|s| s == "true" || s == "{{true}}").unwrap_or(false);
         
         // 检查 CSS 是否定义了颜色
@@ -86,7 +95,7 @@ impl SliderComponent {
         ns.border_width = block_size;
         ns.font_size = value;
         
-        let text = if show_value { format!("{}", value as i32) } else { String::new() };
+        let text = if show_value { format_slider_value(value) } else { String::new() };
         
         let tn = ctx.taffy.new_leaf(ts).unwrap();
         
@@ -101,6 +110,25 @@ impl SliderComponent {
         })
     }
     
+    /// 轨道起点和终点到元素左边的距离（逻辑像素），交互时按它把指针位置换算成取值
+    pub fn track_range(node: &RenderNode, width: f32) -> (f32, f32) {
+        let block_size = node.style.border_width;
+        let value_width = if node.text.is_empty() { 0.0 } else { VALUE_WIDTH };
+        let start = block_size / 2.0;
+        (start, (width - value_width - block_size / 2.0).max(start))
+    }
+    
+    /// 交互更新取值后同步进度、数值和 show-value 文本
+    pub fn set_value(node: &mut RenderNode, value: f32) {
+        let min = attr_f32(&node.attrs, "min", 0.0);
+        let max = attr_f32(&node.attrs, "max", 100.0);
+        node.style.custom_data = if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+        node.style.font_size = value;
+        if !node.text.is_empty() {
+            node.text = format_slider_value(value);
+        }
+    }
+    
     pub fn draw(
         node: &RenderNode, 
         canvas: &mut Canvas, 
@@ -131,10 +159,10 @@ impl SliderComponent {
         };
         
         // 计算轨道区域 - 预留更多空间给数值显示
-        let value_width = if show_value { 50.0 * sf } else { 0.0 };
-        let track_width = w - value_width - block_size;
+        let (track_start, track_end) = Self::track_range(node, w / sf);
+        let track_width = (track_end - track_start) * sf;
         let track_height = 4.0 * sf;
-        let track_x = x + block_size / 2.0;
+        let track_x = x + track_start * sf;
         let track_y = y + (h - track_height) / 2.0;
         
         // 获取轨道圆角
@@ -17,6 +17,7 @@
 use super::base::*;
 use crate::parser::wxml::WxmlNode;
 use crate::{Canvas, Color, Paint, PaintStyle, Path};
+use std::collections::HashMap;
 use taffy::prelude::*;
 
 pub struct SwitchComponent;
@@ -31,8 +32,6 @@ impl SwitchComponent {
         let checked = node.get_attr("checked").map(|s| s == "true" || s == "{{true}}").unwrap_or(false);
         let disabled = node.get_attr("disabled").map(|s| s == "true" || s == "{{true}}").unwrap_or(false);
         let switch_type = node.get_attr("type").unwrap_or("switch");
-        let switch_color = node.get_attr("color").and_then(|c| parse_color_str(c))
-            .unwrap_or(Color::from_hex(0x04BE02));
         
         // 检查 CSS 是否定义了尺寸和颜色
         let has_custom_size = !matches!(ts.size.width, Dimension::Auto) || 
@@ -53,12 +52,7 @@ impl SwitchComponent {
         
         // 只在 CSS 没有定义时使用微信默认颜色
         if !has_custom_bg {
-            let bg_color = if checked {
-                if disabled { Color::from_hex(0xA9DCA8) } else { switch_color }
-            } else {
-                if disabled { Color::from_hex(0xF0F0F0) } else { Color::from_hex(0xDFDFDF) }
-            };
-            ns.background_color = Some(bg_color);
+            ns.background_color = Some(Self::track_color(&node.attributes, checked, disabled));
         }
         
         // 只在 CSS 没有定义时使用默认圆角
@@ -89,6 +83,26 @@ impl SwitchComponent {
         })
     }
     
+    /// 默认配色下的轨道（checkbox 样式为方框）颜色，选中时使用 color 属性
+    fn track_color(attrs: &HashMap<String, String>, checked: bool, disabled: bool) -> Color {
+        match (checked, disabled) {
+            (true, false) => attrs.get("color").and_then(|c| parse_color_str(c)).unwrap_or(Color::from_hex(0x04BE02)),
+            (true, true) => Color::from_hex(0xA9DCA8),
+            (false, false) => Color::from_hex(0xDFDFDF),
+            (false, true) => Color::from_hex(0xF0F0F0),
+        }
+    }
+    
+    /// 按交互状态切换选中态，使用默认配色时轨道颜色随之改变，CSS 自定义的背景色保持不变
+    pub fn set_checked(node: &mut RenderNode, checked: bool) {
+        let disabled = node.attrs.get("disabled").is_some_and(|s| s == "true" || s == "{{true}}");
+        let was_checked = node.style.custom_data > 0.5;
+        if node.style.background_color == Some(Self::track_color(&node.attrs, was_checked, disabled)) {
+            node.style.background_color = Some(Self::track_color(&node.attrs, checked, disabled));
+        }
+        node.style.custom_data = if checked { 1.0 } else { 0.0 };
+    }
+    
     pub fn draw(node: &RenderNode, canvas: &mut Canvas, x: f32, y: f32, w: f32, h: f32, sf: f32) {
         let style = &node.style;
         let checked = style.custom_data > 0.5;
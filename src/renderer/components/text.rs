@@ -2,8 +2,9 @@
 
 use super::base::*;
 use crate::parser::wxml::WxmlNode;
-use crate::text::TextRenderer;
-use crate::{Canvas, Color, Paint, PaintStyle};
+use crate::text::{FontSpec, TextRenderer};
+use crate::ui::interaction::TextLine;
+use crate::{Canvas, Color, Paint, PaintStyle, Rect as GeoRect};
 use taffy::prelude::*;
 
 pub struct TextComponent;
@@ -17,25 +18,41 @@ impl TextComponent {
         let text_content = get_text_content(node);
         if text_content.is_empty() { return None; }
         
-        let sf = ctx.scale_factor;
+        Self::apply_text_size(&mut ts, &ns, &text_content, ctx.scale_factor);
+        
+        let tn = ctx.taffy.new_leaf(ts).unwrap();
+        
+        Some(RenderNode {
+            tag: "text".into(),
+            text: text_content,
+            attrs,
+            taffy_node: tn,
+            style: ns,
+            children: vec![],
+            events,
+        })
+    }
+    
+    /// 按文本行数和估算的行宽设置文本节点的尺寸
+    pub fn apply_text_size(ts: &mut Style, ns: &NodeStyle, text: &str, sf: f32) {
         let font_size = ns.font_size * sf;
-        let line_height = ns.line_height.map(|lh| lh * sf).unwrap_or(font_size * 1.5);
-        // 确保 line_height 至少等于 font_size
-        let actual_line_height = line_height.max(font_size * 1.2);
+        let line_height = ns.used_line_height(sf);
+        let letter_spacing = ns.letter_spacing * sf;
         
         // 计算文本行数（考虑换行符）
-        let newline_count = text_content.matches('\n').count();
+        let newline_count = text.matches('\n').count();
         let min_lines = (newline_count + 1).max(1);
         
         // 估算文本宽度（单行最大宽度）
         let mut max_line_width: f32 = 0.0;
-        for line in text_content.split('\n') {
+        for line in text.split('\n') {
             let line_width: f32 = line.chars().map(|c| {
-                if c.is_ascii() {
+                let advance = if c.is_ascii() {
                     font_size * 0.6
                 } else {
                     font_size
-                }
+                };
+                advance + letter_spacing
             }).sum();
             max_line_width = max_line_width.max(line_width);
         }
@@ -45,32 +62,20 @@ impl TextComponent {
         
         // 设置高度为文本行数 * 行高（而不是 min_size，确保 taffy 正确计算父容器高度）
         // 同时设置 min_size 作为保底
-        let text_height = actual_line_height * min_lines as f32;
+        let text_height = line_height * min_lines as f32;
         ts.size.height = length(text_height);
         ts.min_size.height = length(text_height);
         
         // 如果 CSS 没有设置宽度，根据 display 属性决定宽度
-        // display: block 时使用 100%，否则使用估算的文本宽度
+        // display: block 时使用 100%（设置了内容尺寸关键字时除外），否则使用估算的文本宽度
         if matches!(ts.size.width, Dimension::Auto) {
-            if ns.is_block {
+            if ns.is_block && ns.width_keyword.is_none() {
                 ts.size.width = Dimension::Percent(1.0);
             } else {
                 // 使用估算的文本宽度
                 ts.size.width = length(max_line_width);
             }
         }
-        
-        let tn = ctx.taffy.new_leaf(ts).unwrap();
-        
-        Some(RenderNode {
-            tag: "text".into(),
-            text: text_content,
-            attrs,
-            taffy_node: tn,
-            style: ns,
-            children: vec![],
-            events,
-        })
     }
     
     pub fn draw(
@@ -83,43 +88,96 @@ impl TextComponent {
         h: f32, 
         sf: f32
     ) {
+        // 文本自身的背景和边框（带背景的 ::before / ::after 角标）
+        draw_background(canvas, &node.style, x, y, w, h);
+        
         let color = node.style.text_color.unwrap_or(Color::BLACK);
         let size = node.style.font_size * sf;
-        let line_height = node.style.line_height.map(|lh| lh * sf).unwrap_or(size * 1.5);
+        let line_height = node.style.used_line_height(sf);
         let letter_spacing = node.style.letter_spacing * sf;
+        let font = node.style.font();
         
         if let Some(tr) = text_renderer {
-            let paint = Paint::new().with_color(color).with_style(PaintStyle::Fill);
-            
+            // 单行文字在首个行框内垂直居中
+            let baseline = y + tr.baseline_offset(size, line_height);
             // 处理 white-space: nowrap 和 text-overflow: ellipsis
             let should_wrap = !matches!(node.style.white_space, WhiteSpace::NoWrap | WhiteSpace::Pre);
             let use_ellipsis = matches!(node.style.text_overflow, TextOverflow::Ellipsis);
             
             let text = &node.text;
+            let draw_glyphs = |canvas: &mut Canvas, paint: &Paint| {
+                if w > 0.0 && should_wrap {
+                    // 自动换行绘制
+                    draw_text_wrapped_advanced(
+                        canvas, tr, text, x, y, size, w, h,
+                        line_height, letter_spacing, &node.style, paint
+                    );
+                } else if w > 0.0 && use_ellipsis {
+                    // 单行 + 省略号
+                    draw_text_with_ellipsis(canvas, tr, text, x, baseline, size, w, letter_spacing, font, paint);
+                } else {
+                    // 普通绘制
+                    tr.draw_text_with_font(canvas, text, x, baseline, size, letter_spacing, font, paint);
+                }
+                
+                // 绘制文本装饰
+                if node.style.text_decoration != TextDecoration::None {
+                    draw_text_decoration(canvas, &node.style, x, baseline, w, size, paint);
+                }
+            };
             
-            if w > 0.0 && should_wrap {
-                // 自动换行绘制
-                draw_text_wrapped_advanced(
-                    canvas, tr, text, x, y + size, size, w, h,
-                    line_height, letter_spacing, &node.style, &paint
-                );
-            } else if w > 0.0 && use_ellipsis {
-                // 单行 + 省略号
-                draw_text_with_ellipsis(canvas, tr, text, x, y + size, size, w, letter_spacing, &paint);
-            } else {
-                // 普通绘制
-                tr.draw_text_with_spacing(canvas, text, x, y + size, size, letter_spacing, &paint);
+            // background-clip: text：字形用背景渐变填充
+            match node.style.background_gradient.as_ref().filter(|_| node.style.background_clip_text) {
+                Some(gradient) => fill_with_gradient(canvas, gradient, (x, y, w, h), draw_glyphs),
+                None => draw_glyphs(canvas, &Paint::new().with_color(color).with_style(PaintStyle::Fill)),
             }
-            
-            // 绘制文本装饰
-            if node.style.text_decoration != TextDecoration::None {
-                draw_text_decoration(canvas, &node.style, x, y, w, size, line_height, &paint);
+        }
+    }
+    
+    /// 与 draw 一致的视觉行排版（物理像素，相对文本左上角），返回 (视觉行, 行高)
+    pub fn layout_lines(node: &RenderNode, tr: &TextRenderer, w: f32, sf: f32) -> (Vec<TextLine>, f32) {
+        let size = node.style.font_size * sf;
+        let line_height = node.style.used_line_height(sf);
+        let letter_spacing = node.style.letter_spacing * sf;
+        let should_wrap = !matches!(node.style.white_space, WhiteSpace::NoWrap | WhiteSpace::Pre);
+        let max_width = if w > 0.0 && should_wrap { w } else { f32::INFINITY };
+        let font = node.style.font();
+        let lines = TextLine::wrap(&node.text, max_width, |ch| tr.measure_char_with_font(ch, size, font) + letter_spacing);
+        (lines, line_height)
+    }
+    
+    /// 按行绘制选中范围 [start, end) 的背景
+    pub fn draw_selection(
+        node: &RenderNode,
+        canvas: &mut Canvas,
+        tr: &TextRenderer,
+        sf: f32,
+        selection: &SelectionGeometry,
+    ) {
+        let SelectionGeometry { x, y, width, start, end } = *selection;
+        let (lines, line_height) = Self::layout_lines(node, tr, width, sf);
+        let paint = Paint::new().with_color(Color::new(7, 193, 96, 60)).with_style(PaintStyle::Fill);
+        for (row, line) in lines.iter().enumerate() {
+            let (s, e) = (start.max(line.start), end.min(line.end()));
+            if s < e {
+                let (x0, x1) = (line.x_of(s), line.x_of(e));
+                canvas.draw_rect(&GeoRect::new(x + x0, y + row as f32 * line_height, x1 - x0, line_height), &paint);
             }
         }
     }
 }
 
-/// 高级换行绘制（支持 line-height, letter-spacing, 换行符）
+/// 文本选中背景的绘制位置：文本左上角、排版宽度（物理像素）和选中的字符范围 [start, end)
+#[derive(Clone, Copy)]
+pub struct SelectionGeometry {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// 高级换行绘制（支持 line-height, letter-spacing, 换行符），y 为首行行框顶部，每行文字在行框内垂直居中
 fn draw_text_wrapped_advanced(
     canvas: &mut Canvas,
     tr: &TextRenderer,
@@ -134,16 +192,19 @@ fn draw_text_wrapped_advanced(
     style: &NodeStyle,
     paint: &Paint,
 ) {
+    let font = style.font();
+    let baseline = y + tr.baseline_offset(size, line_height);
     if max_width <= 0.0 {
-        tr.draw_text_with_spacing(canvas, text, x, y, size, letter_spacing, paint);
+        tr.draw_text_with_font(canvas, text, x, baseline, size, letter_spacing, font, paint);
         return;
     }
     
-    // 确保 line_height 至少等于 font_size
-    let actual_line_height = line_height.max(size * 1.2);
-    
-    let mut current_y = y;
+    // 盒子高度能容纳的行数，至少一行
+    let max_rows = if max_height > 0.0 { ((max_height + 0.5) / line_height).floor().max(1.0) as usize } else { usize::MAX };
+    let mut row = 0;
+    let mut current_y = baseline;
     let use_ellipsis = matches!(style.text_overflow, TextOverflow::Ellipsis);
+    let justify = style.text_align == TextAlign::Justify;
     
     // 先按换行符分割
     let paragraphs: Vec<&str> = text.split('\n').collect();
@@ -151,7 +212,8 @@ fn draw_text_wrapped_advanced(
     for (para_idx, paragraph) in paragraphs.iter().enumerate() {
         // 空段落也要换行
         if paragraph.is_empty() {
-            current_y += actual_line_height;
+            current_y += line_height;
+            row += 1;
             continue;
         }
         
@@ -160,24 +222,30 @@ fn draw_text_wrapped_advanced(
         let mut current_width = 0.0;
         
         for (i, ch) in chars.iter().enumerate() {
-            let char_width = tr.measure_char(*ch, size) + letter_spacing;
+            let char_width = tr.measure_char_with_font(*ch, size, font) + letter_spacing;
             
             // 检查是否需要换行
             if current_width + char_width > max_width && i > line_start {
                 // 检查是否超出高度
-                if max_height > 0.0 && current_y + actual_line_height > y + max_height - size {
+                if row + 1 >= max_rows {
                     if use_ellipsis {
                         let line: String = chars[line_start..i].iter().collect();
-                        draw_text_with_ellipsis(canvas, tr, &line, x, current_y, size, max_width, letter_spacing, paint);
+                        draw_text_with_ellipsis(canvas, tr, &line, x, current_y, size, max_width, letter_spacing, font, paint);
                     }
                     return;
                 }
                 
-                // 绘制当前行
-                let line: String = chars[line_start..i].iter().collect();
-                tr.draw_text_with_spacing(canvas, &line, x, current_y, size, letter_spacing, paint);
+                // 绘制当前行（两端对齐时段落最后一行之外的行撑满宽度）
+                if justify {
+                    let glyphs = GlyphStyle { size, letter_spacing, font };
+                    draw_justified_line(canvas, tr, &chars[line_start..i], (x, current_y), max_width, glyphs, paint);
+                } else {
+                    let line: String = chars[line_start..i].iter().collect();
+                    tr.draw_text_with_font(canvas, &line, x, current_y, size, letter_spacing, font, paint);
+                }
                 
-                current_y += actual_line_height;
+                current_y += line_height;
+                row += 1;
                 line_start = i;
                 current_width = char_width;
             } else {
@@ -188,16 +256,72 @@ fn draw_text_wrapped_advanced(
         // 绘制段落的最后一行
         if line_start < chars.len() {
             let line: String = chars[line_start..].iter().collect();
-            tr.draw_text_with_spacing(canvas, &line, x, current_y, size, letter_spacing, paint);
+            tr.draw_text_with_font(canvas, &line, x, current_y, size, letter_spacing, font, paint);
         }
         
         // 段落之间换行
         if para_idx < paragraphs.len() - 1 {
-            current_y += actual_line_height;
+            current_y += line_height;
+            row += 1;
         }
     }
 }
 
+/// 绘制一段文字所用的字号、字间距（物理像素）和字体
+#[derive(Clone, Copy)]
+struct GlyphStyle<'a> {
+    size: f32,
+    letter_spacing: f32,
+    font: FontSpec<'a>,
+}
+
+/// 两端对齐绘制一行：行尾空白不计，多余宽度平分到空格之后和中日韩字符两侧的间隙，
+/// 间隙之间的片段保持原有字距
+fn draw_justified_line(
+    canvas: &mut Canvas,
+    tr: &TextRenderer,
+    chars: &[char],
+    (x, y): (f32, f32),
+    max_width: f32,
+    glyphs: GlyphStyle,
+    paint: &Paint,
+) {
+    let GlyphStyle { size, letter_spacing, font } = glyphs;
+    let end = chars.iter().rposition(|c| !c.is_whitespace()).map_or(0, |i| i + 1);
+    let chars = &chars[..end];
+    let gaps: Vec<bool> = chars.windows(2)
+        .map(|pair| pair[0].is_whitespace() || is_cjk(pair[0]) || is_cjk(pair[1]))
+        .collect();
+    let count = gaps.iter().filter(|&&gap| gap).count();
+    let width: f32 = chars.iter().map(|&ch| tr.measure_char_with_font(ch, size, font) + letter_spacing).sum();
+    let extra = max_width - width;
+    if count == 0 || extra <= 0.0 {
+        let line: String = chars.iter().collect();
+        tr.draw_text_with_font(canvas, &line, x, y, size, letter_spacing, font, paint);
+        return;
+    }
+    
+    let per_gap = extra / count as f32;
+    let mut cursor = x;
+    let mut start = 0;
+    for (i, &gap) in gaps.iter().enumerate() {
+        if gap {
+            let segment: String = chars[start..=i].iter().collect();
+            tr.draw_text_with_font(canvas, &segment, cursor, y, size, letter_spacing, font, paint);
+            cursor += tr.measure_text_with_font(&segment, size, letter_spacing, font) + per_gap;
+            start = i + 1;
+        }
+    }
+    let segment: String = chars[start..].iter().collect();
+    tr.draw_text_with_font(canvas, &segment, cursor, y, size, letter_spacing, font, paint);
+}
+
+/// 是否为中日韩文字或全角标点（两端对齐时字符之间可以加空隙）
+fn is_cjk(ch: char) -> bool {
+    matches!(ch as u32,
+        0x2E80..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF | 0x20000..=0x2FA1F)
+}
+
 /// 绘制带省略号的文本
 fn draw_text_with_ellipsis(
     canvas: &mut Canvas,
@@ -208,14 +332,15 @@ fn draw_text_with_ellipsis(
     size: f32,
     max_width: f32,
     letter_spacing: f32,
+    font: FontSpec,
     paint: &Paint,
 ) {
     let ellipsis = "...";
-    let ellipsis_width = tr.measure_text_with_spacing(ellipsis, size, letter_spacing);
+    let ellipsis_width = tr.measure_text_with_font(ellipsis, size, letter_spacing, font);
     
-    let text_width = tr.measure_text_with_spacing(text, size, letter_spacing);
+    let text_width = tr.measure_text_with_font(text, size, letter_spacing, font);
     if text_width <= max_width {
-        tr.draw_text_with_spacing(canvas, text, x, y, size, letter_spacing, paint);
+        tr.draw_text_with_font(canvas, text, x, y, size, letter_spacing, font, paint);
         return;
     }
     
@@ -225,7 +350,7 @@ fn draw_text_with_ellipsis(
     let mut truncate_at = chars.len();
     
     for (i, ch) in chars.iter().enumerate() {
-        let char_width = tr.measure_char(*ch, size) + letter_spacing;
+        let char_width = tr.measure_char_with_font(*ch, size, font) + letter_spacing;
         if current_width + char_width + ellipsis_width > max_width {
             truncate_at = i;
             break;
@@ -235,24 +360,23 @@ fn draw_text_with_ellipsis(
     
     let truncated: String = chars[..truncate_at].iter().collect();
     let display_text = format!("{}{}", truncated, ellipsis);
-    tr.draw_text_with_spacing(canvas, &display_text, x, y, size, letter_spacing, paint);
+    tr.draw_text_with_font(canvas, &display_text, x, y, size, letter_spacing, font, paint);
 }
 
-/// 绘制文本装饰（下划线、删除线等）
+/// 绘制文本装饰（下划线、删除线等），baseline 为首行基线
 fn draw_text_decoration(
     canvas: &mut Canvas,
     style: &NodeStyle,
     x: f32,
-    y: f32,
+    baseline: f32,
     w: f32,
     size: f32,
-    _line_height: f32,
     paint: &Paint,
 ) {
     let line_y = match style.text_decoration {
-        TextDecoration::Underline => y + size + 2.0,
-        TextDecoration::LineThrough => y + size * 0.6,
-        TextDecoration::Overline => y,
+        TextDecoration::Underline => baseline + 2.0,
+        TextDecoration::LineThrough => baseline - size * 0.4,
+        TextDecoration::Overline => baseline - size,
         TextDecoration::None => return,
     };
     
@@ -126,14 +126,14 @@ impl VideoPlayer {
         let path = actual_path.ok_or_else(|| format!("Cannot find video: {}", self.src))?;
         self.file_path = Some(path.clone());
         
-        println!("🎬 Loading video: {}", path.display());
+        log::info!("🎬 Loading video: {}", path.display());
         
         // 解析 MP4 并解码视频帧
         self.decode_video_manual(&path)?;
         
         // 解码音频
         if let Err(e) = self.decode_audio(&path) {
-            println!("   ⚠️ Audio decode warning: {}", e);
+            log::warn!("   ⚠️ Audio decode warning: {}", e);
         }
         
         Ok(())
@@ -299,7 +299,7 @@ impl VideoPlayer {
         self.duration = total_duration as f64 / timescale as f64;
         self.fps = if self.duration > 0.0 { sample_count as f64 / self.duration } else { 24.0 };
         
-        println!("   Video: {}x{}, {:.1} fps, {:.1}s, {} samples", 
+        log::debug!("   Video: {}x{}, {:.1} fps, {:.1}s, {} samples", 
             self.width, self.height, self.fps, self.duration, sample_count);
         
         // 获取 SPS/PPS
@@ -373,7 +373,7 @@ impl VideoPlayer {
             }
             
             if decoded_count >= 3600 {
-                println!("   ⚠️ Reached max frame limit");
+                log::warn!("   ⚠️ Reached max frame limit");
                 break;
             }
         }
@@ -382,7 +382,7 @@ impl VideoPlayer {
         
         if !self.frames.is_empty() {
             self.is_loaded = true;
-            println!("✅ Video loaded: {} frames decoded, {} skipped ({:.1}s)", 
+            log::info!("✅ Video loaded: {} frames decoded, {} skipped ({:.1}s)", 
                 decoded_count, skipped_count, decode_time.as_secs_f64());
         } else {
             return Err(format!("No frames decoded (skipped {})", skipped_count));
@@ -500,7 +500,7 @@ impl VideoPlayer {
                 }
                 
                 if !sps.is_empty() && !pps.is_empty() {
-                    println!("   Found SPS ({} bytes), PPS ({} bytes) via manual parse", sps.len(), pps.len());
+                    log::debug!("   Found SPS ({} bytes), PPS ({} bytes) via manual parse", sps.len(), pps.len());
                     return Ok((sps, pps));
                 }
             }
@@ -574,7 +574,7 @@ impl VideoPlayer {
         let sample_rate = track.codec_params.sample_rate.unwrap_or(44100);
         let channels = track.codec_params.channels.map(|c| c.count() as u16).unwrap_or(2);
         
-        println!("   Audio: {} Hz, {} channels", sample_rate, channels);
+        log::debug!("   Audio: {} Hz, {} channels", sample_rate, channels);
         
         // 创建解码器
         let mut decoder = symphonia::default::get_codecs()
@@ -613,7 +613,7 @@ impl VideoPlayer {
         if !audio_buffer.samples.is_empty() {
             let duration_secs = audio_buffer.samples.len() as f64 
                 / (audio_buffer.sample_rate as f64 * audio_buffer.channels as f64);
-            println!("✅ Audio loaded: {:.1}s ({} samples)", duration_secs, audio_buffer.samples.len());
+            log::info!("✅ Audio loaded: {:.1}s ({} samples)", duration_secs, audio_buffer.samples.len());
             self.audio_buffer = Some(audio_buffer);
         }
         
@@ -721,12 +721,12 @@ impl VideoPlayer {
                             *cell.borrow_mut() = Some((stream, sink));
                         });
                         
-                        println!("🔊 Audio playback started");
+                        log::info!("🔊 Audio playback started");
                     }
-                    Err(e) => println!("❌ Sink error: {:?}", e),
+                    Err(e) => log::error!("❌ Sink error: {:?}", e),
                 }
             }
-            Err(e) => println!("❌ Audio output error: {:?}", e),
+            Err(e) => log::error!("❌ Audio output error: {:?}", e),
         }
     }
     
@@ -839,7 +839,7 @@ pub fn get_or_create_player(src: &str, autoplay: bool, loop_play: bool) -> bool
                 return true;
             }
             Err(e) => {
-                println!("❌ Failed to load video: {}", e);
+                log::error!("❌ Failed to load video: {}", e);
                 let mut player = VideoPlayer::new(src);
                 player.load_error = Some(e);
                 players_guard.insert(src.to_string(), player);
@@ -930,18 +930,7 @@ impl VideoComponent {
         let autoplay = node.get_attr("autoplay").map(|v| v == "true" || v == "{{true}}").unwrap_or(false);
         let loop_play = node.get_attr("loop").map(|v| v == "true" || v == "{{true}}").unwrap_or(false);
         
-        let default_width = 300.0;
-        let default_height = 225.0;
-        
-        if ts.size.width == Dimension::Auto {
-            ts.size.width = length(default_width * sf);
-        }
-        if ts.size.height == Dimension::Auto {
-            ts.size.height = length(default_height * sf);
-        }
-        
-        ns.background_color = Some(Color::BLACK);
-        ns.border_radius = 4.0 * sf;
+        Self::apply_style(&mut ts, &mut ns, sf);
         
         let tn = ctx.taffy.new_leaf(ts).unwrap();
         
@@ -960,6 +949,22 @@ impl VideoComponent {
         })
     }
     
+    /// 默认尺寸 300x225 和黑色背景（带 cover-view 子节点重建样式时也需要调用）
+    pub fn apply_style(ts: &mut Style, ns: &mut NodeStyle, sf: f32) {
+        let default_width = 300.0;
+        let default_height = 225.0;
+        
+        if ts.size.width == Dimension::Auto {
+            ts.size.width = length(default_width * sf);
+        }
+        if ts.size.height == Dimension::Auto {
+            ts.size.height = length(default_height * sf);
+        }
+        
+        ns.background_color = Some(Color::BLACK);
+        ns.border_radius = 4.0 * sf;
+    }
+    
     pub fn draw(
         node: &RenderNode, 
         canvas: &mut Canvas, 
@@ -985,7 +990,7 @@ impl VideoComponent {
         
         if !src.is_empty() {
             if let Some((frame_data, frame_w, frame_h)) = get_video_frame(src) {
-                canvas.draw_image(&frame_data, frame_w, frame_h, x, y, w, h, "aspectFit", radius);
+                canvas.draw_image(&frame_data, frame_w, frame_h, x, y, w, h, "aspectFit", radius, sf);
                 Self::draw_controls(canvas, text_renderer, src, x, y, w, h, sf);
                 return;
             }
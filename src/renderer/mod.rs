@@ -1,9 +1,10 @@
 //! UI 渲染器 - 将 WXML/WXSS 渲染为 UI
 
 pub mod wxml_renderer;
+pub mod animation;
 mod style_resolver;
 pub mod components;
 
-pub use wxml_renderer::{WxmlRenderer, EventBinding};
+pub use wxml_renderer::{WxmlRenderer, BuildStats, EventBinding, ScrollIntoView};
 pub use style_resolver::StyleResolver;
 pub use components::{RenderNode, NodeStyle, ComponentRegistry};
@@ -1,6 +1,6 @@
 //! 样式解析器 - 将 WXSS 样式应用到组件
 
-use crate::parser::wxss::{StyleSheet, StyleValue, LengthUnit, rpx_to_px};
+use crate::parser::wxss::{StyleSheet, StyleValue, LengthUnit, WxssParser, rpx_to_px};
 use crate::ui::Style;
 use crate::Color;
 use std::collections::HashMap;
@@ -96,10 +96,12 @@ impl StyleResolver {
                         style.margin[3] = px;
                     }
                 }
-                "background-color" | "background" => {
-                    if let StyleValue::Color(color) = value {
-                        style.background_color = Some(*color);
+                "background-color" | "background" => match value {
+                    StyleValue::Color(color) => style.background_color = Some(*color),
+                    StyleValue::String(s) if s.contains("-gradient(") => {
+                        style.background_color = WxssParser::parse_gradient_fallback(s).or(style.background_color);
                     }
+                    _ => {}
                 }
                 "color" => {
                     if let StyleValue::Color(color) = value {
@@ -215,6 +217,9 @@ impl StyleResolver {
                 })
             }
             StyleValue::Number(n) => Some(*n),
+            StyleValue::Calc(expr) => Some(expr.evaluate(&|n, unit| {
+                self.to_px(&StyleValue::Length(n, unit)).unwrap_or(0.0)
+            })),
             _ => None,
         }
     }
@@ -1,26 +1,42 @@
 //! WXML 渲染器 - 使用组件系统渲染微信小程序
 
-use crate::parser::wxml::{WxmlNode, WxmlNodeType};
-use crate::parser::wxss::StyleSheet;
+use crate::parser::wxml::{WxmlNode, WxmlNodeType, WxmlParser};
+use crate::parser::wxss::{PseudoState, StyleSheet, WxssParser};
+use crate::parser::TemplateEngine;
+use crate::js::WxsRuntime;
 use crate::text::TextRenderer;
-use crate::ui::interaction::{InteractionManager, InteractiveElement, InteractionType};
+use crate::ui::interaction::{InputAttrs, InteractionManager, InteractionResult, InteractiveElement, InteractionType, SliderAttrs};
 use crate::ui::scroll_cache::ScrollCacheManager;
-use crate::{Canvas, Color, Rect as GeoRect};
+use super::animation::{AnimatedProps, AnimationTimeline, KeyframeAnimation, KeyframeProps, NodeTransition, ProgressTween};
+use crate::{Canvas, ClipShape, Color, Rect as GeoRect};
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use taffy::prelude::*;
 
 use super::components::{
     RenderNode, NodeStyle, ComponentContext,
-    ViewComponent, TextComponent, ButtonComponent, IconComponent,
+    ViewComponent, TextComponent, SelectionGeometry, ButtonComponent, IconComponent,
     ProgressComponent, SwitchComponent, CheckboxComponent, RadioComponent,
     SliderComponent, InputComponent, ImageComponent, VideoComponent,
     CanvasComponent, SwiperComponent, SwiperItemComponent, RichTextComponent,
-    PickerComponent, PickerViewComponent, PickerViewColumnComponent,
+    PickerComponent, PickerViewComponent, PickerViewColumnComponent, PseudoElementComponent,
     CheckboxGroupComponent, RadioGroupComponent,
-    build_base_style,
+    MovableAreaComponent, MovableViewComponent,
+    CoverViewComponent, CoverImageComponent, compose_preedit, display_value, get_maxlength, is_auto_height, is_password, is_cover_component, should_show_cursor, TextareaState,
+    Overflow, TextAlign, Transform, build_base_style, collapse_child_borders, get_classes, get_border_radii, has_loading_images, resolve_intrinsic_width, resolve_local_path, wrap_inline_runs,
 };
 
+/// render_to_rgba 等待网络图片下载的最长时间
+const IMAGE_LOAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// <wxs module="..."> 声明：(模块名, src, 内联代码)
+type WxsModuleDecl = (String, Option<String>, String);
+
 #[derive(Debug, Clone)]
 pub struct EventBinding {
     pub event_type: String,
@@ -29,6 +45,8 @@ pub struct EventBinding {
     pub bounds: GeoRect,
     /// 是否是 catch 事件（阻止冒泡）
     pub is_catch: bool,
+    /// 绑定所属的节点，用于沿祖先链冒泡
+    pub node: NodeId,
 }
 
 pub struct CachedLayout {
@@ -36,12 +54,78 @@ pub struct CachedLayout {
     pub taffy: TaffyTree,
     pub content_height: f32,
     pub data: JsonValue,
+    /// 模板展开后的 WXML 树，数据变化时与新的展开结果对比
+    pub expanded: Vec<WxmlNode>,
+    /// WXML 树路径 -> 对应渲染节点
+    pub paths: BTreeMap<String, NodeId>,
+    /// 布局根节点
+    pub root: NodeId,
+}
+
+/// 最近一次构建渲染树的统计：新建的节点数和从上次构建中复用的节点数
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BuildStats {
+    pub built: usize,
+    pub reused: usize,
+}
+
+/// 增量重建时上一次构建留下的节点，按 WXML 路径查找，复用后从池中取出
+struct ReusePool {
+    paths: BTreeMap<String, NodeId>,
+    /// 拆开的渲染节点（children 已取出）及其子节点顺序
+    nodes: HashMap<NodeId, (RenderNode, Vec<NodeId>)>,
+    pseudo_nodes: HashMap<NodeId, String>,
+}
+
+/// scroll-into-view / scroll-top 产生的滚动请求，由窗口应用到对应的 ScrollController
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrollIntoView {
+    /// scroll-view 的组件 ID
+    pub scroll_view_id: String,
+    /// 滚动目标在内容中的偏移（逻辑像素，scroll-x 时为横向）
+    pub offset: f32,
+    /// scroll-with-animation
+    pub animated: bool,
+}
+
+/// 正在播放（或已播放完保持终态）的节点动画
+struct ActiveAnimation {
+    timeline: AnimationTimeline,
+    /// 开始时的动画时钟（毫秒）
+    start: f32,
+}
+
+/// 正在播放的 CSS 关键帧动画
+struct ActiveKeyframes {
+    animation: KeyframeAnimation,
+    start: f32,
+    /// 节点自身的样式值（渲染树重建时更新）
+    base: KeyframeProps,
+}
+
+/// 延后绘制的节点：cover-view / cover-image，以及 absolute 定位元素
+struct CoverNode {
+    node: RenderNode,
+    /// 父节点原点（物理像素）
+    ox: f32,
+    oy: f32,
+    inherited_color: Color,
+}
+
+/// scroll-view 吸顶重绘时沿子树不变的参数
+struct StuckContext<'a> {
+    taffy: &'a TaffyTree,
+    interaction: &'a mut InteractionManager,
+    scroll_offset: f32,
+    viewport_height: f32,
 }
 
 pub struct WxmlRenderer {
     stylesheet: StyleSheet,
     screen_width: f32,
     screen_height: f32,
+    /// 安全区域内边距（上、右、下、左，逻辑像素），env(safe-area-inset-*) 的取值
+    safe_area_insets: [f32; 4],
     event_bindings: Vec<EventBinding>,
     text_renderer: Option<TextRenderer>,
     scale_factor: f32,
@@ -50,6 +134,88 @@ pub struct WxmlRenderer {
     scroll_cache: ScrollCacheManager,
     /// 当前视口信息 (scroll_offset, viewport_height) - 用于虚拟列表
     current_viewport: Option<(f32, f32)>,
+    /// 节点 -> 父节点映射，用于事件冒泡
+    node_parents: HashMap<NodeId, NodeId>,
+    /// animation 属性值 -> 动画状态，属性值变化（新的 export()）即开始新动画
+    animations: HashMap<String, ActiveAnimation>,
+    /// 动画时钟（毫秒），由 advance_animations 推进
+    animation_clock: f32,
+    /// 当前按下的 hover-class 计时：(按下序号, 按下时的动画时钟, 松开时的动画时钟)
+    hover_timing: Option<(u64, f32, Option<f32>)>,
+    /// hover-class 状态下一次变化的动画时钟，之前需要持续重绘
+    hover_until: f32,
+    /// 节点 key（id 或树路径）-> CSS transition 状态
+    transitions: HashMap<String, NodeTransition>,
+    /// 节点 key + 动画名 -> CSS animation 状态，动画名变化即重新开始
+    keyframe_animations: HashMap<String, ActiveKeyframes>,
+    /// 节点 key -> progress active 动画状态
+    progress_animations: HashMap<String, ProgressTween>,
+    /// 渲染树重建次数；只有重建后节点上的 transform/opacity 才是新的样式值
+    layout_generation: u64,
+    /// 上次读取 transition 目标值时的 layout_generation
+    transitions_generation: u64,
+    /// scroll-view ID -> 已应用的 scroll-into-view 目标，属性变化时才再次滚动
+    scroll_into_view_applied: HashMap<String, String>,
+    /// scroll-view ID -> 已应用的 scroll-top（scroll-x 时为 scroll-left）属性值
+    scroll_top_applied: HashMap<String, String>,
+    /// 待窗口处理的 scroll-into-view / scroll-top 请求
+    pending_scroll_into_view: Vec<ScrollIntoView>,
+    /// 输入框 ID -> 已应用的 focus 属性值，属性变化时才聚焦/失焦
+    focus_applied: HashMap<String, bool>,
+    /// 本次渲染产生的聚焦（true）/ 失焦（false）请求
+    pending_focus: Vec<(String, bool)>,
+    /// movable-view 节点 -> 组件 ID（按拖动前的布局位置生成，避免 ID 随拖动变化）
+    movable_ids: HashMap<NodeId, String>,
+    /// 交互元素 ID -> 节点，用于按元素查找事件绑定
+    element_nodes: HashMap<String, NodeId>,
+    /// checkbox-group / radio-group 节点 -> (标签, 组件 ID)，用于确定复选框 / 单选框所在的组
+    group_nodes: HashMap<NodeId, (String, String)>,
+    /// 本帧遇到的 cover 组件，主内容绘制完成后统一绘制在最上层
+    cover_layer: Vec<CoverNode>,
+    /// 正在绘制 cover 层（此时 cover 组件直接绘制，不再延后）
+    drawing_cover_layer: bool,
+    /// 本帧遇到的 absolute 定位元素，普通流内容绘制完成后按 z-index 绘制在其上
+    positioned_layer: Vec<CoverNode>,
+    /// 正在绘制的延后定位元素（绘制它自身时不再延后）
+    drawing_positioned: Option<NodeId>,
+    /// 正在绘制 scroll-view 的内容（定位元素随内容滚动和裁剪，不延后）
+    in_scroll_view: bool,
+    /// 正在绘制 transform 图层（定位元素需要随图层一起变换，不延后）
+    in_transform_layer: bool,
+    /// 当前所在透明度图层的累计不透明度（延后绘制的元素离开图层后需要自己带上）
+    group_opacity: f32,
+    /// 节点 -> 本帧绘制顺序（先序，重复绘制取最后一次），命中测试时后绘制的在上层
+    paint_index: HashMap<NodeId, usize>,
+    paint_seq: usize,
+    /// sticky 元素的吸顶基准：最近的滚动容器可见区域顶部（画布物理坐标）
+    sticky_edge: f32,
+    /// sticky 元素最多停留到的位置：父元素底部（画布物理坐标）
+    sticky_limit: f32,
+    /// 有伪类规则或 hover-class 的节点 -> WXML 树路径，构建渲染树时记录，用于计算 :hover / :active / hover-class
+    pseudo_nodes: RefCell<HashMap<NodeId, String>>,
+    /// WXML 树路径 -> 构建渲染树时使用的交互状态
+    pseudo_states: HashMap<String, PseudoState>,
+    /// 页面目录，用于解析 <wxs src="...">
+    source_dir: Option<PathBuf>,
+    /// 已加载的 wxs 模块（模块声明不变时复用）
+    wxs: Option<(Vec<WxsModuleDecl>, Rc<WxsRuntime>)>,
+    /// 自定义组件标签 -> 组件 WXML
+    components: HashMap<String, Vec<WxmlNode>>,
+    /// 增量重建时可复用的旧节点
+    reuse_pool: RefCell<Option<ReusePool>>,
+    /// 本次构建中 WXML 路径 -> 渲染节点
+    built_paths: RefCell<BTreeMap<String, NodeId>>,
+    build_stats: Cell<BuildStats>,
+    /// 交互元素 ID -> (逻辑坐标边界, 可见状态指纹)，用于计算两帧之间的脏区域
+    element_fingerprints: HashMap<String, (GeoRect, u64)>,
+    /// 上一帧绘制时的 layout_generation
+    drawn_generation: u64,
+    /// 渲染树中有 canvas / video / swiper 等不经过 setData 也会变化的内容
+    has_volatile_content: bool,
+    /// 最近一帧相对上一帧的脏区域（页面逻辑坐标），None 表示整帧都需要更新
+    dirty_rects: Option<Vec<GeoRect>>,
+    /// 本帧绘制的动画图片（页面逻辑坐标），每帧都换帧，始终计入脏区域
+    animated_images: Vec<GeoRect>,
 }
 
 impl WxmlRenderer {
@@ -62,18 +228,505 @@ impl WxmlRenderer {
             .or_else(|_| TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf")))
             .ok();
         
-        Self { 
+        let mut renderer = Self { 
             stylesheet, 
             screen_width,
             screen_height,
+            safe_area_insets: [0.0; 4],
             event_bindings: Vec::new(),
             text_renderer,
             scale_factor,
             cache: None,
             scroll_cache: ScrollCacheManager::new(),
             current_viewport: None,
+            node_parents: HashMap::new(),
+            animations: HashMap::new(),
+            animation_clock: 0.0,
+            hover_timing: None,
+            hover_until: 0.0,
+            transitions: HashMap::new(),
+            keyframe_animations: HashMap::new(),
+            progress_animations: HashMap::new(),
+            layout_generation: 0,
+            transitions_generation: 0,
+            scroll_into_view_applied: HashMap::new(),
+            scroll_top_applied: HashMap::new(),
+            pending_scroll_into_view: Vec::new(),
+            focus_applied: HashMap::new(),
+            pending_focus: Vec::new(),
+            movable_ids: HashMap::new(),
+            element_nodes: HashMap::new(),
+            group_nodes: HashMap::new(),
+            cover_layer: Vec::new(),
+            drawing_cover_layer: false,
+            positioned_layer: Vec::new(),
+            drawing_positioned: None,
+            in_scroll_view: false,
+            in_transform_layer: false,
+            group_opacity: 1.0,
+            paint_index: HashMap::new(),
+            paint_seq: 0,
+            sticky_edge: 0.0,
+            sticky_limit: f32::MAX,
+            pseudo_nodes: RefCell::new(HashMap::new()),
+            pseudo_states: HashMap::new(),
+            source_dir: None,
+            wxs: None,
+            components: HashMap::new(),
+            reuse_pool: RefCell::new(None),
+            built_paths: RefCell::new(BTreeMap::new()),
+            build_stats: Cell::new(BuildStats::default()),
+            element_fingerprints: HashMap::new(),
+            drawn_generation: 0,
+            has_volatile_content: false,
+            dirty_rects: None,
+            animated_images: Vec::new(),
+        };
+        renderer.load_font_faces();
+        renderer
+    }
+    
+    /// 无窗口渲染一帧：解析 WXML / WXSS，按 width x height 逻辑像素和缩放比例渲染，返回 RGBA 像素（逐行，每像素 4 字节）
+    ///
+    /// 超出高度的内容被裁掉。网络图片先等待下载完成（最多 IMAGE_LOAD_TIMEOUT）再出图。
+    /// 用于服务端截图、缩略图和 CI 中的视觉测试。
+    pub fn render_to_rgba(wxml: &str, wxss: &str, data: &JsonValue, width: u32, height: u32, scale: f32) -> Result<Vec<u8>, String> {
+        if width == 0 || height == 0 || scale <= 0.0 {
+            return Err(format!("Invalid render size {}x{} @{}x", width, height, scale));
+        }
+        let stylesheet = WxssParser::new(wxss).parse()?;
+        let nodes = WxmlParser::new(wxml).parse()?;
+        let render = || {
+            let mut renderer = Self::new_with_scale(stylesheet.clone(), width as f32, height as f32, scale);
+            let mut canvas = Canvas::new(
+                ((width as f32 * scale).round() as u32).max(1),
+                ((height as f32 * scale).round() as u32).max(1),
+            );
+            canvas.clear(Color::WHITE);
+            let mut interaction = InteractionManager::new();
+            renderer.render_with_interaction(&mut canvas, &nodes, data, &mut interaction);
+            canvas
+        };
+        
+        // 第一帧发起网络图片下载，下载结束（或超时）后用新的渲染器重新布局和绘制
+        let deadline = Instant::now() + IMAGE_LOAD_TIMEOUT;
+        let mut canvas = render();
+        if has_loading_images() {
+            while has_loading_images() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            if has_loading_images() {
+                log::warn!("⚠️ Images still loading after {:?}, rendering without them", IMAGE_LOAD_TIMEOUT);
+            }
+            canvas = render();
+        }
+        Ok(canvas.to_rgba())
+    }
+    
+    /// 注册样式表中 @font-face 声明的字体。相对地址按页面目录解析，以 / 开头的按小程序根目录解析；
+    /// 远程字体和 data URI 暂不支持
+    fn load_font_faces(&mut self) {
+        let Some(tr) = self.text_renderer.as_mut() else { return };
+        for face in &self.stylesheet.font_faces {
+            if tr.has_font_family(&face.family) {
+                continue;
+            }
+            if face.src.starts_with("http://") || face.src.starts_with("https://") || face.src.starts_with("data:") {
+                log::warn!("⚠️ Unsupported font src for {}: {}", face.family, face.src);
+                continue;
+            }
+            let relative = self.source_dir.as_ref()
+                .filter(|_| !face.src.starts_with('/'))
+                .map(|d| d.join(&face.src))
+                .filter(|p| p.is_file());
+            let Some(path) = relative.or_else(|| resolve_local_path(&face.src).map(PathBuf::from)) else {
+                // 页面目录可能稍后才设置，届时重试
+                continue;
+            };
+            match std::fs::read(&path).map_err(|e| e.to_string()).and_then(|data| tr.register_font_family(&face.family, &data)) {
+                Ok(()) => log::info!("✅ Font face {}: {}", face.family, path.display()),
+                Err(e) => log::warn!("⚠️ Failed to load font {}: {}", path.display(), e),
+            }
         }
     }
+    
+    /// 注册自定义组件，页面中的 <tag> 展开为组件的 WXML，子节点投影到组件的 <slot>
+    pub fn register_component(&mut self, tag: &str, nodes: Vec<WxmlNode>) {
+        self.components.insert(tag.to_string(), nodes);
+        self.cache = None;
+    }
+    
+    /// 设置页面所在目录，<wxs src="..."> 相对它解析
+    pub fn set_source_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.source_dir = Some(dir.into());
+        self.wxs = None;
+        self.cache = None;
+        self.load_font_faces();
+    }
+
+    /// 设置安全区域内边距（上、右、下、左，逻辑像素），用于 env(safe-area-inset-*)
+    pub fn set_safe_area_insets(&mut self, insets: [f32; 4]) {
+        if self.safe_area_insets == insets { return; }
+        self.safe_area_insets = insets;
+        self.cache = None;
+    }
+
+    /// 覆盖渲染缩放比例（独立于窗口的 scale_factor），用于生成高倍率截图
+    /// 样式在构建时已按缩放比例换算，因此需要丢弃布局缓存重新构建
+    pub fn set_render_scale(&mut self, scale: f32) {
+        if scale <= 0.0 || (scale - self.scale_factor).abs() < f32::EPSILON {
+            return;
+        }
+        self.scale_factor = scale;
+        self.cache = None;
+        self.scroll_cache = ScrollCacheManager::new();
+    }
+    
+    /// 内容在数据不变的情况下发生变化（如网络图片下载完成），下次渲染时重新布局并重绘 scroll-view 缓存
+    /// （widthFix 图片的高度取决于图片尺寸）
+    pub fn mark_content_dirty(&mut self) {
+        self.cache = None;
+        self.scroll_cache.mark_all_dirty();
+    }
+    
+    /// 当前渲染缩放比例
+    pub fn render_scale(&self) -> f32 {
+        self.scale_factor
+    }
+    
+    /// 推进动画时钟（毫秒），返回是否还有动画在播放
+    pub fn advance_animations(&mut self, dt_ms: f32) -> bool {
+        self.animation_clock += dt_ms;
+        self.has_running_animations()
+    }
+    
+    /// 取出本次渲染产生的 scroll-into-view / scroll-top 请求
+    pub fn take_scroll_into_view(&mut self) -> Vec<ScrollIntoView> {
+        std::mem::take(&mut self.pending_scroll_into_view)
+    }
+    
+    /// 把本次渲染产生的滚动请求应用到对应的 ScrollController，返回是否有滚动
+    ///
+    /// scroll-with-animation 为 true 时缓动到目标位置，否则直接跳到目标位置。
+    pub fn apply_scroll_requests(&mut self, interaction: &mut InteractionManager) -> bool {
+        let mut scrolled = false;
+        for req in self.take_scroll_into_view() {
+            if let Some(c) = interaction.get_scroll_controller_mut(&req.scroll_view_id) {
+                c.scroll_to(req.offset, req.animated);
+                scrolled = true;
+            }
+        }
+        scrolled
+    }
+    
+    /// 把 focus 属性产生的聚焦请求应用到交互管理器，返回需要窗口处理的结果（开启/关闭输入法、bindblur 等）
+    pub fn apply_focus_requests(&mut self, interaction: &mut InteractionManager) -> Vec<InteractionResult> {
+        let mut results = Vec::new();
+        for (id, focus) in std::mem::take(&mut self.pending_focus) {
+            if focus {
+                results.extend(interaction.focus_input(&id));
+            } else if interaction.focused_input.as_ref().is_some_and(|f| f.id == id) {
+                results.extend(interaction.blur_input());
+            }
+        }
+        results
+    }
+    
+    /// 是否有未播放完的动画
+    pub fn has_running_animations(&self) -> bool {
+        self.animations.values().any(|a| !a.timeline.is_finished(self.animation_clock - a.start))
+            || self.transitions.values().any(|t| !t.is_finished(self.animation_clock))
+            || self.keyframe_animations.values().any(|a| !a.animation.is_finished(self.animation_clock - a.start))
+            || self.progress_animations.values().any(|p| !p.is_finished(self.animation_clock))
+            || self.animation_clock < self.hover_until
+    }
+    
+    /// 将 animation 属性绑定的动画和 CSS transition 应用到节点的 transform/opacity
+    fn apply_animations(&mut self, nodes: &mut [RenderNode]) {
+        fn walk(renderer: &mut WxmlRenderer, node: &mut RenderNode, path: String, fresh: bool, seen: &mut Vec<String>) {
+            if let Some(attr) = node.attrs.get("animation").filter(|a| !a.is_empty()).cloned() {
+                if !renderer.animations.contains_key(&attr) {
+                    if let Some(timeline) = AnimationTimeline::from_attr(&attr) {
+                        let start = renderer.animation_clock;
+                        renderer.animations.insert(attr.clone(), ActiveAnimation { timeline, start });
+                    }
+                }
+                if let Some(anim) = renderer.animations.get(&attr) {
+                    let props = anim.timeline.sample(renderer.animation_clock - anim.start);
+                    WxmlRenderer::apply_animated_props(node, &props);
+                    seen.push(attr);
+                }
+            } else if let Some(animation) = &node.style.animation {
+                let key = format!("{}@{}", WxmlRenderer::animation_key(node, &path), animation.spec.name);
+                let clock = renderer.animation_clock;
+                if !renderer.keyframe_animations.contains_key(&key) {
+                    let base = WxmlRenderer::keyframe_base_of(node);
+                    renderer.keyframe_animations.insert(key.clone(), ActiveKeyframes { animation: animation.clone(), start: clock, base });
+                } else if fresh {
+                    // 渲染树重建后节点上是新的样式值，动画继续播放
+                    let base = WxmlRenderer::keyframe_base_of(node);
+                    let active = renderer.keyframe_animations.get_mut(&key).unwrap();
+                    active.animation = animation.clone();
+                    active.base = base;
+                }
+                let active = &renderer.keyframe_animations[&key];
+                let props = active.animation.sample(clock - active.start, &active.base);
+                WxmlRenderer::apply_keyframe_props(node, &props);
+                seen.push(key);
+            } else if !node.style.transitions.is_empty() {
+                let key = WxmlRenderer::animation_key(node, &path);
+                let clock = renderer.animation_clock;
+                // 渲染树未重建时节点上已是上一帧写入的插值结果，只采样不重新设定目标
+                if fresh || !renderer.transitions.contains_key(&key) {
+                    let target = WxmlRenderer::animated_props_of(node);
+                    match renderer.transitions.get_mut(&key) {
+                        Some(t) => t.retarget(target, &node.style.transitions, clock),
+                        None => { renderer.transitions.insert(key.clone(), NodeTransition::new(target)); }
+                    }
+                }
+                let props = renderer.transitions[&key].sample(clock);
+                WxmlRenderer::apply_animated_props(node, &props);
+                seen.push(key);
+            }
+            // progress 的 active 动画：进度从 0（或当前值）增长到 percent
+            if node.tag == "progress" && node.attrs.get("active").is_some_and(|a| a == "true" || a == "{{true}}") {
+                let key = format!("{}:progress", WxmlRenderer::animation_key(node, &path));
+                let clock = renderer.animation_clock;
+                // 节点上的 custom_data 在渲染树重建后才是新的 percent
+                if fresh || !renderer.progress_animations.contains_key(&key) {
+                    let target = node.style.custom_data;
+                    let forwards = node.attrs.get("active-mode").is_some_and(|m| m == "forwards");
+                    match renderer.progress_animations.get_mut(&key) {
+                        Some(p) => p.retarget(target, forwards, clock),
+                        None => {
+                            let ms_per_percent = node.attrs.get("duration").and_then(|d| d.parse().ok()).unwrap_or(30.0);
+                            renderer.progress_animations.insert(key.clone(), ProgressTween::new(target, ms_per_percent, clock));
+                        }
+                    }
+                }
+                node.style.custom_data = renderer.progress_animations[&key].sample(clock);
+                seen.push(key);
+            }
+            for (i, child) in node.children.iter_mut().enumerate() {
+                walk(renderer, child, format!("{}/{}", path, i), fresh, seen);
+            }
+        }
+        
+        let fresh = self.transitions_generation != self.layout_generation;
+        self.transitions_generation = self.layout_generation;
+        let mut seen = Vec::new();
+        for (i, node) in nodes.iter_mut().enumerate() {
+            walk(self, node, i.to_string(), fresh, &mut seen);
+        }
+        // 丢弃已不再绑定的动画
+        self.animations.retain(|k, _| seen.contains(k));
+        self.transitions.retain(|k, _| seen.contains(k));
+        self.keyframe_animations.retain(|k, _| seen.contains(k));
+        self.progress_animations.retain(|k, _| seen.contains(k));
+    }
+    
+    /// 节点在动画状态表中的 key：有 id 时用 id，否则用树路径
+    fn animation_key(node: &RenderNode, path: &str) -> String {
+        node.attrs.get("id").filter(|id| !id.is_empty())
+            .map(|id| format!("#{}", id))
+            .unwrap_or_else(|| path.to_string())
+    }
+    
+    fn animated_props_of(node: &RenderNode) -> AnimatedProps {
+        node.style.transform.unwrap_or_default().to_animated_props(node.style.opacity)
+    }
+    
+    fn apply_animated_props(node: &mut RenderNode, props: &AnimatedProps) {
+        node.style.transform = Some(Transform::from_animated_props(props));
+        node.style.opacity = props.opacity;
+    }
+    
+    /// 节点自身的样式值，作为关键帧动画的基准
+    fn keyframe_base_of(node: &RenderNode) -> KeyframeProps {
+        KeyframeProps {
+            transform: Some(Self::animated_props_of(node)),
+            opacity: Some(node.style.opacity),
+            background_color: node.style.background_color,
+        }
+    }
+    
+    fn apply_keyframe_props(node: &mut RenderNode, props: &KeyframeProps) {
+        if let Some(t) = &props.transform {
+            node.style.transform = Some(Transform::from_animated_props(t));
+        }
+        if let Some(opacity) = props.opacity {
+            node.style.opacity = opacity.clamp(0.0, 1.0);
+        }
+        if props.background_color.is_some() {
+            node.style.background_color = props.background_color;
+        }
+    }
+    
+    /// 节点自身按 transform 后的盒子绘制，子节点只跟随平移
+    fn transformed_bounds(node: &RenderNode, x: f32, y: f32, w: f32, h: f32, sf: f32) -> ((f32, f32, f32, f32), (f32, f32)) {
+        match &node.style.transform {
+            Some(t) => (t.apply_to_box(x, y, w, h, sf), (x + t.translate_x * sf, y + t.translate_y * sf)),
+            None => ((x, y, w, h), (x, y)),
+        }
+    }
+    
+    /// 需要离屏合成的变换（旋转、斜切、带子节点的缩放）：把只保留平移的节点画到图层，
+    /// 再绕变换原点按矩阵重采样到画布。返回 false 表示节点不需要图层，由调用方直接绘制
+    fn draw_in_transform_layer(
+        &mut self,
+        canvas: &mut Canvas,
+        taffy: &TaffyTree,
+        node: &RenderNode,
+        ox: f32,
+        oy: f32,
+        draw: impl FnOnce(&mut Self, &mut Canvas, &RenderNode),
+    ) -> bool {
+        let Some(t) = node.style.transform.filter(|t| t.needs_layer(!node.children.is_empty())) else {
+            return false;
+        };
+        let sf = self.scale_factor;
+        let layout = taffy.layout(node.taffy_node).unwrap();
+        let mut flat = node.clone();
+        flat.style.transform = Some(Transform { translate_x: t.translate_x, translate_y: t.translate_y, ..Transform::new() });
+        let px = ox + layout.location.x + t.translate_x * sf + layout.size.width * t.origin_x;
+        let py = oy + layout.location.y + t.translate_y * sf + layout.size.height * t.origin_y;
+        
+        // 图层只覆盖变换前的子树范围
+        let mut layer = match Self::paint_bounds(taffy, &flat, ox, oy, sf) {
+            Some(bounds) => canvas.new_layer_in(&bounds),
+            None => canvas.new_layer(),
+        };
+        let previous = std::mem::replace(&mut self.in_transform_layer, true);
+        draw(self, &mut layer, &flat);
+        self.in_transform_layer = previous;
+        canvas.draw_layer(&layer, &t.matrix_about(px, py));
+        true
+    }
+    
+    /// opacity < 1 的节点连同子树先画到透明图层，再按不透明度整体合成，
+    /// 子节点不会各自半透明叠加。返回 false 表示节点不透明，由调用方直接绘制。
+    /// bounds 给出子树的绘制范围（见 paint_bounds），图层只覆盖这块区域
+    fn draw_in_opacity_layer(
+        canvas: &mut Canvas,
+        node: &RenderNode,
+        bounds: impl FnOnce() -> Option<GeoRect>,
+        draw: impl FnOnce(&mut Canvas, &RenderNode),
+    ) -> bool {
+        if node.style.opacity >= 1.0 {
+            return false;
+        }
+        let mut flat = node.clone();
+        flat.style.opacity = 1.0;
+        let mut layer = match bounds() {
+            Some(bounds) => canvas.new_layer_in(&bounds),
+            None => canvas.new_layer(),
+        };
+        draw(&mut layer, &flat);
+        canvas.draw_layer_with_opacity(&layer, node.style.opacity);
+        true
+    }
+    
+    /// 同 draw_in_opacity_layer，图层内绘制期间记录累计不透明度供延后绘制的元素使用
+    fn draw_opacity_group(
+        &mut self,
+        canvas: &mut Canvas,
+        node: &RenderNode,
+        bounds: impl FnOnce() -> Option<GeoRect>,
+        draw: impl FnOnce(&mut Self, &mut Canvas, &RenderNode),
+    ) -> bool {
+        let previous = self.group_opacity;
+        let opacity = node.style.opacity;
+        let drawn = Self::draw_in_opacity_layer(canvas, node, bounds, |layer, flat| {
+            self.group_opacity = previous * opacity;
+            draw(self, layer, flat);
+        });
+        self.group_opacity = previous;
+        drawn
+    }
+    
+    /// 节点连同子树可能绘制到的范围（物理像素）：节点盒子和外阴影的并集，裁剪子节点的节点不计子树，
+    /// 需要图层的变换按矩阵映射。ox、oy 与 draw 的含义相同。子树中有 movable-view 时
+    /// （拖动偏移不在布局中）无法确定，返回 None
+    fn paint_bounds(taffy: &TaffyTree, node: &RenderNode, ox: f32, oy: f32, sf: f32) -> Option<GeoRect> {
+        if node.tag == "movable-view" {
+            return None;
+        }
+        let layout = taffy.layout(node.taffy_node).unwrap();
+        let (x, y) = (ox + layout.location.x, oy + layout.location.y);
+        let (w, h) = (layout.size.width, layout.size.height);
+        let layer_transform = node.style.transform.filter(|t| t.needs_layer(!node.children.is_empty()));
+        let ((bx, by, bw, bh), (cx, cy)) = match layer_transform {
+            Some(t) => {
+                let (tx, ty) = (x + t.translate_x * sf, y + t.translate_y * sf);
+                ((tx, ty, w, h), (tx, ty))
+            }
+            None => Self::transformed_bounds(node, x, y, w, h, sf),
+        };
+        
+        let mut bounds = GeoRect::new(bx, by, bw, bh);
+        if let Some(shadow) = node.style.box_shadow.filter(|s| !s.inset) {
+            // 与 draw_box_shadow 一致：按 spread 扩展，模糊再向外扩散 blur + 1
+            let pad = shadow.spread + shadow.blur.ceil() + 1.0;
+            let shadow_rect = GeoRect::new(bx + shadow.offset_x - pad, by + shadow.offset_y - pad, bw + pad * 2.0, bh + pad * 2.0);
+            bounds = bounds.union(&shadow_rect);
+        }
+        let clips_children = node.style.overflow != Overflow::Visible || node.tag == "scroll-view";
+        if !clips_children && !Self::is_leaf_component(&node.tag) {
+            for child in &node.children {
+                bounds = bounds.union(&Self::paint_bounds(taffy, child, cx, cy, sf)?);
+            }
+        }
+        
+        Some(match layer_transform {
+            Some(t) => t.matrix_about(bx + w * t.origin_x, by + h * t.origin_y).map_rect(&bounds),
+            None => bounds,
+        })
+    }
+    
+    /// 同 paint_bounds，节点盒子按 (x, y, w, h) 绘制（fixed 元素按视口定位，子节点相对盒子摆放）
+    fn paint_bounds_at(taffy: &TaffyTree, node: &RenderNode, (x, y, w, h): (f32, f32, f32, f32), sf: f32) -> Option<GeoRect> {
+        let layout = taffy.layout(node.taffy_node).unwrap();
+        let bounds = Self::paint_bounds(taffy, node, x - layout.location.x, y - layout.location.y, sf)?;
+        Some(bounds.union(&GeoRect::new(x, y, w, h)))
+    }
+    
+    /// overflow 不为 visible 的节点和 scroll-view 按节点盒子（含圆角）裁剪子节点，返回是否压入了裁剪
+    fn push_overflow_clip(canvas: &mut Canvas, node: &RenderNode, x: f32, y: f32, w: f32, h: f32) -> bool {
+        if node.style.overflow == Overflow::Visible && node.tag != "scroll-view" {
+            return false;
+        }
+        canvas.push_clip(ClipShape::RoundRect(GeoRect::new(x, y, w, h), get_border_radii(&node.style)));
+        true
+    }
+    
+    /// movable-view 未设置 id 时按拖动前的布局位置生成 ID
+    fn movable_id(node: &RenderNode, x: f32, y: f32, w: f32, h: f32, sf: f32) -> String {
+        Self::get_component_id(node, &GeoRect::new(x / sf, y / sf, w / sf, h / sf))
+    }
+    
+    /// 注册 movable-view 的拖动配置，返回当前平移量（物理像素），其他节点返回 (0, 0)
+    fn movable_offset(
+        &mut self,
+        taffy: &TaffyTree,
+        node: &RenderNode,
+        (x, y, w, h): (f32, f32, f32, f32),
+        interaction: &mut InteractionManager,
+    ) -> (f32, f32) {
+        if node.tag != "movable-view" {
+            return (0.0, 0.0);
+        }
+        let sf = self.scale_factor;
+        let id = Self::movable_id(node, x, y, w, h, sf);
+        // 可移动范围：movable-area 尺寸减去自身尺寸
+        let (area_w, area_h) = taffy.parent(node.taffy_node)
+            .and_then(|p| taffy.layout(p).ok())
+            .map(|l| (l.size.width, l.size.height))
+            .unwrap_or((w, h));
+        let config = MovableViewComponent::config(node, (area_w - w) / sf, (area_h - h) / sf);
+        let (dx, dy) = interaction.register_movable(&id, config);
+        self.movable_ids.insert(node.taffy_node, id);
+        (dx * sf, dy * sf)
+    }
 
     fn update_layout_if_needed(
         &mut self,
@@ -96,17 +749,55 @@ impl WxmlRenderer {
         // 数据变化，标记所有 scroll-view 缓存为脏
         self.scroll_cache.mark_all_dirty();
         
-        let rendered = crate::parser::TemplateEngine::render_with_virtual_list(nodes, data, viewport);
-        let mut taffy = TaffyTree::new();
+        let rendered = self.expand_template(nodes, data, viewport);
+        let root_vars = Arc::new(self.stylesheet.root_variables());
+        self.build_stats.set(BuildStats::default());
+        self.built_paths.borrow_mut().clear();
         
-        let mut render_nodes = Vec::new();
+        // 有上次的渲染树时只重建变化的子树，未变化的子树连同 Taffy 节点（及其布局缓存）一起复用
+        let (mut taffy, previous) = match self.cache.take() {
+            Some(cache) => {
+                let CachedLayout { render_nodes, taffy, expanded, paths, root, .. } = cache;
+                let mut nodes = HashMap::new();
+                Self::explode_tree(render_nodes, &mut nodes);
+                // 构建过程中被替换的节点不会回收，累积过多时整体重建
+                if taffy.total_node_count() > nodes.len() * 2 + 256 {
+                    (TaffyTree::new(), None)
+                } else {
+                    let pseudo_nodes = std::mem::take(&mut *self.pseudo_nodes.borrow_mut());
+                    *self.reuse_pool.borrow_mut() = Some(ReusePool { paths, nodes, pseudo_nodes });
+                    (taffy, Some((expanded, root)))
+                }
+            }
+            None => (TaffyTree::new(), None),
+        };
+        self.pseudo_nodes.borrow_mut().clear();
         
-        for node in &rendered {
-            if let Some(rn) = self.build_tree(&mut taffy, node) {
+        let old_expanded = previous.as_ref().map(|(expanded, _)| expanded.as_slice()).unwrap_or_default();
+        let matches = Self::match_children(&rendered, old_expanded);
+        let mut render_nodes = Vec::new();
+        for (i, node) in rendered.iter().enumerate() {
+            let old = matches[i].map(|(j, same)| (&old_expanded[j], j.to_string(), same));
+            if let Some(rn) = self.build_or_reuse(&mut taffy, node, old, &root_vars, &i.to_string()) {
                 render_nodes.push(rn);
             }
         }
         
+        // 没有被复用的旧节点从布局树中移除
+        if let Some(pool) = self.reuse_pool.borrow_mut().take() {
+            for id in pool.nodes.keys() {
+                taffy.remove(*id).ok();
+            }
+        }
+        if let Some((_, old_root)) = previous {
+            taffy.remove(old_root).ok();
+        }
+        Self::relink_children(&mut taffy, &render_nodes);
+        Self::resolve_inherited(&mut render_nodes, false, &None);
+        
+        self.node_parents = Self::collect_parents(&render_nodes);
+        self.has_volatile_content = Self::contains_volatile(&render_nodes);
+        
         // 构建正常布局树（包含所有节点，fixed 元素也参与布局计算）
         let child_ids: Vec<NodeId> = render_nodes.iter().map(|n| n.taffy_node).collect();
         let root = taffy.new_with_children(
@@ -124,13 +815,164 @@ impl WxmlRenderer {
         let root_layout = taffy.layout(root).unwrap();
         let content_height = root_layout.size.height / self.scale_factor;
         
+        self.layout_generation += 1;
         self.cache = Some(CachedLayout {
             render_nodes,
             taffy,
             content_height,
             data: data.clone(),
+            expanded: rendered,
+            paths: std::mem::take(&mut *self.built_paths.borrow_mut()),
+            root,
         });
     }
+    
+    /// 最近一次构建渲染树时新建和复用的节点数
+    pub fn last_build_stats(&self) -> BuildStats {
+        self.build_stats.get()
+    }
+    
+    /// 把渲染树拆成 节点 -> (不含子节点的渲染节点, 子节点顺序)
+    fn explode_tree(nodes: Vec<RenderNode>, out: &mut HashMap<NodeId, (RenderNode, Vec<NodeId>)>) {
+        for mut node in nodes {
+            let children = std::mem::take(&mut node.children);
+            let ids = children.iter().map(|c| c.taffy_node).collect();
+            Self::explode_tree(children, out);
+            out.insert(node.taffy_node, (node, ids));
+        }
+    }
+    
+    /// 移除旧节点会清掉被复用子节点的父节点记录，这里按渲染树重新关联
+    fn relink_children(taffy: &mut TaffyTree, nodes: &[RenderNode]) {
+        for node in nodes {
+            let ids: Vec<NodeId> = node.children.iter().map(|c| c.taffy_node).collect();
+            if ids.iter().any(|&c| taffy.parent(c) != Some(node.taffy_node)) {
+                taffy.set_children(node.taffy_node, &ids).ok();
+            }
+            Self::relink_children(taffy, &node.children);
+        }
+    }
+    
+    /// 不可见元素不绑定事件
+    fn visible_events(node: &RenderNode) -> &[(String, String, HashMap<String, String>, bool)] {
+        if node.style.hidden { &[] } else { &node.events }
+    }
+    
+    /// 解析可继承的样式：visibility 未声明时跟随父元素（子元素可以用 visible 重新显示），
+    /// font-family 未声明时使用父元素的
+    fn resolve_inherited(nodes: &mut [RenderNode], parent_hidden: bool, parent_family: &Option<Arc<[String]>>) {
+        for node in nodes {
+            node.style.hidden = node.style.visible.map_or(parent_hidden, |v| !v);
+            node.style.used_font_family = node.style.font_family.clone().or_else(|| parent_family.clone());
+            let family = node.style.used_font_family.clone();
+            Self::resolve_inherited(&mut node.children, node.style.hidden, &family);
+        }
+    }
+    
+    /// 为新的子节点匹配上次构建的子节点：内容完全相同的优先（同位置优先，其次是列表增删后
+    /// 移动了位置的），其余按位置对应。返回 (旧下标, 是否完全相同)
+    fn match_children(new: &[WxmlNode], old: &[WxmlNode]) -> Vec<Option<(usize, bool)>> {
+        let mut used = vec![false; old.len()];
+        let mut result = vec![None; new.len()];
+        for (i, node) in new.iter().enumerate() {
+            if old.get(i) == Some(node) {
+                used[i] = true;
+                result[i] = Some((i, true));
+            }
+        }
+        for (i, node) in new.iter().enumerate() {
+            if result[i].is_some() { continue; }
+            if let Some(j) = (0..old.len()).find(|&j| !used[j] && old[j] == *node) {
+                used[j] = true;
+                result[i] = Some((j, true));
+            }
+        }
+        for (i, slot) in result.iter_mut().enumerate() {
+            if slot.is_none() && i < old.len() && !used[i] {
+                used[i] = true;
+                *slot = Some((i, false));
+            }
+        }
+        result
+    }
+    
+    /// 构建子节点：内容完全相同的旧节点直接复用整棵子树，否则与匹配到的旧节点对比重建
+    fn build_or_reuse(
+        &self,
+        taffy: &mut TaffyTree,
+        node: &WxmlNode,
+        old: Option<(&WxmlNode, String, bool)>,
+        css_vars: &Arc<HashMap<String, String>>,
+        path: &str,
+    ) -> Option<RenderNode> {
+        match old {
+            Some((old_node, old_path, same)) => {
+                if same {
+                    if let Some(rn) = self.reuse_subtree(&old_path, path) {
+                        return Some(rn);
+                    }
+                }
+                self.build_tree(taffy, node, css_vars, path, Some((old_node, &old_path)))
+            }
+            None => self.build_tree(taffy, node, css_vars, path, None),
+        }
+    }
+    
+    /// 从复用池中取出旧路径上的整棵子树，并把子树内的路径记录改为新路径
+    fn reuse_subtree(&self, old_path: &str, path: &str) -> Option<RenderNode> {
+        // :hover / :active 状态按路径记录，节点移动位置后状态可能不同
+        if old_path != path && self.pseudo_states.keys().any(|k| Self::path_within(k, old_path) || Self::path_within(k, path)) {
+            return None;
+        }
+        
+        let mut pool = self.reuse_pool.borrow_mut();
+        let pool = pool.as_mut()?;
+        let id = *pool.paths.get(old_path)?;
+        if !pool.nodes.contains_key(&id) {
+            return None;
+        }
+        
+        let mut built_paths = self.built_paths.borrow_mut();
+        for (p, n) in pool.paths.range(old_path.to_string()..).take_while(|(p, _)| Self::path_within(p, old_path)) {
+            built_paths.insert(format!("{}{}", path, &p[old_path.len()..]), *n);
+        }
+        
+        let mut reused = 0;
+        let rn = Self::take_subtree(pool, id, old_path, path, &mut self.pseudo_nodes.borrow_mut(), &mut reused);
+        let mut stats = self.build_stats.get();
+        stats.reused += reused;
+        self.build_stats.set(stats);
+        rn
+    }
+    
+    fn take_subtree(
+        pool: &mut ReusePool,
+        id: NodeId,
+        old_path: &str,
+        path: &str,
+        pseudo_nodes: &mut HashMap<NodeId, String>,
+        count: &mut usize,
+    ) -> Option<RenderNode> {
+        let (mut node, children) = pool.nodes.remove(&id)?;
+        *count += 1;
+        if let Some(p) = pool.pseudo_nodes.get(&id) {
+            pseudo_nodes.insert(id, format!("{}{}", path, &p[old_path.len().min(p.len())..]));
+        }
+        node.children = children.into_iter()
+            .filter_map(|c| Self::take_subtree(pool, c, old_path, path, pseudo_nodes, count))
+            .collect();
+        Some(node)
+    }
+    
+    /// path 是否为 ancestor 本身或其子孙路径
+    fn path_within(path: &str, ancestor: &str) -> bool {
+        path == ancestor || (path.starts_with(ancestor) && path.as_bytes().get(ancestor.len()) == Some(&b'/'))
+    }
+    
+    /// 节点自身（标签、属性、文本）相同，只可能是子节点不同
+    fn same_element(a: &WxmlNode, b: &WxmlNode) -> bool {
+        a.node_type == b.node_type && a.tag_name == b.tag_name && a.attributes == b.attributes && a.text_content == b.text_content
+    }
 
     /// 渲染 WXML 节点，使用交互管理器处理状态
     pub fn render_with_interaction(
@@ -169,24 +1011,326 @@ impl WxmlRenderer {
         // 传递视口信息给模板引擎，用于虚拟列表优化
         self.update_layout_if_needed(nodes, data, Some((scroll_offset, viewport_height)));
         
+        // 指针下的元素变化时按新的 :hover / :active 状态重建渲染树
+        if self.update_pseudo_states(interaction, scroll_offset) {
+            self.cache = None;
+            self.update_layout_if_needed(nodes, data, Some((scroll_offset, viewport_height)));
+        }
+        self.fit_auto_height(interaction);
+        
         self.event_bindings.clear();
+        self.paint_index.clear();
+        self.paint_seq = 0;
+        self.movable_ids.clear();
+        self.element_nodes.clear();
+        self.group_nodes.clear();
+        self.animated_images.clear();
         // 不清除交互元素，保留 scroll controller 状态
         // interaction.clear_elements();  // 移除这行，避免每帧重建
         
-        if let Some(cache) = self.cache.take() {
+        if let Some(mut cache) = self.cache.take() {
+            self.apply_animations(&mut cache.render_nodes);
             let content_height = cache.content_height;
             // 渲染所有元素（fixed 元素会在 draw_with_interaction 中被跳过）
-            // 不使用滚动偏移渲染，滚动在 present_to_buffer 中处理
-            for rn in &cache.render_nodes {
+            // 不使用滚动偏移渲染，滚动在 present_to_buffer 中处理；页面级 sticky 元素按滚动位置吸顶
+            self.sticky_edge = scroll_offset * self.scale_factor;
+            self.sticky_limit = f32::MAX;
+            for rn in Self::paint_order(&cache.render_nodes) {
                 self.draw_with_interaction(canvas, &cache.taffy, rn, 0.0, 0.0, interaction, scroll_offset, viewport_height * self.scale_factor);
             }
+            self.draw_positioned_layer(canvas, &cache.taffy, interaction, scroll_offset, viewport_height * self.scale_factor);
+            self.draw_cover_layer(canvas, &cache.taffy, interaction, scroll_offset, viewport_height * self.scale_factor);
             self.cache = Some(cache);
+            self.update_dirty_rects(interaction);
             return content_height;
         }
         
         0.0
     }
     
+    /// auto-height 的 textarea 按实际宽度和当前内容（输入中的值优先于 value 属性）调整高度，
+    /// 有变化时重新计算布局。上一帧的 element_nodes 用于找到节点对应的输入状态
+    fn fit_auto_height(&mut self, interaction: &InteractionManager) {
+        fn walk<'a>(node: &'a RenderNode, out: &mut Vec<&'a RenderNode>) {
+            if node.tag == "textarea" && is_auto_height(&node.attrs) {
+                out.push(node);
+            }
+            for child in &node.children {
+                walk(child, out);
+            }
+        }
+        
+        let sf = self.scale_factor;
+        let Some(cache) = self.cache.as_mut() else { return };
+        let mut textareas = Vec::new();
+        for rn in &cache.render_nodes {
+            walk(rn, &mut textareas);
+        }
+        
+        let mut heights = Vec::new();
+        for node in textareas {
+            let value = self.element_nodes.iter()
+                .find(|(_, n)| **n == node.taffy_node)
+                .and_then(|(id, _)| interaction.states.get(id))
+                .map(|s| s.value.as_str())
+                .unwrap_or_else(|| node.attrs.get("value").map_or("", |v| v.as_str()));
+            let Ok(layout) = cache.taffy.layout(node.taffy_node) else { continue };
+            let height = InputComponent::auto_height(value, &node.style, layout.size.width, sf);
+            if (height - layout.size.height).abs() >= 0.5 {
+                heights.push((node.taffy_node, height));
+            }
+        }
+        
+        let changed = !heights.is_empty();
+        for (node_id, height) in heights {
+            if let Ok(mut ts) = cache.taffy.style(node_id).cloned() {
+                ts.size.height = length(height);
+                cache.taffy.set_style(node_id, ts).ok();
+            }
+        }
+        
+        if changed {
+            cache.taffy.compute_layout(cache.root, Size::MAX_CONTENT).unwrap();
+            cache.content_height = cache.taffy.layout(cache.root).unwrap().size.height / sf;
+            self.layout_generation += 1;
+        }
+    }
+    
+    /// 最近一次 render_with_scroll_and_viewport 相对上一次变化的区域（页面逻辑坐标）。
+    /// None 表示需要整帧更新：渲染树重建、动画进行中、有 canvas/video 等内容或 fixed 元素变化
+    pub fn dirty_rects(&self) -> Option<&[GeoRect]> {
+        self.dirty_rects.as_deref()
+    }
+    
+    /// 对比交互元素的边界和状态，得到本帧的脏区域
+    fn update_dirty_rects(&mut self, interaction: &InteractionManager) {
+        let mut fingerprints = HashMap::new();
+        for element in interaction.elements() {
+            fingerprints.insert(element.id.clone(), (element.bounds, Self::element_fingerprint(element, interaction)));
+        }
+        let previous = std::mem::replace(&mut self.element_fingerprints, fingerprints);
+        
+        let rebuilt = self.drawn_generation != self.layout_generation;
+        self.drawn_generation = self.layout_generation;
+        if rebuilt || self.has_volatile_content || self.has_running_animations()
+            || !interaction.click_animations.is_empty() || interaction.dragging_movable.is_some() {
+            self.dirty_rects = None;
+            return;
+        }
+        
+        // 状态变化的元素更新新位置，位置变化或消失的元素还要更新旧位置
+        let mut rects = vec![];
+        for (id, entry) in &self.element_fingerprints {
+            match previous.get(id) {
+                Some(old) if old == entry => {}
+                Some(old) if old.0 != entry.0 => rects.extend([old.0, entry.0]),
+                _ => rects.push(entry.0),
+            }
+        }
+        for (id, old) in &previous {
+            if !self.element_fingerprints.contains_key(id) { rects.push(old.0); }
+        }
+        rects.extend(self.animated_images.iter().copied());
+        // fixed 元素绘制在单独的图层上，不在页面坐标系中
+        let fixed_changed = interaction.elements().iter().any(|e| e.is_fixed && rects.contains(&e.bounds));
+        self.dirty_rects = if fixed_changed { None } else { Some(rects) };
+    }
+    
+    /// 元素的可见状态：勾选/取值、输入框内容和光标、滚动位置、按下状态
+    fn element_fingerprint(element: &InteractiveElement, interaction: &InteractionManager) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        element.checked.hash(&mut hasher);
+        element.value.hash(&mut hasher);
+        element.disabled.hash(&mut hasher);
+        if let Some(state) = interaction.states.get(&element.id) {
+            state.checked.hash(&mut hasher);
+            state.value.hash(&mut hasher);
+        }
+        if let Some(input) = interaction.focused_input.as_ref().filter(|f| f.id == element.id) {
+            input.value.hash(&mut hasher);
+            input.cursor_pos.hash(&mut hasher);
+            (input.selection_start, input.selection_end).hash(&mut hasher);
+            input.text_offset.to_bits().hash(&mut hasher);
+            input.scroll_top.to_bits().hash(&mut hasher);
+            input.preedit.hash(&mut hasher);
+            should_show_cursor().hash(&mut hasher);
+        }
+        if let Some(controller) = interaction.scroll_controllers.get(&element.id) {
+            controller.get_position().to_bits().hash(&mut hasher);
+        }
+        if let Some(selection) = interaction.text_selection.as_ref().filter(|s| s.id == element.id) {
+            (selection.start, selection.end).hash(&mut hasher);
+        }
+        interaction.pressed_button.as_ref().map(|b| b.id == element.id).hash(&mut hasher);
+        hasher.finish()
+    }
+    
+    /// 记录主画布上绘制的动画图片，下一帧换帧时需要更新这块区域
+    fn note_animated_image(&mut self, node: &RenderNode, x: f32, y: f32, w: f32, h: f32) {
+        if node.tag == "image" && ImageComponent::is_playing_animation(node) {
+            let sf = self.scale_factor;
+            self.animated_images.push(GeoRect::new(x / sf, y / sf, w / sf, h / sf));
+        }
+    }
+    
+    fn contains_volatile(nodes: &[RenderNode]) -> bool {
+        nodes.iter().any(|n| matches!(n.tag.as_str(), "canvas" | "video" | "swiper" | "movable-view") || Self::contains_volatile(&n.children))
+    }
+    
+    /// 是否有 :active / :hover / :disabled 规则（指针移动时需要重绘）
+    pub fn has_pseudo_class_rules(&self) -> bool {
+        self.stylesheet.has_pseudo_class_rules()
+    }
+    
+    /// 按指针位置计算有伪类规则的节点的 :hover / :active 状态，以及按下位置上 hover-class 节点的状态，
+    /// 返回是否与构建渲染树时的状态不同
+    ///
+    /// 使用上次布局的节点盒子做命中测试（不考虑 scroll-view 内部滚动和 transform）。
+    /// hover-class 在按下 hover-start-time 后生效，松开后保留 hover-stay-time，按动画时钟计时
+    /// （按下、松开之后的第一次渲染开始计时，窗口在按下和松开时都会立即重绘）；
+    /// 祖先元素同样进入按下状态，直到设置了 hover-stop-propagation 的元素为止。
+    fn update_pseudo_states(&mut self, interaction: &InteractionManager, scroll_offset: f32) -> bool {
+        struct WalkCtx<'a> {
+            pseudo_nodes: &'a HashMap<NodeId, String>,
+            pointer: Option<(f32, f32)>,
+            pressed: bool,
+            press: Option<(f32, f32)>,
+            scroll_offset: f32,
+            sf: f32,
+        }
+        
+        fn walk(
+            taffy: &TaffyTree,
+            node: &RenderNode,
+            ox: f32,
+            oy: f32,
+            ctx: &WalkCtx,
+            states: &mut HashMap<String, PseudoState>,
+            hover_chain: &mut Vec<(String, f32, f32, bool)>,
+        ) {
+            let Ok(layout) = taffy.layout(node.taffy_node) else { return };
+            let (x, y) = (ox + layout.location.x, oy + layout.location.y);
+            // 非 fixed 元素在页面坐标系中，指针需要加上页面滚动
+            let contains = |(px, py): (f32, f32)| {
+                let py = if node.style.is_fixed { py } else { py + ctx.scroll_offset };
+                let (px, py) = (px * ctx.sf, py * ctx.sf);
+                px >= x && px < x + layout.size.width && py >= y && py < y + layout.size.height
+            };
+            if let Some(path) = ctx.pseudo_nodes.get(&node.taffy_node) {
+                if ctx.pointer.is_some_and(contains) {
+                    states.insert(path.clone(), PseudoState { hover: true, active: ctx.pressed, ..Default::default() });
+                }
+                let hover_class = node.attrs.get("hover-class").is_some_and(|c| !c.is_empty() && c != "none");
+                if hover_class && ctx.press.is_some_and(contains) {
+                    let ms = |name: &str, default: f32| node.attrs.get(name).and_then(|v| v.parse().ok()).unwrap_or(default);
+                    let stop = node.attrs.get("hover-stop-propagation").is_some_and(|v| v == "true" || v == "{{true}}");
+                    hover_chain.push((path.clone(), ms("hover-start-time", 50.0), ms("hover-stay-time", 400.0), stop));
+                }
+            }
+            for child in &node.children {
+                walk(taffy, child, x, y, ctx, states, hover_chain);
+            }
+        }
+        
+        let clock = self.animation_clock;
+        self.hover_timing = match (interaction.hover_press(), self.hover_timing) {
+            (None, _) => None,
+            (Some(p), Some((seq, pressed_at, released_at))) if p.seq == seq => {
+                Some((seq, pressed_at, released_at.or(p.released.then_some(clock))))
+            }
+            (Some(p), _) => Some((p.seq, clock, p.released.then_some(clock))),
+        };
+        
+        let pseudo_nodes = self.pseudo_nodes.borrow().clone();
+        if pseudo_nodes.is_empty() && self.pseudo_states.is_empty() {
+            return false;
+        }
+        let Some(cache) = &self.cache else { return false };
+        let mut states = HashMap::new();
+        let mut hover_chain = Vec::new();
+        let ctx = WalkCtx {
+            pseudo_nodes: &pseudo_nodes,
+            pointer: interaction.pointer_pos,
+            pressed: interaction.pointer_down,
+            press: interaction.hover_press().map(|p| (p.x, p.y)),
+            scroll_offset,
+            sf: self.scale_factor,
+        };
+        for rn in &cache.render_nodes {
+            walk(&cache.taffy, rn, 0.0, 0.0, &ctx, &mut states, &mut hover_chain);
+        }
+        
+        // 从最内层的 hover-class 节点向外
+        self.hover_until = 0.0;
+        if let Some((_, pressed_at, released_at)) = self.hover_timing {
+            for (path, start, stay, stop) in hover_chain.into_iter().rev() {
+                let shown_at = pressed_at + start;
+                let hidden_at = released_at.map(|r| r.max(shown_at) + stay);
+                if clock >= shown_at && hidden_at.is_none_or(|t| clock < t) {
+                    states.entry(path).or_default().hover_class = true;
+                }
+                for t in [Some(shown_at), hidden_at].into_iter().flatten() {
+                    if t > clock {
+                        self.hover_until = self.hover_until.max(t);
+                    }
+                }
+                if stop {
+                    break;
+                }
+            }
+        }
+        
+        if states == self.pseudo_states {
+            return false;
+        }
+        self.pseudo_states = states;
+        true
+    }
+    
+    /// 绘制本帧收集的 absolute 定位元素：按 z-index 排序（相同时保持文档顺序），
+    /// 每个元素子树中的定位元素紧接着绘制在它之上。交互区域随之后注册，优先被点击命中
+    fn draw_positioned_layer(
+        &mut self,
+        canvas: &mut Canvas,
+        taffy: &TaffyTree,
+        interaction: &mut InteractionManager,
+        scroll_offset: f32,
+        viewport_height: f32,
+    ) {
+        let mut layer = std::mem::take(&mut self.positioned_layer);
+        layer.sort_by_key(|item| item.node.style.z_index);
+        for item in &layer {
+            let previous = self.drawing_positioned.replace(item.node.taffy_node);
+            self.draw_child_with_interaction(
+                canvas, taffy, &item.node, item.ox, item.oy, item.inherited_color,
+                interaction, scroll_offset, viewport_height,
+            );
+            self.drawing_positioned = previous;
+            self.draw_positioned_layer(canvas, taffy, interaction, scroll_offset, viewport_height);
+        }
+    }
+    
+    /// 在主内容之上绘制本帧收集的 cover 组件（同时最后注册交互区域，优先被点击命中）
+    fn draw_cover_layer(
+        &mut self,
+        canvas: &mut Canvas,
+        taffy: &TaffyTree,
+        interaction: &mut InteractionManager,
+        scroll_offset: f32,
+        viewport_height: f32,
+    ) {
+        let covers = std::mem::take(&mut self.cover_layer);
+        self.drawing_cover_layer = true;
+        for cover in &covers {
+            self.draw_child_with_interaction(
+                canvas, taffy, &cover.node, cover.ox, cover.oy, cover.inherited_color,
+                interaction, scroll_offset, viewport_height,
+            );
+        }
+        self.drawing_cover_layer = false;
+    }
+    
     /// 单独渲染 fixed 元素到指定的 canvas
     /// 这个方法应该在主内容渲染后调用，fixed_canvas 是一个覆盖在主内容上的透明层
     pub fn render_fixed_elements(
@@ -275,6 +1419,11 @@ impl WxmlRenderer {
         viewport_height: f32,
     ) {
         let sf = self.scale_factor;
+        if self.draw_opacity_group(canvas, node, || Self::paint_bounds_at(taffy, node, (fixed_x, fixed_y, fixed_w, fixed_h), sf), |r, layer, flat| {
+            r.draw_fixed_element_original(taffy, layer, flat, fixed_x, fixed_y, fixed_w, fixed_h, interaction, viewport_height)
+        }) {
+            return;
+        }
         let logical_bounds = GeoRect::new(fixed_x / sf, fixed_y / sf, fixed_w / sf, fixed_h / sf);
         
         // 绘制 fixed 元素的背景
@@ -284,9 +1433,10 @@ impl WxmlRenderer {
         self.register_interactive_element(node, node, &logical_bounds, interaction, taffy, true);
 
         // 绘制子节点 - 子节点位置相对于 fixed 元素
+        let clipped = Self::push_overflow_clip(canvas, node, fixed_x, fixed_y, fixed_w, fixed_h);
         if !Self::is_leaf_component(&node.tag) {
             let text_color = node.style.text_color.unwrap_or(Color::BLACK);
-            for child in &node.children {
+            for child in Self::paint_order(&node.children) {
                 // 获取子节点在原始布局中相对于父节点的位置
                 let child_layout = taffy.layout(child.taffy_node).unwrap();
                 let child_x = fixed_x + child_layout.location.x;
@@ -297,15 +1447,17 @@ impl WxmlRenderer {
                 self.draw_fixed_child_recursive(taffy, canvas, child, child_x, child_y, child_w, child_h, text_color, interaction, viewport_height);
             }
         }
+        if clipped { canvas.pop_clip(); }
         
         // 记录事件绑定
-        for (et, handler, data, is_catch) in &node.events {
+        for (et, handler, data, is_catch) in Self::visible_events(node) {
             self.event_bindings.push(EventBinding {
                 event_type: et.clone(),
                 handler: handler.clone(),
                 data: data.clone(),
                 bounds: logical_bounds,
                 is_catch: *is_catch,
+                node: node.taffy_node,
             });
         }
     }
@@ -328,8 +1480,13 @@ impl WxmlRenderer {
         if y > viewport_height || y + h < 0.0 {
             return;
         }
-
         let sf = self.scale_factor;
+        if self.draw_opacity_group(canvas, node, || Self::paint_bounds_at(taffy, node, (x, y, w, h), sf), |r, layer, flat| {
+            r.draw_fixed_child_recursive(taffy, layer, flat, x, y, w, h, inherited_color, interaction, viewport_height)
+        }) {
+            return;
+        }
+
         let logical_bounds = GeoRect::new(x / sf, y / sf, w / sf, h / sf);
         
         let text_color = node.style.text_color.unwrap_or(inherited_color);
@@ -344,6 +1501,7 @@ impl WxmlRenderer {
         // 绘制组件 - 特殊处理 button 以支持按下状态
         let component_id = Self::get_component_id(node, &logical_bounds);
         match node.tag.as_str() {
+            _ if node.style.hidden => {}
             "button" => {
                 let pressed = interaction.is_button_pressed(&component_id);
                 ButtonComponent::draw_with_state(
@@ -357,6 +1515,7 @@ impl WxmlRenderer {
         }
         
         // 递归绘制子节点
+        let clipped = Self::push_overflow_clip(canvas, node, x, y, w, h);
         if !Self::is_leaf_component(&node.tag) {
             let is_scroll_view = node.tag == "scroll-view";
             let mut child_offset_y = 0.0;
@@ -382,7 +1541,7 @@ impl WxmlRenderer {
                 let viewport_top = scroll_position * sf;
                 let viewport_bottom = viewport_top + h;
                 
-                for child in &node.children {
+                for child in Self::paint_order(&node.children) {
                     let child_layout = taffy.layout(child.taffy_node).unwrap();
                     let child_top = child_layout.location.y;
                     let child_bottom = child_top + child_layout.size.height;
@@ -398,7 +1557,7 @@ impl WxmlRenderer {
                     }
                 }
             } else {
-                for child in &node.children {
+                for child in Self::paint_order(&node.children) {
                     let child_layout = taffy.layout(child.taffy_node).unwrap();
                     let child_x = x + child_layout.location.x;
                     let child_y = y + child_layout.location.y + child_offset_y;
@@ -413,31 +1572,100 @@ impl WxmlRenderer {
                 canvas.restore();
             }
         }
+        if clipped { canvas.pop_clip(); }
         
         // 记录事件绑定
-        for (et, handler, data, is_catch) in &node.events {
+        for (et, handler, data, is_catch) in Self::visible_events(node) {
             self.event_bindings.push(EventBinding {
                 event_type: et.clone(),
                 handler: handler.clone(),
                 data: data.clone(),
                 bounds: logical_bounds,
                 is_catch: *is_catch,
+                node: node.taffy_node,
             });
         }
     }
     
+    /// 展开模板；页面声明了 wxs 模块时先加载模块，表达式中可以调用模块函数
+    fn expand_template(&mut self, nodes: &[WxmlNode], data: &JsonValue, viewport: Option<(f32, f32)>) -> Vec<WxmlNode> {
+        let mut modules = vec![];
+        Self::collect_wxs_modules(nodes, &mut modules);
+        for component in self.components.values() {
+            Self::collect_wxs_modules(component, &mut modules);
+        }
+        if modules.is_empty() {
+            return TemplateEngine::render_with_components(nodes, data, viewport, &self.components);
+        }
+        
+        if self.wxs.as_ref().map(|(declared, _)| *declared != modules).unwrap_or(true) {
+            let mut runtime = match WxsRuntime::new() {
+                Ok(r) => r,
+                Err(e) => {
+                    log::warn!("⚠️ Failed to create wxs runtime: {}", e);
+                    return TemplateEngine::render_with_components(nodes, data, viewport, &self.components);
+                }
+            };
+            for (name, src, code) in &modules {
+                let code = match src {
+                    Some(src) => {
+                        let path = self.source_dir.as_ref().map(|d| d.join(src)).unwrap_or_else(|| PathBuf::from(src));
+                        match std::fs::read_to_string(&path) {
+                            Ok(code) => code,
+                            Err(e) => {
+                                log::warn!("⚠️ Failed to read wxs {}: {}", path.display(), e);
+                                continue;
+                            }
+                        }
+                    }
+                    None => code.clone(),
+                };
+                if let Err(e) = runtime.load_module(name, &code) {
+                    log::warn!("⚠️ {}", e);
+                }
+            }
+            self.wxs = Some((modules, Rc::new(runtime)));
+        }
+        
+        let wxs = self.wxs.as_ref().map(|(_, r)| r.clone()).unwrap();
+        TemplateEngine::with_wxs(&wxs, || TemplateEngine::render_with_components(nodes, data, viewport, &self.components))
+    }
+    
+    /// 收集 <wxs module="..."> 声明
+    fn collect_wxs_modules(nodes: &[WxmlNode], out: &mut Vec<WxsModuleDecl>) {
+        for node in nodes {
+            if node.tag_name != "wxs" {
+                Self::collect_wxs_modules(&node.children, out);
+                continue;
+            }
+            if let Some(name) = node.get_attr("module") {
+                let code: String = node.children.iter().map(|c| c.text_content.as_str()).collect();
+                out.push((name.to_string(), node.get_attr("src").map(|s| s.to_string()), code));
+            }
+        }
+    }
+    
     /// 兼容旧接口
     pub fn render(&mut self, canvas: &mut Canvas, nodes: &[WxmlNode], data: &JsonValue) {
         self.event_bindings.clear();
-        let rendered = crate::parser::TemplateEngine::render(nodes, data);
+        self.paint_index.clear();
+        self.paint_seq = 0;
+        self.movable_ids.clear();
+        self.element_nodes.clear();
+        self.group_nodes.clear();
+        let rendered = self.expand_template(nodes, data, None);
         let mut taffy = TaffyTree::new();
+        let root_vars = Arc::new(self.stylesheet.root_variables());
+        self.pseudo_nodes.borrow_mut().clear();
         
         let mut render_nodes = Vec::new();
-        for node in &rendered {
-            if let Some(rn) = self.build_tree(&mut taffy, node) {
+        for (i, node) in rendered.iter().enumerate() {
+            if let Some(rn) = self.build_tree(&mut taffy, node, &root_vars, &i.to_string(), None) {
                 render_nodes.push(rn);
             }
         }
+        Self::resolve_inherited(&mut render_nodes, false, &None);
+        self.node_parents = Self::collect_parents(&render_nodes);
         
         let child_ids: Vec<NodeId> = render_nodes.iter().map(|n| n.taffy_node).collect();
         let root = taffy.new_with_children(
@@ -450,13 +1678,42 @@ impl WxmlRenderer {
         ).unwrap();
         
         taffy.compute_layout(root, Size::MAX_CONTENT).unwrap();
+        self.layout_generation += 1;
+        self.apply_animations(&mut render_nodes);
         
         for rn in &render_nodes {
             self.draw(canvas, &taffy, rn, 0.0, 0.0);
         }
     }
 
-    fn build_tree(&self, taffy: &mut TaffyTree, node: &WxmlNode) -> Option<RenderNode> {
+    /// css_vars 为父元素上可用的 CSS 变量，path 为节点在 WXML 树中的路径；
+    /// old 为上次构建时同位置的旧节点及其路径，自身未变化时其中未变化的子树直接复用
+    fn build_tree(
+        &self,
+        taffy: &mut TaffyTree,
+        node: &WxmlNode,
+        css_vars: &Arc<HashMap<String, String>>,
+        path: &str,
+        old: Option<(&WxmlNode, &str)>,
+    ) -> Option<RenderNode> {
+        let render_node = self.build_node(taffy, node, css_vars, path, old);
+        if let Some(rn) = &render_node {
+            self.built_paths.borrow_mut().insert(path.to_string(), rn.taffy_node);
+            let mut stats = self.build_stats.get();
+            stats.built += 1;
+            self.build_stats.set(stats);
+        }
+        render_node
+    }
+    
+    fn build_node(
+        &self,
+        taffy: &mut TaffyTree,
+        node: &WxmlNode,
+        css_vars: &Arc<HashMap<String, String>>,
+        path: &str,
+        old: Option<(&WxmlNode, &str)>,
+    ) -> Option<RenderNode> {
         let sf = self.scale_factor;
         
         if node.node_type == WxmlNodeType::Text {
@@ -488,6 +1745,9 @@ impl WxmlRenderer {
             screen_height: self.screen_height,
             stylesheet: &self.stylesheet,
             taffy,
+            css_vars: css_vars.clone(),
+            pseudo_state: self.pseudo_states.get(path).copied().unwrap_or_default(),
+            safe_area_insets: self.safe_area_insets,
         };
         
         let mut render_node = match tag {
@@ -511,56 +1771,332 @@ impl WxmlRenderer {
             "picker" => PickerComponent::build(node, &mut ctx),
             "picker-view" => PickerViewComponent::build(node, &mut ctx),
             "picker-view-column" => PickerViewColumnComponent::build(node, &mut ctx),
+            "movable-area" => MovableAreaComponent::build(node, &mut ctx),
+            "movable-view" => MovableViewComponent::build(node, &mut ctx),
+            "cover-view" => CoverViewComponent::build(node, &mut ctx),
+            "cover-image" => CoverImageComponent::build(node, &mut ctx),
             _ => ViewComponent::build(node, &mut ctx),
         };
         
-        if let Some(ref mut rn) = render_node {
-            if !Self::is_leaf_component(tag) {
-                let mut children = vec![];
-                for c in &node.children {
-                    if let Some(cr) = self.build_tree(ctx.taffy, c) { 
-                        children.push(cr); 
+        if let Some(ref mut rn) = render_node {
+            if !Self::is_leaf_component(tag) {
+                let mut children = vec![];
+                let child_vars = rn.style.css_vars.clone();
+                // 子节点的样式只取决于自身和父元素传下来的 CSS 变量；border-collapse 会按相邻关系改写子节点边框，不复用
+                let old = old.filter(|(o, _)| Self::same_element(o, node) && !rn.style.border_collapse);
+                let matches = old.map(|(o, _)| Self::match_children(&node.children, &o.children)).unwrap_or_default();
+                for (i, c) in node.children.iter().enumerate() {
+                    let old_child = old.zip(matches.get(i).copied().flatten())
+                        .map(|((o, old_path), (j, same))| (&o.children[j], format!("{}/{}", old_path, j), same));
+                    if let Some(cr) = self.build_or_reuse(ctx.taffy, c, old_child, &child_vars, &format!("{}/{}", path, i)) {
+                        children.push(cr);
+                    }
+                }
+                
+                // ::before / ::after 伪元素作为首尾子节点
+                if let Some(before) = PseudoElementComponent::build(node, "before", &mut ctx) {
+                    children.insert(0, before);
+                }
+                if let Some(after) = PseudoElementComponent::build(node, "after", &mut ctx) {
+                    children.push(after);
+                }
+                
+                if !children.is_empty() {
+                    let (mut ts, mut ns) = build_base_style(node, &mut ctx);
+                    
+                    // 对于 scroll-view，使用 Overflow::Visible 让子节点能够正确布局
+                    // 裁剪在渲染时通过 canvas.clip_rect 处理
+                    if tag == "scroll-view" {
+                        ts.overflow.x = taffy::style::Overflow::Visible;
+                        ts.overflow.y = taffy::style::Overflow::Visible;
+                        
+                        // scroll-x 的子元素横向排成一行
+                        if Self::is_scroll_x(rn) {
+                            ts.flex_direction = FlexDirection::Row;
+                            ts.flex_wrap = FlexWrap::NoWrap;
+                        }
+                        
+                        // 为 scroll-view 的子元素设置 flex-shrink: 0，防止被压缩
+                        for child in &children {
+                            if let Ok(mut style) = ctx.taffy.style(child.taffy_node).cloned() {
+                                style.flex_shrink = 0.0;
+                                ctx.taffy.set_style(child.taffy_node, style).ok();
+                            }
+                        }
+                    }
+                    
+                    if tag == "movable-view" {
+                        MovableViewComponent::apply_layout(&mut ts);
+                    }
+                    
+                    // video 的子节点是覆盖在画面上的 cover-view
+                    if tag == "video" {
+                        VideoComponent::apply_style(&mut ts, &mut ns, sf);
+                    }
+                    
+                    // 相邻的 inline / inline-block 子元素排成一行
+                    children = wrap_inline_runs(ctx.taffy, &ts, &ns, children);
+                    
+                    // text-align 作用于行内子元素（文本、图片、图标）
+                    Self::apply_inline_text_align(&mut ts, &ns, &children);
+                    
+                    if ns.border_collapse {
+                        collapse_child_borders(&mut children);
+                    }
+                    
+                    let child_ids: Vec<NodeId> = children.iter().map(|c| c.taffy_node).collect();
+                    let new_tn = ctx.taffy.new_with_children(ts, &child_ids).unwrap();
+                    ctx.taffy.remove(rn.taffy_node).ok();
+                    
+                    rn.taffy_node = new_tn;
+                    rn.children = children;
+                    // 更新样式（保留原有样式中已设置的值，但用新样式覆盖）
+                    rn.style = ns;
+                }
+            }
+            
+            // width: fit-content 等关键字依赖子树的内容尺寸，子树构建完成后再换算
+            resolve_intrinsic_width(ctx.taffy, rn.taffy_node, &rn.style);
+            
+            let hover_class = node.get_attr("hover-class").is_some_and(|c| !c.is_empty() && c != "none");
+            if hover_class || self.stylesheet.pseudo_class_rules_for(&get_classes(node), tag) != PseudoState::default() {
+                self.pseudo_nodes.borrow_mut().insert(rn.taffy_node, path.to_string());
+            }
+        }
+        
+        render_node
+    }
+    
+    /// 将容器的 text-align 转换为行内子元素在主轴/交叉轴上的对齐
+    /// 仅当所有子元素都是行内元素时生效，CSS 中显式设置的对齐方式优先
+    fn apply_inline_text_align(ts: &mut Style, ns: &NodeStyle, children: &[RenderNode]) {
+        let all_inline = children.iter().all(|c| {
+            matches!(c.tag.as_str(), "#text" | "text" | "image" | "icon") && !c.style.is_block
+        });
+        if !all_inline { return; }
+        
+        // justify 对最后一行不生效，单行内容按左对齐处理
+        let (justify, align) = match ns.text_align {
+            TextAlign::Center => (JustifyContent::Center, AlignItems::Center),
+            TextAlign::Right => (JustifyContent::FlexEnd, AlignItems::FlexEnd),
+            TextAlign::Left | TextAlign::Justify => return,
+        };
+        
+        match ts.flex_direction {
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                if ts.justify_content.is_none() {
+                    ts.justify_content = Some(justify);
+                }
+            }
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                if ts.align_items.is_none() {
+                    ts.align_items = Some(align);
+                }
+            }
+        }
+    }
+    
+    /// text 是否可选中（user-select 或旧版的 selectable）
+    fn is_selectable_text(node: &RenderNode) -> bool {
+        ["user-select", "selectable"].iter().any(|name| {
+            node.attrs.get(*name).map(|v| v == "true" || v == "{{true}}").unwrap_or(false)
+        })
+    }
+    
+    /// scroll-view 是否开启横向滚动（scroll-x）
+    fn is_scroll_x(node: &RenderNode) -> bool {
+        node.attrs.get("scroll-x").map(|v| v != "false" && v != "{{false}}").unwrap_or(false)
+    }
+    
+    /// scroll-into-view 属性变化时记录目标子元素的位置，找不到目标时下次渲染重试
+    fn check_scroll_into_view(&mut self, taffy: &TaffyTree, node: &RenderNode, id: &str, scroll_x: bool) {
+        let target = match node.attrs.get("scroll-into-view") {
+            Some(t) if !t.is_empty() => t,
+            _ => {
+                self.scroll_into_view_applied.remove(id);
+                return;
+            }
+        };
+        if self.scroll_into_view_applied.get(id) == Some(target) {
+            return;
+        }
+        if let Some((x, y)) = Self::find_descendant_offset(taffy, node, target) {
+            self.scroll_into_view_applied.insert(id.to_string(), target.clone());
+            self.pending_scroll_into_view.push(ScrollIntoView {
+                scroll_view_id: id.to_string(),
+                offset: if scroll_x { x } else { y } / self.scale_factor,
+                animated: Self::scroll_with_animation(node),
+            });
+        }
+    }
+    
+    /// scroll-top（scroll-x 时为 scroll-left）属性值变化时滚动到该位置
+    fn check_scroll_top(&mut self, node: &RenderNode, id: &str, scroll_x: bool) {
+        let attr = if scroll_x { "scroll-left" } else { "scroll-top" };
+        let Some(value) = node.attrs.get(attr).filter(|v| !v.is_empty()) else {
+            self.scroll_top_applied.remove(id);
+            return;
+        };
+        if self.scroll_top_applied.get(id) == Some(value) {
+            return;
+        }
+        let Ok(offset) = value.trim().trim_end_matches("px").parse::<f32>() else { return };
+        self.scroll_top_applied.insert(id.to_string(), value.clone());
+        self.pending_scroll_into_view.push(ScrollIntoView {
+            scroll_view_id: id.to_string(),
+            offset,
+            animated: Self::scroll_with_animation(node),
+        });
+    }
+    
+    /// focus 属性变化时记录聚焦 / 失焦请求；首次渲染时只有 focus 为 true 才聚焦
+    fn check_focus(&mut self, node: &RenderNode, id: &str) {
+        let focus = node.attrs.get("focus").map(|v| v == "true" || v == "{{true}}").unwrap_or(false);
+        if self.focus_applied.insert(id.to_string(), focus).unwrap_or(false) != focus {
+            self.pending_focus.push((id.to_string(), focus));
+        }
+    }
+    
+    /// scroll-with-animation：程序触发的滚动是否使用动画，默认 false
+    fn scroll_with_animation(node: &RenderNode) -> bool {
+        node.attrs.get("scroll-with-animation")
+            .map(|v| v != "false" && v != "{{false}}")
+            .unwrap_or(false)
+    }
+    
+    /// 查找 id 匹配的后代节点，返回其相对 node 的位置（物理像素）
+    fn find_descendant_offset(taffy: &TaffyTree, node: &RenderNode, target: &str) -> Option<(f32, f32)> {
+        for child in &node.children {
+            let Ok(layout) = taffy.layout(child.taffy_node) else { continue };
+            if child.attrs.get("id").map(|s| s.as_str()) == Some(target) {
+                return Some((layout.location.x, layout.location.y));
+            }
+            if let Some((x, y)) = Self::find_descendant_offset(taffy, child, target) {
+                return Some((layout.location.x + x, layout.location.y + y));
+            }
+        }
+        None
+    }
+    
+    /// scroll-view 内容尺寸（物理像素）：子节点右边界和底部的最大值
+    fn scroll_content_size(taffy: &TaffyTree, node: &RenderNode) -> (f32, f32) {
+        let mut size = (0.0f32, 0.0f32);
+        for child in &node.children {
+            if let Ok(layout) = taffy.layout(child.taffy_node) {
+                size.0 = size.0.max(layout.location.x + layout.size.width);
+                size.1 = size.1.max(layout.location.y + layout.size.height);
+            }
+        }
+        size
+    }
+    
+    fn is_leaf_component(tag: &str) -> bool {
+        matches!(tag, 
+            "text" | "button" | "icon" | "progress" | "switch" | 
+            "checkbox" | "radio" | "slider" | "input" | "textarea" | "image" | "canvas" |
+            "rich-text" | "picker" | "picker-view-column" | "cover-image"
+        )
+    }
+    
+    /// 主内容绘制过程中遇到 cover 组件时记录下来，返回 true 表示已延后
+    fn defer_cover(&mut self, node: &RenderNode, ox: f32, oy: f32, inherited_color: Color) -> bool {
+        if self.drawing_cover_layer || !is_cover_component(&node.tag) {
+            return false;
+        }
+        self.cover_layer.push(CoverNode { node: self.detached(node), ox, oy, inherited_color });
+        true
+    }
+    
+    /// 延后绘制的节点副本，带上所在透明度图层的不透明度
+    fn detached(&self, node: &RenderNode) -> RenderNode {
+        let mut node = node.clone();
+        node.style.opacity *= self.group_opacity;
+        node
+    }
+    
+    /// 普通流绘制过程中遇到 absolute 定位元素时记录下来，返回 true 表示已延后。
+    /// 负 z-index 的元素留在原位（位于普通流内容之下），scroll-view 内、transform 图层内和 cover 层中的不延后
+    fn defer_positioned(&mut self, taffy: &TaffyTree, node: &RenderNode, ox: f32, oy: f32, inherited_color: Color) -> bool {
+        if self.in_scroll_view || self.in_transform_layer || self.drawing_cover_layer || node.style.is_fixed || node.style.z_index < 0
+            || self.drawing_positioned == Some(node.taffy_node) {
+            return false;
+        }
+        if taffy.style(node.taffy_node).map(|s| s.position != Position::Absolute).unwrap_or(true) {
+            return false;
+        }
+        self.positioned_layer.push(CoverNode { node: self.detached(node), ox, oy, inherited_color });
+        true
+    }
+    
+    /// 绘制 input / textarea，聚焦时把排版结果（横向偏移或多行布局）写回 focused_input 用于点击定位
+    fn draw_input(
+        &self,
+        canvas: &mut Canvas,
+        node: &RenderNode,
+        component_id: &str,
+        (x, y, w, h): (f32, f32, f32, f32),
+        interaction: &mut InteractionManager,
+    ) {
+        let sf = self.scale_factor;
+        let focused = interaction.focused_input.as_ref()
+            .map(|f| f.id == component_id)
+            .unwrap_or(false);
+        let (cursor_pos, selection, scroll_top, preedit) = match interaction.focused_input.as_ref().filter(|_| focused) {
+            Some(f) => (f.cursor_pos, f.get_selection_range(), f.scroll_top, f.preedit.clone()),
+            None => (0, None, 0.0, String::new()),
+        };
+        
+        if node.tag == "textarea" {
+            if let Some(tr) = self.text_renderer.as_ref() {
+                let composed = compose_preedit(node, cursor_pos, &preedit);
+                let (draw_node, draw_cursor, draw_selection, preedit_range) = match &composed {
+                    Some((composed, range)) => (composed, range.1, None, Some(*range)),
+                    None => (node, cursor_pos, selection, None),
+                };
+                let layout = InputComponent::layout_textarea(draw_node, tr, w, h, sf, draw_cursor, scroll_top);
+                let state = TextareaState { focused, cursor_pos: draw_cursor, selection: draw_selection, preedit: preedit_range };
+                InputComponent::draw_textarea(draw_node, canvas, tr, (x, y, w, h), sf, &state, &layout);
+                if let Some(input) = interaction.focused_input.as_mut().filter(|_| focused) {
+                    // 组合期间的换行包含未提交文本，不能用于光标定位
+                    if composed.is_none() {
+                        input.lines = layout.lines;
+                        input.line_height = layout.line_height;
                     }
+                    input.scroll_top = layout.scroll_top;
                 }
+                return;
+            }
+        }
+        
+        InputComponent::draw_with_selection(
+            node, canvas, self.text_renderer.as_ref(), 
+            x, y, w, h, sf, focused, cursor_pos, selection, &preedit
+        );
+        
+        // 更新 text_offset（用于点击位置计算）
+        if focused {
+            if let Some(tr) = self.text_renderer.as_ref() {
+                let font_size = node.style.font_size * sf;
+                let padding_left = 12.0 * sf;
+                let padding_right = 12.0 * sf;
+                let available_width = w - padding_left - padding_right;
                 
-                if !children.is_empty() {
-                    let child_ids: Vec<NodeId> = children.iter().map(|c| c.taffy_node).collect();
-                    let (mut ts, ns) = build_base_style(node, &mut ctx);
+                let text_width = tr.measure_text(&node.text, font_size);
+                let mut text_offset = 0.0;
+                if text_width > available_width {
+                    let cursor_text: String = node.text.chars().take(cursor_pos).collect();
+                    let cursor_x_in_text = tr.measure_text(&cursor_text, font_size);
                     
-                    // 对于 scroll-view，使用 Overflow::Visible 让子节点能够正确布局
-                    // 裁剪在渲染时通过 canvas.clip_rect 处理
-                    if tag == "scroll-view" {
-                        ts.overflow.x = taffy::style::Overflow::Visible;
-                        ts.overflow.y = taffy::style::Overflow::Visible;
-                        
-                        // 为 scroll-view 的子元素设置 flex-shrink: 0，防止被压缩
-                        for child in &children {
-                            if let Ok(mut style) = ctx.taffy.style(child.taffy_node).cloned() {
-                                style.flex_shrink = 0.0;
-                                ctx.taffy.set_style(child.taffy_node, style).ok();
-                            }
-                        }
+                    if cursor_x_in_text > available_width {
+                        text_offset = available_width - cursor_x_in_text - font_size;
                     }
-                    
-                    let new_tn = ctx.taffy.new_with_children(ts, &child_ids).unwrap();
-                    
-                    rn.taffy_node = new_tn;
-                    rn.children = children;
-                    // 更新样式（保留原有样式中已设置的值，但用新样式覆盖）
-                    rn.style = ns;
+                }
+                
+                if let Some(input) = &mut interaction.focused_input {
+                    input.text_offset = text_offset;
                 }
             }
         }
-        
-        render_node
-    }
-    
-    fn is_leaf_component(tag: &str) -> bool {
-        matches!(tag, 
-            "text" | "button" | "icon" | "progress" | "switch" | 
-            "checkbox" | "radio" | "slider" | "input" | "textarea" | "image" | "video" | "canvas" |
-            "rich-text" | "picker" | "picker-view-column"
-        )
     }
     
     fn get_component_id(node: &RenderNode, bounds: &GeoRect) -> String {
@@ -588,12 +2124,35 @@ impl WxmlRenderer {
             return;
         }
         
+        // cover 组件延后到顶层绘制
+        if self.defer_cover(node, ox, oy, Color::BLACK) {
+            return;
+        }
+        if self.defer_positioned(taffy, node, ox, oy, Color::BLACK) {
+            return;
+        }
+        
         let sf = self.scale_factor;
+        if self.draw_opacity_group(canvas, node, || Self::paint_bounds(taffy, node, ox, oy, sf), |r, layer, flat| {
+            r.draw_with_interaction(layer, taffy, flat, ox, oy, interaction, scroll_offset, viewport_height)
+        }) {
+            return;
+        }
+        if self.draw_in_transform_layer(canvas, taffy, node, ox, oy, |r, layer, flat| {
+            r.draw_with_interaction(layer, taffy, flat, ox, oy, interaction, scroll_offset, viewport_height)
+        }) {
+            return;
+        }
+        
         let layout = taffy.layout(node.taffy_node).unwrap();
         let x = ox + layout.location.x;
         let y = oy + layout.location.y;
         let w = layout.size.width;
         let h = layout.size.height;
+        let y = y + self.sticky_offset(node, y, h);
+        let ((x, y, w, h), (cx, cy)) = Self::transformed_bounds(node, x, y, w, h, sf);
+        let (mx, my) = self.movable_offset(taffy, node, (x, y, w, h), interaction);
+        let (x, y, cx, cy) = (x + mx, y + my, cx + mx, cy + my);
         // 渲染整个内容到 canvas，滚动在 present_to_buffer 中处理
         
         let logical_bounds = GeoRect::new(x / sf, y / sf, w / sf, h / sf);
@@ -604,7 +2163,8 @@ impl WxmlRenderer {
         let mut node_to_draw = node.clone();
         if let Some(state) = interaction.get_state(&component_id) {
             match node.tag.as_str() {
-                "checkbox" | "switch" => {
+                "switch" => SwitchComponent::set_checked(&mut node_to_draw, state.checked),
+                "checkbox" => {
                     node_to_draw.style.custom_data = if state.checked { 1.0 } else { 0.0 };
                     // 更新颜色
                     let checkbox_color = node.attrs.get("color")
@@ -634,10 +2194,7 @@ impl WxmlRenderer {
                 }
                 "slider" => {
                     if let Ok(v) = state.value.parse::<f32>() {
-                        node_to_draw.style.custom_data = v / 100.0;
-                        if !node_to_draw.text.is_empty() {
-                            node_to_draw.text = format!("{}", v as i32);
-                        }
+                        SliderComponent::set_value(&mut node_to_draw, v);
                     }
                 }
                 "input" | "textarea" => {
@@ -655,7 +2212,7 @@ impl WxmlRenderer {
                         node_to_draw.style.text_color = Some(Color::from_hex(0xBFBFBF));
                     } else {
                         // 有输入值或聚焦时显示实际值（聚焦时即使为空也不显示 placeholder）
-                        node_to_draw.text = state.value.clone();
+                        node_to_draw.text = display_value(&node.attrs, &state.value);
                         node_to_draw.style.text_color = Some(Color::BLACK);
                     }
                 }
@@ -670,7 +2227,7 @@ impl WxmlRenderer {
                 node_to_draw.text = placeholder;
                 node_to_draw.style.text_color = Some(Color::from_hex(0xBFBFBF));
             } else {
-                node_to_draw.text = initial_value;
+                node_to_draw.text = display_value(&node.attrs, &initial_value);
             }
         }
         
@@ -679,46 +2236,10 @@ impl WxmlRenderer {
 
         // 绘制组件 - 特殊处理 input 和 button 组件
         match node.tag.as_str() {
+                _ if node.style.hidden => {}
                 "input" | "textarea" => {
-                    let focused = interaction.focused_input.as_ref()
-                        .map(|f| f.id == component_id)
-                        .unwrap_or(false);
-                    let (cursor_pos, selection) = if focused {
-                        let f = interaction.focused_input.as_ref().unwrap();
-                        (f.cursor_pos, f.get_selection_range())
-                    } else {
-                        (0, None)
-                    };
-                    InputComponent::draw_with_selection(
-                        &node_to_draw, canvas, self.text_renderer.as_ref(), 
-                        x, y, w, h, sf, focused, cursor_pos, selection
-                    );
-                    
-                    // 更新 text_offset（用于点击位置计算）
-                    if focused {
-                        if let Some(tr) = self.text_renderer.as_ref() {
-                            let font_size = node_to_draw.style.font_size * sf;
-                            let padding_left = 12.0 * sf;
-                            let padding_right = 12.0 * sf;
-                            let available_width = w - padding_left - padding_right;
-                            
-                            let text_width = tr.measure_text(&node_to_draw.text, font_size);
-                            let mut text_offset = 0.0;
-                        if text_width > available_width {
-                            let cursor_text: String = node_to_draw.text.chars().take(cursor_pos).collect();
-                            let cursor_x_in_text = tr.measure_text(&cursor_text, font_size);
-                            
-                            if cursor_x_in_text > available_width {
-                                text_offset = available_width - cursor_x_in_text - font_size;
-                            }
-                        }
-                        
-                        if let Some(input) = &mut interaction.focused_input {
-                            input.text_offset = text_offset;
-                        }
-                    }
+                    self.draw_input(canvas, &node_to_draw, &component_id, (x, y, w, h), interaction);
                 }
-            }
             "button" => {
                 let pressed = interaction.is_button_pressed(&component_id);
                 ButtonComponent::draw_with_state(
@@ -726,25 +2247,32 @@ impl WxmlRenderer {
                     x, y, w, h, sf, pressed
                 );
             }
+            "text" if interaction.text_selection.as_ref().map(|t| t.id == component_id).unwrap_or(false) => {
+                // 选中的文本：先按行画选中背景
+                if let (Some(tr), Some(selection)) = (self.text_renderer.as_ref(), interaction.text_selection.as_ref()) {
+                    let geometry = SelectionGeometry { x, y, width: w, start: selection.start, end: selection.end };
+                    TextComponent::draw_selection(&node_to_draw, canvas, tr, sf, &geometry);
+                }
+                self.draw_component(canvas, &node_to_draw, x, y, w, h, sf);
+            }
             _ => {
                 self.draw_component(canvas, &node_to_draw, x, y, w, h, sf);
+                self.note_animated_image(&node_to_draw, x, y, w, h);
             }
         }
         
         // 绘制子节点
+        let clipped = Self::push_overflow_clip(canvas, node, x, y, w, h);
         if !Self::is_leaf_component(&node.tag) {
             let is_scroll_view = node.tag == "scroll-view";
             let mut child_offset_y = 0.0;
             let scroll_position: f32;
             
             if is_scroll_view {
-                // 计算 scroll-view 内容高度
-                let mut content_height = 0.0f32;
-                for child in &node.children {
-                    let child_layout = taffy.layout(child.taffy_node).unwrap();
-                    let child_bottom = child_layout.location.y + child_layout.size.height;
-                    content_height = content_height.max(child_bottom);
-                }
+                // 计算 scroll-view 内容尺寸
+                let (content_width, content_height) = Self::scroll_content_size(taffy, node);
+                let content_width = content_width.max(w);
+                let scroll_x = Self::is_scroll_x(node);
                 
                 // 获取滚动位置
                 scroll_position = if let Some(controller) = interaction.get_scroll_controller(&component_id) {
@@ -757,7 +2285,7 @@ impl WxmlRenderer {
                 let cache_needs_render = {
                     let cache = self.scroll_cache.get_or_create(
                         &component_id,
-                        w as u32,
+                        content_width.ceil() as u32,
                         content_height.ceil() as u32,
                         (w / sf) as u32,
                         (h / sf) as u32,
@@ -767,13 +2295,13 @@ impl WxmlRenderer {
                 
                 if cache_needs_render {
                     // 创建临时 Canvas 用于渲染
-                    let mut temp_canvas = Canvas::new(w as u32, content_height.ceil() as u32);
+                    let mut temp_canvas = Canvas::new(content_width.ceil() as u32, content_height.ceil() as u32);
                     temp_canvas.clear(node.style.background_color.unwrap_or(Color::TRANSPARENT));
                     
                     let text_color = node.style.text_color.unwrap_or(Color::BLACK);
                     
                     // 渲染所有子元素到临时 Canvas
-                    for child in &node.children {
+                    for child in Self::paint_order(&node.children) {
                         self.draw_child_to_cache(&mut temp_canvas, taffy, child, 0.0, 0.0, text_color, interaction);
                     }
                     
@@ -790,33 +2318,64 @@ impl WxmlRenderer {
                 canvas.clip_rect(GeoRect::new(x, y, w, h));
                 
                 if let Some(cache) = self.scroll_cache.get(&component_id) {
-                    cache.blit_to(canvas, scroll_position, x, y, sf);
+                    if scroll_x {
+                        cache.blit_to_offset(canvas, scroll_position, 0.0, x, y, sf);
+                    } else {
+                        cache.blit_to(canvas, scroll_position, x, y, sf);
+                    }
+                }
+                
+                // 缓存中的 sticky 元素停在原位，吸顶时在缓存内容之上重新绘制
+                if !scroll_x {
+                    let saved = (self.sticky_edge, self.sticky_limit, self.in_scroll_view);
+                    self.sticky_edge = y;
+                    self.in_scroll_view = true;
+                    let text_color = node.style.text_color.unwrap_or(Color::BLACK);
+                    let mut ctx = StuckContext { taffy, interaction, scroll_offset, viewport_height };
+                    self.draw_stuck_children(canvas, node, (cx, cy - scroll_position * sf, content_height.max(h)), text_color, &mut ctx);
+                    (self.sticky_edge, self.sticky_limit, self.in_scroll_view) = saved;
                 }
                 
                 canvas.restore();
                 
                 // 注册子元素的交互区域（需要考虑滚动偏移）
-                child_offset_y = -scroll_position * sf;
                 let text_color = node.style.text_color.unwrap_or(Color::BLACK);
-                for child in &node.children {
-                    self.register_child_interactions(taffy, child, x, y + child_offset_y, text_color, interaction, scroll_position, h / sf);
+                if scroll_x {
+                    // 横向滚动：只注册与视口横向相交的子元素
+                    let child_offset_x = -scroll_position * sf;
+                    for child in Self::paint_order(&node.children) {
+                        let child_layout = taffy.layout(child.taffy_node).unwrap();
+                        let left = child_layout.location.x + child_offset_x;
+                        if left + child_layout.size.width >= 0.0 && left <= w {
+                            self.register_child_interactions(taffy, child, cx + child_offset_x, cy, text_color, interaction, 0.0, f32::MAX);
+                        }
+                    }
+                } else {
+                    child_offset_y = -scroll_position * sf;
+                    for child in Self::paint_order(&node.children) {
+                        self.register_child_interactions(taffy, child, cx, cy + child_offset_y, text_color, interaction, scroll_position, h / sf);
+                    }
                 }
             } else {
                 let text_color = node.style.text_color.unwrap_or(Color::BLACK);
-                for child in &node.children { 
-                    self.draw_child_with_interaction(canvas, taffy, child, x, y + child_offset_y, text_color, interaction, scroll_offset, viewport_height); 
+                let saved_limit = std::mem::replace(&mut self.sticky_limit, y + h);
+                for child in Self::paint_order(&node.children) { 
+                    self.draw_child_with_interaction(canvas, taffy, child, cx, cy + child_offset_y, text_color, interaction, scroll_offset, viewport_height); 
                 }
+                self.sticky_limit = saved_limit;
             }
         }
+        if clipped { canvas.pop_clip(); }
 
         // 记录事件绑定
-        for (et, h, d, is_catch) in &node.events {
+        for (et, h, d, is_catch) in Self::visible_events(node) {
             self.event_bindings.push(EventBinding { 
                 event_type: et.clone(), 
                 handler: h.clone(), 
                 data: d.clone(), 
                 bounds: logical_bounds,
                 is_catch: *is_catch,
+                node: node.taffy_node,
             });
         }
     }
@@ -833,11 +2392,24 @@ impl WxmlRenderer {
         interaction: &InteractionManager,
     ) {
         let sf = self.scale_factor;
+        if Self::draw_in_opacity_layer(canvas, node, || Self::paint_bounds(taffy, node, ox, oy, sf), |layer, flat| {
+            self.draw_child_to_cache(layer, taffy, flat, ox, oy, inherited_color, interaction)
+        }) {
+            return;
+        }
         let layout = taffy.layout(node.taffy_node).unwrap();
         let x = ox + layout.location.x;
         let y = oy + layout.location.y;
         let w = layout.size.width;
         let h = layout.size.height;
+        let ((x, y, w, h), (cx, cy)) = Self::transformed_bounds(node, x, y, w, h, sf);
+        let (mx, my) = match node.tag.as_str() {
+            "movable-view" => interaction.movable_views.get(&Self::movable_id(node, x, y, w, h, sf))
+                .map(|v| (v.position().0 * sf, v.position().1 * sf))
+                .unwrap_or((0.0, 0.0)),
+            _ => (0.0, 0.0),
+        };
+        let (x, y, cx, cy) = (x + mx, y + my, cx + mx, cy + my);
         
         let logical_bounds = GeoRect::new(x / sf, y / sf, w / sf, h / sf);
         let component_id = Self::get_component_id(node, &logical_bounds);
@@ -851,7 +2423,8 @@ impl WxmlRenderer {
         // 应用交互状态
         if let Some(state) = interaction.get_state(&component_id) {
             match node.tag.as_str() {
-                "checkbox" | "switch" => {
+                "switch" => SwitchComponent::set_checked(&mut node_to_draw, state.checked),
+                "checkbox" => {
                     node_to_draw.style.custom_data = if state.checked { 1.0 } else { 0.0 };
                     let checkbox_color = node.attrs.get("color")
                         .and_then(|c| super::components::parse_color_str(c))
@@ -879,10 +2452,7 @@ impl WxmlRenderer {
                 }
                 "slider" => {
                     if let Ok(v) = state.value.parse::<f32>() {
-                        node_to_draw.style.custom_data = v / 100.0;
-                        if !node_to_draw.text.is_empty() {
-                            node_to_draw.text = format!("{}", v as i32);
-                        }
+                        SliderComponent::set_value(&mut node_to_draw, v);
                     }
                 }
                 _ => {}
@@ -893,11 +2463,13 @@ impl WxmlRenderer {
         self.draw_component(canvas, &node_to_draw, x, y, w, h, sf);
         
         // 递归绘制子节点
+        let clipped = Self::push_overflow_clip(canvas, node, x, y, w, h);
         if !Self::is_leaf_component(&node.tag) {
-            for child in &node.children {
-                self.draw_child_to_cache(canvas, taffy, child, x, y, text_color, interaction);
+            for child in Self::paint_order(&node.children) {
+                self.draw_child_to_cache(canvas, taffy, child, cx, cy, text_color, interaction);
             }
         }
+        if clipped { canvas.pop_clip(); }
     }
     
     /// 注册 scroll-view 子元素的交互区域
@@ -918,6 +2490,9 @@ impl WxmlRenderer {
         let y = oy + layout.location.y;
         let w = layout.size.width;
         let h = layout.size.height;
+        let ((x, y, w, h), (cx, cy)) = Self::transformed_bounds(node, x, y, w, h, sf);
+        let (mx, my) = self.movable_offset(taffy, node, (x, y, w, h), interaction);
+        let (x, y, cx, cy) = (x + mx, y + my, cx + mx, cy + my);
         
         // 检查是否在可见区域内
         let logical_y = y / sf;
@@ -938,19 +2513,20 @@ impl WxmlRenderer {
         
         // 递归注册子元素
         if !Self::is_leaf_component(&node.tag) {
-            for child in &node.children {
-                self.register_child_interactions(taffy, child, x, y, text_color, interaction, scroll_position, viewport_height);
+            for child in Self::paint_order(&node.children) {
+                self.register_child_interactions(taffy, child, cx, cy, text_color, interaction, scroll_position, viewport_height);
             }
         }
         
         // 记录事件绑定
-        for (et, h, d, is_catch) in &node.events {
+        for (et, h, d, is_catch) in Self::visible_events(node) {
             self.event_bindings.push(EventBinding {
                 event_type: et.clone(),
                 handler: h.clone(),
                 data: d.clone(),
                 bounds: logical_bounds,
                 is_catch: *is_catch,
+                node: node.taffy_node,
             });
         }
     }
@@ -972,12 +2548,35 @@ impl WxmlRenderer {
             return;
         }
         
+        // cover 组件延后到顶层绘制
+        if self.defer_cover(node, ox, oy, inherited_color) {
+            return;
+        }
+        if self.defer_positioned(taffy, node, ox, oy, inherited_color) {
+            return;
+        }
+        
         let sf = self.scale_factor;
+        if self.draw_opacity_group(canvas, node, || Self::paint_bounds(taffy, node, ox, oy, sf), |r, layer, flat| {
+            r.draw_child_with_interaction(layer, taffy, flat, ox, oy, inherited_color, interaction, scroll_offset, viewport_height)
+        }) {
+            return;
+        }
+        if self.draw_in_transform_layer(canvas, taffy, node, ox, oy, |r, layer, flat| {
+            r.draw_child_with_interaction(layer, taffy, flat, ox, oy, inherited_color, interaction, scroll_offset, viewport_height)
+        }) {
+            return;
+        }
+        
         let layout = taffy.layout(node.taffy_node).unwrap();
         let x = ox + layout.location.x;
         let y = oy + layout.location.y;
         let w = layout.size.width;
         let h = layout.size.height;
+        let y = y + self.sticky_offset(node, y, h);
+        let ((x, y, w, h), (cx, cy)) = Self::transformed_bounds(node, x, y, w, h, sf);
+        let (mx, my) = self.movable_offset(taffy, node, (x, y, w, h), interaction);
+        let (x, y, cx, cy) = (x + mx, y + my, cx + mx, cy + my);
         
         let logical_bounds = GeoRect::new(x / sf, y / sf, w / sf, h / sf);
 
@@ -999,7 +2598,8 @@ impl WxmlRenderer {
             // 应用交互状态
             if let Some(state) = interaction.get_state(&component_id) {
                 match node.tag.as_str() {
-                    "checkbox" | "switch" => {
+                    "switch" => SwitchComponent::set_checked(&mut modified, state.checked),
+                    "checkbox" => {
                         modified.style.custom_data = if state.checked { 1.0 } else { 0.0 };
                         let checkbox_color = node.attrs.get("color")
                             .and_then(|c| super::components::parse_color_str(c))
@@ -1027,10 +2627,7 @@ impl WxmlRenderer {
                     }
                     "slider" => {
                         if let Ok(v) = state.value.parse::<f32>() {
-                            modified.style.custom_data = v / 100.0;
-                            if !modified.text.is_empty() {
-                                modified.text = format!("{}", v as i32);
-                            }
+                            SliderComponent::set_value(&mut modified, v);
                         }
                     }
                     "input" | "textarea" => {
@@ -1043,7 +2640,7 @@ impl WxmlRenderer {
                             modified.text = placeholder;
                             modified.style.text_color = Some(Color::from_hex(0xBFBFBF));
                         } else {
-                            modified.text = state.value.clone();
+                            modified.text = display_value(&node.attrs, &state.value);
                             modified.style.text_color = Some(Color::BLACK);
                         }
                     }
@@ -1061,7 +2658,7 @@ impl WxmlRenderer {
                 modified.text = placeholder;
                 modified.style.text_color = Some(Color::from_hex(0xBFBFBF));
             } else {
-                modified.text = initial_value;
+                modified.text = display_value(&node.attrs, &initial_value);
             }
             std::borrow::Cow::Owned(modified)
         } else {
@@ -1073,46 +2670,9 @@ impl WxmlRenderer {
 
         // 绘制组件 - 特殊处理 input、button 和有点击事件的 view 组件
         match node.tag.as_str() {
+                _ if node.style.hidden => {}
                 "input" | "textarea" => {
-                    let focused = interaction.focused_input.as_ref()
-                        .map(|f| f.id == component_id)
-                        .unwrap_or(false);
-                    let (cursor_pos, selection) = if focused {
-                        let f = interaction.focused_input.as_ref().unwrap();
-                        (f.cursor_pos, f.get_selection_range())
-                    } else {
-                        (0, None)
-                    };
-                    InputComponent::draw_with_selection(
-                        &node_to_draw, canvas, self.text_renderer.as_ref(), 
-                        x, y, w, h, sf, focused, cursor_pos, selection
-                    );
-                    
-                    // 更新 text_offset（用于点击位置计算）
-                    if focused {
-                        if let Some(tr) = self.text_renderer.as_ref() {
-                            let font_size = node_to_draw.style.font_size * sf;
-                            let padding_left = 12.0 * sf;
-                            let padding_right = 12.0 * sf;
-                            let available_width = w - padding_left - padding_right;
-                            
-                            let text_width = tr.measure_text(&node_to_draw.text, font_size);
-                            let mut text_offset = 0.0;
-                            
-                            if text_width > available_width {
-                                let cursor_text: String = node_to_draw.text.chars().take(cursor_pos).collect();
-                                let cursor_x_in_text = tr.measure_text(&cursor_text, font_size);
-                                
-                                if cursor_x_in_text > available_width {
-                                    text_offset = available_width - cursor_x_in_text - font_size;
-                                }
-                            }
-                            
-                            if let Some(input) = &mut interaction.focused_input {
-                                input.text_offset = text_offset;
-                            }
-                        }
-                    }
+                    self.draw_input(canvas, &node_to_draw, &component_id, (x, y, w, h), interaction);
                 }
                 "button" => {
                 let pressed = interaction.is_button_pressed(&component_id);
@@ -1123,21 +2683,27 @@ impl WxmlRenderer {
             }
             _ => {
                 self.draw_component(canvas, &node_to_draw, x, y, w, h, sf);
+                self.note_animated_image(&node_to_draw, x, y, w, h);
             }
         }
         
+        let clipped = Self::push_overflow_clip(canvas, node, x, y, w, h);
         if !Self::is_leaf_component(&node.tag) {
             let is_scroll_view = node.tag == "scroll-view";
             let mut child_offset_y = 0.0;
             let scroll_position: f32;
             
+            let scroll_x = is_scroll_view && Self::is_scroll_x(node);
+            let mut child_offset_x = 0.0;
+            
             if is_scroll_view {
                 canvas.save();
                 canvas.clip_rect(GeoRect::new(x, y, w, h));
                 
                 scroll_position = if let Some(controller) = interaction.get_scroll_controller(&component_id) {
                     let pos = controller.get_position();
-                    child_offset_y = -pos * sf; // 转换为物理像素
+                    // 转换为物理像素
+                    if scroll_x { child_offset_x = -pos * sf; } else { child_offset_y = -pos * sf; }
                     pos
                 } else {
                     0.0
@@ -1145,45 +2711,112 @@ impl WxmlRenderer {
             } else {
                 scroll_position = 0.0;
             }
+            
+            // 子元素中的 sticky 元素停留在本元素内；纵向 scroll-view 内以其可见区域顶部为吸顶基准
+            let saved_sticky = (self.sticky_edge, self.sticky_limit, self.in_scroll_view);
+            self.sticky_limit = if is_scroll_view && !scroll_x { y + child_offset_y + h.max(Self::scroll_content_size(taffy, node).1) } else { y + h };
+            if is_scroll_view && !scroll_x { self.sticky_edge = y; }
+            self.in_scroll_view |= is_scroll_view;
 
             // 对于 scroll-view，只渲染可见区域内的子元素（视口裁剪优化）
-            if is_scroll_view {
+            if scroll_x {
+                let viewport_left = scroll_position * sf;
+                let viewport_right = viewport_left + w;
+                
+                for child in Self::paint_order(&node.children) {
+                    let child_layout = taffy.layout(child.taffy_node).unwrap();
+                    let child_left = child_layout.location.x;
+                    let child_right = child_left + child_layout.size.width;
+                    
+                    if child_right >= viewport_left && child_left <= viewport_right {
+                        self.draw_child_with_interaction(canvas, taffy, child, cx + child_offset_x, cy, text_color, interaction, scroll_offset, viewport_height);
+                    }
+                }
+            } else if is_scroll_view {
                 let viewport_top = scroll_position * sf;
                 let viewport_bottom = viewport_top + h;
                 
-                for child in &node.children {
+                for child in Self::paint_order(&node.children) {
                     let child_layout = taffy.layout(child.taffy_node).unwrap();
                     let child_top = child_layout.location.y;
                     let child_bottom = child_top + child_layout.size.height;
                     
-                    // 只渲染与视口相交的子元素
-                    if child_bottom >= viewport_top && child_top <= viewport_bottom {
-                        self.draw_child_with_interaction(canvas, taffy, child, x, y + child_offset_y, text_color, interaction, scroll_offset, viewport_height); 
+                    // 只渲染与视口相交的子元素（sticky 元素吸顶时仍在视口内）
+                    if (child_bottom >= viewport_top || child.style.is_sticky) && child_top <= viewport_bottom {
+                        self.draw_child_with_interaction(canvas, taffy, child, cx, cy + child_offset_y, text_color, interaction, scroll_offset, viewport_height); 
                     }
                 }
             } else {
-                for child in &node.children { 
-                    self.draw_child_with_interaction(canvas, taffy, child, x, y + child_offset_y, text_color, interaction, scroll_offset, viewport_height); 
+                for child in Self::paint_order(&node.children) { 
+                    self.draw_child_with_interaction(canvas, taffy, child, cx, cy + child_offset_y, text_color, interaction, scroll_offset, viewport_height); 
                 }
             }
+            (self.sticky_edge, self.sticky_limit, self.in_scroll_view) = saved_sticky;
 
             if is_scroll_view {
                 canvas.restore();
             }
         }
+        if clipped { canvas.pop_clip(); }
 
-        for (et, h, d, is_catch) in &node.events {
+        for (et, h, d, is_catch) in Self::visible_events(node) {
             self.event_bindings.push(EventBinding { 
                 event_type: et.clone(), 
                 handler: h.clone(), 
                 data: d.clone(), 
                 bounds: logical_bounds,
                 is_catch: *is_catch,
+                node: node.taffy_node,
             });
         }
     }
     
+    /// sticky 元素为停在吸顶位置需要的纵向偏移：超过父元素底部后随父元素一起移出
+    fn sticky_offset(&self, node: &RenderNode, y: f32, h: f32) -> f32 {
+        let Some(top) = node.style.fixed_top.filter(|_| node.style.is_sticky) else { return 0.0 };
+        let target = self.sticky_edge + top;
+        if y >= target {
+            return 0.0;
+        }
+        (target - y).min(self.sticky_limit - h - y).max(0.0)
+    }
+    
+    /// 子元素的绘制顺序：按 z-index 从低到高，相同时 sticky 元素在普通流元素之上，其余保持文档顺序。
+    /// 交互元素按绘制顺序注册，命中测试时后绘制的（上层）优先
+    fn paint_order(children: &[RenderNode]) -> Vec<&RenderNode> {
+        let mut ordered: Vec<&RenderNode> = children.iter().collect();
+        ordered.sort_by_key(|c| (c.style.z_index, c.style.is_sticky));
+        ordered
+    }
+    
+    /// 在 scroll-view 缓存内容之上绘制处于吸顶状态的 sticky 元素，(ox, oy) 为 node 的内容原点
+    fn draw_stuck_children(
+        &mut self,
+        canvas: &mut Canvas,
+        node: &RenderNode,
+        (ox, oy, h): (f32, f32, f32),
+        inherited_color: Color,
+        ctx: &mut StuckContext,
+    ) {
+        let text_color = node.style.text_color.unwrap_or(inherited_color);
+        for child in &node.children {
+            let Ok(layout) = ctx.taffy.layout(child.taffy_node) else { continue };
+            let (child_y, child_h) = (oy + layout.location.y, layout.size.height);
+            if child.style.is_sticky {
+                self.sticky_limit = oy + h;
+                if self.sticky_offset(child, child_y, child_h) > 0.0 {
+                    self.draw_child_with_interaction(canvas, ctx.taffy, child, ox, oy, text_color, ctx.interaction, ctx.scroll_offset, ctx.viewport_height);
+                }
+            } else if child.tag != "scroll-view" && !Self::is_leaf_component(&child.tag) {
+                self.draw_stuck_children(canvas, child, (ox + layout.location.x, child_y, child_h), text_color, ctx);
+            }
+        }
+    }
+    
     fn draw_component(&self, canvas: &mut Canvas, node: &RenderNode, x: f32, y: f32, w: f32, h: f32, sf: f32) {
+        if node.style.hidden {
+            return;
+        }
         match node.tag.as_str() {
             "#text" | "text" => TextComponent::draw(node, canvas, self.text_renderer.as_ref(), x, y, w, h, sf),
             "button" => ButtonComponent::draw(node, canvas, self.text_renderer.as_ref(), x, y, w, h, sf),
@@ -1196,7 +2829,7 @@ impl WxmlRenderer {
             "radio-group" => RadioGroupComponent::draw(node, canvas, x, y, w, h, sf),
             "slider" => SliderComponent::draw(node, canvas, self.text_renderer.as_ref(), x, y, w, h, sf),
             "input" | "textarea" => InputComponent::draw(node, canvas, self.text_renderer.as_ref(), x, y, w, h, sf),
-            "image" => ImageComponent::draw(node, canvas, self.text_renderer.as_ref(), x, y, w, h, sf),
+            "image" | "cover-image" => ImageComponent::draw(node, canvas, self.text_renderer.as_ref(), x, y, w, h, sf),
             "video" => VideoComponent::draw(node, canvas, self.text_renderer.as_ref(), x, y, w, h, sf),
             "canvas" => CanvasComponent::draw(node, canvas, x, y, w, h, sf),
             "swiper" => SwiperComponent::draw(node, canvas, x, y, w, h, sf),
@@ -1208,7 +2841,7 @@ impl WxmlRenderer {
     }
     
     fn register_interactive_element(
-        &self, 
+        &mut self, 
         original_node: &RenderNode, 
         drawn_node: &RenderNode,
         bounds: &GeoRect, 
@@ -1216,28 +2849,51 @@ impl WxmlRenderer {
         taffy: &TaffyTree,
         is_in_fixed_container: bool
     ) {
+        // visibility: hidden 的元素保留占位，但不响应事件
+        if original_node.style.hidden {
+            return;
+        }
         let disabled = original_node.attrs.get("disabled")
             .map(|s| s == "true" || s == "{{true}}")
             .unwrap_or(false);
         
-        let id = Self::get_component_id(original_node, bounds);
+        let id = self.movable_ids.get(&original_node.taffy_node).cloned()
+            .unwrap_or_else(|| Self::get_component_id(original_node, bounds));
         let is_fixed = is_in_fixed_container || original_node.style.is_fixed;
+        self.element_nodes.insert(id.clone(), original_node.taffy_node);
+        self.paint_index.insert(original_node.taffy_node, self.paint_seq);
+        self.paint_seq += 1;
         
         match original_node.tag.as_str() {
+            "movable-view" => {
+                interaction.register_element(InteractiveElement {
+                    interaction_type: InteractionType::Movable,
+                    id,
+                    bounds: *bounds,
+                    checked: false,
+                    value: String::new(),
+                    disabled,
+                    min: 0.0,
+                    max: 0.0,
+                    content_height: 0.0,
+                    viewport_height: 0.0,
+                    is_fixed,
+                    scroll_x: false,
+                });
+            }
             "scroll-view" => {
-                let mut content_height = 0.0;
-                // 计算内容高度：所有子节点的底部最大值
-                for child in original_node.children.iter() {
-                    if let Ok(layout) = taffy.layout(child.taffy_node) {
-                        let bottom = layout.location.y + layout.size.height;
-                        if bottom > content_height {
-                            content_height = bottom;
-                        }
-                    }
-                }
+                // 内容尺寸：所有子节点的右边/底部最大值
+                let (content_width, content_height) = Self::scroll_content_size(taffy, original_node);
+                let scroll_x = Self::is_scroll_x(original_node);
+                self.check_scroll_top(original_node, &id, scroll_x);
+                self.check_scroll_into_view(taffy, original_node, &id, scroll_x);
                 
-                // 转换为逻辑像素
-                let logical_content_height = content_height / self.scale_factor;
+                // 转换为逻辑像素（横向滚动时使用宽度）
+                let (logical_content, viewport) = if scroll_x {
+                    (content_width / self.scale_factor, bounds.width)
+                } else {
+                    (content_height / self.scale_factor, bounds.height)
+                };
                 
                 interaction.register_element(InteractiveElement {
                     interaction_type: InteractionType::ScrollArea,
@@ -1248,12 +2904,19 @@ impl WxmlRenderer {
                     disabled,
                     min: 0.0,
                     max: 0.0,
-                    content_height: logical_content_height,
-                    viewport_height: bounds.height,
+                    content_height: logical_content,
+                    viewport_height: viewport,
                     is_fixed,
+                    scroll_x,
                 });
             }
+            "checkbox-group" | "radio-group" => {
+                self.group_nodes.insert(original_node.taffy_node, (original_node.tag.clone(), id));
+            }
             "checkbox" => {
+                if let Some(group) = self.enclosing_group(original_node.taffy_node, "checkbox-group") {
+                    interaction.set_group(&id, group);
+                }
                 interaction.register_element(InteractiveElement {
                     interaction_type: InteractionType::Checkbox,
                     id,
@@ -1266,9 +2929,13 @@ impl WxmlRenderer {
                     content_height: 0.0,
                     viewport_height: 0.0,
                     is_fixed,
+                    scroll_x: false,
                 });
             }
             "radio" => {
+                if let Some(group) = self.enclosing_group(original_node.taffy_node, "radio-group") {
+                    interaction.set_group(&id, group);
+                }
                 interaction.register_element(InteractiveElement {
                     interaction_type: InteractionType::Radio,
                     id,
@@ -1281,6 +2948,7 @@ impl WxmlRenderer {
                     content_height: 0.0,
                     viewport_height: 0.0,
                     is_fixed,
+                    scroll_x: false,
                 });
             }
             "switch" => {
@@ -1296,23 +2964,29 @@ impl WxmlRenderer {
                     content_height: 0.0,
                     viewport_height: 0.0,
                     is_fixed,
+                    scroll_x: false,
                 });
             }
             "slider" => {
                 let min = original_node.attrs.get("min").and_then(|s| s.parse().ok()).unwrap_or(0.0);
                 let max = original_node.attrs.get("max").and_then(|s| s.parse().ok()).unwrap_or(100.0);
+                interaction.set_slider_attrs(&id, SliderAttrs {
+                    step: original_node.attrs.get("step").and_then(|s| s.parse().ok()).unwrap_or(1.0),
+                    track: SliderComponent::track_range(drawn_node, bounds.width),
+                });
                 interaction.register_element(InteractiveElement {
                     interaction_type: InteractionType::Slider,
                     id,
                     bounds: *bounds,
                     checked: false,
-                    value: format!("{}", (drawn_node.style.custom_data * 100.0) as i32),
+                    value: drawn_node.style.font_size.to_string(),
                     disabled,
                     min,
                     max,
                     content_height: 0.0,
                     viewport_height: 0.0,
                     is_fixed,
+                    scroll_x: false,
                 });
             }
             "input" | "textarea" => {
@@ -1323,8 +2997,17 @@ impl WxmlRenderer {
                     .map(|s| s.value.clone())
                     .unwrap_or(actual_value);
                 
+                interaction.set_input_attrs(&id, InputAttrs {
+                    input_type: original_node.attrs.get("type").cloned().unwrap_or_else(|| "text".to_string()),
+                    password: is_password(&original_node.attrs),
+                    maxlength: get_maxlength(&original_node.attrs),
+                    confirm_type: original_node.attrs.get("confirm-type").cloned()
+                        .unwrap_or_else(|| if original_node.tag == "textarea" { "return" } else { "done" }.to_string()),
+                    confirm_hold: original_node.attrs.get("confirm-hold").map(|s| s == "true" || s == "{{true}}").unwrap_or(false),
+                });
+                self.check_focus(original_node, &id);
                 interaction.register_element(InteractiveElement {
-                    interaction_type: InteractionType::Input,
+                    interaction_type: if original_node.tag == "textarea" { InteractionType::Textarea } else { InteractionType::Input },
                     id,
                     bounds: *bounds,
                     checked: false,
@@ -1335,9 +3018,46 @@ impl WxmlRenderer {
                     content_height: 0.0,
                     viewport_height: 0.0,
                     is_fixed,
+                    scroll_x: false,
+                });
+            }
+            "video" => {
+                interaction.register_element(InteractiveElement {
+                    interaction_type: InteractionType::Video,
+                    id,
+                    bounds: *bounds,
+                    checked: false,
+                    value: original_node.text.clone(),
+                    disabled,
+                    min: 0.0,
+                    max: 0.0,
+                    content_height: 0.0,
+                    viewport_height: 0.0,
+                    is_fixed,
+                    scroll_x: false,
+                });
+            }
+            "cover-view" | "cover-image" => {
+                // 注册为普通视图，遮挡下面的 video，点击不会切换播放状态
+                interaction.register_element(InteractiveElement {
+                    interaction_type: InteractionType::View,
+                    id,
+                    bounds: *bounds,
+                    checked: false,
+                    value: String::new(),
+                    disabled,
+                    min: 0.0,
+                    max: 0.0,
+                    content_height: 0.0,
+                    viewport_height: 0.0,
+                    is_fixed,
+                    scroll_x: false,
                 });
             }
             "button" => {
+                if let Some(open_type) = original_node.attrs.get("open-type").filter(|s| !s.is_empty()) {
+                    interaction.set_open_type(&id, open_type);
+                }
                 interaction.register_element(InteractiveElement {
                     interaction_type: InteractionType::Button,
                     id,
@@ -1350,6 +3070,32 @@ impl WxmlRenderer {
                     content_height: 0.0,
                     viewport_height: 0.0,
                     is_fixed,
+                    scroll_x: false,
+                });
+            }
+            "text" if Self::is_selectable_text(original_node) => {
+                // 记录排版（逻辑像素），拖动选择时按字符定位
+                if let Some(tr) = self.text_renderer.as_ref() {
+                    let sf = self.scale_factor;
+                    let (mut lines, line_height) = TextComponent::layout_lines(original_node, tr, bounds.width * sf, sf);
+                    for line in &mut lines {
+                        line.offsets.iter_mut().for_each(|x| *x /= sf);
+                    }
+                    interaction.set_text_layout(&id, lines, line_height / sf);
+                }
+                interaction.register_element(InteractiveElement {
+                    interaction_type: InteractionType::Text,
+                    id,
+                    bounds: *bounds,
+                    checked: false,
+                    value: original_node.text.clone(),
+                    disabled,
+                    min: 0.0,
+                    max: 0.0,
+                    content_height: 0.0,
+                    viewport_height: 0.0,
+                    is_fixed,
+                    scroll_x: false,
                 });
             }
             _ => {
@@ -1360,42 +3106,73 @@ impl WxmlRenderer {
     }
 
     
+    /// 节点所在的最近一层 checkbox-group / radio-group 的组件 ID（组先于子节点绘制和注册）
+    fn enclosing_group(&self, node: NodeId, group_tag: &str) -> Option<&String> {
+        let mut current = self.node_parents.get(&node).copied();
+        while let Some(parent) = current {
+            if let Some((_, id)) = self.group_nodes.get(&parent).filter(|(tag, _)| tag == group_tag) {
+                return Some(id);
+            }
+            current = self.node_parents.get(&parent).copied();
+        }
+        None
+    }
+    
     fn draw(&mut self, canvas: &mut Canvas, taffy: &TaffyTree, node: &RenderNode, ox: f32, oy: f32) {
         let sf = self.scale_factor;
+        if self.draw_opacity_group(canvas, node, || Self::paint_bounds(taffy, node, ox, oy, sf), |r, layer, flat| r.draw(layer, taffy, flat, ox, oy)) {
+            return;
+        }
+        if self.draw_in_transform_layer(canvas, taffy, node, ox, oy, |r, layer, flat| r.draw(layer, taffy, flat, ox, oy)) {
+            return;
+        }
         let layout = taffy.layout(node.taffy_node).unwrap();
         let x = ox + layout.location.x;
         let y = oy + layout.location.y;
         let w = layout.size.width;
         let h = layout.size.height;
+        let ((x, y, w, h), (cx, cy)) = Self::transformed_bounds(node, x, y, w, h, sf);
         let logical_bounds = GeoRect::new(x / sf, y / sf, w / sf, h / sf);
 
         self.draw_component(canvas, node, x, y, w, h, sf);
         
+        let clipped = Self::push_overflow_clip(canvas, node, x, y, w, h);
         if !Self::is_leaf_component(&node.tag) {
             let text_color = node.style.text_color.unwrap_or(Color::BLACK);
-            for child in &node.children { 
-                self.draw_with_color(canvas, taffy, child, x, y, text_color); 
+            for child in Self::paint_order(&node.children) { 
+                self.draw_with_color(canvas, taffy, child, cx, cy, text_color); 
             }
         }
+        if clipped { canvas.pop_clip(); }
 
-        for (et, h, d, is_catch) in &node.events {
+        for (et, h, d, is_catch) in Self::visible_events(node) {
             self.event_bindings.push(EventBinding { 
                 event_type: et.clone(), 
                 handler: h.clone(), 
                 data: d.clone(), 
                 bounds: logical_bounds,
                 is_catch: *is_catch,
+                node: node.taffy_node,
             });
         }
     }
     
     fn draw_with_color(&mut self, canvas: &mut Canvas, taffy: &TaffyTree, node: &RenderNode, ox: f32, oy: f32, inherited_color: Color) {
         let sf = self.scale_factor;
+        if self.draw_opacity_group(canvas, node, || Self::paint_bounds(taffy, node, ox, oy, sf), |r, layer, flat| r.draw_with_color(layer, taffy, flat, ox, oy, inherited_color)) {
+            return;
+        }
+        if self.draw_in_transform_layer(canvas, taffy, node, ox, oy, |r, layer, flat| {
+            r.draw_with_color(layer, taffy, flat, ox, oy, inherited_color)
+        }) {
+            return;
+        }
         let layout = taffy.layout(node.taffy_node).unwrap();
         let x = ox + layout.location.x;
         let y = oy + layout.location.y;
         let w = layout.size.width;
         let h = layout.size.height;
+        let ((x, y, w, h), (cx, cy)) = Self::transformed_bounds(node, x, y, w, h, sf);
         let logical_bounds = GeoRect::new(x / sf, y / sf, w / sf, h / sf);
 
         let text_color = node.style.text_color.unwrap_or(inherited_color);
@@ -1406,19 +3183,22 @@ impl WxmlRenderer {
 
         self.draw_component(canvas, &node_with_color, x, y, w, h, sf);
         
+        let clipped = Self::push_overflow_clip(canvas, node, x, y, w, h);
         if !Self::is_leaf_component(&node.tag) {
-            for child in &node.children { 
-                self.draw_with_color(canvas, taffy, child, x, y, text_color); 
+            for child in Self::paint_order(&node.children) { 
+                self.draw_with_color(canvas, taffy, child, cx, cy, text_color); 
             }
         }
+        if clipped { canvas.pop_clip(); }
 
-        for (et, h, d, is_catch) in &node.events {
+        for (et, h, d, is_catch) in Self::visible_events(node) {
             self.event_bindings.push(EventBinding { 
                 event_type: et.clone(), 
                 handler: h.clone(), 
                 data: d.clone(), 
                 bounds: logical_bounds,
                 is_catch: *is_catch,
+                node: node.taffy_node,
             });
         }
     }
@@ -1432,9 +3212,91 @@ impl WxmlRenderer {
     pub fn get_event_bindings(&self) -> &[EventBinding] { 
         &self.event_bindings 
     }
+    
+    /// 查找交互元素（按组件 ID）上指定类型的事件绑定
+    pub fn binding_for_element(&self, id: &str, event_type: &str) -> Option<&EventBinding> {
+        let node = *self.element_nodes.get(id)?;
+        self.event_bindings.iter().find(|b| b.node == node && b.event_type == event_type)
+    }
 
+    /// 命中测试：从点击位置最内层节点向上，返回第一个（非触摸、非 open-type）事件绑定
     pub fn hit_test(&self, x: f32, y: f32) -> Option<&EventBinding> {
-        self.event_bindings.iter().rev().find(|b| b.bounds.contains(&crate::Point::new(x, y)))
+        let mut current = self.hit_target(&crate::Point::new(x, y));
+        while let Some(node) = current {
+            let found = self.event_bindings.iter()
+                .find(|b| b.node == node && !Self::is_touch_event(&b.event_type) && !Self::is_open_type_event(&b.event_type));
+            if found.is_some() { return found; }
+            current = self.node_parents.get(&node).copied();
+        }
+        None
+    }
+    
+    /// 事件传播路径：从命中的最内层节点沿祖先链向上，收集匹配 event_type 的绑定
+    /// 遇到 catch 绑定的节点后停止冒泡
+    pub fn hit_test_path(&self, x: f32, y: f32, event_type: &str) -> Vec<&EventBinding> {
+        let point = crate::Point::new(x, y);
+        let mut result = Vec::new();
+        let mut current = self.hit_target(&point);
+        
+        while let Some(node) = current {
+            let mut stop = false;
+            for binding in self.event_bindings.iter().filter(|b| b.node == node && b.event_type == event_type) {
+                result.push(binding);
+                stop |= binding.is_catch;
+            }
+            if stop { break; }
+            current = self.node_parents.get(&node).copied();
+        }
+        result
+    }
+    
+    /// 查找包含该点的最上层节点：按绘制顺序（考虑 z-index）取最后绘制的，
+    /// 没有绘制顺序（旧接口渲染）时取最深的节点，深度相同时取后注册的
+    fn hit_target(&self, point: &crate::Point) -> Option<NodeId> {
+        let mut target = None;
+        let mut max_key = (None, 0);
+        for binding in self.event_bindings.iter().filter(|b| b.bounds.contains(point)) {
+            let key = (self.paint_index.get(&binding.node).copied(), self.node_depth(binding.node));
+            if target.is_none() || key >= max_key {
+                target = Some(binding.node);
+                max_key = key;
+            }
+        }
+        target
+    }
+    
+    fn node_depth(&self, node: NodeId) -> usize {
+        let mut depth = 0;
+        let mut current = node;
+        while let Some(parent) = self.node_parents.get(&current) {
+            depth += 1;
+            current = *parent;
+        }
+        depth
+    }
+    
+    /// 收集渲染树中的父子关系
+    fn collect_parents(nodes: &[RenderNode]) -> HashMap<NodeId, NodeId> {
+        fn walk(node: &RenderNode, parents: &mut HashMap<NodeId, NodeId>) {
+            for child in &node.children {
+                parents.insert(child.taffy_node, node.taffy_node);
+                walk(child, parents);
+            }
+        }
+        let mut parents = HashMap::new();
+        for node in nodes {
+            walk(node, &mut parents);
+        }
+        parents
+    }
+    
+    fn is_touch_event(event_type: &str) -> bool {
+        matches!(event_type, "touchstart" | "touchmove" | "touchend")
+    }
+    
+    /// 按钮 open-type 的事件只由 InteractionResult::OpenType 触发，不作为点击处理函数
+    fn is_open_type_event(event_type: &str) -> bool {
+        matches!(event_type, "getuserinfo" | "getphonenumber" | "chooseavatar" | "opensetting" | "contact")
     }
     
     /// 获取事件绑定数量
@@ -1442,10 +3304,10 @@ impl WxmlRenderer {
         self.event_bindings.len()
     }
     
-    /// 打印所有事件绑定（调试用）
+    /// 输出所有事件绑定（debug 级别日志）
     pub fn debug_events(&self) {
         for (i, binding) in self.event_bindings.iter().enumerate() {
-            println!("   [{}] {} -> {} bounds=({:.1},{:.1},{:.1},{:.1}) data={:?}", 
+            log::debug!("   [{}] {} -> {} bounds=({:.1},{:.1},{:.1},{:.1}) data={:?}", 
                 i, binding.event_type, binding.handler,
                 binding.bounds.x, binding.bounds.y, binding.bounds.width, binding.bounds.height,
                 binding.data);
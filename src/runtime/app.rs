@@ -1,8 +1,13 @@
 //! 小程序应用
 
-use crate::{Canvas, Color};
-use crate::js::{JsRuntime, MiniAppApi, JsBridge, BridgeEvent};
+use crate::{Canvas, Color, Matrix};
+use crate::renderer::WxmlRenderer;
+use crate::ui::ComponentTree;
+use crate::js::{JsRuntime, MiniAppApi, SystemInfo, JsBridge, BridgeEvent};
 use crate::event::{Event, TouchEvent, Touch, TapEvent};
+use super::replay::{Clock, EventLog, EventRecorder, RecordedEvent};
+use serde_json::Value as JsonValue;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use std::collections::HashMap;
@@ -16,6 +21,37 @@ pub enum UiEvent {
     HideLoading,
     ShowModal { title: String, content: String, show_cancel: bool, cancel_text: String, confirm_text: String },
     HideModal,
+    /// 显示底部操作菜单，宿主应调用 MiniApp::resolve_action_sheet 应答
+    ShowActionSheet { item_list: Vec<String>, item_color: String },
+    /// 小程序请求授权，宿主应调用 MiniApp::resolve_auth 应答
+    AuthRequest { scope: String },
+    /// 显示/收起下拉刷新指示器
+    StartPullDownRefresh,
+    StopPullDownRefresh,
+    /// 小程序发起支付，宿主应调用 MiniApp::complete_bridge_call(id, ..) 应答
+    RequestPayment { id: u32, order: JsonValue },
+}
+
+/// 启动参数（对应 wx.getLaunchOptionsSync 的返回值）
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchOptions {
+    /// 启动页面路径
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// 场景值，默认 1001（发现栏小程序主入口）
+    pub scene: u32,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self { path: String::new(), query: HashMap::new(), scene: 1001 }
+    }
+}
+
+impl LaunchOptions {
+    fn to_json(&self) -> JsonValue {
+        serde_json::json!({ "path": self.path, "query": self.query, "scene": self.scene, "referrerInfo": {} })
+    }
 }
 
 /// 小程序应用
@@ -26,16 +62,35 @@ pub struct MiniApp {
     canvas: Canvas,
     width: u32,
     height: u32,
+    /// 渲染缩放比例（设备像素比），画布尺寸为逻辑尺寸 * render_scale
+    render_scale: f32,
+    /// wx.getSystemInfoSync 返回的设备信息
+    system_info: SystemInfo,
     running: bool,
     last_frame: Instant,
     timers: HashMap<u32, TimerState>,
     ui_events: Vec<UiEvent>,
+    /// 定时器使用的时钟
+    clock: Clock,
+    /// 事件录制，None 表示未在录制
+    recorder: Option<EventRecorder>,
+    launch_options: LaunchOptions,
+    /// 是否在前台，避免重复触发 onShow/onHide
+    foreground: bool,
+    /// 宿主已应答、等待下一次 update 回调到 JS 的异步调用
+    completed_calls: Vec<(u32, Result<JsonValue, String>)>,
+    /// wx.request 后台线程的结果
+    network_tx: Sender<(u32, Result<JsonValue, String>)>,
+    network_rx: Receiver<(u32, Result<JsonValue, String>)>,
+    /// 尚未完成的 wx.request 数量
+    pending_requests: usize,
 }
 
 struct TimerState {
     delay_ms: u32,
     repeat: bool,
-    last_trigger: Instant,
+    /// 下次触发的时钟（毫秒）
+    deadline: u64,
 }
 
 impl MiniApp {
@@ -43,6 +98,7 @@ impl MiniApp {
         let runtime = Arc::new(Mutex::new(JsRuntime::new()?));
         let bridge = Arc::new(JsBridge::new(runtime.clone()));
         let api = MiniAppApi::new(runtime.clone());
+        let (network_tx, network_rx) = mpsc::channel();
         
         Ok(Self {
             runtime,
@@ -51,45 +107,56 @@ impl MiniApp {
             canvas: Canvas::new(width, height),
             width,
             height,
+            render_scale: 1.0,
+            system_info: SystemInfo::new(width, height),
             running: false,
             last_frame: Instant::now(),
             timers: HashMap::new(),
             ui_events: Vec::new(),
+            clock: Clock::System(Instant::now()),
+            recorder: None,
+            launch_options: LaunchOptions::default(),
+            foreground: false,
+            completed_calls: Vec::new(),
+            network_tx,
+            network_rx,
+            pending_requests: 0,
         })
     }
     
     /// 初始化应用
     pub fn init(&mut self) -> Result<(), String> {
         // 先初始化桥接（注册 native 函数）
-        println!("  Initializing Bridge...");
+        log::debug!("  Initializing Bridge...");
         self.bridge.init().map_err(|e| format!("Bridge init failed: {}", e))?;
         
         // 再初始化 API（使用 native 函数）
-        println!("  Initializing API...");
+        log::debug!("  Initializing API...");
         self.api.init().map_err(|e| format!("API init failed: {}", e))?;
+        self.api.set_system_info(&self.system_info)?;
         
-        println!("Mini App Engine initialized");
+        log::info!("Mini App Engine initialized");
         Ok(())
     }
     
     /// 加载并运行 JS 代码
     pub fn load_script(&self, code: &str) -> Result<(), String> {
-        println!("  Loading script...");
+        log::debug!("  Loading script...");
         let rt = self.runtime.lock().unwrap();
         rt.eval(code).map_err(|e| format!("Script error: {}", e))?;
         
-        // 打印 JS 输出
+        // 输出 JS 的 console 内容
         let output = rt.eval("__print_buffer.join('\\n')").unwrap_or_default();
         if !output.is_empty() && output != "undefined" {
-            println!("\n--- JS Output ---");
-            println!("{}", output);
-            println!("-----------------\n");
+            for line in output.lines() {
+                log::info!("   {}", line);
+            }
         }
         
         // 清空缓冲区
         rt.eval("__print_buffer = [];").ok();
         
-        println!("  Script loaded successfully");
+        log::debug!("  Script loaded successfully");
         Ok(())
     }
     
@@ -102,17 +169,71 @@ impl MiniApp {
     /// 启动应用
     pub fn start(&mut self) -> Result<(), String> {
         self.running = true;
-        
-        // 触发 App.onLaunch
-        self.bridge.dispatch_event(&Event::AppShow)?;
-        
-        Ok(())
+        self.on_app_show()
     }
     
     /// 停止应用
     pub fn stop(&mut self) {
         self.running = false;
-        self.bridge.dispatch_event(&Event::AppHide).ok();
+        self.on_app_hide().ok();
+    }
+    
+    /// 设置启动参数，需要在加载 app.js 之前调用，onLaunch 和首次 onShow 会收到这些参数
+    pub fn set_launch_options(&mut self, options: LaunchOptions) -> Result<(), String> {
+        self.eval(&format!("__launchOptions = {}; __enterOptions = __launchOptions;", options.to_json()))?;
+        self.launch_options = options;
+        Ok(())
+    }
+    
+    /// 切到前台：调用 App.onShow 和 wx.onAppShow 监听器，参数中带启动场景值
+    /// 已在前台时不重复触发
+    pub fn on_app_show(&mut self) -> Result<(), String> {
+        if self.foreground {
+            return Ok(());
+        }
+        self.foreground = true;
+        self.eval(&format!("__appShow({})", self.launch_options.to_json()))?;
+        self.process_bridge_events()
+    }
+    
+    /// 切到后台：调用 App.onHide 和 wx.onAppHide 监听器
+    pub fn on_app_hide(&mut self) -> Result<(), String> {
+        if !self.foreground {
+            return Ok(());
+        }
+        self.foreground = false;
+        self.eval("__appHide()")?;
+        self.process_bridge_events()
+    }
+    
+    /// 页面入栈：执行页面脚本，原栈顶页面 onHide，新页面 onLoad(query) → onShow
+    pub fn push_page(&mut self, route: &str, code: &str, query: &JsonValue) -> Result<(), String> {
+        self.load_script(code)?;
+        self.eval(&format!("__pushPage({}, {})", JsonValue::String(route.to_string()), query))?;
+        self.process_bridge_events()
+    }
+    
+    /// 返回 delta 层（至少保留一个页面）：被关闭的页面 onUnload，回到的页面 onShow
+    pub fn pop_pages(&mut self, delta: usize) -> Result<(), String> {
+        self.eval(&format!("__popPages({})", delta))?;
+        self.process_bridge_events()
+    }
+    
+    /// 关闭栈顶的 count 个页面（redirectTo / reLaunch），之后应紧接着 push_page
+    pub fn unload_pages(&mut self, count: usize) -> Result<(), String> {
+        self.eval(&format!("__unloadPages({})", count))?;
+        self.process_bridge_events()
+    }
+    
+    /// 栈顶页面首次渲染完成后调用，触发 onReady（每个页面只触发一次）
+    pub fn page_ready(&mut self) -> Result<(), String> {
+        self.eval("__pageReady()")?;
+        self.process_bridge_events()
+    }
+    
+    /// 是否在前台
+    pub fn is_foreground(&self) -> bool {
+        self.foreground
     }
     
     /// 检查是否有活动定时器
@@ -120,6 +241,25 @@ impl MiniApp {
         !self.timers.is_empty()
     }
     
+    /// 执行 requestAnimationFrame 注册的回调，时间戳为 MiniApp 时钟的毫秒数。
+    /// 宿主每帧调用一次，返回执行后是否还有等待下一帧的回调
+    pub fn flush_animation_frames(&mut self) -> Result<bool, String> {
+        let timestamp = self.clock.now_ms();
+        let pending = self.eval(&format!("__flushAnimationFrames({})", timestamp))? == "true";
+        self.process_bridge_events()?;
+        Ok(pending)
+    }
+    
+    /// 是否有等待执行的帧回调（宿主需要保持逐帧刷新）
+    pub fn has_animation_frames(&self) -> bool {
+        self.eval("__frameCallbacks.length > 0").map(|r| r == "true").unwrap_or(false)
+    }
+    
+    /// 是否有进行中的 wx.request（宿主需要继续调用 update 以触发回调）
+    pub fn has_pending_requests(&self) -> bool {
+        self.pending_requests > 0
+    }
+    
     /// 更新一帧
     pub fn update(&mut self) -> Result<(), String> {
         let now = Instant::now();
@@ -132,35 +272,63 @@ impl MiniApp {
         // 处理桥接事件
         self.process_bridge_events()?;
         
+        // 收集已完成的网络请求
+        while let Ok(result) = self.network_rx.try_recv() {
+            self.pending_requests = self.pending_requests.saturating_sub(1);
+            self.completed_calls.push(result);
+        }
+        
+        // 回调宿主已应答的异步调用
+        self.process_completed_calls()?;
+        
         Ok(())
     }
     
+    /// 应答 RequestPayment 等由宿主处理的异步调用，Ok 触发 success、Err 触发 fail（内容为 errMsg），
+    /// 两者之后都会触发 complete。回调在下一次 update 中执行
+    pub fn complete_bridge_call(&mut self, id: u32, result: Result<JsonValue, String>) {
+        self.completed_calls.push((id, result));
+    }
+    
+    fn process_completed_calls(&mut self) -> Result<(), String> {
+        for (id, result) in std::mem::take(&mut self.completed_calls) {
+            let (ok, res) = match result {
+                Ok(JsonValue::Object(map)) => (true, JsonValue::Object(map)),
+                Ok(_) => (true, serde_json::json!({})),
+                Err(msg) => (false, serde_json::json!({ "errMsg": msg })),
+            };
+            self.eval(&format!("__completeBridgeCall({}, {}, {})", id, ok, res))?;
+        }
+        self.process_bridge_events()
+    }
+    
     fn process_timers(&mut self) -> Result<(), String> {
-        let now = Instant::now();
-        let mut to_trigger = Vec::new();
-        let mut to_remove = Vec::new();
+        let now = self.clock.now_ms();
+        let mut due: Vec<(u64, u32)> = self.timers.iter()
+            .filter(|(_, state)| state.deadline <= now)
+            .map(|(id, state)| (state.deadline, *id))
+            .collect();
+        // 按到期时间触发，同时到期的按 ID（即创建顺序），保证回放时顺序一致
+        due.sort_unstable();
         
-        for (id, state) in &self.timers {
-            let elapsed = now.duration_since(state.last_trigger).as_millis() as u32;
-            if elapsed >= state.delay_ms {
-                to_trigger.push(*id);
-                if !state.repeat {
-                    to_remove.push(*id);
+        for (deadline, id) in due {
+            // 先前的回调可能已经 clearTimeout/clearInterval
+            let repeat = match self.timers.get(&id) {
+                Some(state) => state.repeat,
+                None => continue,
+            };
+            if repeat {
+                // interval 按固定节奏重新计时，落后太多时不补发
+                if let Some(state) = self.timers.get_mut(&id) {
+                    let next = deadline + state.delay_ms.max(1) as u64;
+                    state.deadline = if next > now { next } else { now + state.delay_ms.max(1) as u64 };
                 }
+            } else {
+                self.timers.remove(&id);
             }
-        }
-        
-        // 触发定时器
-        for id in to_trigger {
             self.bridge.trigger_timer(id)?;
-            if let Some(state) = self.timers.get_mut(&id) {
-                state.last_trigger = now;
-            }
-        }
-        
-        // 移除一次性定时器
-        for id in to_remove {
-            self.timers.remove(&id);
+            // 回调中的 clearTimeout/setTimeout 立即生效
+            self.process_bridge_events()?;
         }
         
         Ok(())
@@ -175,37 +343,67 @@ impl MiniApp {
                     self.timers.insert(id, TimerState {
                         delay_ms: delay,
                         repeat,
-                        last_trigger: Instant::now(),
+                        deadline: self.clock.now_ms() + delay as u64,
                     });
                 }
                 BridgeEvent::ClearTimer(id) => {
                     self.timers.remove(&id);
                 }
                 BridgeEvent::ShowToast { title, icon, duration, .. } => {
-                    println!("[Toast] {} ({})", title, icon);
+                    log::debug!("[Toast] {} ({})", title, icon);
                     self.ui_events.push(UiEvent::ShowToast { title, icon, duration });
                 }
                 BridgeEvent::HideToast => {
                     self.ui_events.push(UiEvent::HideToast);
                 }
                 BridgeEvent::ShowLoading { title, .. } => {
-                    println!("[Loading] {}", title);
+                    log::debug!("[Loading] {}", title);
                     self.ui_events.push(UiEvent::ShowLoading { title });
                 }
                 BridgeEvent::HideLoading => {
-                    println!("[HideLoading]");
+                    log::debug!("[HideLoading]");
                     self.ui_events.push(UiEvent::HideLoading);
                 }
                 BridgeEvent::ShowModal { title, content, show_cancel, cancel_text, confirm_text } => {
-                    println!("[Modal] {}: {}", title, content);
+                    log::debug!("[Modal] {}: {}", title, content);
                     self.ui_events.push(UiEvent::ShowModal { title, content, show_cancel, cancel_text, confirm_text });
                 }
+                BridgeEvent::ShowActionSheet { item_list, item_color } => {
+                    log::debug!("[ActionSheet] {}", item_list.join(", "));
+                    self.ui_events.push(UiEvent::ShowActionSheet { item_list, item_color });
+                }
+                BridgeEvent::AuthRequest { scope } => {
+                    log::debug!("[Authorize] {}", scope);
+                    self.ui_events.push(UiEvent::AuthRequest { scope });
+                }
+                BridgeEvent::StartPullDownRefresh => {
+                    self.ui_events.push(UiEvent::StartPullDownRefresh);
+                }
+                BridgeEvent::StopPullDownRefresh => {
+                    log::debug!("[PullDownRefresh] stop");
+                    self.ui_events.push(UiEvent::StopPullDownRefresh);
+                }
+                BridgeEvent::RequestPayment { id, order } => {
+                    log::debug!("[RequestPayment] #{} {}", id, order);
+                    self.ui_events.push(UiEvent::RequestPayment { id, order });
+                }
+                BridgeEvent::Request { id, request } => {
+                    log::debug!("[Request] #{} {} {}", id, request["method"].as_str().unwrap_or("GET"), request["url"].as_str().unwrap_or(""));
+                    self.pending_requests += 1;
+                    super::network::spawn_request(id, request, self.network_tx.clone());
+                }
                 BridgeEvent::NavigateTo(url) => {
-                    println!("[Navigate] {}", url);
+                    log::debug!("[Navigate] {}", url);
                 }
                 BridgeEvent::CanvasDraw { canvas_id, commands } => {
                     crate::renderer::components::execute_canvas_draw(&canvas_id, &commands);
                 }
+                BridgeEvent::CanvasToTempFile { id, options } => {
+                    let result = crate::renderer::components::canvas_to_temp_file(&options)
+                        .map(|path| serde_json::json!({ "tempFilePath": path }))
+                        .map_err(|e| format!("canvasToTempFilePath:fail {}", e));
+                    self.completed_calls.push((id, result));
+                }
                 _ => {}
             }
         }
@@ -213,32 +411,94 @@ impl MiniApp {
         Ok(())
     }
     
+    /// 应答授权请求：记录授权结果并回调 wx.authorize 的 success/fail
+    pub fn resolve_auth(&mut self, scope: &str, granted: bool) -> Result<(), String> {
+        self.bridge.set_auth(scope, granted);
+        let scope_json = serde_json::to_string(scope).map_err(|e| e.to_string())?;
+        let rt = self.runtime.lock().unwrap();
+        rt.eval(&format!("__resolveAuth({}, {})", scope_json, granted))?;
+        Ok(())
+    }
+    
+    /// 应答 wx.showModal：按用户点击的按钮回调 success/complete（confirm 或 cancel）
+    pub fn resolve_modal(&mut self, confirm: bool) -> Result<(), String> {
+        self.eval(&format!("__handleModalResult({})", confirm))?;
+        // 回调中可能再次弹出 toast / modal 或设置定时器
+        self.process_bridge_events()
+    }
+    
+    /// 应答 wx.showActionSheet：tap_index 为选中项下标，None 表示取消（回调 fail）
+    pub fn resolve_action_sheet(&mut self, tap_index: Option<usize>) -> Result<(), String> {
+        let index = tap_index.map(|i| i as i64).unwrap_or(-1);
+        self.eval(&format!("__handleActionSheetResult({})", index))?;
+        self.process_bridge_events()
+    }
+    
     /// 获取并清空 UI 事件
     pub fn drain_ui_events(&mut self) -> Vec<UiEvent> {
         std::mem::take(&mut self.ui_events)
     }
     
+    /// 设置设备信息（像素比、状态栏高度、安全区域），wx.getSystemInfoSync 随之更新
+    pub fn set_system_info(&mut self, info: SystemInfo) -> Result<(), String> {
+        self.api.set_system_info(&info)?;
+        self.system_info = info;
+        Ok(())
+    }
+    
+    /// 当前设备信息
+    pub fn system_info(&self) -> &SystemInfo {
+        &self.system_info
+    }
+    
+    /// 覆盖渲染缩放比例（独立于窗口的 scale_factor），例如生成 3x 截图
+    pub fn set_render_scale(&mut self, scale: f32) {
+        if scale <= 0.0 { return; }
+        self.render_scale = scale;
+        let (pw, ph) = self.physical_size();
+        self.canvas = Canvas::new(pw, ph);
+    }
+    
+    /// 当前渲染缩放比例
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+    
+    /// 按渲染缩放比例计算的画布尺寸
+    pub fn physical_size(&self) -> (u32, u32) {
+        (
+            (self.width as f32 * self.render_scale).round() as u32,
+            (self.height as f32 * self.render_scale).round() as u32,
+        )
+    }
+    
+    /// 原生组件树，render 时按渲染缩放比例绘制
+    pub fn component_tree(&self) -> Arc<Mutex<ComponentTree>> {
+        self.bridge.component_tree()
+    }
+    
     /// 渲染
     pub fn render(&mut self) {
         // 清空画布
         self.canvas.clear(Color::WHITE);
         
-        // 渲染组件树
+        // 组件树使用逻辑坐标，按渲染缩放比例直接绘制到物理画布
         let tree = self.bridge.component_tree();
         let tree = tree.lock().unwrap();
+        self.canvas.save();
+        self.canvas.concat(&Matrix::scale(self.render_scale, self.render_scale));
         tree.render(&mut self.canvas);
+        self.canvas.restore();
     }
     
     /// 处理触摸事件
     pub fn on_touch(&mut self, x: f32, y: f32, touch_type: &str) -> Result<(), String> {
+        self.record(RecordedEvent::Touch { x, y, phase: touch_type.to_string() });
         let touch = Touch::new(0, x, y);
         let touch_event = TouchEvent {
             touches: vec![touch.clone()],
             changed_touches: vec![touch],
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
+            timestamp: self.event_timestamp(),
         };
         
         let event = match touch_type {
@@ -258,19 +518,18 @@ impl MiniApp {
         
         // 分发给 JS
         self.bridge.dispatch_event(&event)?;
+        self.process_bridge_events()?;
         
         Ok(())
     }
     
     /// 处理点击事件
     pub fn on_tap(&mut self, x: f32, y: f32) -> Result<(), String> {
+        self.record(RecordedEvent::Tap { x, y });
         let tap = TapEvent {
             x,
             y,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
+            timestamp: self.event_timestamp(),
         };
         
         let event = Event::Tap(tap);
@@ -284,10 +543,139 @@ impl MiniApp {
         
         // 分发给 JS
         self.bridge.dispatch_event(&event)?;
+        self.process_bridge_events()?;
         
         Ok(())
     }
     
+    /// 页面滚动，调用 Page.onPageScroll({ scrollTop })
+    pub fn on_page_scroll(&mut self, scroll_top: f32) -> Result<(), String> {
+        self.record(RecordedEvent::Scroll { scroll_top });
+        self.eval(&format!(
+            "__currentPage && __currentPage.onPageScroll && __currentPage.onPageScroll({{ scrollTop: {} }})",
+            scroll_top
+        ))?;
+        Ok(())
+    }
+    
+    /// 文本输入：调用 bindinput 处理函数，event.detail.value 为输入框当前值
+    /// 处理函数返回字符串时返回 Some(新值)，由调用方写回输入框
+    pub fn on_input(&mut self, handler: &str, data: &JsonValue, value: &str) -> Result<Option<String>, String> {
+        self.record(RecordedEvent::Input { handler: handler.to_string(), data: data.clone(), value: value.to_string() });
+        let mut event_data = data.clone();
+        if let Some(obj) = event_data.as_object_mut() {
+            obj.insert("value".to_string(), JsonValue::String(value.to_string()));
+        }
+        let extra = serde_json::json!({ "type": "input", "detail": { "value": value } });
+        if !self.call_page_method(handler, &event_data, &extra)? {
+            return Ok(None);
+        }
+        let result = self.eval("__takeInputResult()")?;
+        if result.is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_str(&result).map(Some).map_err(|e| e.to_string())
+    }
+    
+    /// 调用页面事件处理函数（bindtap 等），extra 中的字段覆盖到事件对象上
+    /// 返回页面上是否存在该处理函数
+    pub fn dispatch_page_event(&mut self, handler: &str, data: &JsonValue, extra: &JsonValue) -> Result<bool, String> {
+        self.record(RecordedEvent::PageEvent { handler: handler.to_string(), data: data.clone(), extra: extra.clone() });
+        self.call_page_method(handler, data, extra)
+    }
+    
+    fn call_page_method(&mut self, handler: &str, data: &JsonValue, extra: &JsonValue) -> Result<bool, String> {
+        let handler_json = serde_json::to_string(handler).map_err(|e| e.to_string())?;
+        let result = self.eval(&format!("__callPageMethod({}, {}, {})", handler_json, data, extra))?;
+        // 立即注册处理函数中设置的定时器，使其从当前时刻开始计时
+        self.process_bridge_events()?;
+        Ok(result == "true")
+    }
+    
+    /// 记录宿主发起的导航（tabBar 切换、返回按钮等）
+    /// 由页面脚本调用 wx.navigateTo 产生的导航不需要记录，回放触发它的事件即可
+    pub fn record_navigation(&mut self, nav_type: &str, url: &str) {
+        self.record(RecordedEvent::Navigate { nav_type: nav_type.to_string(), url: url.to_string() });
+    }
+    
+    /// 改用模拟时钟，之后只能通过 advance_clock 推进时间
+    pub fn use_mock_clock(&mut self) {
+        let now = self.clock.now_ms();
+        self.clock = Clock::Mock(now);
+    }
+    
+    /// 推进模拟时钟并处理到期的定时器（系统时钟下无效）
+    pub fn advance_clock(&mut self, ms: u64) -> Result<(), String> {
+        if let Clock::Mock(now) = &mut self.clock {
+            *now += ms;
+        }
+        self.update()
+    }
+    
+    /// 开始录制事件（会丢弃尚未取出的录制）
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(EventRecorder { start_ms: self.clock.now_ms(), log: EventLog::new() });
+    }
+    
+    /// 停止录制并返回事件序列
+    pub fn stop_recording(&mut self) -> Option<EventLog> {
+        let recorder = self.recorder.take()?;
+        let mut log = recorder.log;
+        log.duration_ms = log.duration_ms.max(self.clock.now_ms().saturating_sub(recorder.start_ms));
+        Some(log)
+    }
+    
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+    
+    /// 回放录制的事件：切换到模拟时钟，按录制时间推进定时器并依次派发事件
+    pub fn replay(&mut self, log: &EventLog) -> Result<(), String> {
+        self.use_mock_clock();
+        let start = self.clock.now_ms();
+        
+        for timed in &log.events {
+            let elapsed = self.clock.now_ms() - start;
+            self.advance_clock(timed.at_ms.saturating_sub(elapsed))?;
+            
+            match &timed.event {
+                RecordedEvent::Tap { x, y } => self.on_tap(*x, *y)?,
+                RecordedEvent::Touch { x, y, phase } => self.on_touch(*x, *y, phase)?,
+                RecordedEvent::Scroll { scroll_top } => self.on_page_scroll(*scroll_top)?,
+                RecordedEvent::Input { handler, data, value } => { self.on_input(handler, data, value)?; }
+                RecordedEvent::PageEvent { handler, data, extra } => { self.dispatch_page_event(handler, data, extra)?; }
+                RecordedEvent::Navigate { nav_type, url } => {
+                    // 与 wx.navigateTo 等相同，交给宿主在下一帧处理
+                    self.record_navigation(nav_type, url);
+                    let nav = serde_json::json!({ "type": nav_type, "url": url });
+                    self.eval(&format!("__pendingNavigation = {}", nav))?;
+                }
+            }
+        }
+        
+        let elapsed = self.clock.now_ms() - start;
+        self.advance_clock(log.duration_ms.saturating_sub(elapsed))
+    }
+    
+    fn record(&mut self, event: RecordedEvent) {
+        let now = self.clock.now_ms();
+        if let Some(recorder) = &mut self.recorder {
+            let at_ms = now.saturating_sub(recorder.start_ms);
+            recorder.log.push(at_ms, event);
+        }
+    }
+    
+    /// 事件对象的时间戳：模拟时钟下使用时钟值，保证回放结果一致
+    fn event_timestamp(&self) -> u64 {
+        match self.clock {
+            Clock::Mock(ms) => ms,
+            Clock::System(_) => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+        }
+    }
+    
     /// 获取画布像素数据
     pub fn pixels(&self) -> &Canvas {
         &self.canvas
@@ -298,6 +686,11 @@ impl MiniApp {
         self.canvas.to_rgba()
     }
     
+    /// 不经过窗口把一个页面（WXML + WXSS + data）渲染成 RGBA 像素，使用当前的渲染缩放比例
+    pub fn render_page_to_rgba(&self, wxml: &str, wxss: &str, data: &JsonValue, width: u32, height: u32) -> Result<Vec<u8>, String> {
+        WxmlRenderer::render_to_rgba(wxml, wxss, data, width, height, self.render_scale)
+    }
+    
     pub fn width(&self) -> u32 {
         self.width
     }
@@ -1,7 +1,10 @@
 //! 应用运行时
 
 mod app;
+mod network;
+mod replay;
 mod window;
 
-pub use app::{MiniApp, UiEvent};
+pub use app::{LaunchOptions, MiniApp, UiEvent};
+pub use replay::{EventLog, RecordedEvent, TimedEvent};
 pub use window::Window;
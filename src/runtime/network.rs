@@ -0,0 +1,105 @@
+//! wx.request 的网络请求
+//!
+//! JS 侧把请求参数序列化为 JSON 交给 MiniApp，请求在后台线程中用 ureq 执行，
+//! 结果通过 channel 送回，由下一次 update 回调到 JS。
+//! HTTP 错误状态码（4xx/5xx）仍然走 success，只有网络错误和超时走 fail。
+
+use serde_json::Value as JsonValue;
+use std::io::Read;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+/// 响应体大小上限
+const MAX_RESPONSE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// 在后台线程中执行请求，完成后把 (调用 ID, 结果) 发送到 tx
+pub(crate) fn spawn_request(id: u32, request: JsonValue, tx: Sender<(u32, Result<JsonValue, String>)>) {
+    std::thread::spawn(move || {
+        let result = perform_request(&request);
+        let _ = tx.send((id, result));
+    });
+}
+
+/// 执行请求，返回 {statusCode, data, header}；失败时返回 errMsg
+fn perform_request(request: &JsonValue) -> Result<JsonValue, String> {
+    let url = request["url"].as_str().unwrap_or_default();
+    let method = request["method"].as_str().unwrap_or("GET").to_uppercase();
+    let timeout = request["timeout"].as_u64().unwrap_or(60000);
+    let agent = ureq::AgentBuilder::new().timeout(Duration::from_millis(timeout)).build();
+
+    let mut req = agent.request(&method, url);
+    let mut content_type = None;
+    if let Some(header) = request["header"].as_object() {
+        for (name, value) in header {
+            let value = value_to_text(value);
+            if name.eq_ignore_ascii_case("content-type") {
+                content_type = Some(value.clone());
+            }
+            req = req.set(name, &value);
+        }
+    }
+
+    // GET 的 data 拼到查询字符串，其他方法按 content-type 编码为请求体
+    let data = &request["data"];
+    let response = match (method.as_str(), data) {
+        (_, JsonValue::Null) => req.call(),
+        ("GET" | "HEAD", JsonValue::Object(fields)) => {
+            for (key, value) in fields {
+                req = req.query(key, &value_to_text(value));
+            }
+            req.call()
+        }
+        (_, JsonValue::Object(fields)) if content_type.as_deref().is_some_and(|t| t.contains("x-www-form-urlencoded")) => {
+            let pairs: Vec<(String, String)> = fields.iter().map(|(k, v)| (k.clone(), value_to_text(v))).collect();
+            let pairs: Vec<(&str, &str)> = pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+            req.send_form(&pairs)
+        }
+        (_, JsonValue::String(body)) => req.send_string(body),
+        (_, body) => {
+            if content_type.is_none() {
+                req = req.set("Content-Type", "application/json");
+            }
+            req.send_string(&body.to_string())
+        }
+    };
+
+    let response = match response {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(ureq::Error::Transport(e)) => {
+            let message = e.to_string();
+            if message.contains("timed out") || message.contains("timeout") {
+                return Err("request:fail timeout".into());
+            }
+            return Err(format!("request:fail {}", message));
+        }
+    };
+
+    let status = response.status();
+    let mut header = serde_json::Map::new();
+    for name in response.headers_names() {
+        if let Some(value) = response.header(&name) {
+            header.insert(name, JsonValue::String(value.to_string()));
+        }
+    }
+
+    let mut body = String::new();
+    response.into_reader().take(MAX_RESPONSE_BYTES).read_to_string(&mut body)
+        .map_err(|e| format!("request:fail {}", e))?;
+
+    // dataType 为 json 时尝试解析，解析失败保留原文
+    let data = if request["dataType"].as_str().unwrap_or("json") == "json" {
+        serde_json::from_str(&body).unwrap_or(JsonValue::String(body))
+    } else {
+        JsonValue::String(body)
+    };
+
+    Ok(serde_json::json!({ "statusCode": status, "data": data, "header": header }))
+}
+
+fn value_to_text(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
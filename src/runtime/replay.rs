@@ -0,0 +1,91 @@
+//! 事件录制与回放
+//!
+//! 录制期间 MiniApp 把进入页面逻辑的输入（点击、触摸、页面滚动、文本输入、
+//! 事件处理函数调用、宿主发起的导航）按时间顺序记录到 `EventLog`。
+//! 回放时切换到模拟时钟，按录制的时间推进定时器并重新派发这些输入，
+//! 得到与录制时相同的页面状态，可用于把用户反馈的问题转成回归测试。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// 录制的输入事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RecordedEvent {
+    /// 点击（逻辑坐标）
+    Tap { x: f32, y: f32 },
+    /// 触摸，phase 为 start / move / end / cancel
+    Touch { x: f32, y: f32, phase: String },
+    /// 页面滚动，触发 Page.onPageScroll
+    Scroll { scroll_top: f32 },
+    /// 文本输入，调用 bindinput 处理函数
+    Input { handler: String, data: JsonValue, value: String },
+    /// 调用页面事件处理函数（bindtap 等），extra 为覆盖到事件对象上的字段
+    PageEvent { handler: String, data: JsonValue, extra: JsonValue },
+    /// 宿主发起的导航（tabBar 切换、返回等），nav_type 与 wx.navigateTo 等一致
+    Navigate { nav_type: String, url: String },
+}
+
+/// 带时间戳的事件，at_ms 为相对录制开始的毫秒数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimedEvent {
+    pub at_ms: u64,
+    pub event: RecordedEvent,
+}
+
+/// 一次录制的完整事件序列
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventLog {
+    pub events: Vec<TimedEvent>,
+    /// 录制总时长（毫秒），回放结束前时钟会推进到这里，让尾部的定时器触发
+    pub duration_ms: u64,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, at_ms: u64, event: RecordedEvent) {
+        self.events.push(TimedEvent { at_ms, event });
+        self.duration_ms = self.duration_ms.max(at_ms);
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid event log: {}", e))
+    }
+}
+
+/// 录制中的状态
+pub(crate) struct EventRecorder {
+    /// 录制开始时的时钟（毫秒）
+    pub start_ms: u64,
+    pub log: EventLog,
+}
+
+/// MiniApp 的时钟：默认使用系统时间，回放和测试时使用手动推进的模拟时钟
+pub(crate) enum Clock {
+    System(std::time::Instant),
+    Mock(u64),
+}
+
+impl Clock {
+    pub fn now_ms(&self) -> u64 {
+        match self {
+            Clock::System(start) => start.elapsed().as_millis() as u64,
+            Clock::Mock(ms) => *ms,
+        }
+    }
+}
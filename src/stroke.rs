@@ -0,0 +1,233 @@
+//! 描边轮廓生成
+//!
+//! 折线按线宽展开成多边形后交给路径填充绘制：每条线段展开为一个矩形，
+//! 拐角按 StrokeJoin 补上斜接 / 圆角 / 斜切，开放折线的两端按 StrokeCap 处理。
+//! 生成的多边形统一为同一方向，用非零规则填充时重叠部分只混合一次。
+
+use crate::paint::{StrokeCap, StrokeJoin};
+use crate::Point;
+
+/// 小于这个距离的相邻点视为重合
+const EPSILON: f32 = 1e-4;
+
+/// 按虚线模式切分折线，返回各段实线（都是开放折线）
+///
+/// 与 canvas 2D 的 setLineDash 一致：奇数个元素时重复一遍，
+/// 含负数或全为 0 时按实线处理（返回 None）
+pub(crate) fn dash_polyline(points: &[Point], closed: bool, pattern: &[f32], offset: f32) -> Option<Vec<Vec<Point>>> {
+    if pattern.is_empty() || pattern.iter().any(|d| *d < 0.0 || !d.is_finite()) {
+        return None;
+    }
+    let mut pattern = pattern.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_slice(&pattern.clone());
+    }
+    let total: f32 = pattern.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let mut pts = points.to_vec();
+    if closed && pts.len() > 1 && pts[0] != pts[pts.len() - 1] {
+        pts.push(pts[0]);
+    }
+
+    // 定位起始偏移所在的虚线段
+    let mut index = 0;
+    let mut remain = pattern[0];
+    let mut skip = offset.rem_euclid(total);
+    while skip > 0.0 {
+        if skip >= remain {
+            skip -= remain;
+            index = (index + 1) % pattern.len();
+            remain = pattern[index];
+        } else {
+            remain -= skip;
+            skip = 0.0;
+        }
+    }
+
+    let mut dashes = Vec::new();
+    let mut current = Vec::new();
+    if index % 2 == 0 && !pts.is_empty() {
+        current.push(pts[0]);
+    }
+    for seg in pts.windows(2) {
+        let (a, b) = (seg[0], seg[1]);
+        let len = a.distance(&b);
+        let mut t = 0.0;
+        while len - t > remain {
+            t += remain;
+            let p = if len > 0.0 { a.lerp(&b, t / len) } else { a };
+            current.push(p);
+            if index % 2 == 0 {
+                dashes.push(std::mem::take(&mut current));
+            }
+            index = (index + 1) % pattern.len();
+            remain = pattern[index];
+        }
+        remain -= len - t;
+        if index % 2 == 0 {
+            current.push(b);
+        }
+    }
+    if index % 2 == 0 && current.len() >= 2 {
+        dashes.push(current);
+    }
+    Some(dashes)
+}
+
+/// 描边参数（设备坐标）
+pub(crate) struct StrokeStyle {
+    pub width: f32,
+    pub cap: StrokeCap,
+    pub join: StrokeJoin,
+    pub miter_limit: f32,
+}
+
+/// 生成折线的描边轮廓，返回若干同向的多边形
+pub(crate) fn stroke_outline(points: &[Point], closed: bool, style: &StrokeStyle) -> Vec<Vec<Point>> {
+    let hw = style.width / 2.0;
+    let mut polygons = Vec::new();
+    if hw <= 0.0 {
+        return polygons;
+    }
+
+    let mut pts: Vec<Point> = Vec::with_capacity(points.len());
+    for p in points {
+        if pts.last().is_none_or(|last: &Point| last.distance(p) > EPSILON) {
+            pts.push(*p);
+        }
+    }
+    if closed && pts.len() > 1 && pts[0].distance(&pts[pts.len() - 1]) <= EPSILON {
+        pts.pop();
+    }
+
+    // 长度为 0 的线段只有线帽
+    if pts.len() == 1 {
+        let p = pts[0];
+        match style.cap {
+            StrokeCap::Round => polygons.push(circle(p, hw)),
+            StrokeCap::Square => polygons.push(vec![
+                Point::new(p.x - hw, p.y - hw),
+                Point::new(p.x + hw, p.y - hw),
+                Point::new(p.x + hw, p.y + hw),
+                Point::new(p.x - hw, p.y + hw),
+            ]),
+            StrokeCap::Butt => {}
+        }
+        return polygons;
+    }
+    if pts.is_empty() {
+        return polygons;
+    }
+
+    let closed = closed && pts.len() > 2;
+    let n = pts.len();
+
+    // 方形线帽：两端沿线段方向延长半个线宽
+    if !closed && style.cap == StrokeCap::Square {
+        let d = direction(pts[1], pts[0]);
+        pts[0] = Point::new(pts[0].x + d.x * hw, pts[0].y + d.y * hw);
+        let d = direction(pts[n - 2], pts[n - 1]);
+        pts[n - 1] = Point::new(pts[n - 1].x + d.x * hw, pts[n - 1].y + d.y * hw);
+    }
+
+    let segments = if closed { n } else { n - 1 };
+    for i in 0..segments {
+        let (a, b) = (pts[i], pts[(i + 1) % n]);
+        let d = direction(a, b);
+        let (nx, ny) = (-d.y * hw, d.x * hw);
+        polygons.push(vec![
+            Point::new(a.x + nx, a.y + ny),
+            Point::new(b.x + nx, b.y + ny),
+            Point::new(b.x - nx, b.y - ny),
+            Point::new(a.x - nx, a.y - ny),
+        ]);
+    }
+
+    let joints = if closed { 0..n } else { 1..n - 1 };
+    for i in joints {
+        let (prev, p, next) = (pts[(i + n - 1) % n], pts[i], pts[(i + 1) % n]);
+        if let Some(join) = join_polygon(prev, p, next, hw, style) {
+            polygons.push(join);
+        }
+    }
+
+    if !closed && style.cap == StrokeCap::Round {
+        polygons.push(circle(pts[0], hw));
+        polygons.push(circle(pts[n - 1], hw));
+    }
+
+    for polygon in &mut polygons {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    polygons
+}
+
+/// 拐角 p 处外侧需要补的多边形
+fn join_polygon(prev: Point, p: Point, next: Point, hw: f32, style: &StrokeStyle) -> Option<Vec<Point>> {
+    let d0 = direction(prev, p);
+    let d1 = direction(p, next);
+    let cross = d0.x * d1.y - d0.y * d1.x;
+    let dot = d0.x * d1.x + d0.y * d1.y;
+    if cross.abs() < 1e-6 && dot > 0.0 {
+        return None;
+    }
+    if style.join == StrokeJoin::Round {
+        return Some(circle(p, hw));
+    }
+
+    // 折线向法线正方向转弯时外侧在负方向
+    let s = if cross > 0.0 { -hw } else { hw };
+    let a = Point::new(p.x - d0.y * s, p.y + d0.x * s);
+    let b = Point::new(p.x - d1.y * s, p.y + d1.x * s);
+
+    if style.join == StrokeJoin::Miter {
+        let (mx, my) = (-d0.y - d1.y, d0.x + d1.x);
+        let len = (mx * mx + my * my).sqrt();
+        if len > EPSILON {
+            // cos(θ/2) = m · n0，斜接长度 / 线宽 = 1 / cos(θ/2)
+            let cos_half = (mx * -d0.y + my * d0.x) / len;
+            if cos_half > EPSILON && 1.0 / cos_half <= style.miter_limit {
+                let k = s / cos_half / len;
+                let tip = Point::new(p.x + mx * k, p.y + my * k);
+                return Some(vec![p, a, tip, b]);
+            }
+        }
+    }
+    Some(vec![p, a, b])
+}
+
+/// 以 radius 为半径的圆，边数保证弦高误差不超过 0.05 像素
+fn circle(center: Point, radius: f32) -> Vec<Point> {
+    let step = (1.0 - 0.05 / radius).clamp(-1.0, 1.0).acos();
+    let count = if step > 0.0 { (std::f32::consts::PI / step).ceil() as usize } else { 8 };
+    let count = count.clamp(8, 128);
+    (0..count)
+        .map(|i| {
+            let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+            Point::new(center.x + radius * angle.cos(), center.y + radius * angle.sin())
+        })
+        .collect()
+}
+
+/// a 指向 b 的单位向量
+fn direction(a: Point, b: Point) -> Point {
+    let len = a.distance(&b);
+    if len <= 0.0 {
+        return Point::new(1.0, 0.0);
+    }
+    Point::new((b.x - a.x) / len, (b.y - a.y) / len)
+}
+
+fn signed_area(polygon: &[Point]) -> f32 {
+    let mut area = 0.0;
+    for i in 0..polygon.len() {
+        let (p, q) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        area += p.x * q.y - q.x * p.y;
+    }
+    area / 2.0
+}
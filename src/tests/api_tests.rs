@@ -0,0 +1,643 @@
+//! 小程序 API 单元测试
+//! 测试 wx.* 接口与宿主之间的交互
+
+use crate::runtime::{EventLog, LaunchOptions, MiniApp, RecordedEvent, UiEvent};
+use crate::js::SystemInfo;
+use serde_json::json;
+
+/// 创建已初始化的小程序实例
+fn create_test_app() -> MiniApp {
+    let mut app = MiniApp::new(375, 667).unwrap();
+    app.init().unwrap();
+    app
+}
+
+/// 测试授权流程：authorize 发起请求，宿主应答后 getSetting 反映授权结果
+#[test]
+fn test_authorize_then_get_setting() {
+    let mut app = create_test_app();
+    
+    app.eval(r#"
+        var __authResult = 'none';
+        wx.authorize({
+            scope: 'scope.userLocation',
+            success: function() { __authResult = 'success'; },
+            fail: function() { __authResult = 'fail'; }
+        });
+    "#).unwrap();
+    
+    // 尚未应答，回调不应触发
+    assert_eq!(app.eval("__authResult").unwrap(), "none");
+    
+    app.update().unwrap();
+    let events = app.drain_ui_events();
+    assert!(events.iter().any(|e| matches!(e, UiEvent::AuthRequest { scope } if scope == "scope.userLocation")));
+    
+    app.resolve_auth("scope.userLocation", true).unwrap();
+    assert_eq!(app.eval("__authResult").unwrap(), "success");
+    
+    let granted = app.eval(r#"
+        var __granted = null;
+        wx.getSetting({ success: function(res) { __granted = res.authSetting['scope.userLocation']; } });
+        __granted
+    "#).unwrap();
+    assert_eq!(granted, "true");
+}
+
+/// 测试 showModal 交给宿主显示，宿主按点击的按钮应答后回调 success/complete
+#[test]
+fn test_show_modal_resolved_by_host() {
+    let mut app = create_test_app();
+    app.eval(r#"
+        var __modalResult = 'none';
+        wx.showModal({
+            title: '删除',
+            content: '确定删除吗？',
+            success: function(res) { __modalResult = res.confirm + '|' + res.cancel; },
+            complete: function() { wx.showToast({ title: '已处理' }); }
+        });
+    "#).unwrap();
+    
+    app.update().unwrap();
+    let events = app.drain_ui_events();
+    assert!(events.iter().any(|e| matches!(e, UiEvent::ShowModal { title, show_cancel: true, .. } if title == "删除")));
+    assert_eq!(app.eval("__modalResult").unwrap(), "none");
+    
+    app.resolve_modal(false).unwrap();
+    assert_eq!(app.eval("__modalResult").unwrap(), "false|true");
+    assert_eq!(app.eval("JSON.parse(__getUIState()).modal").unwrap(), "null");
+    // complete 中弹出的 toast 立即交给宿主
+    assert!(app.drain_ui_events().iter().any(|e| matches!(e, UiEvent::ShowToast { title, .. } if title == "已处理")));
+}
+
+/// 测试 wx.getSystemInfoSync 返回宿主设置的设备信息和安全区域
+#[test]
+fn test_system_info_reflects_host_metrics() {
+    let mut app = create_test_app();
+    assert_eq!(app.eval("wx.getSystemInfoSync().windowWidth").unwrap(), "375");
+    
+    let info = SystemInfo { pixel_ratio: 2.0, status_bar_height: 44.0, safe_area_insets: [44.0, 0.0, 34.0, 0.0], ..app.system_info().clone() };
+    app.set_system_info(info).unwrap();
+    assert_eq!(app.eval("wx.getSystemInfoSync().pixelRatio").unwrap(), "2");
+    assert_eq!(app.eval("wx.getSystemInfoSync().statusBarHeight").unwrap(), "44");
+    let area = app.eval("JSON.stringify(wx.getSystemInfoSync().safeArea)").unwrap();
+    let area: serde_json::Value = serde_json::from_str(&area).unwrap();
+    assert_eq!(area, json!({ "left": 0, "right": 375, "top": 44, "bottom": 633, "width": 375, "height": 589 }));
+    
+    // 修改返回值不影响下一次调用
+    assert_eq!(app.eval("var i = wx.getSystemInfoSync(); i.windowWidth = 1; wx.getSystemInfoSync().windowWidth").unwrap(), "375");
+    app.eval("var __info; wx.getSystemInfo({ success: function(res) { __info = res; } })").unwrap();
+    assert_eq!(app.eval("__info.errMsg + '|' + __info.windowHeight").unwrap(), "getSystemInfo:ok|667");
+}
+
+/// 测试定时器按到期时间触发，interval 按固定节奏重复，clear 后不再触发
+#[test]
+fn test_timers_fire_in_deadline_order_and_intervals_repeat() {
+    let mut app = create_test_app();
+    app.use_mock_clock();
+    app.eval(r#"
+        var __fired = [];
+        setTimeout(function() { __fired.push('late'); }, 300);
+        setTimeout(function() { __fired.push('early'); }, 100);
+        var __ticks = 0;
+        var __interval = setInterval(function() {
+            __ticks++;
+            if (__ticks === 3) { clearInterval(__interval); }
+        }, 100);
+        var __cancelled = setTimeout(function() { __fired.push('cancelled'); }, 50);
+        clearTimeout(__cancelled);
+    "#).unwrap();
+    app.update().unwrap();
+    
+    app.advance_clock(99).unwrap();
+    assert_eq!(app.eval("__fired.join(',') + '|' + __ticks").unwrap(), "|0");
+    app.advance_clock(1).unwrap();
+    assert_eq!(app.eval("__fired.join(',') + '|' + __ticks").unwrap(), "early|1");
+    // 每帧推进 40ms，interval 仍在 200ms / 300ms 触发而不是逐帧累积误差
+    for _ in 0..5 { app.advance_clock(40).unwrap(); }
+    assert_eq!(app.eval("__fired.join(',') + '|' + __ticks").unwrap(), "early,late|3");
+    assert!(!app.has_active_timers());
+    
+    app.advance_clock(1000).unwrap();
+    assert_eq!(app.eval("__ticks").unwrap(), "3");
+}
+
+/// 测试 wx.showActionSheet 由宿主返回选中项，取消走 fail
+#[test]
+fn test_show_action_sheet_resolved_by_host() {
+    let mut app = create_test_app();
+    app.eval(r#"
+        var __sheetResult = 'none';
+        function __showSheet() {
+            wx.showActionSheet({
+                itemList: ['拍照', '从相册选择'],
+                itemColor: '#576B95',
+                success: function(res) { __sheetResult = 'ok:' + res.tapIndex; },
+                fail: function(res) { __sheetResult = res.errMsg; }
+            });
+        }
+        __showSheet();
+    "#).unwrap();
+    
+    app.update().unwrap();
+    let events = app.drain_ui_events();
+    assert!(events.iter().any(|e| matches!(e, UiEvent::ShowActionSheet { item_list, item_color }
+        if item_list.len() == 2 && item_list[1] == "从相册选择" && item_color == "#576B95")));
+    
+    app.resolve_action_sheet(Some(1)).unwrap();
+    assert_eq!(app.eval("__sheetResult").unwrap(), "ok:1");
+    assert_eq!(app.eval("JSON.parse(__getUIState()).actionSheet").unwrap(), "null");
+    
+    app.eval("__showSheet()").unwrap();
+    app.update().unwrap();
+    app.drain_ui_events();
+    app.resolve_action_sheet(None).unwrap();
+    assert_eq!(app.eval("__sheetResult").unwrap(), "showActionSheet:fail cancel");
+    
+    // 超过 6 项直接失败，不弹出
+    app.eval("wx.showActionSheet({ itemList: ['1','2','3','4','5','6','7'], fail: function(res) { __sheetResult = res.errMsg; } })").unwrap();
+    app.update().unwrap();
+    assert!(!app.drain_ui_events().iter().any(|e| matches!(e, UiEvent::ShowActionSheet { .. })));
+    assert!(app.eval("__sheetResult").unwrap().starts_with("showActionSheet:fail parameter error"));
+}
+
+/// 测试 toast 由 JS 定时器在 duration 后关闭，宿主收到 HideToast
+#[test]
+fn test_toast_hidden_by_js_timer() {
+    let mut app = create_test_app();
+    app.use_mock_clock();
+    app.eval("wx.showToast({ title: '加载中', icon: 'loading', duration: 1000 })").unwrap();
+    app.update().unwrap();
+    assert!(app.drain_ui_events().iter().any(|e| matches!(e, UiEvent::ShowToast { icon, duration: 1000, .. } if icon == "loading")));
+    
+    app.advance_clock(999).unwrap();
+    assert!(!app.drain_ui_events().iter().any(|e| matches!(e, UiEvent::HideToast)));
+    app.advance_clock(1).unwrap();
+    assert!(app.drain_ui_events().iter().any(|e| matches!(e, UiEvent::HideToast)));
+    assert_eq!(app.eval("JSON.parse(__getUIState()).toast").unwrap(), "null");
+}
+
+/// 测试未授权的 scope 默认拒绝，已拒绝的 scope 不再发起请求
+#[test]
+fn test_authorize_denied() {
+    let mut app = create_test_app();
+    
+    let setting = app.eval(r#"
+        var __camera = 'unset';
+        wx.getSetting({ success: function(res) { __camera = String(res.authSetting['scope.camera']); } });
+        __camera
+    "#).unwrap();
+    assert_eq!(setting, "undefined");
+    
+    app.resolve_auth("scope.camera", false).unwrap();
+    
+    let result = app.eval(r#"
+        var __cameraResult = 'none';
+        wx.authorize({
+            scope: 'scope.camera',
+            success: function() { __cameraResult = 'success'; },
+            fail: function() { __cameraResult = 'fail'; }
+        });
+        __cameraResult
+    "#).unwrap();
+    assert_eq!(result, "fail");
+    
+    app.update().unwrap();
+    assert!(app.drain_ui_events().is_empty());
+}
+
+/// 测试支付：requestPayment 交给宿主处理，宿主应答后在 update 中回调 success/complete
+#[test]
+fn test_request_payment_completed_by_host() {
+    let mut app = create_test_app();
+    
+    app.eval(r#"
+        var __payResult = 'none';
+        var __payComplete = false;
+        wx.requestPayment({
+            timeStamp: '1700000000',
+            nonceStr: 'abc',
+            package: 'prepay_id=wx123',
+            paySign: 'sign',
+            success: function(res) { __payResult = res.errMsg + '|' + res.transactionId; },
+            fail: function(res) { __payResult = 'fail'; },
+            complete: function() { __payComplete = true; }
+        });
+    "#).unwrap();
+    
+    app.update().unwrap();
+    let id = app.drain_ui_events().into_iter().find_map(|e| match e {
+        UiEvent::RequestPayment { id, order } => {
+            assert_eq!(order["package"], "prepay_id=wx123");
+            Some(id)
+        }
+        _ => None,
+    }).expect("payment request");
+    assert_eq!(app.eval("__payResult").unwrap(), "none");
+    
+    // 应答后下一次 update 才回调
+    app.complete_bridge_call(id, Ok(json!({ "transactionId": "T001" })));
+    assert_eq!(app.eval("__payResult").unwrap(), "none");
+    app.update().unwrap();
+    assert_eq!(app.eval("__payResult").unwrap(), "requestPayment:ok|T001");
+    assert_eq!(app.eval("__payComplete").unwrap(), "true");
+    
+    // 重复应答同一个调用没有效果
+    app.complete_bridge_call(id, Err("requestPayment:fail cancel".into()));
+    app.update().unwrap();
+    assert_eq!(app.eval("__payResult").unwrap(), "requestPayment:ok|T001");
+}
+
+/// 加载带点击、输入和定时器的测试页面
+fn load_form_page(app: &MiniApp) {
+    app.load_script(r#"
+        Page({
+            data: { count: 0, name: '', saved: false },
+            onAdd: function(e) {
+                this.setData({ count: this.data.count + e.currentTarget.dataset.step });
+                var page = this;
+                setTimeout(function() { page.setData({ saved: true }); }, 500);
+            },
+            onInput: function(e) {
+                this.setData({ name: e.detail.value });
+            }
+        });
+    "#).unwrap();
+}
+
+/// 测试录制点击和输入序列后回放，得到相同的页面数据
+#[test]
+fn test_record_and_replay_tap_and_input() {
+    let mut app = create_test_app();
+    load_form_page(&app);
+    app.use_mock_clock();
+    app.start_recording();
+    
+    app.dispatch_page_event("onAdd", &json!({ "step": "2" }), &json!({})).unwrap();
+    app.advance_clock(100).unwrap();
+    app.on_input("onInput", &json!({}), "h").unwrap();
+    app.advance_clock(50).unwrap();
+    app.on_input("onInput", &json!({}), "hi").unwrap();
+    app.advance_clock(600).unwrap();
+    
+    let log = app.stop_recording().unwrap();
+    assert_eq!(log.len(), 3);
+    assert_eq!(log.duration_ms, 750);
+    assert!(matches!(&log.events[2].event, RecordedEvent::Input { value, .. } if value == "hi"));
+    let expected = app.eval("__getPageData()").unwrap();
+    assert!(expected.contains(r#""saved":true"#), "{}", expected);
+    
+    // 经过 JSON 序列化后在新实例上回放
+    let log = EventLog::from_json(&log.to_json().unwrap()).unwrap();
+    let mut replayed = create_test_app();
+    load_form_page(&replayed);
+    replayed.replay(&log).unwrap();
+    assert_eq!(replayed.eval("__getPageData()").unwrap(), expected);
+}
+
+/// 测试 bindinput 返回字符串时作为输入框的新值，不返回时保持原值
+#[test]
+fn test_input_handler_return_value_replaces_value() {
+    let mut app = create_test_app();
+    app.load_script(r#"
+        Page({
+            data: {},
+            onUpper: function(e) { return e.detail.value.toUpperCase(); },
+            onDigits: function(e) { return { value: e.detail.value.replace(/\D/g, '') }; },
+            onPlain: function(e) {}
+        });
+    "#).unwrap();
+    
+    assert_eq!(app.on_input("onUpper", &json!({}), "abc").unwrap(), Some("ABC".to_string()));
+    assert_eq!(app.on_input("onDigits", &json!({}), "1a2").unwrap(), Some("12".to_string()));
+    assert_eq!(app.on_input("onPlain", &json!({}), "abc").unwrap(), None);
+    assert_eq!(app.on_input("onMissing", &json!({}), "abc").unwrap(), None);
+}
+
+/// 测试切到后台再回到前台，App.onHide 先于 App.onShow 触发，onShow 带上场景值
+#[test]
+fn test_app_hide_then_show() {
+    let mut app = create_test_app();
+    app.set_launch_options(LaunchOptions { path: "pages/index/index".into(), scene: 1011, ..Default::default() }).unwrap();
+    app.load_script(r#"
+        var __log = [];
+        App({
+            onLaunch: function(o) { __log.push('launch:' + o.scene); },
+            onShow: function(o) { __log.push('show:' + o.scene + ':' + o.path); },
+            onHide: function() { __log.push('hide'); }
+        });
+        wx.onAppHide(function() { __log.push('listener-hide'); });
+        wx.onAppShow(function(o) { __log.push('listener-show:' + o.scene); });
+    "#).unwrap();
+    
+    app.start().unwrap();
+    // 重复的前台通知不会再次触发 onShow
+    app.on_app_show().unwrap();
+    assert_eq!(app.eval("JSON.stringify(__log)").unwrap(),
+        r#"["launch:1011","show:1011:pages/index/index","listener-show:1011"]"#);
+    app.eval("__log.length = 0").unwrap();
+    
+    app.on_app_hide().unwrap();
+    assert!(!app.is_foreground());
+    app.on_app_show().unwrap();
+    assert!(app.is_foreground());
+    
+    let log = app.eval("JSON.stringify(__log)").unwrap();
+    assert_eq!(log, r#"["hide","listener-hide","show:1011:pages/index/index","listener-show:1011"]"#);
+    assert_eq!(app.eval("wx.getLaunchOptionsSync().scene").unwrap(), "1011");
+}
+
+/// 在本地端口上启动只应答一次的 HTTP 服务，delay 后返回 JSON 响应
+fn serve_once(delay_ms: u64) -> u16 {
+    use std::io::{Read, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        if let Ok((mut stream, _)) = listener.accept() {
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            std::thread::sleep(std::time::Duration::from_millis(delay_ms));
+            let body = format!(r#"{{"ok":true,"path":"{}"}}"#, request.split_whitespace().nth(1).unwrap_or(""));
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nX-Test: yes\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(), body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    port
+}
+
+/// 驱动 update 直到 wx.request 回调（最多约 5 秒）
+fn wait_for_request(app: &mut MiniApp) {
+    app.update().unwrap();
+    for _ in 0..500 {
+        if !app.has_pending_requests() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        app.update().unwrap();
+    }
+    app.update().unwrap();
+}
+
+/// 测试 wx.request：JSON 响应解析后在 update 中回调 success
+#[test]
+fn test_request_json_success() {
+    let mut app = create_test_app();
+    let port = serve_once(0);
+    
+    app.eval(&format!(r#"
+        var __res = null;
+        var __complete = false;
+        wx.request({{
+            url: 'http://127.0.0.1:{}/api/list',
+            data: {{ page: 2 }},
+            success: function(res) {{ __res = res; }},
+            complete: function() {{ __complete = true; }}
+        }});
+    "#, port)).unwrap();
+    assert_eq!(app.eval("__complete").unwrap(), "false");
+    
+    wait_for_request(&mut app);
+    assert_eq!(app.eval("__res.statusCode").unwrap(), "200");
+    assert_eq!(app.eval("__res.data.ok").unwrap(), "true");
+    assert_eq!(app.eval("__res.data.path").unwrap(), "/api/list?page=2");
+    assert_eq!(app.eval("__res.header['x-test']").unwrap(), "yes");
+    assert_eq!(app.eval("__res.errMsg").unwrap(), "request:ok");
+    assert_eq!(app.eval("__complete").unwrap(), "true");
+}
+
+/// 测试 wx.request 的失败路径：连接失败与超时
+#[test]
+fn test_request_fail_and_timeout() {
+    let mut app = create_test_app();
+    
+    // 绑定后立即释放端口，连接会被拒绝
+    let closed_port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    app.eval(&format!(r#"
+        var __fail = null;
+        wx.request({{
+            url: 'http://127.0.0.1:{}/',
+            success: function() {{ __fail = 'success'; }},
+            fail: function(res) {{ __fail = res.errMsg; }}
+        }});
+    "#, closed_port)).unwrap();
+    wait_for_request(&mut app);
+    let err = app.eval("__fail").unwrap();
+    assert!(err.starts_with("request:fail"), "{}", err);
+    
+    let slow_port = serve_once(1000);
+    app.eval(&format!(r#"
+        var __timeout = null;
+        wx.request({{
+            url: 'http://127.0.0.1:{}/slow',
+            timeout: 100,
+            success: function() {{ __timeout = 'success'; }},
+            fail: function(res) {{ __timeout = res.errMsg; }}
+        }});
+    "#, slow_port)).unwrap();
+    wait_for_request(&mut app);
+    assert_eq!(app.eval("__timeout").unwrap(), "request:fail timeout");
+    
+    // 空 url 直接失败
+    app.eval("var __empty = null; wx.request({ fail: function(res) { __empty = res.errMsg; } });").unwrap();
+    app.update().unwrap();
+    assert_eq!(app.eval("__empty").unwrap(), "request:fail invalid url");
+}
+
+/// 测试 requestAnimationFrame：每次 flush 执行一帧，回调中注册的留到下一帧
+#[test]
+fn test_request_animation_frame() {
+    let mut app = create_test_app();
+    app.use_mock_clock();
+    
+    app.eval(r#"
+        var __frames = [];
+        var __start = null;
+        function step(ts) {
+            if (__start === null) { __start = ts; }
+            __frames.push(ts - __start);
+            if (__frames.length < 3) { wx.requestAnimationFrame(step); }
+        }
+        wx.requestAnimationFrame(step);
+        var __cancelled = false;
+        var cancelId = requestAnimationFrame(function() { __cancelled = true; });
+        cancelAnimationFrame(cancelId);
+    "#).unwrap();
+    assert!(app.has_animation_frames());
+    
+    assert!(app.flush_animation_frames().unwrap());
+    app.advance_clock(16).unwrap();
+    assert!(app.flush_animation_frames().unwrap());
+    app.advance_clock(16).unwrap();
+    assert!(!app.flush_animation_frames().unwrap());
+    
+    assert_eq!(app.eval("JSON.stringify(__frames)").unwrap(), "[0,16,32]");
+    assert_eq!(app.eval("__cancelled").unwrap(), "false");
+    assert!(!app.has_animation_frames());
+}
+
+/// 测试页面脚本发起的导航：请求写入 __pendingNavigation 供宿主取出
+#[test]
+fn test_navigation_requests_from_js() {
+    let app = create_test_app();
+    
+    app.eval(r#"
+        var __navLog = [];
+        wx.redirectTo({ url: '../detail/detail?id=7', success: function(res) { __navLog.push(res.errMsg); } });
+    "#).unwrap();
+    let nav: serde_json::Value = serde_json::from_str(&app.eval("JSON.stringify(__pendingNavigation)").unwrap()).unwrap();
+    assert_eq!(nav, json!({ "type": "redirectTo", "url": "../detail/detail?id=7" }));
+    
+    app.eval("wx.navigateBack({ delta: 2 })").unwrap();
+    let nav: serde_json::Value = serde_json::from_str(&app.eval("JSON.stringify(__pendingNavigation)").unwrap()).unwrap();
+    assert_eq!(nav["type"], "navigateBack");
+    assert_eq!(nav["delta"], 2);
+    
+    app.eval("wx.reLaunch({ url: '/pages/index/index' })").unwrap();
+    assert_eq!(app.eval("__pendingNavigation.type").unwrap(), "reLaunch");
+    
+    // 缺少 url 时走 fail，不覆盖已有请求
+    app.eval("wx.navigateTo({ fail: function(res) { __navLog.push(res.errMsg); }, complete: function() { __navLog.push('complete'); } })").unwrap();
+    assert_eq!(app.eval("__pendingNavigation.type").unwrap(), "reLaunch");
+    assert_eq!(app.eval("JSON.stringify(__navLog)").unwrap(),
+        r#"["redirectTo:ok","navigateTo:fail url is required","complete"]"#);
+}
+
+/// 页面脚本：生命周期写入全局 __life
+fn lifecycle_page(name: &str) -> String {
+    format!(r#"
+        Page({{
+            data: {{ name: '{0}', visits: 0 }},
+            onLoad: function(q) {{ __life.push('{0}.load:' + (q.id || '')); }},
+            onShow: function() {{ this.data.visits++; __life.push('{0}.show'); }},
+            onReady: function() {{ __life.push('{0}.ready'); }},
+            onHide: function() {{ __life.push('{0}.hide'); }},
+            onUnload: function() {{ __life.push('{0}.unload'); }}
+        }});
+    "#, name)
+}
+
+/// 测试页面生命周期：入栈、覆盖、返回、重定向时的回调顺序
+#[test]
+fn test_page_lifecycle_order() {
+    let mut app = create_test_app();
+    app.eval("var __life = [];").unwrap();
+    let take_log = |app: &MiniApp| {
+        let log = app.eval("JSON.stringify(__life.splice(0))").unwrap();
+        serde_json::from_str::<Vec<String>>(&log).unwrap().join(",")
+    };
+    
+    app.push_page("pages/a/a", &lifecycle_page("a"), &json!({})).unwrap();
+    app.page_ready().unwrap();
+    app.page_ready().unwrap();
+    assert_eq!(take_log(&app), "a.load:,a.show,a.ready");
+    
+    app.push_page("pages/b/b", &lifecycle_page("b"), &json!({ "id": "42" })).unwrap();
+    assert_eq!(take_log(&app), "a.hide,b.load:42,b.show");
+    assert_eq!(app.eval("getCurrentPages().map(function(p) { return p.route; }).join('|')").unwrap(), "pages/a/a|pages/b/b");
+    
+    // 返回时原页面保留数据，只触发 onShow
+    app.pop_pages(1).unwrap();
+    assert_eq!(take_log(&app), "b.unload,a.show");
+    assert_eq!(app.eval("__getPageInstance().data.visits").unwrap(), "2");
+    
+    // redirectTo：当前页面 onUnload，不触发下面页面的 onShow
+    app.push_page("pages/b/b", &lifecycle_page("b"), &json!({})).unwrap();
+    take_log(&app);
+    app.unload_pages(1).unwrap();
+    app.push_page("pages/c/c", &lifecycle_page("c"), &json!({})).unwrap();
+    assert_eq!(take_log(&app), "b.unload,c.load:,c.show");
+    
+    // 切到后台和前台时栈顶页面也会 onHide / onShow
+    app.start().unwrap();
+    app.on_app_hide().unwrap();
+    app.on_app_show().unwrap();
+    assert_eq!(take_log(&app), "c.hide,c.show");
+    
+    // 至少保留一个页面
+    app.pop_pages(5).unwrap();
+    assert_eq!(take_log(&app), "c.unload,a.show");
+    assert_eq!(app.eval("getCurrentPages().length").unwrap(), "1");
+}
+
+/// 测试 setData 的路径写法：数组元素、嵌套字段以及自动创建中间节点
+#[test]
+fn test_set_data_with_paths() {
+    let app = create_test_app();
+    app.load_script(r#"
+        Page({
+            data: {
+                todos: [{ text: 'a', done: false }, { text: 'b', done: false }],
+                user: { name: 'x' }
+            }
+        });
+    "#).unwrap();
+    
+    app.eval(r#"
+        __currentPage.setData({
+            'todos[1].done': true,
+            'user.profile.age': 18,
+            'matrix[1][0]': 'm',
+            'count': 3
+        });
+    "#).unwrap();
+    
+    let data: serde_json::Value = serde_json::from_str(&app.eval("__getPageData()").unwrap()).unwrap();
+    assert_eq!(data["todos"][0]["done"], false);
+    assert_eq!(data["todos"][1]["done"], true);
+    assert_eq!(data["todos"][1]["text"], "b");
+    assert_eq!(data["user"], json!({ "name": "x", "profile": { "age": 18 } }));
+    assert_eq!(data["matrix"], json!([null, ["m"]]));
+    assert_eq!(data["count"], 3);
+}
+
+/// 测试 canvas 上下文的 measureText 按 setFontSize 的字号同步返回宽度
+#[test]
+fn test_canvas_measure_text() {
+    let app = create_test_app();
+    let widths = app.eval(r#"
+        var ctx = wx.createCanvasContext('measure');
+        var small = ctx.measureText('hello').width;
+        ctx.setFontSize(20);
+        var large = ctx.measureText('hello').width;
+        JSON.stringify([small, large])
+    "#).unwrap();
+    let widths: Vec<f64> = serde_json::from_str(&widths).unwrap();
+    assert!(widths[0] > 0.0);
+    assert!((widths[1] - widths[0] * 2.0).abs() < 0.5);
+}
+
+/// 测试 canvasToTempFilePath 在 draw 之后导出 PNG，支持裁剪区域和目标尺寸
+#[test]
+fn test_canvas_to_temp_file_path() {
+    let mut app = create_test_app();
+    app.eval(r#"
+        var __exported = null;
+        var __exportError = null;
+        var ctx = wx.createCanvasContext('export-test');
+        ctx.setFillStyle('#ff0000');
+        ctx.fillRect(10, 10, 20, 20);
+        ctx.draw();
+        wx.canvasToTempFilePath({
+            canvasId: 'export-test',
+            x: 10, y: 10, width: 20, height: 20,
+            destWidth: 40, destHeight: 40,
+            success: function(res) { __exported = res.tempFilePath; }
+        });
+        wx.canvasToTempFilePath({
+            canvasId: 'missing-canvas',
+            fail: function(res) { __exportError = res.errMsg; }
+        });
+    "#).unwrap();
+    app.update().unwrap();
+
+    let path = app.eval("__exported").unwrap();
+    let img = image::open(&path).unwrap().to_rgba8();
+    assert_eq!(img.dimensions(), (40, 40));
+    assert_eq!(img.get_pixel(20, 20).0, [255, 0, 0, 255]);
+    assert!(app.eval("__exportError").unwrap().starts_with("canvasToTempFilePath:fail"));
+    std::fs::remove_file(&path).ok();
+}
@@ -661,3 +661,121 @@ fn test_flex_basis() {
     // 应该使用 flex-basis 的值
     assert_eq!(layout.size.width, 150.0, "width should be flex-basis value");
 }
+
+/// 测试 width: fit-content（标签 chip 宽度贴合文字，而不是被 stretch 拉满整行）
+#[test]
+fn test_fit_content_chip() {
+    use crate::parser::wxss::StyleValue;
+    use crate::renderer::components::{resolve_intrinsic_width, NodeStyle, SizeKeyword};
+    
+    let keyword = SizeKeyword::parse(&StyleValue::String("fit-content".into()));
+    assert_eq!(keyword, Some(SizeKeyword::FitContent));
+    
+    let mut taffy: TaffyTree<()> = TaffyTree::new();
+    
+    // 文字标签（宽度为测量出的文本宽度）
+    let label = taffy.new_leaf(Style {
+        size: Size { width: length(56.0), height: length(20.0) },
+        ..Default::default()
+    }).unwrap();
+    
+    // chip - width: fit-content，按 auto 布局后再换算
+    let chip = taffy.new_with_children(
+        Style {
+            flex_direction: FlexDirection::Column,
+            padding: Rect { left: length(8.0), right: length(8.0), top: length(4.0), bottom: length(4.0) },
+            ..Default::default()
+        },
+        &[label],
+    ).unwrap();
+    let ns = NodeStyle { width_keyword: keyword, ..Default::default() };
+    resolve_intrinsic_width(&mut taffy, chip, &ns);
+    
+    // 普通 view（宽度 auto，会被拉伸）
+    let row = taffy.new_leaf(Style {
+        size: Size { width: auto(), height: length(20.0) },
+        ..Default::default()
+    }).unwrap();
+    
+    let container = taffy.new_with_children(
+        Style {
+            size: Size { width: length(375.0), height: auto() },
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        },
+        &[chip, row],
+    ).unwrap();
+    
+    taffy.compute_layout(container, Size::MAX_CONTENT).unwrap();
+    
+    let chip_layout = taffy.layout(chip).unwrap();
+    let row_layout = taffy.layout(row).unwrap();
+    
+    // chip 宽度 = 文字宽度 + 左右内边距
+    assert_eq!(chip_layout.size.width, 72.0, "fit-content chip should hug its label");
+    assert_eq!(row_layout.size.width, 375.0, "auto width view should stretch");
+}
+
+/// 测试 display: inline-block 标签（两个 chip 并排，宽度贴合内容，不再各占一行）
+#[test]
+fn test_inline_block_chips_side_by_side() {
+    use crate::renderer::components::{wrap_inline_runs, InlineLevel, NodeStyle, RenderNode};
+    use std::collections::HashMap;
+    
+    let mut taffy: TaffyTree<()> = TaffyTree::new();
+    
+    // min-width 模拟内容宽度（含左右内边距）
+    let node = |taffy: &mut TaffyTree<()>, content_width: f32, inline: bool| {
+        let id = taffy.new_leaf(Style {
+            size: Size { width: auto(), height: length(20.0) },
+            min_size: Size { width: length(content_width), height: auto() },
+            padding: Rect { left: length(8.0), right: length(8.0), top: zero(), bottom: zero() },
+            ..Default::default()
+        }).unwrap();
+        RenderNode {
+            tag: "view".into(),
+            text: String::new(),
+            attrs: HashMap::new(),
+            taffy_node: id,
+            style: NodeStyle {
+                inline_level: if inline { Some(InlineLevel::InlineBlock) } else { None },
+                ..Default::default()
+            },
+            children: vec![],
+            events: vec![],
+        }
+    };
+    let chip_a = node(&mut taffy, 40.0, true);
+    let chip_b = node(&mut taffy, 56.0, true);
+    let title = node(&mut taffy, 0.0, false);
+    let (a, b) = (chip_a.taffy_node, chip_b.taffy_node);
+    
+    // 默认 view：列方向 flex 容器，没有显式声明 display: flex
+    let ts = Style {
+        size: Size { width: length(375.0), height: auto() },
+        flex_direction: FlexDirection::Column,
+        ..Default::default()
+    };
+    let children = wrap_inline_runs(&mut taffy, &ts, &NodeStyle::default(), vec![chip_a, chip_b, title]);
+    assert_eq!(children.len(), 2, "chips should share one anonymous line box");
+    
+    let ids: Vec<NodeId> = children.iter().map(|c| c.taffy_node).collect();
+    let container = taffy.new_with_children(ts, &ids).unwrap();
+    taffy.compute_layout(container, Size::MAX_CONTENT).unwrap();
+    
+    let a_layout = *taffy.layout(a).unwrap();
+    let b_layout = *taffy.layout(b).unwrap();
+    let line_layout = *taffy.layout(ids[0]).unwrap();
+    let title_layout = *taffy.layout(ids[1]).unwrap();
+    
+    // chip 宽度 = 内容宽度，两者在同一行
+    assert_eq!(a_layout.size.width, 40.0, "inline-block chip should hug its content");
+    assert_eq!(b_layout.size.width, 56.0);
+    assert_eq!(a_layout.location.y, b_layout.location.y, "chips should sit on the same line");
+    assert_eq!(b_layout.location.x, 40.0, "second chip should follow the first");
+    
+    // 块级兄弟元素仍然另起一行并占满宽度
+    assert_eq!(line_layout.size.width, 375.0);
+    assert_eq!(title_layout.location.y, 20.0);
+    assert_eq!(title_layout.size.width, 375.0);
+}
@@ -62,6 +62,9 @@ fn test_view_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = ViewComponent::build(&node, &mut ctx);
@@ -99,6 +102,9 @@ fn test_text_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = TextComponent::build(&node, &mut ctx);
@@ -132,6 +138,9 @@ fn test_button_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = ButtonComponent::build(&node, &mut ctx);
@@ -161,6 +170,9 @@ fn test_icon_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = IconComponent::build(&node, &mut ctx);
@@ -170,6 +182,40 @@ fn test_icon_component_build() {
     assert_eq!(rn.tag, "icon");
 }
 
+/// 测试 icon 的 size 单位和 clear 的默认颜色
+#[test]
+fn test_icon_size_units_and_clear_color() {
+    let stylesheet = parse_css("");
+    let mut taffy = TaffyTree::new();
+    let mut ctx = ComponentContext {
+        scale_factor: 2.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+    
+    let icon = |ctx: &mut ComponentContext, ty: &str, size: &str| {
+        let mut attrs = HashMap::new();
+        attrs.insert("type".to_string(), ty.to_string());
+        attrs.insert("size".to_string(), size.to_string());
+        IconComponent::build(&create_test_node("icon", &[], attrs), ctx).unwrap()
+    };
+    
+    // 750rpx 为屏幕宽度：100rpx = 50px，再乘缩放比例
+    for (size, expected) in [("100rpx", 100.0), ("30px", 60.0), ("30", 60.0)] {
+        let rn = icon(&mut ctx, "success", size);
+        let layout = ctx.taffy.style(rn.taffy_node).unwrap().size.width;
+        assert_eq!(layout, length(expected), "size={}", size);
+    }
+    
+    assert_eq!(icon(&mut ctx, "clear", "20").style.text_color, Some(crate::Color::from_hex(0xB2B2B2)));
+    assert_eq!(icon(&mut ctx, "cancel", "20").style.text_color, Some(crate::Color::from_hex(0xF43530)));
+}
+
 /// 测试 Progress 组件构建
 #[test]
 fn test_progress_component_build() {
@@ -188,6 +234,9 @@ fn test_progress_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = ProgressComponent::build(&node, &mut ctx);
@@ -216,6 +265,9 @@ fn test_switch_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = SwitchComponent::build(&node, &mut ctx);
@@ -244,6 +296,9 @@ fn test_checkbox_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = CheckboxComponent::build(&node, &mut ctx);
@@ -271,6 +326,9 @@ fn test_radio_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = RadioComponent::build(&node, &mut ctx);
@@ -299,6 +357,9 @@ fn test_slider_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = SliderComponent::build(&node, &mut ctx);
@@ -334,6 +395,9 @@ fn test_input_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = InputComponent::build(&node, &mut ctx);
@@ -370,6 +434,9 @@ fn test_image_component_build() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = ImageComponent::build(&node, &mut ctx);
@@ -498,6 +565,9 @@ fn test_fixed_position_style() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let render_node = ViewComponent::build(&node, &mut ctx);
@@ -509,3 +579,592 @@ fn test_fixed_position_style() {
     assert_eq!(rn.style.fixed_left, Some(0.0));
     assert_eq!(rn.style.fixed_right, Some(0.0));
 }
+
+/// 测试网络图片：后台线程下载，完成前返回 None，完成后缓存解码后的 RGBA
+#[test]
+fn test_remote_image_loads_in_background() {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    
+    let mut png = Vec::new();
+    image::RgbaImage::from_pixel(3, 2, image::Rgba([255, 0, 0, 255]))
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .unwrap();
+    
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/avatar.png", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = stream.read(&mut buf);
+        let header = format!("HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", png.len());
+        stream.write_all(header.as_bytes()).unwrap();
+        stream.write_all(&png).unwrap();
+    });
+    
+    assert!(get_image_rgba(&url).is_none());
+    let start = std::time::Instant::now();
+    let (data, w, h) = loop {
+        if let Some(img) = get_image_rgba(&url) {
+            break img;
+        }
+        assert!(start.elapsed().as_secs() < 5, "image download timed out");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    };
+    assert_eq!((w, h), (3, 2));
+    assert_eq!(&data[..4], &[255, 0, 0, 255]);
+    assert!(take_images_loaded());
+}
+
+/// 测试 image mode 的缩放和对齐
+#[test]
+fn test_image_mode_layout() {
+    use crate::canvas::image_mode_layout;
+    
+    // 200x100 的图片放进 100x100 的区域
+    assert_eq!(image_mode_layout("scaleToFill", 200, 100, 100.0, 100.0, 2.0), (0.5, 1.0, 0.0, 0.0));
+    assert_eq!(image_mode_layout("aspectFit", 200, 100, 100.0, 100.0, 2.0), (0.5, 0.5, 0.0, 25.0));
+    // aspectFill 居中裁剪
+    assert_eq!(image_mode_layout("aspectFill", 200, 100, 100.0, 100.0, 2.0), (1.0, 1.0, -50.0, 0.0));
+    
+    // 定位模式不缩放（按像素比），只对齐
+    assert_eq!(image_mode_layout("top left", 200, 100, 100.0, 100.0, 1.0), (1.0, 1.0, 0.0, 0.0));
+    assert_eq!(image_mode_layout("bottom right", 200, 100, 100.0, 100.0, 1.0), (1.0, 1.0, -100.0, 0.0));
+    assert_eq!(image_mode_layout("center", 20, 10, 100.0, 100.0, 2.0), (2.0, 2.0, 30.0, 40.0));
+    assert_eq!(image_mode_layout("left", 20, 10, 100.0, 100.0, 1.0), (1.0, 1.0, 0.0, 45.0));
+    assert_eq!(image_mode_layout("bottom", 20, 10, 100.0, 100.0, 1.0), (1.0, 1.0, 40.0, 90.0));
+}
+
+/// 测试 widthFix：布局高度由图片宽高比推导
+#[test]
+fn test_image_width_fix_height_from_aspect_ratio() {
+    let path = std::env::temp_dir().join(format!("mini_render_width_fix_{}.png", std::process::id()));
+    image::RgbaImage::from_pixel(40, 30, image::Rgba([0, 0, 255, 255])).save(&path).unwrap();
+    
+    let stylesheet = parse_css(".banner { width: 200px; }");
+    let mut taffy = TaffyTree::new();
+    let mut attrs = HashMap::new();
+    attrs.insert("src".to_string(), path.to_string_lossy().to_string());
+    attrs.insert("mode".to_string(), "widthFix".to_string());
+    let node = create_test_node("image", &["banner"], attrs);
+    
+    let mut ctx = ComponentContext {
+        scale_factor: 1.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+    let rn = ImageComponent::build(&node, &mut ctx).unwrap();
+    taffy.compute_layout(rn.taffy_node, Size::MAX_CONTENT).unwrap();
+    let layout = taffy.layout(rn.taffy_node).unwrap();
+    assert_eq!(layout.size.width, 200.0);
+    assert_eq!(layout.size.height, 150.0);
+    
+    std::fs::remove_file(&path).ok();
+}
+
+/// 测试图片缓存按最近使用淘汰，被引用的帧在淘汰后仍然可用
+#[test]
+fn test_image_cache_lru_eviction() {
+    use crate::renderer::components::{image_cache_stats, ImageCache, ImageData, ImageEntry};
+    use std::sync::Arc;
+    
+    let frame = |v: u8| Arc::new(ImageData::new(vec![v; 40], 10, 1));
+    let mut cache = ImageCache::new(100);
+    cache.insert("a.png".into(), ImageEntry::Ready(frame(1)));
+    cache.insert("b.png".into(), ImageEntry::Ready(frame(2)));
+    
+    // 正在绘制的帧持有引用
+    let Some(ImageEntry::Ready(b)) = cache.get("b.png") else { panic!("b.png should be cached") };
+    assert!(matches!(cache.get("a.png"), Some(ImageEntry::Ready(_))));
+    
+    // 超出容量，淘汰最久未使用的 b.png
+    cache.insert("c.png".into(), ImageEntry::Ready(frame(3)));
+    assert!(cache.get("b.png").is_none());
+    assert!(cache.get("a.png").is_some());
+    assert_eq!(b.data[0], 2, "evicted frame stays alive while referenced");
+    
+    let stats = cache.stats();
+    assert_eq!((stats.entries, stats.bytes, stats.budget), (2, 80, 100));
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (3, 1, 1));
+    
+    // 缩小容量立即淘汰，新插入的单张超大图片仍然保留
+    cache.set_budget(30);
+    assert_eq!(cache.stats().entries, 0);
+    cache.insert("big.png".into(), ImageEntry::Ready(frame(4)));
+    assert!(cache.get("big.png").is_some());
+    assert_eq!(cache.stats().bytes, 40);
+    
+    assert!(image_cache_stats().budget > 0);
+}
+
+/// 测试动画 GIF 解码出所有帧并按帧时长循环，以及无损 WebP（VP8L）解码
+#[test]
+fn test_animated_gif_and_lossless_webp() {
+    use crate::renderer::components::decode_image_bytes;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, Rgba, RgbaImage};
+    
+    let mut gif = Vec::new();
+    {
+        let mut encoder = GifEncoder::new(&mut gif);
+        let red = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+        let blue = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 255, 255]));
+        encoder.encode_frame(Frame::from_parts(red, 0, 0, Delay::from_numer_denom_ms(100, 1))).unwrap();
+        encoder.encode_frame(Frame::from_parts(blue, 0, 0, Delay::from_numer_denom_ms(200, 1))).unwrap();
+    }
+    
+    let img = decode_image_bytes(&gif).expect("gif should decode");
+    assert!(img.is_animated());
+    assert_eq!((img.width, img.height), (4, 4));
+    assert_eq!(img.delays_ms, vec![100, 200]);
+    assert_eq!(&img.frame_at(0)[..4], &[255, 0, 0, 255]);
+    assert_eq!(&img.frame_at(150)[..4], &[0, 0, 255, 255]);
+    // 300ms 后循环回第一帧
+    assert_eq!(&img.frame_at(310)[..4], &[255, 0, 0, 255]);
+    
+    // 1x1 无损 WebP
+    let webp: [u8; 34] = [
+        0x52, 0x49, 0x46, 0x46, 0x1a, 0x00, 0x00, 0x00, 0x57, 0x45, 0x42, 0x50, 0x56, 0x50, 0x38, 0x4c,
+        0x0d, 0x00, 0x00, 0x00, 0x2f, 0x00, 0x00, 0x00, 0x10, 0x07, 0x10, 0x11, 0x11, 0x88, 0x88, 0xfe,
+        0x07, 0x00,
+    ];
+    let img = decode_image_bytes(&webp).expect("lossless webp should decode");
+    assert!(!img.is_animated());
+    assert_eq!((img.width, img.height), (1, 1));
+    assert_eq!(img.data.len(), 4);
+}
+
+/// 测试 canvas 上下文的 setLineCap / setLineJoin / setLineDash 命令
+#[test]
+fn test_canvas_context_stroke_style_commands() {
+    let mut manager = CanvasContextManager::new();
+    manager.execute_commands("stroke", r#"[
+        {"type": "setLineWidth", "width": 10},
+        {"type": "setLineCap", "cap": "round"},
+        {"type": "setLineJoin", "join": "bevel"},
+        {"type": "beginPath"},
+        {"type": "moveTo", "x": 20, "y": 20.5},
+        {"type": "lineTo", "x": 60, "y": 20.5},
+        {"type": "stroke"},
+        {"type": "setLineDash", "pattern": [10, 10], "offset": 0},
+        {"type": "setLineCap", "cap": "butt"},
+        {"type": "beginPath"},
+        {"type": "moveTo", "x": 0, "y": 60.5},
+        {"type": "lineTo", "x": 100, "y": 60.5},
+        {"type": "stroke"}
+    ]"#);
+
+    let ctx = manager.get_existing_context("stroke").unwrap();
+    let canvas = ctx.get_canvas();
+    let canvas = canvas.lock().unwrap();
+    let dark = |x: u32, y: u32| canvas.get_pixel(x, y).a > 192;
+    // 圆形线帽延伸到端点之外
+    assert!(dark(17, 20));
+    assert!(!dark(12, 20));
+    // 虚线
+    assert!(dark(5, 60) && !dark(15, 60) && dark(25, 60));
+}
+
+/// 测试 canvas 上下文 fill('evenodd')：两个同向子路径组成的圆环中间挖空
+#[test]
+fn test_canvas_context_fill_rule() {
+    let mut manager = CanvasContextManager::new();
+    let ring = |rule: &str| format!(r#"[
+        {{"type": "clearRect", "x": 0, "y": 0, "width": 100, "height": 100}},
+        {{"type": "beginPath"}},
+        {{"type": "arc", "x": 50, "y": 50, "r": 40, "sAngle": 0, "eAngle": 6.2832}},
+        {{"type": "moveTo", "x": 70, "y": 50}},
+        {{"type": "arc", "x": 50, "y": 50, "r": 20, "sAngle": 0, "eAngle": 6.2832}},
+        {{"type": "fill", "rule": "{}"}}
+    ]"#, rule);
+
+    for (rule, hole) in [("nonzero", false), ("evenodd", true)] {
+        manager.execute_commands("ring", &ring(rule));
+        let ctx = manager.get_existing_context("ring").unwrap();
+        let canvas = ctx.get_canvas();
+        let canvas = canvas.lock().unwrap();
+        assert_eq!(canvas.get_pixel(50, 50).a == 0, hole, "{}", rule);
+        assert_eq!(canvas.get_pixel(15, 50).a, 255);
+    }
+}
+
+/// 测试 canvas 上下文 fillText / strokeText 按对齐方式和基线定位，measureText 随字号变化
+#[test]
+fn test_canvas_context_text_commands() {
+    let mut manager = CanvasContextManager::new();
+    manager.execute_commands("text", r#"[
+        {"type": "setFontSize", "size": 20},
+        {"type": "setTextAlign", "align": "center"},
+        {"type": "setTextBaseline", "baseline": "top"},
+        {"type": "fillText", "text": "HHHH", "x": 100, "y": 10},
+        {"type": "setTextAlign", "align": "right"},
+        {"type": "setTextBaseline", "baseline": "bottom"},
+        {"type": "setLineWidth", "width": 2},
+        {"type": "strokeText", "text": "HHHH", "x": 200, "y": 100}
+    ]"#);
+
+    let ctx = manager.get_existing_context("text").unwrap();
+    let width = ctx.measure_text("HHHH");
+    assert!(width > 0.0);
+    let canvas = ctx.get_canvas();
+    let canvas = canvas.lock().unwrap();
+    let bounds = |y0: u32, y1: u32| {
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (u32::MAX, 0, u32::MAX, 0);
+        for y in y0..y1 {
+            for x in 0..400 {
+                if canvas.get_pixel(x, y).a > 128 {
+                    min_x = min_x.min(x);
+                    max_x = max_x.max(x);
+                    min_y = min_y.min(y);
+                    max_y = max_y.max(y);
+                }
+            }
+        }
+        (min_x, max_x, min_y, max_y)
+    };
+
+    // 居中 + top：文字关于 x=100 对称，整体在 y=10 之下
+    let (min_x, max_x, min_y, _) = bounds(0, 50);
+    assert!(((min_x + max_x) as f32 / 2.0 - 100.0).abs() <= 2.0, "{} {}", min_x, max_x);
+    assert!(min_y >= 10);
+
+    // 右对齐 + bottom：描边右边界在 x=200 附近，整体在 y=100 之上
+    let (_, max_x, _, max_y) = bounds(50, 150);
+    assert!((max_x as i32 - 200).abs() <= 3, "{}", max_x);
+    assert!(max_y < 100);
+
+    drop(canvas);
+    let mut ctx = ctx.clone();
+    ctx.set_font_size(40.0);
+    assert!((ctx.measure_text("HHHH") - width * 2.0).abs() < 1.0);
+}
+
+/// 测试 canvas 上下文的渐变填充：按用户坐标逐像素取色，纯色样式会替换渐变
+#[test]
+fn test_canvas_context_gradient_fill() {
+    let mut manager = CanvasContextManager::new();
+    manager.execute_commands("gradient", r##"[
+        {"type": "translate", "x": 100, "y": 0},
+        {"type": "setFillStyle", "gradient": {"kind": "linear", "x0": 0, "y0": 0, "x1": 100, "y1": 0,
+            "stops": [{"offset": 0, "color": "#ff0000"}, {"offset": 1, "color": "#0000ff"}]}},
+        {"type": "fillRect", "x": 0, "y": 0, "width": 100, "height": 20},
+        {"type": "setFillStyle", "gradient": {"kind": "radial", "x0": 50, "y0": 50, "r0": 0, "x1": 50, "y1": 50, "r1": 20,
+            "stops": [{"offset": 0, "color": "#ffffff"}, {"offset": 1, "color": "#000000"}]}},
+        {"type": "beginPath"},
+        {"type": "arc", "x": 50, "y": 50, "r": 20, "sAngle": 0, "eAngle": 6.2832},
+        {"type": "fill"},
+        {"type": "setFillStyle", "color": "#00ff00"},
+        {"type": "fillRect", "x": 0, "y": 80, "width": 10, "height": 10}
+    ]"##);
+
+    let ctx = manager.get_existing_context("gradient").unwrap();
+    let canvas = ctx.get_canvas();
+    let canvas = canvas.lock().unwrap();
+    let left = canvas.get_pixel(101, 10);
+    let middle = canvas.get_pixel(150, 10);
+    let right = canvas.get_pixel(198, 10);
+    assert!(left.r > 240 && left.b < 15, "{:?}", left);
+    assert!(right.b > 240 && right.r < 15, "{:?}", right);
+    assert!((middle.r as i32 - middle.b as i32).abs() < 10, "{:?}", middle);
+    assert_eq!(canvas.get_pixel(99, 10).a, 0);
+
+    // 径向渐变中心亮、边缘暗
+    let center = canvas.get_pixel(150, 50);
+    let edge = canvas.get_pixel(167, 50);
+    assert!(center.r > 230 && edge.r < 60, "{:?} {:?}", center, edge);
+
+    assert_eq!(canvas.get_pixel(105, 85), crate::Color::new(0, 255, 0, 255));
+}
+
+/// 测试 canvas 上下文的 rotate / scale 与 translate 组合，save / restore 恢复变换
+#[test]
+fn test_canvas_context_rotate_scale() {
+    let mut manager = CanvasContextManager::new();
+    manager.execute_commands("transform", r##"[
+        {"type": "setFillStyle", "color": "#ff0000"},
+        {"type": "save"},
+        {"type": "translate", "x": 100, "y": 100},
+        {"type": "rotate", "angle": 1.5707964},
+        {"type": "scale", "scaleX": 2, "scaleY": 1},
+        {"type": "fillRect", "x": 0, "y": 0, "width": 20, "height": 10},
+        {"type": "translate", "x": 10, "y": 0},
+        {"type": "beginPath"},
+        {"type": "rect", "x": 0, "y": 0, "width": 5, "height": 5},
+        {"type": "fill"},
+        {"type": "restore"},
+        {"type": "fillRect", "x": 0, "y": 0, "width": 10, "height": 10}
+    ]"##);
+
+    let ctx = manager.get_existing_context("transform").unwrap();
+    let canvas = ctx.get_canvas();
+    let canvas = canvas.lock().unwrap();
+    let red = |x: u32, y: u32| canvas.get_pixel(x, y).a > 200;
+    // 旋转 90° 后 x 轴朝下：宽 20 被放大到 40，沿 y 方向延伸；高 10 沿 -x 方向
+    assert!(red(95, 105) && red(95, 138));
+    assert!(!red(105, 105) && !red(95, 95) && !red(95, 142));
+    // 平移在当前坐标系中进行：(10, 0) 经过缩放和旋转落在 (100, 120)
+    assert!(red(97, 122));
+    // restore 后恢复单位变换
+    assert!(red(5, 5) && !red(15, 5));
+}
+
+/// 测试 canvas 上下文 drawImage 的 3 / 5 / 9 参数形式和 globalAlpha
+#[test]
+fn test_canvas_context_draw_image() {
+    let path = std::env::temp_dir().join(format!("mini_render_canvas_img_{}.png", std::process::id()));
+    let mut img = image::RgbaImage::from_pixel(2, 2, image::Rgba([255, 0, 0, 255]));
+    img.put_pixel(1, 0, image::Rgba([0, 0, 255, 255]));
+    img.put_pixel(1, 1, image::Rgba([0, 0, 255, 255]));
+    img.save(&path).unwrap();
+    let src = path.to_string_lossy().replace('\\', "/");
+
+    let mut manager = CanvasContextManager::new();
+    manager.execute_commands("image", &format!(r#"[
+        {{"type": "drawImage", "src": "{src}", "dx": 0, "dy": 0}},
+        {{"type": "drawImage", "src": "{src}", "dx": 10, "dy": 0, "dWidth": 20, "dHeight": 10}},
+        {{"type": "drawImage", "src": "{src}", "sx": 1, "sy": 0, "sWidth": 1, "sHeight": 2, "dx": 40, "dy": 0, "dWidth": 10, "dHeight": 10}},
+        {{"type": "setGlobalAlpha", "alpha": 0.5}},
+        {{"type": "drawImage", "src": "{src}", "dx": 60, "dy": 0, "dWidth": 10, "dHeight": 10}}
+    ]"#));
+
+    let ctx = manager.get_existing_context("image").unwrap();
+    let canvas = ctx.get_canvas();
+    let canvas = canvas.lock().unwrap();
+    let red = crate::Color::new(255, 0, 0, 255);
+    let blue = crate::Color::new(0, 0, 255, 255);
+    assert_eq!(canvas.get_pixel(0, 0), red);
+    assert_eq!(canvas.get_pixel(1, 1), blue);
+    assert_eq!(canvas.get_pixel(2, 0).a, 0);
+    // 放大到 20x10，左半红右半蓝
+    assert_eq!(canvas.get_pixel(12, 5), red);
+    assert_eq!(canvas.get_pixel(27, 5), blue);
+    // 只取源图右半列
+    assert_eq!(canvas.get_pixel(41, 5), blue);
+    assert!((120..=136).contains(&canvas.get_pixel(61, 5).a));
+
+    std::fs::remove_file(&path).ok();
+}
+
+/// 测试尺寸、边框和阴影中的 rpx 按同一比例换算（375 宽屏幕上 200rpx = 100px）
+#[test]
+fn test_rpx_consistent_across_properties() {
+    let css = r#"
+        .card {
+            width: 200rpx;
+            height: 100rpx;
+            border: 4rpx solid #333;
+            border-top: 8rpx solid #333;
+            box-shadow: 10rpx 20rpx 40rpx #000;
+        }
+    "#;
+    let stylesheet = parse_css(css);
+    let mut taffy = TaffyTree::new();
+    let node = create_test_node("view", &["card"], HashMap::new());
+    let mut ctx = ComponentContext {
+        scale_factor: 1.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+
+    let rn = ViewComponent::build(&node, &mut ctx).unwrap();
+    let style = taffy.style(rn.taffy_node).unwrap();
+    assert_eq!(style.size.width, Dimension::Length(100.0));
+    assert_eq!(style.size.height, Dimension::Length(50.0));
+    assert_eq!(rn.style.border_width, 2.0);
+    assert_eq!(rn.style.border_sides[0].as_ref().map(|s| s.width), Some(4.0));
+    let shadow = rn.style.box_shadow.unwrap();
+    assert_eq!((shadow.offset_x, shadow.offset_y, shadow.blur), (5.0, 10.0, 20.0));
+}
+
+/// 测试输入法组合文本插入到光标处，并在下方绘制下划线
+#[test]
+fn test_input_preedit_drawn_with_underline() {
+    let stylesheet = parse_css(".f { width: 200px; height: 42px; }");
+    let mut taffy = TaffyTree::new();
+    let mut ctx = ComponentContext {
+        scale_factor: 1.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+    let mut attrs = HashMap::new();
+    attrs.insert("value".to_string(), "ab".to_string());
+    let node = create_test_node("input", &["f"], attrs);
+    let rn = InputComponent::build(&node, &mut ctx).unwrap();
+    
+    let (composed, range) = compose_preedit(&rn, 1, "你好").unwrap();
+    assert_eq!(composed.text, "a你好b");
+    assert_eq!(range, (1, 3));
+    assert!(compose_preedit(&rn, 1, "").is_none());
+    
+    let tr = crate::text::TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf")).unwrap();
+    let underline_pixels = |preedit: &str| {
+        let mut canvas = crate::Canvas::new(200, 42);
+        InputComponent::draw_with_selection(&rn, &mut canvas, Some(&tr), 0.0, 0.0, 200.0, 42.0, 1.0, false, 2, None, preedit);
+        // 基线 y = (42 + 16) / 2 - 2 = 27，下划线在基线下 2px
+        (12..188).filter(|&x| canvas.get_pixel(x, 29).r < 80).count()
+    };
+    
+    let plain = underline_pixels("");
+    let composing = underline_pixels("你好");
+    assert!(composing >= plain + 20, "plain {} composing {}", plain, composing);
+}
+
+/// 测试渐变文字：background-clip: text 时字形按背景渐变着色，不绘制背景
+#[test]
+fn test_gradient_text_with_background_clip() {
+    let gradient = CssGradient::parse("linear-gradient(to right, #FF0000, rgb(0, 255, 0) 50%, #0000FF)").unwrap();
+    assert_eq!(gradient.angle, Some(90.0));
+    let offsets: Vec<f32> = gradient.stops.iter().map(|s| s.0).collect();
+    assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+    assert_eq!(gradient.sample(50.0, 5.0, 0.0, 0.0, 100.0, 10.0), crate::Color::new(0, 255, 0, 255));
+    
+    let stylesheet = parse_css(r#"
+        .title { font-size: 40px; width: 200px; background: linear-gradient(90deg, #FF0000, #0000FF); -webkit-background-clip: text; color: transparent; }
+    "#);
+    let mut taffy = TaffyTree::new();
+    let mut ctx = ComponentContext {
+        scale_factor: 1.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+    let mut node = create_test_node("text", &["title"], HashMap::new());
+    node.children.push(create_text_node("MMMMM"));
+    let rn = TextComponent::build(&node, &mut ctx).unwrap();
+    assert!(rn.style.background_clip_text);
+    assert!(rn.style.background_gradient.is_some());
+    
+    let tr = crate::text::TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf")).unwrap();
+    let mut canvas = crate::Canvas::new(200, 60);
+    canvas.clear(crate::Color::WHITE);
+    TextComponent::draw(&rn, &mut canvas, Some(&tr), 0.0, 0.0, 200.0, 60.0, 1.0);
+    
+    // 只有字形被着色：左侧偏红，右侧偏蓝，字形之外保持白色
+    let inked = |x0: u32, x1: u32| -> Vec<crate::Color> {
+        (x0..x1).flat_map(|x| (0..60).map(move |y| (x, y)))
+            .map(|(x, y)| canvas.get_pixel(x, y))
+            .filter(|c| c.r < 128 || c.g < 128 || c.b < 128)
+            .collect()
+    };
+    let left = inked(0, 40);
+    let right = inked(130, 200);
+    assert!(!left.is_empty() && !right.is_empty());
+    assert!(left.iter().all(|c| c.r >= c.b), "left should be reddish");
+    assert!(right.iter().all(|c| c.b >= c.r), "right should be bluish");
+    assert!(canvas.get_pixel(199, 59) == crate::Color::WHITE);
+}
+
+/// 测试 line-height：无单位行高按字号计算，单行文字在行框内垂直居中，多行按行高排列
+#[test]
+fn test_line_height_centers_and_spaces_lines() {
+    let stylesheet = parse_css(r#"
+        .single { line-height: 3; font-size: 20px; width: 200px; }
+        .multi { font-size: 20px; line-height: 50px; width: 200px; }
+    "#);
+    let mut taffy = TaffyTree::new();
+    let mut ctx = ComponentContext {
+        scale_factor: 1.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+    let mut single = create_test_node("text", &["single"], HashMap::new());
+    single.children.push(create_text_node("HHH"));
+    let single = TextComponent::build(&single, &mut ctx).unwrap();
+    assert_eq!(single.style.line_height, Some(60.0));
+    let mut multi = create_test_node("text", &["multi"], HashMap::new());
+    multi.children.push(create_text_node("HHH\nHHH"));
+    let multi = TextComponent::build(&multi, &mut ctx).unwrap();
+    assert_eq!(taffy.style(multi.taffy_node).unwrap().size.height, length(100.0));
+    
+    let tr = crate::text::TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf")).unwrap();
+    let ink_rows = |node: &RenderNode, h: u32| -> Vec<u32> {
+        let mut canvas = crate::Canvas::new(200, h);
+        canvas.clear(crate::Color::WHITE);
+        TextComponent::draw(node, &mut canvas, Some(&tr), 0.0, 0.0, 200.0, h as f32, 1.0);
+        (0..h).filter(|&y| (0..200).any(|x| canvas.get_pixel(x, y).r < 128)).collect()
+    };
+    
+    // 大写字母的墨迹中心接近 60px 行框的中线
+    let rows = ink_rows(&single, 60);
+    let center = (rows[0] + rows[rows.len() - 1]) as f32 / 2.0;
+    assert!((center - 30.0).abs() <= 3.0, "ink rows {:?}", rows);
+    
+    // 第二行的墨迹比第一行整体下移一个行高
+    let rows = ink_rows(&multi, 100);
+    let (first, second): (Vec<u32>, Vec<u32>) = rows.iter().partition(|&&y| y < 50);
+    assert!(!first.is_empty() && !second.is_empty());
+    assert_eq!(second[0] - first[0], 50);
+}
+
+/// 测试 text-align: justify：除段落最后一行外撑满宽度，中文在字符间加空隙，英文在空格处加空隙
+#[test]
+fn test_text_align_justify() {
+    let stylesheet = parse_css(r#"
+        .left { font-size: 20px; line-height: 30px; width: 210px; }
+        .justify { font-size: 20px; line-height: 30px; width: 210px; text-align: justify; }
+    "#);
+    let mut taffy = TaffyTree::new();
+    let mut ctx = ComponentContext {
+        scale_factor: 1.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+    let mut build = |class: &str, text: &str| {
+        let mut node = create_test_node("text", &[class], HashMap::new());
+        node.children.push(create_text_node(text));
+        TextComponent::build(&node, &mut ctx).unwrap()
+    };
+    let cjk = "中文两端对齐的排版测试文字内容";
+    let latin = "ab cd ef gh ij kl mn op qr st";
+    let nodes = [build("left", cjk), build("justify", cjk), build("left", latin), build("justify", latin)];
+    
+    let tr = crate::text::TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf")).unwrap();
+    // 每行墨迹的最右列
+    let right_edges = |node: &RenderNode| -> [u32; 2] {
+        let mut canvas = crate::Canvas::new(220, 60);
+        canvas.clear(crate::Color::WHITE);
+        TextComponent::draw(node, &mut canvas, Some(&tr), 0.0, 0.0, 210.0, 60.0, 1.0);
+        let edge = |rows: std::ops::Range<u32>| (0..220u32).rev()
+            .find(|&x| rows.clone().any(|y| canvas.get_pixel(x, y).r < 128))
+            .unwrap();
+        [edge(0..30), edge(30..60)]
+    };
+    
+    for pair in nodes.chunks(2) {
+        let [left_first, left_last] = right_edges(&pair[0]);
+        let [first, last] = right_edges(&pair[1]);
+        assert!(first >= 205 && first < 210, "justified first line ends at {}", first);
+        assert!(left_first < first);
+        // 最后一行保持左对齐
+        assert_eq!(last, left_last);
+    }
+}
@@ -382,6 +382,8 @@ fn test_rpx_to_px_conversion() {
     
     // 320px 屏幕 (iPhone SE)
     assert_eq!(rpx_to_px(750.0, 320.0), 320.0);
+    
+    assert_eq!(rpx_to_px(200.0, 375.0), 100.0);
 }
 
 /// 测试灰色系颜色解析 (#F5F5F5)
@@ -495,3 +497,71 @@ fn test_selector_specificity() {
         assert_eq!(c.b, 0);
     }
 }
+
+/// 测试 calc() 表达式解析和求值
+#[test]
+fn test_calc_parsing() {
+    use crate::renderer::components::to_px;
+    
+    let css = r#"
+        .sidebar {
+            width: calc(100% - 32rpx);
+            height: calc((100vh - 20px) / 2 + calc(10rpx * 2));
+            margin-left: calc(-1 * 8px);
+        }
+    "#;
+    let stylesheet = parse_css(css);
+    let styles = stylesheet.get_styles(&["sidebar"], "view");
+    
+    let eval = |name: &str| match styles.get(name) {
+        Some(v @ StyleValue::Calc(_)) => to_px(v, 375.0, 667.0).unwrap(),
+        other => panic!("{} should be calc, got {:?}", name, other),
+    };
+    // 375 - 32rpx(16px)
+    assert_eq!(eval("width"), 359.0);
+    // (667 - 20) / 2 + 20rpx(10px)
+    assert_eq!(eval("height"), 333.5);
+    assert_eq!(eval("margin-left"), -8.0);
+    
+    // 格式错误时保留原始字符串
+    let styles = parse_css(".bad { width: calc(100% - ); }").get_styles(&["bad"], "view");
+    assert!(matches!(styles.get("width"), Some(StyleValue::String(_))));
+}
+
+/// 测试 !important：低特异性的 important 声明覆盖类选择器的普通声明
+#[test]
+fn test_important_overrides_class_rule() {
+    let css = r#"
+        view { color: #FF0000 !important; width: 10px; }
+        .title { color: #0000FF; width: 20px; height: 30px ! important; }
+        .title { height: 40px; }
+    "#;
+    let stylesheet = parse_css(css);
+    let (styles, important) = stylesheet.get_styles_with_importance(&["title"], "view");
+    
+    match styles.get("color") {
+        Some(StyleValue::Color(c)) => assert_eq!((c.r, c.g, c.b), (255, 0, 0)),
+        other => panic!("color should be the important red, got {:?}", other),
+    }
+    assert!(matches!(styles.get("width"), Some(StyleValue::Length(w, LengthUnit::Px)) if *w == 20.0));
+    assert!(matches!(styles.get("height"), Some(StyleValue::Length(h, LengthUnit::Px)) if *h == 30.0));
+    assert!(important.contains("color") && important.contains("height"));
+    assert!(!important.contains("width"));
+}
+
+/// 测试 @font-face：提取 font-family 与 src 中的第一个 url，不产生样式规则
+#[test]
+fn test_font_face_rule() {
+    let css = r#"
+        @font-face {
+            font-family: "Brand Sans";
+            src: url('/fonts/brand.ttf') format('truetype'), url(brand.woff);
+        }
+        .title { font-family: 'Brand Sans', sans-serif; }
+    "#;
+    let stylesheet = parse_css(css);
+    assert_eq!(stylesheet.font_faces.len(), 1);
+    assert_eq!(stylesheet.font_faces[0].family, "Brand Sans");
+    assert_eq!(stylesheet.font_faces[0].src, "/fonts/brand.ttf");
+    assert_eq!(stylesheet.rules.len(), 1);
+}
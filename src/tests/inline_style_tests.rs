@@ -14,6 +14,9 @@ fn test_inline_style_parsing() {
         screen_height: 667.0,
         stylesheet: &stylesheet,
         taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
     };
     
     let mut node = WxmlNode::new_element("view");
@@ -36,3 +39,60 @@ fn test_inline_style_parsing() {
         panic!("Width should be percent, got {:?}", style.size.width);
     }
 }
+
+#[test]
+fn test_inline_style_respects_important() {
+    let stylesheet = crate::parser::wxss::WxssParser::new(".box { width: 100px !important; height: 50px; }")
+        .parse()
+        .unwrap();
+    let mut taffy = TaffyTree::new();
+    
+    let mut ctx = ComponentContext {
+        scale_factor: 1.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+    
+    let mut node = WxmlNode::new_element("view");
+    node.attributes.insert("class".to_string(), "box".to_string());
+    node.attributes.insert("style".to_string(), "width: 200px; height: 80px;".to_string());
+    let (style, _) = build_base_style(&node, &mut ctx);
+    // 普通内联声明不能覆盖 !important
+    assert_eq!(style.size.width, Dimension::Length(100.0));
+    assert_eq!(style.size.height, Dimension::Length(80.0));
+    
+    // 内联 !important 优先级最高
+    node.attributes.insert("style".to_string(), "width: 200px !important".to_string());
+    let (style, _) = build_base_style(&node, &mut ctx);
+    assert_eq!(style.size.width, Dimension::Length(200.0));
+}
+
+/// 测试数字字重：无单位数值按整百取值，与关键字等价
+#[test]
+fn test_numeric_font_weight() {
+    let stylesheet = StyleSheet::new();
+    let mut taffy = TaffyTree::new();
+    
+    let mut ctx = ComponentContext {
+        scale_factor: 1.0,
+        screen_width: 375.0,
+        screen_height: 667.0,
+        stylesheet: &stylesheet,
+        taffy: &mut taffy,
+        css_vars: Default::default(),
+        pseudo_state: Default::default(),
+        safe_area_insets: Default::default(),
+    };
+    
+    let mut node = WxmlNode::new_element("text");
+    for (css, weight) in [("700", 700), ("bold", 700), ("550", 600), ("300", 300), ("1000", 900)] {
+        node.attributes.insert("style".to_string(), format!("font-weight: {}", css));
+        let (_, node_style) = build_base_style(&node, &mut ctx);
+        assert_eq!(node_style.font_weight.value(), weight, "font-weight: {}", css);
+    }
+}
@@ -8,3 +8,5 @@ pub mod component_tests;
 pub mod renderer_tests;
 pub mod inline_style_tests;
 pub mod ui_overlay_tests;
+pub mod api_tests;
+pub mod snapshot_tests;
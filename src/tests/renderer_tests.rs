@@ -1,11 +1,12 @@
 //! 渲染器单元测试
 //! 测试 WXML 渲染器的各种功能
 
+use crate::renderer::components::ButtonComponent;
 use crate::renderer::wxml_renderer::WxmlRenderer;
 use crate::parser::wxml::WxmlParser;
 use crate::parser::wxss::WxssParser;
-use crate::ui::interaction::InteractionManager;
-use crate::Canvas;
+use crate::ui::interaction::{InteractionManager, InteractionResult, KeyInput};
+use crate::{Canvas, Color};
 use serde_json::json;
 
 /// 辅助函数：解析 CSS
@@ -448,3 +449,1660 @@ fn test_multiple_event_bindings() {
     let bindings = renderer.get_event_bindings();
     assert_eq!(bindings.len(), 3);
 }
+
+/// 测试 text-align: center 使行内文本在 view 中居中
+#[test]
+fn test_text_align_center_inline_children() {
+    let css = r#"
+        .card {
+            width: 200px;
+            text-align: center;
+        }
+    "#;
+    
+    let wxml = r#"
+        <view class="card">
+            <text bindtap="onTap">Hi</text>
+        </view>
+    "#;
+    
+    let mut renderer = create_test_renderer(css);
+    let mut canvas = create_test_canvas();
+    let nodes = parse_wxml(wxml);
+    let data = json!({});
+    
+    renderer.render(&mut canvas, &nodes, &data);
+    
+    let bindings = renderer.get_event_bindings();
+    assert_eq!(bindings.len(), 1);
+    
+    let bounds = &bindings[0].bounds;
+    let center_x = bounds.x + bounds.width / 2.0;
+    assert!(bounds.x > 0.0);
+    assert!((center_x - 100.0).abs() < 1.0, "文本中心应位于 view 中心, 实际: {}", center_x);
+}
+
+/// 测试触摸事件冒泡与 catch 阻止冒泡
+#[test]
+fn test_touch_event_bindings() {
+    let css = r#"
+        .outer { width: 200px; height: 200px; }
+        .inner { width: 100px; height: 100px; }
+    "#;
+    
+    let wxml = r#"
+        <view class="outer" bindtouchstart="onOuterStart" bindtouchmove="onOuterMove">
+            <view class="inner" catchtouchstart="onInnerStart" bindtouchmove="onInnerMove"></view>
+        </view>
+    "#;
+    
+    let mut renderer = create_test_renderer(css);
+    let mut canvas = create_test_canvas();
+    let nodes = parse_wxml(wxml);
+    let data = json!({});
+    
+    renderer.render(&mut canvas, &nodes, &data);
+    
+    // catch 阻止冒泡
+    let start: Vec<&str> = renderer.hit_test_path(50.0, 50.0, "touchstart").iter().map(|b| b.handler.as_str()).collect();
+    assert_eq!(start, vec!["onInnerStart"]);
+    
+    // bind 由内向外冒泡
+    let moves: Vec<&str> = renderer.hit_test_path(50.0, 50.0, "touchmove").iter().map(|b| b.handler.as_str()).collect();
+    assert_eq!(moves, vec!["onInnerMove", "onOuterMove"]);
+    
+    // 内部元素之外只命中外层
+    let outside: Vec<&str> = renderer.hit_test_path(150.0, 150.0, "touchstart").iter().map(|b| b.handler.as_str()).collect();
+    assert_eq!(outside, vec!["onOuterStart"]);
+    
+    // 触摸绑定不参与点击命中测试
+    assert!(renderer.hit_test(50.0, 50.0).is_none());
+}
+
+/// 统计某一行中已绘制（非透明）的像素数
+fn count_painted_in_row(canvas: &Canvas, y: u32) -> u32 {
+    (0..canvas.width()).filter(|&x| canvas.get_pixel(x, y).a > 0).count() as u32
+}
+
+/// 测试覆盖渲染缩放比例（1x 与 3x）
+#[test]
+fn test_render_scale_override() {
+    let css = r#"
+        .box {
+            width: 100px;
+            height: 20px;
+            background-color: #FF0000;
+            border: 1px solid #0000FF;
+        }
+    "#;
+    
+    let wxml = r#"
+        <view class="box"></view>
+    "#;
+    
+    let nodes = parse_wxml(wxml);
+    let data = json!({});
+    
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas_1x = Canvas::new(375, 667);
+    renderer.render(&mut canvas_1x, &nodes, &data);
+    
+    renderer.set_render_scale(3.0);
+    assert_eq!(renderer.render_scale(), 3.0);
+    let mut canvas_3x = Canvas::new(375 * 3, 667 * 3);
+    renderer.render(&mut canvas_3x, &nodes, &data);
+    
+    assert_eq!(canvas_3x.width(), canvas_1x.width() * 3);
+    assert_eq!(canvas_3x.height(), canvas_1x.height() * 3);
+    
+    // 元素的设备像素宽度（含边框）应为 3 倍
+    let width_1x = count_painted_in_row(&canvas_1x, 10);
+    let width_3x = count_painted_in_row(&canvas_3x, 30);
+    assert_eq!(width_1x, 100);
+    assert_eq!(width_3x, width_1x * 3);
+}
+
+/// 测试 MiniApp 渲染缩放比例影响画布尺寸
+#[test]
+fn test_mini_app_render_scale() {
+    let mut app = crate::runtime::MiniApp::new(375, 667).unwrap();
+    assert_eq!(app.physical_size(), (375, 667));
+    
+    app.set_render_scale(3.0);
+    app.render();
+    assert_eq!(app.pixels().width(), 1125);
+    assert_eq!(app.pixels().height(), 2001);
+    
+    // 按物理像素绘制而不是放大逻辑画布：x = 10.4 的边缘落在物理像素 31 上，而不是对齐到 3 像素的块
+    let view = crate::ui::View::new().with_frame(10.4, 10.0, 50.0, 30.0).with_background(Color::from_hex(0xFF0000));
+    app.component_tree().lock().unwrap().set_root(Box::new(view));
+    app.render();
+    let canvas = app.pixels();
+    assert_eq!(canvas.get_pixel(30, 45), Color::WHITE);
+    assert!(canvas.get_pixel(31, 45).g < 255);
+    assert_eq!(canvas.get_pixel(32, 45), Color::from_hex(0xFF0000));
+}
+
+/// 测试 tap 事件冒泡：catchtap 阻止外层 bindtap
+#[test]
+fn test_tap_bubbling_and_catch() {
+    let css = r#"
+        .outer { width: 200px; height: 200px; }
+        .inner { width: 100px; height: 100px; }
+    "#;
+    
+    let wxml = r#"
+        <view class="outer" bindtap="onOuterTap">
+            <view class="inner" bindtap="onInnerTap"></view>
+            <view class="inner" catchtap="onCatchTap"></view>
+        </view>
+    "#;
+    
+    let mut renderer = create_test_renderer(css);
+    let mut canvas = create_test_canvas();
+    let nodes = parse_wxml(wxml);
+    let data = json!({});
+    
+    renderer.render(&mut canvas, &nodes, &data);
+    
+    // bindtap 由内向外冒泡到外层
+    let bubbling: Vec<&str> = renderer.hit_test_path(50.0, 50.0, "tap").iter().map(|b| b.handler.as_str()).collect();
+    assert_eq!(bubbling, vec!["onInnerTap", "onOuterTap"]);
+    
+    // catchtap 阻止外层 bindtap 触发
+    let caught: Vec<&str> = renderer.hit_test_path(50.0, 150.0, "tap").iter().map(|b| b.handler.as_str()).collect();
+    assert_eq!(caught, vec!["onCatchTap"]);
+    
+    // hit_test 返回最内层的绑定
+    assert_eq!(renderer.hit_test(50.0, 150.0).map(|b| b.handler.as_str()), Some("onCatchTap"));
+    assert_eq!(renderer.hit_test(150.0, 150.0).map(|b| b.handler.as_str()), Some("onOuterTap"));
+}
+
+/// 测试 animation 绑定：wx.createAnimation 导出两步动画（先放大再淡出），按时间依次生效
+#[test]
+fn test_animation_data_two_steps() {
+    let mut app = crate::runtime::MiniApp::new(375, 667).unwrap();
+    app.init().unwrap();
+    let exported = app.eval(r#"
+        var animation = wx.createAnimation({ duration: 100, timingFunction: 'linear' });
+        animation.scale(2).step();
+        animation.opacity(0).step();
+        JSON.stringify({ animationData: animation.export() })
+    "#).unwrap();
+    let data: serde_json::Value = serde_json::from_str(&exported).unwrap();
+    
+    let css = r#"
+        .box {
+            width: 100px;
+            height: 20px;
+            margin-left: 100px;
+            margin-top: 100px;
+            background-color: #FF0000;
+        }
+    "#;
+    let nodes = parse_wxml(r#"<view class="box" animation="{{animationData}}"></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    
+    let render_at = |renderer: &mut WxmlRenderer| {
+        let mut canvas = Canvas::new(375, 667);
+        renderer.render(&mut canvas, &nodes, &data);
+        canvas
+    };
+    
+    // 开始时保持原尺寸
+    let canvas = render_at(&mut renderer);
+    assert_eq!(count_painted_in_row(&canvas, 110), 100);
+    assert!(renderer.has_running_animations());
+    
+    // 第一步进行到一半：1.5 倍
+    renderer.advance_animations(50.0);
+    let canvas = render_at(&mut renderer);
+    assert_eq!(count_painted_in_row(&canvas, 110), 150);
+    
+    // 第一步结束：2 倍，且仍不透明
+    renderer.advance_animations(50.0);
+    let canvas = render_at(&mut renderer);
+    assert_eq!(count_painted_in_row(&canvas, 110), 200);
+    assert_eq!(canvas.get_pixel(150, 110).a, 255);
+    
+    // 第二步进行到一半：尺寸保持，透明度减半
+    renderer.advance_animations(50.0);
+    let canvas = render_at(&mut renderer);
+    assert_eq!(count_painted_in_row(&canvas, 110), 200);
+    let alpha = canvas.get_pixel(150, 110).a;
+    assert!(alpha > 100 && alpha < 160, "alpha = {}", alpha);
+    
+    // 全部结束：完全透明，动画停止
+    renderer.advance_animations(60.0);
+    let canvas = render_at(&mut renderer);
+    assert_eq!(count_painted_in_row(&canvas, 110), 0);
+    assert!(!renderer.has_running_animations());
+}
+
+/// 测试 CSS transition：setData 切换 class 后 opacity 随时间过渡，而不是立即变化
+#[test]
+fn test_css_transition_opacity() {
+    let css = r#"
+        .box { width: 100px; height: 20px; background-color: #FF0000; transition: opacity 100ms linear; }
+        .hidden { opacity: 0; }
+    "#;
+    let nodes = parse_wxml(r#"<view class="box {{hidden ? 'hidden' : ''}}"></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    
+    let mut render_with = |renderer: &mut WxmlRenderer, data: &serde_json::Value| {
+        let mut canvas = Canvas::new(375, 667);
+        renderer.render_with_interaction(&mut canvas, &nodes, data, &mut interaction);
+        canvas.get_pixel(50, 10).a
+    };
+    
+    // 首次渲染直接显示样式值
+    assert_eq!(render_with(&mut renderer, &json!({ "hidden": false })), 255);
+    assert!(!renderer.has_running_animations());
+    
+    // 切换 class：过渡开始时仍然不透明
+    let hidden = json!({ "hidden": true });
+    assert_eq!(render_with(&mut renderer, &hidden), 255);
+    assert!(renderer.has_running_animations());
+    
+    renderer.advance_animations(50.0);
+    let alpha = render_with(&mut renderer, &hidden);
+    assert!(alpha > 100 && alpha < 160, "alpha = {}", alpha);
+    
+    renderer.advance_animations(60.0);
+    assert_eq!(render_with(&mut renderer, &hidden), 0);
+    assert!(!renderer.has_running_animations());
+}
+
+/// 测试 @keyframes 动画：按关键帧插值背景色，无限循环
+#[test]
+fn test_css_keyframes_background() {
+    let css = r#"
+        @keyframes pulse {
+            from { background-color: #FF0000; }
+            to { background-color: #0000FF; }
+        }
+        .badge { width: 40px; height: 20px; background-color: #00FF00; animation: pulse 200ms linear infinite; }
+    "#;
+    let stylesheet = parse_css(css);
+    assert_eq!(stylesheet.keyframes["pulse"].len(), 2);
+    
+    let nodes = parse_wxml(r#"<view class="badge"></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(stylesheet, 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let mut render = |renderer: &mut WxmlRenderer| {
+        let mut canvas = Canvas::new(375, 667);
+        renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+        canvas.get_pixel(10, 10)
+    };
+    
+    assert_eq!(render(&mut renderer), Color::from_hex(0xFF0000));
+    assert!(renderer.has_running_animations());
+    
+    renderer.advance_animations(100.0);
+    let mid = render(&mut renderer);
+    assert!(mid.r > 100 && mid.r < 160 && mid.b > 100 && mid.b < 160, "{:?}", mid);
+    assert_eq!(mid.g, 0);
+    
+    // 第二轮从头开始
+    renderer.advance_animations(150.0);
+    let next = render(&mut renderer);
+    assert!(next.r > next.b, "{:?}", next);
+    assert!(renderer.has_running_animations());
+}
+
+/// 测试 border-collapse: collapse：列表项的下边框合并为内部分隔线，末尾不绘制
+#[test]
+fn test_border_collapse_list_separators() {
+    let css = r#"
+        .list { width: 200px; }
+        .collapse { border-collapse: collapse; }
+        .item { height: 30px; border-bottom: 1px solid #000000; }
+    "#;
+    let separator_rows = |class: &str| {
+        let wxml = format!(r#"<view class="{}"><view class="item"></view><view class="item"></view><view class="item"></view></view>"#, class);
+        let nodes = parse_wxml(&wxml);
+        let mut renderer = create_test_renderer(css);
+        let mut canvas = create_test_canvas();
+        renderer.render(&mut canvas, &nodes, &json!({}));
+        (0..240).filter(|&y| canvas.get_pixel(10, y).a > 0).collect::<Vec<u32>>()
+    };
+    
+    // 2x 缩放下 1px 边框占两行物理像素；默认每项都绘制自己的下边框
+    assert_eq!(separator_rows("list"), vec![58, 59, 118, 119, 178, 179]);
+    assert_eq!(separator_rows("list collapse"), vec![58, 59, 118, 119]);
+}
+
+/// 测试 transform 旋转和带子节点的缩放：通过图层重采样绘制
+#[test]
+fn test_transform_rotate_and_scale_subtree() {
+    let css = r#"
+        .bar { width: 100px; height: 20px; margin: 100px; background-color: #FF0000; transform: rotate(90deg); }
+        .card { width: 100px; height: 100px; margin-left: 100px; transform: scale(0.5); }
+        .fill { width: 100px; height: 100px; background-color: #0000FF; }
+    "#;
+    let nodes = parse_wxml(r#"<view class="bar"></view><view class="card"><view class="fill"></view></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render(&mut canvas, &nodes, &json!({}));
+    
+    // 100x20 的条绕中心 (150, 110) 旋转 90 度后变成 20x100 的竖条
+    let painted_in_column = |x: u32, y0: u32, y1: u32| (y0..y1).filter(|&y| canvas.get_pixel(x, y).a > 0).count();
+    assert_eq!(count_painted_in_row(&canvas, 110), 20);
+    assert!(canvas.get_pixel(150, 65).r > 200);
+    assert_eq!(painted_in_column(150, 0, 220), 100);
+    
+    // 缩放容器时子节点一起缩放：100x100 -> 50x50，居中于 (150, 270)
+    assert_eq!(canvas.get_pixel(150, 270), Color::from_hex(0x0000FF));
+    assert_eq!(count_painted_in_row(&canvas, 270), 50);
+    assert_eq!(painted_in_column(150, 220, 320), 50);
+}
+
+/// 测试旋转容器内的 absolute 子元素随容器一起旋转，而不是延后绘制在未变换的位置
+#[test]
+fn test_absolute_child_follows_rotated_parent() {
+    let css = r#"
+        .parent { position: relative; width: 100px; height: 100px; margin: 50px; background-color: #0000FF; transform: rotate(90deg); }
+        .corner { position: absolute; left: 0; top: 0; width: 20px; height: 20px; background-color: #FF0000; }
+    "#;
+    let nodes = parse_wxml(r#"<view class="parent"><view class="corner"></view></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 300);
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut InteractionManager::new());
+    
+    // 绕中心 (100, 100) 顺时针旋转 90 度：左上角的方块转到右上角
+    assert_eq!(canvas.get_pixel(140, 60), Color::from_hex(0xFF0000));
+    assert_eq!(canvas.get_pixel(60, 60), Color::from_hex(0x0000FF));
+}
+
+/// 测试 box-shadow 模糊：阴影从边缘向外平滑衰减，没有台阶
+#[test]
+fn test_box_shadow_blur_is_smooth() {
+    let css = r#"
+        .card { width: 100px; height: 100px; margin: 60px; background-color: #FFFFFF; box-shadow: 0 0 40px #000000; }
+    "#;
+    let nodes = parse_wxml(r#"<view class="card"></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render(&mut canvas, &nodes, &json!({}));
+    
+    // 沿卡片中线向左，从卡片边缘 (60) 往外
+    let alphas: Vec<u8> = (10..60).rev().map(|x| canvas.get_pixel(x, 110).a).collect();
+    assert!(alphas[0] > 100, "edge alpha {}", alphas[0]);
+    assert!(alphas[alphas.len() - 1] < 10, "far alpha {}", alphas[alphas.len() - 1]);
+    for pair in alphas.windows(2) {
+        assert!(pair[1] <= pair[0], "shadow alpha should not increase outward: {:?}", alphas);
+        assert!(pair[0] - pair[1] <= 12, "shadow has a visible step: {:?}", alphas);
+    }
+}
+
+/// 测试内阴影：画在元素内部，从边缘向中心变淡，元素外不受影响
+#[test]
+fn test_inset_box_shadow() {
+    let css = r#"
+        .well { width: 100px; height: 100px; margin: 20px; border-radius: 10px; background-color: #FFFFFF; box-shadow: inset 0 4px 12px #000000; }
+    "#;
+    let nodes = parse_wxml(r#"<view class="well"></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render(&mut canvas, &nodes, &json!({}));
+    
+    // 中心保持背景色
+    assert_eq!(canvas.get_pixel(70, 70), Color::from_hex(0xFFFFFF));
+    // 向下偏移：上边缘内侧比下边缘内侧更暗，越靠近中心越亮
+    let top = canvas.get_pixel(70, 21);
+    let inner = canvas.get_pixel(70, 30);
+    let bottom = canvas.get_pixel(70, 118);
+    assert!(top.r < inner.r, "top {:?} inner {:?}", top, inner);
+    assert!(top.r < bottom.r, "top {:?} bottom {:?}", top, bottom);
+    // 元素外和圆角外不画阴影
+    assert_eq!(canvas.get_pixel(70, 15).a, 0);
+    assert_eq!(canvas.get_pixel(20, 20).a, 0);
+}
+
+/// 测试 overflow: hidden 按圆角裁剪子节点
+#[test]
+fn test_overflow_hidden_clips_children_to_rounded_rect() {
+    let css = r#"
+        .card { width: 100px; height: 100px; border-radius: 20px; }
+        .hidden { overflow: hidden; }
+        .photo { width: 100px; height: 150px; flex-shrink: 0; background-color: #FF0000; }
+    "#;
+    let render = |class: &str| {
+        let nodes = parse_wxml(&format!(r#"<view class="{}"><view class="photo"></view></view>"#, class));
+        let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+        let mut canvas = Canvas::new(375, 667);
+        renderer.render(&mut canvas, &nodes, &json!({}));
+        canvas
+    };
+    
+    // 默认子节点溢出到卡片外，四角也是方的
+    let canvas = render("card");
+    assert!(canvas.get_pixel(1, 1).a > 0);
+    assert!(canvas.get_pixel(50, 120).a > 0);
+    
+    let canvas = render("card hidden");
+    assert_eq!(canvas.get_pixel(50, 50), Color::from_hex(0xFF0000));
+    assert_eq!(canvas.get_pixel(1, 1).a, 0);
+    assert_eq!(canvas.get_pixel(98, 98).a, 0);
+    assert!(canvas.get_pixel(50, 1).a > 0);
+    assert_eq!(canvas.get_pixel(50, 120).a, 0);
+}
+
+/// 测试 scroll-x 横向滚动：子元素横向排列，滚动后向左平移
+#[test]
+fn test_scroll_view_scroll_x() {
+    let css = r#"
+        .rail { width: 200px; height: 50px; }
+        .card { width: 100px; height: 50px; background-color: #0000FF; }
+        .first { background-color: #FF0000; }
+    "#;
+    let wxml = r#"
+        <scroll-view id="rail" class="rail" scroll-x="true">
+            <view class="card first"></view>
+            <view class="card"></view>
+            <view class="card"></view>
+            <view class="card"></view>
+        </scroll-view>
+    "#;
+    
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let nodes = parse_wxml(wxml);
+    let data = json!({});
+    
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    assert_eq!(canvas.get_pixel(50, 25).r, 255);
+    // 超出视口的部分被裁剪
+    assert_eq!(count_painted_in_row(&canvas, 25), 200);
+    
+    let controller = interaction.get_scroll_controller_mut("rail").unwrap();
+    assert!(controller.is_horizontal());
+    assert_eq!(controller.get_max_scroll(), 200.0);
+    controller.handle_scroll(150.0, true);
+    
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    // 第一张卡片已完全滚出视口，x=50 处为第三张卡片
+    let pixel = canvas.get_pixel(50, 25);
+    assert_eq!((pixel.r, pixel.b), (0, 255));
+    assert_eq!(count_painted_in_row(&canvas, 25), 200);
+}
+
+/// 测试 scroll-into-view：记录目标子元素位置，属性不变时不重复请求
+#[test]
+fn test_scroll_into_view() {
+    let css = r#"
+        .list { height: 100px; }
+        .item { height: 60px; }
+    "#;
+    let wxml = r#"
+        <scroll-view id="chat" class="list" scroll-y="true" scroll-into-view="{{target}}" scroll-with-animation="true">
+            <view class="item" wx:for="{{items}}" id="msg{{item}}"></view>
+        </scroll-view>
+    "#;
+    
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let nodes = parse_wxml(wxml);
+    let mut canvas = Canvas::new(375, 667);
+    
+    let data = json!({ "items": [0, 1, 2, 3, 4], "target": "msg3" });
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    let requests = renderer.take_scroll_into_view();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].scroll_view_id, "chat");
+    assert_eq!(requests[0].offset, 180.0);
+    assert!(requests[0].animated);
+    
+    // 目标未变化时不再请求滚动
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    assert!(renderer.take_scroll_into_view().is_empty());
+    
+    // 目标变化后滚动到新位置（被限制在最大滚动距离内）
+    let data = json!({ "items": [0, 1, 2, 3, 4], "target": "msg4" });
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    let requests = renderer.take_scroll_into_view();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].offset, 240.0);
+    let controller = interaction.get_scroll_controller_mut("chat").unwrap();
+    controller.scroll_to(requests[0].offset, false);
+    assert_eq!(controller.get_position(), 200.0);
+}
+
+/// 测试 scroll-with-animation：关闭时 scroll-top 一步跳到目标，开启时分多帧缓动
+#[test]
+fn test_scroll_top_with_animation_toggle() {
+    let css = r#"
+        .list { height: 100px; }
+        .item { height: 60px; }
+    "#;
+    let render = |animated: bool, top: f32| {
+        let wxml = format!(r#"
+            <scroll-view id="list" class="list" scroll-y="true" scroll-top="{{{{top}}}}" scroll-with-animation="{}">
+                <view class="item" wx:for="{{{{items}}}}"></view>
+            </scroll-view>
+        "#, animated);
+        let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+        let mut interaction = InteractionManager::new();
+        let mut canvas = Canvas::new(375, 667);
+        let nodes = parse_wxml(&wxml);
+        renderer.render_with_interaction(&mut canvas, &nodes, &json!({ "items": [0, 1, 2, 3, 4], "top": 0 }), &mut interaction);
+        renderer.apply_scroll_requests(&mut interaction);
+        renderer.render_with_interaction(&mut canvas, &nodes, &json!({ "items": [0, 1, 2, 3, 4], "top": top }), &mut interaction);
+        assert!(renderer.apply_scroll_requests(&mut interaction));
+        interaction
+    };
+    
+    let mut interaction = render(false, 150.0);
+    let controller = interaction.get_scroll_controller_mut("list").unwrap();
+    assert_eq!(controller.get_position(), 150.0);
+    assert!(!controller.update(0.016));
+    
+    let mut interaction = render(true, 150.0);
+    let controller = interaction.get_scroll_controller_mut("list").unwrap();
+    assert_eq!(controller.get_position(), 0.0);
+    let mut ticks = 0;
+    while controller.update(0.016) {
+        let pos = controller.get_position();
+        assert!(pos > 0.0 && pos < 150.0, "pos = {}", pos);
+        ticks += 1;
+    }
+    assert!(ticks > 1);
+    assert_eq!(controller.get_position(), 150.0);
+}
+
+/// 测试 position: sticky：页面和 scroll-view 滚动时标题吸顶，父元素底部离开时随之移出
+#[test]
+fn test_sticky_headers_pin_within_section() {
+    let css = r#"
+        .list { height: 200px; }
+        .section { height: 100px; flex-shrink: 0; }
+        .header { height: 20px; flex-shrink: 0; position: sticky; top: 0; background-color: #FF0000; }
+        .blue { background-color: #0000FF; }
+        .row { height: 80px; flex-shrink: 0; background-color: #FFFFFF; }
+    "#;
+    let sections = r#"
+        <view class="section"><view class="header"></view><view class="row"></view></view>
+        <view class="section"><view class="header blue"></view><view class="row"></view></view>
+        <view class="section"><view class="row"></view></view>
+    "#;
+    let red = Color::new(255, 0, 0, 255);
+    let blue = Color::new(0, 0, 255, 255);
+    
+    // 页面滚动：画布为页面坐标，吸顶位置随滚动偏移变化
+    let nodes = parse_wxml(sections);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let mut canvas = Canvas::new(375, 400);
+    let mut render_at = |scroll: f32, canvas: &mut Canvas| {
+        canvas.clear(Color::WHITE);
+        renderer.render_with_scroll_and_viewport(canvas, &nodes, &json!({}), &mut interaction, scroll, 200.0);
+    };
+    render_at(0.0, &mut canvas);
+    assert_eq!(canvas.get_pixel(5, 5), red);
+    assert_eq!(canvas.get_pixel(5, 50), Color::WHITE);
+    render_at(50.0, &mut canvas);
+    assert_eq!(canvas.get_pixel(5, 55), red);
+    assert_eq!(canvas.get_pixel(5, 5), Color::WHITE);
+    // 第一节只剩 10px：标题贴着第一节底部，被第二节的标题推走
+    render_at(90.0, &mut canvas);
+    assert_eq!(canvas.get_pixel(5, 85), red);
+    assert_eq!(canvas.get_pixel(5, 95), red);
+    assert_eq!(canvas.get_pixel(5, 105), blue);
+    render_at(150.0, &mut canvas);
+    assert_eq!(canvas.get_pixel(5, 155), blue);
+    // 第一节的标题停在第一节底部（已滚出视口）
+    assert_eq!(canvas.get_pixel(5, 95), red);
+    assert_eq!(canvas.get_pixel(5, 75), Color::WHITE);
+    
+    // scroll-view 内：以 scroll-view 可见区域顶部为吸顶基准
+    let nodes = parse_wxml(&format!(r#"<scroll-view id="list" class="list" scroll-y="true">{}</scroll-view>"#, sections));
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let mut canvas = Canvas::new(375, 400);
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    interaction.get_scroll_controller_mut("list").unwrap().scroll_to(30.0, false);
+    canvas.clear(Color::WHITE);
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(canvas.get_pixel(5, 5), red);
+    assert_eq!(canvas.get_pixel(5, 25), Color::WHITE);
+    assert_eq!(canvas.get_pixel(5, 75), blue);
+}
+
+/// 测试 z-index：高层级的兄弟元素绘制在上面并优先命中，层级相同时保持文档顺序
+#[test]
+fn test_z_index_orders_siblings_for_paint_and_hit_test() {
+    let css = r#"
+        .box { position: relative; width: 100px; height: 100px; }
+        .badge { position: absolute; left: 0; top: 0; width: 50px; height: 50px; z-index: 10; background-color: #FF0000; }
+        .cover { position: absolute; left: 0; top: 0; width: 80px; height: 80px; background-color: #0000FF; }
+        .late { position: absolute; left: 60px; top: 60px; width: 30px; height: 30px; background-color: #00FF00; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="box">
+            <view class="badge" bindtap="onBadge"></view>
+            <view class="cover" bindtap="onCover"></view>
+            <view class="late" bindtap="onLate"></view>
+        </view>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 200);
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    
+    assert_eq!(canvas.get_pixel(10, 10), Color::new(255, 0, 0, 255));
+    assert_eq!(canvas.get_pixel(60, 30), Color::new(0, 0, 255, 255));
+    assert_eq!(canvas.get_pixel(70, 70), Color::new(0, 255, 0, 255));
+    
+    assert_eq!(renderer.hit_test(10.0, 10.0).unwrap().handler, "onBadge");
+    assert_eq!(renderer.hit_test(60.0, 30.0).unwrap().handler, "onCover");
+    assert_eq!(renderer.hit_test(70.0, 70.0).unwrap().handler, "onLate");
+}
+
+/// 测试 absolute 定位的下拉菜单：绘制在后面的普通流内容之上、不被祖先裁剪，并优先命中
+#[test]
+fn test_absolute_overlay_paints_above_following_content() {
+    let css = r#"
+        .bar { height: 40px; overflow: hidden; flex-shrink: 0; }
+        .anchor { position: relative; width: 100px; height: 40px; }
+        .menu { position: absolute; left: 0; top: 40px; width: 100px; height: 80px; background-color: #FF0000; }
+        .item { width: 100px; height: 40px; }
+        .content { height: 200px; flex-shrink: 0; background-color: #0000FF; }
+        .below { width: 100px; height: 100px; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="bar">
+            <view class="anchor">
+                <view class="menu" bindtap="onMenu"><button id="item" class="item">A</button></view>
+            </view>
+        </view>
+        <view class="content" bindtap="onContent"><button id="below" class="below">B</button></view>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 300);
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    
+    // 菜单超出 .bar 的部分没有被裁剪，也没有被 .content 覆盖
+    assert_eq!(canvas.get_pixel(95, 110), Color::new(255, 0, 0, 255));
+    assert_eq!(canvas.get_pixel(150, 110), Color::new(0, 0, 255, 255));
+    
+    assert_eq!(renderer.hit_test(95.0, 110.0).unwrap().handler, "onMenu");
+    assert_eq!(renderer.hit_test(150.0, 110.0).unwrap().handler, "onContent");
+    assert_eq!(interaction.hit_test(50.0, 60.0).unwrap().id, "item");
+    assert_eq!(interaction.hit_test(50.0, 130.0).unwrap().id, "below");
+}
+
+/// 测试 visibility: hidden：保留布局占位但不绘制、不响应点击，子元素可用 visible 重新显示
+#[test]
+fn test_visibility_hidden_keeps_layout_space() {
+    let css = r#"
+        .ghost { height: 50px; flex-shrink: 0; visibility: hidden; background-color: #FF0000; }
+        .shown { width: 100px; height: 20px; visibility: visible; background-color: #00FF00; }
+        .next { height: 50px; flex-shrink: 0; background-color: #0000FF; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="ghost" bindtap="onGhost"><view class="shown"></view></view>
+        <view class="next" bindtap="onNext"></view>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 200);
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    
+    // 隐藏的元素仍占 50px，后面的兄弟节点没有上移
+    assert_eq!(canvas.get_pixel(10, 60), Color::new(0, 0, 255, 255));
+    assert_ne!(canvas.get_pixel(200, 30), Color::new(255, 0, 0, 255));
+    assert_eq!(canvas.get_pixel(10, 10), Color::new(0, 255, 0, 255));
+    
+    assert!(renderer.hit_test(200.0, 30.0).is_none());
+    assert_eq!(renderer.hit_test(10.0, 60.0).unwrap().handler, "onNext");
+}
+
+/// 测试 opacity 作用于整棵子树：子节点（包括延后绘制的 absolute 元素）随父节点整体半透明
+#[test]
+fn test_opacity_applies_to_whole_subtree() {
+    let css = r#"
+        .card { position: relative; width: 200px; height: 100px; opacity: 0.5; background-color: #FF0000; }
+        .inner { width: 100px; height: 100px; background-color: #0000FF; }
+        .badge { position: absolute; left: 200px; top: 0; width: 50px; height: 50px; background-color: #00FF00; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="card"><view class="inner"></view><view class="badge"></view></view>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 200);
+    canvas.clear(Color::WHITE);
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    
+    let near = |c: Color, r: u8, g: u8, b: u8| {
+        (c.r as i32 - r as i32).abs() <= 2 && (c.g as i32 - g as i32).abs() <= 2 && (c.b as i32 - b as i32).abs() <= 2
+    };
+    // 子节点盖住父背景的区域只看到半透明的子节点，而不是子节点叠在半透明红色上
+    let inner = canvas.get_pixel(50, 50);
+    assert!(near(inner, 128, 128, 255), "inner = {:?}", inner);
+    let card = canvas.get_pixel(120, 80);
+    assert!(near(card, 255, 128, 128), "card = {:?}", card);
+    let badge = canvas.get_pixel(225, 25);
+    assert!(near(badge, 128, 255, 128), "badge = {:?}", badge);
+}
+
+/// 测试半透明图层只覆盖子树范围时，外阴影和溢出的子节点仍然完整绘制
+#[test]
+fn test_opacity_layer_keeps_shadow_and_overflow() {
+    let css = r#"
+        .card { width: 100px; height: 50px; margin: 60px; opacity: 0.5; background-color: #0000FF; box-shadow: 0 20px 0 #FF0000; }
+        .tail { width: 150px; height: 10px; background-color: #00FF00; }
+    "#;
+    let nodes = parse_wxml(r#"<view class="card"><view class="tail"></view></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 200);
+    canvas.clear(Color::WHITE);
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut InteractionManager::new());
+    
+    let near = |c: Color, r: u8, g: u8, b: u8| {
+        (c.r as i32 - r as i32).abs() <= 2 && (c.g as i32 - g as i32).abs() <= 2 && (c.b as i32 - b as i32).abs() <= 2
+    };
+    let card = canvas.get_pixel(100, 100);
+    assert!(near(card, 128, 128, 255), "card = {:?}", card);
+    // 阴影向下偏移 20px，露出卡片下方 110..130
+    let shadow = canvas.get_pixel(100, 120);
+    assert!(near(shadow, 255, 128, 128), "shadow = {:?}", shadow);
+    // 子节点超出卡片宽度的部分
+    let tail = canvas.get_pixel(200, 65);
+    assert!(near(tail, 128, 255, 128), "tail = {:?}", tail);
+    assert_eq!(canvas.get_pixel(215, 65), Color::WHITE);
+}
+
+/// 测试 movable-view：按 x 属性和拖动平移，并限制在 movable-area 内
+#[test]
+fn test_movable_view_drag() {
+    let css = r#"
+        .area { width: 200px; height: 100px; }
+        .block { width: 50px; height: 50px; background-color: #FF0000; }
+    "#;
+    let wxml = r#"
+        <movable-area class="area">
+            <movable-view id="block" class="block" direction="all" x="20" bindchange="onMove"></movable-view>
+        </movable-area>
+    "#;
+    
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let nodes = parse_wxml(wxml);
+    let data = json!({});
+    
+    // 首帧即按 x 属性定位
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    assert_eq!(canvas.get_pixel(19, 25).a, 0);
+    assert_eq!(canvas.get_pixel(20, 25).r, 255);
+    assert!(renderer.binding_for_element("block", "change").is_some());
+    
+    // 拖动超出范围时被限制在 movable-area 内
+    let element = interaction.hit_test(40.0, 25.0).cloned().unwrap();
+    assert!(interaction.begin_movable_drag(&element.id, 40.0, 25.0, 0));
+    let change = interaction.update_movable_drag(400.0, 45.0, 16);
+    assert!(matches!(change, Some(InteractionResult::MovableChange { x, y, .. }) if x == 150.0 && y == 20.0));
+    interaction.end_movable_drag();
+    
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    assert_eq!(canvas.get_pixel(149, 45).a, 0);
+    assert_eq!(canvas.get_pixel(150, 45).r, 255);
+    assert_eq!(canvas.get_pixel(199, 69).r, 255);
+    assert_eq!(canvas.get_pixel(200, 45).a, 0);
+}
+
+/// 测试 auto-height 的 textarea 随输入的行数增高，后面的元素随之下移
+#[test]
+fn test_textarea_auto_height_grows_with_input() {
+    let css = ".below { height: 20px; background-color: #FF0000; }";
+    let wxml = r#"<textarea id="note" auto-height="true"></textarea><view class="below"></view>"#;
+    
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let nodes = parse_wxml(wxml);
+    let data = json!({});
+    let height = |im: &InteractionManager| im.elements().iter().rev().find(|e| e.id == "note").unwrap().bounds.height;
+    
+    // 空内容时为一行高度：16px * 1.5 + 上下内边距
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    assert_eq!(height(&interaction), 40.0);
+    assert_eq!(canvas.get_pixel(5, 45).g, 0);
+    
+    assert!(matches!(interaction.handle_click(50.0, 10.0), Some(InteractionResult::Focus { .. })));
+    for key in [KeyInput::Char('a'), KeyInput::Enter, KeyInput::Char('b'), KeyInput::Enter, KeyInput::Char('c')] {
+        interaction.handle_key_input(key);
+    }
+    
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    assert_eq!(height(&interaction), 88.0);
+    assert_eq!(canvas.get_pixel(5, 45).g, 255);
+    assert_eq!(canvas.get_pixel(5, 95).g, 0);
+    
+    // 渲染后记录了排版结果，上移回到第二行
+    assert_eq!(interaction.focused_input.as_ref().unwrap().lines.len(), 3);
+    interaction.handle_key_input(KeyInput::Up);
+    assert_eq!(interaction.focused_input.as_ref().unwrap().cursor_pos, 3);
+}
+
+/// 测试 cover-view：绘制在最上层，点击命中 cover-view 而不是切换视频播放
+#[test]
+fn test_cover_view_over_video() {
+    let css = r#"
+        .play { position: absolute; left: 10px; top: 10px; width: 40px; height: 40px; background-color: #FF0000; }
+        .overlap { height: 100px; margin-top: -200px; background-color: #0000FF; }
+    "#;
+    let wxml = r#"
+        <video id="player">
+            <cover-view class="play" bindtap="onPlay"></cover-view>
+        </video>
+        <view class="overlap"></view>
+    "#;
+    
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let nodes = parse_wxml(wxml);
+    let data = json!({});
+    
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    
+    // 后绘制的兄弟节点盖住了视频，但 cover-view 仍在最上层
+    let pixel = canvas.get_pixel(20, 40);
+    assert_eq!((pixel.r, pixel.b), (255, 0));
+    assert_eq!(canvas.get_pixel(100, 40).b, 255);
+    
+    // 点击 cover-view 触发其事件，不切换播放
+    assert!(interaction.handle_click(20.0, 40.0).is_none());
+    assert_eq!(renderer.hit_test(20.0, 40.0).map(|b| b.handler.as_str()), Some("onPlay"));
+    
+    // 点击视频其他区域切换播放
+    let result = interaction.handle_click(200.0, 150.0);
+    assert!(matches!(result, Some(InteractionResult::VideoTap { ref id, .. }) if id == "player"));
+}
+
+/// 测试 RGB565 导出的有序抖动：平滑渐变不再出现大段相同的色带
+#[test]
+fn test_rgb565_dither_reduces_banding() {
+    let mut canvas = Canvas::new(256, 8);
+    for y in 0..8 {
+        for x in 0..256 {
+            let v = x as u8;
+            canvas.set_pixel_direct(x, y, Color::new(v, v, v, 255));
+        }
+    }
+    
+    let changes = |data: &[u16]| data.chunks(256)
+        .map(|row| row.windows(2).filter(|w| w[0] != w[1]).count())
+        .sum::<usize>();
+    
+    let plain = canvas.to_rgb565(false);
+    let dithered = canvas.to_rgb565(true);
+    assert_eq!(plain.len(), 256 * 8);
+    assert_eq!(plain[0], 0);
+    assert_eq!(plain[255], 0xFFFF);
+    assert!(changes(&dithered) > changes(&plain) * 2,
+        "dithered {} vs plain {}", changes(&dithered), changes(&plain));
+    
+    // 结果是确定的
+    assert_eq!(dithered, canvas.to_rgb565(true));
+    
+    // 1 位量化：抖动后的平均亮度仍接近原值
+    let mono = canvas.quantize(1, true);
+    let avg = (0..8).map(|y| mono.get_pixel(128, y).r as u32).sum::<u32>() as f32 / 8.0;
+    let levels: std::collections::HashSet<u8> = mono.pixels().iter().map(|c| c.r).collect();
+    assert_eq!(levels.len(), 2);
+    assert!((avg - 128.0).abs() < 64.0, "avg = {}", avg);
+}
+
+/// 测试 background-image 的尺寸、位置和简写
+#[test]
+fn test_background_image_size_and_position() {
+    let path = std::env::temp_dir().join(format!("mini_render_bg_{}.png", std::process::id()));
+    image::RgbaImage::from_pixel(4, 2, image::Rgba([255, 0, 0, 255])).save(&path).unwrap();
+    let url = path.to_string_lossy();
+    
+    let css = format!(r#"
+        .hero {{
+            width: 100px;
+            height: 50px;
+            flex-shrink: 0;
+            background-color: #FFFFFF;
+            background-image: url("{url}");
+            background-size: 20px 10px;
+            background-position: right bottom;
+        }}
+        .cover {{
+            width: 100px;
+            height: 50px;
+            flex-shrink: 0;
+            background: #FFFFFF url({url}) no-repeat center / cover;
+        }}
+    "#);
+    let nodes = parse_wxml(r#"<view class="hero"></view><view class="cover"></view>"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(&css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 200);
+    renderer.render(&mut canvas, &nodes, &json!({}));
+    
+    let red = Color::new(255, 0, 0, 255);
+    // 20x10 的图块对齐右下角，不平铺
+    assert_eq!(canvas.get_pixel(90, 45), red);
+    assert_eq!(canvas.get_pixel(70, 45), Color::WHITE);
+    assert_eq!(canvas.get_pixel(90, 35), Color::WHITE);
+    assert_eq!(canvas.get_pixel(10, 10), Color::WHITE);
+    
+    // cover 铺满整个元素
+    assert_eq!(canvas.get_pixel(2, 52), red);
+    assert_eq!(canvas.get_pixel(97, 97), red);
+    assert_eq!(canvas.get_pixel(50, 75), red);
+}
+
+/// 测试 CSS 变量：page 上声明、子树覆盖、fallback 和未定义变量
+#[test]
+fn test_css_variables_cascade() {
+    let css = r#"
+        page { --primary: #07C160; }
+        .card { --primary: #FF0000; }
+        .btn {
+            width: 40px;
+            height: 20px;
+            flex-shrink: 0;
+            background-color: var(--primary);
+        }
+        .fallback {
+            width: var(--size, 30px);
+            height: 10px;
+            flex-shrink: 0;
+            background-color: var(--accent, #0000FF);
+            border-color: var(--undefined);
+        }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="btn"></view>
+        <view class="card"><view class="btn"></view></view>
+        <view class="fallback"></view>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 100);
+    renderer.render(&mut canvas, &nodes, &json!({}));
+    
+    assert_eq!(canvas.get_pixel(5, 5), Color::new(0x07, 0xC1, 0x60, 255));
+    // .card 内覆盖了 --primary
+    assert_eq!(canvas.get_pixel(5, 25), Color::new(255, 0, 0, 255));
+    // fallback 值
+    assert_eq!(canvas.get_pixel(25, 45), Color::new(0, 0, 255, 255));
+    assert_ne!(canvas.get_pixel(35, 45), Color::new(0, 0, 255, 255));
+}
+
+/// 测试 env(safe-area-inset-*)：按渲染器的安全区域取值，可与 calc() 组合，未知变量使用 fallback
+#[test]
+fn test_safe_area_env_insets() {
+    let css = r#"
+        .bar-content { height: 20px; }
+        .bar {
+            padding-bottom: env(safe-area-inset-bottom);
+            flex-shrink: 0;
+            background-color: #FF0000;
+        }
+        .nav {
+            height: calc(10px + env(safe-area-inset-top));
+            flex-shrink: 0;
+            background-color: #0000FF;
+        }
+        .unknown { height: env(keyboard-inset-height, 5px); flex-shrink: 0; background-color: #00FF00; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="nav"></view>
+        <view class="bar"><view class="bar-content"></view></view>
+        <view class="unknown"></view>
+    "#);
+    let red = Color::new(255, 0, 0, 255);
+    let blue = Color::new(0, 0, 255, 255);
+    let green = Color::new(0, 255, 0, 255);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 200);
+    
+    // 默认内边距为 0
+    renderer.render(&mut canvas, &nodes, &json!({}));
+    assert_eq!(canvas.get_pixel(5, 5), blue);
+    assert_eq!(canvas.get_pixel(5, 25), red);
+    assert_eq!(canvas.get_pixel(5, 32), green);
+    
+    renderer.set_safe_area_insets([20.0, 0.0, 34.0, 0.0]);
+    canvas.clear(Color::WHITE);
+    renderer.render(&mut canvas, &nodes, &json!({}));
+    assert_eq!(canvas.get_pixel(5, 25), blue);
+    assert_eq!(canvas.get_pixel(5, 35), red);
+    // .bar 内容 20px + 34px 底部内边距
+    assert_eq!(canvas.get_pixel(5, 30 + 53), red);
+    assert_eq!(canvas.get_pixel(5, 30 + 56), green);
+}
+
+/// 测试伪类：:hover 跟随指针位置，按下时 :active，disabled 属性匹配 :disabled
+#[test]
+fn test_pseudo_class_states() {
+    let css = r#"
+        .item { width: 40px; height: 20px; flex-shrink: 0; background-color: #FFFFFF; }
+        .item:hover { background-color: #0000FF; }
+        .item:active { background-color: #FF0000; }
+        .item:disabled { background-color: #999999; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="item"></view>
+        <view class="item" disabled="true"></view>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    assert!(renderer.has_pseudo_class_rules());
+    let mut canvas = Canvas::new(375, 100);
+    let mut interaction = InteractionManager::new();
+    let blue = Color::new(0, 0, 255, 255);
+    let red = Color::new(255, 0, 0, 255);
+    let gray = Color::new(0x99, 0x99, 0x99, 255);
+    
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(canvas.get_pixel(5, 5), Color::WHITE);
+    assert_eq!(canvas.get_pixel(5, 25), gray);
+    
+    interaction.pointer_pos = Some((10.0, 10.0));
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(canvas.get_pixel(5, 5), blue);
+    
+    interaction.pointer_down = true;
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(canvas.get_pixel(5, 5), red);
+    
+    // 指针移开后恢复
+    interaction.pointer_pos = Some((200.0, 80.0));
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(canvas.get_pixel(5, 5), Color::WHITE);
+}
+
+/// 测试 ::before / ::after：空 content 的装饰盒子、文本 content 和 attr()
+#[test]
+fn test_pseudo_elements() {
+    let css = r#"
+        .item { position: relative; width: 100px; height: 40px; flex-shrink: 0; }
+        .item::after {
+            content: '';
+            position: absolute;
+            left: 0;
+            bottom: 0;
+            width: 100%;
+            height: 2px;
+            background-color: #FF0000;
+        }
+        .tag { width: 100px; flex-shrink: 0; }
+        .tag::before {
+            content: attr(data-label);
+            display: block;
+            background-color: #0000FF;
+        }
+        .plain:after { content: none; height: 10px; display: block; background-color: #00FF00; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="item"></view>
+        <view class="tag" data-label="Hot"></view>
+        <view class="tag" data-label=""></view>
+        <view class="plain"></view>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 120);
+    renderer.render(&mut canvas, &nodes, &json!({}));
+    
+    let red = Color::new(255, 0, 0, 255);
+    let blue = Color::new(0, 0, 255, 255);
+    // 底部分隔线
+    assert_eq!(canvas.get_pixel(50, 39), red);
+    assert_ne!(canvas.get_pixel(50, 37), red);
+    assert_ne!(canvas.get_pixel(150, 39), red);
+    
+    // attr() 有值时生成文本块（14px * 1.5 行高），空值时是没有高度的空盒子
+    assert_eq!(canvas.get_pixel(95, 41), blue);
+    assert_eq!(canvas.get_pixel(95, 60), blue);
+    assert_ne!(canvas.get_pixel(95, 62), blue);
+    
+    // content: none 不生成伪元素
+    assert_ne!(canvas.get_pixel(5, 65), Color::new(0, 255, 0, 255));
+}
+
+/// 测试 wxs 模块：内联模块在表达式、条件和列表中调用，沙箱中没有 Date，也不能经由函数的 constructor 拿到 Function
+#[test]
+fn test_wxs_module_calls() {
+    let wxml = r#"
+        <wxs module="fmt">
+            function price(cents) { return '¥' + (cents / 100).toFixed(2); }
+            function isCheap(cents) { return cents < 1000; }
+            function sandboxed() {
+                return typeof Date === 'undefined' && typeof getDate() === 'object'
+                    && (function () {}).constructor === undefined && (async function () {}).constructor === undefined;
+            }
+            module.exports = { price: price, isCheap: isCheap, sandboxed: sandboxed };
+        </wxs>
+        <view wx:for="{{items}}" data-label="{{fmt.price(item.cents)}}" class="{{fmt.isCheap(item.cents) ? 'cheap' : ''}}"></view>
+        <view wx:if="{{fmt.sandboxed()}}" class="sandbox"></view>
+    "#;
+    let nodes = parse_wxml(wxml);
+    let css = ".cheap { width: 10px; height: 10px; flex-shrink: 0; background-color: #FF0000; } .sandbox { width: 10px; height: 10px; flex-shrink: 0; background-color: #0000FF; }";
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 100);
+    renderer.render(&mut canvas, &nodes, &json!({ "items": [{ "cents": 500 }, { "cents": 12345 }] }));
+    
+    // 第一项 500 分是 cheap，第二项没有样式（高度 0），sandbox 紧随其后
+    assert_eq!(canvas.get_pixel(5, 5), Color::new(255, 0, 0, 255));
+    assert_eq!(canvas.get_pixel(5, 15), Color::new(0, 0, 255, 255));
+    
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({ "items": [{ "cents": 12345 }] }), &mut interaction);
+    assert_eq!(canvas.get_pixel(5, 5), Color::new(0, 0, 255, 255));
+}
+
+/// 测试增量重建：只修改一个字段时复用未变化的子树，结果与完整重建一致
+#[test]
+fn test_incremental_rebuild_reuses_unchanged_subtrees() {
+    let css = r#"
+        .title { height: 30px; flex-shrink: 0; background-color: #FF0000; }
+        .item { height: 10px; flex-shrink: 0; margin-bottom: 2px; }
+        .done { background-color: #00FF00; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="page">
+            <view class="title"><text>{{title}}</text></view>
+            <view class="list">
+                <view wx:for="{{items}}" wx:key="id" class="item {{item.done ? 'done' : ''}}">
+                    <text>{{item.name}}</text>
+                </view>
+            </view>
+        </view>
+    "#);
+    let items: Vec<_> = (0..50).map(|i| json!({ "id": i, "name": format!("item {}", i), "done": false })).collect();
+    let data = json!({ "title": "Todo", "items": items });
+    
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    let full = renderer.last_build_stats();
+    assert_eq!(full.reused, 0);
+    assert!(full.built > 100);
+    
+    // 修改标题：列表整体复用
+    let mut data = data;
+    data["title"] = json!("Todo (1)");
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    let stats = renderer.last_build_stats();
+    assert!(stats.built < 10, "{:?}", stats);
+    assert!(stats.reused >= 100, "{:?}", stats);
+    
+    // 修改单个列表项：只重建这一项
+    data["items"][3]["done"] = json!(true);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    let stats = renderer.last_build_stats();
+    assert!(stats.built < 10, "{:?}", stats);
+    assert_eq!(canvas.get_pixel(300, 30 + 3 * 12 + 5), Color::new(0, 255, 0, 255));
+    
+    // 列表头部插入一项：其余项按内容匹配复用
+    data["items"].as_array_mut().unwrap().insert(0, json!({ "id": 100, "name": "new", "done": true }));
+    canvas.clear(Color::WHITE);
+    renderer.render_with_interaction(&mut canvas, &nodes, &data, &mut interaction);
+    let stats = renderer.last_build_stats();
+    assert!(stats.built < 10, "{:?}", stats);
+    
+    // 与完整重建的结果逐像素一致
+    let mut fresh = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut fresh_canvas = Canvas::new(375, 667);
+    fresh_canvas.clear(Color::WHITE);
+    fresh.render_with_interaction(&mut fresh_canvas, &nodes, &data, &mut InteractionManager::new());
+    assert!(canvas.pixels() == fresh_canvas.pixels());
+    assert_eq!(canvas.get_pixel(300, 35), Color::new(0, 255, 0, 255));
+    assert_eq!(canvas.get_pixel(300, 30 + 4 * 12 + 5), Color::new(0, 255, 0, 255));
+}
+
+/// 测试脏区域：布局重建时整帧更新，之后只报告状态变化的交互元素
+#[test]
+fn test_dirty_rects_track_changed_elements() {
+    use crate::ui::interaction::ComponentState;
+    let nodes = parse_wxml(r#"
+        <view style="height: 200px; flex-shrink: 0;"></view>
+        <checkbox value="a" />
+        <checkbox value="b" />
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let mut canvas = Canvas::new(375, 667);
+    
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert!(renderer.dirty_rects().is_none());
+    
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(renderer.dirty_rects(), Some(&[][..]));
+    
+    let second = interaction.elements().iter().find(|e| e.value == "b").unwrap().clone();
+    interaction.states.insert(second.id.clone(), ComponentState { checked: true, value: "b".into() });
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(renderer.dirty_rects(), Some(&[second.bounds][..]));
+    assert!(second.bounds.y >= 200.0);
+    
+    // 数据变化导致重建时整帧更新
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({ "x": 1 }), &mut interaction);
+    assert!(renderer.dirty_rects().is_none());
+}
+
+/// 测试播放中的动画 GIF 每帧都计入脏区域，窗口不会因为没有变化而跳过呈现
+#[test]
+fn test_dirty_rects_include_playing_gif() {
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, Rgba, RgbaImage};
+    
+    let path = std::env::temp_dir().join(format!("mini_render_dirty_{}.gif", std::process::id()));
+    {
+        let mut encoder = GifEncoder::new(std::fs::File::create(&path).unwrap());
+        for color in [[255, 0, 0, 255], [0, 0, 255, 255]] {
+            let frame = RgbaImage::from_pixel(4, 4, Rgba(color));
+            encoder.encode_frame(Frame::from_parts(frame, 0, 0, Delay::from_numer_denom_ms(100, 1))).unwrap();
+        }
+    }
+    let wxml = format!(r#"
+        <view style="height: 100px; flex-shrink: 0;"></view>
+        <image src="{}" style="width: 40px; height: 40px;" />
+    "#, path.to_string_lossy());
+    let nodes = parse_wxml(&wxml);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let mut canvas = Canvas::new(375, 667);
+    
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    let rects = renderer.dirty_rects().expect("unchanged page should report dirty rects");
+    assert_eq!(rects.len(), 1);
+    assert_eq!((rects[0].y, rects[0].width, rects[0].height), (100.0, 40.0, 40.0));
+    std::fs::remove_file(&path).ok();
+}
+
+/// 测试 focus 属性：变为 true 时聚焦输入框，变为 false 时失焦，属性不变时不重复聚焦
+#[test]
+fn test_focus_attribute_focuses_and_blurs_input() {
+    let wxml = r#"<input id="name" /><input id="search" focus="{{focus}}" value="abc" />"#;
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let nodes = parse_wxml(wxml);
+    let render = |renderer: &mut WxmlRenderer, interaction: &mut InteractionManager, focus: bool| {
+        let mut canvas = Canvas::new(375, 667);
+        renderer.render_with_interaction(&mut canvas, &nodes, &json!({ "focus": focus }), interaction);
+        renderer.apply_focus_requests(interaction)
+    };
+    
+    let results = render(&mut renderer, &mut interaction, true);
+    assert!(matches!(results.as_slice(), [InteractionResult::Focus { id, .. }] if id == "search"));
+    let input = interaction.focused_input.as_ref().unwrap();
+    assert_eq!((input.id.as_str(), input.cursor_pos), ("search", 3));
+    
+    // 用户点击其他输入框后，属性没有变化不会抢回焦点
+    interaction.handle_click(50.0, 10.0);
+    assert!(render(&mut renderer, &mut interaction, true).is_empty());
+    assert_eq!(interaction.focused_input.as_ref().unwrap().id, "name");
+    
+    // 属性变为 false 时只让自己失焦
+    assert!(render(&mut renderer, &mut interaction, false).is_empty());
+    assert_eq!(interaction.focused_input.as_ref().unwrap().id, "name");
+    
+    // 再次变为 true：先让当前输入框失焦，再聚焦
+    let results = render(&mut renderer, &mut interaction, true);
+    assert!(matches!(results.as_slice(), [InteractionResult::InputBlur { .. }, InteractionResult::Focus { .. }]));
+    assert!(matches!(render(&mut renderer, &mut interaction, false).as_slice(), [InteractionResult::InputBlur { id, .. }] if id == "search"));
+    assert!(!interaction.has_focused_input());
+}
+
+/// 测试 progress 的 active 动画：进度从 0 匀速增长到 percent，forwards 模式从当前值继续
+#[test]
+fn test_progress_active_animation() {
+    let nodes = parse_wxml(r##"
+        <progress percent="{{percent}}" active="true" active-mode="forwards" duration="10" stroke-width="10" activeColor="#FF0000" />
+    "##);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 40);
+    let mut interaction = InteractionManager::new();
+    let red = Color::new(255, 0, 0, 255);
+    let mut render = |renderer: &mut WxmlRenderer, percent: u32| {
+        canvas.clear(Color::WHITE);
+        renderer.render_with_interaction(&mut canvas, &nodes, &json!({ "percent": percent }), &mut interaction);
+        // 进度条垂直居中在 14px 高的容器内
+        (0..375u32).filter(|&x| canvas.get_pixel(x, 7) == red).count()
+    };
+    
+    // 刚出现时从 0 开始（最短也画出一个圆头）
+    assert!(render(&mut renderer, 50) <= 10);
+    assert!(renderer.has_running_animations());
+    // 每 1% 10ms，250ms 后到 25%
+    renderer.advance_animations(250.0);
+    let quarter = render(&mut renderer, 50);
+    assert!((quarter as i32 - 94).abs() <= 3, "quarter width {}", quarter);
+    assert!(!renderer.advance_animations(250.0));
+    let half = render(&mut renderer, 50);
+    assert!((half as i32 - 187).abs() <= 3, "half width {}", half);
+    
+    // forwards：percent 增加后从 50% 继续增长
+    assert!((render(&mut renderer, 100) as i32 - half as i32).abs() <= 1);
+    renderer.advance_animations(250.0);
+    let three_quarters = render(&mut renderer, 100);
+    assert!((three_quarters as i32 - 281).abs() <= 3, "width {}", three_quarters);
+}
+
+/// 测试环形进度条：从 12 点方向顺时针绘制 percent 对应的圆弧，其余部分为轨道色
+#[test]
+fn test_progress_circle_ring() {
+    let nodes = parse_wxml(r##"
+        <progress type="circle" percent="25" stroke-width="10" activeColor="#FF0000" backgroundColor="#0000FF" />
+    "##);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 100);
+    canvas.clear(Color::WHITE);
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    
+    // 80px 见方，圆心 (40, 40)，环的中线半径 35
+    let (red, blue) = (Color::new(255, 0, 0, 255), Color::new(0, 0, 255, 255));
+    let at = |deg: f32| {
+        let a = (deg - 90.0).to_radians();
+        canvas.get_pixel((40.0 + 35.0 * a.cos()).round() as u32, (40.0 + 35.0 * a.sin()).round() as u32)
+    };
+    assert_eq!(at(45.0), red);
+    assert_eq!(at(135.0), blue);
+    assert_eq!(at(270.0), blue);
+    assert_eq!(canvas.get_pixel(40, 40), Color::WHITE);
+    assert_eq!(canvas.get_pixel(100, 40), Color::WHITE);
+}
+
+/// 测试 switch 的 color 和 type="checkbox"：点击切换后轨道使用 color 属性，而不是复选框的配色
+#[test]
+fn test_switch_color_follows_interaction_state() {
+    let nodes = parse_wxml(r##"
+        <switch color="#FF0000" />
+        <switch type="checkbox" color="#0000FF" checked="true" />
+    "##);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 100);
+    let mut interaction = InteractionManager::new();
+    let mut render = |interaction: &mut InteractionManager| {
+        canvas.clear(Color::WHITE);
+        renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), interaction);
+        // 开关轨道右侧、左侧（滑块在另一侧时露出）和复选框左边缘
+        (canvas.get_pixel(40, 15), canvas.get_pixel(10, 15), canvas.get_pixel(1, 43))
+    };
+    
+    let (track, _, checkbox) = render(&mut interaction);
+    assert_eq!(track, Color::from_hex(0xDFDFDF));
+    assert_eq!(checkbox, Color::new(0, 0, 255, 255));
+    
+    // 打开开关：轨道变为 color；取消复选框：显示白底灰框
+    interaction.handle_click(25.0, 15.0);
+    interaction.handle_click(12.0, 43.0);
+    let (_, track, checkbox) = render(&mut interaction);
+    assert_eq!(track, Color::new(255, 0, 0, 255));
+    assert_eq!(checkbox, Color::from_hex(0xD1D1D1));
+}
+
+/// 测试 checkbox-group / radio-group：单选框只在同一组内互斥，切换后产生组的 change（选中项的 value）
+#[test]
+fn test_checkbox_and_radio_groups() {
+    let nodes = parse_wxml(r#"
+        <radio-group id="size" bindchange="onSize">
+            <radio value="s" />
+            <radio value="m" checked="true" />
+        </radio-group>
+        <radio-group id="color">
+            <radio value="red" checked="true" />
+            <radio value="blue" />
+        </radio-group>
+        <checkbox-group id="tags" bindchange="onTags">
+            <checkbox value="a" />
+            <checkbox value="b" checked="true" />
+            <checkbox value="c" />
+        </checkbox-group>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut interaction = InteractionManager::new();
+    let mut canvas = Canvas::new(375, 667);
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(renderer.binding_for_element("size", "change").unwrap().handler, "onSize");
+    
+    let mut click = |interaction: &mut InteractionManager, value: &str| {
+        let element = interaction.elements().iter().find(|e| e.value == value).unwrap().clone();
+        let (x, y) = (element.bounds.x + element.bounds.width / 2.0, element.bounds.y + element.bounds.height / 2.0);
+        let result = interaction.handle_click(x, y).unwrap();
+        let change = interaction.group_change(&result);
+        renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), interaction);
+        change
+    };
+    let checked = |interaction: &InteractionManager, value: &str| {
+        let element = interaction.elements().iter().rev().find(|e| e.value == value).unwrap();
+        interaction.get_state(&element.id).map(|s| s.checked).unwrap_or(element.checked)
+    };
+    
+    // 选中 s 只取消同组的 m，另一组的 red 保持选中
+    let change = click(&mut interaction, "s");
+    assert!(matches!(change, Some(InteractionResult::RadioGroupChange { ref id, ref value }) if id == "size" && value == "s"));
+    assert!(checked(&interaction, "s") && !checked(&interaction, "m"));
+    assert!(checked(&interaction, "red"));
+    
+    let change = click(&mut interaction, "c");
+    assert!(matches!(change, Some(InteractionResult::CheckboxGroupChange { ref id, ref values }) if id == "tags" && values == &["b", "c"]));
+    let change = click(&mut interaction, "b");
+    assert!(matches!(change, Some(InteractionResult::CheckboxGroupChange { ref values, .. }) if values == &["c"]));
+}
+
+/// 测试 slider 的 step / block-size / show-value：点击按步长取整，轨道为右侧数值留出空间
+#[test]
+fn test_slider_step_and_show_value() {
+    let nodes = parse_wxml(r#"<slider min="0" max="50" step="10" block-size="40" show-value="true" />"#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 100);
+    let mut interaction = InteractionManager::new();
+    let (active, track) = (Color::from_hex(0x1AAD19), Color::from_hex(0xE9E9E9));
+    
+    canvas.clear(Color::WHITE);
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    assert_eq!(canvas.get_pixel(100, 13), track);
+    // block-size 最大 28：轨道从 14 到 375 - 50 - 14 = 311，右侧留给数值
+    assert_eq!(canvas.get_pixel(305, 13), track);
+    assert_eq!(canvas.get_pixel(318, 13), Color::WHITE);
+    
+    // 16.5 按步长 10 取整为 20
+    let x = 14.0 + 297.0 * 0.33;
+    assert!(matches!(interaction.handle_click(x, 14.0), Some(InteractionResult::SliderChange { value, .. }) if value == 20.0));
+    interaction.handle_mouse_release();
+    let id = interaction.hit_test(x, 14.0).unwrap().id.clone();
+    assert_eq!(interaction.get_state(&id).unwrap().value, "20");
+    
+    canvas.clear(Color::WHITE);
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    // 进度 0.4：已选择部分到 14 + 297 * 0.4 ≈ 133
+    assert_eq!(canvas.get_pixel(100, 13), active);
+    assert_eq!(canvas.get_pixel(160, 13), track);
+}
+
+/// 测试 icon 的矢量绘制：search 的镜片透出背景，success 为主色圆底上的白色对勾
+#[test]
+fn test_icon_vector_shapes() {
+    let nodes = parse_wxml(r##"
+        <view style="background-color: #FF0000;">
+            <icon type="search" size="40" />
+            <icon type="success" size="40" />
+        </view>
+    "##);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 2.0);
+    let mut canvas = Canvas::new(375, 200);
+    canvas.clear(Color::WHITE);
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    
+    // 2x 下每个图标 80px 见方，上下排列；放大镜镜片中心在 (40 - 6, 40 - 6)
+    let red = Color::new(255, 0, 0, 255);
+    assert_eq!(canvas.get_pixel(34, 34), red);
+    // 镜片圆环：半径 22，线宽约 7
+    assert_ne!(canvas.get_pixel(34, 12), red);
+    
+    // success：圆底为默认绿色，对勾拐点附近为白色
+    assert_eq!(canvas.get_pixel(40, 80 + 20), Color::from_hex(0x09BB07));
+    assert_eq!(canvas.get_pixel(36, 80 + 52), Color::WHITE);
+    // 圆底外的角落是背景
+    assert_eq!(canvas.get_pixel(2, 80 + 2), red);
+}
+
+/// 测试 button 的 open-type：点击后除 bindtap 外产生 OpenType，bindgetuserinfo 不当作点击处理函数
+#[test]
+fn test_button_open_type_event() {
+    let nodes = parse_wxml(r#"
+        <button open-type="getUserInfo" bindgetuserinfo="onUserInfo" bindtap="onTap">登录</button>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 100);
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    
+    let result = interaction.handle_click(100.0, 30.0).unwrap();
+    let Some(InteractionResult::OpenType { id, open_type }) = interaction.open_type_event(&result) else {
+        panic!("expected open-type event");
+    };
+    assert_eq!(open_type, "getUserInfo");
+    assert_eq!(renderer.hit_test(100.0, 30.0).unwrap().handler, "onTap");
+    
+    let (event_type, detail) = ButtonComponent::open_type_event(&open_type).unwrap();
+    assert_eq!(event_type, "getuserinfo");
+    assert_eq!(detail["errMsg"], "getUserInfo:ok");
+    assert_eq!(renderer.binding_for_element(&id, event_type).unwrap().handler, "onUserInfo");
+}
+
+/// 测试 button 的 loading 图标和禁用配色
+#[test]
+fn test_button_loading_and_disabled() {
+    let nodes = parse_wxml(r#"
+        <button type="primary" loading="true"></button>
+        <button type="primary" disabled="true">提交</button>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(""), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 200);
+    canvas.clear(Color::WHITE);
+    let mut interaction = InteractionManager::new();
+    renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), &mut interaction);
+    
+    // 第一个按钮 y 5..47，加载图标从左内边距开始，圆心 (8 + 9, 26)：12 点方向的辐条最不透明
+    let green = Color::from_hex(0x07C160);
+    assert_ne!(canvas.get_pixel(17, 19), green);
+    assert_eq!(canvas.get_pixel(100, 26), green);
+    
+    // 禁用的 primary 按钮为浅绿色，且点击不产生结果
+    assert_eq!(canvas.get_pixel(10, 70), Color::from_hex(0x9ED99D));
+    assert!(interaction.handle_click(10.0, 70.0).is_none());
+}
+
+/// 测试 hover-class：按下 hover-start-time 后生效，松开后保留 hover-stay-time，
+/// hover-stop-propagation 阻止祖先进入按下状态
+#[test]
+fn test_hover_class_timing() {
+    let css = r#"
+        .row { height: 40px; background-color: #FFFFFF; }
+        .row-hover { background-color: #FF0000; }
+        .inner { width: 20px; height: 20px; }
+        .inner-hover { background-color: #0000FF; }
+    "#;
+    let nodes = parse_wxml(r#"
+        <view class="row" hover-class="row-hover" hover-start-time="20" hover-stay-time="100">
+            <view class="inner" hover-class="inner-hover" hover-stop-propagation="true"></view>
+        </view>
+        <view class="row" hover-class="row-hover"></view>
+    "#);
+    let mut renderer = WxmlRenderer::new_with_scale(parse_css(css), 375.0, 667.0, 1.0);
+    let mut canvas = Canvas::new(375, 100);
+    let mut interaction = InteractionManager::new();
+    let red = Color::new(255, 0, 0, 255);
+    let blue = Color::new(0, 0, 255, 255);
+    let mut render = |renderer: &mut WxmlRenderer, interaction: &mut InteractionManager| {
+        renderer.render_with_interaction(&mut canvas, &nodes, &json!({}), interaction);
+        (canvas.get_pixel(100, 10), canvas.get_pixel(5, 5), canvas.get_pixel(100, 50))
+    };
+    render(&mut renderer, &mut interaction);
+    
+    // 按下后 20ms 才生效
+    interaction.press_hover(100.0, 10.0);
+    assert_eq!(render(&mut renderer, &mut interaction).0, Color::WHITE);
+    assert!(renderer.has_running_animations());
+    renderer.advance_animations(20.0);
+    assert_eq!(render(&mut renderer, &mut interaction).0, red);
+    
+    // 松开后保留 100ms（从松开后的下一次渲染开始计时）
+    interaction.release_hover();
+    assert_eq!(render(&mut renderer, &mut interaction).0, red);
+    renderer.advance_animations(99.0);
+    assert_eq!(render(&mut renderer, &mut interaction).0, red);
+    renderer.advance_animations(1.0);
+    assert_eq!(render(&mut renderer, &mut interaction).0, Color::WHITE);
+    assert!(!renderer.has_running_animations());
+    
+    // 按下子元素：子元素生效，父元素被 hover-stop-propagation 挡住
+    interaction.press_hover(5.0, 5.0);
+    render(&mut renderer, &mut interaction);
+    renderer.advance_animations(50.0);
+    let (row, inner, _) = render(&mut renderer, &mut interaction);
+    assert_eq!((row, inner), (Color::WHITE, blue));
+    
+    // 默认 hover-start-time 为 50ms；按下后滑动取消
+    interaction.press_hover(100.0, 50.0);
+    render(&mut renderer, &mut interaction);
+    renderer.advance_animations(50.0);
+    assert_eq!(render(&mut renderer, &mut interaction).2, red);
+    interaction.cancel_hover();
+    assert_eq!(render(&mut renderer, &mut interaction).2, Color::WHITE);
+}
+
+/// 测试无窗口渲染：按逻辑尺寸和缩放比例输出 RGBA，数据绑定生效
+#[test]
+fn test_render_to_rgba_headless() {
+    let wxml = r#"<view class="box" style="width: {{w}}px;"></view>"#;
+    let wxss = ".box { height: 10px; background-color: #FF0000; }";
+    let rgba = WxmlRenderer::render_to_rgba(wxml, wxss, &json!({ "w": 20 }), 40, 30, 2.0).unwrap();
+    assert_eq!(rgba.len(), 80 * 60 * 4);
+    let pixel = |x: usize, y: usize| &rgba[(y * 80 + x) * 4..(y * 80 + x) * 4 + 4];
+    assert_eq!(pixel(39, 19), &[255, 0, 0, 255]);
+    assert_eq!(pixel(40, 19), &[255, 255, 255, 255]);
+    assert_eq!(pixel(39, 20), &[255, 255, 255, 255]);
+    
+    assert!(WxmlRenderer::render_to_rgba(wxml, wxss, &json!({}), 0, 30, 1.0).is_err());
+    
+    let mut app = crate::runtime::MiniApp::new(375, 667).unwrap();
+    app.set_render_scale(3.0);
+    let rgba = app.render_page_to_rgba(wxml, wxss, &json!({ "w": 20 }), 40, 30).unwrap();
+    assert_eq!(rgba.len(), 120 * 90 * 4);
+}
+
+/// 测试无窗口渲染等待网络图片下载完成后再出图
+#[test]
+fn test_render_to_rgba_waits_for_remote_images() {
+    use std::io::{Read, Write};
+    
+    let mut png = Vec::new();
+    image::RgbaImage::from_pixel(2, 2, image::Rgba([255, 0, 0, 255]))
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .unwrap();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/slow.png", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = stream.read(&mut buf);
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        let header = format!("HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", png.len());
+        stream.write_all(header.as_bytes()).unwrap();
+        stream.write_all(&png).unwrap();
+    });
+    
+    let wxml = r#"<image src="{{url}}" style="width: 10px; height: 10px;"></image>"#;
+    let rgba = WxmlRenderer::render_to_rgba(wxml, "", &json!({ "url": url }), 20, 20, 1.0).unwrap();
+    assert_eq!(&rgba[(5 * 20 + 5) * 4..(5 * 20 + 5) * 4 + 4], &[255, 0, 0, 255]);
+}
@@ -0,0 +1,141 @@
+//! 渲染快照测试
+//! 把 WXML + WXSS + data 渲染到画布，与 tests/snapshots/ 下保存的 PNG 逐像素比较（允许少量抗锯齿误差）。
+//! 设置 UPDATE_SNAPSHOTS=1 时写入新快照，快照缺失视为失败；不一致时在旁边写出 <name>.actual.png 便于对比。
+//! 文字依赖系统字体，不同机器上的输出不同，快照中尽量不放文字。
+
+use crate::renderer::wxml_renderer::WxmlRenderer;
+use image::RgbaImage;
+use serde_json::{json, Value as JsonValue};
+use std::path::PathBuf;
+
+/// 单个颜色通道允许的误差
+const CHANNEL_TOLERANCE: u8 = 2;
+/// 允许超出误差的像素比例
+const MAX_DIFF_RATIO: f64 = 0.001;
+
+fn snapshot_path(name: &str, suffix: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}{}.png", name, suffix))
+}
+
+/// 按逻辑尺寸（1x）渲染一帧到白底画布
+pub fn render_snapshot(wxml: &str, wxss: &str, data: &JsonValue, width: u32, height: u32) -> RgbaImage {
+    let rgba = WxmlRenderer::render_to_rgba(wxml, wxss, data, width, height, 1.0)
+        .expect("failed to render snapshot");
+    RgbaImage::from_raw(width, height, rgba).expect("render output size mismatch")
+}
+
+/// RGBA 数据中有通道误差超过容差的像素个数
+fn diff_pixels(expected: &[u8], actual: &[u8]) -> usize {
+    expected.chunks(4).zip(actual.chunks(4))
+        .filter(|(e, a)| e.iter().zip(a.iter()).any(|(x, y)| x.abs_diff(*y) > CHANNEL_TOLERANCE))
+        .count()
+}
+
+/// 与 tests/snapshots/<name>.png 比较，不一致时 panic
+pub fn assert_render_matches(name: &str, actual: &RgbaImage) {
+    let path = snapshot_path(name, "");
+    let actual_path = snapshot_path(name, ".actual");
+    let update = std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1");
+    if update {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        actual.save(&path).unwrap();
+        std::fs::remove_file(&actual_path).ok();
+        println!("📸 Snapshot written: {}", path.display());
+        return;
+    }
+    
+    let fail = |reason: String| -> ! {
+        actual.save(&actual_path).ok();
+        panic!("snapshot '{}' mismatch: {} (actual output: {})", name, reason, actual_path.display());
+    };
+    if !path.exists() {
+        actual.save(&actual_path).ok();
+        panic!("snapshot missing: {} (run with UPDATE_SNAPSHOTS=1 to create it)", path.display());
+    }
+    let expected = match image::open(&path) {
+        Ok(img) => img.to_rgba8(),
+        Err(e) => fail(format!("failed to read {}: {}", path.display(), e)),
+    };
+    if expected.dimensions() != actual.dimensions() {
+        fail(format!("size {:?} != expected {:?}", actual.dimensions(), expected.dimensions()));
+    }
+    let diff = diff_pixels(expected.as_raw(), actual.as_raw());
+    let total = (actual.width() * actual.height()) as usize;
+    if diff as f64 > total as f64 * MAX_DIFF_RATIO {
+        fail(format!("{} of {} pixels differ", diff, total));
+    }
+    std::fs::remove_file(&actual_path).ok();
+}
+
+/// 测试像素比较的容差
+#[test]
+fn test_diff_pixels_tolerance() {
+    let expected = [10, 20, 30, 255, 0, 0, 0, 255];
+    assert_eq!(diff_pixels(&expected, &[12, 18, 30, 255, 0, 0, 0, 255]), 0);
+    assert_eq!(diff_pixels(&expected, &[13, 20, 30, 255, 0, 0, 0, 255]), 1);
+    assert_eq!(diff_pixels(&expected, &[10, 20, 30, 255, 0, 0, 0, 0]), 1);
+}
+
+/// 快照：flex 布局、边框、圆角和阴影
+#[test]
+fn snapshot_layout_boxes() {
+    let wxss = r#"
+        .page { padding: 10px; }
+        .row { display: flex; flex-direction: row; justify-content: space-between; margin-bottom: 10px; }
+        .box { width: 50px; height: 40px; background-color: #4A90D9; }
+        .round { border-radius: 20px; background-color: #E74C3C; }
+        .border { border: 3px solid #2ECC71; background-color: #FFFFFF; border-radius: 6px; }
+        .shadow { box-shadow: 2px 4px 6px rgba(0, 0, 0, 0.4); }
+        .bar { flex: 1; height: 30px; background-color: #FF9500; }
+        .grow { flex: 1; height: 20px; margin-right: 10px; background-color: #F1C40F; }
+        .grow:last-child { margin-right: 0; }
+    "#;
+    let wxml = r#"
+        <view class="page">
+            <view class="row">
+                <view class="box"></view>
+                <view class="box round"></view>
+                <view class="box border"></view>
+                <view class="box shadow"></view>
+            </view>
+            <view class="row"><view class="bar"></view></view>
+            <view class="row">
+                <view wx:for="{{items}}" class="grow" style="flex: {{item}};"></view>
+            </view>
+        </view>
+    "#;
+    let image = render_snapshot(wxml, wxss, &json!({ "items": [1, 2, 1] }), 240, 160);
+    assert_render_matches("layout_boxes", &image);
+}
+
+/// 快照：表单控件和图标的绘制
+#[test]
+fn snapshot_form_controls() {
+    let wxss = r#"
+        .page { padding: 10px; }
+        .row { display: flex; flex-direction: row; align-items: center; margin-bottom: 10px; }
+    "#;
+    let wxml = r#"
+        <view class="page">
+            <view class="row">
+                <switch checked="true" />
+                <switch />
+                <checkbox checked="true" />
+                <radio checked="true" />
+            </view>
+            <slider value="40" />
+            <progress percent="60" stroke-width="4" />
+            <view class="row">
+                <progress type="circle" percent="75" />
+                <icon type="success" size="30" />
+                <icon type="warn" size="30" />
+                <icon type="search" size="30" />
+                <icon type="clear" size="30" />
+            </view>
+        </view>
+    "#;
+    let image = render_snapshot(wxml, wxss, &json!({}), 260, 220);
+    assert_render_matches("form_controls", &image);
+}
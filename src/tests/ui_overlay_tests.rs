@@ -1,8 +1,8 @@
 //! UI 覆盖层测试（Toast/Loading/Modal）
 //! 测试文字渲染、图标绘制、布局计算等功能
 
-use crate::{Canvas, Color, Paint};
-use crate::text::TextRenderer;
+use crate::{Canvas, Color, Paint, Path};
+use crate::text::{FontSpec, TextRenderer};
 
 /// 测试 TextRenderer 的 baseline 定位
 #[test]
@@ -83,6 +83,72 @@ fn test_text_on_transparent_background() {
     assert!(has_white, "White text should be rendered on transparent background");
 }
 
+/// 测试字形缓存：重复绘制命中缓存，超出容量时按 LRU 淘汰
+#[test]
+fn test_glyph_cache_reuse_and_eviction() {
+    let tr = TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf"))
+        .expect("Failed to load font");
+    let mut canvas = Canvas::new(200, 30);
+    let paint = Paint::new().with_color(Color::BLACK);
+
+    tr.draw_text(&mut canvas, "abcabc", 0.0, 20.0, 16.0, &paint);
+    let stats = tr.glyph_cache_stats();
+    assert_eq!((stats.misses, stats.hits, stats.len), (3, 3, 3));
+
+    // 缓存的位图与重新光栅化的结果一致
+    let first = canvas.pixels().to_vec();
+    canvas.clear(Color::TRANSPARENT);
+    tr.draw_text(&mut canvas, "abcabc", 0.0, 20.0, 16.0, &paint);
+    assert_eq!(canvas.pixels(), &first[..]);
+
+    // 不同字号是不同的字形
+    tr.draw_text(&mut canvas, "a", 0.0, 20.0, 18.0, &paint);
+    assert_eq!(tr.glyph_cache_stats().len, 4);
+
+    // 容量为 4 时，超出后淘汰最久未用的 'b'、'c'，刚用过的 'a' 保留
+    tr.set_glyph_cache_capacity(4);
+    tr.draw_text(&mut canvas, "a", 0.0, 20.0, 16.0, &paint);
+    tr.draw_text(&mut canvas, "x", 0.0, 20.0, 16.0, &paint);
+    let stats = tr.glyph_cache_stats();
+    assert_eq!((stats.len, stats.evictions), (3, 2));
+    let misses = stats.misses;
+    tr.draw_text(&mut canvas, "a", 0.0, 20.0, 16.0, &paint);
+    assert_eq!(tr.glyph_cache_stats().misses, misses);
+
+    // 测量走字宽缓存，结果与逐字相加一致
+    let width = tr.measure_text("abc", 16.0);
+    let sum: f32 = "abc".chars().map(|c| tr.measure_char(c, 16.0)).sum();
+    assert_eq!(width, sum);
+}
+
+/// 基准：500 字段落重绘 100 次，对比不缓存与缓存的耗时
+/// 运行：cargo test --release bench_paragraph_redraw -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_paragraph_redraw() {
+    let tr = TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf"))
+        .expect("Failed to load font");
+    let paragraph: String = "小程序渲染引擎 Mini Skyline renders text, 滚动长文本页面。"
+        .chars().cycle().take(500).collect();
+    let mut canvas = Canvas::new(750, 1334);
+    let paint = Paint::new().with_color(Color::BLACK);
+
+    let mut run = |label: &str| {
+        let start = std::time::Instant::now();
+        for _ in 0..100 {
+            tr.draw_text_wrapped(&mut canvas, &paragraph, 10.0, 30.0, 28.0, 730.0, &paint);
+        }
+        let elapsed = start.elapsed();
+        println!("📊 {}: {:?} ({:?}/frame)", label, elapsed, elapsed / 100);
+    };
+
+    tr.set_glyph_cache_capacity(0);
+    run("uncached");
+    tr.set_glyph_cache_capacity(4096);
+    run("cached");
+    assert_eq!(tr.glyph_cache_stats().misses, 100 * 500 + tr.glyph_cache_stats().len as u64);
+}
+
 /// 测试 Canvas 像素操作
 #[test]
 fn test_canvas_pixel_operations() {
@@ -104,6 +170,278 @@ fn test_canvas_pixel_operations() {
     assert_eq!(pixels[idx].b, 0);
 }
 
+/// 测试 Canvas 行填充：裁剪到画布和裁剪区域，并正确混合
+#[test]
+fn test_canvas_fill_row_clamp_and_blend() {
+    let mut canvas = Canvas::new(10, 10);
+    canvas.clear(Color::WHITE);
+
+    // 超出画布的区间被截断，越界行被忽略
+    canvas.fill_row(0, -5, 20, Color::RED);
+    canvas.fill_row(-1, 0, 10, Color::RED);
+    canvas.fill_row(10, 0, 10, Color::RED);
+    assert!((0..10).all(|x| canvas.get_pixel(x, 0) == Color::RED));
+    assert_eq!(canvas.get_pixel(0, 9), Color::WHITE);
+
+    // 裁剪区域内才会绘制
+    canvas.save();
+    canvas.clip_rect(crate::Rect::new(2.0, 2.0, 4.0, 4.0));
+    canvas.fill_row(3, 0, 10, Color::BLUE);
+    canvas.fill_row(7, 0, 10, Color::BLUE);
+    canvas.restore();
+    assert_eq!(canvas.get_pixel(1, 3), Color::WHITE);
+    assert_eq!(canvas.get_pixel(2, 3), Color::BLUE);
+    assert_eq!(canvas.get_pixel(5, 3), Color::BLUE);
+    assert_eq!(canvas.get_pixel(6, 3), Color::WHITE);
+    assert!((0..10).all(|x| canvas.get_pixel(x, 7) == Color::WHITE));
+
+    // 半透明颜色与背景混合，结果与 set_pixel 一致
+    let half_red = Color::new(255, 0, 0, 128);
+    canvas.fill_row(5, 0, 3, half_red);
+    canvas.set_pixel(4, 5, half_red);
+    assert_eq!(canvas.get_pixel(0, 5), half_red.blend(&Color::WHITE));
+    assert_eq!(canvas.get_pixel(0, 5), canvas.get_pixel(4, 5));
+    assert_eq!(canvas.get_pixel(3, 5), Color::WHITE);
+
+    // blend_row 丢弃越界部分并跳过透明像素
+    let row = [Color::GREEN, Color::TRANSPARENT, Color::GREEN, Color::GREEN];
+    canvas.blend_row(8, -1, &row);
+    assert_eq!(canvas.get_pixel(0, 8), Color::WHITE);
+    assert_eq!(canvas.get_pixel(1, 8), Color::GREEN);
+    assert_eq!(canvas.get_pixel(2, 8), Color::GREEN);
+    canvas.blend_row(8, 8, &row);
+    assert_eq!(canvas.get_pixel(8, 8), Color::GREEN);
+    assert_eq!(canvas.get_pixel(9, 8), Color::WHITE);
+}
+
+/// 测试 blend_span 与逐像素 set_pixel 的结果完全一致（不透明和半透明背景、各种长度）
+#[test]
+fn test_canvas_blend_span_matches_set_pixel() {
+    let backgrounds: Vec<Color> = (0..37u32)
+        .map(|i| {
+            let a = if i % 5 == 4 { (i * 40 % 256) as u8 } else { 255 };
+            Color::new((i * 67 % 256) as u8, (i * 131 % 256) as u8, (i * 29 % 256) as u8, a)
+        })
+        .collect();
+    let colors = [Color::new(0, 0, 0, 128), Color::new(255, 77, 79, 1), Color::new(12, 200, 90, 254), Color::new(30, 60, 90, 255)];
+
+    for color in colors {
+        for len in [1, 3, 4, 7, 8, 33, 37] {
+            let mut expected = Canvas::new(37, 1);
+            let mut actual = Canvas::new(37, 1);
+            expected.pixels_mut().copy_from_slice(&backgrounds);
+            actual.pixels_mut().copy_from_slice(&backgrounds);
+            for x in 0..len {
+                expected.set_pixel(x, 0, color);
+            }
+            actual.blend_span(0, 0, len, color);
+            assert_eq!(actual.pixels(), expected.pixels(), "color {:?}, len {}", color, len);
+        }
+    }
+}
+
+/// 测试路径填充的抗锯齿：圆的边缘覆盖率与解析结果一致，关闭抗锯齿时只有实心像素
+#[test]
+fn test_path_fill_edge_coverage() {
+    let (cx, cy, r) = (30.3, 30.7, 20.0);
+    // 提高展平精度，只比较光栅化本身的误差
+    let mut path = crate::Path::new();
+    path.set_tolerance(0.01).add_circle(cx, cy, r);
+
+    // 参考覆盖率：每个像素 32x32 超采样
+    let expected = |x: u32, y: u32| {
+        let n = 32;
+        let inside = (0..n * n)
+            .filter(|i| {
+                let dx = x as f32 + ((i % n) as f32 + 0.5) / n as f32 - cx;
+                let dy = y as f32 + ((i / n) as f32 + 0.5) / n as f32 - cy;
+                dx * dx + dy * dy <= r * r
+            })
+            .count();
+        inside as f32 / (n * n) as f32
+    };
+
+    let mut canvas = Canvas::new(60, 60);
+    canvas.clear(Color::WHITE);
+    canvas.draw_path(&path, &Paint::new().with_color(Color::BLACK));
+    let mut partial = 0;
+    for y in 0..60 {
+        for x in 0..60 {
+            let coverage = 1.0 - canvas.get_pixel(x, y).r as f32 / 255.0;
+            let reference = expected(x, y);
+            assert!((coverage - reference).abs() < 0.08, "pixel ({}, {}): {} vs {}", x, y, coverage, reference);
+            if coverage > 0.05 && coverage < 0.95 {
+                partial += 1;
+            }
+        }
+    }
+    // 周长约 126 像素，边缘应有一圈过渡像素
+    assert!(partial > 100, "only {} edge pixels are anti-aliased", partial);
+
+    canvas.clear(Color::WHITE);
+    canvas.draw_path(&path, &Paint::new().with_color(Color::BLACK).with_anti_alias(false));
+    assert!(canvas.pixels().iter().all(|p| *p == Color::WHITE || *p == Color::BLACK));
+    assert_eq!(canvas.get_pixel(30, 30), Color::BLACK);
+    assert_eq!(canvas.get_pixel(9, 30), Color::WHITE);
+}
+
+/// 测试描边的线帽、连接和虚线
+#[test]
+fn test_stroke_caps_joins_and_dashes() {
+    use crate::{StrokeCap, StrokeJoin};
+    let stroke = Paint::new().with_color(Color::BLACK).with_style(crate::PaintStyle::Stroke).with_stroke_width(10.0);
+    let mut canvas = Canvas::new(120, 120);
+    let is_dark = |c: &Canvas, x: u32, y: u32| c.get_pixel(x, y).r < 64;
+
+    // 线帽：butt 不延伸，square 延伸半个线宽，round 是半圆
+    for (cap, left_end, corner) in [(StrokeCap::Butt, false, false), (StrokeCap::Square, true, true), (StrokeCap::Round, true, false)] {
+        canvas.clear(Color::WHITE);
+        canvas.draw_line(20.0, 20.5, 60.0, 20.5, &stroke.clone().with_stroke_cap(cap));
+        assert!(is_dark(&canvas, 30, 20) && is_dark(&canvas, 30, 16) && !is_dark(&canvas, 30, 26));
+        assert_eq!(is_dark(&canvas, 17, 20), left_end, "{:?}", cap);
+        assert_eq!(is_dark(&canvas, 16, 16), corner, "{:?}", cap);
+    }
+
+    // 连接：miter 补齐外角，bevel 切掉外角，斜接过长时退化为 bevel
+    let mut corner = Path::new();
+    corner.move_to(20.0, 60.0).line_to(60.0, 60.0).line_to(60.0, 100.0);
+    for (join, limit, filled) in [(StrokeJoin::Miter, 10.0, true), (StrokeJoin::Bevel, 10.0, false), (StrokeJoin::Miter, 1.2, false), (StrokeJoin::Round, 10.0, false)] {
+        canvas.clear(Color::WHITE);
+        canvas.draw_path(&corner, &stroke.clone().with_stroke_join(join).with_stroke_miter(limit));
+        assert!(is_dark(&canvas, 61, 59));
+        assert_eq!(is_dark(&canvas, 64, 55), filled, "{:?} limit {}", join, limit);
+    }
+
+    // 半透明描边的重叠部分只混合一次
+    canvas.clear(Color::WHITE);
+    let translucent = stroke.clone().with_color(Color::new(0, 0, 0, 128)).with_stroke_join(StrokeJoin::Round);
+    canvas.draw_path(&corner, &translucent);
+    assert_eq!(canvas.get_pixel(60, 60), canvas.get_pixel(40, 60));
+
+    // 虚线：10 实 10 空，偏移 5 后整体左移
+    canvas.clear(Color::WHITE);
+    canvas.draw_line(0.0, 100.5, 100.0, 100.5, &stroke.clone().with_stroke_width(4.0).with_dash(&[10.0, 10.0], 0.0));
+    assert!(is_dark(&canvas, 5, 100) && !is_dark(&canvas, 15, 100) && is_dark(&canvas, 25, 100));
+    canvas.clear(Color::WHITE);
+    canvas.draw_line(0.0, 100.5, 100.0, 100.5, &stroke.clone().with_stroke_width(4.0).with_dash(&[10.0, 10.0], 5.0));
+    assert!(is_dark(&canvas, 2, 100) && !is_dark(&canvas, 7, 100) && is_dark(&canvas, 17, 100));
+
+    // 细线虚线
+    canvas.clear(Color::WHITE);
+    canvas.draw_line(0.0, 110.0, 100.0, 110.0, &Paint::new().with_color(Color::BLACK).with_dash(&[4.0, 4.0], 0.0));
+    assert!(is_dark(&canvas, 1, 110) && !is_dark(&canvas, 6, 110));
+}
+
+/// 测试填充规则：同向的两个圆按非零规则是实心，按奇偶规则中间挖空；五角星同理
+#[test]
+fn test_path_fill_rules() {
+    use crate::FillRule;
+    let paint = Paint::new().with_color(Color::BLACK);
+    let mut canvas = Canvas::new(100, 100);
+
+    let mut ring = Path::new();
+    ring.add_circle(30.0, 30.0, 20.0).add_circle(30.0, 30.0, 10.0);
+    let mut star = Path::new();
+    for i in 0..5 {
+        let angle = -std::f32::consts::FRAC_PI_2 + i as f32 * std::f32::consts::TAU * 2.0 / 5.0;
+        let (x, y) = (70.0 + 25.0 * angle.cos(), 70.0 + 25.0 * angle.sin());
+        if i == 0 { star.move_to(x, y); } else { star.line_to(x, y); }
+    }
+    star.close();
+
+    for (rule, center_filled) in [(FillRule::NonZero, true), (FillRule::EvenOdd, false)] {
+        canvas.clear(Color::WHITE);
+        ring.set_fill_rule(rule);
+        star.set_fill_rule(rule);
+        canvas.draw_path(&ring, &paint);
+        canvas.draw_path(&star, &paint);
+        assert_eq!(canvas.get_pixel(15, 30), Color::BLACK);
+        assert_eq!(canvas.get_pixel(30, 30) == Color::BLACK, center_filled, "{:?}", rule);
+        assert_eq!(canvas.get_pixel(70, 70) == Color::BLACK, center_filled, "{:?}", rule);
+        // 星角在两种规则下都填充
+        assert_eq!(canvas.get_pixel(70, 50), Color::BLACK);
+    }
+
+    // 完整圆环在非零规则下也有内孔
+    let mut donut = Path::new();
+    donut.add_ring_segment(30.0, 30.0, 10.0, 20.0, 0.0, std::f32::consts::TAU);
+    canvas.clear(Color::WHITE);
+    canvas.draw_path(&donut, &paint);
+    assert_eq!(canvas.get_pixel(30, 30), Color::WHITE);
+    assert_eq!(canvas.get_pixel(15, 30), Color::BLACK);
+}
+
+/// 基准：全屏半透明遮罩填充，对比逐像素 set_pixel 和 blend_span
+/// 运行：cargo test --release bench_fullscreen_overlay -- --ignored --nocapture（可加 --features simd）
+#[test]
+#[ignore]
+fn bench_fullscreen_overlay() {
+    let (w, h) = (750, 1334);
+    let mask = Color::new(0, 0, 0, 153);
+    let mut canvas = Canvas::new(w, h);
+    canvas.clear(Color::WHITE);
+
+    let start = std::time::Instant::now();
+    for _ in 0..20 {
+        for y in 0..h as i32 {
+            for x in 0..w as i32 {
+                canvas.set_pixel(x, y, mask);
+            }
+        }
+    }
+    println!("📊 set_pixel: {:?}/frame", start.elapsed() / 20);
+
+    canvas.clear(Color::WHITE);
+    let start = std::time::Instant::now();
+    for _ in 0..20 {
+        canvas.draw_rect(&crate::Rect::new(0.0, 0.0, w as f32, h as f32), &Paint::new().with_color(mask));
+    }
+    println!("📊 blend_span: {:?}/frame", start.elapsed() / 20);
+}
+
+/// 基准：500x300 卡片的 40px 模糊阴影，对比旧的多层扩展矩形和离屏遮罩 + 三次盒式模糊
+/// 运行：cargo test --release bench_box_shadow_blur -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_box_shadow_blur() {
+    use crate::renderer::components::{draw_box_shadow, BoxShadow};
+    let shadow = BoxShadow {
+        offset_x: 0.0,
+        offset_y: 8.0,
+        blur: 40.0,
+        spread: 0.0,
+        color: Color::new(0, 0, 0, 80),
+        inset: false,
+    };
+    let (x, y, w, h, radius) = (100.0, 100.0, 500.0, 300.0, 12.0);
+    let mut canvas = Canvas::new(750, 600);
+
+    // 旧实现：每 2px 画一层半透明圆角矩形，越往外越淡
+    canvas.clear(Color::WHITE);
+    let start = std::time::Instant::now();
+    for _ in 0..20 {
+        let steps = (shadow.blur / 2.0).max(1.0) as i32;
+        let base_alpha = shadow.color.a as f32 / steps as f32;
+        for i in 0..steps {
+            let expand = i as f32 * 2.0;
+            let alpha = (base_alpha * (1.0 - i as f32 / steps as f32)) as u8;
+            if alpha == 0 { continue; }
+            let paint = Paint::new().with_color(Color::new(0, 0, 0, alpha)).with_style(crate::PaintStyle::Fill);
+            let mut path = Path::new();
+            path.add_round_rect(x - expand, y + shadow.offset_y - expand, w + expand * 2.0, h + expand * 2.0, radius + expand);
+            canvas.draw_path(&path, &paint);
+        }
+    }
+    println!("📊 stacked rects: {:?}/frame", start.elapsed() / 20);
+
+    canvas.clear(Color::WHITE);
+    let start = std::time::Instant::now();
+    for _ in 0..20 {
+        draw_box_shadow(&mut canvas, &shadow, x, y, w, h, radius);
+    }
+    println!("📊 box blur: {:?}/frame", start.elapsed() / 20);
+}
+
 /// 测试 Color 混合
 #[test]
 fn test_color_blending() {
@@ -287,3 +625,135 @@ fn test_gradient_fallback_parsing() {
     let styles3 = stylesheet.get_styles(&["test3"], "view");
     assert!(styles3.contains_key("background-color"), "test3 should have background-color");
 }
+
+/// 测试 faux bold：600 以上字重加粗字形，普通字重与 draw_text 一致
+#[test]
+fn test_faux_bold_thickens_glyphs() {
+    let tr = TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf"))
+        .expect("Failed to load font");
+    let paint = Paint::new().with_color(Color::BLACK);
+    let ink = |weight: u16| {
+        let mut canvas = Canvas::new(200, 40);
+        canvas.clear(Color::WHITE);
+        tr.draw_text_with_font(&mut canvas, "Hello", 10.0, 30.0, 24.0, 0.0, FontSpec::weight(weight), &paint);
+        canvas.pixels().iter().map(|p| 255 - p.r as u32).sum::<u32>()
+    };
+    
+    let mut canvas = Canvas::new(200, 40);
+    canvas.clear(Color::WHITE);
+    tr.draw_text(&mut canvas, "Hello", 10.0, 30.0, 24.0, &paint);
+    let normal: u32 = canvas.pixels().iter().map(|p| 255 - p.r as u32).sum();
+    assert_eq!(ink(400), normal);
+    assert_eq!(ink(500), normal);
+    assert!(ink(700) > normal * 6 / 5, "bold {} normal {}", ink(700), normal);
+    assert!(ink(900) >= ink(700));
+}
+
+/// 测试字体回退链与自定义字体族：主字体缺字时由回退字体绘制，font-family 命中注册的字体
+#[test]
+fn test_font_fallback_chain_and_family() {
+    let mut tr = TextRenderer::from_bytes(include_bytes!("../../assets/Monaco.ttf"))
+        .expect("Failed to load font");
+    let paint = Paint::new().with_color(Color::BLACK);
+    let ink = |tr: &TextRenderer, text: &str| {
+        let mut canvas = Canvas::new(120, 40);
+        canvas.clear(Color::WHITE);
+        tr.draw_text(&mut canvas, text, 10.0, 30.0, 24.0, &paint);
+        canvas.pixels().iter().map(|p| 255 - p.r as u32).sum::<u32>()
+    };
+    
+    let latin = ink(&tr, "A");
+    let without_fallback = ink(&tr, "中");
+    tr.add_fallback_font(include_bytes!("../../assets/ArialUnicode.ttf")).unwrap();
+    // 拉丁字符仍由主字体绘制，中文改由回退字体绘制
+    assert_eq!(ink(&tr, "A"), latin);
+    assert_ne!(ink(&tr, "中"), without_fallback);
+    assert!(ink(&tr, "中") > 0);
+    
+    let families = ["Brand".to_string(), "sans-serif".to_string()];
+    let brand = FontSpec { families: &families, ..Default::default() };
+    let mono = tr.measure_text_with_font("iiii", 24.0, 0.0, brand);
+    assert!(!tr.has_font_family("brand"));
+    tr.register_font_family("Brand", include_bytes!("../../assets/Arial.ttf")).unwrap();
+    assert!(tr.has_font_family("brand"));
+    // 等宽的 Monaco 与 Arial 的窄字母宽度明显不同
+    let arial = tr.measure_text_with_font("iiii", 24.0, 0.0, brand);
+    assert!(arial < mono * 0.7, "arial {} mono {}", arial, mono);
+}
+
+/// 测试 letter-spacing：测量宽度每个字符（包括最后一个）增加一个字间距，与绘制的字形位置一致
+#[test]
+fn test_letter_spacing_measure_matches_draw() {
+    let tr = TextRenderer::from_bytes(include_bytes!("../../assets/ArialUnicode.ttf"))
+        .expect("Failed to load font");
+    let plain = tr.measure_text("Title", 20.0);
+    let spaced = tr.measure_text_with_spacing("Title", 20.0, 4.0);
+    assert!((spaced - plain - 5.0 * 4.0).abs() < 0.01, "plain {} spaced {}", plain, spaced);
+    assert_eq!(tr.measure_text_with_spacing("", 20.0, 4.0), 0.0);
+    
+    // 绘制时最后一个字形右侧不超过测量宽度，且比不带字间距时靠右
+    let paint = Paint::new().with_color(Color::BLACK);
+    let right_edge = |spacing: f32| {
+        let mut canvas = Canvas::new(200, 40);
+        canvas.clear(Color::WHITE);
+        tr.draw_text_with_spacing(&mut canvas, "Title", 0.0, 30.0, 20.0, spacing, &paint);
+        (0..200u32).rev().find(|&x| (0..40).any(|y| canvas.get_pixel(x, y).r < 128)).unwrap() as f32
+    };
+    assert!(right_edge(4.0) >= right_edge(0.0) + 15.0);
+    assert!(right_edge(4.0) <= spaced);
+}
+
+/// 测试局部图层：只分配指定区域的像素，图层内按画布坐标绘制，合成回原位置
+#[test]
+fn test_canvas_bounded_layer() {
+    let mut canvas = Canvas::new(100, 100);
+    canvas.clear(Color::WHITE);
+    canvas.translate(10.0, 0.0);
+
+    let layer_rect = crate::Rect::new(20.0, 30.0, 15.0, 10.0);
+    let mut layer = canvas.new_layer_in(&layer_rect);
+    assert_eq!((layer.width(), layer.height()), (15, 10));
+    assert_eq!(layer.origin(), (30, 30));
+
+    // 超出图层范围的部分被丢弃
+    let paint = Paint::new().with_color(Color::RED);
+    layer.draw_rect(&crate::Rect::new(0.0, 0.0, 100.0, 35.0), &paint);
+    canvas.draw_layer_with_opacity(&layer, 1.0);
+    assert_eq!(canvas.get_pixel(30, 30), Color::RED);
+    assert_eq!(canvas.get_pixel(44, 34), Color::RED);
+    assert_eq!(canvas.get_pixel(45, 34), Color::WHITE);
+    assert_eq!(canvas.get_pixel(29, 30), Color::WHITE);
+    assert_eq!(canvas.get_pixel(30, 35), Color::WHITE);
+
+    // 按矩阵合成时同样回到图层所在位置
+    let mut layer = canvas.new_layer_in(&layer_rect);
+    layer.draw_rect(&crate::Rect::new(20.0, 30.0, 15.0, 10.0), &Paint::new().with_color(Color::BLUE));
+    canvas.draw_layer(&layer, &crate::Matrix::IDENTITY);
+    assert_eq!(canvas.get_pixel(30, 30), Color::BLUE);
+    assert_eq!(canvas.get_pixel(44, 39), Color::BLUE);
+    assert_eq!(canvas.get_pixel(45, 39), Color::WHITE);
+}
+
+/// 测试描边矩形应用虚线和连接样式，覆盖范围仍在矩形内
+#[test]
+fn test_stroke_rect_dash_and_join() {
+    use crate::StrokeJoin;
+    let rect = crate::Rect::new(10.0, 10.0, 40.0, 40.0);
+    let stroke = Paint::new().with_color(Color::BLACK).with_style(crate::PaintStyle::Stroke);
+    let mut canvas = Canvas::new(60, 60);
+    let is_dark = |c: &Canvas, x: u32, y: u32| c.get_pixel(x, y).r < 64;
+
+    // 虚线：上边有间隔
+    canvas.clear(Color::WHITE);
+    canvas.draw_rect(&rect, &stroke.clone().with_stroke_width(4.0).with_dash(&[6.0, 6.0], 0.0));
+    let dark = (10..50).filter(|&x| is_dark(&canvas, x, 11)).count();
+    assert!(dark > 10 && dark < 30, "dark = {}", dark);
+    assert!(!is_dark(&canvas, 30, 8) && !is_dark(&canvas, 30, 15));
+
+    // 圆角连接：外角被切圆，边的中段不受影响
+    canvas.clear(Color::WHITE);
+    canvas.draw_rect(&rect, &stroke.clone().with_stroke_width(10.0).with_stroke_join(StrokeJoin::Round));
+    assert!(!is_dark(&canvas, 10, 10));
+    assert!(is_dark(&canvas, 12, 15) && is_dark(&canvas, 30, 12));
+    assert!(!is_dark(&canvas, 30, 21) && !is_dark(&canvas, 30, 9));
+}
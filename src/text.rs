@@ -2,35 +2,232 @@
 
 use crate::{Canvas, Color, Paint};
 use fontdue::{Font, FontSettings, Metrics};
+use once_cell::sync::Lazy;
 use std::path::Path;
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::sync::{Arc, Mutex};
 
-/// 文本渲染器 - 支持多字体回退（中文 + Emoji）
+/// 字形缓存默认容量（字形个数），常用中文段落约 2~3 千个不同字形
+const DEFAULT_GLYPH_CAPACITY: usize = 4096;
+/// 字宽缓存容量
+const ADVANCE_CAPACITY: usize = 16384;
+/// 常规字重
+pub const NORMAL_WEIGHT: u16 = 400;
+
+/// 进程内共享的文本渲染器，供没有持有渲染器的地方（如 canvas 上下文）使用
+static SHARED: Lazy<Option<Arc<TextRenderer>>> = Lazy::new(|| {
+    TextRenderer::load_system_font()
+        .or_else(|_| TextRenderer::from_bytes(include_bytes!("../assets/ArialUnicode.ttf")))
+        .ok()
+        .map(Arc::new)
+});
+
+/// 字形缓存 key：字体、字符、字号（0.1px 精度）、字重
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    face: u16,
+    ch: char,
+    size_key: u32,
+    weight: u16,
+}
+
+impl GlyphKey {
+    fn new(face: usize, ch: char, size: f32, weight: u16) -> Self {
+        Self { face: face as u16, ch, size_key: (size * 10.0) as u32, weight }
+    }
+}
+
+/// 绘制和测量文本时选用的字体：font-family 列表（按顺序查找已注册的字体）和字重
+#[derive(Debug, Clone, Copy)]
+pub struct FontSpec<'a> {
+    pub families: &'a [String],
+    pub weight: u16,
+}
+
+impl Default for FontSpec<'_> {
+    fn default() -> Self {
+        Self { families: &[], weight: NORMAL_WEIGHT }
+    }
+}
+
+impl FontSpec<'_> {
+    /// 只指定字重，字体按默认回退链选择
+    pub fn weight(weight: u16) -> Self {
+        Self { weight, ..Default::default() }
+    }
+}
+
+/// 光栅化后的字形覆盖率位图
+struct Glyph {
+    metrics: Metrics,
+    coverage: Vec<u8>,
+}
+
+/// 加粗字形需要向右扩展的像素数，字重低于 600 时不加粗
+fn faux_bold_width(size: f32, weight: u16) -> usize {
+    if weight < 600 {
+        return 0;
+    }
+    (size * (weight - NORMAL_WEIGHT) as f32 / 6000.0).round().max(1.0) as usize
+}
+
+/// 把覆盖率位图向右膨胀 extra 像素（每个像素取自身和左侧 extra 个像素的最大值），笔画随之变粗
+fn embolden(mut metrics: Metrics, coverage: &[u8], extra: usize) -> Glyph {
+    let (w, h) = (metrics.width, metrics.height);
+    let new_w = w + extra;
+    let mut out = vec![0u8; new_w * h];
+    for row in 0..h {
+        let src = &coverage[row * w..(row + 1) * w];
+        for (x, dst) in out[row * new_w..(row + 1) * new_w].iter_mut().enumerate() {
+            let from = x.saturating_sub(extra);
+            *dst = src[from.min(w)..(x + 1).min(w)].iter().copied().max().unwrap_or(0);
+        }
+    }
+    metrics.width = new_w;
+    Glyph { metrics, coverage: out }
+}
+
+/// 字形缓存命中统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GlyphCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    /// 当前缓存的字形数
+    pub len: usize,
+}
+
+/// 简单的 LRU 缓存：每次访问记录递增的 tick，超出容量时淘汰最久未用的 1/4
+struct LruCache<K, V> {
+    map: HashMap<K, (V, u64)>,
+    capacity: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl<K: Copy + Eq + Hash, V: Clone> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self { map: HashMap::new(), capacity, tick: 0, hits: 0, misses: 0, evictions: 0 }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        match self.map.get_mut(key) {
+            Some((value, used)) => {
+                *used = self.tick;
+                self.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        self.map.insert(key, (value, self.tick));
+        if self.map.len() > self.capacity {
+            self.evict(self.capacity * 3 / 4);
+        }
+    }
+
+    /// 淘汰最久未用的条目，直到剩下 keep 个
+    fn evict(&mut self, keep: usize) {
+        if self.map.len() <= keep {
+            return;
+        }
+        let mut ticks: Vec<u64> = self.map.values().map(|(_, used)| *used).collect();
+        ticks.sort_unstable();
+        let threshold = ticks[self.map.len() - keep - 1];
+        let before = self.map.len();
+        self.map.retain(|_, (_, used)| *used > threshold);
+        self.evictions += (before - self.map.len()) as u64;
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict(capacity);
+    }
+}
+
+/// 文本渲染器 - 支持多字体回退（中文 + 西文 + Emoji）和按 font-family 注册的字体
 pub struct TextRenderer {
-    /// 主字体（中文/英文）
-    main_font: Font,
+    /// 已加载的字体，第一个是主字体（行高等度量以它为准）
+    faces: Vec<Font>,
+    /// 默认回退链（faces 下标），逐个查找包含该字符的字体
+    fallback: Vec<usize>,
     /// Emoji 字体
-    emoji_font: Option<Font>,
-    /// 简单的字形缓存 (char, size_u32) -> (Metrics, Bitmap)
+    emoji_face: Option<usize>,
+    /// font-family 名称（小写）-> faces 下标，来自 @font-face
+    families: HashMap<String, usize>,
+    /// 字形缓存 (char, size, weight) -> 覆盖率位图，LRU 淘汰
     /// 使用 Mutex 实现内部可变性，因为 draw 方法是 &self
-    cache: Arc<Mutex<HashMap<(char, u32), (Metrics, Vec<u8>)>>>,
+    cache: Arc<Mutex<LruCache<GlyphKey, Arc<Glyph>>>>,
+    /// 字宽缓存 (char, size) -> advance_width，供测量和换行使用
+    advances: Arc<Mutex<LruCache<GlyphKey, f32>>>,
 }
 
 impl TextRenderer {
     /// 从字体数据创建
     pub fn from_bytes(font_data: &[u8]) -> Result<Self, String> {
+        let font = Self::parse_font(font_data)?;
+        Ok(Self { 
+            faces: vec![font],
+            fallback: vec![0],
+            emoji_face: None,
+            families: HashMap::new(),
+            cache: Arc::new(Mutex::new(LruCache::new(DEFAULT_GLYPH_CAPACITY))),
+            advances: Arc::new(Mutex::new(LruCache::new(ADVANCE_CAPACITY))),
+        })
+    }
+    
+    fn parse_font(font_data: &[u8]) -> Result<Font, String> {
         let settings = FontSettings {
             scale: 40.0,
             ..Default::default()
         };
-        let font = Font::from_bytes(font_data, settings)
-            .map_err(|e| e.to_string())?;
-        Ok(Self { 
-            main_font: font,
-            emoji_font: None,
-            cache: Arc::new(Mutex::new(HashMap::new())),
-        })
+        Font::from_bytes(font_data, settings).map_err(|e| e.to_string())
+    }
+    
+    /// 在默认回退链末尾追加字体，前面的字体都缺字时使用
+    pub fn add_fallback_font(&mut self, font_data: &[u8]) -> Result<(), String> {
+        self.faces.push(Self::parse_font(font_data)?);
+        self.fallback.push(self.faces.len() - 1);
+        Ok(())
+    }
+    
+    /// 注册 font-family 对应的字体（@font-face），同名时替换
+    pub fn register_font_family(&mut self, family: &str, font_data: &[u8]) -> Result<(), String> {
+        let font = Self::parse_font(font_data)?;
+        let key = family.trim().to_lowercase();
+        match self.families.get(&key) {
+            Some(&index) => self.faces[index] = font,
+            None => {
+                self.faces.push(font);
+                self.families.insert(key, self.faces.len() - 1);
+            }
+        }
+        // 替换字体后旧的字形和字宽不再有效
+        self.clear_caches();
+        Ok(())
+    }
+    
+    /// 是否注册了该 font-family
+    pub fn has_font_family(&self, family: &str) -> bool {
+        self.families.contains_key(&family.trim().to_lowercase())
+    }
+    
+    fn clear_caches(&self) {
+        self.cache.lock().unwrap().map.clear();
+        self.advances.lock().unwrap().map.clear();
     }
     
     /// 从文件路径加载字体
@@ -50,6 +247,12 @@ impl TextRenderer {
             "/System/Library/Fonts/STHeiti Light.ttc",
         ];
         
+        // 主字体缺字时的回退字体（覆盖更多符号和少数民族文字）
+        let fallback_font_paths = [
+            "/Library/Fonts/Arial Unicode.ttf",
+            "/System/Library/Fonts/Apple Symbols.ttf",
+        ];
+        
         // Emoji 字体路径
         let emoji_font_paths = [
             "/System/Library/Fonts/Apple Color Emoji.ttc",
@@ -58,12 +261,14 @@ impl TextRenderer {
         
         // 加载主字体
         let mut renderer: Option<TextRenderer> = None;
+        let mut main_path = "";
         for path in &main_font_paths {
             if Path::new(path).exists() {
                 match Self::from_file(path) {
                     Ok(r) => {
-                        println!("✅ Main font: {}", path);
+                        log::info!("✅ Main font: {}", path);
                         renderer = Some(r);
+                        main_path = path;
                         break;
                     }
                     Err(_) => continue,
@@ -73,17 +278,25 @@ impl TextRenderer {
         
         let mut renderer = renderer.ok_or("No main font found")?;
         
+        // 加载回退字体
+        for path in fallback_font_paths.iter().filter(|p| **p != main_path) {
+            if Path::new(path).exists() {
+                if let Ok(data) = std::fs::read(path) {
+                    if renderer.add_fallback_font(&data).is_ok() {
+                        log::info!("✅ Fallback font: {}", path);
+                    }
+                }
+            }
+        }
+        
         // 加载 Emoji 字体
         for path in &emoji_font_paths {
             if Path::new(path).exists() {
                 if let Ok(data) = std::fs::read(path) {
-                    let settings = FontSettings {
-                        scale: 40.0,
-                        ..Default::default()
-                    };
-                    if let Ok(font) = Font::from_bytes(data.as_slice(), settings) {
-                        println!("✅ Emoji font: {}", path);
-                        renderer.emoji_font = Some(font);
+                    if let Ok(font) = Self::parse_font(&data) {
+                        log::info!("✅ Emoji font: {}", path);
+                        renderer.faces.push(font);
+                        renderer.emoji_face = Some(renderer.faces.len() - 1);
                         break;
                     }
                 }
@@ -93,6 +306,11 @@ impl TextRenderer {
         Ok(renderer)
     }
 
+    /// 共享的文本渲染器（系统字体，找不到时用内置字体），首次调用时加载
+    pub fn shared() -> Option<Arc<TextRenderer>> {
+        SHARED.clone()
+    }
+
     /// 判断字符是否为 Emoji
     fn is_emoji(ch: char) -> bool {
         let code = ch as u32;
@@ -126,39 +344,20 @@ impl TextRenderer {
     
     /// 渲染文本到画布（带字间距）
     pub fn draw_text_with_spacing(&self, canvas: &mut Canvas, text: &str, x: f32, y: f32, size: f32, letter_spacing: f32, paint: &Paint) {
+        self.draw_text_with_font(canvas, text, x, y, size, letter_spacing, FontSpec::default(), paint);
+    }
+
+    /// 按字体渲染文本：font-family 中注册过的字体优先，缺字时走默认回退链；
+    /// 字重 600 及以上没有对应字体文件，加粗字形（faux bold），字宽不变
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text_with_font(&self, canvas: &mut Canvas, text: &str, x: f32, y: f32, size: f32, letter_spacing: f32, font: FontSpec, paint: &Paint) {
         let mut cursor_x = x;
-        let size_key = (size * 10.0) as u32; // 将 size 转换为整数 key，保留1位小数精度
-        
-        // 批量获取锁，避免循环中频繁锁竞争
-        // 注意：这里为了简化，我们会在需要时获取锁。更好的做法可能是先收集所有需要的 glyph，然后一次性 rasterize。
-        // 但考虑到 font.rasterize 是耗时操作，不应该在锁内做。
-        
+
         for ch in text.chars() {
-            // 先尝试从缓存获取（快速路径）
-            let cached_data = {
-                let cache = self.cache.lock().unwrap();
-                cache.get(&(ch, size_key)).cloned()
-            };
-            
-            let (metrics, bitmap) = if let Some(data) = cached_data {
-                data
-            } else {
-                // 缓存未命中，执行光栅化
-                // 选择字体
-                let font = if Self::is_emoji(ch) {
-                    self.emoji_font.as_ref().unwrap_or(&self.main_font)
-                } else {
-                    &self.main_font
-                };
-                
-                let (metrics, bitmap) = font.rasterize(ch, size);
-                
-                // 存入缓存
-                let mut cache = self.cache.lock().unwrap();
-                cache.insert((ch, size_key), (metrics.clone(), bitmap.clone()));
-                (metrics, bitmap)
-            };
-            
+            let glyph = self.glyph(self.face_for(ch, font.families), ch, size, font.weight);
+            let metrics = glyph.metrics;
+            let bitmap = &glyph.coverage;
+
             if metrics.width == 0 || metrics.height == 0 {
                 cursor_x += metrics.advance_width + letter_spacing;
                 continue;
@@ -175,16 +374,15 @@ impl TextRenderer {
                         let px = (glyph_x + gx as f32).round() as i32;
                         let py = (glyph_y + gy as f32).round() as i32;
 
-                        if px >= 0 && py >= 0 && px < canvas.width() as i32 && py < canvas.height() as i32 {
-                            // 优化：移除 gamma 校正，直接使用 linear alpha
-                            // let gamma_coverage = coverage.powf(0.8);
-                            let alpha = (paint.color.a as f32 * coverage) as u8;
+                        // 超出画布的像素由 set_pixel 丢弃
+                        // 优化：移除 gamma 校正，直接使用 linear alpha
+                        // let gamma_coverage = coverage.powf(0.8);
+                        let alpha = (paint.color.a as f32 * coverage) as u8;
+                        
+                        if alpha > 0 {
+                            let color = Color::new(paint.color.r, paint.color.g, paint.color.b, alpha);
                             
-                            if alpha > 0 {
-                                let color = Color::new(paint.color.r, paint.color.g, paint.color.b, alpha);
-                                
-                                canvas.set_pixel(px, py, color);
-                            }
+                            canvas.set_pixel(px, py, color);
                         }
                     }
                 }
@@ -194,6 +392,67 @@ impl TextRenderer {
         }
     }
 
+    /// 选择字符对应的字体（faces 下标）：font-family 中注册且含该字符的字体 > Emoji 字体 >
+    /// 默认回退链中第一个含该字符的字体，都没有时用主字体
+    fn face_for(&self, ch: char, families: &[String]) -> usize {
+        let has_glyph = |index: usize| self.faces[index].lookup_glyph_index(ch) != 0;
+        for family in families {
+            if let Some(&index) = self.families.get(&family.to_lowercase()) {
+                if has_glyph(index) {
+                    return index;
+                }
+            }
+        }
+        if let Some(index) = self.emoji_face.filter(|_| Self::is_emoji(ch)) {
+            return index;
+        }
+        self.fallback.iter().copied().find(|&index| has_glyph(index)).unwrap_or(0)
+    }
+
+    /// 获取字形位图，未命中时光栅化并写入缓存
+    fn glyph(&self, face: usize, ch: char, size: f32, weight: u16) -> Arc<Glyph> {
+        let key = GlyphKey::new(face, ch, size, weight);
+        if let Some(glyph) = self.cache.lock().unwrap().get(&key) {
+            return glyph;
+        }
+        // 光栅化比较耗时，不在锁内执行
+        let (metrics, coverage) = self.faces[face].rasterize(ch, size);
+        let glyph = match faux_bold_width(size, weight) {
+            extra if extra > 0 && !Self::is_emoji(ch) => embolden(metrics, &coverage, extra),
+            _ => Glyph { metrics, coverage },
+        };
+        let glyph = Arc::new(glyph);
+        self.cache.lock().unwrap().insert(key, glyph.clone());
+        glyph
+    }
+
+    /// 字符宽度（带缓存）
+    fn advance(&self, ch: char, size: f32) -> f32 {
+        self.advance_with_font(ch, size, &[])
+    }
+
+    fn advance_with_font(&self, ch: char, size: f32, families: &[String]) -> f32 {
+        let face = self.face_for(ch, families);
+        let key = GlyphKey::new(face, ch, size, NORMAL_WEIGHT);
+        if let Some(width) = self.advances.lock().unwrap().get(&key) {
+            return width;
+        }
+        let width = self.faces[face].metrics(ch, size).advance_width;
+        self.advances.lock().unwrap().insert(key, width);
+        width
+    }
+
+    /// 字形缓存统计
+    pub fn glyph_cache_stats(&self) -> GlyphCacheStats {
+        let cache = self.cache.lock().unwrap();
+        GlyphCacheStats { hits: cache.hits, misses: cache.misses, evictions: cache.evictions, len: cache.map.len() }
+    }
+
+    /// 设置字形缓存容量，0 表示不缓存
+    pub fn set_glyph_cache_capacity(&self, capacity: usize) {
+        self.cache.lock().unwrap().set_capacity(capacity);
+    }
+
     /// 测量文本宽度
     pub fn measure_text(&self, text: &str, size: f32) -> f32 {
         self.measure_text_with_spacing(text, size, 0.0)
@@ -201,36 +460,42 @@ impl TextRenderer {
     
     /// 测量文本宽度（带字间距）
     pub fn measure_text_with_spacing(&self, text: &str, size: f32, letter_spacing: f32) -> f32 {
-        let mut width = 0.0;
-        let char_count = text.chars().count();
-        for (i, ch) in text.chars().enumerate() {
-            let font = if Self::is_emoji(ch) {
-                self.emoji_font.as_ref().unwrap_or(&self.main_font)
-            } else {
-                &self.main_font
-            };
-            let metrics = font.metrics(ch, size);
-            width += metrics.advance_width;
-            if i < char_count - 1 {
-                width += letter_spacing;
-            }
-        }
-        width
+        self.measure_text_with_font(text, size, letter_spacing, FontSpec::default())
+    }
+    
+    /// 按字体测量文本宽度（带字间距）
+    pub fn measure_text_with_font(&self, text: &str, size: f32, letter_spacing: f32, font: FontSpec) -> f32 {
+        // 与绘制一致，每个字符（包括最后一个）之后都加字间距
+        text.chars().map(|ch| self.advance_with_font(ch, size, font.families) + letter_spacing).sum()
     }
     
     /// 测量单个字符宽度
     pub fn measure_char(&self, ch: char, size: f32) -> f32 {
-        let font = if Self::is_emoji(ch) {
-            self.emoji_font.as_ref().unwrap_or(&self.main_font)
-        } else {
-            &self.main_font
-        };
-        font.metrics(ch, size).advance_width
+        self.advance(ch, size)
+    }
+    
+    /// 按字体测量单个字符宽度
+    pub fn measure_char_with_font(&self, ch: char, size: f32, font: FontSpec) -> f32 {
+        self.advance_with_font(ch, size, font.families)
     }
     
+    /// 主字体在 size 下的上行高度和下行深度（都为正数）
+    pub fn vertical_metrics(&self, size: f32) -> (f32, f32) {
+        match self.faces[0].horizontal_line_metrics(size) {
+            Some(m) => (m.ascent, -m.descent),
+            None => (size * 0.8, size * 0.2),
+        }
+    }
+
+    /// 文字在高 line_height 的行框中垂直居中时，基线相对行框顶部的偏移
+    pub fn baseline_offset(&self, size: f32, line_height: f32) -> f32 {
+        let (ascent, descent) = self.vertical_metrics(size);
+        (line_height - ascent - descent) / 2.0 + ascent
+    }
+
     /// 测量文本高度
     pub fn measure_height(&self, size: f32) -> f32 {
-        let metrics = self.main_font.metrics('M', size);
+        let metrics = self.faces[0].metrics('M', size);
         metrics.height as f32
     }
     
@@ -248,13 +513,7 @@ impl TextRenderer {
         let mut current_width = 0.0;
         
         for (i, ch) in chars.iter().enumerate() {
-            let font = if Self::is_emoji(*ch) {
-                self.emoji_font.as_ref().unwrap_or(&self.main_font)
-            } else {
-                &self.main_font
-            };
-            let metrics = font.metrics(*ch, size);
-            let char_width = metrics.advance_width;
+            let char_width = self.advance(*ch, size);
             
             // 检查是否需要换行
             if current_width + char_width > max_width && i > line_start {
@@ -289,13 +548,7 @@ impl TextRenderer {
         let mut current_width = 0.0;
         
         for ch in text.chars() {
-            let font = if Self::is_emoji(ch) {
-                self.emoji_font.as_ref().unwrap_or(&self.main_font)
-            } else {
-                &self.main_font
-            };
-            let metrics = font.metrics(ch, size);
-            let char_width = metrics.advance_width;
+            let char_width = self.advance(ch, size);
             
             if current_width + char_width > max_width && current_width > 0.0 {
                 line_count += 1;
@@ -139,24 +139,25 @@ impl Component for Image {
                 ImageMode::None => (1.0, 1.0, 0.0, 0.0),
             };
             
-            for dy in 0..dst_h as i32 {
-                for dx in 0..dst_w as i32 {
-                    let sx = ((dx as f32 - offset_x) * scale_x) as i32;
-                    let sy = ((dy as f32 - offset_y) * scale_y) as i32;
+            // 按设备像素逐行采样，画布带缩放时不损失清晰度
+            let device = canvas.map_rect(&bounds);
+            let (kx, ky) = (device.width / dst_w.max(1.0), device.height / dst_h.max(1.0));
+            let mut row = vec![Color::TRANSPARENT; device.width.max(0.0) as usize];
+            for dy in 0..device.height as i32 {
+                row.fill(Color::TRANSPARENT);
+                for (dx, dst) in row.iter_mut().enumerate() {
+                    let sx = ((dx as f32 / kx - offset_x) * scale_x) as i32;
+                    let sy = ((dy as f32 / ky - offset_y) * scale_y) as i32;
                     
                     if sx >= 0 && sx < self.image_width as i32 &&
                        sy >= 0 && sy < self.image_height as i32 {
                         let idx = (sy as u32 * self.image_width + sx as u32) as usize;
                         if idx < pixels.len() {
-                            let color = pixels[idx];
-                            canvas.set_pixel(
-                                bounds.x as i32 + dx,
-                                bounds.y as i32 + dy,
-                                color
-                            );
+                            *dst = pixels[idx];
                         }
                     }
                 }
+                canvas.blend_row(device.y as i32 + dy, device.x as i32, &row);
             }
         }
     }
@@ -4,6 +4,7 @@
 use crate::Rect;
 use std::collections::HashMap;
 use super::scroll_controller::ScrollController;
+use super::movable::{MovableView, MovableConfig};
 
 /// 组件交互状态
 #[derive(Clone, Debug, Default)]
@@ -25,6 +26,100 @@ pub struct FocusedInput {
     pub text_offset: f32, // 文本滚动偏移（物理像素）
     pub maxlength: i32, // 最大输入长度，-1 为不限制
     pub input_type: String, // 输入类型：text/number/idcard/digit
+    pub multiline: bool, // textarea：回车换行，上下键在视觉行之间移动
+    pub lines: Vec<TextLine>, // 多行排版（渲染时更新）
+    pub line_height: f32, // 行高（物理像素，渲染时更新）
+    pub scroll_top: f32, // 多行文本的纵向滚动（物理像素，渲染时更新）
+    pub preedit: String, // 输入法正在组合的文本，提交前不修改 value
+    pub confirm_type: String, // 回车键类型，textarea 为 return 时回车换行
+    pub confirm_hold: bool, // 回车确认后保持聚焦
+}
+
+/// 输入框的输入限制（渲染时从 type / password / maxlength / confirm-type 等属性登记）
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputAttrs {
+    pub input_type: String,
+    pub password: bool,
+    /// 最大输入长度，-1 为不限制
+    pub maxlength: i32,
+    /// 回车键类型：send/search/next/go/done，textarea 为 return 时回车换行
+    pub confirm_type: String,
+    /// 回车确认后保持聚焦（confirm-hold）
+    pub confirm_hold: bool,
+}
+
+impl Default for InputAttrs {
+    fn default() -> Self {
+        Self {
+            input_type: "text".to_string(),
+            password: false,
+            maxlength: 140,
+            confirm_type: "done".to_string(),
+            confirm_hold: false,
+        }
+    }
+}
+
+/// 滑块的步长和轨道范围（渲染时从 step / block-size / show-value 等属性登记）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SliderAttrs {
+    pub step: f32,
+    /// 轨道起点和终点到元素左边的距离（逻辑像素），两端各留出半个滑块，show-value 时右侧留出数值
+    pub track: (f32, f32),
+}
+
+/// 多行输入框中的一个视觉行（自动换行或换行符分隔）
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLine {
+    /// 行首字符下标
+    pub start: usize,
+    /// 行内各字符边界相对行首的 x 坐标（物理像素），首项为 0，长度为字符数 + 1
+    pub offsets: Vec<f32>,
+}
+
+impl TextLine {
+    /// 行尾位置（不含换行符）
+    pub fn end(&self) -> usize {
+        self.start + self.offsets.len().saturating_sub(1)
+    }
+    
+    /// 光标位置在行内的 x 坐标
+    pub fn x_of(&self, pos: usize) -> f32 {
+        let i = pos.saturating_sub(self.start).min(self.offsets.len().saturating_sub(1));
+        self.offsets.get(i).copied().unwrap_or(0.0)
+    }
+    
+    /// 按宽度逐字换行，换行符强制分行（换行符本身不属于任何一行）
+    pub fn wrap(text: &str, max_width: f32, char_width: impl Fn(char) -> f32) -> Vec<TextLine> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for paragraph in text.split('\n') {
+            let mut line = TextLine { start, offsets: vec![0.0] };
+            for (i, ch) in paragraph.chars().enumerate() {
+                let width = char_width(ch);
+                let x = line.offsets[line.offsets.len() - 1];
+                if x + width > max_width && line.offsets.len() > 1 {
+                    let next = TextLine { start: start + i, offsets: vec![0.0, width] };
+                    lines.push(std::mem::replace(&mut line, next));
+                } else {
+                    line.offsets.push(x + width);
+                }
+            }
+            lines.push(line);
+            start += paragraph.chars().count() + 1;
+        }
+        lines
+    }
+    
+    /// 离 x 最近的字符边界
+    pub fn pos_at(&self, x: f32) -> usize {
+        let i = self.offsets.iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| (*a - x).abs().total_cmp(&(*b - x).abs()))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        self.start + i
+    }
 }
 
 impl FocusedInput {
@@ -85,6 +180,15 @@ impl FocusedInput {
         }
     }
     
+    /// 显示的文本：密码框用圆点代替，字符数与 value 一致，光标位置可直接复用
+    pub fn display_value(&self) -> String {
+        if self.is_password {
+            "•".repeat(self.value.chars().count())
+        } else {
+            self.value.clone()
+        }
+    }
+    
     /// 检查是否可以插入更多字符（maxlength 限制）
     pub fn can_insert(&self, insert_len: usize) -> bool {
         if self.maxlength < 0 {
@@ -97,6 +201,76 @@ impl FocusedInput {
         // 当前长度 - 选中长度 + 插入长度 <= maxlength
         (current_len - selection_len + insert_len) <= self.maxlength as usize
     }
+    
+    /// 当前使用的视觉行；排版与内容不一致时（输入后尚未重绘）按换行符分行，
+    /// 每个字符宽度记为 1，上下移动时按列对齐
+    fn visual_lines(&self) -> Vec<TextLine> {
+        let len = self.value.chars().count();
+        if self.lines.last().is_some_and(|l| l.end() == len) {
+            return self.lines.clone();
+        }
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in self.value.split('\n') {
+            let count = line.chars().count();
+            lines.push(TextLine { start, offsets: (0..=count).map(|i| i as f32).collect() });
+            start += count + 1;
+        }
+        lines
+    }
+    
+    /// 光标所在的视觉行（自动换行处的光标属于下一行）
+    fn line_index(lines: &[TextLine], pos: usize) -> usize {
+        lines.iter().rposition(|l| l.start <= pos).unwrap_or(0)
+    }
+    
+    /// 上下移动光标 delta 行，保持 x 坐标最接近；单行输入框移到开头或结尾
+    pub fn move_lines(&mut self, delta: i32) {
+        self.clear_selection();
+        if !self.multiline {
+            self.cursor_pos = if delta < 0 { 0 } else { self.value.chars().count() };
+            return;
+        }
+        let lines = self.visual_lines();
+        let current = Self::line_index(&lines, self.cursor_pos);
+        let target = current as i32 + delta;
+        self.cursor_pos = if target < 0 {
+            0
+        } else if target as usize >= lines.len() {
+            self.value.chars().count()
+        } else {
+            let x = lines[current].x_of(self.cursor_pos);
+            lines[target as usize].pos_at(x)
+        };
+    }
+    
+    /// 多行输入框中点击位置对应的光标，x / y 相对文本区域左上角（物理像素，未计滚动）
+    pub fn cursor_at(&self, x: f32, y: f32) -> usize {
+        let lines = self.visual_lines();
+        if lines.is_empty() || self.line_height <= 0.0 {
+            return self.value.chars().count();
+        }
+        let row = ((y + self.scroll_top) / self.line_height).floor().max(0.0) as usize;
+        lines[row.min(lines.len() - 1)].pos_at(x)
+    }
+    
+    /// 点击位置对应的光标，x / y 相对输入框左上角（物理像素），char_width 为单个字符宽度
+    pub fn cursor_from_point(&self, x: f32, y: f32, sf: f32, char_width: impl Fn(char) -> f32) -> usize {
+        if !self.multiline {
+            let text = self.display_value();
+            let widths: Vec<f32> = text.chars().map(&char_width).collect();
+            return calculate_cursor_position(&text, &widths, x, 12.0 * sf, self.text_offset);
+        }
+        let (x, y) = (x - 12.0 * sf, y - 8.0 * sf);
+        if self.lines.last().is_some_and(|l| l.end() == self.value.chars().count()) {
+            return self.cursor_at(x, y);
+        }
+        // 刚聚焦还没有排版时按默认字号 16px、1.5 倍行高排版
+        let mut laid_out = self.clone();
+        laid_out.lines = TextLine::wrap(&self.value, (self.bounds.width - 24.0) * sf, char_width);
+        laid_out.line_height = 24.0 * sf;
+        laid_out.cursor_at(x, y)
+    }
 }
 
 /// 计算光标位置
@@ -144,6 +318,28 @@ pub struct DraggingSlider {
     pub bounds: Rect,
     pub min: f32,
     pub max: f32,
+    pub attrs: SliderAttrs,
+}
+
+impl DraggingSlider {
+    /// 指针横坐标对应的取值，按步长从 min 开始取整
+    fn value_at(&self, x: f32) -> f32 {
+        let (start, end) = (self.bounds.x + self.attrs.track.0, self.bounds.x + self.attrs.track.1);
+        let progress = if end > start { ((x - start) / (end - start)).clamp(0.0, 1.0) } else { 0.0 };
+        let value = self.min + progress * (self.max - self.min);
+        if self.attrs.step <= 0.0 {
+            return value;
+        }
+        let stepped = self.min + ((value - self.min) / self.attrs.step).round() * self.attrs.step;
+        // 最后一步超出 max 时退回一步
+        if stepped > self.max + f32::EPSILON { stepped - self.attrs.step } else { stepped }
+    }
+}
+
+/// 滑块取值的文本形式，整数不带小数点（step 为小数时保留小数）
+pub fn format_slider_value(value: f32) -> String {
+    let rounded = (value * 1000.0).round() / 1000.0;
+    if rounded.fract() == 0.0 { format!("{}", rounded as i32) } else { format!("{}", rounded) }
 }
 
 /// 交互组件类型
@@ -154,9 +350,43 @@ pub enum InteractionType {
     Switch,
     Slider,
     Input,
+    /// textarea，多行输入
+    Textarea,
     Button,
     ScrollArea,
+    /// movable-view
+    Movable,
+    /// video，点击切换播放/暂停
+    Video,
     View,
+    /// 可选中文本（text 的 user-select / selectable），长按全选并复制
+    Text,
+}
+
+/// 可选中文本的选择范围（字符索引）
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSelection {
+    pub id: String,
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// 在可选中文本上拖动选择的状态
+#[derive(Debug, Clone)]
+struct TextDrag {
+    id: String,
+    /// 按下位置的字符下标
+    anchor: usize,
+    /// 元素是否 fixed（坐标不随页面滚动）
+    is_fixed: bool,
+}
+
+impl TextSelection {
+    /// 选中的文本
+    pub fn selected_text(&self) -> String {
+        self.text.chars().skip(self.start).take(self.end.saturating_sub(self.start)).collect()
+    }
 }
 
 /// 可交互组件信息
@@ -171,9 +401,13 @@ pub struct InteractiveElement {
     pub min: f32,
     pub max: f32,
     // Scroll area specific
+    /// 滚动方向上的内容长度（scroll_x 时为宽度）
     pub content_height: f32,
+    /// 滚动方向上的视口长度（scroll_x 时为宽度）
     pub viewport_height: f32,
     pub is_fixed: bool,
+    /// 横向滚动区域（scroll-view scroll-x）
+    pub scroll_x: bool,
 }
 
 /// 按下的按钮
@@ -183,6 +417,16 @@ pub struct PressedButton {
     pub bounds: Rect,
 }
 
+/// 按下的位置（窗口逻辑坐标），渲染器据此计算 hover-class 状态
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HoverPress {
+    pub x: f32,
+    pub y: f32,
+    /// 每次按下递增，用于区分两次按下
+    pub seq: u64,
+    pub released: bool,
+}
+
 /// 点击动画状态
 #[derive(Clone, Debug)]
 pub struct ClickAnimation {
@@ -203,6 +447,10 @@ pub struct InteractionManager {
     pub scroll_controllers: HashMap<String, ScrollController>,
     /// 正在拖动的滚动区域 ID
     pub dragging_scroll_area: Option<String>,
+    /// movable-view 状态集合
+    pub movable_views: HashMap<String, MovableView>,
+    /// 正在拖动的 movable-view ID
+    pub dragging_movable: Option<String>,
     /// 按下的按钮
     pub pressed_button: Option<PressedButton>,
     /// 点击动画
@@ -213,6 +461,27 @@ pub struct InteractionManager {
     pub is_selecting_text: bool,
     /// 选择起始位置（用于拖动选择）
     pub selection_anchor: Option<usize>,
+    /// 可选中文本的选择（长按全选或拖动选择）
+    pub text_selection: Option<TextSelection>,
+    /// 可选中文本的排版：ID -> (视觉行, 行高)，逻辑像素，相对元素左上角，渲染时更新
+    text_layouts: HashMap<String, (Vec<TextLine>, f32)>,
+    /// 正在拖动选择的可选中文本
+    text_drag: Option<TextDrag>,
+    /// 输入框的输入限制：ID -> 属性，渲染时更新
+    input_attrs: HashMap<String, InputAttrs>,
+    /// 滑块的步长和轨道：ID -> 属性，渲染时更新
+    slider_attrs: HashMap<String, SliderAttrs>,
+    /// 复选框 / 单选框 ID -> 所在 checkbox-group / radio-group 的 ID，渲染时更新
+    groups: HashMap<String, String>,
+    /// 设置了 open-type 的按钮：ID -> open-type，渲染时更新
+    open_types: HashMap<String, String>,
+    /// 当前（或刚松开的）按下，用于 hover-class
+    hover_press: Option<HoverPress>,
+    hover_seq: u64,
+    /// 指针位置（窗口逻辑坐标），用于 :hover
+    pub pointer_pos: Option<(f32, f32)>,
+    /// 指针是否按下，用于 :active
+    pub pointer_down: bool,
 }
 
 impl InteractionManager {
@@ -226,8 +495,21 @@ impl InteractionManager {
             elements: Vec::new(),
             scroll_controllers: HashMap::new(),
             dragging_scroll_area: None,
+            movable_views: HashMap::new(),
+            dragging_movable: None,
             is_selecting_text: false,
             selection_anchor: None,
+            text_selection: None,
+            text_layouts: HashMap::new(),
+            text_drag: None,
+            input_attrs: HashMap::new(),
+            slider_attrs: HashMap::new(),
+            groups: HashMap::new(),
+            open_types: HashMap::new(),
+            hover_press: None,
+            hover_seq: 0,
+            pointer_pos: None,
+            pointer_down: false,
         }
     }
     
@@ -236,11 +518,20 @@ impl InteractionManager {
         self.elements.clear();
     }
     
+    /// 本次渲染注册的交互元素
+    pub fn elements(&self) -> &[InteractiveElement] {
+        &self.elements
+    }
+    
     /// 注册交互元素
     pub fn register_element(&mut self, element: InteractiveElement) {
         if element.interaction_type == InteractionType::ScrollArea {
             if !self.scroll_controllers.contains_key(&element.id) {
-                let controller = ScrollController::new(element.content_height, element.viewport_height);
+                let controller = if element.scroll_x {
+                    ScrollController::new_horizontal(element.content_height, element.viewport_height)
+                } else {
+                    ScrollController::new(element.content_height, element.viewport_height)
+                };
                 self.scroll_controllers.insert(element.id.clone(), controller);
             } else if let Some(controller) = self.scroll_controllers.get_mut(&element.id) {
                 controller.update_content_height(element.content_height, element.viewport_height);
@@ -264,6 +555,65 @@ impl InteractionManager {
         self.scroll_controllers.get_mut(id)
     }
     
+    /// 注册或更新 movable-view，返回当前平移量（逻辑像素）
+    pub fn register_movable(&mut self, id: &str, config: MovableConfig) -> (f32, f32) {
+        match self.movable_views.get_mut(id) {
+            Some(view) => {
+                view.update_config(config);
+                view.position()
+            }
+            None => {
+                let view = MovableView::new(config);
+                let pos = view.position();
+                self.movable_views.insert(id.to_string(), view);
+                pos
+            }
+        }
+    }
+    
+    /// 开始拖动 movable-view
+    pub fn begin_movable_drag(&mut self, id: &str, x: f32, y: f32, timestamp: u64) -> bool {
+        if let Some(view) = self.movable_views.get_mut(id) {
+            view.begin_drag(x, y, timestamp);
+            self.dragging_movable = Some(id.to_string());
+            return true;
+        }
+        false
+    }
+    
+    /// 拖动 movable-view，位置变化时返回 change 事件
+    pub fn update_movable_drag(&mut self, x: f32, y: f32, timestamp: u64) -> Option<InteractionResult> {
+        let id = self.dragging_movable.clone()?;
+        let view = self.movable_views.get_mut(&id)?;
+        view.update_drag(x, y, timestamp);
+        view.take_change().map(|(x, y, source)| InteractionResult::MovableChange { id, x, y, source: source.to_string() })
+    }
+    
+    /// 结束 movable-view 拖动，返回是否有拖动被结束
+    pub fn end_movable_drag(&mut self) -> bool {
+        if let Some(id) = self.dragging_movable.take() {
+            if let Some(view) = self.movable_views.get_mut(&id) {
+                view.end_drag();
+            }
+            return true;
+        }
+        false
+    }
+    
+    /// 推进 movable-view 的惯性/回弹/属性动画，返回 (是否还在动画中, change 事件)
+    pub fn update_movables(&mut self, dt: f32) -> (bool, Vec<InteractionResult>) {
+        let mut animating = false;
+        let mut results = Vec::new();
+        for (id, view) in self.movable_views.iter_mut() {
+            if view.is_dragging() { continue; }
+            animating |= view.update(dt);
+            if let Some((x, y, source)) = view.take_change() {
+                results.push(InteractionResult::MovableChange { id: id.clone(), x, y, source: source.to_string() });
+            }
+        }
+        (animating, results)
+    }
+    
     /// 设置组件状态
     pub fn set_state(&mut self, id: String, state: ComponentState) {
         self.states.insert(id, state);
@@ -278,8 +628,163 @@ impl InteractionManager {
         })
     }
     
-    /// 处理点击事件
+    /// 处理长按：落在可选中文本上时全选其内容，并返回复制全部文本的结果
+    pub fn handle_long_press(&mut self, x: f32, y: f32) -> Option<InteractionResult> {
+        let element = self.hit_test(x, y)?;
+        if element.interaction_type != InteractionType::Text || element.value.is_empty() {
+            return None;
+        }
+        self.text_selection = Some(TextSelection {
+            id: element.id.clone(),
+            text: element.value.clone(),
+            start: 0,
+            end: element.value.chars().count(),
+        });
+        self.copy_text_selection()
+    }
+    
+    /// 记录可选中文本的排版（逻辑像素，相对元素左上角），用于拖动选择时定位字符
+    pub fn set_text_layout(&mut self, id: &str, lines: Vec<TextLine>, line_height: f32) {
+        self.text_layouts.insert(id.to_string(), (lines, line_height));
+    }
+    
+    /// 记录滑块的步长和轨道范围，点击和拖动时按轨道换算取值并按步长取整
+    pub fn set_slider_attrs(&mut self, id: &str, attrs: SliderAttrs) {
+        self.slider_attrs.insert(id.to_string(), attrs);
+    }
+    
+    /// 记录复选框 / 单选框所在的 checkbox-group / radio-group
+    pub fn set_group(&mut self, id: &str, group: &str) {
+        self.groups.insert(id.to_string(), group.to_string());
+    }
+    
+    /// 复选框 / 单选框切换后所在组的 change 事件，detail.value 为组内选中项的 value（按页面顺序）
+    pub fn group_change(&self, result: &InteractionResult) -> Option<InteractionResult> {
+        let id = match result {
+            InteractionResult::Toggle { id, .. } | InteractionResult::Select { id, .. } => id,
+            _ => return None,
+        };
+        let group = self.groups.get(id)?;
+        let mut seen = std::collections::HashSet::new();
+        let members: Vec<&InteractiveElement> = self.elements.iter()
+            .filter(|e| self.groups.get(&e.id) == Some(group) && seen.insert(e.id.as_str()))
+            .collect();
+        let mut checked = members.iter()
+            .filter(|e| self.states.get(&e.id).map(|s| s.checked).unwrap_or(e.checked))
+            .map(|e| e.value.clone());
+        Some(match result {
+            InteractionResult::Toggle { .. } => InteractionResult::CheckboxGroupChange { id: group.clone(), values: checked.collect() },
+            _ => InteractionResult::RadioGroupChange { id: group.clone(), value: checked.next().unwrap_or_default() },
+        })
+    }
+    
+    /// 按下：之后按住的元素在 hover-start-time 后应用 hover-class
+    pub fn press_hover(&mut self, x: f32, y: f32) {
+        self.hover_seq += 1;
+        self.hover_press = Some(HoverPress { x, y, seq: self.hover_seq, released: false });
+    }
+    
+    /// 松开：hover-class 再保留 hover-stay-time
+    pub fn release_hover(&mut self) {
+        if let Some(press) = &mut self.hover_press {
+            press.released = true;
+        }
+    }
+    
+    /// 按下后开始滑动（滚动）时取消 hover-class
+    pub fn cancel_hover(&mut self) {
+        self.hover_press = None;
+    }
+    
+    pub fn hover_press(&self) -> Option<HoverPress> {
+        self.hover_press
+    }
+    
+    /// 记录按钮的 open-type（share / getUserInfo / getPhoneNumber 等）
+    pub fn set_open_type(&mut self, id: &str, open_type: &str) {
+        self.open_types.insert(id.to_string(), open_type.to_string());
+    }
+    
+    /// 设置了 open-type 的按钮被点击后的开放能力事件，与 bindtap 一起触发
+    pub fn open_type_event(&self, result: &InteractionResult) -> Option<InteractionResult> {
+        let InteractionResult::ButtonClick { id, .. } = result else { return None };
+        let open_type = self.open_types.get(id)?;
+        Some(InteractionResult::OpenType { id: id.clone(), open_type: open_type.clone() })
+    }
+    
+    /// 记录输入框的 type / password / maxlength，聚焦时用于过滤字符、限制长度和遮盖密码
+    pub fn set_input_attrs(&mut self, id: &str, attrs: InputAttrs) {
+        self.input_attrs.insert(id.to_string(), attrs);
+    }
+    
+    /// 点击位置对应的字符边界，超出文本范围时取最近的行
+    fn text_position(&self, element: &InteractiveElement, x: f32, y: f32) -> Option<usize> {
+        let (lines, line_height) = self.text_layouts.get(&element.id)?;
+        if lines.is_empty() || *line_height <= 0.0 {
+            return None;
+        }
+        let row = ((y - element.bounds.y) / line_height).floor().max(0.0) as usize;
+        Some(lines[row.min(lines.len() - 1)].pos_at(x - element.bounds.x))
+    }
+    
+    /// 在可选中文本上按下时记录锚点，拖动后开始选择。
+    /// y 为视口坐标，先匹配 fixed 元素，再按页面坐标（加上 scroll_pos）匹配
+    pub fn begin_text_drag(&mut self, x: f32, y: f32, scroll_pos: f32) -> bool {
+        let page_y = y + scroll_pos;
+        let hit = self.hit_test(x, y).filter(|e| e.is_fixed).map(|e| (e.clone(), y))
+            .or_else(|| self.hit_test(x, page_y).filter(|e| !e.is_fixed).map(|e| (e.clone(), page_y)));
+        let Some((element, y)) = hit else { return false };
+        if element.interaction_type != InteractionType::Text {
+            return false;
+        }
+        let Some(anchor) = self.text_position(&element, x, y) else { return false };
+        self.text_selection = None;
+        self.text_drag = Some(TextDrag { id: element.id, anchor, is_fixed: element.is_fixed });
+        true
+    }
+    
+    /// 拖动选择：选中锚点到当前位置之间的文本，返回选择是否变化
+    pub fn update_text_drag(&mut self, x: f32, y: f32, scroll_pos: f32) -> bool {
+        let Some(drag) = self.text_drag.clone() else { return false };
+        let Some(element) = self.elements.iter().rev().find(|e| e.id == drag.id).cloned() else { return false };
+        let y = if drag.is_fixed { y } else { y + scroll_pos };
+        let Some(pos) = self.text_position(&element, x, y) else { return false };
+        let selection = (pos != drag.anchor).then(|| TextSelection {
+            id: drag.id,
+            text: element.value,
+            start: pos.min(drag.anchor),
+            end: pos.max(drag.anchor),
+        });
+        if selection == self.text_selection {
+            return false;
+        }
+        self.text_selection = selection;
+        true
+    }
+    
+    /// 结束拖动选择，返回是否选中了文本（选中时松开不再当作点击）
+    pub fn end_text_drag(&mut self) -> bool {
+        self.text_drag.take().is_some() && self.text_selection.is_some()
+    }
+    
+    /// 是否正在可选中文本上拖动
+    pub fn is_dragging_text(&self) -> bool {
+        self.text_drag.is_some()
+    }
+    
+    /// 复制可选中文本的选中部分
+    pub fn copy_text_selection(&self) -> Option<InteractionResult> {
+        let selection = self.text_selection.as_ref()?;
+        let text = selection.selected_text();
+        if text.is_empty() {
+            return None;
+        }
+        Some(InteractionResult::CopyText { text })
+    }
+    
+    /// 处理点击事件（点击会取消可选中文本的选择）
     pub fn handle_click(&mut self, x: f32, y: f32) -> Option<InteractionResult> {
+        self.text_selection = None;
         let element = self.hit_test(x, y)?.clone();
         
         match element.interaction_type {
@@ -300,11 +805,12 @@ impl InteractionManager {
                 })
             }
             InteractionType::Radio => {
-                // Radio 是互斥的，需要取消同一组内其他 radio 的选中状态
-                // 简单实现：取消所有其他 radio 的选中状态（假设页面上只有一组 radio）
-                // 更好的实现应该基于 radio-group 或父容器
+                // Radio 是互斥的，取消同一 radio-group 内其他 radio 的选中状态
+                // （不在 radio-group 内的 radio 之间互斥）
+                let group = self.groups.get(&element.id);
                 let radio_ids: Vec<String> = self.elements.iter()
                     .filter(|e| e.interaction_type == InteractionType::Radio && e.id != element.id)
+                    .filter(|e| self.groups.get(&e.id) == group)
                     .map(|e| e.id.clone())
                     .collect();
                 
@@ -326,69 +832,33 @@ impl InteractionManager {
                 })
             }
             InteractionType::Slider => {
-                let progress = ((x - element.bounds.x) / element.bounds.width).clamp(0.0, 1.0);
-                let value = element.min + progress * (element.max - element.min);
-                
-                self.states.insert(element.id.clone(), ComponentState {
-                    checked: false,
-                    value: format!("{}", value as i32),
-                });
-                
-                self.dragging_slider = Some(DraggingSlider {
+                let attrs = self.slider_attrs.get(&element.id).copied()
+                    .unwrap_or(SliderAttrs { step: 1.0, track: (0.0, element.bounds.width) });
+                let slider = DraggingSlider {
                     id: element.id.clone(),
                     bounds: element.bounds,
                     min: element.min,
                     max: element.max,
+                    attrs,
+                };
+                let value = slider.value_at(x);
+                
+                self.states.insert(element.id.clone(), ComponentState {
+                    checked: false,
+                    value: format_slider_value(value),
                 });
+                self.dragging_slider = Some(slider);
                 
                 Some(InteractionResult::SliderChange {
                     id: element.id,
-                    value: value as i32,
+                    value,
                 })
             }
-            InteractionType::Input => {
-                // 获取当前值（如果没有状态，使用空字符串而不是 element.value）
-                let current_value = self.states.get(&element.id)
-                    .map(|s| s.value.clone())
-                    .unwrap_or_else(|| {
-                        // 如果 element.value 是空的，说明没有初始值
-                        if element.value.is_empty() {
-                            String::new()
-                        } else {
-                            element.value.clone()
-                        }
-                    });
-                
-                // 初始化状态（如果还没有）
-                if !self.states.contains_key(&element.id) {
-                    self.states.insert(element.id.clone(), ComponentState {
-                        checked: false,
-                        value: current_value.clone(),
-                    });
-                }
-                
-                self.focused_input = Some(FocusedInput {
-                    id: element.id.clone(),
-                    value: current_value.clone(),
-                    cursor_pos: current_value.chars().count(),
-                    selection_start: None,
-                    selection_end: None,
-                    is_password: false,
-                    bounds: element.bounds,
-                    text_offset: 0.0, // 初始偏移为0，会在渲染时更新
-                    maxlength: 140, // 默认值，会在外部更新
-                    input_type: "text".to_string(), // 默认值，会在外部更新
-                });
-
+            InteractionType::Input | InteractionType::Textarea => {
                 // 记录点击位置用于后续计算光标位置
                 let click_x = x - element.bounds.x;
-
-                Some(InteractionResult::Focus {
-                    id: element.id,
-                    bounds: element.bounds,
-                    click_x,
-                    is_fixed: element.is_fixed,
-                })
+                let click_y = y - element.bounds.y;
+                Some(self.focus_element(element, click_x, click_y))
             }
             InteractionType::Button => {
                 // 按钮点击不需要在这里触发动画，按下状态由鼠标按下/松开控制
@@ -398,24 +868,29 @@ impl InteractionManager {
                 })
             }
             InteractionType::View => None,
+            InteractionType::Text => None,
             InteractionType::ScrollArea => None,
+            InteractionType::Movable => None,
+            InteractionType::Video => Some(InteractionResult::VideoTap {
+                id: element.id,
+                src: element.value,
+            }),
         }
     }
     
     /// 处理鼠标移动（用于滑块拖动）
     pub fn handle_mouse_move(&mut self, x: f32, _y: f32) -> Option<InteractionResult> {
         if let Some(ref slider) = self.dragging_slider {
-            let progress = ((x - slider.bounds.x) / slider.bounds.width).clamp(0.0, 1.0);
-            let value = slider.min + progress * (slider.max - slider.min);
+            let value = slider.value_at(x);
             
             self.states.insert(slider.id.clone(), ComponentState {
                 checked: false,
-                value: format!("{}", value as i32),
+                value: format_slider_value(value),
             });
             
             return Some(InteractionResult::SliderChange {
                 id: slider.id.clone(),
-                value: value as i32,
+                value,
             });
         }
         None
@@ -429,9 +904,23 @@ impl InteractionManager {
         None
     }
     
+    /// 更新输入法组合文本（空字符串表示取消组合），返回是否有变化
+    pub fn set_preedit(&mut self, text: &str) -> bool {
+        match self.focused_input.as_mut() {
+            Some(input) if input.preedit != text => {
+                input.preedit = text.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+    
     /// 处理键盘输入
     pub fn handle_key_input(&mut self, key: KeyInput) -> Option<InteractionResult> {
         let input = self.focused_input.as_mut()?;
+        if matches!(key, KeyInput::Commit(_)) {
+            input.preedit.clear();
+        }
         
         match key {
             KeyInput::Char(c) => {
@@ -542,6 +1031,14 @@ impl InteractionManager {
                 }
                 None
             }
+            KeyInput::Up => {
+                input.move_lines(-1);
+                None
+            }
+            KeyInput::Down => {
+                input.move_lines(1);
+                None
+            }
             KeyInput::Home => {
                 input.clear_selection();
                 input.cursor_pos = 0;
@@ -557,7 +1054,10 @@ impl InteractionManager {
                 None
             }
             KeyInput::Copy => {
-                // 返回选中的文本用于复制
+                // 返回选中的文本用于复制（密码框不允许复制）
+                if input.is_password {
+                    return None;
+                }
                 if let Some((start, end)) = input.get_selection_range() {
                     let selected: String = input.value.chars().skip(start).take(end - start).collect();
                     return Some(InteractionResult::CopyText { text: selected });
@@ -565,7 +1065,10 @@ impl InteractionManager {
                 None
             }
             KeyInput::Cut => {
-                // 剪切：复制并删除
+                // 剪切：复制并删除（密码框不允许剪切）
+                if input.is_password {
+                    return None;
+                }
                 if let Some((start, end)) = input.get_selection_range() {
                     let selected: String = input.value.chars().skip(start).take(end - start).collect();
                     input.delete_selection();
@@ -583,7 +1086,7 @@ impl InteractionManager {
                 }
                 None
             }
-            KeyInput::Paste(text) => {
+            KeyInput::Paste(text) | KeyInput::Commit(text) => {
                 // 过滤不符合输入类型的字符
                 let filtered_text: String = text.chars()
                     .filter(|&c| input.validate_char(c))
@@ -687,14 +1190,19 @@ impl InteractionManager {
                 input.selection_end = Some(len);
                 None
             }
+            // textarea 中回车插入换行
+            KeyInput::Enter if input.multiline && input.confirm_type == "return" => {
+                self.handle_key_input(KeyInput::Char('\n'))
+            }
             KeyInput::Enter => {
-                // Enter 键触发 confirm 事件
+                // Enter 键触发 confirm 事件，confirm-hold 时保持聚焦
                 let id = input.id.clone();
                 let value = input.value.clone();
-                // 注意：confirm 事件不一定要失焦，取决于 confirm-hold 属性
-                // 这里默认失焦，如果需要保持焦点，可以在外部处理
-                self.focused_input = None;
-                Some(InteractionResult::InputConfirm { id, value })
+                let hold = input.confirm_hold;
+                if !hold {
+                    self.focused_input = None;
+                }
+                Some(InteractionResult::InputConfirm { id, value, hold })
             }
             KeyInput::Escape => {
                 let id = input.id.clone();
@@ -705,6 +1213,88 @@ impl InteractionManager {
         }
     }
     
+    /// 用 bindinput 返回的值替换输入框内容，光标不超过新值末尾
+    pub fn replace_input_value(&mut self, id: &str, value: String) {
+        if let Some(input) = self.focused_input.as_mut().filter(|f| f.id == id) {
+            input.cursor_pos = input.cursor_pos.min(value.chars().count());
+            input.value = value.clone();
+            input.clear_selection();
+        }
+        self.states.insert(id.to_string(), ComponentState { checked: false, value });
+    }
+    
+    /// 聚焦输入框，光标放在末尾；click_x / click_y 为点击位置（相对输入框左上角）
+    fn focus_element(&mut self, element: InteractiveElement, click_x: f32, click_y: f32) -> InteractionResult {
+        // 获取当前值（如果没有状态，使用空字符串而不是 element.value）
+        let current_value = self.states.get(&element.id)
+            .map(|s| s.value.clone())
+            .unwrap_or_else(|| {
+                // 如果 element.value 是空的，说明没有初始值
+                if element.value.is_empty() {
+                    String::new()
+                } else {
+                    element.value.clone()
+                }
+            });
+        
+        // 初始化状态（如果还没有）
+        if !self.states.contains_key(&element.id) {
+            self.states.insert(element.id.clone(), ComponentState {
+                checked: false,
+                value: current_value.clone(),
+            });
+        }
+        
+        let multiline = element.interaction_type == InteractionType::Textarea;
+        let attrs = self.input_attrs.get(&element.id).cloned().unwrap_or_else(|| InputAttrs {
+            confirm_type: if multiline { "return" } else { "done" }.to_string(),
+            ..Default::default()
+        });
+        self.focused_input = Some(FocusedInput {
+            id: element.id.clone(),
+            value: current_value.clone(),
+            cursor_pos: current_value.chars().count(),
+            selection_start: None,
+            selection_end: None,
+            is_password: attrs.password,
+            bounds: element.bounds,
+            text_offset: 0.0, // 初始偏移为0，会在渲染时更新
+            maxlength: attrs.maxlength,
+            input_type: attrs.input_type,
+            multiline,
+            lines: Vec::new(),
+            line_height: 0.0,
+            scroll_top: 0.0,
+            preedit: String::new(),
+            confirm_type: attrs.confirm_type,
+            confirm_hold: attrs.confirm_hold,
+        });
+        
+        InteractionResult::Focus {
+            id: element.id,
+            bounds: element.bounds,
+            click_x,
+            click_y,
+            is_fixed: element.is_fixed,
+        }
+    }
+    
+    /// 按 ID 聚焦输入框（focus 属性），已聚焦时不重复聚焦；聚焦另一个输入框前先让当前输入框失焦
+    pub fn focus_input(&mut self, id: &str) -> Vec<InteractionResult> {
+        if self.focused_input.as_ref().is_some_and(|f| f.id == id) {
+            return Vec::new();
+        }
+        let Some(element) = self.elements.iter()
+            .find(|e| e.id == id && !e.disabled && matches!(e.interaction_type, InteractionType::Input | InteractionType::Textarea))
+            .cloned() else {
+            return Vec::new();
+        };
+        let mut results: Vec<InteractionResult> = self.blur_input().into_iter().collect();
+        let (click_x, click_y) = (element.bounds.width, element.bounds.height / 2.0);
+        results.push(self.focus_element(element, click_x, click_y));
+        results
+    }
+    
     /// 取消输入框聚焦
     pub fn blur_input(&mut self) -> Option<InteractionResult> {
         if let Some(input) = self.focused_input.take() {
@@ -716,6 +1306,16 @@ impl InteractionManager {
         None
     }
     
+    /// 点击位置不在当前聚焦的输入框上时失焦（点击空白处、其他组件或另一个输入框）
+    /// 与微信一致，返回的 InputBlur 携带输入框的最终值，用于触发 bindblur
+    pub fn blur_if_outside(&mut self, x: f32, y: f32) -> Option<InteractionResult> {
+        let focused_id = self.focused_input.as_ref()?.id.clone();
+        if self.hit_test(x, y).is_some_and(|e| e.id == focused_id) {
+            return None;
+        }
+        self.blur_input()
+    }
+    
     /// 是否有输入框聚焦
     pub fn has_focused_input(&self) -> bool {
         self.focused_input.is_some()
@@ -731,13 +1331,23 @@ impl InteractionManager {
         self.states.clear();
         self.focused_input = None;
         self.dragging_slider = None;
+        self.hover_press = None;
         self.scroll_controllers.clear();
         self.dragging_scroll_area = None;
+        self.movable_views.clear();
+        self.dragging_movable = None;
         self.pressed_button = None;
         self.click_animations.clear();
         self.elements.clear();
         self.is_selecting_text = false;
         self.selection_anchor = None;
+        self.text_selection = None;
+        self.text_layouts.clear();
+        self.groups.clear();
+        self.open_types.clear();
+        self.text_drag = None;
+        self.input_attrs.clear();
+        self.slider_attrs.clear();
     }
     
     /// 准备文本选择（鼠标按下时调用）
@@ -864,6 +1474,8 @@ pub enum KeyInput {
     Delete,
     Left,
     Right,
+    Up,             // 多行输入框中移到上一行
+    Down,           // 多行输入框中移到下一行
     Home,
     End,
     Enter,
@@ -872,6 +1484,7 @@ pub enum KeyInput {
     Copy,           // Ctrl+C
     Cut,            // Ctrl+X
     Paste(String),  // Ctrl+V
+    Commit(String), // IME 提交，与粘贴一样按 maxlength 截断
     ShiftLeft,      // Shift+Left (扩展选择)
     ShiftRight,     // Shift+Right
     ShiftHome,      // Shift+Home
@@ -883,13 +1496,310 @@ pub enum KeyInput {
 pub enum InteractionResult {
     Toggle { id: String, checked: bool },
     Select { id: String, value: String },
-    SliderChange { id: String, value: i32 },
+    SliderChange { id: String, value: f32 },
     SliderEnd { id: String },
-    Focus { id: String, bounds: Rect, click_x: f32, is_fixed: bool },
+    Focus { id: String, bounds: Rect, click_x: f32, click_y: f32, is_fixed: bool },
     InputChange { id: String, value: String },
     InputBlur { id: String, value: String },
-    InputConfirm { id: String, value: String }, // 按下回车/完成键
+    InputConfirm { id: String, value: String, hold: bool }, // 按下回车/完成键，hold 为 true 时保持聚焦
     ButtonClick { id: String, bounds: Rect },
     CopyText { text: String },
     CutText { text: String, id: String, value: String },
+    /// movable-view 位置变化，source 为 touch / friction / out-of-bounds / 空（setData）
+    MovableChange { id: String, x: f32, y: f32, source: String },
+    /// 点击 video 画面（未被 cover-view 遮挡），src 为视频地址
+    VideoTap { id: String, src: String },
+    /// checkbox-group 的 change，values 为选中的复选框的 value
+    CheckboxGroupChange { id: String, values: Vec<String> },
+    /// radio-group 的 change，value 为选中的单选框的 value
+    RadioGroupChange { id: String, value: String },
+    /// 设置了 open-type 的按钮被点击，由宿主触发对应的开放能力事件（bindgetuserinfo 等）
+    OpenType { id: String, open_type: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(interaction_type: InteractionType, id: &str, bounds: Rect) -> InteractiveElement {
+        InteractiveElement {
+            interaction_type,
+            id: id.to_string(),
+            bounds,
+            checked: false,
+            value: String::new(),
+            disabled: false,
+            min: 0.0,
+            max: 0.0,
+            content_height: 0.0,
+            viewport_height: 0.0,
+            is_fixed: false,
+            scroll_x: false,
+        }
+    }
+
+    fn setup() -> InteractionManager {
+        let mut im = InteractionManager::new();
+        im.register_element(element(InteractionType::Input, "name", Rect::new(10.0, 10.0, 200.0, 40.0)));
+        im.register_element(element(InteractionType::Input, "phone", Rect::new(10.0, 60.0, 200.0, 40.0)));
+        im.register_element(element(InteractionType::Button, "submit", Rect::new(10.0, 110.0, 200.0, 40.0)));
+        im
+    }
+
+    #[test]
+    fn test_tap_outside_blurs_with_typed_value() {
+        let mut im = setup();
+        assert!(matches!(im.handle_click(50.0, 30.0), Some(InteractionResult::Focus { .. })));
+        for c in "abc".chars() {
+            im.handle_key_input(KeyInput::Char(c));
+        }
+
+        // 点击聚焦的输入框本身不会失焦
+        assert!(im.blur_if_outside(100.0, 30.0).is_none());
+        assert!(im.has_focused_input());
+
+        // 点击空白处失焦，并带上最终值
+        match im.blur_if_outside(300.0, 400.0) {
+            Some(InteractionResult::InputBlur { id, value }) => {
+                assert_eq!(id, "name");
+                assert_eq!(value, "abc");
+            }
+            other => panic!("expected blur, got {:?}", other),
+        }
+        assert!(!im.has_focused_input());
+        assert!(im.blur_if_outside(300.0, 400.0).is_none());
+    }
+
+    #[test]
+    fn test_tap_other_component_blurs() {
+        let mut im = setup();
+        im.handle_click(50.0, 30.0);
+
+        // 点击按钮：先失焦再处理点击
+        assert!(matches!(im.blur_if_outside(50.0, 130.0), Some(InteractionResult::InputBlur { ref id, .. }) if id == "name"));
+        assert!(matches!(im.handle_click(50.0, 130.0), Some(InteractionResult::ButtonClick { .. })));
+
+        // 从一个输入框切换到另一个输入框，旧输入框失焦
+        im.handle_click(50.0, 30.0);
+        assert!(matches!(im.blur_if_outside(50.0, 80.0), Some(InteractionResult::InputBlur { ref id, .. }) if id == "name"));
+        im.handle_click(50.0, 80.0);
+        assert_eq!(im.focused_input.as_ref().map(|f| f.id.as_str()), Some("phone"));
+    }
+
+    #[test]
+    fn test_long_press_selects_all_selectable_text() {
+        let mut im = setup();
+        let mut text = element(InteractionType::Text, "address", Rect::new(10.0, 160.0, 200.0, 20.0));
+        text.value = "广东省深圳市南山区".into();
+        im.register_element(text);
+
+        // 不可选中的元素上长按没有效果
+        assert!(im.handle_long_press(50.0, 130.0).is_none());
+        assert!(im.text_selection.is_none());
+
+        match im.handle_long_press(50.0, 170.0) {
+            Some(InteractionResult::CopyText { text }) => assert_eq!(text, "广东省深圳市南山区"),
+            other => panic!("expected copy, got {:?}", other),
+        }
+        let selection = im.text_selection.clone().unwrap();
+        assert_eq!((selection.id.as_str(), selection.start, selection.end), ("address", 0, 9));
+
+        // 点击取消选择
+        im.handle_click(300.0, 400.0);
+        assert!(im.text_selection.is_none());
+    }
+
+    #[test]
+    fn test_drag_selects_part_of_selectable_text() {
+        let mut im = setup();
+        let mut text = element(InteractionType::Text, "article", Rect::new(10.0, 160.0, 40.0, 40.0));
+        text.value = "abcdefg".into();
+        im.register_element(text);
+        // 两行 "abcd" / "efg"，每个字符宽 10，行高 20
+        im.set_text_layout("article", TextLine::wrap("abcdefg", 40.0, |_| 10.0), 20.0);
+        
+        // 页面滚动 100 后在 b 和 c 之间按下，还没有选择
+        assert!(im.begin_text_drag(30.0, 65.0, 100.0));
+        assert!(im.text_selection.is_none());
+        
+        // 拖到第二行的 f 之后
+        assert!(im.update_text_drag(30.0, 85.0, 100.0));
+        assert!(!im.update_text_drag(31.0, 85.0, 100.0));
+        let selection = im.text_selection.clone().unwrap();
+        assert_eq!((selection.start, selection.end), (2, 6));
+        match im.copy_text_selection() {
+            Some(InteractionResult::CopyText { text }) => assert_eq!(text, "cdef"),
+            other => panic!("expected copy, got {:?}", other),
+        }
+        assert!(im.end_text_drag());
+        assert!(!im.is_dragging_text());
+        
+        // 不在可选中文本上按下不会开始拖动
+        assert!(!im.begin_text_drag(50.0, 30.0, 0.0));
+    }
+    
+    #[test]
+    fn test_ime_commit_truncates_at_maxlength() {
+        let mut im = setup();
+        im.handle_click(50.0, 30.0);
+        im.focused_input.as_mut().unwrap().maxlength = 6;
+        for c in "ab".chars() {
+            im.handle_key_input(KeyInput::Char(c));
+        }
+        im.handle_key_input(KeyInput::Left);
+
+        // 在 a 和 b 之间提交超长的候选词，只保留能放下的部分
+        match im.handle_key_input(KeyInput::Commit("你好世界朋友".into())) {
+            Some(InteractionResult::InputChange { value, .. }) => assert_eq!(value, "a你好世界b"),
+            other => panic!("expected input change, got {:?}", other),
+        }
+        assert_eq!(im.focused_input.as_ref().unwrap().cursor_pos, 5);
+
+        // 已满时不再接受提交
+        assert!(im.handle_key_input(KeyInput::Commit("再".into())).is_none());
+    }
+    
+    #[test]
+    fn test_textarea_newline_and_vertical_navigation() {
+        let mut im = setup();
+        im.register_element(element(InteractionType::Textarea, "note", Rect::new(10.0, 160.0, 200.0, 80.0)));
+        im.handle_click(50.0, 200.0);
+        assert!(im.focused_input.as_ref().unwrap().multiline);
+        for c in "abc".chars() {
+            im.handle_key_input(KeyInput::Char(c));
+        }
+        
+        // 回车插入换行而不是确认
+        match im.handle_key_input(KeyInput::Enter) {
+            Some(InteractionResult::InputChange { value, .. }) => assert_eq!(value, "abc\n"),
+            other => panic!("expected input change, got {:?}", other),
+        }
+        im.handle_key_input(KeyInput::Char('d'));
+        im.handle_key_input(KeyInput::Char('e'));
+        
+        // 尚未排版时按换行符分行，按列对齐
+        im.handle_key_input(KeyInput::Up);
+        assert_eq!(im.focused_input.as_ref().unwrap().cursor_pos, 2);
+        im.handle_key_input(KeyInput::Down);
+        assert_eq!(im.focused_input.as_ref().unwrap().cursor_pos, 6);
+        
+        // 排版后在视觉行之间移动："ab" / "c" / "de"
+        let input = im.focused_input.as_mut().unwrap();
+        input.lines = TextLine::wrap(&input.value, 20.0, |_| 10.0);
+        input.line_height = 20.0;
+        assert_eq!(input.lines.iter().map(|l| (l.start, l.end())).collect::<Vec<_>>(), vec![(0, 2), (2, 3), (4, 6)]);
+        im.handle_key_input(KeyInput::Up);
+        assert_eq!(im.focused_input.as_ref().unwrap().cursor_pos, 3);
+        im.handle_key_input(KeyInput::Up);
+        assert_eq!(im.focused_input.as_ref().unwrap().cursor_pos, 1);
+        im.handle_key_input(KeyInput::Up);
+        assert_eq!(im.focused_input.as_ref().unwrap().cursor_pos, 0);
+        
+        // 点击第三行靠右的位置
+        assert_eq!(im.focused_input.as_ref().unwrap().cursor_at(16.0, 45.0), 6);
+        
+        // 单行输入框的回车仍然是确认
+        im.handle_click(50.0, 30.0);
+        assert!(matches!(im.handle_key_input(KeyInput::Enter), Some(InteractionResult::InputConfirm { .. })));
+    }
+    
+    #[test]
+    fn test_input_type_filters_and_password_masks() {
+        let mut im = setup();
+        im.set_input_attrs("name", InputAttrs { input_type: "number".to_string(), ..Default::default() });
+        im.set_input_attrs("phone", InputAttrs { password: true, ..Default::default() });
+        
+        im.handle_click(50.0, 30.0);
+        for c in "1a2.b3".chars() {
+            im.handle_key_input(KeyInput::Char(c));
+        }
+        assert_eq!(im.focused_input.as_ref().unwrap().value, "123");
+        
+        // 密码框保留真实值，显示为等长圆点，且不允许复制
+        im.handle_click(50.0, 80.0);
+        for c in "pw1".chars() {
+            im.handle_key_input(KeyInput::Char(c));
+        }
+        im.handle_key_input(KeyInput::SelectAll);
+        let input = im.focused_input.as_ref().unwrap();
+        assert_eq!(input.value, "pw1");
+        assert_eq!(input.display_value(), "•••");
+        assert!(im.handle_key_input(KeyInput::Copy).is_none());
+        
+        // 按圆点宽度计算点击位置：每个圆点 10px，点在第二个圆点之后
+        let input = im.focused_input.as_ref().unwrap();
+        assert_eq!(input.cursor_from_point(12.0 + 22.0, 10.0, 1.0, |c| if c == '•' { 10.0 } else { 30.0 }), 2);
+    }
+    
+    #[test]
+    fn test_typing_past_maxlength_is_clamped() {
+        let mut im = setup();
+        im.set_input_attrs("name", InputAttrs { maxlength: 3, ..Default::default() });
+        im.handle_click(50.0, 30.0);
+        for c in "abcd".chars() {
+            im.handle_key_input(KeyInput::Char(c));
+        }
+        assert_eq!(im.focused_input.as_ref().unwrap().value, "abc");
+        assert!(im.handle_key_input(KeyInput::Char('e')).is_none());
+        
+        // bindinput 返回的新值直接写回，光标收回到末尾以内
+        im.replace_input_value("name", "A".to_string());
+        let input = im.focused_input.as_ref().unwrap();
+        assert_eq!((input.value.as_str(), input.cursor_pos), ("A", 1));
+        assert_eq!(im.get_state("name").unwrap().value, "A");
+    }
+    
+    #[test]
+    fn test_enter_confirms_and_respects_confirm_hold() {
+        let mut im = setup();
+        im.set_input_attrs("phone", InputAttrs { confirm_type: "search".to_string(), confirm_hold: true, ..Default::default() });
+        im.register_element(element(InteractionType::Textarea, "note", Rect::new(10.0, 160.0, 200.0, 80.0)));
+        im.set_input_attrs("note", InputAttrs { confirm_type: "send".to_string(), ..Default::default() });
+        
+        im.handle_click(50.0, 30.0);
+        im.handle_key_input(KeyInput::Char('a'));
+        match im.handle_key_input(KeyInput::Enter) {
+            Some(InteractionResult::InputConfirm { id, value, hold }) => assert_eq!((id.as_str(), value.as_str(), hold), ("name", "a", false)),
+            other => panic!("expected confirm, got {:?}", other),
+        }
+        assert!(!im.has_focused_input());
+        
+        // confirm-hold 时确认后仍保持聚焦
+        im.handle_click(50.0, 80.0);
+        assert!(matches!(im.handle_key_input(KeyInput::Enter), Some(InteractionResult::InputConfirm { hold: true, .. })));
+        assert_eq!(im.focused_input.as_ref().unwrap().id, "phone");
+        
+        // textarea 设置 confirm-type 后回车确认而不是换行
+        im.handle_click(50.0, 200.0);
+        assert!(matches!(im.handle_key_input(KeyInput::Enter), Some(InteractionResult::InputConfirm { .. })));
+    }
+
+    #[test]
+    fn test_slider_drag_snaps_to_step() {
+        let mut im = InteractionManager::new();
+        let mut slider = element(InteractionType::Slider, "volume", Rect::new(0.0, 0.0, 220.0, 28.0));
+        slider.max = 100.0;
+        im.register_element(slider);
+        // 轨道从 10 到 210，每 1px 对应 0.5
+        im.set_slider_attrs("volume", SliderAttrs { step: 10.0, track: (10.0, 210.0) });
+
+        assert!(matches!(im.handle_click(66.0, 14.0), Some(InteractionResult::SliderChange { value, .. }) if value == 30.0));
+        assert_eq!(im.get_state("volume").unwrap().value, "30");
+
+        // 拖动时同样按步长取整，超出轨道的位置夹到端点
+        assert!(matches!(im.handle_mouse_move(125.0, 14.0), Some(InteractionResult::SliderChange { value, .. }) if value == 60.0));
+        assert!(matches!(im.handle_mouse_move(300.0, 14.0), Some(InteractionResult::SliderChange { value, .. }) if value == 100.0));
+        assert!(matches!(im.handle_mouse_move(-20.0, 14.0), Some(InteractionResult::SliderChange { value, .. }) if value == 0.0));
+
+        // step 不能整除 max - min 时，最后一步不超过 max
+        im.handle_mouse_release();
+        im.set_slider_attrs("volume", SliderAttrs { step: 40.0, track: (10.0, 210.0) });
+        assert!(matches!(im.handle_click(210.0, 14.0), Some(InteractionResult::SliderChange { value, .. }) if value == 80.0));
+
+        // 小数步长原样上报，不取整
+        im.handle_mouse_release();
+        im.set_slider_attrs("volume", SliderAttrs { step: 0.5, track: (10.0, 210.0) });
+        assert!(matches!(im.handle_click(15.0, 14.0), Some(InteractionResult::SliderChange { value, .. }) if value == 2.5));
+        assert_eq!(im.get_state("volume").unwrap().value, "2.5");
+    }
 }
@@ -10,6 +10,7 @@ mod layout;
 pub mod interaction;
 pub mod scroll_controller;
 pub mod scroll_cache;
+pub mod movable;
 
 pub use component::{Component, ComponentId, ComponentTree, Style};
 pub use view::View;
@@ -19,5 +20,6 @@ pub use image::Image;
 pub use scroll_view::ScrollView;
 pub use layout::{Layout, FlexDirection, FlexAlign};
 pub use interaction::{InteractionManager, InteractiveElement, InteractionType, InteractionResult, KeyInput, ComponentState, calculate_cursor_position};
-pub use scroll_controller::ScrollController;
+pub use scroll_controller::{ScrollController, ScrollAxis};
 pub use scroll_cache::{ScrollViewCache, ScrollCacheManager};
+pub use movable::{MovableView, MovableConfig, MovableDirection};
@@ -0,0 +1,217 @@
+//! movable-view 拖动状态
+//!
+//! 每个轴使用一个 ScrollController 复用拖动速度和惯性计算。
+//! ScrollController 的位置随手指反向变化，因此传入的坐标取负值，位置即为平移量。
+
+use super::scroll_controller::ScrollController;
+
+/// movable-view 可移动方向
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MovableDirection {
+    All,
+    Horizontal,
+    Vertical,
+    #[default]
+    None,
+}
+
+impl MovableDirection {
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "all" => MovableDirection::All,
+            "horizontal" => MovableDirection::Horizontal,
+            "vertical" => MovableDirection::Vertical,
+            _ => MovableDirection::None,
+        }
+    }
+
+    pub fn allows_x(&self) -> bool {
+        matches!(self, MovableDirection::All | MovableDirection::Horizontal)
+    }
+
+    pub fn allows_y(&self) -> bool {
+        matches!(self, MovableDirection::All | MovableDirection::Vertical)
+    }
+}
+
+/// movable-view 的配置（每次渲染时由属性和布局计算）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovableConfig {
+    pub direction: MovableDirection,
+    /// 可移动范围：movable-area 尺寸减去 movable-view 尺寸（逻辑像素）
+    pub range_x: f32,
+    pub range_y: f32,
+    /// x/y 属性
+    pub x: f32,
+    pub y: f32,
+    /// 松手后是否有惯性
+    pub inertia: bool,
+    /// 拖动时是否可以超出范围（松手后回弹）
+    pub out_of_bounds: bool,
+    /// x/y 属性变化时是否使用动画
+    pub animation: bool,
+}
+
+/// 单个 movable-view 的状态
+pub struct MovableView {
+    config: MovableConfig,
+    x: ScrollController,
+    y: ScrollController,
+    /// 最近一次上报 bindchange 的位置
+    last_reported: (f32, f32),
+    /// 位置变化的来源：touch（拖动）、friction（惯性）、空字符串（setData）
+    source: &'static str,
+}
+
+impl MovableView {
+    pub fn new(config: MovableConfig) -> Self {
+        let mut view = Self {
+            config,
+            x: ScrollController::new(config.range_x, 0.0),
+            y: ScrollController::new(config.range_y, 0.0),
+            last_reported: (0.0, 0.0),
+            source: "",
+        };
+        view.apply_config();
+        view.x.scroll_to(config.x, false);
+        view.y.scroll_to(config.y, false);
+        view.last_reported = view.position();
+        view
+    }
+
+    /// 更新配置，x/y 属性变化时移动到新位置
+    pub fn update_config(&mut self, config: MovableConfig) {
+        let old = self.config;
+        self.config = config;
+        self.apply_config();
+        if (config.x - old.x).abs() > f32::EPSILON || (config.y - old.y).abs() > f32::EPSILON {
+            self.source = "";
+            self.x.scroll_to(config.x, config.animation);
+            self.y.scroll_to(config.y, config.animation);
+        }
+    }
+
+    fn apply_config(&mut self) {
+        let range_x = if self.config.direction.allows_x() { self.config.range_x } else { 0.0 };
+        let range_y = if self.config.direction.allows_y() { self.config.range_y } else { 0.0 };
+        self.x.update_content_height(range_x, 0.0);
+        self.y.update_content_height(range_y, 0.0);
+        self.x.set_overscroll(self.config.out_of_bounds);
+        self.y.set_overscroll(self.config.out_of_bounds);
+    }
+
+    /// 当前平移量（逻辑像素）
+    pub fn position(&self) -> (f32, f32) {
+        (self.x.get_position(), self.y.get_position())
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.x.is_dragging || self.y.is_dragging
+    }
+
+    pub fn begin_drag(&mut self, x: f32, y: f32, timestamp: u64) {
+        self.source = "touch";
+        if self.config.direction.allows_x() { self.x.begin_drag(-x, timestamp); }
+        if self.config.direction.allows_y() { self.y.begin_drag(-y, timestamp); }
+    }
+
+    pub fn update_drag(&mut self, x: f32, y: f32, timestamp: u64) {
+        self.x.update_drag(-x, timestamp);
+        self.y.update_drag(-y, timestamp);
+    }
+
+    pub fn end_drag(&mut self) {
+        self.x.end_drag();
+        self.y.end_drag();
+        self.source = "friction";
+        if !self.config.inertia {
+            // 没有惯性时停在松手位置（超出范围时仍然回弹）
+            let (px, py) = self.position();
+            if (0.0..=self.x.get_max_scroll()).contains(&px) { self.x.scroll_to(px, false); }
+            if (0.0..=self.y.get_max_scroll()).contains(&py) { self.y.scroll_to(py, false); }
+        }
+    }
+
+    /// 推进惯性/回弹/属性动画，返回是否还在动画中
+    pub fn update(&mut self, dt: f32) -> bool {
+        let x = self.x.update(dt);
+        let y = self.y.update(dt);
+        x || y
+    }
+
+    /// 位置相对上次上报发生变化时，返回 (x, y, source)
+    pub fn take_change(&mut self) -> Option<(f32, f32, &'static str)> {
+        let (x, y) = self.position();
+        if (x - self.last_reported.0).abs() < 0.01 && (y - self.last_reported.1).abs() < 0.01 {
+            return None;
+        }
+        self.last_reported = (x, y);
+        let out_of_range = x < 0.0 || y < 0.0 || x > self.x.get_max_scroll() || y > self.y.get_max_scroll();
+        let source = if out_of_range && self.source != "touch" { "out-of-bounds" } else { self.source };
+        Some((x, y, source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(direction: MovableDirection) -> MovableConfig {
+        MovableConfig {
+            direction,
+            range_x: 200.0,
+            range_y: 100.0,
+            x: 0.0,
+            y: 0.0,
+            inertia: false,
+            out_of_bounds: false,
+            animation: false,
+        }
+    }
+
+    #[test]
+    fn test_drag_is_clamped_to_area() {
+        let mut view = MovableView::new(config(MovableDirection::All));
+        view.begin_drag(10.0, 10.0, 0);
+        view.update_drag(60.0, 40.0, 16);
+        assert_eq!(view.position(), (50.0, 30.0));
+        assert_eq!(view.take_change(), Some((50.0, 30.0, "touch")));
+
+        view.update_drag(500.0, -100.0, 32);
+        assert_eq!(view.position(), (200.0, 0.0));
+        view.end_drag();
+        assert!(!view.update(0.016));
+        assert_eq!(view.position(), (200.0, 0.0));
+    }
+
+    #[test]
+    fn test_direction_restricts_axis() {
+        let mut view = MovableView::new(config(MovableDirection::Horizontal));
+        view.begin_drag(0.0, 0.0, 0);
+        view.update_drag(30.0, 30.0, 16);
+        assert_eq!(view.position(), (30.0, 0.0));
+    }
+
+    #[test]
+    fn test_inertia_continues_after_release() {
+        let mut view = MovableView::new(MovableConfig { inertia: true, ..config(MovableDirection::All) });
+        view.begin_drag(0.0, 0.0, 0);
+        view.update_drag(20.0, 0.0, 16);
+        view.update_drag(40.0, 0.0, 32);
+        view.end_drag();
+        view.take_change();
+        assert!(view.update(0.016));
+        let (x, _) = view.position();
+        assert!(x > 40.0, "x = {}", x);
+        assert_eq!(view.take_change().map(|c| c.2), Some("friction"));
+    }
+
+    #[test]
+    fn test_attr_change_moves_view() {
+        let mut view = MovableView::new(MovableConfig { x: 20.0, ..config(MovableDirection::All) });
+        assert_eq!(view.position(), (20.0, 0.0));
+        view.update_config(MovableConfig { x: 80.0, y: 500.0, ..config(MovableDirection::All) });
+        assert_eq!(view.position(), (80.0, 100.0));
+        assert_eq!(view.take_change(), Some((80.0, 100.0, "")));
+    }
+}
@@ -91,35 +91,49 @@ impl ScrollViewCache {
         dest_x: f32,
         dest_y: f32,
         scale_factor: f32,
+    ) {
+        self.blit_to_offset(target, 0.0, scroll_offset, dest_x, dest_y, scale_factor);
+    }
+    
+    /// 从缓存复制可见区域到目标 Canvas，支持横向滚动偏移
+    pub fn blit_to_offset(
+        &self,
+        target: &mut Canvas,
+        scroll_x: f32,
+        scroll_y: f32,
+        dest_x: f32,
+        dest_y: f32,
+        scale_factor: f32,
     ) {
         let src_pixels = self.canvas.pixels();
         let src_width = self.canvas.width();
         let src_height = self.canvas.height();
         
         // 计算源区域（物理像素）
-        let src_y_start = (scroll_offset * scale_factor).max(0.0) as u32;
+        let src_y_start = (scroll_y * scale_factor).max(0.0) as u32;
         let visible_height = (self.viewport_height as f32 * scale_factor) as u32;
         let src_y_end = (src_y_start + visible_height).min(src_height);
+        let src_x_start = (scroll_x * scale_factor).max(0.0) as u32;
+        // 视口宽度按逻辑像素取整存储，多复制一个逻辑像素，超出部分由目标 Canvas 的裁剪区域处理
+        let visible_width = ((self.viewport_width + 1) as f32 * scale_factor) as u32;
+        let src_x_end = (src_x_start + visible_width).min(src_width);
         
         // 目标位置
         let dest_x = dest_x as i32;
         let dest_y = dest_y as i32;
         
         // 复制像素
+        if src_x_start >= src_x_end {
+            return;
+        }
         for src_y in src_y_start..src_y_end {
             let dst_y = dest_y + (src_y - src_y_start) as i32;
-            
-            for src_x in 0..src_width {
-                let dst_x = dest_x + src_x as i32;
-                let src_idx = (src_y * src_width + src_x) as usize;
-                
-                if src_idx < src_pixels.len() {
-                    let color = src_pixels[src_idx];
-                    if color.a > 0 {
-                        target.set_pixel(dst_x, dst_y, color);
-                    }
-                }
-            }
+            let row_start = (src_y * src_width) as usize;
+            target.blend_row(
+                dst_y,
+                dest_x,
+                &src_pixels[row_start + src_x_start as usize..row_start + src_x_end as usize],
+            );
         }
     }
 }
@@ -8,12 +8,25 @@ pub const LOGICAL_HEIGHT: u32 = 667;
 pub enum ScrollEvent {
     /// 滚动到底部（回弹结束后触发）
     ReachBottom,
-    /// 滚动到顶部/下拉刷新（回弹结束后触发）
+    /// 滚动到顶部（回弹结束后触发）
     ReachTop,
+    /// 下拉超过阈值后松手，触发下拉刷新
+    PullDownRefresh,
+}
+
+/// 滚动方向
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScrollAxis {
+    #[default]
+    Vertical,
+    /// scroll-view 的 scroll-x
+    Horizontal,
 }
 
 /// 微信小程序风格滚动控制器
 pub struct ScrollController {
+    /// 滚动方向，控制器本身只处理一维位置，由调用方按方向传入 x 或 y 坐标
+    axis: ScrollAxis,
     position: f32,
     velocity: f32,
     min_scroll: f32,
@@ -39,11 +52,24 @@ pub struct ScrollController {
     reach_bottom_distance: f32,
     /// 是否已经触发过触底事件（防止重复触发）
     reach_bottom_triggered: bool,
+    
+    // 下拉刷新相关
+    /// 下拉刷新触发阈值（逻辑像素）
+    pull_refresh_threshold: f32,
+    /// 顶部最大下拉距离
+    max_pull_distance: f32,
+    /// 松手时已超过阈值，等待发出下拉刷新事件
+    pull_refresh_pending: bool,
+    /// 拖动时是否允许超出边界（橡皮筋效果）
+    overscroll: bool,
+    /// 是否开启下拉刷新，关闭时顶部不能下拉，也不发出下拉刷新事件
+    pull_down_refresh: bool,
 }
 
 impl ScrollController {
     pub fn new(content_height: f32, viewport_height: f32) -> Self {
         Self {
+            axis: ScrollAxis::Vertical,
             position: 0.0,
             velocity: 0.0,
             min_scroll: 0.0,
@@ -62,9 +88,32 @@ impl ScrollController {
             was_over_top: false,
             reach_bottom_distance: 50.0,
             reach_bottom_triggered: false,
+            pull_refresh_threshold: 50.0,
+            max_pull_distance: 100.0,
+            pull_refresh_pending: false,
+            overscroll: true,
+            pull_down_refresh: true,
         }
     }
     
+    /// 创建横向滚动控制器（内容宽度、视口宽度）
+    pub fn new_horizontal(content_width: f32, viewport_width: f32) -> Self {
+        Self { axis: ScrollAxis::Horizontal, ..Self::new(content_width, viewport_width) }
+    }
+    
+    /// 设置拖动时是否允许超出边界，关闭后拖动位置被严格限制在可滚动范围内
+    pub fn set_overscroll(&mut self, enabled: bool) {
+        self.overscroll = enabled;
+    }
+    
+    /// 设置是否开启下拉刷新（页面配置 enablePullDownRefresh）
+    pub fn set_pull_down_refresh(&mut self, enabled: bool) {
+        self.pull_down_refresh = enabled;
+    }
+    
+    pub fn axis(&self) -> ScrollAxis { self.axis }
+    pub fn is_horizontal(&self) -> bool { self.axis == ScrollAxis::Horizontal }
+    
     /// 更新内容高度（当实际内容高度变化时调用）
     pub fn update_content_height(&mut self, content_height: f32, viewport_height: f32) {
         if (content_height - self.last_content_height).abs() > 1.0 || (self.max_scroll - (content_height - viewport_height).max(0.0)).abs() > 1.0 {
@@ -97,9 +146,13 @@ impl ScrollController {
         if !self.is_dragging { return; }
         let delta = self.drag_start_pos - y;
         let mut new_pos = self.drag_start_scroll + delta;
-        if new_pos < self.min_scroll {
+        if !self.overscroll {
+            new_pos = new_pos.clamp(self.min_scroll, self.max_scroll);
+        } else if new_pos < self.min_scroll && !self.pull_down_refresh {
+            new_pos = self.min_scroll;
+        } else if new_pos < self.min_scroll {
             let overshoot = self.min_scroll - new_pos;
-            new_pos = self.min_scroll - Self::rubber_band(overshoot, LOGICAL_HEIGHT as f32);
+            new_pos = self.min_scroll - Self::rubber_band(overshoot, LOGICAL_HEIGHT as f32).min(self.max_pull_distance);
             // 记录超出顶部
             self.was_over_top = true;
         } else if new_pos > self.max_scroll {
@@ -118,6 +171,9 @@ impl ScrollController {
         if !self.is_dragging { return false; }
         self.is_dragging = false;
         self.velocity = self.calculate_release_velocity();
+        if self.pull_distance() >= self.pull_refresh_threshold {
+            self.pull_refresh_pending = true;
+        }
         if self.position < self.min_scroll || self.position > self.max_scroll {
             self.start_bounce();
         } else if self.velocity.abs() > 50.0 {
@@ -190,8 +246,27 @@ impl ScrollController {
     
     /// 更新滚动状态并检查事件，返回 (是否还在动画中, 可能的事件)
     pub fn update_with_events(&mut self, dt: f32) -> (bool, Option<ScrollEvent>) {
+        self.rearm_reach_bottom();
+        let (animating, event) = self.step_with_events(dt);
+        if event.is_some() || self.is_dragging {
+            return (animating, event);
+        }
+        // 进入触底距离（滚轮、惯性途中等）即触发，每次接近底部只触发一次
+        if self.check_reach_bottom() {
+            return (animating, Some(ScrollEvent::ReachBottom));
+        }
+        (animating, None)
+    }
+    
+    fn step_with_events(&mut self, dt: f32) -> (bool, Option<ScrollEvent>) {
         if self.is_dragging { return (false, None); }
         
+        // 松手时超过下拉阈值，先发出刷新事件，回弹在后续帧继续
+        if self.pull_refresh_pending {
+            self.pull_refresh_pending = false;
+            return (self.is_bouncing, Some(ScrollEvent::PullDownRefresh));
+        }
+        
         if self.is_bouncing {
             self.bounce_timer += dt;
             let duration = 0.3;
@@ -273,6 +348,26 @@ impl ScrollController {
         None
     }
     
+    /// 滚动到指定位置（限制在可滚动范围内），animated 时使用与回弹相同的缓动过渡
+    pub fn scroll_to(&mut self, offset: f32, animated: bool) {
+        let target = offset.clamp(self.min_scroll, self.max_scroll);
+        self.is_dragging = false;
+        self.is_decelerating = false;
+        self.velocity = 0.0;
+        self.was_over_top = false;
+        self.was_over_bottom = false;
+        
+        if animated && (target - self.position).abs() > 0.5 {
+            self.is_bouncing = true;
+            self.bounce_timer = 0.0;
+            self.bounce_start_pos = self.position;
+            self.bounce_target_pos = target;
+        } else {
+            self.is_bouncing = false;
+            self.position = target;
+        }
+    }
+    
     pub fn handle_scroll(&mut self, delta: f32, is_precise: bool) {
         // 忽略极微小的滚动事件
         if delta.abs() < 0.1 {
@@ -329,10 +424,34 @@ impl ScrollController {
         self.reach_bottom_triggered = false;
     }
     
+    /// 离开触底区域后重新允许触发
+    fn rearm_reach_bottom(&mut self) {
+        if self.reach_bottom_triggered && self.position < self.max_scroll - self.reach_bottom_distance {
+            self.reach_bottom_triggered = false;
+        }
+    }
+    
+    /// 设置触底距离（页面配置 onReachBottomDistance）
+    pub fn set_reach_bottom_distance(&mut self, distance: f32) {
+        self.reach_bottom_distance = distance.max(0.0);
+    }
+    
+    pub fn reach_bottom_distance(&self) -> f32 { self.reach_bottom_distance }
+    
     pub fn get_position(&self) -> f32 { self.position }
     pub fn get_max_scroll(&self) -> f32 { self.max_scroll }
     pub fn is_animating(&self) -> bool { self.is_decelerating || self.is_bouncing }
     
+    /// 当前顶部下拉距离（未下拉时为 0）
+    pub fn pull_distance(&self) -> f32 {
+        (self.min_scroll - self.position).max(0.0)
+    }
+    
+    /// 下拉距离是否已达到刷新阈值（用于提示“松开刷新”）
+    pub fn is_pull_threshold_reached(&self) -> bool {
+        self.pull_distance() >= self.pull_refresh_threshold
+    }
+    
     /// 是否在顶部
     pub fn is_at_top(&self) -> bool {
         self.position <= self.min_scroll + 1.0
@@ -343,3 +462,102 @@ impl ScrollController {
         self.position >= self.max_scroll - 1.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pull_distance_is_bounded() {
+        let mut sc = ScrollController::new(2000.0, 600.0);
+        sc.begin_drag(0.0, 0);
+        sc.update_drag(2000.0, 100);
+        assert!(sc.pull_distance() > 0.0);
+        assert!(sc.pull_distance() <= 100.0);
+    }
+
+    #[test]
+    fn test_pull_down_refresh_event() {
+        let mut sc = ScrollController::new(2000.0, 600.0);
+        sc.begin_drag(0.0, 0);
+        sc.update_drag(300.0, 500);
+        assert!(sc.is_pull_threshold_reached());
+        assert!(sc.end_drag());
+
+        let (_, event) = sc.update_with_events(0.016);
+        assert_eq!(event, Some(ScrollEvent::PullDownRefresh));
+
+        // 松手后回弹到 0
+        for _ in 0..60 {
+            sc.update_with_events(0.016);
+        }
+        assert_eq!(sc.get_position(), 0.0);
+        assert_eq!(sc.pull_distance(), 0.0);
+    }
+
+    #[test]
+    fn test_reach_bottom_fires_once_per_approach() {
+        let mut sc = ScrollController::new(2000.0, 600.0);
+        sc.set_reach_bottom_distance(100.0);
+
+        // 距离底部 150，未进入触底区域
+        sc.handle_scroll(1250.0, true);
+        assert_eq!(sc.update_with_events(0.016).1, None);
+
+        // 进入触底区域，只触发一次
+        sc.handle_scroll(80.0, true);
+        assert_eq!(sc.update_with_events(0.016).1, Some(ScrollEvent::ReachBottom));
+        sc.handle_scroll(20.0, true);
+        assert_eq!(sc.update_with_events(0.016).1, None);
+
+        // 离开后再次接近，重新触发
+        sc.handle_scroll(-300.0, true);
+        assert_eq!(sc.update_with_events(0.016).1, None);
+        sc.handle_scroll(300.0, true);
+        assert_eq!(sc.update_with_events(0.016).1, Some(ScrollEvent::ReachBottom));
+    }
+
+    #[test]
+    fn test_small_pull_does_not_refresh() {
+        let mut sc = ScrollController::new(2000.0, 600.0);
+        sc.begin_drag(0.0, 0);
+        sc.update_drag(20.0, 500);
+        assert!(!sc.is_pull_threshold_reached());
+        sc.end_drag();
+
+        for _ in 0..60 {
+            let (_, event) = sc.update_with_events(0.016);
+            assert_ne!(event, Some(ScrollEvent::PullDownRefresh));
+        }
+    }
+
+    #[test]
+    fn test_pull_down_refresh_disabled() {
+        let mut sc = ScrollController::new(2000.0, 600.0);
+        sc.set_pull_down_refresh(false);
+        sc.begin_drag(0.0, 0);
+        sc.update_drag(300.0, 500);
+        assert_eq!(sc.pull_distance(), 0.0);
+        sc.end_drag();
+
+        for _ in 0..60 {
+            let (_, event) = sc.update_with_events(0.016);
+            assert_ne!(event, Some(ScrollEvent::PullDownRefresh));
+        }
+    }
+
+    #[test]
+    fn test_scroll_to_clamps_and_animates() {
+        let mut sc = ScrollController::new(2000.0, 600.0);
+        sc.scroll_to(5000.0, false);
+        assert_eq!(sc.get_position(), 1400.0);
+
+        sc.scroll_to(400.0, true);
+        assert_eq!(sc.get_position(), 1400.0);
+        assert!(sc.update(0.1));
+        let mid = sc.get_position();
+        assert!(mid > 400.0 && mid < 1400.0, "mid = {}", mid);
+        while sc.update(0.1) {}
+        assert_eq!(sc.get_position(), 400.0);
+    }
+}
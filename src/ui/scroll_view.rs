@@ -1,6 +1,6 @@
 //! ScrollView 组件 - 可滚动容器
 
-use crate::{Canvas, Color, Paint, PaintStyle, Point, Rect};
+use crate::{Canvas, Color, Matrix, Paint, PaintStyle, Point, Rect};
 use crate::event::Event;
 use super::component::{Component, ComponentId, Style};
 use super::scroll_controller::ScrollController;
@@ -120,8 +120,8 @@ impl Component for ScrollView {
             canvas.draw_rect(&bounds, &paint);
         }
         
-        // 设置裁剪区域
-        canvas.clip_rect(bounds);
+        // 设置裁剪区域（裁剪使用设备坐标）
+        canvas.clip_rect(canvas.map_rect(&bounds));
         
         let scroll_y = self.controller.get_position();
         
@@ -140,7 +140,7 @@ impl Component for ScrollView {
                     // 注意：这里简化处理，实际应该用变换矩阵
                     // 保存画布状态
                     canvas.save();
-                    canvas.translate(-self.scroll_x, -scroll_y);
+                    canvas.concat(&Matrix::translate(-self.scroll_x, -scroll_y));
                     child.render(canvas);
                     canvas.restore();
                 }